@@ -177,6 +177,11 @@ impl Aggregation {
         } else {
             None
         };
+        // a `$text` search should be the first stage
+        let full_text_search = Input::full_text_search(r#where);
+        if let Some((_, term)) = full_text_search {
+            retval.push(doc!{"$match": {"$text": {"$search": term}}});
+        }
         // build `$lookup`s for relation where
         if let Some(r#where) = r#where {
             let lookups_for_relation_where = Self::build_lookups_for_relation_where(model, graph, r#where)?;
@@ -219,6 +224,8 @@ impl Aggregation {
                 if !sort.is_empty() {
                     retval.push(doc!{"$sort": sort});
                 }
+            } else if full_text_search.is_some() {
+                retval.push(doc!{"$sort": {"_score": {"$meta": "textScore"}}});
             } else if let Some(take) = take {
                 if take.as_i64().unwrap() < 0 {
                     let sort = Self::build_order_by(model, &Self::default_desc_order(model), false)?;
@@ -367,6 +374,15 @@ impl Aggregation {
                 _ => {
                     if let Some(field) = model.field(key) {
                         let column_name = field.column_name();
+                        if field.field_type().is_json() {
+                            let (path, item) = Self::build_json_where_item(column_name, value);
+                            retval.insert(path, item);
+                            continue
+                        }
+                        // searches are matched with `$text` in their own stage
+                        if let Some(map) = value.as_hashmap() {
+                            if map.contains_key("search") && !map.keys().any(|k| k != "search" && k != "mode") { continue }
+                        }
                         retval.insert(column_name, Self::build_where_item(model, graph, field.field_type(), field.is_optional(), value)?);
                     } else if let Some(relation) = model.relation(key) {
                         let relation_model = graph.model(relation.model()).unwrap();
@@ -393,7 +409,7 @@ impl Aggregation {
 
     fn build_where_item(_model: &Model, _graph: &Graph, _type: &FieldType, _optional: bool, value: &Value) -> Result<Bson> {
         if let Some(map) = value.as_hashmap() {
-            Ok(Bson::Document(map.iter().filter(|(k, _)| k.as_str() != "mode").map(|(k, v)| {
+            Ok(Bson::Document(map.iter().filter(|(k, _)| k.as_str() != "mode" && k.as_str() != "search").map(|(k, v)| {
                 let k = k.as_str();
                 match k {
                     "startsWith" => {
@@ -439,6 +455,37 @@ impl Aggregation {
         }
     }
 
+    /// Json filters match the embedded document at a dotted path.
+    fn build_json_where_item(column_name: &str, value: &Value) -> (String, Document) {
+        let map = value.as_hashmap().unwrap();
+        let mut path = column_name.to_owned();
+        if let Some(components) = map.get("path") {
+            for component in components.as_vec().unwrap() {
+                path.push('.');
+                path += &match component {
+                    Value::String(s) => s.clone(),
+                    _ => component.as_i64().unwrap().to_string(),
+                };
+            }
+        }
+        let mut item = doc!{};
+        for (key, value) in map {
+            match key.as_str() {
+                "path" => (),
+                "equals" => { item.insert("$eq", Bson::from(value)); }
+                "arrayContains" => { item.insert("$all", vec![Bson::from(value)]); }
+                "stringContains" => {
+                    item.insert("$regex", Bson::RegularExpression(BsonRegex {
+                        pattern: regex::escape(value.as_str().unwrap()),
+                        options: "".to_string(),
+                    }));
+                }
+                _ => panic!("Unhandled key."),
+            }
+        }
+        (path, item)
+    }
+
     fn build_where_key(key: &str) -> Bson {
         Bson::String(match key {
             "equals" => "$eq",
@@ -104,7 +104,21 @@ impl BsonCoder {
                     None => Err(Error::record_decoding_error(model.name(), path, "document")),
                 }
             }
+            FieldType::Json => Ok(Self::decode_json(bson_value)),
             FieldType::Object(_) => panic!("Saving embedded object into database is not implemented yet.")
         }
     }
+
+    fn decode_json(bson_value: &Bson) -> Value {
+        match bson_value {
+            Bson::Document(doc) => Value::IndexMap(doc.iter().map(|(k, v)| (k.to_owned(), Self::decode_json(v))).collect()),
+            Bson::Array(arr) => Value::Vec(arr.iter().map(Self::decode_json).collect()),
+            Bson::String(s) => Value::String(s.to_owned()),
+            Bson::Boolean(b) => Value::Bool(*b),
+            Bson::Int32(n) => Value::I64(*n as i64),
+            Bson::Int64(n) => Value::I64(*n),
+            Bson::Double(n) => Value::F64(*n),
+            _ => Value::Null,
+        }
+    }
 }
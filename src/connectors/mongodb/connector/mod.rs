@@ -4,26 +4,30 @@ use std::fmt::{Debug};
 use std::ops::Neg;
 use std::sync::Arc;
 use std::sync::atomic::{Ordering};
+use std::time::{Duration, Instant};
 use async_trait::async_trait;
 use bson::{Bson, doc, Document};
 use futures_util::StreamExt;
 use key_path::path;
 use mongodb::{options::ClientOptions, Client, Database, Collection, IndexModel};
 use mongodb::error::{ErrorKind, WriteFailure, Error as MongoDBError};
-use mongodb::options::{FindOneAndUpdateOptions, IndexOptions, ReturnDocument};
+use mongodb::options::{AggregateOptions, FindOneAndUpdateOptions, IndexOptions, ReturnDocument};
 use regex::Regex;
+use serde_json::json;
 use crate::connectors::mongodb::aggregation::Aggregation;
 use crate::connectors::mongodb::bson::coder::BsonCoder;
 use crate::connectors::mongodb::connector::save_session::MongoDBSaveSession;
 use crate::core::action::{Action, FIND, MANY, NESTED, SINGLE};
 use crate::core::action::source::ActionSource;
-use crate::core::connector::Connector;
+use crate::core::connector::{Connector, ConnectorHealth, MigrationPlan};
 use crate::core::object::Object;
 use crate::core::field::Sort;
 use crate::core::graph::Graph;
 use crate::core::model::{Model};
 use crate::core::model::index::{ModelIndex, ModelIndexType};
 use crate::core::connector::SaveSession;
+use crate::core::database::log::{self as query_log, QueryLog};
+use crate::core::database::pool::PoolConf;
 use crate::core::database::r#type::DatabaseType;
 use crate::core::teon::Value;
 use crate::core::error::Error;
@@ -37,14 +41,28 @@ pub struct MongoDBConnector {
     loaded: bool,
     client: Client,
     database: Database,
+    statement_timeout: Option<Duration>,
 }
 
 impl MongoDBConnector {
-    pub(crate) async fn new(url: String) -> MongoDBConnector {
-        let options = match ClientOptions::parse(url).await {
+    pub(crate) async fn new(url: String, pool: PoolConf) -> MongoDBConnector {
+        let mut options = match ClientOptions::parse(url).await {
             Ok(options) => options,
             Err(_) => panic!("MongoDB url is invalid.")
         };
+        if let Some(size) = pool.size {
+            options.max_pool_size = Some(size);
+        }
+        if let Some(connect_timeout) = pool.connect_timeout {
+            options.connect_timeout = Some(connect_timeout);
+        }
+        // the driver waits for a usable server rather than for a pooled connection
+        if let Some(acquire_timeout) = pool.acquire_timeout {
+            options.server_selection_timeout = Some(acquire_timeout);
+        }
+        if let Some(idle_lifetime) = pool.idle_lifetime {
+            options.max_idle_time = Some(idle_lifetime);
+        }
         let database_name = match &options.default_database {
             Some(database_name) => database_name,
             None => panic!("No database name found in MongoDB url.")
@@ -62,6 +80,22 @@ impl MongoDBConnector {
             loaded: false,
             client,
             database,
+            statement_timeout: pool.statement_timeout,
+        }
+    }
+
+    /// Errors which mean the database can't be used right now, rather than that an operation
+    /// is wrong.
+    fn availability_error(err: &MongoDBError) -> Option<Error> {
+        match err.kind.as_ref() {
+            ErrorKind::Command(command_error) if command_error.code == 50 => Some(Error::database_timeout()),
+            ErrorKind::Io(io_error) if io_error.kind() == std::io::ErrorKind::TimedOut => Some(Error::database_timeout()),
+            ErrorKind::Io(_) |
+            ErrorKind::ServerSelection { .. } |
+            ErrorKind::ConnectionPoolCleared { .. } |
+            ErrorKind::Authentication { .. } |
+            ErrorKind::DnsResolve { .. } => Some(Error::database_unavailable(err.to_string())),
+            _ => None,
         }
     }
 
@@ -69,6 +103,33 @@ impl MongoDBConnector {
         self.database.collection(name)
     }
 
+    /// Run an aggregation pipeline on the collection of `model` and collect the returned documents.
+    async fn aggregate_documents(&self, model: &Model, pipeline: Vec<Document>) -> std::result::Result<Vec<std::result::Result<Document, MongoDBError>>, MongoDBError> {
+        let col = self.get_collection(model.name());
+        let statement = if query_log::enabled() {
+            Some(json!({"aggregate": model.name(), "pipeline": Bson::from(pipeline.clone()).into_relaxed_extjson()}))
+        } else {
+            None
+        };
+        let start = Instant::now();
+        let options = AggregateOptions::builder().max_time(self.statement_timeout).build();
+        let result = match col.aggregate(pipeline, options).await {
+            Ok(cur) => Ok(cur.collect().await),
+            Err(err) => Err(err),
+        };
+        if let Some(statement) = statement {
+            QueryLog {
+                database: "mongo",
+                statement,
+                params: None,
+                duration: start.elapsed(),
+                rows: result.as_ref().ok().map(|results: &Vec<_>| results.len() as u64),
+                error: result.as_ref().err().map(|err| err.to_string()),
+            }.emit();
+        }
+        result
+    }
+
     fn document_to_object(&self, document: &Document, object: &Object, select: Option<&Value>, include: Option<&Value>) -> Result<()> {
         for key in document.keys() {
             let object_field = object.model().fields().iter().find(|f| f.column_name() == key);
@@ -127,8 +188,11 @@ impl MongoDBConnector {
         Ok(())
     }
 
-    fn _handle_write_error(&self, error_kind: &ErrorKind, object: &Object) -> Error {
-        return match error_kind {
+    fn _handle_write_error(&self, error: &MongoDBError, object: &Object) -> Error {
+        if let Some(error) = Self::availability_error(error) {
+            return error;
+        }
+        match error.kind.as_ref() {
             ErrorKind::Write(write) => {
                 match write {
                     WriteFailure::WriteError(write_error) => {
@@ -157,14 +221,14 @@ impl MongoDBConnector {
 
     async fn aggregate_or_group_by(&self, graph: &Graph, model: &Model, finder: &Value) -> Result<Vec<Value>> {
         let aggregate_input = Aggregation::build_for_aggregate(model, graph, finder)?;
-        let col = self.get_collection(model.name());
-        let cur = col.aggregate(aggregate_input, None).await;
-        if cur.is_err() {
-            println!("{:?}", cur);
-            return Err(Error::unknown_database_find_error());
-        }
-        let cur = cur.unwrap();
-        let results: Vec<std::result::Result<Document, MongoDBError>> = cur.collect().await;
+        let results = self.aggregate_documents(model, aggregate_input).await;
+        let results = match results {
+            Ok(results) => results,
+            Err(err) => {
+                println!("{:?}", err);
+                return Err(Self::availability_error(&err).unwrap_or_else(Error::unknown_database_find_error));
+            }
+        };
         let mut final_retval: Vec<Value> = vec![];
         for result in results.iter() {
             // there are records
@@ -238,7 +302,7 @@ impl MongoDBConnector {
                 }
             }
             Err(error) => {
-                return Err(self._handle_write_error(&error.kind, object));
+                return Err(self._handle_write_error(&error, object));
             }
         }
         Ok(())
@@ -255,6 +319,7 @@ impl MongoDBConnector {
         let mut inc = doc!{};
         let mut mul = doc!{};
         let mut push = doc!{};
+        let mut merged = false;
         for key in keys {
             if let Some(field) = model.field(key) {
                 let column_name = field.column_name();
@@ -266,6 +331,13 @@ impl MongoDBConnector {
                         "multiply" => mul.insert(column_name, Bson::from(val)),
                         "divide" => mul.insert(column_name, Bson::Double(val.recip())),
                         "push" => push.insert(column_name, Bson::from(val)),
+                        "merge" => {
+                            for (k, v) in val.as_indexmap().unwrap() {
+                                set.insert(format!("{column_name}.{k}"), Bson::from(v));
+                            }
+                            merged = true;
+                            None
+                        }
                         _ => panic!("Unhandled key."),
                     };
                 } else {
@@ -286,7 +358,7 @@ impl MongoDBConnector {
             }
         }
         let mut update_doc = doc!{};
-        let mut return_new = false;
+        let mut return_new = merged;
         if !set.is_empty() {
             update_doc.insert("$set", set);
         }
@@ -308,18 +380,25 @@ impl MongoDBConnector {
         if update_doc.is_empty() {
             return Ok(());
         }
+        let mut filter = identifier.clone();
+        let version = model.version_field().zip(object.expected_version());
+        if let Some((field, expected)) = &version {
+            filter.insert(field.column_name(), BsonCoder::encode(field.field_type(), expected.clone())?);
+        }
         if !return_new {
-            let result = col.update_one(identifier.clone(), update_doc, None).await;
+            let result = col.update_one(filter, update_doc, None).await;
             return match result {
+                Ok(result) if result.matched_count == 0 && version.is_some() => Err(Error::version_conflict(version.unwrap().0.name())),
                 Ok(_) => Ok(()),
                 Err(error) => {
-                    Err(self._handle_write_error(&error.kind, object))
+                    Err(self._handle_write_error(&error, object))
                 }
             }
         } else {
             let options = FindOneAndUpdateOptions::builder().return_document(ReturnDocument::After).build();
-            let result = col.find_one_and_update(identifier.clone(), update_doc, options).await;
+            let result = col.find_one_and_update(filter, update_doc, options).await;
             match result {
+                Ok(None) if version.is_some() => return Err(Error::version_conflict(version.unwrap().0.name())),
                 Ok(updated_document) => {
                     for key in object.inner.atomic_updator_map.lock().unwrap().keys() {
                         let bson_new_val = updated_document.as_ref().unwrap().get(key).unwrap();
@@ -329,7 +408,7 @@ impl MongoDBConnector {
                     }
                 }
                 Err(error) => {
-                    return Err(self._handle_write_error(&error.kind, object));
+                    return Err(self._handle_write_error(&error, object));
                 }
             }
         }
@@ -356,11 +435,12 @@ impl Connector for MongoDBConnector {
             FieldType::Vec(_) => panic!(""),
             FieldType::HashMap(_) => panic!(""),
             FieldType::BTreeMap(_) => panic!(""),
+            FieldType::Json => DatabaseType::Json,
             FieldType::Object(_) => panic!(""),
         }
     }
 
-    async fn migrate(&mut self, models: &Vec<Model>, reset_database: bool) -> Result<()> {
+    async fn migrate(&self, models: &[Model], reset_database: bool) -> Result<()> {
         if reset_database {
             let _ = self.database.drop(None).await;
         }
@@ -368,6 +448,14 @@ impl Connector for MongoDBConnector {
             let name = model.name();
             let collection = self.get_collection(name);
             let mut reviewed_names: Vec<String> = Vec::new();
+            let full_text_indices: Vec<(String, Document)> = model.full_text_indices().iter().map(|fields| {
+                let columns: Vec<&str> = fields.iter().map(|f| model.field(f).unwrap().column_name()).collect();
+                let mut keys = doc!{};
+                for column in columns.iter() {
+                    keys.insert(*column, "text");
+                }
+                (format!("{}_text", columns.join("_")), keys)
+            }).collect();
             let cursor_result = collection.list_indexes(None).await;
             if cursor_result.is_ok() {
                 let mut cursor = cursor_result.unwrap();
@@ -376,6 +464,10 @@ impl Connector for MongoDBConnector {
                         continue
                     }
                     let name = (&index).options.as_ref().unwrap().name.as_ref().unwrap();
+                    if full_text_indices.iter().any(|(n, _)| n == name) {
+                        reviewed_names.push(name.clone());
+                        continue
+                    }
                     let result = model.indices().iter().find(|i| &i.mongodb_name() == name);
                     if result.is_none() {
                         // not in our model definition, but in the database
@@ -435,10 +527,36 @@ impl Connector for MongoDBConnector {
                     }
                 }
             }
+            for (name, keys) in full_text_indices {
+                if reviewed_names.contains(&name) { continue }
+                let index_options = IndexOptions::builder().name(name).build();
+                let index_model = IndexModel::builder().keys(keys).options(index_options).build();
+                let result = collection.create_index(index_model, None).await;
+                if result.is_err() {
+                    println!("index create error: {:?}", result.err().unwrap());
+                }
+            }
         }
         Ok(())
     }
 
+    async fn migration_plan(&self, _models: &[Model]) -> Result<MigrationPlan> {
+        // MongoDB is schemaless, indices are synced by `migrate`
+        Ok(MigrationPlan::default())
+    }
+
+    async fn applied_migrations(&self) -> Result<Vec<String>> {
+        Err(Error::internal_server_error("MongoDB doesn't support migration files."))
+    }
+
+    async fn apply_migration(&self, _version: &str, _name: &str, _up: &str) -> Result<()> {
+        Err(Error::internal_server_error("MongoDB doesn't support migration files."))
+    }
+
+    async fn revert_migration(&self, _version: &str, _down: &str) -> Result<()> {
+        Err(Error::internal_server_error("MongoDB doesn't support migration files."))
+    }
+
     async fn query_raw(&self, _query: &Value) -> Result<Value> {
         unreachable!()
         // let collection = self.collections.get(table.unwrap()).unwrap();
@@ -467,8 +585,8 @@ impl Connector for MongoDBConnector {
         let result = col.delete_one(document_identifier.clone(), None).await;
         return match result {
             Ok(_result) => Ok(()),
-            Err(_err) => {
-                Err(Error::unknown_database_delete_error())
+            Err(err) => {
+                Err(Self::availability_error(&err).unwrap_or_else(Error::unknown_database_delete_error))
             }
         }
     }
@@ -478,13 +596,13 @@ impl Connector for MongoDBConnector {
         let include = finder.get("include");
 
         let aggregate_input = Aggregation::build(model, graph, finder)?;
-        let col = self.get_collection(model.name());
-        let cur = col.aggregate(aggregate_input, None).await;
-        if cur.is_err() {
-            return Err(Error::unknown_database_find_unique_error());
-        }
-        let cur = cur.unwrap();
-        let results: Vec<std::result::Result<Document, MongoDBError>> = cur.collect().await;
+        let results = self.aggregate_documents(model, aggregate_input).await;
+        let results = match results {
+            Ok(results) => results,
+            Err(err) => {
+                return Err(Self::availability_error(&err).unwrap_or_else(Error::unknown_database_find_unique_error));
+            }
+        };
         if results.is_empty() {
             return Err(Error::object_not_found());
         }
@@ -501,16 +619,15 @@ impl Connector for MongoDBConnector {
         let include = finder.get("include");
         let aggregate_input = Aggregation::build(model, graph, finder)?;
         let reverse = Input::has_negative_take(finder);
-        let col = self.get_collection(model.name());
-        // println!("see aggregate input: {:?}", aggregate_input);
-        let cur = col.aggregate(aggregate_input, None).await;
-        if cur.is_err() {
-            println!("{:?}", cur);
-            return Err(Error::unknown_database_find_error());
-        }
-        let cur = cur.unwrap();
+        let results = self.aggregate_documents(model, aggregate_input).await;
+        let results = match results {
+            Ok(results) => results,
+            Err(err) => {
+                println!("{:?}", err);
+                return Err(Self::availability_error(&err).unwrap_or_else(Error::unknown_database_find_error));
+            }
+        };
         let mut result: Vec<Object> = vec![];
-        let results: Vec<std::result::Result<Document, MongoDBError>> = cur.collect().await;
         for doc in results {
             let obj = graph.new_object(model.name(), action, action_source.clone())?;
             match self.document_to_object(&doc.unwrap(), &obj, select, include) {
@@ -531,14 +648,14 @@ impl Connector for MongoDBConnector {
 
     async fn count(&self, graph: &Graph, model: &Model, finder: &Value) -> Result<usize> {
         let input = Aggregation::build_for_count(model, graph, finder)?;
-        let col = self.get_collection(model.name());
-        let cur = col.aggregate(input, None).await;
-        if cur.is_err() {
-            println!("{:?}", cur);
-            return Err(Error::unknown_database_find_error());
-        }
-        let cur = cur.unwrap();
-        let results: Vec<std::result::Result<Document, MongoDBError>> = cur.collect().await;
+        let results = self.aggregate_documents(model, input).await;
+        let results = match results {
+            Ok(results) => results,
+            Err(err) => {
+                println!("{:?}", err);
+                return Err(Self::availability_error(&err).unwrap_or_else(Error::unknown_database_find_error));
+            }
+        };
         if results.is_empty() {
             Ok(0)
         } else {
@@ -577,6 +694,17 @@ impl Connector for MongoDBConnector {
     fn new_save_session(&self) -> Arc<dyn SaveSession> {
         Arc::new(MongoDBSaveSession {})
     }
+
+    async fn health(&self) -> ConnectorHealth {
+        let start = Instant::now();
+        let result = self.database.run_command(doc! {"ping": 1}, None).await;
+        ConnectorHealth {
+            reachable: result.is_ok(),
+            latency: Some(start.elapsed()),
+            error: result.err().map(|err| err.to_string()),
+            pool: None,
+        }
+    }
 }
 
 unsafe impl Sync for MongoDBConnector {}
@@ -1,10 +1,26 @@
+use std::any::Any;
 use std::fmt::{Debug};
+use async_trait::async_trait;
 use crate::core::connector::SaveSession;
+use crate::core::result::Result;
 
-
+/// MongoDB writes are not grouped into a transaction since multi-document transactions require
+/// a replica set deployment. Committing and aborting are no-ops.
 #[derive(Debug)]
 pub struct MongoDBSaveSession { }
 
+#[async_trait]
 impl SaveSession for MongoDBSaveSession {
 
+    async fn commit(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn abort(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
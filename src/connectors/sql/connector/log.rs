@@ -0,0 +1,141 @@
+use std::sync::Arc;
+use std::time::Instant;
+use async_trait::async_trait;
+use quaint_forked::ast::{Query, Value};
+use quaint_forked::connector::{IsolationLevel, OwnedTransaction, ResultSet, Transaction};
+use quaint_forked::prelude::Queryable;
+use serde_json::{json, Value as JsonValue};
+use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::core::database::log::{self, QueryLog};
+
+/// A connection which logs the statements executed through it.
+pub(crate) struct LoggedQueryable {
+    inner: Arc<dyn Queryable>,
+    dialect: SQLDialect,
+}
+
+impl LoggedQueryable {
+
+    /// Wrap `inner` if query logging is configured, otherwise return it as is.
+    pub(crate) fn wrap(inner: Arc<dyn Queryable>, dialect: SQLDialect) -> Arc<dyn Queryable> {
+        if log::enabled() {
+            Arc::new(Self { inner, dialect })
+        } else {
+            inner
+        }
+    }
+
+    fn database(&self) -> &'static str {
+        match self.dialect {
+            SQLDialect::MySQL => "mysql",
+            SQLDialect::PostgreSQL => "postgres",
+            SQLDialect::SQLite => "sqlite",
+            SQLDialect::MSSQL => "mssql",
+        }
+    }
+
+    fn log<T>(&self, sql: &str, params: Option<&[Value<'_>]>, start: Instant, result: &quaint_forked::Result<T>, rows: impl FnOnce(&T) -> Option<u64>) {
+        let (rows, error) = match result {
+            Ok(value) => (rows(value), None),
+            Err(err) => (None, Some(err.to_string())),
+        };
+        QueryLog {
+            database: self.database(),
+            statement: json!(sql),
+            params: params.map(|params| JsonValue::Array(params.iter().map(|p| JsonValue::from(p.clone())).collect())),
+            duration: start.elapsed(),
+            rows,
+            error,
+        }.emit();
+    }
+
+    fn statement(q: &Query<'_>) -> String {
+        match q {
+            Query::Raw(sql) => sql.to_string(),
+            _ => format!("{:?}", q),
+        }
+    }
+}
+
+#[async_trait]
+impl Queryable for LoggedQueryable {
+
+    async fn query(&self, q: Query<'_>) -> quaint_forked::Result<ResultSet> {
+        let statement = Self::statement(&q);
+        let start = Instant::now();
+        let result = self.inner.query(q).await;
+        self.log(&statement, None, start, &result, |set| Some(set.len() as u64));
+        result
+    }
+
+    async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> quaint_forked::Result<ResultSet> {
+        let start = Instant::now();
+        let result = self.inner.query_raw(sql, params).await;
+        self.log(sql, Some(params), start, &result, |set| Some(set.len() as u64));
+        result
+    }
+
+    async fn query_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> quaint_forked::Result<ResultSet> {
+        let start = Instant::now();
+        let result = self.inner.query_raw_typed(sql, params).await;
+        self.log(sql, Some(params), start, &result, |set| Some(set.len() as u64));
+        result
+    }
+
+    async fn execute(&self, q: Query<'_>) -> quaint_forked::Result<u64> {
+        let statement = Self::statement(&q);
+        let start = Instant::now();
+        let result = self.inner.execute(q).await;
+        self.log(&statement, None, start, &result, |count| Some(*count));
+        result
+    }
+
+    async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> quaint_forked::Result<u64> {
+        let start = Instant::now();
+        let result = self.inner.execute_raw(sql, params).await;
+        self.log(sql, Some(params), start, &result, |count| Some(*count));
+        result
+    }
+
+    async fn execute_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> quaint_forked::Result<u64> {
+        let start = Instant::now();
+        let result = self.inner.execute_raw_typed(sql, params).await;
+        self.log(sql, Some(params), start, &result, |count| Some(*count));
+        result
+    }
+
+    async fn raw_cmd(&self, cmd: &str) -> quaint_forked::Result<()> {
+        let start = Instant::now();
+        let result = self.inner.raw_cmd(cmd).await;
+        self.log(cmd, None, start, &result, |_| None);
+        result
+    }
+
+    async fn version(&self) -> quaint_forked::Result<Option<String>> {
+        self.inner.version().await
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.inner.is_healthy()
+    }
+
+    async fn server_reset_query(&self, tx: &Transaction<'_>) -> quaint_forked::Result<()> {
+        self.inner.server_reset_query(tx).await
+    }
+
+    async fn server_reset_query_owned(&self, tx: &OwnedTransaction) -> quaint_forked::Result<()> {
+        self.inner.server_reset_query_owned(tx).await
+    }
+
+    fn begin_statement(&self) -> &'static str {
+        self.inner.begin_statement()
+    }
+
+    async fn set_tx_isolation_level(&self, isolation_level: IsolationLevel) -> quaint_forked::Result<()> {
+        self.inner.set_tx_isolation_level(isolation_level).await
+    }
+
+    fn requires_isolation_first(&self) -> bool {
+        self.inner.requires_isolation_first()
+    }
+}
@@ -1,26 +1,33 @@
 pub mod save_session;
+pub(crate) mod replica;
+pub(crate) mod log;
+pub(crate) mod pool;
 
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
 use async_trait::async_trait;
-use quaint_forked::{prelude::*, pooled::Quaint, ast::Query as QuaintQuery};
+use quaint_forked::{prelude::*, ast::Query as QuaintQuery};
 use quaint_forked::error::DatabaseConstraint;
 use quaint_forked::error::ErrorKind::UniqueConstraintViolation;
 use crate::core::model::Model;
 use crate::connectors::sql::schema::r#type::field::ToDatabaseType;
+use crate::connectors::sql::connector::pool::{availability_error, SQLPool};
+use crate::connectors::sql::connector::replica::SQLReplicaSet;
 use crate::connectors::sql::connector::save_session::SQLSaveSession;
 use crate::connectors::sql::execution::Execution;
+use crate::connectors::sql::migration::history::SQLMigrationHistory;
 use crate::connectors::sql::migration::migrate::SQLMigration;
 use crate::connectors::sql::query::Query;
 use crate::connectors::sql::stmts::SQL;
 use crate::connectors::sql::schema::dialect::SQLDialect;
 use crate::connectors::sql::schema::value::decode::RowDecoder;
-use crate::connectors::sql::schema::value::encode::ToSQLString;
-use crate::connectors::sql::schema::value::encode::PSQLArrayToSQLString;
-use crate::connectors::sql::url::url_utils;
+use crate::connectors::sql::schema::value::encode::{SQLEscape, ToSQLParam, ToSQLString};
+use crate::connectors::sql::stmts::params::SQLParams;
 use crate::core::action::Action;
 use crate::core::action::source::ActionSource;
-use crate::core::connector::{Connector, SaveSession};
+use crate::core::connector::{Connector, ConnectorHealth, MigrationPlan, PoolStats, SaveSession};
+use crate::core::database::consistency;
+use crate::core::database::pool::PoolConf;
 use crate::core::database::r#type::DatabaseType;
 use crate::core::error::Error;
 use crate::core::field::r#type::{FieldType, FieldTypeOwner};
@@ -31,40 +38,78 @@ use crate::teon;
 
 pub(crate) struct SQLConnector {
     dialect: SQLDialect,
-    pool: Quaint,
+    pool: SQLPool,
+    pool_conf: PoolConf,
+    replicas: SQLReplicaSet,
+    read_your_writes: bool,
 }
 
 impl SQLConnector {
 
-    pub(crate) async fn new(dialect: SQLDialect, url: &str, reset: bool) -> Self {
+    pub(crate) async fn new(dialect: SQLDialect, url: &str, pool_conf: PoolConf, reset: bool) -> Self {
         SQLMigration::create_database_if_needed(dialect, url, reset).await;
-        let url = url_utils::normalized_url(dialect, url);
-        let pool = Quaint::builder(url.as_str()).unwrap().build();
-        Self { dialect, pool }
+        let pool = SQLPool::new(dialect, url, &pool_conf);
+        let replicas = SQLReplicaSet::new(dialect, &[], &pool_conf);
+        Self { dialect, pool, pool_conf, replicas, read_your_writes: false }
     }
 
-    async fn create_object(&self, object: &Object) -> Result<()> {
-        let conn = self.pool.check_out().await.unwrap();
+    /// Send reads to the databases at `urls`. With `read_your_writes`, a request reads from the
+    /// primary database after it has written anything.
+    pub(crate) fn with_replicas(mut self, urls: &[String], read_your_writes: bool) -> Self {
+        self.replicas = SQLReplicaSet::new(self.dialect, urls, &self.pool_conf);
+        self.read_your_writes = read_your_writes;
+        self
+    }
+
+    /// Returns the transaction of the save session if it's a SQL one, otherwise a connection
+    /// checked out from the pool.
+    async fn queryable(&self, session: Option<Arc<dyn SaveSession>>) -> Result<Arc<dyn Queryable>> {
+        if let Some(session) = session {
+            if let Some(session) = session.as_any().downcast_ref::<SQLSaveSession>() {
+                let transaction: Arc<dyn Queryable> = session.transaction().await?;
+                return Ok(transaction);
+            }
+        }
+        self.pool.check_out().await
+    }
+
+    /// Returns a connection for reading. Reads go to a replica unless they are inside a
+    /// transaction, are made for updating, or follow a write of the request with
+    /// `readYourWrites`. The primary database is used if no replica is healthy.
+    async fn read_queryable(&self, session: Option<Arc<dyn SaveSession>>, mutation_mode: bool) -> Result<Arc<dyn Queryable>> {
+        let pinned = mutation_mode || (self.read_your_writes && consistency::has_written());
+        if session.is_none() && !pinned && !self.replicas.is_empty() {
+            if let Some(conn) = self.replicas.check_out().await {
+                return Ok(conn);
+            }
+        }
+        self.queryable(session).await
+    }
+
+    async fn create_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()> {
+        let conn = self.queryable(Some(session)).await?;
         let model = object.model();
         let keys = object.keys_for_save();
         let auto_keys = model.auto_keys();
+        let mut params = SQLParams::new();
         let mut values: Vec<(&str, String)> = vec![];
         for key in keys {
             if let Some(field) = model.field(key) {
                 let column_name = field.column_name();
                 let val = object.get_value(key).unwrap();
                 if !(field.auto_increment && val.is_null()) {
-                    values.push((column_name, PSQLArrayToSQLString::to_string_with_ft(&val, self.dialect, field.field_type())));
+                    values.push((column_name, params.push(val.to_sql_param(field.field_type(), self.dialect))));
                 }
             } else if let Some(property) = model.property(key) {
                 let val: Value = object.get_property(key).await.unwrap();
-                values.push((key, PSQLArrayToSQLString::to_string_with_ft(&val, self.dialect, property.field_type())));
+                values.push((key, params.push(val.to_sql_param(property.field_type(), self.dialect))));
             }
         }
         let value_refs: Vec<(&str, &str)> = values.iter().map(|(k, v)| (*k, v.as_str())).collect();
         let stmt = SQL::insert_into(model.table_name()).values(value_refs).returning(auto_keys).to_string(self.dialect);
-        if self.dialect == SQLDialect::PostgreSQL {
-            match conn.query(QuaintQuery::from(stmt)).await {
+        let (stmt, params) = params.finish(&stmt, self.dialect);
+        if self.dialect == SQLDialect::PostgreSQL || self.dialect == SQLDialect::MSSQL {
+            match conn.query_raw(&stmt, &params).await {
                 Ok(result_set) => {
                     let columns = result_set.columns().clone();
                     let result = result_set.into_iter().next();
@@ -82,7 +127,7 @@ impl SQLConnector {
                 }
             }
         } else {
-            match conn.query(QuaintQuery::from(stmt)).await {
+            match conn.query_raw(&stmt, &params).await {
                 Ok(result) => {
                     let id = result.last_insert_id().unwrap();
                     for key in auto_keys {
@@ -102,10 +147,11 @@ impl SQLConnector {
         }
     }
 
-    async fn update_object(&self, object: &Object) -> Result<()> {
-        let conn = self.pool.check_out().await.unwrap();
+    async fn update_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()> {
+        let conn = self.queryable(Some(session)).await?;
         let model = object.model();
         let keys = object.keys_for_save();
+        let mut params = SQLParams::new();
         let mut values: Vec<(&str, String)> = vec![];
         for key in &keys {
             if let Some(field) = model.field(key) {
@@ -113,34 +159,46 @@ impl SQLConnector {
                 if let Some(updator) = object.get_atomic_updator(key) {
                     let (key, val) = Input::key_value(updator.as_hashmap().unwrap());
                     match key {
-                        "increment" => values.push((column_name, format!("{} + {}", column_name, val.to_string(self.dialect)))),
-                        "decrement" => values.push((column_name, format!("{} - {}", column_name, val.to_string(self.dialect)))),
-                        "multiply" => values.push((column_name, format!("{} * {}", column_name, val.to_string(self.dialect)))),
-                        "divide" => values.push((column_name, format!("{} / {}", column_name, val.to_string(self.dialect)))),
-                        "push" => values.push((column_name, format!("ARRAY_APPEND({}, {})", column_name, val.to_string(self.dialect)))),
+                        "increment" => values.push((column_name, format!("{} + {}", column_name, params.push(val.to_sql_param(field.field_type(), self.dialect))))),
+                        "decrement" => values.push((column_name, format!("{} - {}", column_name, params.push(val.to_sql_param(field.field_type(), self.dialect))))),
+                        "multiply" => values.push((column_name, format!("{} * {}", column_name, params.push(val.to_sql_param(field.field_type(), self.dialect))))),
+                        "divide" => values.push((column_name, format!("{} / {}", column_name, params.push(val.to_sql_param(field.field_type(), self.dialect))))),
+                        "push" => {
+                            let element_type = field.field_type().element_field().unwrap().field_type();
+                            values.push((column_name, format!("ARRAY_APPEND({}, {})", column_name, params.push(val.to_sql_param(element_type, self.dialect)))))
+                        },
+                        "merge" => values.push((column_name, Query::json_merge(column_name, val, self.dialect, &mut params))),
                         _ => panic!("Unhandled key."),
                     }
                 } else {
                     let val = object.get_value(key).unwrap();
-                    values.push((column_name, PSQLArrayToSQLString::to_string_with_ft(&val, self.dialect, field.field_type())));
+                    values.push((column_name, params.push(val.to_sql_param(field.field_type(), self.dialect))));
                 }
             } else if let Some(property) = model.property(key) {
                 let val: Value = object.get_property(key).await.unwrap();
-                values.push((key, PSQLArrayToSQLString::to_string_with_ft(&val, self.dialect, property.field_type())));
+                values.push((key, params.push(val.to_sql_param(property.field_type(), self.dialect))));
             }
         }
         let value_refs: Vec<(&str, &str)> = values.iter().map(|(k, v)| (*k, v.as_str())).collect();
         let identifier = object.identifier();
-        let r#where = Query::where_from_identifier(object, self.dialect);
+        let mut r#where = Query::where_from_identifier(object, self.dialect, &mut params);
+        let version = model.version_field().zip(object.expected_version());
+        if let Some((field, expected)) = &version {
+            r#where = format!("{} AND {} = {}", r#where, field.column_name().escape(self.dialect), params.push(expected.to_sql_param(field.field_type(), self.dialect)));
+        }
         if !value_refs.is_empty() {
             let stmt = SQL::update(model.table_name()).values(value_refs).r#where(&r#where).to_string(self.dialect);
-            let result = conn.execute(QuaintQuery::from(stmt)).await;
-            if result.is_err() {
-                println!("{:?}", result.err().unwrap());
-                return Err(Error::unknown_database_write_error());
+            let (stmt, params) = params.finish(&stmt, self.dialect);
+            match conn.execute_raw(&stmt, &params).await {
+                Ok(0) if version.is_some() => return Err(Error::version_conflict(version.unwrap().0.name())),
+                Ok(_) => (),
+                Err(err) => {
+                    println!("{:?}", err);
+                    return Err(availability_error(&err).unwrap_or_else(Error::unknown_database_write_error));
+                }
             }
         }
-        let result = Execution::query(&self.pool, model, object.graph(), &teon!({"where": identifier, "take": 1}), self.dialect).await?;
+        let result = Execution::query(conn.as_ref(), model, object.graph(), &teon!({"where": identifier, "take": 1}), self.dialect).await?;
         if result.is_empty() {
             Err(Error::object_not_found())
         } else {
@@ -150,6 +208,9 @@ impl SQLConnector {
     }
 
     fn handle_err_result(&self, err: quaint_forked::error::Error) -> Error {
+        if let Some(error) = availability_error(&err) {
+            return error;
+        }
         match err.kind() {
             UniqueConstraintViolation { constraint } => {
                 match constraint {
@@ -178,16 +239,35 @@ impl Connector for SQLConnector {
         field_type.to_database_type(self.dialect)
     }
 
-    async fn migrate(&mut self, models: &Vec<Model>, _reset_database: bool) -> Result<()> {
-        SQLMigration::migrate(self.dialect, &self.pool, models).await;
-        Ok(())
+    async fn migrate(&self, models: &[Model], _reset_database: bool) -> Result<()> {
+        SQLMigration::migrate(self.dialect, self.pool.quaint(), models).await
+    }
+
+    async fn migration_plan(&self, models: &[Model]) -> Result<MigrationPlan> {
+        SQLMigration::plan(self.dialect, self.pool.quaint(), models).await
+    }
+
+    async fn applied_migrations(&self) -> Result<Vec<String>> {
+        SQLMigrationHistory::applied_versions(self.dialect, &self.pool).await
+    }
+
+    async fn apply_migration(&self, version: &str, name: &str, up: &str) -> Result<()> {
+        SQLMigrationHistory::apply(self.dialect, &self.pool, version, name, up).await
+    }
+
+    async fn revert_migration(&self, version: &str, down: &str) -> Result<()> {
+        SQLMigrationHistory::revert(self.dialect, &self.pool, version, down).await
     }
 
     async fn query_raw(&self, query: &Value) -> Result<Value> {
-        let conn = self.pool.check_out().await.unwrap();
+        consistency::mark_written();
+        let conn = self.queryable(None).await?;
         let result = conn.query(QuaintQuery::from(query.as_str().unwrap())).await;
         if result.is_err() {
             let err = result.unwrap_err();
+            if let Some(error) = availability_error(&err) {
+                return Err(error);
+            }
             let msg = err.original_message();
             return Err(Error::internal_server_error(msg.unwrap()));
         } else {
@@ -200,34 +280,39 @@ impl Connector for SQLConnector {
         }
     }
 
-    async fn save_object(&self, object: &Object, _session: Arc<dyn SaveSession>) -> Result<()> {
+    async fn save_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()> {
+        consistency::mark_written();
         let is_new = object.inner.is_new.load(Ordering::SeqCst);
         if is_new {
-            self.create_object(object).await
+            self.create_object(object, session).await
         } else {
-            self.update_object(object).await
+            self.update_object(object, session).await
         }
     }
 
-    async fn delete_object(&self, object: &Object, _session: Arc<dyn SaveSession>) -> Result<()> {
-        let conn = self.pool.check_out().await.unwrap();
+    async fn delete_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()> {
+        consistency::mark_written();
+        let conn = self.queryable(Some(session)).await?;
         if object.inner.is_new.load(Ordering::SeqCst) {
             return Err(Error::object_is_not_saved_thus_cant_be_deleted());
         }
         let model = object.model();
-        let r#where = Query::where_from_identifier(object, self.dialect);
+        let mut params = SQLParams::new();
+        let r#where = Query::where_from_identifier(object, self.dialect, &mut params);
         let stmt = SQL::delete_from(model.table_name()).r#where(r#where).to_string(self.dialect);
-        let result = conn.execute(QuaintQuery::from(stmt)).await;
-        if result.is_err() {
-            println!("{:?}", result.err().unwrap());
-            return Err(Error::unknown_database_write_error());
+        let (stmt, params) = params.finish(&stmt, self.dialect);
+        let result = conn.execute_raw(&stmt, &params).await;
+        if let Err(err) = result {
+            println!("{:?}", err);
+            return Err(availability_error(&err).unwrap_or_else(Error::unknown_database_write_error));
         } else {
             Ok(())
         }
     }
 
-    async fn find_unique(&self, graph: &Graph, model: &Model, finder: &Value, _mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Object> {
-        let objects = Execution::query_objects(&self.pool, model, graph, finder, self.dialect, action, action_source.clone()).await?;
+    async fn find_unique(&self, graph: &Graph, model: &Model, finder: &Value, mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Object> {
+        let conn = self.read_queryable(graph.bound_save_session(model), mutation_mode).await?;
+        let objects = Execution::query_objects(conn.as_ref(), model, graph, finder, self.dialect, action, action_source.clone()).await?;
         if objects.is_empty() {
             Err(Error::object_not_found())
         } else {
@@ -235,26 +320,38 @@ impl Connector for SQLConnector {
         }
     }
 
-    async fn find_many(&self, graph: &Graph, model: &Model, finder: &Value, _mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Vec<Object>> {
-        Execution::query_objects(&self.pool, model, graph, finder, self.dialect, action, action_source).await
+    async fn find_many(&self, graph: &Graph, model: &Model, finder: &Value, mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Vec<Object>> {
+        let conn = self.read_queryable(graph.bound_save_session(model), mutation_mode).await?;
+        Execution::query_objects(conn.as_ref(), model, graph, finder, self.dialect, action, action_source).await
     }
 
     async fn count(&self, graph: &Graph, model: &Model, finder: &Value) -> Result<usize> {
-        match Execution::query_count(&self.pool, model, graph, finder, self.dialect).await {
+        let conn = self.read_queryable(graph.bound_save_session(model), false).await?;
+        match Execution::query_count(conn.as_ref(), model, graph, finder, self.dialect).await {
             Ok(c) => Ok(c as usize),
             Err(e) => Err(e),
         }
     }
 
     async fn aggregate(&self, graph: &Graph, model: &Model, finder: &Value) -> Result<Value> {
-        Execution::query_aggregate(&self.pool, model, graph, finder, self.dialect).await
+        let conn = self.read_queryable(graph.bound_save_session(model), false).await?;
+        Execution::query_aggregate(conn.as_ref(), model, graph, finder, self.dialect).await
     }
 
     async fn group_by(&self, graph: &Graph, model: &Model, finder: &Value) -> Result<Value> {
-        Execution::query_group_by(&self.pool, model, graph, finder, self.dialect).await
+        let conn = self.read_queryable(graph.bound_save_session(model), false).await?;
+        Execution::query_group_by(conn.as_ref(), model, graph, finder, self.dialect).await
     }
 
     fn new_save_session(&self) -> Arc<dyn SaveSession> {
-        Arc::new(SQLSaveSession { })
+        Arc::new(SQLSaveSession::new(self.pool.clone()))
+    }
+
+    async fn health(&self) -> ConnectorHealth {
+        self.pool.health().await
+    }
+
+    async fn pool_stats(&self) -> Option<PoolStats> {
+        Some(self.pool.stats().await)
     }
 }
@@ -0,0 +1,270 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use quaint_forked::ast::{Query, Value};
+use quaint_forked::connector::{IsolationLevel, OwnedTransaction, ResultSet, Transaction};
+use quaint_forked::error::{Error as QuaintError, ErrorKind};
+use quaint_forked::pooled::{PooledConnection, Quaint};
+use quaint_forked::prelude::Queryable;
+use crate::connectors::sql::connector::log::LoggedQueryable;
+use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::connectors::sql::url::url_utils;
+use crate::core::connector::{ConnectorHealth, PoolStats};
+use crate::core::database::pool::PoolConf;
+use crate::core::error::Error;
+use crate::core::result::Result;
+
+/// A connection pool which keeps count of its connections in use and of its checkout timeouts.
+#[derive(Clone)]
+pub(crate) struct SQLPool {
+    quaint: Quaint,
+    dialect: SQLDialect,
+    size: Option<u32>,
+    /// Run on every checked out connection, it sets the statement timeout on MySQL.
+    session_setup: Option<String>,
+    in_use: Arc<AtomicU32>,
+    timeouts: Arc<AtomicU64>,
+}
+
+impl SQLPool {
+
+    pub(crate) fn new(dialect: SQLDialect, url: &str, conf: &PoolConf) -> Self {
+        let url = Self::url_with_timeouts(dialect, url, conf);
+        let mut builder = Quaint::builder(url.as_str()).unwrap();
+        if let Some(size) = conf.size {
+            builder.connection_limit(size as usize);
+        }
+        if let Some(acquire_timeout) = conf.acquire_timeout {
+            builder.pool_timeout(acquire_timeout);
+        }
+        if let Some(idle_lifetime) = conf.idle_lifetime {
+            builder.max_idle_lifetime(idle_lifetime);
+        }
+        Self {
+            quaint: builder.build(),
+            dialect,
+            size: conf.size,
+            session_setup: Self::session_setup(dialect, conf),
+            in_use: Arc::new(AtomicU32::new(0)),
+            timeouts: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Connect timeouts are options of the database url. They are whole seconds, and SQLite
+    /// doesn't have them.
+    ///
+    /// Statement timeouts are enforced by the database server, so that it stops running a
+    /// statement once the client gives up on it. PostgreSQL sets `statement_timeout` with the
+    /// startup options of the url. SQL Server doesn't have a statement timeout of the server, a
+    /// socket timeout on the client is used instead.
+    fn url_with_timeouts(dialect: SQLDialect, url: &str, conf: &PoolConf) -> String {
+        let seconds = |duration: Duration| duration.as_millis().div_ceil(1000).max(1).to_string();
+        match dialect {
+            SQLDialect::MSSQL => {
+                let mut url = url.trim_end_matches(';').to_owned();
+                if let Some(connect_timeout) = conf.connect_timeout {
+                    url += &format!(";connectTimeout={}", seconds(connect_timeout));
+                }
+                if let Some(statement_timeout) = conf.statement_timeout {
+                    url += &format!(";socketTimeout={}", seconds(statement_timeout));
+                }
+                url
+            }
+            SQLDialect::SQLite => url_utils::normalized_url(dialect, url).to_string(),
+            _ => {
+                let mut url = url_utils::normalized_url(dialect, url);
+                if let Some(connect_timeout) = conf.connect_timeout {
+                    url.query_pairs_mut().append_pair("connect_timeout", &seconds(connect_timeout));
+                }
+                if let (SQLDialect::PostgreSQL, Some(statement_timeout)) = (dialect, conf.statement_timeout) {
+                    // the url takes one `options`, the timeout is added to the user's ones
+                    let mut options: Vec<String> = vec![];
+                    let pairs: Vec<(String, String)> = url.query_pairs().into_owned().filter(|(key, value)| {
+                        if key == "options" { options.push(value.clone()) }
+                        key != "options"
+                    }).collect();
+                    options.push(format!("-c statement_timeout={}", statement_timeout.as_millis()));
+                    url.query_pairs_mut().clear().extend_pairs(pairs).append_pair("options", &options.join(" "));
+                }
+                url.to_string()
+            }
+        }
+    }
+
+    /// MySQL doesn't take session variables in the url. `max_execution_time` only applies to
+    /// read only `SELECT` statements.
+    fn session_setup(dialect: SQLDialect, conf: &PoolConf) -> Option<String> {
+        match (dialect, conf.statement_timeout) {
+            (SQLDialect::MySQL, Some(statement_timeout)) => Some(format!("SET SESSION max_execution_time = {}", statement_timeout.as_millis())),
+            _ => None,
+        }
+    }
+
+    /// The underlying pool, for migrations.
+    pub(crate) fn quaint(&self) -> &Quaint {
+        &self.quaint
+    }
+
+    /// Check out a connection. Exhausting the pool and failing to reach the database are
+    /// reported with their own error types.
+    pub(crate) async fn check_out(&self) -> Result<Arc<dyn Queryable>> {
+        match self.quaint.check_out().await {
+            Ok(conn) => {
+                self.in_use.fetch_add(1, Ordering::SeqCst);
+                let conn = PooledQueryable { inner: conn, in_use: self.in_use.clone() };
+                if let Some(session_setup) = &self.session_setup {
+                    if let Err(err) = conn.raw_cmd(session_setup).await {
+                        return Err(availability_error(&err).unwrap_or_else(|| Error::internal_server_error(format!("Cannot set up database connection: {}", err))));
+                    }
+                }
+                Ok(LoggedQueryable::wrap(Arc::new(conn), self.dialect))
+            }
+            Err(err) => {
+                if let ErrorKind::PoolTimeout { .. } = err.kind() {
+                    self.timeouts.fetch_add(1, Ordering::SeqCst);
+                }
+                Err(availability_error(&err).unwrap_or_else(|| Error::internal_server_error(format!("Cannot check out database connection: {}", err))))
+            }
+        }
+    }
+
+    pub(crate) async fn stats(&self) -> PoolStats {
+        let size = match self.size {
+            Some(size) => size,
+            None => self.quaint.capacity().await,
+        };
+        PoolStats {
+            size,
+            in_use: self.in_use.load(Ordering::SeqCst),
+            timeouts: self.timeouts.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Run a trivial statement on a connection of this pool.
+    pub(crate) async fn health(&self) -> ConnectorHealth {
+        let start = Instant::now();
+        let result = match self.check_out().await {
+            Ok(conn) => conn.raw_cmd("SELECT 1").await.map_err(|err| err.to_string()),
+            Err(err) => Err(err.errors.and_then(|errors| errors.get("database").cloned()).unwrap_or(err.message)),
+        };
+        ConnectorHealth {
+            reachable: result.is_ok(),
+            latency: Some(start.elapsed()),
+            error: result.err(),
+            pool: Some(self.stats().await),
+        }
+    }
+}
+
+/// Errors which mean the database can't be used right now, rather than that a statement is
+/// wrong.
+pub(crate) fn availability_error(err: &QuaintError) -> Option<Error> {
+    // statements cancelled by the statement timeout of PostgreSQL and MySQL
+    if matches!(err.original_code(), Some("57014") | Some("3024")) {
+        return Some(Error::database_timeout());
+    }
+    match err.kind() {
+        ErrorKind::PoolTimeout { .. } => Some(Error::database_pool_exhausted()),
+        ErrorKind::SocketTimeout => Some(Error::database_timeout()),
+        ErrorKind::ConnectTimeout |
+        ErrorKind::ConnectionError(_) |
+        ErrorKind::ConnectionClosed |
+        ErrorKind::PoolClosed { .. } |
+        ErrorKind::IoError(_) |
+        ErrorKind::TlsError { .. } |
+        ErrorKind::AuthenticationFailed { .. } |
+        ErrorKind::DatabaseDoesNotExist { .. } |
+        ErrorKind::DatabaseAccessDenied { .. } => Some(Error::database_unavailable(err.to_string())),
+        _ => None,
+    }
+}
+
+/// A connection checked out from a `SQLPool`. It's counted as in use until it's dropped.
+struct PooledQueryable {
+    inner: PooledConnection,
+    in_use: Arc<AtomicU32>,
+}
+
+impl Drop for PooledQueryable {
+    fn drop(&mut self) {
+        self.in_use.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl Queryable for PooledQueryable {
+
+    async fn query(&self, q: Query<'_>) -> quaint_forked::Result<ResultSet> {
+        self.inner.query(q).await
+    }
+
+    async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> quaint_forked::Result<ResultSet> {
+        self.inner.query_raw(sql, params).await
+    }
+
+    async fn query_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> quaint_forked::Result<ResultSet> {
+        self.inner.query_raw_typed(sql, params).await
+    }
+
+    async fn execute(&self, q: Query<'_>) -> quaint_forked::Result<u64> {
+        self.inner.execute(q).await
+    }
+
+    async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> quaint_forked::Result<u64> {
+        self.inner.execute_raw(sql, params).await
+    }
+
+    async fn execute_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> quaint_forked::Result<u64> {
+        self.inner.execute_raw_typed(sql, params).await
+    }
+
+    async fn raw_cmd(&self, cmd: &str) -> quaint_forked::Result<()> {
+        self.inner.raw_cmd(cmd).await
+    }
+
+    async fn version(&self) -> quaint_forked::Result<Option<String>> {
+        self.inner.version().await
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.inner.is_healthy()
+    }
+
+    async fn server_reset_query(&self, tx: &Transaction<'_>) -> quaint_forked::Result<()> {
+        self.inner.server_reset_query(tx).await
+    }
+
+    async fn server_reset_query_owned(&self, tx: &OwnedTransaction) -> quaint_forked::Result<()> {
+        self.inner.server_reset_query_owned(tx).await
+    }
+
+    fn begin_statement(&self) -> &'static str {
+        self.inner.begin_statement()
+    }
+
+    async fn set_tx_isolation_level(&self, isolation_level: IsolationLevel) -> quaint_forked::Result<()> {
+        self.inner.set_tx_isolation_level(isolation_level).await
+    }
+
+    fn requires_isolation_first(&self) -> bool {
+        self.inner.requires_isolation_first()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statement_timeout_is_set_on_the_server() {
+        let conf = PoolConf { statement_timeout: Some(Duration::from_millis(1500)), ..Default::default() };
+        let url = SQLPool::url_with_timeouts(SQLDialect::PostgreSQL, "postgres://u:p@localhost/app?options=--search_path%3Dapp", &conf);
+        let url = url::Url::parse(&url).unwrap();
+        let options: Vec<String> = url.query_pairs().filter(|(key, _)| key == "options").map(|(_, value)| value.into_owned()).collect();
+        assert_eq!(options, vec!["--search_path=app -c statement_timeout=1500"]);
+        assert!(url.query_pairs().all(|(key, _)| key != "socket_timeout"));
+        assert_eq!(SQLPool::session_setup(SQLDialect::MySQL, &conf).unwrap(), "SET SESSION max_execution_time = 1500");
+        assert!(SQLPool::session_setup(SQLDialect::PostgreSQL, &conf).is_none());
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+use quaint_forked::prelude::Queryable;
+use crate::connectors::sql::connector::pool::SQLPool;
+use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::connectors::sql::url::url_utils;
+use crate::core::database::pool::PoolConf;
+
+/// How often unhealthy replicas are checked to be taken back.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+struct SQLReplica {
+    /// The host and the database, for logging.
+    name: String,
+    pool: SQLPool,
+    healthy: AtomicBool,
+}
+
+/// Read replicas of a SQL database. Reads are spread over the healthy replicas in turn.
+pub(crate) struct SQLReplicaSet {
+    replicas: Arc<Vec<SQLReplica>>,
+    next: AtomicUsize,
+}
+
+impl SQLReplicaSet {
+
+    pub(crate) fn new(dialect: SQLDialect, urls: &[String], conf: &PoolConf) -> Self {
+        let replicas: Vec<SQLReplica> = urls.iter().map(|url| {
+            let pool = SQLPool::new(dialect, url, conf);
+            SQLReplica { name: url_utils::redacted_url(url), pool, healthy: AtomicBool::new(true) }
+        }).collect();
+        let replicas = Arc::new(replicas);
+        if !replicas.is_empty() {
+            tokio::spawn(Self::check_health(replicas.clone()));
+        }
+        Self { replicas, next: AtomicUsize::new(0) }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.replicas.is_empty()
+    }
+
+    /// Check out a connection from the next healthy replica. A replica which fails is marked
+    /// unhealthy until the health check takes it back. Returns `None` if no replica is usable.
+    pub(crate) async fn check_out(&self) -> Option<Arc<dyn Queryable>> {
+        let count = self.replicas.len();
+        for _ in 0..count {
+            let replica = &self.replicas[self.next.fetch_add(1, Ordering::SeqCst) % count];
+            if !replica.healthy.load(Ordering::SeqCst) {
+                continue;
+            }
+            match replica.pool.check_out().await {
+                Ok(conn) => return Some(conn),
+                Err(err) => {
+                    eprintln!("Read replica {} is unavailable: {}", replica.name, err.message());
+                    replica.healthy.store(false, Ordering::SeqCst);
+                }
+            }
+        }
+        None
+    }
+
+    async fn check_health(replicas: Arc<Vec<SQLReplica>>) {
+        let mut interval = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            for replica in replicas.iter() {
+                let healthy = match replica.pool.check_out().await {
+                    Ok(conn) => conn.raw_cmd("SELECT 1").await.is_ok(),
+                    Err(_) => false,
+                };
+                replica.healthy.store(healthy, Ordering::SeqCst);
+            }
+        }
+    }
+}
@@ -1,9 +1,85 @@
-use std::fmt::Debug;
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use async_trait::async_trait;
+use quaint_forked::connector::{OwnedTransaction, start_owned_transaction};
+use tokio::sync::OnceCell;
+use crate::connectors::sql::connector::pool::{availability_error, SQLPool};
 use crate::core::connector::SaveSession;
+use crate::core::error::Error;
+use crate::core::result::Result;
 
-#[derive(Debug)]
-pub struct SQLSaveSession { }
+pub struct SQLSaveSession {
+    pool: SQLPool,
+    transaction: OnceCell<Arc<OwnedTransaction>>,
+    finished: AtomicBool,
+}
+
+impl SQLSaveSession {
+
+    pub(crate) fn new(pool: SQLPool) -> Self {
+        Self { pool, transaction: OnceCell::new(), finished: AtomicBool::new(false) }
+    }
+
+    /// The transaction of this session. A connection is checked out from the pool and the
+    /// transaction is started when this is first called.
+    pub(crate) async fn transaction(&self) -> Result<Arc<OwnedTransaction>> {
+        if self.finished.load(Ordering::SeqCst) {
+            return Err(Error::internal_server_error("Save session is already committed or aborted."));
+        }
+        let transaction = self.transaction.get_or_try_init(|| async {
+            let queryable = self.pool.check_out().await?;
+            match start_owned_transaction(queryable, None).await {
+                Ok(transaction) => Ok(Arc::new(transaction)),
+                Err(err) => Err(availability_error(&err).unwrap_or_else(|| Error::internal_server_error(format!("Cannot start transaction: {}", err)))),
+            }
+        }).await?;
+        Ok(transaction.clone())
+    }
+}
 
+impl Debug for SQLSaveSession {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SQLSaveSession")
+            .field("started", &self.transaction.initialized())
+            .field("finished", &self.finished.load(Ordering::SeqCst))
+            .finish()
+    }
+}
+
+#[async_trait]
 impl SaveSession for SQLSaveSession {
 
+    async fn commit(&self) -> Result<()> {
+        if self.finished.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+        if let Some(transaction) = self.transaction.get() {
+            if let Err(err) = transaction.commit().await {
+                // the connection goes back to the pool, it shouldn't be left inside the transaction
+                if let Err(rollback_err) = transaction.rollback().await {
+                    eprintln!("Failed to rollback transaction after a failed commit: {}", rollback_err);
+                }
+                return Err(availability_error(&err).unwrap_or_else(|| Error::internal_server_error(format!("Cannot commit transaction: {}", err))));
+            }
+        }
+        Ok(())
+    }
+
+    async fn abort(&self) -> Result<()> {
+        if self.finished.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+        if let Some(transaction) = self.transaction.get() {
+            if let Err(err) = transaction.rollback().await {
+                return Err(Error::internal_server_error(format!("Cannot rollback transaction: {}", err)));
+            }
+        }
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
@@ -2,13 +2,13 @@ use std::borrow::Cow;
 use array_tool::vec::Uniq;
 use std::collections::HashMap;
 use async_recursion::async_recursion;
-use quaint_forked::pooled::Quaint;
 use quaint_forked::prelude::{Queryable, ResultRow};
-use quaint_forked::ast::{Query as QuaintQuery};
+use crate::connectors::sql::connector::pool::availability_error;
 use crate::connectors::sql::query::Query;
 use crate::connectors::sql::schema::dialect::SQLDialect;
 use crate::connectors::sql::schema::value::decode::RowDecoder;
-use crate::connectors::sql::schema::value::encode::{SQLEscape, ToSQLString, ToWrapped};
+use crate::connectors::sql::schema::value::encode::{SQLEscape, ToSQLParam, ToWrapped};
+use crate::connectors::sql::stmts::params::SQLParams;
 use crate::core::action::Action;
 use crate::core::action::source::ActionSource;
 use crate::core::error::Error;
@@ -82,8 +82,8 @@ impl Execution {
         Value::HashMap(retval)
     }
 
-    pub(crate) async fn query_objects(pool: &Quaint, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect, action: Action, action_source: ActionSource) -> Result<Vec<Object>> {
-        let values = Self::query(pool, model, graph, finder, dialect).await?;
+    pub(crate) async fn query_objects(conn: &dyn Queryable, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect, action: Action, action_source: ActionSource) -> Result<Vec<Object>> {
+        let values = Self::query(conn, model, graph, finder, dialect).await?;
         let select = finder.as_hashmap().unwrap().get("select");
         let include = finder.as_hashmap().unwrap().get("include");
         let mut results = vec![];
@@ -96,8 +96,7 @@ impl Execution {
     }
 
     #[async_recursion]
-    async fn query_internal(pool: &Quaint, model: &Model, graph: &Graph, value: &Value, dialect: SQLDialect, additional_where: Option<String>, additional_left_join: Option<String>, join_table_results: Option<Vec<String>>, force_negative_take: bool, additional_distinct: Option<Vec<String>>) -> Result<Vec<Value>> {
-        let conn = pool.check_out().await.unwrap();
+    async fn query_internal(conn: &dyn Queryable, model: &Model, graph: &Graph, value: &Value, dialect: SQLDialect, additional_where: Option<String>, additional_left_join: Option<String>, join_table_results: Option<Vec<String>>, force_negative_take: bool, additional_distinct: Option<Vec<String>>, mut params: SQLParams) -> Result<Vec<Value>> {
         let _select = value.get("select");
         let include = value.get("include");
         let original_distinct = value.get("distinct").map(|v| if v.as_vec().unwrap().is_empty() { None } else { Some(v.as_vec().unwrap()) }).flatten();
@@ -110,14 +109,15 @@ impl Execution {
         } else {
             Cow::Borrowed(value)
         };
-        let stmt = Query::build(model, graph, value_for_build.as_ref(), dialect, additional_where, additional_left_join, join_table_results, force_negative_take);
+        let stmt = Query::build(model, graph, value_for_build.as_ref(), dialect, additional_where, additional_left_join, join_table_results, force_negative_take, &mut params);
+        let (stmt, values) = params.finish(&stmt, dialect);
         // println!("sql stmt: {}", &stmt);
         let reverse = Input::has_negative_take(value);
-        let rows = match conn.query(QuaintQuery::from(stmt)).await {
+        let rows = match conn.query_raw(&stmt, &values).await {
             Ok(rows) => rows,
             Err(err) => {
                 println!("{:?}", err);
-                return Err(Error::unknown_database_find_error());
+                return Err(availability_error(&err).unwrap_or_else(Error::unknown_database_find_error));
             }
         };
         if rows.is_empty() {
@@ -162,16 +162,21 @@ impl Execution {
                     } else {
                         opposite_fields.iter().map(|f| opposite_model.field(f).unwrap().column_name().escape(dialect)).collect::<Vec<String>>().join(",").to_wrapped()
                     };
+                    let mut nested_params = SQLParams::new();
                     let values = if opposite_fields.len() == 1 {
                         // in a (?,?,?,?,?) format
                         let field_name = fields.get(0).unwrap();
+                        let field_type = model.field(field_name).unwrap().field_type();
                         results.iter().map(|v| {
-                            v.as_hashmap().unwrap().get(field_name).unwrap().to_string(dialect)
+                            nested_params.push(v.as_hashmap().unwrap().get(field_name).unwrap().to_sql_param(field_type, dialect))
                         }).collect::<Vec<String>>().join(",").to_wrapped()
                     } else {
                         // in a (VALUES (?,?),(?,?)) format
                         format!("(VALUES {})", results.iter().map(|o| {
-                            fields.iter().map(|f| o.as_hashmap().unwrap().get(f).unwrap().to_string(dialect)).collect::<Vec<String>>().join(",").to_wrapped()
+                            fields.iter().map(|f| {
+                                let field_type = model.field(f).unwrap().field_type();
+                                nested_params.push(o.as_hashmap().unwrap().get(f).unwrap().to_sql_param(field_type, dialect))
+                            }).collect::<Vec<String>>().join(",").to_wrapped()
                         }).collect::<Vec<String>>().join(","))
                     };
                     let where_addition = Query::where_item(&names, "IN", &values);
@@ -180,7 +185,7 @@ impl Execution {
                     } else {
                         Cow::Owned(teon!({}))
                     };
-                    let included_values = Self::query_internal(pool, opposite_model, graph, &nested_query, dialect, Some(where_addition), None, None, negative_take, None).await?;
+                    let included_values = Self::query_internal(conn, opposite_model, graph, &nested_query, dialect, Some(where_addition), None, None, negative_take, None, nested_params).await?;
                     // println!("see included: {:?}", included_values);
                     for result in results.iter_mut() {
                         let mut skipped = 0;
@@ -237,14 +242,19 @@ impl Execution {
                     } else {
                         through_relation.fields().iter().map(|f| format!("j.{}", through_table.field(f).unwrap().column_name().escape(dialect))).collect::<Vec<String>>().join(",").to_wrapped()
                     };
+                    let mut nested_params = SQLParams::new();
                     let values = if through_relation.len() == 1 { // (?,?,?,?,?) format
                         let field_name = through_relation.references().get(0).unwrap();
+                        let field_type = model.field(field_name).unwrap().field_type();
                         results.iter().map(|v| {
-                            v.as_hashmap().unwrap().get(field_name).unwrap().to_string(dialect)
+                            nested_params.push(v.as_hashmap().unwrap().get(field_name).unwrap().to_sql_param(field_type, dialect))
                         }).collect::<Vec<String>>().join(",").to_wrapped()
                     } else { // (VALUES (?,?),(?,?)) format
                         let pairs = results.iter().map(|o| {
-                            through_relation.references().iter().map(|f| o.as_hashmap().unwrap().get(f).unwrap().to_string(dialect)).collect::<Vec<String>>().join(",").to_wrapped()
+                            through_relation.references().iter().map(|f| {
+                                let field_type = model.field(f).unwrap().field_type();
+                                nested_params.push(o.as_hashmap().unwrap().get(f).unwrap().to_sql_param(field_type, dialect))
+                            }).collect::<Vec<String>>().join(",").to_wrapped()
                         }).collect::<Vec<String>>().join(",");
                         format!("(VALUES {})", pairs)
                     };
@@ -256,7 +266,7 @@ impl Execution {
                     };
                     let join_table_results = through_relation.iter().map(|(f, r)| {
                         let through_column_name = through_model.field(f).unwrap().column_name().to_string();
-                        if dialect == SQLDialect::PostgreSQL {
+                        if dialect == SQLDialect::PostgreSQL || dialect == SQLDialect::MSSQL {
                             format!("j.{} AS \"{}.{}\"", through_column_name.as_str().escape(dialect), opposite_relation.unwrap().name(), r)
                         } else {
                             format!("j.{} AS `{}.{}`", through_column_name, opposite_relation.unwrap().name(), r)
@@ -269,7 +279,7 @@ impl Execution {
                     } else {
                         None
                     };
-                    let included_values = Self::query_internal(pool, opposite_model, graph, &nested_query, dialect, Some(where_addition), Some(left_join), Some(join_table_results), negative_take, additional_inner_distinct).await?;
+                    let included_values = Self::query_internal(conn, opposite_model, graph, &nested_query, dialect, Some(where_addition), Some(left_join), Some(join_table_results), negative_take, additional_inner_distinct, nested_params).await?;
                     // println!("see included {:?}", included_values);
                     for result in results.iter_mut() {
                         let mut skipped = 0;
@@ -313,14 +323,15 @@ impl Execution {
         Ok(results)
     }
 
-    pub(crate) async fn query(pool: &Quaint, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<Vec<Value>> {
-       Self::query_internal(pool, model, graph, finder, dialect, None, None, None, false, None).await
+    pub(crate) async fn query(conn: &dyn Queryable, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<Vec<Value>> {
+       Self::query_internal(conn, model, graph, finder, dialect, None, None, None, false, None, SQLParams::new()).await
     }
 
-    pub(crate) async fn query_aggregate(pool: &Quaint, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<Value> {
-        let conn = pool.check_out().await.unwrap();
-        let stmt = Query::build_for_aggregate(model, graph, finder, dialect);
-        match conn.query(QuaintQuery::from(&*stmt)).await {
+    pub(crate) async fn query_aggregate(conn: &dyn Queryable, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<Value> {
+        let mut params = SQLParams::new();
+        let stmt = Query::build_for_aggregate(model, graph, finder, dialect, &mut params);
+        let (stmt, values) = params.finish(&stmt, dialect);
+        match conn.query_raw(&stmt, &values).await {
             Ok(result_set) => {
                 let columns = result_set.columns().clone();
                 let result = result_set.into_iter().next().unwrap();
@@ -328,19 +339,20 @@ impl Execution {
             },
             Err(err) => {
                 println!("{:?}", err);
-                return Err(Error::unknown_database_find_error());
+                Err(availability_error(&err).unwrap_or_else(Error::unknown_database_find_error))
             }
         }
     }
 
-    pub(crate) async fn query_group_by(pool: &Quaint, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<Value> {
-        let conn = pool.check_out().await.unwrap();
-        let stmt = Query::build_for_group_by(model, graph, finder, dialect);
-        let rows = match conn.query(QuaintQuery::from(stmt)).await {
+    pub(crate) async fn query_group_by(conn: &dyn Queryable, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<Value> {
+        let mut params = SQLParams::new();
+        let stmt = Query::build_for_group_by(model, graph, finder, dialect, &mut params);
+        let (stmt, values) = params.finish(&stmt, dialect);
+        let rows = match conn.query_raw(&stmt, &values).await {
             Ok(rows) => rows,
             Err(err) => {
                 println!("{:?}", err);
-                return Err(Error::unknown_database_find_error());
+                return Err(availability_error(&err).unwrap_or_else(Error::unknown_database_find_error));
             }
         };
         let columns = rows.columns().clone();
@@ -349,10 +361,11 @@ impl Execution {
         }).collect::<Vec<Value>>()))
     }
 
-    pub(crate) async fn query_count(pool: &Quaint, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<u64> {
-        let conn = pool.check_out().await.unwrap();
-        let stmt = Query::build_for_count(model, graph, finder, dialect, None, None, None, false);
-        match conn.query(QuaintQuery::from(stmt)).await {
+    pub(crate) async fn query_count(conn: &dyn Queryable, model: &Model, graph: &Graph, finder: &Value, dialect: SQLDialect) -> Result<u64> {
+        let mut params = SQLParams::new();
+        let stmt = Query::build_for_count(model, graph, finder, dialect, None, None, None, false, &mut params);
+        let (stmt, values) = params.finish(&stmt, dialect);
+        match conn.query_raw(&stmt, &values).await {
             Ok(result) => {
                 let result = result.into_iter().next().unwrap();
                 let count: i64 = result.into_iter().next().unwrap().as_i64().unwrap();
@@ -360,7 +373,7 @@ impl Execution {
             },
             Err(err) => {
                 println!("{:?}", err);
-                return Err(Error::unknown_database_find_error());
+                Err(availability_error(&err).unwrap_or_else(Error::unknown_database_find_error))
             }
         }
     }
@@ -0,0 +1,117 @@
+use chrono::{SecondsFormat, Utc};
+use quaint_forked::prelude::Queryable;
+use quaint_forked::ast::Value as QuaintValue;
+use crate::connectors::sql::connector::pool::SQLPool;
+use crate::connectors::sql::connector::save_session::SQLSaveSession;
+use crate::connectors::sql::migration::migrate::SQLMigration;
+use crate::connectors::sql::schema::column::SQLColumn;
+use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::connectors::sql::schema::value::encode::{SQLEscape, ToSQLString};
+use crate::connectors::sql::stmts::params::SQLParams;
+use crate::connectors::sql::stmts::SQL;
+use crate::core::connector::SaveSession;
+use crate::core::database::r#type::DatabaseType;
+use crate::core::error::Error;
+use crate::core::result::Result;
+
+/// The table which records the versions of the applied migration files. Auto migration never
+/// touches this table.
+pub(crate) static MIGRATION_HISTORY_TABLE: &str = "_teo_migrations";
+
+pub(crate) struct SQLMigrationHistory { }
+
+impl SQLMigrationHistory {
+
+    async fn create_table_if_needed(dialect: SQLDialect, pool: &SQLPool) -> Result<()> {
+        let conn = pool.check_out().await?;
+        let db_tables = SQLMigration::get_db_user_tables(dialect, conn.as_ref()).await;
+        if db_tables.iter().any(|t| t == MIGRATION_HISTORY_TABLE) {
+            return Ok(());
+        }
+        let mut stmt = SQL::create().table(MIGRATION_HISTORY_TABLE);
+        stmt.columns(vec![
+            SQLColumn::new("version".to_owned(), DatabaseType::VarChar { m: 191, n: None, c: None }, true, false, None, true),
+            SQLColumn::new("name".to_owned(), DatabaseType::VarChar { m: 191, n: None, c: None }, true, false, None, false),
+            SQLColumn::new("appliedAt".to_owned(), DatabaseType::VarChar { m: 64, n: None, c: None }, true, false, None, false),
+        ]);
+        conn.raw_cmd(&stmt.to_string(dialect)).await.map_err(|e| Error::internal_server_error(e.to_string()))
+    }
+
+    pub(crate) async fn applied_versions(dialect: SQLDialect, pool: &SQLPool) -> Result<Vec<String>> {
+        Self::create_table_if_needed(dialect, pool).await?;
+        let conn = pool.check_out().await?;
+        let columns = vec!["version"];
+        let table = MIGRATION_HISTORY_TABLE.escape(dialect);
+        let mut stmt = SQL::select(Some(&columns), &table);
+        stmt.order_by("version ASC".to_owned());
+        let result = conn.query_raw(&stmt.to_string(dialect), &[]).await.map_err(|e| Error::internal_server_error(e.to_string()))?;
+        Ok(result.into_iter().map(|row| row.get("version").unwrap().to_string().unwrap()).collect())
+    }
+
+    /// Run the statements of a migration file and record its version in one transaction. MySQL
+    /// commits every DDL statement implicitly, so there a migration which fails halfway keeps
+    /// the statements before the failing one, and its version isn't recorded.
+    pub(crate) async fn apply(dialect: SQLDialect, pool: &SQLPool, version: &str, name: &str, up: &str) -> Result<()> {
+        Self::create_table_if_needed(dialect, pool).await?;
+        let session = SQLSaveSession::new(pool.clone());
+        let result = Self::apply_in_session(dialect, &session, version, name, up).await;
+        match result {
+            Ok(()) => session.commit().await,
+            Err(err) => {
+                if let Err(abort_err) = session.abort().await {
+                    eprintln!("Failed to abort transaction: {}", abort_err.message());
+                }
+                Err(err)
+            }
+        }
+    }
+
+    async fn apply_in_session(dialect: SQLDialect, session: &SQLSaveSession, version: &str, name: &str, up: &str) -> Result<()> {
+        let transaction = session.transaction().await?;
+        if !up.trim().is_empty() {
+            transaction.raw_cmd(up).await.map_err(|e| Error::internal_server_error(format!("Migration {} failed: {}", version, e)))?;
+        }
+        let mut params = SQLParams::new();
+        let applied_at = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+        let values = [
+            ("version", params.push(QuaintValue::text(version.to_owned()))),
+            ("name", params.push(QuaintValue::text(name.to_owned()))),
+            ("appliedAt", params.push(QuaintValue::text(applied_at))),
+        ];
+        let value_refs: Vec<(&str, &str)> = values.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        let stmt = SQL::insert_into(MIGRATION_HISTORY_TABLE).values(value_refs).to_string(dialect);
+        let (stmt, params) = params.finish(&stmt, dialect);
+        transaction.execute_raw(&stmt, &params).await.map_err(|e| Error::internal_server_error(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Run the reverting statements of a migration file and remove its version from the history
+    /// in one transaction. Like `apply`, this isn't atomic for DDL statements on MySQL.
+    pub(crate) async fn revert(dialect: SQLDialect, pool: &SQLPool, version: &str, down: &str) -> Result<()> {
+        Self::create_table_if_needed(dialect, pool).await?;
+        let session = SQLSaveSession::new(pool.clone());
+        let result = Self::revert_in_session(dialect, &session, version, down).await;
+        match result {
+            Ok(()) => session.commit().await,
+            Err(err) => {
+                if let Err(abort_err) = session.abort().await {
+                    eprintln!("Failed to abort transaction: {}", abort_err.message());
+                }
+                Err(err)
+            }
+        }
+    }
+
+    async fn revert_in_session(dialect: SQLDialect, session: &SQLSaveSession, version: &str, down: &str) -> Result<()> {
+        let transaction = session.transaction().await?;
+        if !down.trim().is_empty() {
+            transaction.raw_cmd(down).await.map_err(|e| Error::internal_server_error(format!("Reverting migration {} failed: {}", version, e)))?;
+        }
+        let mut params = SQLParams::new();
+        let r#where = format!("{} = {}", "version".escape(dialect), params.push(QuaintValue::text(version.to_owned())));
+        let stmt = SQL::delete_from(&MIGRATION_HISTORY_TABLE.escape(dialect)).r#where(r#where).to_string(dialect);
+        let (stmt, params) = params.finish(&stmt, dialect);
+        transaction.execute_raw(&stmt, &params).await.map_err(|e| Error::internal_server_error(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -5,18 +5,24 @@ use maplit::hashset;
 use quaint_forked::pooled::{PooledConnection, Quaint};
 use quaint_forked::prelude::Queryable;
 use quaint_forked::ast::Query;
-use crate::connectors::sql::migration::sql::{sqlite_auto_increment_query, sqlite_list_indices_query};
+use crate::connectors::sql::migration::history::MIGRATION_HISTORY_TABLE;
+use crate::connectors::sql::migration::sql::{foreign_keys_query, full_text_indices_query, mssql_columns_query, mssql_list_indices_query, sqlite_auto_increment_query, sqlite_list_indices_query};
 use super::super::url::url_utils;
 use crate::connectors::sql::schema::column::decoder::{ColumnDecoder, ColumnManipulation};
 use crate::connectors::sql::stmts::create::table::SQLCreateTableStatement;
 use crate::connectors::sql::stmts::SQL;
 use crate::connectors::sql::schema::column::SQLColumn;
 use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::connectors::sql::schema::foreign_key::SQLForeignKey;
+use crate::connectors::sql::schema::full_text_index::SQLFullTextIndex;
 use crate::core::model::Model;
 use crate::connectors::sql::schema::value::encode::ToSQLString;
+use crate::core::connector::MigrationPlan;
+use crate::core::error::Error;
 use crate::core::field::Sort;
 use crate::core::model::index::{ModelIndex, ModelIndexItem, ModelIndexType};
 use crate::core::pipeline::ctx::Ctx;
+use crate::core::result::Result;
 use crate::prelude::Value;
 
 pub(crate) struct SQLMigration { }
@@ -28,6 +34,7 @@ impl SQLMigration {
     pub(crate) async fn create_database_if_needed(dialect: SQLDialect, url: &str, reset: bool) {
         match dialect {
             SQLDialect::SQLite => Self::create_sqlite_database_if_needed(url, reset).await,
+            SQLDialect::MSSQL => Self::create_mssql_database_if_needed(url, reset).await,
             _ => Self::create_server_database_if_needed(dialect, url, reset).await,
         }
     }
@@ -81,6 +88,25 @@ impl SQLMigration {
         }
     }
 
+    pub(crate) async fn create_mssql_database_if_needed(url: &str, reset: bool) {
+        let db_name = url_utils::mssql_database_name(url).expect("SQL Server connection string should specify a database.");
+        let url_without_db = url_utils::mssql_remove_database(url);
+        let pool = Quaint::builder(url_without_db.as_str()).unwrap().build();
+        let conn = pool.check_out().await.unwrap();
+        // drop database if needed
+        if reset {
+            let stmt = SQL::drop().database(db_name).if_exists().to_string(SQLDialect::MSSQL);
+            conn.execute(Query::from(stmt)).await.unwrap();
+        }
+        // create database if needed
+        let result = conn.query_raw("SELECT name FROM sys.databases WHERE name = @P1", &[db_name.into()]).await
+            .unwrap_or_else(|err| panic!("Cannot list SQL Server databases: {}", err));
+        if result.is_empty() {
+            let stmt = SQL::create().database(db_name).to_string(SQLDialect::MSSQL);
+            conn.execute(Query::from(stmt)).await.unwrap();
+        }
+    }
+
     // Migrate
 
     pub(crate) async fn db_columns(conn: &PooledConnection, dialect: SQLDialect, table_name: &str) -> HashSet<SQLColumn> {
@@ -92,6 +118,10 @@ impl SQLMigration {
                 let db_columns = ColumnDecoder::decode_sqlite_columns(columns_result, indices_result, auto_increment_result);
                 db_columns
             }
+            SQLDialect::MSSQL => {
+                let columns_result = conn.query_raw(mssql_columns_query(), &[table_name.into()]).await.unwrap();
+                ColumnDecoder::decode_mssql_columns(columns_result)
+            }
             _ => {
                 let mut results = hashset! {};
                 let db_table_columns = conn.query(if dialect == SQLDialect::PostgreSQL {
@@ -110,7 +140,7 @@ impl SQLMigration {
         }
     }
 
-    pub(crate) async fn get_db_user_tables(dialect: SQLDialect, conn: &PooledConnection) -> Vec<String> {
+    pub(crate) async fn get_db_user_tables(dialect: SQLDialect, conn: &dyn Queryable) -> Vec<String> {
         match dialect {
             SQLDialect::MySQL => {
                 let sql = "SHOW TABLES";
@@ -127,35 +157,124 @@ impl SQLMigration {
                 let db_result = conn.query(Query::from(sql)).await.unwrap();
                 db_result.into_iter().map(|result| { result.into_single().unwrap().to_string().unwrap() }).collect()
             }
-            _ => unreachable!()
+            SQLDialect::MSSQL => {
+                let sql = "SELECT TABLE_NAME FROM INFORMATION_SCHEMA.TABLES WHERE TABLE_TYPE = 'BASE TABLE'";
+                let db_result = conn.query(Query::from(sql)).await.unwrap();
+                db_result.into_iter().map(|result| { result.into_single().unwrap().to_string().unwrap() }).collect()
+            }
         }
     }
 
-    pub(crate) async fn rename_table(dialect: SQLDialect, conn: &PooledConnection, old_name: &str, new_name: &str) {
+    fn rename_table_stmt(dialect: SQLDialect, old_name: &str, new_name: &str) -> String {
         let escape = dialect.escape();
-        let sql = format!("ALTER TABLE {escape}{old_name}{escape} RENAME TO {escape}{new_name}{escape}");
-        conn.execute(Query::from(sql)).await.unwrap();
+        if dialect == SQLDialect::MSSQL {
+            format!("EXEC sp_rename '{old_name}', '{new_name}'")
+        } else {
+            format!("ALTER TABLE {escape}{old_name}{escape} RENAME TO {escape}{new_name}{escape}")
+        }
+    }
+
+    fn rename_column_stmt(dialect: SQLDialect, table_name: &str, old: &str, new: &str) -> String {
+        if dialect == SQLDialect::PostgreSQL {
+            format!("ALTER TABLE {} RENAME COLUMN '{}' TO '{}'", table_name, old, new)
+        } else if dialect == SQLDialect::MSSQL {
+            format!("EXEC sp_rename '{}.{}', '{}', 'COLUMN'", table_name, old, new)
+        } else {
+            format!("ALTER TABLE {} RENAME COLUMN `{}` TO `{}`", table_name, old, new)
+        }
+    }
+
+    fn drop_table_stmt(dialect: SQLDialect, table: &str) -> String {
+        let escape = dialect.escape();
+        format!("DROP TABLE {escape}{table}{escape}")
     }
 
     pub(crate) async fn table_has_records(dialect: SQLDialect, conn: &PooledConnection, table_name: &str) -> bool {
         let escape = dialect.escape();
-        let sql = format!("select * from {escape}{table_name}{escape} limit 1");
+        let sql = if dialect == SQLDialect::MSSQL {
+            format!("select top 1 * from {escape}{table_name}{escape}")
+        } else {
+            format!("select * from {escape}{table_name}{escape} limit 1")
+        };
         !conn.query(Query::from(sql)).await.unwrap().is_empty()
     }
 
-    pub(crate) async fn migrate(dialect: SQLDialect, pool: &Quaint, models: &Vec<Model>) {
-        let conn = pool.check_out().await.unwrap();
+    pub(crate) async fn migrate(dialect: SQLDialect, pool: &Quaint, models: &[Model]) -> Result<()> {
+        Self::run(dialect, pool, models, false).await?;
+        Ok(())
+    }
+
+    /// Diff the database against the models without changing anything. The database is only
+    /// read.
+    pub(crate) async fn plan(dialect: SQLDialect, pool: &Quaint, models: &[Model]) -> Result<MigrationPlan> {
+        Self::run(dialect, pool, models, true).await
+    }
+
+    /// Record a statement and the statement reverting it, and run it unless this is a dry run.
+    async fn execute(conn: &PooledConnection, plan: &mut MigrationPlan, dry_run: bool, up: String, down: Option<String>) -> Result<()> {
+        if !dry_run {
+            conn.raw_cmd(&up).await.map_err(|e| Error::internal_server_error(format!("Cannot run `{}`: {}", up, e)))?;
+        }
+        plan.up.push(up);
+        if let Some(down) = down {
+            plan.down.insert(0, down);
+        }
+        Ok(())
+    }
+
+    async fn run(dialect: SQLDialect, pool: &Quaint, models: &[Model], dry_run: bool) -> Result<MigrationPlan> {
+        let conn = pool.check_out().await.map_err(|e| Error::internal_server_error(e.to_string()))?;
+        let mut plan = MigrationPlan::default();
         let mut db_tables = Self::get_db_user_tables(dialect, &conn).await;
+        db_tables.retain(|t| t != MIGRATION_HISTORY_TABLE);
+        // SQLite cannot alter constraints, foreign keys are only created with their tables
+        let foreign_keys: Vec<SQLForeignKey> = models.iter().filter(|m| !m.r#virtual()).flat_map(|m| SQLForeignKey::from_model(m, models, dialect)).collect();
+        let mut db_foreign_keys = if dialect == SQLDialect::SQLite { vec![] } else { Self::db_foreign_keys(dialect, &conn).await };
+        // drop outdated foreign keys before tables and columns change
+        for (name, table, on_update, on_delete) in db_foreign_keys.clone() {
+            let foreign_key = foreign_keys.iter().find(|f| f.name == name && f.table == table);
+            if let Some(foreign_key) = foreign_key {
+                if foreign_key.on_update == on_update && foreign_key.on_delete == on_delete { continue }
+            }
+            let down = foreign_key.map(|f| {
+                let mut previous = f.clone();
+                previous.on_update = Self::static_rule(&on_update);
+                previous.on_delete = Self::static_rule(&on_delete);
+                previous.to_sql_add(dialect)
+            });
+            Self::execute(&conn, &mut plan, dry_run, SQLForeignKey::drop_stmt(dialect, &table, &name), down).await?;
+            db_foreign_keys.retain(|(n, t, _, _)| !(n == &name && t == &table));
+        }
+        // SQLite keeps full-text indices in virtual tables, which are not user tables
+        if dialect == SQLDialect::SQLite {
+            let virtual_tables = Self::sqlite_virtual_tables(&conn).await;
+            db_tables.retain(|t| !virtual_tables.iter().any(|(v, _)| t == v || t.starts_with(&format!("{v}_"))));
+        }
+        let full_text_indices: Vec<SQLFullTextIndex> = models.iter().filter(|m| !m.r#virtual()).flat_map(SQLFullTextIndex::from_model).collect();
+        let mut db_full_text_indices = Self::db_full_text_indices(dialect, &conn).await;
+        // drop outdated full-text indices before tables and columns change
+        for (name, table) in db_full_text_indices.clone() {
+            if full_text_indices.iter().any(|i| i.name == name && i.table == table) { continue }
+            Self::execute(&conn, &mut plan, dry_run, SQLFullTextIndex::drop_stmt(dialect, &table, &name), None).await?;
+            db_full_text_indices.retain(|(n, t)| !(n == &name && t == &table));
+        }
         // compare each table and do migration
         for model in models {
             if model.r#virtual() { continue }
             let table_name = model.table_name();
+            // on a dry run, a renamed table still has its old name in the database
+            let mut db_table_name = table_name.to_owned();
             if let Some(migration) = model.migration() {
                 if !db_tables.iter().any(|x| x == table_name) {
                     for old_name in &migration.renamed {
                         if db_tables.contains(old_name) {
                             // rename
-                            Self::rename_table(dialect, &conn, old_name.as_str(), table_name).await;
+                            let up = Self::rename_table_stmt(dialect, old_name.as_str(), table_name);
+                            let down = Self::rename_table_stmt(dialect, table_name, old_name.as_str());
+                            Self::execute(&conn, &mut plan, dry_run, up, Some(down)).await?;
+                            if dry_run {
+                                db_table_name = old_name.clone();
+                            }
                             let index = db_tables.clone().iter().find_position(|v| *v == old_name).unwrap().0;
                             db_tables.remove(index);
                             db_tables.push(table_name.to_string());
@@ -168,43 +287,50 @@ impl SQLMigration {
             let is_table_exist = db_tables.iter().any(|x| x == table_name);
             if !is_table_exist {
                 // table not exist, create table
-                Self::create_table(dialect, &conn, model).await;
+                Self::create_table(dialect, &conn, &mut plan, dry_run, model, models).await?;
             } else {
                 // remove from list
                 let index = db_tables.clone().iter().find_position(|x| *x == table_name).unwrap().0;
                 db_tables.remove(index);
                 // start migrate for this table
                 let model_columns = ColumnDecoder::decode_model_columns(model);
-                let db_columns = Self::db_columns(&conn, dialect, table_name).await;
+                let db_columns = Self::db_columns(&conn, dialect, &db_table_name).await;
                 let need_to_alter_any_column = ColumnDecoder::need_to_alter_any_columns(&db_columns, &model_columns);
                 if need_to_alter_any_column && dialect == SQLDialect::SQLite {
-                    panic!("SQLite doesn't support column altering");
+                    return Err(Error::internal_server_error(format!("SQLite doesn't support altering columns of table `{}'.", table_name)));
                 }
-                let table_has_records = Self::table_has_records(dialect, &conn, table_name).await;
-                let db_indices = Self::db_indices(dialect, &conn, model).await;
+                let table_has_records = Self::table_has_records(dialect, &conn, &db_table_name).await;
+                let db_indices = Self::db_indices(dialect, &conn, &db_table_name).await;
                 let model_indices = Self::normalized_model_indices(model.indices(), dialect, table_name);
                 // here update columns and indices
                 let manipulations = ColumnDecoder::manipulations(&db_columns, &model_columns, &db_indices, &model_indices, model);
                 if table_has_records && manipulations.iter().find(|m| m.is_add_column_non_null()).is_some() && model.allows_drop_when_migrate() {
-                    Self::drop_table(dialect, &conn, table_name).await;
-                    Self::create_table(dialect, &conn, model).await;
+                    for index in full_text_indices.iter().filter(|i| i.table == table_name) {
+                        if !db_full_text_indices.iter().any(|(n, t)| n == &index.name && t == &index.table) { continue }
+                        Self::execute(&conn, &mut plan, dry_run, index.to_sql_drop(dialect), Some(index.to_sql_create(dialect))).await?;
+                    }
+                    db_full_text_indices.retain(|(_, t)| t != table_name);
+                    Self::drop_table(dialect, &conn, &mut plan, dry_run, table_name, &db_columns).await?;
+                    db_foreign_keys.retain(|(_, t, _, _)| t != table_name);
+                    Self::create_table(dialect, &conn, &mut plan, dry_run, model, models).await?;
                 } else {
                     for m in manipulations.iter() {
                         match m {
                             ColumnManipulation::CreateIndex(index) => {
                                 let create = index.to_sql_create(dialect, table_name);
-                                conn.execute(Query::from(create)).await.unwrap();
+                                let drop = index.to_sql_drop(dialect, table_name);
+                                Self::execute(&conn, &mut plan, dry_run, create, Some(drop)).await?;
                             }
                             ColumnManipulation::DropIndex(index) => {
                                 let drop = index.to_sql_drop(dialect, table_name);
-                                conn.execute(Query::from(drop)).await.unwrap();
+                                let create = index.to_sql_create(dialect, table_name);
+                                Self::execute(&conn, &mut plan, dry_run, drop, Some(create)).await?;
                             }
                             ColumnManipulation::AddColumn(column, action, default) => {
                                 if column.not_null() && default.is_none() {
                                     // if any records, just raise here
-                                    let has_records = Self::table_has_records(dialect, &conn, table_name).await;
-                                    if has_records {
-                                        panic!("Cannot add new non null column `{}', table `{}' has records. Consider add a default value or drop the table.", column.name(), table_name)
+                                    if table_has_records {
+                                        return Err(Error::internal_server_error(format!("Cannot add new non null column `{}', table `{}' has records. Consider add a default value or drop the table.", column.name(), table_name)));
                                     }
                                 }
                                 let mut c = column.clone().clone();
@@ -212,38 +338,46 @@ impl SQLMigration {
                                     c.set_default(Some(default.as_ref().unwrap().to_string(dialect)));
                                 }
                                 let stmt = SQL::alter_table(table_name).add(c).to_string(dialect);
-                                conn.execute(Query::from(stmt)).await.unwrap();
+                                let down = SQL::alter_table(table_name).drop_column(column.name()).to_string(dialect);
+                                Self::execute(&conn, &mut plan, dry_run, stmt, Some(down)).await?;
                                 if let Some(action)= action {
-                                    let ctx = Ctx::initial_state_with_value(Value::Null);
-                                    action.process(ctx).await.unwrap();
+                                    if !dry_run {
+                                        let ctx = Ctx::initial_state_with_value(Value::Null);
+                                        action.process(ctx).await?;
+                                    }
                                 }
                             }
                             ColumnManipulation::AlterColumn(old_column, new_column, _action) => {
                                 if dialect != SQLDialect::PostgreSQL {
-                                    let alter = SQL::alter_table(table_name).modify(new_column.clone().clone()).to_string(dialect);
-                                    conn.execute(Query::from(alter)).await.unwrap();
+                                    let alter = SQL::alter_table(table_name).modify((*new_column).clone()).to_string(dialect);
+                                    let down = SQL::alter_table(table_name).modify((*old_column).clone()).to_string(dialect);
+                                    Self::execute(&conn, &mut plan, dry_run, alter, Some(down)).await?;
                                 } else {
-                                    let clauses = Self::psql_alter_clauses(table_name, *old_column, *new_column);
-                                    for clause in clauses {
-                                        conn.execute(Query::from(clause)).await.unwrap();
+                                    let clauses = Self::psql_alter_clauses(table_name, old_column, new_column);
+                                    let down_clauses = Self::psql_alter_clauses(table_name, new_column, old_column);
+                                    if !clauses.is_empty() {
+                                        let down = if down_clauses.is_empty() { None } else { Some(down_clauses.join(";\n")) };
+                                        Self::execute(&conn, &mut plan, dry_run, clauses.join(";\n"), down).await?;
                                     }
                                 }
                             }
                             ColumnManipulation::RemoveColumn(name, action) => {
                                 if let Some(action)= action {
-                                    let ctx = Ctx::initial_state_with_value(Value::Null);
-                                    action.process(ctx).await.unwrap();
+                                    if !dry_run {
+                                        let ctx = Ctx::initial_state_with_value(Value::Null);
+                                        action.process(ctx).await?;
+                                    }
                                 }
                                 let stmt = SQL::alter_table(table_name).drop_column(name).to_string(dialect);
-                                conn.execute(Query::from(stmt)).await.unwrap();
+                                let down = db_columns.iter().find(|c| c.name() == name).map(|c| {
+                                    SQL::alter_table(table_name).add(c.clone()).to_string(dialect)
+                                });
+                                Self::execute(&conn, &mut plan, dry_run, stmt, down).await?;
                             }
                             ColumnManipulation::RenameColumn { old, new } => {
-                                let stmt = if dialect == SQLDialect::PostgreSQL {
-                                    format!("ALTER TABLE {} RENAME COLUMN '{}' TO '{}'", table_name, old, new)
-                                } else {
-                                    format!("ALTER TABLE {} RENAME COLUMN `{}` TO `{}`", table_name, old, new)
-                                };
-                                conn.execute(Query::from(stmt)).await.unwrap();
+                                let stmt = Self::rename_column_stmt(dialect, table_name, old, new);
+                                let down = Self::rename_column_stmt(dialect, table_name, new, old);
+                                Self::execute(&conn, &mut plan, dry_run, stmt, Some(down)).await?;
                             }
                         }
                     }
@@ -252,27 +386,94 @@ impl SQLMigration {
         }
         // drop tables
         for table in db_tables {
-            Self::drop_table(dialect, &conn, &table).await;
+            let db_columns = Self::db_columns(&conn, dialect, &table).await;
+            Self::drop_table(dialect, &conn, &mut plan, dry_run, &table, &db_columns).await?;
+        }
+        // add missing foreign keys after all tables are in place
+        if dialect != SQLDialect::SQLite {
+            for foreign_key in foreign_keys.iter() {
+                if db_foreign_keys.iter().any(|(n, t, _, _)| n == &foreign_key.name && t == &foreign_key.table) { continue }
+                Self::execute(&conn, &mut plan, dry_run, foreign_key.to_sql_add(dialect), Some(foreign_key.to_sql_drop(dialect))).await?;
+            }
         }
+        // add missing full-text indices after all tables are in place
+        for index in full_text_indices.iter() {
+            if db_full_text_indices.iter().any(|(n, t)| n == &index.name && t == &index.table) { continue }
+            Self::execute(&conn, &mut plan, dry_run, index.to_sql_create(dialect), Some(index.to_sql_drop(dialect))).await?;
+        }
+        Ok(plan)
     }
 
-    async fn drop_table(dialect: SQLDialect, conn: &PooledConnection, table: &str) {
-        let escape = dialect.escape();
-        let sql = format!("DROP TABLE {escape}{table}{escape}");
-        conn.execute(Query::from(sql)).await.unwrap();
+    /// Generated foreign keys in the database, as name, table, update rule and delete rule.
+    async fn db_foreign_keys(dialect: SQLDialect, conn: &PooledConnection) -> Vec<(String, String, String, String)> {
+        let result_set = conn.query(Query::from(foreign_keys_query(dialect))).await.unwrap();
+        result_set.into_iter().filter_map(|row| {
+            let name = row.get("constraint_name").unwrap().to_string().unwrap();
+            if !SQLForeignKey::is_generated_name(&name) { return None }
+            let table = row.get("table_name").unwrap().to_string().unwrap();
+            let on_update = row.get("update_rule").unwrap().to_string().unwrap().to_uppercase().replace('_', " ");
+            let on_delete = row.get("delete_rule").unwrap().to_string().unwrap().to_uppercase().replace('_', " ");
+            Some((name, table, on_update, on_delete))
+        }).collect()
+    }
+
+    /// Generated full-text indices in the database, as name and table.
+    async fn db_full_text_indices(dialect: SQLDialect, conn: &PooledConnection) -> Vec<(String, String)> {
+        let indices = match dialect {
+            SQLDialect::MSSQL => vec![],
+            SQLDialect::SQLite => Self::sqlite_virtual_tables(conn).await.into_iter().filter_map(|(name, sql)| {
+                Some((name, SQLFullTextIndex::sqlite_content_table(&sql)?))
+            }).collect(),
+            _ => {
+                let result_set = conn.query(Query::from(full_text_indices_query(dialect))).await.unwrap();
+                result_set.into_iter().map(|row| {
+                    (row.get("index_name").unwrap().to_string().unwrap(), row.get("table_name").unwrap().to_string().unwrap())
+                }).collect()
+            }
+        };
+        indices.into_iter().filter(|(name, _)| SQLFullTextIndex::is_generated_name(name)).collect()
+    }
+
+    /// Virtual tables in a SQLite database, as name and create statement.
+    async fn sqlite_virtual_tables(conn: &PooledConnection) -> Vec<(String, String)> {
+        let result_set = conn.query(Query::from(full_text_indices_query(SQLDialect::SQLite))).await.unwrap();
+        result_set.into_iter().map(|row| {
+            (row.get("name").unwrap().to_string().unwrap(), row.get("sql").unwrap().to_string().unwrap())
+        }).collect()
+    }
+
+    fn static_rule(rule: &str) -> &'static str {
+        match rule {
+            "CASCADE" => "CASCADE",
+            "SET NULL" => "SET NULL",
+            "RESTRICT" => "RESTRICT",
+            "SET DEFAULT" => "SET DEFAULT",
+            _ => "NO ACTION",
+        }
     }
 
-    async fn create_table(dialect: SQLDialect, conn: &PooledConnection, model: &Model) {
+    async fn drop_table(dialect: SQLDialect, conn: &PooledConnection, plan: &mut MigrationPlan, dry_run: bool, table: &str, db_columns: &HashSet<SQLColumn>) -> Result<()> {
+        let mut create = SQL::create().table(table);
+        create.columns(db_columns.iter().cloned().collect());
+        Self::execute(conn, plan, dry_run, Self::drop_table_stmt(dialect, table), Some(create.to_string(dialect))).await
+    }
+
+    async fn create_table(dialect: SQLDialect, conn: &PooledConnection, plan: &mut MigrationPlan, dry_run: bool, model: &Model, models: &[Model]) -> Result<()> {
         // create table
-        let stmt = SQLCreateTableStatement::from(model).to_string(dialect);
-        conn.execute(Query::from(stmt)).await.unwrap();
+        let mut stmt = SQLCreateTableStatement::from(model);
+        if dialect == SQLDialect::SQLite {
+            stmt.foreign_keys(SQLForeignKey::from_model(model, models, dialect));
+        }
+        let stmt = stmt.to_string(dialect);
+        Self::execute(conn, plan, dry_run, stmt, Some(Self::drop_table_stmt(dialect, model.table_name()))).await?;
         // create indices
         for index in model.indices() {
             // primary is created when creating table
             if index.r#type().is_primary() { continue }
             let stmt = index.to_sql_create(dialect, model.table_name());
-            conn.execute(Query::from(stmt)).await.unwrap();
+            Self::execute(conn, plan, dry_run, stmt, None).await?;
         }
+        Ok(())
     }
 
     fn psql_alter_clauses(table: &str, old_column: &SQLColumn, new_column: &SQLColumn) -> Vec<String> {
@@ -304,21 +505,22 @@ impl SQLMigration {
         }).collect()
     }
 
-    async fn db_indices(dialect: SQLDialect, conn: &PooledConnection, model: &Model) -> HashSet<ModelIndex> {
+    async fn db_indices(dialect: SQLDialect, conn: &PooledConnection, table_name: &str) -> HashSet<ModelIndex> {
         match dialect {
-            SQLDialect::PostgreSQL => Self::psql_db_indices(conn, model).await,
-            SQLDialect::MySQL => Self::mysql_db_indices(conn, model).await,
-            SQLDialect::SQLite => Self::sqlite_db_indices(conn, model).await,
-            _ => unreachable!(),
+            SQLDialect::PostgreSQL => Self::psql_db_indices(conn, table_name).await,
+            SQLDialect::MySQL => Self::mysql_db_indices(conn, table_name).await,
+            SQLDialect::SQLite => Self::sqlite_db_indices(conn, table_name).await,
+            SQLDialect::MSSQL => Self::mssql_db_indices(conn, table_name).await,
         }
     }
 
-    async fn mysql_db_indices(conn: &PooledConnection, model: &Model) -> HashSet<ModelIndex> {
-        let table_name = model.table_name();
+    async fn mysql_db_indices(conn: &PooledConnection, table_name: &str) -> HashSet<ModelIndex> {
         let sql = format!("SHOW INDEX FROM `{}`", table_name);
         let result_set = conn.query(Query::from(sql)).await.unwrap();
         let mut indices = vec![];
         for row in result_set {
+            // full-text indices are migrated separately
+            if row.get("Index_type").and_then(|t| t.as_str()) == Some("FULLTEXT") { continue }
             let index_name = row.get("Key_name").unwrap().as_str().unwrap();
             let column_name = row.get("Column_name").unwrap().as_str().unwrap();
             let order = Sort::from_mysql_str(row.get("Collation").unwrap().as_str().unwrap()).unwrap();
@@ -339,8 +541,7 @@ impl SQLMigration {
         indices.into_iter().collect()
     }
 
-    async fn psql_db_indices(conn: &PooledConnection, model: &Model) -> HashSet<ModelIndex> {
-        let table_name = model.table_name();
+    async fn psql_db_indices(conn: &PooledConnection, table_name: &str) -> HashSet<ModelIndex> {
         let sql = format!(r#"SELECT     irel.relname                           AS index_name,
            a.attname                              AS column_name,
            i.indisunique                          AS is_unique,
@@ -399,8 +600,41 @@ GROUP BY   tnsp.nspname,
         indices.into_iter().collect()
     }
 
-    async fn sqlite_db_indices(conn: &PooledConnection, model: &Model) -> HashSet<ModelIndex> {
-        let table_name = model.table_name();
+    async fn mssql_db_indices(conn: &PooledConnection, table_name: &str) -> HashSet<ModelIndex> {
+        let result_set = conn.query_raw(mssql_list_indices_query(), &[table_name.into()]).await.unwrap();
+        let mut indices = vec![];
+        for row in result_set {
+            let index_name = row.get("index_name").unwrap().as_str().unwrap();
+            let column_name = row.get("column_name").unwrap().as_str().unwrap();
+            let order = Sort::from_desc_bool(row.get("is_descending").unwrap().as_bool().unwrap());
+            if let Some(position) = indices.iter().position(|m: &ModelIndex| m.name().unwrap() == index_name) {
+                let model_index = indices.get_mut(position).unwrap();
+                let item = ModelIndexItem::new(column_name, order, None);
+                model_index.append_item(item);
+            } else {
+                let is_unique = row.get("is_unique").unwrap().as_bool().unwrap();
+                let is_primary = row.get("is_primary").unwrap().as_bool().unwrap();
+                let item = ModelIndexItem::new(column_name, order, None);
+                indices.push(ModelIndex::new(
+                    if is_primary { ModelIndexType::Primary } else if is_unique { ModelIndexType::Unique} else { ModelIndexType::Index },
+                    Some(index_name),
+                    vec![item],
+                ))
+            }
+        }
+        // primary key constraints are named by SQL Server, use the normalized name instead
+        indices.into_iter().map(|index| {
+            if index.r#type().is_primary() {
+                let mut normalized = ModelIndex::new(ModelIndexType::Primary, None::<String>, index.items().clone());
+                normalized.set_name(normalized.normalize_name(table_name, SQLDialect::MSSQL));
+                normalized
+            } else {
+                index
+            }
+        }).collect()
+    }
+
+    async fn sqlite_db_indices(conn: &PooledConnection, table_name: &str) -> HashSet<ModelIndex> {
         let sql = format!(r#"SELECT
     il.name as index_name,
     ii.name as column_name,
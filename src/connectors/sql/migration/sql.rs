@@ -1,3 +1,5 @@
+use crate::connectors::sql::schema::dialect::SQLDialect;
+
 pub(crate) fn sqlite_list_indices_query(table_name: &str) -> String {
     format!("SELECT
   m.tbl_name AS table_name,
@@ -22,3 +24,81 @@ pub(crate) fn sqlite_auto_increment_query(table_name: &str) -> String {
 pub(crate) fn psql_is_auto_increment(table_name: &str, column_name: &str) -> String {
     format!("select relname from pg_class where relname = '{}_{}_seq'", table_name, column_name)
 }
+
+/// Columns of the table named by the first parameter.
+pub(crate) fn mssql_columns_query() -> &'static str {
+    "SELECT
+  c.COLUMN_NAME AS column_name,
+  c.DATA_TYPE AS data_type,
+  c.IS_NULLABLE AS is_nullable,
+  c.CHARACTER_MAXIMUM_LENGTH AS character_maximum_length,
+  c.NUMERIC_PRECISION AS numeric_precision,
+  c.NUMERIC_SCALE AS numeric_scale,
+  c.DATETIME_PRECISION AS datetime_precision,
+  COLUMNPROPERTY(OBJECT_ID(c.TABLE_SCHEMA + '.' + c.TABLE_NAME), c.COLUMN_NAME, 'IsIdentity') AS is_identity,
+  CASE WHEN pk.COLUMN_NAME IS NULL THEN 0 ELSE 1 END AS is_primary
+FROM INFORMATION_SCHEMA.COLUMNS AS c
+LEFT JOIN (
+  SELECT ku.TABLE_NAME, ku.COLUMN_NAME
+  FROM INFORMATION_SCHEMA.TABLE_CONSTRAINTS AS tc
+  JOIN INFORMATION_SCHEMA.KEY_COLUMN_USAGE AS ku
+  ON tc.CONSTRAINT_NAME = ku.CONSTRAINT_NAME AND tc.CONSTRAINT_TYPE = 'PRIMARY KEY'
+) AS pk ON pk.TABLE_NAME = c.TABLE_NAME AND pk.COLUMN_NAME = c.COLUMN_NAME
+WHERE c.TABLE_NAME = @P1"
+}
+
+/// Indices of the table named by the first parameter.
+pub(crate) fn mssql_list_indices_query() -> &'static str {
+    "SELECT
+  i.name AS index_name,
+  c.name AS column_name,
+  i.is_unique AS is_unique,
+  i.is_primary_key AS is_primary,
+  ic.is_descending_key AS is_descending
+FROM sys.indexes AS i
+JOIN sys.index_columns AS ic ON i.object_id = ic.object_id AND i.index_id = ic.index_id
+JOIN sys.columns AS c ON ic.object_id = c.object_id AND ic.column_id = c.column_id
+WHERE i.object_id = OBJECT_ID(QUOTENAME(@P1)) AND i.type > 0
+ORDER BY i.name, ic.key_ordinal"
+}
+
+pub(crate) fn foreign_keys_query(dialect: SQLDialect) -> String {
+    let schema = match dialect {
+        SQLDialect::MySQL => "DATABASE()",
+        SQLDialect::PostgreSQL => "current_schema()",
+        SQLDialect::MSSQL => "SCHEMA_NAME()",
+        SQLDialect::SQLite => unreachable!(),
+    };
+    format!("SELECT
+  rc.CONSTRAINT_NAME AS constraint_name,
+  tc.TABLE_NAME AS table_name,
+  rc.UPDATE_RULE AS update_rule,
+  rc.DELETE_RULE AS delete_rule
+FROM
+  INFORMATION_SCHEMA.REFERENTIAL_CONSTRAINTS rc
+  JOIN INFORMATION_SCHEMA.TABLE_CONSTRAINTS tc
+    ON rc.CONSTRAINT_NAME = tc.CONSTRAINT_NAME AND rc.CONSTRAINT_SCHEMA = tc.CONSTRAINT_SCHEMA
+WHERE
+  rc.CONSTRAINT_SCHEMA = {}", schema)
+}
+
+pub(crate) fn full_text_indices_query(dialect: SQLDialect) -> String {
+    match dialect {
+        SQLDialect::MySQL => "SELECT DISTINCT
+  INDEX_NAME AS index_name,
+  TABLE_NAME AS table_name
+FROM
+  INFORMATION_SCHEMA.STATISTICS
+WHERE
+  TABLE_SCHEMA = DATABASE() AND INDEX_TYPE = 'FULLTEXT'".to_owned(),
+        SQLDialect::PostgreSQL => "SELECT
+  indexname AS index_name,
+  tablename AS table_name
+FROM
+  pg_indexes
+WHERE
+  schemaname = current_schema() AND indexdef LIKE '%to_tsvector%'".to_owned(),
+        SQLDialect::SQLite => "SELECT name, sql FROM sqlite_master WHERE type = 'table' AND sql LIKE 'CREATE VIRTUAL TABLE%'".to_owned(),
+        SQLDialect::MSSQL => unreachable!(),
+    }
+}
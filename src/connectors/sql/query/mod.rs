@@ -3,10 +3,13 @@ use std::collections::BTreeMap;
 use maplit::{btreemap, hashmap};
 use once_cell::sync::Lazy;
 use crate::connectors::sql::schema::dialect::SQLDialect;
-use crate::connectors::sql::schema::value::encode::{IfIMode, SQLEscape, ToLike, ToSQLString, ToWrapped, ValueToSQLString, WrapInArray};
+use crate::connectors::sql::schema::full_text_index::SQLFullTextIndex;
+use quaint_forked::ast::Value as QuaintValue;
+use crate::connectors::sql::schema::value::encode::{IfIMode, SQLEscape, ToSQLParam, ToSQLString, ToWrapped};
 use crate::connectors::sql::stmts::select::r#where::{ToWrappedSQLString, WhereClause};
 use crate::connectors::sql::stmts::select::r#where::WhereClause::{And, Not};
 use crate::connectors::sql::stmts::SQL;
+use crate::connectors::sql::stmts::params::SQLParams;
 use crate::core::field::r#type::{FieldType, FieldTypeOwner};
 use crate::core::input::Input;
 use crate::core::model::Model;
@@ -20,8 +23,27 @@ impl Query {
         format!("{} {} {}", lhs.as_ref(), op, rhs)
     }
 
-    pub(crate) fn where_from_identifier(object: &Object, dialect: SQLDialect) -> String {
-        Self::where_from_value(object.model(), object.graph(), &object.identifier(), dialect)
+    pub(crate) fn where_from_identifier(object: &Object, dialect: SQLDialect, params: &mut SQLParams) -> String {
+        Self::where_from_value(object.model(), object.graph(), &object.identifier(), dialect, params)
+    }
+
+    fn param(value: &Value, r#type: &FieldType, optional: bool, dialect: SQLDialect, params: &mut SQLParams) -> String {
+        if optional && value.is_null() {
+            "NULL".to_owned()
+        } else {
+            params.push(value.to_sql_param(r#type, dialect))
+        }
+    }
+
+    fn like_param(value: &Value, left: bool, right: bool, params: &mut SQLParams) -> String {
+        let mut pattern = value.as_str().unwrap().to_owned();
+        if left {
+            pattern.insert(0, '%');
+        }
+        if right {
+            pattern.push('%');
+        }
+        params.push(QuaintValue::Text(Some(Cow::Owned(pattern))))
     }
 
     fn where_entry_array(
@@ -29,13 +51,14 @@ impl Query {
         r#type: &FieldType,
         optional: bool,
         value: &Value,
-        graph: &Graph,
-        op: &str
+        dialect: SQLDialect,
+        op: &str,
+        params: &mut SQLParams,
     ) -> String {
         let arr_val = value.as_vec().unwrap();
         let mut arr: Vec<String> = Vec::new();
         for val in arr_val {
-            arr.push(val.to_sql_string(r#type, optional, graph));
+            arr.push(Self::param(val, r#type, optional, dialect, params));
         }
         Query::where_item(column_name, op, &arr.join(", ").to_wrapped())
     }
@@ -45,86 +68,96 @@ impl Query {
         r#type: &FieldType,
         optional: bool,
         value: &Value,
-        graph: &Graph,
         dialect: SQLDialect,
+        params: &mut SQLParams,
     ) -> String {
-        let column_name = column_name.escape(dialect);
+        // a column of a joined table is prefixed with its alias, which isn't quoted
+        let column_name = match column_name.split_once('.') {
+            Some((alias, column)) => format!("{}.{}", alias, column.escape(dialect)),
+            None => column_name.escape(dialect),
+        };
         if let Some(map) = value.as_hashmap() {
             let mut result: Vec<String> = vec![];
             for (key, value) in map {
                 match key.as_str() {
-                    "equals" => {
-                        result.push(Self::where_item(&column_name, "=", &value.to_sql_string(r#type, optional, graph)));
+                    "equals" => if value.is_null() {
+                        result.push(Self::where_item(&column_name, "IS", "NULL"));
+                    } else {
+                        result.push(Self::where_item(&column_name, "=", &Self::param(value, r#type, optional, dialect, params)));
                     }
-                    "not" => {
-                        result.push(Self::where_item(&column_name, "<>", &value.to_sql_string(r#type, optional, graph)));
+                    "not" => if value.is_null() {
+                        result.push(Self::where_item(&column_name, "IS NOT", "NULL"));
+                    } else {
+                        result.push(Self::where_item(&column_name, "<>", &Self::param(value, r#type, optional, dialect, params)));
                     }
                     "gt" => {
-                        result.push(Self::where_item(&column_name, ">", &value.to_sql_string(r#type, false, graph)));
+                        result.push(Self::where_item(&column_name, ">", &Self::param(value, r#type, false, dialect, params)));
                     }
                     "gte" => {
-                        result.push(Self::where_item(&column_name, ">=", &value.to_sql_string(r#type, false, graph)));
+                        result.push(Self::where_item(&column_name, ">=", &Self::param(value, r#type, false, dialect, params)));
                     }
                     "lt" => {
-                        result.push(Self::where_item(&column_name, "<", &value.to_sql_string(r#type, false, graph)));
+                        result.push(Self::where_item(&column_name, "<", &Self::param(value, r#type, false, dialect, params)));
                     }
                     "lte" => {
-                        result.push(Self::where_item(&column_name, "<=", &value.to_sql_string(r#type, false, graph)));
+                        result.push(Self::where_item(&column_name, "<=", &Self::param(value, r#type, false, dialect, params)));
                     }
                     "in" => {
-                        result.push(Self::where_entry_array(&column_name, r#type, optional, value, graph, "IN"));
+                        result.push(Self::where_entry_array(&column_name, r#type, optional, value, dialect, "IN", params));
                     }
                     "notIn" => {
-                        result.push(Self::where_entry_array(&column_name, r#type, optional, value, graph, "NOT IN"));
+                        result.push(Self::where_entry_array(&column_name, r#type, optional, value, dialect, "NOT IN", params));
                     }
                     "contains" => {
                         let i_mode = Input::has_i_mode(map);
-                        result.push(Self::where_item(&column_name.to_i_mode(i_mode), "LIKE", &value.to_sql_string(r#type, false, graph).to_like(true, true).to_i_mode(i_mode)));
+                        result.push(Self::where_item(column_name.to_i_mode(i_mode), "LIKE", &Self::like_param(value, true, true, params).to_i_mode(i_mode)));
                     }
                     "startsWith" => {
                         let i_mode = Input::has_i_mode(map);
-                        result.push(Self::where_item(&column_name.to_i_mode(i_mode), "LIKE", &value.to_sql_string(r#type, false, graph).to_like(false, true).to_i_mode(i_mode)));
+                        result.push(Self::where_item(column_name.to_i_mode(i_mode), "LIKE", &Self::like_param(value, false, true, params).to_i_mode(i_mode)));
                     }
                     "endsWith" => {
                         let i_mode = Input::has_i_mode(map);
-                        result.push(Self::where_item(&column_name.to_i_mode(i_mode), "LIKE", &value.to_sql_string(r#type, false, graph).to_like(true, false).to_i_mode(i_mode)));
+                        result.push(Self::where_item(column_name.to_i_mode(i_mode), "LIKE", &Self::like_param(value, true, false, params).to_i_mode(i_mode)));
                     }
                     "matches" => {
                         let i_mode = Input::has_i_mode(map);
-                        result.push(Self::where_item(&column_name.to_i_mode(i_mode), "REGEXP", &value.to_sql_string(r#type, false, graph).to_i_mode(i_mode)));
+                        result.push(Self::where_item(column_name.to_i_mode(i_mode), "REGEXP", &Self::param(value, r#type, false, dialect, params).to_i_mode(i_mode)));
                     }
                     "mode" => { }
                     "has" => {
-                        let element_type = r#type.element_field().unwrap();
-                        result.push(Self::where_item(&column_name, "@>", &value.to_sql_string_array_arg(element_type.field_type(), element_type.is_optional(), graph).wrap_in_array()));
+                        let element_value = Value::Vec(vec![value.clone()]);
+                        result.push(Self::where_item(&column_name, "@>", &Self::param(&element_value, r#type, false, dialect, params)));
                     }
                     "hasEvery" => {
-                        result.push(Self::where_item(&column_name, "@>", &value.to_sql_string_array_arg(r#type, false, graph)));
+                        result.push(Self::where_item(&column_name, "@>", &Self::param(value, r#type, false, dialect, params)));
                     }
                     "hasSome" => {
-                        result.push(Self::where_item(&column_name, "&&", &value.to_sql_string_array_arg(r#type, false, graph)));
+                        result.push(Self::where_item(&column_name, "&&", &Self::param(value, r#type, false, dialect, params)));
                     }
                     "isEmpty" => {
-                        result.push(Self::where_item(&format!("ARRAY_LENGTH({})", &column_name), "=", "0"));
+                        result.push(Self::where_item(format!("ARRAY_LENGTH({})", &column_name), "=", "0"));
                     }
                     "length" => {
-                        result.push(Self::where_item(&format!("ARRAY_LENGTH({})", &column_name), "=", &value.to_sql_string(&FieldType::I64, false, graph)));
+                        result.push(Self::where_item(format!("ARRAY_LENGTH({})", &column_name), "=", &Self::param(value, &FieldType::I64, false, dialect, params)));
                     }
                     "_count" => {
-                        result.push(Self::where_entry_item(&format!("COUNT({})", &column_name), &FieldType::I64, false, value, graph, dialect));
+                        result.push(Self::where_entry_item(&format!("COUNT({})", &column_name), &FieldType::I64, false, value, dialect, params));
                     }
                     "_avg" | "_sum" => {
-                        result.push(Self::where_entry_item(&format!("{}({})", key[1..].to_uppercase(), &column_name), &FieldType::F64, true, value, graph, dialect));
+                        result.push(Self::where_entry_item(&format!("{}({})", key[1..].to_uppercase(), &column_name), &FieldType::F64, true, value, dialect, params));
                     }
                     "_min" | "_max" => {
-                        result.push(Self::where_entry_item(&format!("{}({})", key[1..].to_uppercase(), &column_name), r#type, optional, value, graph, dialect));
+                        result.push(Self::where_entry_item(&format!("{}({})", key[1..].to_uppercase(), &column_name), r#type, optional, value, dialect, params));
                     }
                     _ => panic!("Unhandled key."),
                 }
             }
             And(result).to_wrapped_string(dialect)
+        } else if value.is_null() {
+            Query::where_item(column_name, "IS", "NULL")
         } else {
-            Query::where_item(column_name, "=", &value.to_sql_string(r#type, optional, graph))
+            Query::where_item(column_name, "=", &Self::param(value, r#type, optional, dialect, params))
         }
     }
 
@@ -133,36 +166,37 @@ impl Query {
         field_type: &FieldType,
         optional: bool,
         value: &Value,
-        graph: &Graph,
+        _graph: &Graph,
         dialect: SQLDialect,
+        params: &mut SQLParams,
     ) -> String {
-        Self::where_entry_item(column_name, field_type, optional, value, graph, dialect)
+        Self::where_entry_item(column_name, field_type, optional, value, dialect, params)
     }
 
-    pub(crate) fn where_from_value(model: &Model, _graph: &Graph, identifier: &Value, dialect: SQLDialect) -> String {
+    pub(crate) fn where_from_value(model: &Model, _graph: &Graph, identifier: &Value, dialect: SQLDialect, params: &mut SQLParams) -> String {
         let mut retval: Vec<String> = vec![];
         for (key, value) in identifier.as_hashmap().unwrap() {
             let field = model.field(key).unwrap();
             let column_name = field.column_name();
-            retval.push(format!("{} = {}", column_name, value.to_string(dialect)));
+            retval.push(format!("{} = {}", column_name, Self::param(value, field.field_type(), true, dialect, params)));
         }
         And(retval).to_string(dialect)
     }
 
-    pub(crate) fn r#where(model: &Model, graph: &Graph, r#where: &Value, dialect: SQLDialect, table_alias: Option<&str>) -> String {
+    pub(crate) fn r#where(model: &Model, graph: &Graph, r#where: &Value, dialect: SQLDialect, table_alias: Option<&str>, params: &mut SQLParams) -> String {
         let r#where = r#where.as_hashmap().unwrap();
         let mut retval: Vec<String> = vec![];
         for (key, value) in r#where.iter() {
             if key == "AND" {
-                let inner = WhereClause::And(value.as_vec().unwrap().iter().map(|w| Self::r#where(model, graph, w, dialect, table_alias)).collect()).to_string(dialect);
+                let inner = WhereClause::And(value.as_vec().unwrap().iter().map(|w| Self::r#where(model, graph, w, dialect, table_alias, params)).collect()).to_string(dialect);
                 let val = "(".to_owned() + &inner + ")";
                 retval.push(val);
             } else if key == "OR" {
-                let inner = WhereClause::Or(value.as_vec().unwrap().iter().map(|_w| Self::r#where(model, graph, value, dialect, table_alias)).collect()).to_string(dialect);
+                let inner = WhereClause::Or(value.as_vec().unwrap().iter().map(|w| Self::r#where(model, graph, w, dialect, table_alias, params)).collect()).to_string(dialect);
                 let val = "(".to_owned() + &inner + ")";
                 retval.push(val);
             } else if key == "NOT" {
-                let inner = WhereClause::Not(Self::r#where(model, graph, value, dialect, table_alias)).to_string(dialect);
+                let inner = WhereClause::Not(Self::r#where(model, graph, value, dialect, table_alias, params)).to_string(dialect);
                 let val = "(".to_owned() + &inner + ")";
                 retval.push(val);
             } else {
@@ -174,8 +208,19 @@ impl Query {
                     } else {
                         Cow::Borrowed(column_name)
                     };
-                    let where_entry = Query::where_entry(&entry_column_name, field.field_type(), optional, value, graph, dialect);
-                    retval.push(where_entry);
+                    if field.field_type().is_json() {
+                        retval.push(Query::json_where_entry(&entry_column_name, value, dialect, params));
+                    } else if let Some(term) = value.get("search") {
+                        retval.push(Query::full_text_search(model, key, term.as_str().unwrap(), dialect, table_alias, params));
+                        let mut remaining = value.as_hashmap().unwrap().clone();
+                        remaining.remove("search");
+                        if remaining.keys().any(|k| k != "mode") {
+                            retval.push(Query::where_entry(&entry_column_name, field.field_type(), optional, &Value::HashMap(remaining), graph, dialect, params));
+                        }
+                    } else {
+                        let where_entry = Query::where_entry(&entry_column_name, field.field_type(), optional, value, graph, dialect, params);
+                        retval.push(where_entry);
+                    }
                 } else if let Some(relation) = model.relation(key) {
                     let has_join_table = relation.has_join_table();
                     let id_columns: Vec<&str> = model.primary_index().keys().iter().map(|k| model.field(k).unwrap().column_name()).collect();
@@ -230,7 +275,7 @@ impl Query {
                                 format!("t.{} IS NOT NULL", f.escape(dialect))
                             }).collect::<Vec<String>>().join(" AND ")
                         };
-                        let mut inner_where = Query::r#where(opposite_model, graph, value, dialect, Some("j"));
+                        let mut inner_where = Query::r#where(opposite_model, graph, value, dialect, Some("j"), params);
                         if key.as_str() == "every" {
                             inner_where = Not(inner_where.to_wrapped()).to_string(dialect).to_wrapped();
                         }
@@ -259,6 +304,204 @@ impl Query {
         And(retval).to_string(dialect)
     }
 
+    /// The path of a json filter as a parameter. MySQL and SQLite take a path string while
+    /// PostgreSQL takes an array of keys.
+    fn json_path_param(path: Option<&Value>, dialect: SQLDialect, params: &mut SQLParams) -> String {
+        let components = path.map(|p| p.as_vec().unwrap().clone()).unwrap_or_default();
+        match dialect {
+            SQLDialect::PostgreSQL => params.push(QuaintValue::Array(Some(components.iter().map(|c| {
+                QuaintValue::Text(Some(Cow::Owned(match c {
+                    Value::String(s) => s.clone(),
+                    _ => c.as_i64().unwrap().to_string(),
+                })))
+            }).collect()))),
+            _ => {
+                let mut path = "$".to_owned();
+                for component in components.iter() {
+                    match component {
+                        Value::String(s) => path += &format!(".\"{}\"", s.replace('"', "\\\"")),
+                        _ => path += &format!("[{}]", component.as_i64().unwrap()),
+                    }
+                }
+                params.push(QuaintValue::Text(Some(Cow::Owned(path))))
+            }
+        }
+    }
+
+    /// Sets top level keys of a json column, other keys are kept.
+    pub(crate) fn json_merge(column_name: &str, value: &Value, dialect: SQLDialect, params: &mut SQLParams) -> String {
+        let entries = value.as_indexmap().unwrap();
+        if entries.is_empty() {
+            return column_name.to_owned();
+        }
+        match dialect {
+            SQLDialect::PostgreSQL => format!("COALESCE({column_name}, '{{}}'::jsonb) || {}", params.push(value.to_sql_param(&FieldType::Json, dialect))),
+            SQLDialect::MySQL | SQLDialect::SQLite => {
+                let args = entries.iter().map(|(k, v)| {
+                    let path = Self::json_path_param(Some(&Value::Vec(vec![Value::String(k.clone())])), dialect, params);
+                    let param = params.push(v.to_sql_param(&FieldType::Json, dialect));
+                    if dialect == SQLDialect::MySQL {
+                        format!("{path}, CAST({param} AS JSON)")
+                    } else {
+                        format!("{path}, json({param})")
+                    }
+                }).collect::<Vec<String>>().join(", ");
+                if dialect == SQLDialect::MySQL {
+                    format!("JSON_SET(COALESCE({column_name}, JSON_OBJECT()), {args})")
+                } else {
+                    format!("json_set(COALESCE({column_name}, '{{}}'), {args})")
+                }
+            }
+            // Json fields are rejected on SQL Server by the resolver
+            SQLDialect::MSSQL => unreachable!("Json updates are not supported on SQL Server."),
+        }
+    }
+
+    /// The JSON text of a `json_each` or `json_tree` row. The bundled SQLite has no `->`, and
+    /// the value of a row is 1 or 0 for booleans.
+    fn sqlite_json_text(alias: &str) -> String {
+        format!("CASE {alias}.type WHEN 'true' THEN 'true' WHEN 'false' THEN 'false' WHEN 'null' THEN 'null' ELSE json_quote({alias}.value) END")
+    }
+
+    fn json_where_entry(column_name: &str, value: &Value, dialect: SQLDialect, params: &mut SQLParams) -> String {
+        let column_name = column_name.escape(dialect);
+        let map = value.as_hashmap().unwrap();
+        let path = map.get("path");
+        let mut result: Vec<String> = vec![];
+        for (key, value) in map {
+            match key.as_str() {
+                "path" => (),
+                "equals" => {
+                    let path = Self::json_path_param(path, dialect, params);
+                    let target = match dialect {
+                        SQLDialect::MySQL => format!("JSON_EXTRACT({column_name}, {path})"),
+                        SQLDialect::PostgreSQL => format!("({column_name} #> {path})"),
+                        SQLDialect::SQLite => format!("(SELECT {} FROM json_tree({column_name}, {path}) AS e WHERE e.parent IS NULL)", Self::sqlite_json_text("e")),
+                        // Json fields are rejected on SQL Server by the resolver
+                        SQLDialect::MSSQL => unreachable!("Json filters are not supported on SQL Server."),
+                    };
+                    if value.is_null() {
+                        result.push(Self::where_item(target, "IS", "NULL"));
+                    } else {
+                        let param = params.push(value.to_sql_param(&FieldType::Json, dialect));
+                        let rhs = match dialect {
+                            SQLDialect::MySQL => format!("CAST({param} AS JSON)"),
+                            SQLDialect::SQLite => format!("json({param})"),
+                            _ => param,
+                        };
+                        result.push(Self::where_item(target, "=", &rhs));
+                    }
+                }
+                "arrayContains" => {
+                    let element = Value::Vec(vec![value.clone()]);
+                    match dialect {
+                        SQLDialect::MySQL => {
+                            let element = params.push(element.to_sql_param(&FieldType::Json, dialect));
+                            let path = Self::json_path_param(path, dialect, params);
+                            result.push(format!("JSON_CONTAINS({column_name}, {element}, {path})"));
+                        }
+                        SQLDialect::PostgreSQL => {
+                            let path = Self::json_path_param(path, dialect, params);
+                            let element = params.push(element.to_sql_param(&FieldType::Json, dialect));
+                            result.push(Self::where_item(format!("({column_name} #> {path})"), "@>", &element));
+                        }
+                        SQLDialect::SQLite => {
+                            let path = Self::json_path_param(path, dialect, params);
+                            let element = params.push(value.to_sql_param(&FieldType::Json, dialect));
+                            result.push(format!("EXISTS (SELECT 1 FROM json_each({column_name}, {path}) AS e WHERE {} = json({element}))", Self::sqlite_json_text("e")));
+                        }
+                        // Json fields are rejected on SQL Server by the resolver
+                        SQLDialect::MSSQL => unreachable!("Json filters are not supported on SQL Server."),
+                    }
+                }
+                "stringContains" => {
+                    let path = Self::json_path_param(path, dialect, params);
+                    let target = match dialect {
+                        SQLDialect::MySQL => format!("JSON_UNQUOTE(JSON_EXTRACT({column_name}, {path}))"),
+                        SQLDialect::PostgreSQL => format!("({column_name} #>> {path})"),
+                        SQLDialect::SQLite => format!("json_extract({column_name}, {path})"),
+                        // Json fields are rejected on SQL Server by the resolver
+                        SQLDialect::MSSQL => unreachable!("Json filters are not supported on SQL Server."),
+                    };
+                    result.push(Self::where_item(target, "LIKE", &Self::like_param(value, true, true, params)));
+                }
+                _ => panic!("Unhandled key."),
+            }
+        }
+        And(result).to_wrapped_string(dialect)
+    }
+
+    /// The columns of the full-text index which a field belongs to.
+    fn full_text_columns(model: &Model, field_name: &str, dialect: SQLDialect, table_alias: Option<&str>) -> Vec<String> {
+        model.full_text_index(field_name).unwrap().iter().map(|k| {
+            let column_name = model.field(k).unwrap().column_name().escape(dialect);
+            if let Some(alias) = table_alias {
+                format!("{}.{}", alias, column_name)
+            } else {
+                column_name
+            }
+        }).collect()
+    }
+
+    /// FTS5 treats some characters as operators, quote each word to search it as is.
+    fn fts5_term(term: &str) -> String {
+        term.split_whitespace().map(|w| format!("\"{}\"", w.replace('"', "\"\""))).collect::<Vec<String>>().join(" ")
+    }
+
+    fn full_text_search(model: &Model, field_name: &str, term: &str, dialect: SQLDialect, table_alias: Option<&str>, params: &mut SQLParams) -> String {
+        let columns = Self::full_text_columns(model, field_name, dialect, table_alias);
+        match dialect {
+            SQLDialect::MySQL => {
+                let param = params.push(QuaintValue::Text(Some(Cow::Owned(term.to_owned()))));
+                format!("MATCH({}) AGAINST({} IN NATURAL LANGUAGE MODE)", columns.join(", "), param)
+            }
+            SQLDialect::PostgreSQL => {
+                let param = params.push(QuaintValue::Text(Some(Cow::Owned(term.to_owned()))));
+                format!("{} @@ plainto_tsquery('simple', {})", SQLFullTextIndex::psql_document(&columns), param)
+            }
+            SQLDialect::SQLite => {
+                let fts = Self::sqlite_fts_table(model, field_name);
+                let param = params.push(QuaintValue::Text(Some(Cow::Owned(Self::fts5_term(term)))));
+                let rowid = if let Some(alias) = table_alias { format!("{}.rowid", alias) } else { "rowid".to_owned() };
+                format!("{} IN (SELECT rowid FROM {} WHERE {} MATCH {})", rowid, fts, fts, param)
+            }
+            // @@fullTextIndex is rejected on SQL Server by the resolver
+            SQLDialect::MSSQL => unreachable!("Full-text search is not supported on SQL Server."),
+        }
+    }
+
+    /// Order by relevance, most relevant first.
+    fn full_text_relevance(model: &Model, field_name: &str, term: &str, dialect: SQLDialect, table_alias: Option<&str>, negative_take: bool, params: &mut SQLParams) -> String {
+        let columns = Self::full_text_columns(model, field_name, dialect, table_alias);
+        let (asc, desc) = if negative_take { ("DESC", "ASC") } else { ("ASC", "DESC") };
+        match dialect {
+            SQLDialect::MySQL => {
+                let param = params.push(QuaintValue::Text(Some(Cow::Owned(term.to_owned()))));
+                format!("MATCH({}) AGAINST({} IN NATURAL LANGUAGE MODE) {}", columns.join(", "), param, desc)
+            }
+            SQLDialect::PostgreSQL => {
+                let param = params.push(QuaintValue::Text(Some(Cow::Owned(term.to_owned()))));
+                format!("ts_rank({}, plainto_tsquery('simple', {})) {}", SQLFullTextIndex::psql_document(&columns), param, desc)
+            }
+            SQLDialect::SQLite => {
+                // bm25 is smaller for better matches
+                let fts = Self::sqlite_fts_table(model, field_name);
+                let param = params.push(QuaintValue::Text(Some(Cow::Owned(Self::fts5_term(term)))));
+                let table = table_alias.map(|a| a.to_owned()).unwrap_or(model.table_name().escape(dialect));
+                format!("(SELECT bm25({}) FROM {} WHERE {} MATCH {} AND rowid = {}.rowid) {}", fts, fts, fts, param, table, asc)
+            }
+            // @@fullTextIndex is rejected on SQL Server by the resolver
+            SQLDialect::MSSQL => unreachable!("Full-text search is not supported on SQL Server."),
+        }
+    }
+
+    fn sqlite_fts_table(model: &Model, field_name: &str) -> String {
+        let index = SQLFullTextIndex::from_model(model).into_iter().find(|i| {
+            i.columns.iter().any(|c| c == model.field(field_name).unwrap().column_name())
+        }).unwrap();
+        index.name.as_str().escape(SQLDialect::SQLite)
+    }
+
     pub(crate) fn order_by(
         model: &Model,
         _graph: &Graph,
@@ -295,8 +538,9 @@ impl Query {
         additional_left_join: Option<String>,
         join_table_results: Option<Vec<String>>,
         force_negative_take: bool,
+        params: &mut SQLParams,
     ) -> String {
-        format!("SELECT COUNT(*) FROM ({}) AS _", Self::build(model, graph, value, dialect, additional_where, additional_left_join, join_table_results, force_negative_take))
+        format!("SELECT COUNT(*) FROM ({}) AS _", Self::build(model, graph, value, dialect, additional_where, additional_left_join, join_table_results, force_negative_take, params))
     }
 
     pub(crate) fn build_for_group_by(
@@ -304,15 +548,16 @@ impl Query {
         graph: &Graph,
         value: &Value,
         dialect: SQLDialect,
+        params: &mut SQLParams,
     ) -> String {
-        let aggregate = Self::build_for_aggregate(model, graph, value, dialect);
+        let aggregate = Self::build_for_aggregate(model, graph, value, dialect, params);
         let map = value.as_hashmap().unwrap();
         let by = map.get("by").unwrap().as_vec().unwrap().iter().map(|v| {
             let field_name = v.as_str().unwrap();
             model.field(field_name).unwrap().column_name()
         }).collect::<Vec<&str>>().join(",");
         let having = if let Some(having) = map.get("having") {
-            let inner = Query::r#where(model, graph, having, dialect, None);
+            let inner = Query::r#where(model, graph, having, dialect, None, params);
             " HAVING (".to_owned() + &inner + ")"
         } else {
             "".to_owned()
@@ -325,7 +570,9 @@ impl Query {
         graph: &Graph,
         value: &Value,
         dialect: SQLDialect,
+        params: &mut SQLParams,
     ) -> String {
+        let escape = dialect.escape();
         let map = value.as_hashmap().unwrap();
         let mut results: Vec<String> = vec![];
         for (key, value) in map {
@@ -335,17 +582,17 @@ impl Query {
                         let k = k.as_str();
                         if v.as_bool().unwrap() {
                             match k {
-                                "_all" => results.push("COUNT(*) as `_count._all`".to_owned()),
+                                "_all" => results.push(format!("COUNT(*) as {escape}_count._all{escape}")),
                                 _ => {
                                     let column_name = model.field(k).unwrap().column_name();
                                     let func = SQL_AGGREGATE_MAP.get(key.as_str()).unwrap();
                                     // CAST(AVG(id) as DOUBLE)
                                     let mut left = format!("{}({})", func, column_name);
                                     match key.as_str() {
-                                        "_avg" | "_sum" => left = format!("CAST({} AS DOUBLE)", left),
+                                        "_avg" | "_sum" => left = format!("CAST({} AS {})", left, if dialect == SQLDialect::MSSQL { "FLOAT" } else { "DOUBLE" }),
                                         _ => ()
                                     }
-                                    results.push(format!("{} as `{}.{}`", left, key, k));
+                                    results.push(format!("{} as {escape}{}.{}{escape}", left, key, k));
                                 }
                             }
                         }
@@ -360,7 +607,7 @@ impl Query {
                 results.push(model.field(field_name).unwrap().column_name().to_string());
             }
         }
-        format!("SELECT {} FROM ({}) AS _", results.join(","), Self::build(model, graph, value, dialect, None, None, None, false))
+        format!("SELECT {} FROM ({}) AS _", results.join(","), Self::build(model, graph, value, dialect, None, None, None, false, params))
     }
 
     pub(crate) fn build(
@@ -372,6 +619,7 @@ impl Query {
         additional_left_join: Option<String>,
         join_table_results: Option<Vec<String>>,
         force_negative_take: bool,
+        params: &mut SQLParams,
     ) -> String {
         let r#where = value.get("where");
         let order_by = value.get("orderBy");
@@ -406,15 +654,12 @@ impl Query {
             let order_by = order_by.unwrap().as_vec().unwrap().get(0).unwrap().as_hashmap().unwrap();
             let key = order_by.keys().next().unwrap();
             let column_key = model.field(key).unwrap().column_name();
+            let escape = dialect.escape();
             let columns = cursor.as_hashmap().unwrap().keys().map(|_k| {
-                if dialect == SQLDialect::PostgreSQL {
-                    format!("{} AS \"c.{}\"", column_key, column_key)
-                } else {
-                    format!("{} AS `c.{}`", column_key, column_key)
-                }
+                format!("{} AS {escape}c.{}{escape}", column_key, column_key)
             }).collect::<Vec<String>>();
             let column_refs: Vec<&str> = columns.iter().map(|k| k.as_str()).collect();
-            let sub_where = Query::r#where(model, graph, cursor, dialect, None);
+            let sub_where = Query::r#where(model, graph, cursor, dialect, None, params);
             let mut query = SQL::select(Some(&column_refs), &table_name);
             query.r#where(sub_where);
             Cow::Owned(format!("{}, ({}) AS c", &table_name, &query.to_string(dialect)))
@@ -424,7 +669,7 @@ impl Query {
         let mut stmt = SQL::select(if columns.is_empty() { None } else { Some(&column_refs) }, from.as_ref());
         if let Some(r#where) = r#where {
             if !r#where.as_hashmap().unwrap().is_empty() {
-                stmt.r#where(Query::r#where(model, graph, r#where, dialect, None));
+                stmt.r#where(Query::r#where(model, graph, r#where, dialect, None, params));
             }
         }
         if let Some(additional_where) = additional_where {
@@ -439,18 +684,22 @@ impl Query {
             let key = order_by.keys().next().unwrap();
             let order = if order_by.values().next().unwrap().as_str().unwrap() == if negative_take { "desc" } else { "asc" }
                 { ">=" } else { "<=" };
-            let cursor_where = Query::where_item(&key, order, &format!("`c.{}`", key));
+            let escape = dialect.escape();
+            let cursor_where = Query::where_item(key, order, &format!("{escape}c.{}{escape}", key));
             if stmt.r#where.is_some() {
                 stmt.r#where(And(vec![stmt.r#where.as_ref().unwrap().clone(), cursor_where]).to_string(dialect));
             } else {
                 stmt.r#where(cursor_where);
             }
         }
+        let table_alias = if additional_left_join.is_some() { Some("t") } else { None };
         if let Some(additional_left_join) = additional_left_join {
             stmt.left_join(additional_left_join);
         }
         if let Some(order_bys) = order_by {
             stmt.order_by(Query::order_by(model, graph, order_bys, dialect, negative_take));
+        } else if let Some((field_name, term)) = Input::full_text_search(r#where) {
+            stmt.order_by(Query::full_text_relevance(model, field_name, term, dialect, table_alias, negative_take, params));
         } else if negative_take {
             let val = Self::default_desc_order(model);
             stmt.order_by(Query::order_by(model, graph, &val, dialect, false));
@@ -491,3 +740,101 @@ static SQL_AGGREGATE_MAP: Lazy<BTreeMap<&str, &str>> = Lazy::new(|| {
         "_max" => "MAX"
     }
 });
+
+#[cfg(test)]
+mod tests {
+    use serial_test::serial;
+    use crate::core::app::testing::sqlite_app;
+    use crate::teon;
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model Article {
+  @id @default($cuid)
+  id: String
+  title: String
+  body: String
+  @@fullTextIndex([.title, .body])
+}
+"#;
+
+    async fn titles(graph: &Graph, finder: Value) -> Vec<String> {
+        let articles: Vec<Object> = graph.find_many("Article", &finder).await.unwrap();
+        articles.iter().map(|a| a.get("title").unwrap()).collect()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn search_orders_matches_by_relevance() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        for (title, body) in [
+            ("Go", "A long article about concurrency which mentions rust once among many other words."),
+            ("Python", "Snakes and notebooks."),
+            ("Rust", "Rust ownership, rust lifetimes and rust traits."),
+        ] {
+            graph.create_object("Article", teon!({"title": title, "body": body})).await.unwrap().save().await.unwrap();
+        }
+        assert_eq!(titles(graph, teon!({"where": {"body": {"search": "rust"}}})).await, vec!["Rust", "Go"]);
+        assert_eq!(titles(graph, teon!({"where": {"title": {"search": "snakes"}}})).await, vec!["Python"]);
+        assert_eq!(titles(graph, teon!({"where": {"body": {"search": "rust", "not": "Rust ownership, rust lifetimes and rust traits."}}})).await, vec!["Go"]);
+        assert_eq!(titles(graph, teon!({"where": {"body": {"search": "rust"}}, "orderBy": [{"title": "asc"}]})).await, vec!["Go", "Rust"]);
+        assert_eq!(titles(graph, teon!({"where": {"body": {"search": "rust"}}, "take": 1})).await, vec!["Rust"]);
+        // operator characters are searched as words
+        assert_eq!(titles(graph, teon!({"where": {"body": {"search": "\"rust\" OR -(snakes*"}}})).await, Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn search_follows_updates_and_deletes() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        let article = graph.create_object("Article", teon!({"title": "Draft", "body": "Nothing yet."})).await.unwrap();
+        article.save().await.unwrap();
+        assert!(titles(graph, teon!({"where": {"body": {"search": "rust"}}})).await.is_empty());
+        article.set("body", "All about rust.").unwrap();
+        article.save().await.unwrap();
+        assert_eq!(titles(graph, teon!({"where": {"body": {"search": "rust"}}})).await, vec!["Draft"]);
+        article.delete().await.unwrap();
+        assert!(titles(graph, teon!({"where": {"body": {"search": "rust"}}})).await.is_empty());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn json_filters_match_values_at_paths() {
+        let app = sqlite_app(r#"
+model Tenant {
+  @id @default($cuid)
+  id: String
+  @unique
+  name: String
+  settings: Json?
+}
+"#).await;
+        let graph = app.graph();
+        for (name, settings) in [
+            ("a", teon!({"theme": "dark", "tags": ["x", "y"], "limits": {"users": 5}, "beta": true})),
+            ("b", teon!({"theme": "light", "tags": ["y"], "limits": {"users": 10}, "note": "hello world"})),
+            ("c", Value::Null),
+        ] {
+            graph.create_object("Tenant", teon!({"name": name, "settings": settings})).await.unwrap().save().await.unwrap();
+        }
+        async fn names(graph: &Graph, settings: Value) -> Vec<String> {
+            let tenants: Vec<Object> = graph.find_many("Tenant", &teon!({"where": {"settings": settings}, "orderBy": [{"name": "asc"}]})).await.unwrap();
+            tenants.iter().map(|t| t.get("name").unwrap()).collect()
+        }
+        assert_eq!(names(graph, teon!({"path": ["theme"], "equals": "dark"})).await, vec!["a"]);
+        assert_eq!(names(graph, teon!({"path": ["limits", "users"], "equals": 10})).await, vec!["b"]);
+        assert_eq!(names(graph, teon!({"path": ["limits"], "equals": {"users": 5}})).await, vec!["a"]);
+        assert_eq!(names(graph, teon!({"path": ["note"], "equals": null})).await, vec!["a", "c"]);
+        assert_eq!(names(graph, teon!({"path": ["beta"], "equals": true})).await, vec!["a"]);
+        assert_eq!(names(graph, teon!({"path": ["tags"], "arrayContains": "x"})).await, vec!["a"]);
+        assert_eq!(names(graph, teon!({"path": ["tags"], "arrayContains": "y"})).await, vec!["a", "b"]);
+        assert_eq!(names(graph, teon!({"path": ["note"], "stringContains": "world"})).await, vec!["b"]);
+        assert_eq!(names(graph, teon!({"path": ["theme"], "equals": "light", "stringContains": "dark"})).await, Vec::<String>::new());
+        let tenant: Object = graph.find_unique("Tenant", &teon!({"where": {"name": "b"}})).await.unwrap();
+        let settings: serde_json::Value = tenant.get_value("settings").unwrap().into();
+        assert_eq!(settings, serde_json::json!({"theme": "light", "tags": ["y"], "limits": {"users": 10}, "note": "hello world"}));
+    }
+}
+
@@ -198,6 +198,42 @@ impl ColumnDecoder {
         result
     }
 
+    pub(crate) fn decode_mssql_columns(columns: ResultSet) -> HashSet<SQLColumn> {
+        let mut result = hashset!{};
+        for column in columns {
+            let name = column.get("column_name").unwrap().to_string().unwrap();
+            let data_type = column.get("data_type").unwrap().to_string().unwrap();
+            let nullable = column.get("is_nullable").unwrap().to_string().unwrap() == "YES";
+            let r#type = match data_type.as_str() {
+                "char" | "varchar" | "nchar" | "nvarchar" => {
+                    match column.get("character_maximum_length").unwrap().as_integer() {
+                        Some(-1) | None => format!("{data_type}(max)"),
+                        Some(len) => format!("{data_type}({len})"),
+                    }
+                }
+                "decimal" | "numeric" => {
+                    let precision = column.get("numeric_precision").unwrap().as_integer().unwrap();
+                    let scale = column.get("numeric_scale").unwrap().as_integer().unwrap();
+                    format!("{data_type}({precision},{scale})")
+                }
+                "datetime2" => {
+                    let precision = column.get("datetime_precision").unwrap().as_integer().unwrap();
+                    format!("{data_type}({precision})")
+                }
+                _ => data_type,
+            };
+            result.insert(SQLColumn {
+                name,
+                r#type: SQLTypeDecoder::decode(&r#type, SQLDialect::MSSQL),
+                not_null: !nullable,
+                auto_increment: column.get("is_identity").unwrap().as_bool().unwrap_or(false),
+                default: None,
+                primary_key: column.get("is_primary").unwrap().as_bool().unwrap(),
+            });
+        }
+        result
+    }
+
     async fn psql_primary_field_name(conn: &PooledConnection, table_name: &str) -> Vec<String> {
         let sql = format!("SELECT a.attname
 FROM   pg_index i
@@ -76,6 +76,9 @@ impl ToSQLString for SQLColumn {
                 t
             };
             format!("\"{name}\" {t_with_auto_inc}{default}{not_null}{primary}")
+        } else if dialect == SQLDialect::MSSQL {
+            let identity = if self.auto_increment { " IDENTITY(1,1)" } else { "" };
+            format!("\"{name}\" {t}{identity}{default}{not_null}{primary}")
         } else {
             format!("`{name}` {t}{default}{not_null}{primary}{auto_inc}")
         }
@@ -9,7 +9,7 @@ pub enum SQLDialect {
 impl SQLDialect {
     pub(crate) fn escape(&self) -> &str {
         match self {
-            SQLDialect::PostgreSQL => "\"",
+            SQLDialect::PostgreSQL | SQLDialect::MSSQL => "\"",
             _ => "`",
         }
     }
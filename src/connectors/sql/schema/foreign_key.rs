@@ -0,0 +1,89 @@
+use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::connectors::sql::schema::value::encode::{SQLEscape, ToSQLString};
+use crate::core::model::Model;
+use crate::core::relation::delete_rule::DeleteRule;
+use crate::core::relation::update_rule::UpdateRule;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SQLForeignKey {
+    pub(crate) name: String,
+    pub(crate) table: String,
+    pub(crate) columns: Vec<String>,
+    pub(crate) referenced_table: String,
+    pub(crate) referenced_columns: Vec<String>,
+    pub(crate) on_delete: &'static str,
+    pub(crate) on_update: &'static str,
+}
+
+impl SQLForeignKey {
+
+    /// Foreign key constraints of a model's relations which declare both `onDelete` and
+    /// `onUpdate`. A constraint always restricts the rule it doesn't declare, while the default
+    /// rule leaves the related objects alone, so relations with a default rule don't get one.
+    pub(crate) fn from_model(model: &Model, models: &[Model], dialect: SQLDialect) -> Vec<SQLForeignKey> {
+        model.relations().iter().filter(|r| r.has_foreign_key_constraint()).filter_map(|relation| {
+            let referenced_model = models.iter().find(|m| m.name() == relation.model())?;
+            if referenced_model.r#virtual() { return None }
+            let columns: Vec<String> = relation.fields().iter().map(|f| model.field(f).unwrap().column_name().to_owned()).collect();
+            let referenced_columns: Vec<String> = relation.references().iter().map(|f| referenced_model.field(f).unwrap().column_name().to_owned()).collect();
+            Some(SQLForeignKey {
+                name: format!("{}_{}_fkey", model.table_name(), columns.join("_")),
+                table: model.table_name().to_owned(),
+                columns,
+                referenced_table: referenced_model.table_name().to_owned(),
+                referenced_columns,
+                on_delete: Self::delete_action(relation.delete_rule(), dialect)?,
+                on_update: Self::update_action(relation.update_rule(), dialect)?,
+            })
+        }).collect()
+    }
+
+    /// Whether a constraint name is generated by us. Constraints with other names are never
+    /// touched by migrations.
+    pub(crate) fn is_generated_name(name: &str) -> bool {
+        name.ends_with("_fkey")
+    }
+
+    fn delete_action(rule: DeleteRule, dialect: SQLDialect) -> Option<&'static str> {
+        match rule {
+            DeleteRule::Default => None,
+            DeleteRule::Nullify => Some("SET NULL"),
+            DeleteRule::Cascade => Some("CASCADE"),
+            DeleteRule::Deny => Some(if dialect == SQLDialect::MSSQL { "NO ACTION" } else { "RESTRICT" }),
+        }
+    }
+
+    fn update_action(rule: UpdateRule, dialect: SQLDialect) -> Option<&'static str> {
+        match rule {
+            UpdateRule::Default => None,
+            UpdateRule::Nullify => Some("SET NULL"),
+            UpdateRule::Cascade => Some("CASCADE"),
+            UpdateRule::Deny => Some(if dialect == SQLDialect::MSSQL { "NO ACTION" } else { "RESTRICT" }),
+        }
+    }
+
+    pub(crate) fn to_sql_add(&self, dialect: SQLDialect) -> String {
+        format!("ALTER TABLE {} ADD {}", self.table.as_str().escape(dialect), self.to_string(dialect))
+    }
+
+    pub(crate) fn to_sql_drop(&self, dialect: SQLDialect) -> String {
+        Self::drop_stmt(dialect, &self.table, &self.name)
+    }
+
+    pub(crate) fn drop_stmt(dialect: SQLDialect, table: &str, name: &str) -> String {
+        if dialect == SQLDialect::MySQL {
+            format!("ALTER TABLE {} DROP FOREIGN KEY {}", table.escape(dialect), name.escape(dialect))
+        } else {
+            format!("ALTER TABLE {} DROP CONSTRAINT {}", table.escape(dialect), name.escape(dialect))
+        }
+    }
+}
+
+impl ToSQLString for SQLForeignKey {
+    fn to_string(&self, dialect: SQLDialect) -> String {
+        let columns = self.columns.iter().map(|c| c.as_str().escape(dialect)).collect::<Vec<String>>().join(", ");
+        let referenced_columns = self.referenced_columns.iter().map(|c| c.as_str().escape(dialect)).collect::<Vec<String>>().join(", ");
+        format!("CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {}({}) ON DELETE {} ON UPDATE {}",
+                self.name.as_str().escape(dialect), columns, self.referenced_table.as_str().escape(dialect), referenced_columns, self.on_delete, self.on_update)
+    }
+}
@@ -0,0 +1,96 @@
+use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::connectors::sql::schema::value::encode::SQLEscape;
+use crate::core::model::Model;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SQLFullTextIndex {
+    pub(crate) name: String,
+    pub(crate) table: String,
+    pub(crate) columns: Vec<String>,
+}
+
+impl SQLFullTextIndex {
+
+    /// Full-text indices declared with `@@fullTextIndex` on a model.
+    pub(crate) fn from_model(model: &Model) -> Vec<SQLFullTextIndex> {
+        model.full_text_indices().iter().map(|fields| {
+            let columns: Vec<String> = fields.iter().map(|f| model.field(f).unwrap().column_name().to_owned()).collect();
+            SQLFullTextIndex {
+                name: format!("{}_{}_fts", model.table_name(), columns.join("_")),
+                table: model.table_name().to_owned(),
+                columns,
+            }
+        }).collect()
+    }
+
+    /// Whether an index name is generated by us. Indices with other names are never touched by
+    /// migrations.
+    pub(crate) fn is_generated_name(name: &str) -> bool {
+        name.ends_with("_fts")
+    }
+
+    /// The content table of a SQLite FTS5 table created by `to_sql_create`.
+    pub(crate) fn sqlite_content_table(create_sql: &str) -> Option<String> {
+        let start = create_sql.find("content='")? + "content='".len();
+        let len = create_sql[start..].find('\'')?;
+        Some(create_sql[start..start + len].to_owned())
+    }
+
+    pub(crate) fn to_sql_create(&self, dialect: SQLDialect) -> String {
+        let name = self.name.as_str().escape(dialect);
+        let table = self.table.as_str().escape(dialect);
+        let columns = self.columns.iter().map(|c| c.as_str().escape(dialect)).collect::<Vec<String>>();
+        match dialect {
+            SQLDialect::MySQL => format!("CREATE FULLTEXT INDEX {} ON {}({})", name, table, columns.join(", ")),
+            SQLDialect::PostgreSQL => format!("CREATE INDEX {} ON {} USING GIN ({})", name, table, Self::psql_document(&columns)),
+            SQLDialect::SQLite => {
+                // an external content table kept in sync by triggers
+                let new_values = columns.iter().map(|c| format!("new.{c}")).collect::<Vec<String>>().join(", ");
+                let old_values = columns.iter().map(|c| format!("old.{c}")).collect::<Vec<String>>().join(", ");
+                let columns = columns.join(", ");
+                let insert = format!("INSERT INTO {name}(rowid, {columns}) VALUES (new.rowid, {new_values});");
+                let delete = format!("INSERT INTO {name}({name}, rowid, {columns}) VALUES ('delete', old.rowid, {old_values});");
+                [
+                    format!("CREATE VIRTUAL TABLE {name} USING fts5({columns}, content='{}', content_rowid='rowid')", self.table),
+                    format!("CREATE TRIGGER {} AFTER INSERT ON {table} BEGIN {insert} END", self.trigger_name("ai")),
+                    format!("CREATE TRIGGER {} AFTER DELETE ON {table} BEGIN {delete} END", self.trigger_name("ad")),
+                    format!("CREATE TRIGGER {} AFTER UPDATE ON {table} BEGIN {delete} {insert} END", self.trigger_name("au")),
+                    format!("INSERT INTO {name}({name}) VALUES ('rebuild')"),
+                ].join(";\n")
+            }
+            // @@fullTextIndex is rejected on SQL Server by the resolver
+            SQLDialect::MSSQL => unreachable!("Full-text indices are not supported on SQL Server."),
+        }
+    }
+
+    pub(crate) fn to_sql_drop(&self, dialect: SQLDialect) -> String {
+        Self::drop_stmt(dialect, &self.table, &self.name)
+    }
+
+    pub(crate) fn drop_stmt(dialect: SQLDialect, table: &str, name: &str) -> String {
+        match dialect {
+            SQLDialect::MySQL => format!("DROP INDEX {} ON {}", name.escape(dialect), table.escape(dialect)),
+            SQLDialect::PostgreSQL => format!("DROP INDEX {}", name.escape(dialect)),
+            SQLDialect::SQLite => {
+                let mut stmts: Vec<String> = ["ai", "ad", "au"].iter().map(|s| {
+                    format!("DROP TRIGGER IF EXISTS {}", format!("{name}_{s}").as_str().escape(dialect))
+                }).collect();
+                stmts.push(format!("DROP TABLE IF EXISTS {}", name.escape(dialect)));
+                stmts.join(";\n")
+            }
+            // @@fullTextIndex is rejected on SQL Server by the resolver
+            SQLDialect::MSSQL => unreachable!("Full-text indices are not supported on SQL Server."),
+        }
+    }
+
+    /// The document searched in PostgreSQL. Queries must use the same expression for the index
+    /// to be used.
+    pub(crate) fn psql_document(escaped_columns: &[String]) -> String {
+        let columns = escaped_columns.iter().map(|c| format!("coalesce({c}, '')")).collect::<Vec<String>>().join(" || ' ' || ");
+        format!("to_tsvector('simple', {columns})")
+    }
+
+    fn trigger_name(&self, suffix: &str) -> String {
+        format!("{}_{}", self.name, suffix).as_str().escape(SQLDialect::SQLite)
+    }
+}
@@ -39,6 +39,7 @@ fn mysql_type_to_database_type(r#type: &str) -> DatabaseType {
                 "varchar" => DatabaseType::VarChar { m: arg.map(|a| u16::from_str(a).unwrap()).unwrap(), n: None, c: None },
                 "date" => DatabaseType::Date,
                 "datetime" => DatabaseType::DateTime(u8::from_str(arg.unwrap()).unwrap()),
+                "json" => DatabaseType::Json,
                 "decimal" => {
                     if let Some(args) = arg {
                         let args = args.split(",").into_iter().collect::<Vec<&str>>();
@@ -66,6 +67,7 @@ fn postgresql_type_to_database_type(r#type: &str) -> DatabaseType {
         "real" | "float4" => DatabaseType::Real,
         "date" => DatabaseType::Date,
         "numeric" => DatabaseType::Decimal { m: Some(65), d: Some(30) },
+        "jsonb" => DatabaseType::Json,
         _ => if lower_str.starts_with("array|") {
             let inner = &lower_str[6..];
             DatabaseType::Vec(Box::new(postgresql_type_to_database_type(inner)))
@@ -105,7 +107,53 @@ fn sqlite_type_to_database_type(r#type: &str) -> DatabaseType {
 }
 
 fn mssql_type_to_database_type(r#type: &str) -> DatabaseType {
-    match r#type.to_lowercase().as_str() {
-        _ => panic!("Unhandled database type.")
+    let r#type_string = r#type.to_lowercase();
+    let r#type: &str = r#type_string.as_str();
+    let regex = Regex::new("([^ \\(\\)]+)(\\((.+)\\))?").unwrap();
+    match regex.captures(r#type) {
+        None => panic!("Unhandled database type '{}' '{}'.", r#type, regex),
+        Some(captures) => {
+            let name = captures.get(1).unwrap().as_str();
+            let arg = captures.get(3).map(|m| m.as_str());
+            match name {
+                "bit" => DatabaseType::Bool,
+                // SQL Server's tinyint is unsigned by itself, `TINYINT UNSIGNED` isn't valid T-SQL
+                "tinyint" => DatabaseType::TinyInt { m: None, u: false },
+                "smallint" => DatabaseType::SmallInt { m: None, u: false },
+                "int" => DatabaseType::Int { m: None, u: false },
+                "bigint" => DatabaseType::BigInt { m: None, u: false },
+                "real" => DatabaseType::Real,
+                "float" => DatabaseType::Double { m: None, d: None },
+                "date" => DatabaseType::Date,
+                "datetime2" => DatabaseType::DateTime(arg.map(|a| u8::from_str(a).unwrap()).unwrap_or(7)),
+                "char" => DatabaseType::Char { m: arg.map(|a| u8::from_str(a).unwrap()), n: None, c: None },
+                "varchar" => DatabaseType::VarChar { m: arg.map(|a| u16::from_str(a).unwrap()).unwrap(), n: None, c: None },
+                "nvarchar" => DatabaseType::NVarChar { m: arg.filter(|a| *a != "max").map(|a| u16::from_str(a).unwrap()) },
+                "text" | "ntext" => DatabaseType::Text { m: None, n: None, c: None },
+                "decimal" | "numeric" => {
+                    if let Some(args) = arg {
+                        let args = args.split(",").collect::<Vec<&str>>();
+                        DatabaseType::Decimal { m: Some(args.first().unwrap().trim().parse().unwrap()), d: Some(args.get(1).unwrap().trim().parse().unwrap()) }
+                    } else {
+                        DatabaseType::Decimal { m: None, d: None }
+                    }
+                }
+                _ => panic!("Unhandled type '{}' '{:?}'.", name, arg)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connectors::sql::schema::value::encode::ToSQLString;
+    use super::*;
+
+    #[test]
+    fn mssql_types_encode_as_they_are_decoded() {
+        for r#type in ["tinyint", "smallint", "int", "bigint", "bit", "real", "date", "nvarchar(450)", "nvarchar(max)"] {
+            let decoded = SQLTypeDecoder::decode(r#type, SQLDialect::MSSQL);
+            assert_eq!(decoded.to_string(SQLDialect::MSSQL).to_lowercase(), r#type);
+        }
     }
 }
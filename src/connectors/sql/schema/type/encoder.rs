@@ -57,6 +57,8 @@ impl ToSQLString for DatabaseType {
             DatabaseType::Double { m: _m, d: _d } => {
                 if dialect == SQLDialect::PostgreSQL {
                     "DOUBLE PRECISION".to_string()
+                } else if dialect == SQLDialect::MSSQL {
+                    "FLOAT(53)".to_string()
                 } else {
                     "DOUBLE".to_string()
                 }
@@ -69,7 +71,11 @@ impl ToSQLString for DatabaseType {
                 }
             }
             DatabaseType::Date => "DATE".to_string(),
-            DatabaseType::DateTime(fsp) => format!("DATETIME({fsp})"),
+            DatabaseType::DateTime(fsp) => if dialect == SQLDialect::MSSQL {
+                format!("DATETIME2({fsp})")
+            } else {
+                format!("DATETIME({fsp})")
+            },
             DatabaseType::Timestamp { p, z } => {
                 if dialect == SQLDialect::PostgreSQL {
                     let tzinfo = if *z { " WITH TIME ZONE" } else { "" };
@@ -144,6 +150,14 @@ impl ToSQLString for DatabaseType {
                     "TEXT".to_string()
                 }
             }
+            DatabaseType::NVarChar { m } => {
+                let arg = if let Some(m) = m {
+                    Cow::Owned(format!("({m})"))
+                } else {
+                    Cow::Borrowed("(MAX)")
+                };
+                format!("NVARCHAR{arg}")
+            }
             DatabaseType::Binary(l) => format!("BINARY({l})"),
             DatabaseType::VarBinary(l) => format!("VARBINARY({l})"),
             DatabaseType::TinyBlob => "TINYBLOB".to_string(),
@@ -159,6 +173,12 @@ impl ToSQLString for DatabaseType {
             } else {
                 panic!("Array is only supported for PostgreSQL.")
             }
+            DatabaseType::Json => match dialect {
+                SQLDialect::MySQL => "JSON".to_string(),
+                SQLDialect::PostgreSQL => "JSONB".to_string(),
+                SQLDialect::SQLite => "TEXT".to_string(),
+                SQLDialect::MSSQL => "NVARCHAR(MAX)".to_string(),
+            }
         }
     }
 }
@@ -19,7 +19,19 @@ impl ToDatabaseType for FieldType {
 
 fn default_database_type_mssql(field_type: &FieldType) -> DatabaseType {
     match field_type {
-        _ => panic!("Unhandled."),
+        FieldType::Bool => DatabaseType::Bool,
+        FieldType::I32 => DatabaseType::Int { m: None, u: false },
+        FieldType::I64 => DatabaseType::BigInt { m: None, u: false },
+        FieldType::F32 => DatabaseType::Real,
+        FieldType::F64 => DatabaseType::Double { m: None, d: None },
+        FieldType::String => DatabaseType::NVarChar { m: Some(450) },
+        FieldType::Date => DatabaseType::Date,
+        FieldType::DateTime => DatabaseType::DateTime(3),
+        FieldType::Decimal => DatabaseType::Decimal { m: Some(32), d: Some(16) },
+        FieldType::Enum(_) => DatabaseType::NVarChar { m: Some(450) },
+        FieldType::Json => DatabaseType::NVarChar { m: None },
+        // the schema resolver reports these field types on SQL Server connectors
+        _ => unreachable!("SQL Server doesn't support {:?} fields.", field_type),
     }
 }
 
@@ -38,6 +50,7 @@ fn default_database_type_mysql(field_type: &FieldType) -> DatabaseType {
         FieldType::Vec(_) => panic!(),
         FieldType::HashMap(_) => panic!(),
         FieldType::BTreeMap(_) => panic!(),
+        FieldType::Json => DatabaseType::Json,
         FieldType::Object(_) => panic!(),
         _ => panic!(),
     }
@@ -58,6 +71,7 @@ fn default_database_type_postgresql(field_type: &FieldType) -> DatabaseType {
         FieldType::Vec(inner) => DatabaseType::Vec(Box::new(default_database_type_postgresql(inner.field_type()))),
         FieldType::HashMap(_) => panic!(),
         FieldType::BTreeMap(_) => panic!(),
+        FieldType::Json => DatabaseType::Json,
         FieldType::Object(_) => panic!(),
         _ => panic!(),
     }
@@ -78,7 +92,20 @@ fn default_database_type_sqlite(field_type: &FieldType) -> DatabaseType {
         FieldType::Vec(_) => panic!(),
         FieldType::HashMap(_) => panic!(),
         FieldType::BTreeMap(_) => panic!(),
+        FieldType::Json => DatabaseType::Text { m: None, n: None, c: None },
         FieldType::Object(_) => panic!(),
         _ => panic!(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::connectors::sql::schema::value::encode::ToSQLString;
+    use super::*;
+
+    #[test]
+    fn mssql_stores_enums_as_nvarchar() {
+        let database_type = FieldType::Enum("Sex".to_owned()).to_database_type(SQLDialect::MSSQL);
+        assert_eq!(database_type.to_string(SQLDialect::MSSQL), "NVARCHAR(450)");
+    }
+}
@@ -65,6 +65,12 @@ impl RowDecoder {
                     None => Value::Null,
                 }
             }
+            quaint_forked::Value::Json(j) => {
+                match j {
+                    Some(j) => j.into(),
+                    None => Value::Null,
+                }
+            }
             _ => unreachable!()
         }
     }
@@ -122,7 +128,8 @@ impl RowDecoder {
             }
         }
         if r#type.is_int32() {
-            if let Some(v) = value.as_i32() {
+            // SQLite returns expressions like MIN and MAX as 64-bit integers
+            if let Some(v) = value.as_i32().or_else(|| value.as_i64().and_then(|v| i32::try_from(v).ok())) {
                 return Value::I32(v);
             } else {
                 return Value::Null;
@@ -209,6 +216,16 @@ impl RowDecoder {
                 return Value::Null;
             }
         }
+        if r#type.is_json() {
+            // SQLite and MSSQL return the stored text
+            if let Some(json) = value.as_json() {
+                return json.into();
+            } else if let Some(text) = value.as_str() {
+                return serde_json::from_str::<serde_json::Value>(text).map(|json| (&json).into()).unwrap_or(Value::Null);
+            } else {
+                return Value::Null;
+            }
+        }
         panic!("Unhandled database when decoding type.")
     }
 
@@ -1,111 +1,16 @@
+use std::borrow::Cow;
 use bigdecimal::BigDecimal;
 use chrono::{NaiveDate, Utc, DateTime, SecondsFormat};
 use itertools::Itertools;
+use quaint_forked::ast::Value as QuaintValue;
 use crate::connectors::sql::schema::dialect::SQLDialect;
 use crate::core::field::r#type::{FieldType, FieldTypeOwner};
-use crate::prelude::{Graph, Value};
+use crate::prelude::Value;
 
 pub trait ToSQLString {
     fn to_string(&self, dialect: SQLDialect) -> String;
 }
 
-pub trait TypeOrNull {
-    fn or_null(&self, optional: bool) -> String;
-}
-
-impl TypeOrNull for &str {
-    fn or_null(&self, optional: bool) -> String {
-        self.to_string() + if optional { " or null" } else { "" }
-    }
-}
-
-pub(crate) trait ValueToSQLString {
-    fn to_sql_string<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph) -> String;
-    fn to_sql_string_array_arg<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph) -> String;
-}
-
-impl ValueToSQLString for Value {
-    fn to_sql_string<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph) -> String {
-        if optional {
-            if self.is_null() {
-                return "NULL".to_owned()
-            }
-        }
-        match r#type {
-            #[cfg(feature = "data-source-mongodb")]
-            FieldType::ObjectId => panic!("SQL doesn't support `ObjectId`."),
-            FieldType::String => ToSQLInput::to_sql_input(&self.as_str().unwrap()),
-            FieldType::Bool => self.as_bool().unwrap().to_sql_input(),
-            FieldType::I32 | FieldType::I64 |
-            FieldType::F32 | FieldType::F64 => if let Some(val) = self.as_f64() {
-                val.to_string()
-            } else if let Some(val) = self.as_i64() {
-                val.to_string()
-            } else {
-                panic!("Uncoded number.")
-            }
-            FieldType::Enum(_) => ToSQLInput::to_sql_input(&self.as_str().unwrap()),
-            FieldType::Vec(element_field) => {
-                let val = self.as_vec().unwrap();
-                let mut result: Vec<String> = vec![];
-                for (_i, v) in val.iter().enumerate() {
-                    result.push(v.to_sql_string(element_field.field_type(), element_field.is_optional(), graph));
-                }
-                result.join(", ").wrap_in_array()
-            }
-            FieldType::Date => self.as_date().unwrap().to_string().to_sql_input(),
-            FieldType::DateTime => self.as_datetime().unwrap().to_string().to_sql_input(),
-            FieldType::Decimal => self.as_decimal().unwrap().to_string().to_sql_input(),
-            _ => { panic!() }
-        }
-    }
-
-    fn to_sql_string_array_arg<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph) -> String {
-        if optional {
-            if self.is_null() {
-                return "NULL".to_owned()
-            }
-        }
-        match r#type {
-            #[cfg(feature = "data-source-mongodb")]
-            FieldType::ObjectId => panic!("SQL doesn't support `ObjectId`."),
-            FieldType::String => ToSQLInputDialect::to_sql_input(&self.as_str().unwrap(), SQLDialect::PostgreSQL),
-            FieldType::Bool => self.as_bool().unwrap().to_sql_input(),
-            FieldType::I32 | FieldType::I64 |
-            FieldType::F32 | FieldType::F64 => if let Some(val) = self.as_f64() {
-                val.to_string()
-            } else if let Some(val) = self.as_i64() {
-                val.to_string()
-            } else {
-                panic!("Uncoded number.")
-            }
-            FieldType::Enum(_) => ToSQLInput::to_sql_input(&self.as_str().unwrap()),
-            FieldType::Vec(element_field) => {
-                let val = self.as_vec().unwrap();
-                let mut result: Vec<String> = vec![];
-                for (_i, v) in val.iter().enumerate() {
-                    result.push(v.to_sql_string_array_arg(element_field.field_type(), element_field.is_optional(), graph));
-                }
-                result.join(",").wrap_in_array()
-            }
-            FieldType::Date => self.as_date().unwrap().to_string(),
-            FieldType::DateTime => self.as_datetime().unwrap().to_string(),
-            FieldType::Decimal => self.as_decimal().unwrap().to_string(),
-            _ => { panic!() }
-        }
-    }
-}
-
-impl ValueToSQLString for &Value {
-    fn to_sql_string<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph) -> String {
-        (*self).to_sql_string(r#type, optional, graph)
-    }
-
-    fn to_sql_string_array_arg<'a>(&self, r#type: &FieldType, optional: bool, graph: &Graph) -> String {
-        (*self).to_sql_string_array_arg(r#type, optional, graph)
-    }
-}
-
 impl ToSQLString for Value {
     fn to_string(&self, dialect: SQLDialect) -> String {
         match self {
@@ -115,7 +20,11 @@ impl ToSQLString for Value {
             Value::I64(i) => i.to_string(),
             Value::F32(i) => i.to_string(),
             Value::F64(i) => i.to_string(),
-            Value::Bool(b) => b.to_sql_input(),
+            Value::Bool(b) => if dialect == SQLDialect::MSSQL {
+                (if *b { "1" } else { "0" }).to_owned()
+            } else {
+                b.to_sql_input()
+            },
             Value::Date(d) => d.to_sql_input(dialect),
             Value::DateTime(d) => d.to_sql_input(dialect),
             Value::Decimal(d) => d.to_sql_input(dialect),
@@ -125,34 +34,39 @@ impl ToSQLString for Value {
     }
 }
 
-pub(crate) trait PSQLArrayToSQLString {
-    fn to_string_with_ft(&self, dialect: SQLDialect, field_type: &FieldType) -> String;
-}
-
-fn field_type_to_psql(field_type: &FieldType) -> &'static str {
-    match field_type {
-        FieldType::Decimal => "decimal",
-        FieldType::I32 | FieldType::I64 => "integer",
-        FieldType::F32 | FieldType::F64 => "double precision",
-        FieldType::String => "text",
-        FieldType::Bool => "boolean",
-        FieldType::Date => "date",
-        FieldType::DateTime => "timestamp",
-        _ => unreachable!(),
-    }
+pub(crate) trait ToSQLParam {
+    fn to_sql_param(&self, r#type: &FieldType, dialect: SQLDialect) -> QuaintValue<'static>;
 }
 
-impl PSQLArrayToSQLString for Value {
-    fn to_string_with_ft(&self, dialect: SQLDialect, field_type: &FieldType) -> String {
-        match self {
-            Value::Vec(values) => if values.is_empty() {
-                format!("array[]::{}[]", field_type_to_psql(field_type.element_field().unwrap().field_type()))
+impl ToSQLParam for Value {
+    fn to_sql_param(&self, r#type: &FieldType, dialect: SQLDialect) -> QuaintValue<'static> {
+        match r#type {
+            #[cfg(feature = "data-source-mongodb")]
+            FieldType::ObjectId => panic!("SQL doesn't support `ObjectId`."),
+            FieldType::String | FieldType::Enum(_) => QuaintValue::Text(self.as_str().map(|s| Cow::Owned(s.to_owned()))),
+            FieldType::Bool => QuaintValue::Boolean(self.as_bool()),
+            FieldType::I32 => QuaintValue::Int32(self.as_i64().map(|v| v as i32)),
+            FieldType::I64 => QuaintValue::Int64(self.as_i64()),
+            FieldType::F32 => QuaintValue::Float(self.as_f64().map(|v| v as f32)),
+            FieldType::F64 => QuaintValue::Double(self.as_f64()),
+            FieldType::Decimal => QuaintValue::Numeric(self.as_decimal()),
+            // SQLite stores dates and datetimes as text
+            FieldType::Date => if dialect == SQLDialect::SQLite {
+                QuaintValue::Text(self.as_date().map(|d| Cow::Owned(d.format("%Y-%m-%d").to_string())))
+            } else {
+                QuaintValue::Date(self.as_date().cloned())
+            },
+            FieldType::DateTime => if dialect == SQLDialect::SQLite {
+                QuaintValue::Text(self.as_datetime().map(|d| Cow::Owned(d.to_rfc3339_opts(SecondsFormat::Millis, true))))
             } else {
-                format!("array[{}]", values.iter().map(|v| {
-                    v.to_string(dialect)
-                }).join(","))
+                QuaintValue::DateTime(self.as_datetime().cloned())
             },
-            _ => self.to_string(dialect),
+            FieldType::Vec(element_field) => QuaintValue::Array(self.as_vec().map(|values| {
+                values.iter().map(|v| v.to_sql_param(element_field.field_type(), dialect)).collect()
+            })),
+            // SQLite and MSSQL bind this as text
+            FieldType::Json => QuaintValue::Json(if self.is_null() { None } else { Some(self.into()) }),
+            _ => panic!("Unhandled SQL parameter type."),
         }
     }
 }
@@ -284,46 +198,9 @@ impl IfIMode for String {
     }
 }
 
-pub trait ToLike {
-    fn to_like(&self, left: bool, right: bool) -> String;
-}
-
-impl ToLike for &str {
-    fn to_like(&self, left: bool, right: bool) -> String {
-        let mut retval = "".to_owned();
-        retval.push(self.chars().nth(0).unwrap());
-        if left {
-            retval.push('%');
-        }
-        retval += &self[1..self.len() - 1];
-        if right {
-            retval.push('%');
-        }
-        retval.push(self.chars().nth(self.len() - 1).unwrap());
-        retval
-    }
-}
-
-impl ToLike for String {
-    fn to_like(&self, left: bool, right: bool) -> String {
-        self.as_str().to_like(left, right)
-    }
-}
-
-pub trait WrapInArray {
-    fn wrap_in_array(&self) -> String;
-}
-
-impl WrapInArray for &str {
-    fn wrap_in_array(&self) -> String {
-        "\'{".to_owned() + self + "}\'"
-    }
-}
-
-impl WrapInArray for String {
-    fn wrap_in_array(&self) -> String {
-        self.as_str().wrap_in_array()
-    }
+/// Quote `name` as a SQL Server identifier, doubling any `]` in it.
+pub(crate) fn mssql_quote(name: &str) -> String {
+    format!("[{}]", name.replace(']', "]]"))
 }
 
 pub trait SQLEscape {
@@ -334,7 +211,7 @@ impl SQLEscape for &str {
     fn escape(&self, dialect: SQLDialect) -> String {
         match dialect {
             SQLDialect::MySQL => format!("`{}`", self),
-            SQLDialect::PostgreSQL => format!("\"{}\"", self),
+            SQLDialect::PostgreSQL | SQLDialect::MSSQL => format!("\"{}\"", self),
             _ => format!("`{}`", self),
         }
     }
@@ -11,7 +11,7 @@ impl ToSQLString for SQLAlterTableAddStatement {
     fn to_string(&self, dialect: SQLDialect) -> String {
         let table = &self.table;
         let def = self.column_def.to_string(dialect);
-        let escape = if dialect == SQLDialect::PostgreSQL { "\"" } else { "`" };
+        let escape = dialect.escape();
         format!("ALTER TABLE {escape}{table}{escape} ADD {def}")
     }
 }
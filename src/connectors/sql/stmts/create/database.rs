@@ -1,5 +1,5 @@
 use crate::connectors::sql::schema::dialect::SQLDialect;
-use crate::connectors::sql::schema::value::encode::ToSQLString;
+use crate::connectors::sql::schema::value::encode::{mssql_quote, ToSQLString};
 
 pub(crate) struct SQLCreateDatabaseStatement {
     pub(crate) database: String,
@@ -19,8 +19,21 @@ impl ToSQLString for SQLCreateDatabaseStatement {
         let if_not_exists = if self.if_not_exists { " IF NOT EXISTS" } else { "" };
         if dialect == SQLDialect::PostgreSQL {
             format!("CREATE DATABASE{if_not_exists} {database};")
+        } else if dialect == SQLDialect::MSSQL {
+            format!("CREATE DATABASE {};", mssql_quote(database))
         } else {
             format!("CREATE DATABASE{if_not_exists} `{database}`;")
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mssql_database_names_are_bracket_quoted() {
+        let stmt = SQLCreateDatabaseStatement { database: "my]db".to_owned(), if_not_exists: false };
+        assert_eq!(stmt.to_string(SQLDialect::MSSQL), "CREATE DATABASE [my]]db];");
+    }
+}
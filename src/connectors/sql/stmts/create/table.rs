@@ -1,12 +1,14 @@
 use crate::connectors::sql::schema::column::SQLColumn;
 use crate::connectors::sql::schema::dialect::SQLDialect;
+use crate::connectors::sql::schema::foreign_key::SQLForeignKey;
 use crate::connectors::sql::schema::value::encode::ToSQLString;
 
 
 pub(crate) struct SQLCreateTableStatement {
     pub(crate) table: String,
     pub(crate) if_not_exists: bool,
-    pub(crate) columns: Vec<SQLColumn>
+    pub(crate) columns: Vec<SQLColumn>,
+    pub(crate) foreign_keys: Vec<SQLForeignKey>,
 }
 
 impl SQLCreateTableStatement {
@@ -24,19 +26,40 @@ impl SQLCreateTableStatement {
         self.columns.extend(defs);
         self
     }
+
+    pub(crate) fn foreign_keys(&mut self, foreign_keys: Vec<SQLForeignKey>) -> &mut Self {
+        self.foreign_keys.extend(foreign_keys);
+        self
+    }
 }
 
 impl ToSQLString for SQLCreateTableStatement {
     fn to_string(&self, dialect: SQLDialect) -> String {
-        let if_not_exists = if self.if_not_exists { " IF NOT EXISTS" } else { "" };
+        // SQL Server doesn't support `IF NOT EXISTS` here
+        let if_not_exists = if self.if_not_exists && dialect != SQLDialect::MSSQL { " IF NOT EXISTS" } else { "" };
         let table_name = &self.table;
         let columns = self.columns.iter().map(|c| {
             c.to_string(dialect)
-        }).collect::<Vec<String>>().join(", ");
-        if dialect == SQLDialect::PostgreSQL {
+        }).chain(self.foreign_keys.iter().map(|f| {
+            f.to_string(dialect)
+        })).collect::<Vec<String>>().join(", ");
+        if dialect == SQLDialect::PostgreSQL || dialect == SQLDialect::MSSQL {
             format!("CREATE TABLE{if_not_exists} \"{table_name}\"( {columns} );")
         } else {
             format!("CREATE TABLE{if_not_exists} `{table_name}`( {columns} );")
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn if_not_exists_is_omitted_on_mssql() {
+        let mut stmt = SQLCreateTableStatement { table: "users".to_owned(), if_not_exists: false, columns: vec![], foreign_keys: vec![] };
+        stmt.if_not_exists();
+        assert!(stmt.to_string(SQLDialect::MySQL).starts_with("CREATE TABLE IF NOT EXISTS"));
+        assert!(!stmt.to_string(SQLDialect::MSSQL).contains("IF NOT EXISTS"));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::connectors::sql::schema::dialect::SQLDialect;
-use crate::connectors::sql::schema::value::encode::ToSQLString;
+use crate::connectors::sql::schema::value::encode::{mssql_quote, ToSQLString};
 
 pub(crate) struct SQLDropDatabaseStatement {
     pub(crate) database: String,
@@ -14,9 +14,25 @@ impl SQLDropDatabaseStatement {
 }
 
 impl ToSQLString for SQLDropDatabaseStatement {
-    fn to_string(&self, _dialect: SQLDialect) -> String {
+    fn to_string(&self, dialect: SQLDialect) -> String {
         let database = &self.database;
         let if_exists = if self.if_exists { " IF EXISTS" } else { "" };
-        format!("DROP DATABASE{if_exists} `{database}`;")
+        if dialect == SQLDialect::MSSQL {
+            return format!("DROP DATABASE{if_exists} {};", mssql_quote(database));
+        }
+        let escape = dialect.escape();
+        format!("DROP DATABASE{if_exists} {escape}{database}{escape};")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mssql_database_names_are_bracket_quoted() {
+        let stmt = SQLDropDatabaseStatement { database: "my]db".to_owned(), if_exists: true };
+        assert_eq!(stmt.to_string(SQLDialect::MSSQL), "DROP DATABASE IF EXISTS [my]]db];");
+        assert_eq!(stmt.to_string(SQLDialect::MySQL), "DROP DATABASE IF EXISTS `my]db`;");
     }
 }
@@ -19,6 +19,7 @@ pub mod alter_table;
 pub mod select;
 pub mod update;
 pub mod delete_from;
+pub mod params;
 
 pub(crate) struct SQL { }
 
@@ -0,0 +1,108 @@
+use quaint_forked::ast::Value as QuaintValue;
+use crate::connectors::sql::schema::dialect::SQLDialect;
+
+const MARKER: char = '\u{1}';
+
+/// Values bound to a statement instead of being spliced into its text.
+///
+/// Builders call `push` for each value and put the returned marker into the statement. Since
+/// fragments are not always built in the order they appear in the final statement, `finish`
+/// replaces the markers with the dialect's placeholders in textual order and reorders the
+/// values to match.
+pub(crate) struct SQLParams {
+    values: Vec<QuaintValue<'static>>,
+}
+
+impl SQLParams {
+
+    pub(crate) fn new() -> Self {
+        Self { values: vec![] }
+    }
+
+    pub(crate) fn push(&mut self, value: QuaintValue<'static>) -> String {
+        self.values.push(value);
+        format!("{MARKER}{}{MARKER}", self.values.len() - 1)
+    }
+
+    pub(crate) fn finish(self, sql: &str, dialect: SQLDialect) -> (String, Vec<QuaintValue<'static>>) {
+        let mut values: Vec<QuaintValue<'static>> = Vec::with_capacity(self.values.len());
+        let mut result = String::with_capacity(sql.len());
+        let mut chars = sql.chars();
+        while let Some(ch) = chars.next() {
+            if ch != MARKER {
+                result.push(ch);
+                continue
+            }
+            let index: usize = chars.by_ref().take_while(|c| *c != MARKER).collect::<String>().parse().unwrap();
+            values.push(self.values[index].clone());
+            result.push_str(&Self::placeholder(dialect, values.len()));
+        }
+        (result, values)
+    }
+
+    fn placeholder(dialect: SQLDialect, position: usize) -> String {
+        match dialect {
+            SQLDialect::PostgreSQL => format!("${position}"),
+            SQLDialect::MSSQL => format!("@P{position}"),
+            _ => "?".to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(value: &str) -> QuaintValue<'static> {
+        QuaintValue::text(value.to_owned())
+    }
+
+    #[test]
+    fn placeholders_follow_the_dialect() {
+        for (dialect, expected) in [
+            (SQLDialect::PostgreSQL, "SELECT * FROM t WHERE a = $1 AND b = $2"),
+            (SQLDialect::MSSQL, "SELECT * FROM t WHERE a = @P1 AND b = @P2"),
+            (SQLDialect::MySQL, "SELECT * FROM t WHERE a = ? AND b = ?"),
+            (SQLDialect::SQLite, "SELECT * FROM t WHERE a = ? AND b = ?"),
+        ] {
+            let mut params = SQLParams::new();
+            let sql = format!("SELECT * FROM t WHERE a = {} AND b = {}", params.push(text("a")), params.push(text("b")));
+            assert_eq!(params.finish(&sql, dialect), (expected.to_owned(), vec![text("a"), text("b")]));
+        }
+    }
+
+    #[test]
+    fn values_are_reordered_to_match_the_statement() {
+        for (dialect, expected) in [
+            (SQLDialect::PostgreSQL, "SELECT * FROM t WHERE a = $1 LIMIT $2"),
+            (SQLDialect::MSSQL, "SELECT * FROM t WHERE a = @P1 LIMIT @P2"),
+            (SQLDialect::SQLite, "SELECT * FROM t WHERE a = ? LIMIT ?"),
+        ] {
+            let mut params = SQLParams::new();
+            let limit = params.push(QuaintValue::int64(10));
+            let r#where = format!("a = {}", params.push(text("a")));
+            let sql = format!("SELECT * FROM t WHERE {where} LIMIT {limit}");
+            assert_eq!(params.finish(&sql, dialect), (expected.to_owned(), vec![text("a"), QuaintValue::int64(10)]));
+        }
+    }
+
+    #[test]
+    fn nested_fragments_are_numbered_in_textual_order() {
+        let mut params = SQLParams::new();
+        let inner = format!("(SELECT id FROM u WHERE name = {})", params.push(text("inner")));
+        let sql = format!("SELECT * FROM t WHERE a = {} AND id IN {inner} AND b = {}", params.push(text("a")), params.push(text("b")));
+        let (sql, values) = params.finish(&sql, SQLDialect::PostgreSQL);
+        assert_eq!(sql, "SELECT * FROM t WHERE a = $1 AND id IN (SELECT id FROM u WHERE name = $2) AND b = $3");
+        assert_eq!(values, vec![text("a"), text("inner"), text("b")]);
+    }
+
+    #[test]
+    fn a_value_used_twice_is_bound_twice() {
+        let mut params = SQLParams::new();
+        let value = params.push(text("a"));
+        let sql = format!("SELECT * FROM t WHERE a = {value} OR b = {value}");
+        let (sql, values) = params.finish(&sql, SQLDialect::MSSQL);
+        assert_eq!(sql, "SELECT * FROM t WHERE a = @P1 OR b = @P2");
+        assert_eq!(values, vec![text("a"), text("a")]);
+    }
+}
@@ -61,12 +61,17 @@ impl<'a> ToSQLString for SQLSelectStatement<'a> {
         };
         let order_by = if let Some(order_by) = &self.order_by {
             " ORDER BY ".to_owned() + order_by
+        } else if dialect == SQLDialect::MSSQL && self.limit.is_some() {
+            // OFFSET FETCH requires an ORDER BY clause
+            " ORDER BY (SELECT NULL)".to_owned()
         } else {
             "".to_owned()
         };
         let limit = if let Some(limit) = &self.limit {
             if dialect == SQLDialect::PostgreSQL {
                 format!(" LIMIT {} OFFSET {}", limit.0, limit.1)
+            } else if dialect == SQLDialect::MSSQL {
+                format!(" OFFSET {} ROWS FETCH NEXT {} ROWS ONLY", limit.1, limit.0)
             } else {
                 format!(" LIMIT {},{}", limit.1, limit.0)
             }
@@ -6,8 +6,9 @@ pub(crate) struct SQLUseDatabaseStatement {
 }
 
 impl ToSQLString for SQLUseDatabaseStatement {
-    fn to_string(&self, _dialect: SQLDialect) -> String {
+    fn to_string(&self, dialect: SQLDialect) -> String {
         let database = &self.database;
-        format!("USE `{database}`")
+        let escape = dialect.escape();
+        format!("USE {escape}{database}{escape}")
     }
 }
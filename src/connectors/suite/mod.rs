@@ -0,0 +1,153 @@
+use crate::core::error::Error;
+use crate::core::result::Result;
+use crate::prelude::{Graph, Object, Value};
+use crate::teon;
+
+/// The models which the suite runs against. Append them to the schema of the connector under
+/// test, then pass the built app's graph to `run`.
+pub const SCHEMA: &str = r#"
+model SuiteAuthor {
+  @id @default($cuid)
+  id: String
+  name: String
+  age: Int
+  @relation(fields: .id, references: .authorId)
+  posts: SuitePost[]
+}
+
+model SuitePost {
+  @id @default($cuid)
+  id: String
+  title: String
+  authorId: String
+  @relation(fields: .authorId, references: .id)
+  author: SuiteAuthor
+}
+"#;
+
+/// Run every check of the suite. Records of the suite models are deleted before each check.
+pub async fn run(graph: &Graph) -> Result<()> {
+    clean(graph).await?;
+    crud(graph).await?;
+    clean(graph).await?;
+    filtering(graph).await?;
+    clean(graph).await?;
+    relations(graph).await?;
+    clean(graph).await?;
+    aggregation(graph).await?;
+    clean(graph).await
+}
+
+/// Objects are created, found, updated and deleted.
+pub async fn crud(graph: &Graph) -> Result<()> {
+    let author = create_author(graph, "Ada", 36).await?;
+    let id = author.get_value("id")?;
+    check(!author.is_new(), "saved object is still new")?;
+    let found: Object = graph.find_unique("SuiteAuthor", &teon!({"where": {"id": id.clone()}})).await?;
+    check(found.get_value("name")? == teon!("Ada"), "find unique returns the saved values")?;
+    found.set("name", "Grace")?;
+    found.save().await?;
+    let found: Object = graph.find_unique("SuiteAuthor", &teon!({"where": {"id": id.clone()}})).await?;
+    check(found.get_value("name")? == teon!("Grace"), "update is persisted")?;
+    found.delete().await?;
+    let deleted: Result<Object> = graph.find_unique("SuiteAuthor", &teon!({"where": {"id": id}})).await;
+    check(deleted.is_err(), "find unique of a deleted object returns an error")
+}
+
+/// Filters, ordering and pagination are applied.
+pub async fn filtering(graph: &Graph) -> Result<()> {
+    create_author(graph, "Alice", 20).await?;
+    create_author(graph, "Bob", 30).await?;
+    create_author(graph, "Carol", 40).await?;
+    let older: Vec<Object> = graph.find_many("SuiteAuthor", &teon!({"where": {"age": {"gt": 25}}})).await?;
+    check(older.len() == 2, "'gt' filter")?;
+    let named: Vec<Object> = graph.find_many("SuiteAuthor", &teon!({"where": {"name": {"contains": "o"}}})).await?;
+    check(named.len() == 2, "'contains' filter")?;
+    let either: Vec<Object> = graph.find_many("SuiteAuthor", &teon!({"where": {"OR": [{"age": 20}, {"name": "Carol"}]}})).await?;
+    check(either.len() == 2, "'OR' filter")?;
+    let ordered: Vec<Object> = graph.find_many("SuiteAuthor", &teon!({"orderBy": [{"age": "desc"}]})).await?;
+    check(ordered.first().map(|o| o.get_value("age").unwrap()) == Some(teon!(40)), "descending order")?;
+    let page: Vec<Object> = graph.find_many("SuiteAuthor", &teon!({"orderBy": [{"age": "asc"}], "skip": 1, "take": 1})).await?;
+    check(page.len() == 1 && page[0].get_value("age")? == teon!(30), "skip and take")?;
+    let count = graph.count("SuiteAuthor", &teon!({"where": {"age": {"lte": 30}}})).await?;
+    check(count == 2, "count with filter")
+}
+
+/// Relations are fetched from both sides and filtered on.
+pub async fn relations(graph: &Graph) -> Result<()> {
+    let author = create_author(graph, "Ada", 36).await?;
+    let author_id = author.get_value("id")?;
+    for title in ["First", "Second"] {
+        let post = graph.create_object("SuitePost", teon!({"title": title, "authorId": author_id.clone()})).await?;
+        post.save().await?;
+    }
+    let posts = author.force_get_relation_objects("posts", teon!({})).await?;
+    check(posts.len() == 2, "fetch the many side of a relation")?;
+    let post_author = posts[0].force_get_relation_object("author").await?;
+    check(post_author.map(|a| a.get_value("id").unwrap()) == Some(author_id.clone()), "fetch the single side of a relation")?;
+    let included: Object = graph.find_unique("SuiteAuthor", &teon!({"where": {"id": author_id}, "include": {"posts": true}})).await?;
+    check(included.get_relation_vec("posts")?.len() == 2, "include a relation")?;
+    let filtered: Vec<Object> = graph.find_many("SuitePost", &teon!({"where": {"author": {"is": {"name": "Ada"}}}})).await?;
+    check(filtered.len() == 2, "filter by a related object")
+}
+
+/// Aggregates and groups are computed.
+pub async fn aggregation(graph: &Graph) -> Result<()> {
+    create_author(graph, "Alice", 20).await?;
+    create_author(graph, "Bob", 30).await?;
+    create_author(graph, "Bob", 40).await?;
+    let result = graph.aggregate("SuiteAuthor", &teon!({"_count": {"_all": true}, "_sum": {"age": true}, "_avg": {"age": true}, "_min": {"age": true}, "_max": {"age": true}})).await?;
+    check(number(&result, "_count", "_all") == Some(3.0), "'_count' aggregate")?;
+    check(number(&result, "_sum", "age") == Some(90.0), "'_sum' aggregate")?;
+    check(number(&result, "_avg", "age") == Some(30.0), "'_avg' aggregate")?;
+    check(number(&result, "_min", "age") == Some(20.0), "'_min' aggregate")?;
+    check(number(&result, "_max", "age") == Some(40.0), "'_max' aggregate")?;
+    let groups = graph.group_by("SuiteAuthor", &teon!({"by": ["name"], "_sum": {"age": true}, "orderBy": [{"name": "asc"}]})).await?;
+    let groups = groups.as_vec().ok_or_else(|| failure("'groupBy' returns a list"))?;
+    check(groups.len() == 2, "'groupBy' groups by keys")?;
+    check(number(&groups[1], "_sum", "age") == Some(70.0), "'groupBy' aggregates each group")
+}
+
+async fn create_author(graph: &Graph, name: &str, age: i32) -> Result<Object> {
+    let author = graph.create_object("SuiteAuthor", teon!({"name": name, "age": age})).await?;
+    author.save().await?;
+    Ok(author)
+}
+
+async fn clean(graph: &Graph) -> Result<()> {
+    for model in ["SuitePost", "SuiteAuthor"] {
+        let objects: Vec<Object> = graph.find_many(model, &teon!({})).await?;
+        for object in objects {
+            object.delete().await?;
+        }
+    }
+    Ok(())
+}
+
+fn number(value: &Value, aggregate: &str, key: &str) -> Option<f64> {
+    value.get(aggregate).and_then(|v| v.get(key)).and_then(|v| v.as_f64())
+}
+
+fn check(condition: bool, name: &str) -> Result<()> {
+    if condition { Ok(()) } else { Err(failure(name)) }
+}
+
+fn failure(name: &str) -> Error {
+    Error::custom_internal_server_error(format!("Connector suite check failed: {name}."))
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::serial;
+    use crate::core::app::testing::sqlite_app;
+    use super::*;
+
+    #[tokio::test]
+    #[serial]
+    async fn sqlite_passes_suite() {
+        let app = sqlite_app(SCHEMA).await;
+        if let Err(err) = run(app.graph()).await {
+            panic!("{}", err.message());
+        }
+    }
+}
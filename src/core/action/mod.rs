@@ -1,4 +1,4 @@
-pub(crate) mod source;
+pub mod source;
 
 use std::collections::HashSet;
 use std::slice::Iter;
@@ -20,6 +20,9 @@ pub(crate) const COUNT: u32 = 1 << 11;
 pub(crate) const AGGREGATE: u32 = 1 << 12;
 pub(crate) const GROUP_BY: u32 = 1 << 13;
 pub(crate) const PROGRAM_CODE: u32 = 1 << 14;
+pub(crate) const RESTORE: u32 = 1 << 21;
+pub(crate) const REFRESH: u32 = 1 << 22;
+pub(crate) const SIGN_OUT: u32 = 1 << 23;
 
 pub(crate) const UPSERT: u32 = CREATE | UPDATE;
 pub(crate) const CONNECT_OR_CREATE: u32 = CONNECT | CREATE;
@@ -35,7 +38,7 @@ pub(crate) const SINGLE: u32 = 1 << 18;
 pub(crate) const MANY: u32 = 1 << 19;
 pub(crate) const INTERNAL_AMOUNT: u32 = 1 << 20;
 
-const ALL_NAMES: u32 = CREATE | UPDATE | UPSERT | DELETE | FIND | FIND_FIRST | CONNECT | CONNECT_OR_CREATE | DISCONNECT | SET | JOIN_CREATE | JOIN_DELETE | IDENTITY | SIGN_IN | COUNT | AGGREGATE | GROUP_BY;
+const ALL_NAMES: u32 = CREATE | UPDATE | UPSERT | DELETE | FIND | FIND_FIRST | CONNECT | CONNECT_OR_CREATE | DISCONNECT | SET | JOIN_CREATE | JOIN_DELETE | IDENTITY | SIGN_IN | COUNT | AGGREGATE | GROUP_BY | RESTORE | REFRESH | SIGN_OUT;
 const ALL_POSITIONS: u32 = ENTRY | NESTED | INTERNAL_POSITION;
 const ALL_AMOUNTS: u32 = SINGLE | MANY | INTERNAL_AMOUNT;
 
@@ -58,6 +61,9 @@ pub(crate) const AGGREGATE_HANDLER: u32 = AGGREGATE | ENTRY;
 pub(crate) const GROUP_BY_HANDLER: u32 = GROUP_BY | ENTRY;
 pub(crate) const SIGN_IN_HANDLER: u32 = SIGN_IN | ENTRY;
 pub(crate) const IDENTITY_HANDLER: u32 = IDENTITY | ENTRY;
+pub(crate) const RESTORE_HANDLER: u32 = RESTORE | ENTRY | SINGLE;
+pub(crate) const REFRESH_HANDLER: u32 = REFRESH | ENTRY;
+pub(crate) const SIGN_OUT_HANDLER: u32 = SIGN_OUT | ENTRY;
 
 pub(crate) const NESTED_CREATE_ACTION: u32 = CREATE | NESTED | SINGLE;
 pub(crate) const NESTED_UPDATE_ACTION: u32 = UPDATE | NESTED | SINGLE;
@@ -72,7 +78,7 @@ pub(crate) const NESTED_UPDATE_MANY_ACTION: u32 = UPDATE | NESTED | MANY;
 pub(crate) const NESTED_DELETE_MANY_ACTION: u32 = DELETE | NESTED | MANY;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-pub(crate) struct Action {
+pub struct Action {
     value: u32
 }
 
@@ -87,7 +93,14 @@ impl Action {
     }
 
     pub(crate) fn from_name(name: &str) -> Self {
-        Action {
+        match Self::try_from_name(name) {
+            Some(action) => action,
+            None => panic!("Unrecognized action option name '{}'.", name)
+        }
+    }
+
+    pub(crate) fn try_from_name(name: &str) -> Option<Self> {
+        Some(Action {
             value: match name {
                 "create" => CREATE,
                 "update" => UPDATE,
@@ -109,9 +122,12 @@ impl Action {
                 "internalAmount" => INTERNAL_AMOUNT,
                 "programCode" => PROGRAM_CODE,
                 "identity" => IDENTITY,
-                _ => panic!("Unrecognized action option name '{}'.", name)
+                "restore" => RESTORE,
+                "refresh" => REFRESH,
+                "signOut" => SIGN_OUT,
+                _ => return None
             }
-        }
+        })
     }
 
     pub(crate) const fn from_u32(value: u32) -> Self {
@@ -227,6 +243,9 @@ impl Action {
             GROUP_BY_HANDLER => &GROUP_BY_INPUT_JSON_KEYS,
             SIGN_IN_HANDLER => &SIGN_IN_INPUT_JSON_KEYS,
             IDENTITY_HANDLER => &IDENTITY_INPUT_JSON_KEYS,
+            RESTORE_HANDLER => &RESTORE_INPUT_JSON_KEYS,
+            REFRESH_HANDLER => &REFRESH_INPUT_JSON_KEYS,
+            SIGN_OUT_HANDLER => &SIGN_OUT_INPUT_JSON_KEYS,
             _ => unreachable!()
         }
     }
@@ -243,6 +262,13 @@ impl Action {
         self.value == SIGN_IN_HANDLER
     }
 
+    pub(crate) fn handler_requires_refresh_token(&self) -> bool {
+        self.value == REFRESH_HANDLER
+    }
+
+    pub(crate) fn handler_accepts_refresh_token(&self) -> bool {
+        self.value == REFRESH_HANDLER || self.value == SIGN_OUT_HANDLER
+    }
 
     pub(crate) fn handler_requires_update(&self) -> bool {
         match self.value {
@@ -261,7 +287,7 @@ impl Action {
 
     pub(crate) fn handler_requires_where_unique(&self) -> bool {
         match self.value {
-            FIND_UNIQUE_HANDLER | UPDATE_HANDLER | UPSERT_HANDLER | DELETE_HANDLER => true,
+            FIND_UNIQUE_HANDLER | UPDATE_HANDLER | UPSERT_HANDLER | DELETE_HANDLER | RESTORE_HANDLER => true,
             _ => false,
         }
     }
@@ -290,6 +316,9 @@ impl Action {
             GROUP_BY_HANDLER => ResMeta::NoMeta,
             SIGN_IN_HANDLER => ResMeta::TokenInfo,
             IDENTITY_HANDLER => ResMeta::NoMeta,
+            RESTORE_HANDLER => ResMeta::NoMeta,
+            REFRESH_HANDLER => ResMeta::TokenInfo,
+            SIGN_OUT_HANDLER => ResMeta::NoMeta,
             _ => unreachable!()
         }
     }
@@ -311,6 +340,9 @@ impl Action {
             GROUP_BY_HANDLER => ResData::Other,
             SIGN_IN_HANDLER => ResData::Single,
             IDENTITY_HANDLER => ResData::Single,
+            RESTORE_HANDLER => ResData::Single,
+            REFRESH_HANDLER => ResData::Single,
+            SIGN_OUT_HANDLER => ResData::Single,
             _ => unreachable!()
         }
     }
@@ -332,6 +364,9 @@ impl Action {
             GROUP_BY_HANDLER => "groupBy",
             SIGN_IN_HANDLER => "signIn",
             IDENTITY_HANDLER => "identity",
+            RESTORE_HANDLER => "restore",
+            REFRESH_HANDLER => "refresh",
+            SIGN_OUT_HANDLER => "signOut",
             _ => unreachable!()
         }
     }
@@ -354,13 +389,16 @@ impl Action {
                 "groupBy" => GROUP_BY_HANDLER,
                 "signIn" => SIGN_IN_HANDLER,
                 "identity" => IDENTITY_HANDLER,
+                "restore" => RESTORE_HANDLER,
+                "refresh" => REFRESH_HANDLER,
+                "signOut" => SIGN_OUT_HANDLER,
                 _ => None?
             }
         })
     }
 
     pub(crate) fn handlers_iter() -> Iter<'static, Action> {
-        static HANDLER_TYPES: [Action; 15] = [
+        static HANDLER_TYPES: [Action; 18] = [
             Action::from_u32(FIND_UNIQUE_HANDLER),
             Action::from_u32(FIND_FIRST_HANDLER),
             Action::from_u32(FIND_MANY_HANDLER),
@@ -376,6 +414,9 @@ impl Action {
             Action::from_u32(GROUP_BY_HANDLER),
             Action::from_u32(SIGN_IN_HANDLER),
             Action::from_u32(IDENTITY_HANDLER),
+            Action::from_u32(RESTORE_HANDLER),
+            Action::from_u32(REFRESH_HANDLER),
+            Action::from_u32(SIGN_OUT_HANDLER),
         ];
         HANDLER_TYPES.iter()
     }
@@ -437,13 +478,13 @@ pub enum ResData {
 }
 
 static FIND_UNIQUE_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset! {"include", "select", "where"}
+    hashset! {"include", "select", "where", "withDeleted", "onlyDeleted"}
 });
 static FIND_FIRST_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset! {"include", "select", "where", "orderBy", "skip", "cursor", "distinct"}
+    hashset! {"include", "select", "where", "orderBy", "skip", "cursor", "distinct", "withDeleted", "onlyDeleted"}
 });
 static FIND_MANY_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset! {"include", "select", "where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor", "distinct"}
+    hashset! {"include", "select", "where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor", "distinct", "withDeleted", "onlyDeleted"}
 });
 static CREATE_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset! {"include", "select", "create"}
@@ -467,13 +508,13 @@ static DELETE_MANY_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset! {"select", "where"}
 });
 static COUNT_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset! {"where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor", "distinct"}
+    hashset! {"where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor", "distinct", "withDeleted", "onlyDeleted"}
 });
 static AGGREGATE_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset! {"_avg", "_count", "_sum", "_min", "_max", "where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor"}
+    hashset! {"_avg", "_count", "_sum", "_min", "_max", "where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor", "withDeleted", "onlyDeleted"}
 });
 static GROUP_BY_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset! {"_avg", "_count", "_sum", "_min", "_max", "by", "having", "where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor"}
+    hashset! {"_avg", "_count", "_sum", "_min", "_max", "by", "having", "where", "orderBy", "skip", "take", "pageSize", "pageNumber", "cursor", "withDeleted", "onlyDeleted"}
 });
 static SIGN_IN_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset! {"include", "select", "credentials"}
@@ -481,3 +522,12 @@ static SIGN_IN_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
 static IDENTITY_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset! {"include", "select"}
 });
+static RESTORE_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    hashset! {"include", "select", "where"}
+});
+static REFRESH_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    hashset! {"include", "select", "refreshToken"}
+});
+static SIGN_OUT_INPUT_JSON_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    hashset! {"include", "select", "refreshToken"}
+});
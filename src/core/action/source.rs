@@ -2,7 +2,7 @@ use crate::prelude::{Object, Value};
 use self::ActionSource::*;
 
 #[derive(Clone)]
-pub(crate) enum ActionSource {
+pub enum ActionSource {
     Identity(Option<Object>),
     DataClient,
     ProgramCode,
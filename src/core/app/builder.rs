@@ -2,7 +2,9 @@ use std::collections::HashMap;
 use std::env;
 use std::ffi::{OsString};
 use std::fmt::{Debug};
+use std::future::Future;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use to_mut_proc_macro::ToMut;
 use to_mut::ToMut;
 use clap::{Arg, ArgAction, Command as ClapCommand};
@@ -10,17 +12,21 @@ use dotenvy::dotenv;
 use crate::connectors::mongodb::connector::MongoDBConnector;
 use crate::connectors::sql::connector::SQLConnector;
 use crate::connectors::sql::schema::dialect::SQLDialect;
-use crate::core::app::command::{CLI, CLICommand, GenerateClientCommand, GenerateCommand, GenerateEntityCommand, MigrateCommand, ServeCommand};
-use crate::core::app::conf::{ClientGeneratorConf, EntityGeneratorConf, ServerConf};
+use crate::core::app::command::{CLI, CLICommand, GenerateClientCommand, GenerateCommand, GenerateEntityCommand, GenerateOpenAPICommand, MigrateCommand, MigrateCreateCommand, MigrateSubcommand, SeedCommand, ServeCommand};
+use crate::core::app::conf::{ClientGeneratorConf, CorsConf, EntityGeneratorConf, ServerConf};
 use crate::core::app::entrance::Entrance;
 use crate::core::app::environment::EnvironmentVersion;
-use crate::core::connector::Connector;
+use crate::core::app::revocation::{DatabaseRevocationStore, RevocationStore};
+use crate::core::connector::{Connector, ConnectorFactory};
 use crate::core::field::Field;
+use crate::core::database::log::{self as query_log, QueryLogConf};
 use crate::core::database::name::DatabaseName;
 use crate::core::field::r#type::FieldType;
 use crate::core::graph::builder::GraphBuilder;
+use crate::core::handler::HandlerFn;
+use crate::core::handler::ctx::HandlerCtx;
 use crate::parser::ast::field::FieldClass;
-use crate::prelude::{App, Value};
+use crate::prelude::{App, Result, Value};
 use crate::core::pipeline::item::Item;
 use crate::core::pipeline::items::function::compare::{CompareArgument, CompareItem};
 use crate::core::pipeline::items::function::perform::{PerformArgument, PerformItem, PerformResult};
@@ -29,6 +35,7 @@ use crate::core::pipeline::items::function::validate::{ValidateArgument, Validat
 use crate::core::property::Property;
 use crate::core::relation::Relation;
 use crate::parser::ast::r#type::Arity;
+use crate::parser::ast::connector::Connector as ConnectorDeclaration;
 use crate::parser::parser::Parser;
 
 #[derive(Debug)]
@@ -40,14 +47,17 @@ pub(crate) struct CallbackLookupTable {
 }
 
 impl CallbackLookupTable {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self { transforms: HashMap::new(), validators: HashMap::new(), callbacks: HashMap::new(), compares: HashMap::new() }
     }
 }
 
 #[derive(ToMut)]
 pub struct AppBuilder {
-    pub(crate) connector: Option<Arc<dyn Connector>>,
+    pub(crate) connectors: Vec<(String, Arc<dyn Connector>)>,
+    pub(crate) connector_factories: HashMap<String, ConnectorFactory>,
+    pub(crate) handlers: Vec<(String, String, HandlerFn)>,
+    pub(crate) revocation_store: Option<Arc<dyn RevocationStore>>,
     pub(crate) graph_builder: GraphBuilder,
     pub(crate) server_conf: Option<ServerConf>,
     pub(crate) entity_generator_confs: Vec<EntityGeneratorConf>,
@@ -74,16 +84,25 @@ impl AppBuilder {
 
     pub fn new_with_environment_version_and_entrance(environment_version: EnvironmentVersion, entrance: Entrance) -> Self {
         let _ = dotenv(); // load dotenv file if exist. If the file does not exist, do nothing.
+        let args = Self::parse_cli_args(environment_version.clone(), entrance);
+        Self::new_with_args(environment_version, entrance, args)
+    }
+
+    /// Create a builder with already parsed command line arguments.
+    pub(crate) fn new_with_args(environment_version: EnvironmentVersion, entrance: Entrance, args: CLI) -> Self {
         Self {
-            connector: None,
+            connectors: vec![],
+            connector_factories: HashMap::new(),
+            handlers: vec![],
+            revocation_store: None,
             graph_builder: GraphBuilder::new(),
             server_conf: None,
             entity_generator_confs: vec![],
             client_generator_confs: vec![],
             callback_lookup_table: Arc::new(Mutex::new(CallbackLookupTable::new())),
-            environment_version: environment_version.clone(),
+            environment_version,
             entrance,
-            args: Arc::new(Self::parse_cli_args(environment_version.clone(), entrance.clone())),
+            args: Arc::new(args),
         }
     }
 
@@ -148,14 +167,42 @@ impl AppBuilder {
                         .action(ArgAction::Append)
                         .conflicts_with("all")
                         .help("Entity names to generate")
-                        .num_args(1..))))
+                        .num_args(1..)))
+                .subcommand(ClapCommand::new("openapi")
+                    .about("Generate an OpenAPI document, YAML is written if the output ends with .yaml or .yml")
+                    .arg_required_else_help(false)
+                    .arg(Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .help("The output file path")
+                        .action(ArgAction::Set)
+                        .default_value("openapi.json")
+                        .num_args(1))))
             .subcommand(ClapCommand::new("migrate")
                 .about("Run migration")
                 .arg(Arg::new("dry")
                     .short('d')
                     .long("dry")
                     .help("Dry run")
-                    .action(ArgAction::SetTrue)))
+                    .global(true)
+                    .action(ArgAction::SetTrue))
+                .subcommand(ClapCommand::new("create")
+                    .about("Create a migration file from the difference between the schema and the database")
+                    .arg(Arg::new("NAME")
+                        .required(true)
+                        .help("Migration name")))
+                .subcommand(ClapCommand::new("rollback")
+                    .about("Revert the last applied migration file")))
+            .subcommand(ClapCommand::new("seed")
+                .about("Seed the database with datasets")
+                .arg(Arg::new("reset")
+                    .short('r')
+                    .long("reset")
+                    .help("Delete records of the dataset's models before seeding")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("NAME")
+                    .required(false)
+                    .help("Dataset name, all datasets are seeded if omitted")))
             .get_matches_from(match environment_version {
                 EnvironmentVersion::Python(_) | EnvironmentVersion::NodeJS(_) => env::args_os().enumerate().filter(|(i, _x)| *i != 1).map(|(_i, x)| x).collect::<Vec<OsString>>(),
                 EnvironmentVersion::Rust(_) => env::args_os().enumerate().filter(|(i, x)| {
@@ -178,18 +225,35 @@ impl AppBuilder {
                         let names: Option<Vec<String>> = submatches.get_many::<String>("NAME").map(|s| s.map(|v| v.to_string()).collect::<Vec<String>>());
                         CLICommand::Generate(GenerateCommand::GenerateEntityCommand(GenerateEntityCommand { all: false, names }))
                     }
+                    Some(("openapi", submatches)) => {
+                        let output = submatches.get_one::<String>("output").unwrap().to_string();
+                        CLICommand::Generate(GenerateCommand::GenerateOpenAPICommand(GenerateOpenAPICommand { output }))
+                    }
                     _ => unreachable!()
                 }
             }
             Some(("migrate", submatches)) => {
-                CLICommand::Migrate(MigrateCommand { dry: submatches.get_flag("dry") })
+                match submatches.subcommand() {
+                    Some(("create", submatches)) => {
+                        let name = submatches.get_one::<String>("NAME").unwrap().to_string();
+                        CLICommand::Migrate(MigrateCommand { dry: submatches.get_flag("dry"), subcommand: Some(MigrateSubcommand::Create(MigrateCreateCommand { name })) })
+                    }
+                    Some(("rollback", submatches)) => {
+                        CLICommand::Migrate(MigrateCommand { dry: submatches.get_flag("dry"), subcommand: Some(MigrateSubcommand::Rollback) })
+                    }
+                    _ => CLICommand::Migrate(MigrateCommand { dry: submatches.get_flag("dry"), subcommand: None })
+                }
+            }
+            Some(("seed", submatches)) => {
+                let name = submatches.get_one::<String>("NAME").map(|n| n.to_string());
+                CLICommand::Seed(SeedCommand { reset: submatches.get_flag("reset"), name })
             }
             _ => unreachable!()
         };
         CLI { command, schema: schema.map(|s| s.to_string()) }
     }
 
-    fn rust_environment_version() -> EnvironmentVersion {
+    pub(crate) fn rust_environment_version() -> EnvironmentVersion {
         EnvironmentVersion::Rust(env!("TEO_RUSTC_VERSION").to_string())
     }
 
@@ -201,6 +265,31 @@ impl AppBuilder {
         self
     }
 
+    /// Register a connector for schemas declaring `connector { provider: .custom("name") }`.
+    /// `factory` receives the connector's url when the app is built.
+    pub fn register_connector<F, Fut>(&mut self, name: impl Into<String>, factory: F) -> &mut Self where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Arc<dyn Connector>> + Send + 'static {
+        self.connector_factories.insert(name.into(), Arc::new(move |url| Box::pin(factory(url))));
+        self
+    }
+
+    /// Implement the handler `name` declared with `@@handler` on `model`. Its return value is
+    /// sent as the response's data. It runs in a transaction, which is rolled back if it returns
+    /// an error.
+    pub fn handler<F, Fut>(&mut self, model: impl Into<String>, name: impl Into<String>, f: F) -> &mut Self where
+        F: Fn(HandlerCtx) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Value>> + Send + 'static {
+        self.handlers.push((model.into(), name.into(), Arc::new(move |ctx| Box::pin(f(ctx)))));
+        self
+    }
+
+    /// Replace the database table which stores signed out tokens with a custom store.
+    pub fn revocation_store(&mut self, store: impl RevocationStore + 'static) -> &mut Self {
+        self.revocation_store = Some(Arc::new(store));
+        self
+    }
+
     pub fn callback<T, F, O>(&mut self, name: impl Into<String>, f: F) -> &mut Self where
         T: From<Value> + Send + Sync + 'static,
         F: PerformArgument<T, O> + 'static,
@@ -232,6 +321,12 @@ impl AppBuilder {
             None => None
         };
         parser.parse(main);
+        if !parser.diagnostics.is_empty() {
+            eprint!("{}", parser.diagnostics.render(true));
+        }
+        if parser.diagnostics.has_errors() {
+            std::process::exit(1);
+        }
         self.load_config_from_parser(&parser).await;
     }
 
@@ -241,42 +336,67 @@ impl AppBuilder {
             server_conf: self.server_conf.clone().unwrap(),
             entity_generator_confs: self.entity_generator_confs.clone(),
             client_generator_confs: self.client_generator_confs.clone(),
-            graph: self.graph_builder.build(self.connector.as_ref().unwrap().clone()).await,
+            graph: self.graph_builder.build(self.connectors.clone()).await,
             environment_version: self.environment_version.clone(),
             entrance: self.entrance.clone(),
             args: self.args.clone(),
         }
     }
 
-    async fn load_config_from_parser(&mut self, parser: &Parser) {
-        // connector
-        let connector_ref = parser.connector.unwrap();
-        let source = parser.get_source(connector_ref.0);
-        let connector_declaration = source.get_connector(connector_ref.1);
-        let url = connector_declaration.url.as_ref().unwrap();
-        if connector_declaration.debug {
-            env::set_var("_TEO_LOG_DB_OPERATION", "true");
-        }
-        let connector: Arc<dyn Connector> = match connector_declaration.provider.unwrap() {
+    async fn connector_from_declaration(&self, declaration: &ConnectorDeclaration) -> Arc<dyn Connector> {
+        let url = declaration.url.as_ref().unwrap();
+        match declaration.provider.as_ref().unwrap() {
             DatabaseName::MySQL => {
                 #[cfg(feature = "data-source-mysql")]
-                Arc::new(SQLConnector::new(SQLDialect::MySQL, url, false).await)
+                Arc::new(SQLConnector::new(SQLDialect::MySQL, url, declaration.pool, false).await.with_replicas(&declaration.replicas, declaration.read_your_writes))
             },
             DatabaseName::PostgreSQL => {
                 #[cfg(feature = "data-source-postgres")]
-                Arc::new(SQLConnector::new(SQLDialect::PostgreSQL, url, false).await)
+                Arc::new(SQLConnector::new(SQLDialect::PostgreSQL, url, declaration.pool, false).await.with_replicas(&declaration.replicas, declaration.read_your_writes))
             },
             #[cfg(feature = "data-source-sqlite")]
             DatabaseName::SQLite => {
                 #[cfg(feature = "data-source-sqlite")]
-                Arc::new(SQLConnector::new(SQLDialect::SQLite, url, false).await)
+                Arc::new(SQLConnector::new(SQLDialect::SQLite, url, declaration.pool, false).await)
             },
             DatabaseName::MongoDB => {
                 #[cfg(feature = "data-source-mongodb")]
-                Arc::new(MongoDBConnector::new(url.clone()).await)
+                Arc::new(MongoDBConnector::new(url.clone(), declaration.pool).await)
             },
-        };
-        self.connector = Some(connector.clone());
+            #[cfg(feature = "data-source-mssql")]
+            DatabaseName::MSSQL => {
+                Arc::new(SQLConnector::new(SQLDialect::MSSQL, url, declaration.pool, false).await.with_replicas(&declaration.replicas, declaration.read_your_writes))
+            },
+            DatabaseName::Custom(name) => {
+                let Some(factory) = self.connector_factories.get(name) else {
+                    eprintln!("Connector '{name}' is not registered. Register it with `AppBuilder::register_connector`.");
+                    std::process::exit(1);
+                };
+                factory(url.clone()).await
+            },
+        }
+    }
+
+    async fn load_config_from_parser(&mut self, parser: &Parser) {
+        // connectors, the default one first
+        let default_connector = parser.default_connector().unwrap();
+        let mut declarations = vec![default_connector];
+        for connector_ref in parser.connectors.iter() {
+            let declaration = parser.get_connector(*connector_ref);
+            if declaration.id != default_connector.id || declaration.source_id != default_connector.source_id {
+                declarations.push(declaration);
+            }
+        }
+        // connectors share one query log, the lowest slow query threshold applies to all of them
+        query_log::setup(QueryLogConf {
+            debug: declarations.iter().any(|d| d.debug),
+            slow_query_threshold: declarations.iter().filter_map(|d| d.slow_query_threshold).min().map(Duration::from_millis),
+        });
+        self.connectors = vec![];
+        for declaration in declarations {
+            let connector = self.connector_from_declaration(declaration).await;
+            self.connectors.push((declaration.name().to_owned(), connector));
+        }
         // server config
         let config_ref = parser.config.unwrap();
         let source = parser.get_source(config_ref.0);
@@ -293,7 +413,26 @@ impl AppBuilder {
                 Some(jwt_secret.clone())
             } else {
                 None
-            }
+            },
+            graphql: config.graphql,
+            cors: CorsConf {
+                origins: config.cors_origins.clone().unwrap_or(vec!["*".to_owned()]),
+                headers: config.cors_headers.clone().unwrap_or(vec!["*".to_owned()]),
+                credentials: config.cors_credentials,
+                max_age: config.cors_max_age.unwrap_or(86400),
+            },
+            body_limit: config.body_limit.unwrap_or(262_144),
+            compression: config.compression,
+            request_timeout: config.request_timeout,
+            // access tokens are short lived, clients renew them with the refresh token
+            token_expiry: config.token_expiry.unwrap_or(60 * 60),
+            refresh_token_expiry: config.refresh_token_expiry.unwrap_or(30 * 24 * 60 * 60),
+            revocation_store: match &self.revocation_store {
+                Some(store) => store.clone(),
+                None => Arc::new(DatabaseRevocationStore::new()),
+            },
+            health_details: config.health_details,
+            health_checks: Arc::new(Default::default()),
         });
         // entity generators
         for entity_generator_ref in parser.generators.iter() {
@@ -519,6 +658,19 @@ impl AppBuilder {
                 }
             });
         }
+        // signed out tokens are stored in the database unless a custom store is registered
+        if self.revocation_store.is_none() && self.graph_builder.model_builders.iter().any(|m| m.identity) {
+            DatabaseRevocationStore::install_model(&mut self.graph_builder);
+        }
+        for (model_name, name, implementation) in self.handlers.iter() {
+            let Some(model) = self.graph_builder.model_builders.iter_mut().find(|m| &m.name == model_name) else {
+                panic!("Handler '{}' is implemented for undefined model '{}'.", name, model_name);
+            };
+            let Some(handler) = model.handlers.iter_mut().find(|h| &h.name == name) else {
+                panic!("Handler '{}' is implemented but not declared on model '{}'.", name, model_name);
+            };
+            handler.implementation = Some(implementation.clone());
+        }
     }
 
     fn install_types_to_field_builder(name: &str, field: &mut Field) {
@@ -532,6 +684,7 @@ impl AppBuilder {
             "Date" => field.field_type = Some(FieldType::Date),
             "DateTime" => field.field_type = Some(FieldType::DateTime),
             "Decimal" => field.field_type = Some(FieldType::Decimal),
+            "Json" => field.field_type = Some(FieldType::Json),
             #[cfg(feature = "data-source-mongodb")]
             "ObjectId" => field.field_type = Some(FieldType::ObjectId),
             // _ => panic!("Unrecognized type: '{}'.", name)
@@ -550,6 +703,7 @@ impl AppBuilder {
             "Date" =>  property.field_type = Some(FieldType::Date),
             "DateTime" =>  property.field_type = Some(FieldType::DateTime),
             "Decimal" => property.field_type = Some(FieldType::Decimal),
+            "Json" => property.field_type = Some(FieldType::Json),
             #[cfg(feature = "data-source-mongodb")]
             "ObjectId" =>  property.field_type = Some(FieldType::ObjectId),
             _ => property.field_type = Some(FieldType::Enum(name.to_string())),
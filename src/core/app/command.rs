@@ -9,6 +9,7 @@ pub(crate) enum CLICommand {
     Serve(ServeCommand),
     Generate(GenerateCommand),
     Migrate(MigrateCommand),
+    Seed(SeedCommand),
 }
 
 #[derive(Debug)]
@@ -20,6 +21,7 @@ pub(crate) struct ServeCommand {
 pub(crate) enum GenerateCommand {
     GenerateClientCommand(GenerateClientCommand),
     GenerateEntityCommand(GenerateEntityCommand),
+    GenerateOpenAPICommand(GenerateOpenAPICommand),
 }
 
 #[derive(Debug)]
@@ -34,7 +36,30 @@ pub(crate) struct GenerateEntityCommand {
     pub(crate) names: Option<Vec<String>>,
 }
 
+#[derive(Debug)]
+pub(crate) struct GenerateOpenAPICommand {
+    pub(crate) output: String,
+}
+
 #[derive(Debug)]
 pub(crate) struct MigrateCommand {
     pub(crate) dry: bool,
+    pub(crate) subcommand: Option<MigrateSubcommand>,
+}
+
+#[derive(Debug)]
+pub(crate) enum MigrateSubcommand {
+    Create(MigrateCreateCommand),
+    Rollback,
+}
+
+#[derive(Debug)]
+pub(crate) struct SeedCommand {
+    pub(crate) reset: bool,
+    pub(crate) name: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct MigrateCreateCommand {
+    pub(crate) name: String,
 }
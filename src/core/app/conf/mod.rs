@@ -1,5 +1,10 @@
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Mutex;
 use crate::core::app::environment::Environment;
+use crate::core::app::revocation::RevocationStore;
+use crate::core::connector::ConnectorHealth;
 use crate::parser::ast::client::ClientLanguage;
 
 #[derive(Clone)]
@@ -7,6 +12,29 @@ pub struct ServerConf {
     pub(crate) bind: (String, u16),
     pub(crate) jwt_secret: Option<String>,
     pub(crate) path_prefix: Option<String>,
+    pub(crate) graphql: bool,
+    pub(crate) cors: CorsConf,
+    pub(crate) body_limit: usize,
+    pub(crate) compression: bool,
+    pub(crate) request_timeout: Option<u64>,
+    pub(crate) token_expiry: i64,
+    pub(crate) refresh_token_expiry: i64,
+    pub(crate) revocation_store: Arc<dyn RevocationStore>,
+    /// Whether `/_health` reports latencies and database errors.
+    pub(crate) health_details: bool,
+    pub(crate) health_checks: HealthChecks,
+}
+
+/// The last health check of each connector and when it was made. It's shared by the requests
+/// to `/_health`, so that they don't reach the databases more often than they need to.
+pub(crate) type HealthChecks = Arc<Mutex<Option<(Instant, Vec<(String, ConnectorHealth)>)>>>;
+
+#[derive(Clone)]
+pub struct CorsConf {
+    pub(crate) origins: Vec<String>,
+    pub(crate) headers: Vec<String>,
+    pub(crate) credentials: bool,
+    pub(crate) max_age: u32,
 }
 
 #[derive(Clone)]
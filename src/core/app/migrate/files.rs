@@ -0,0 +1,62 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use chrono::Utc;
+use crate::core::connector::MigrationPlan;
+
+/// A versioned migration file. It's a directory named `{version}_{name}` which contains an
+/// `up.sql` and a `down.sql`.
+#[derive(Debug)]
+pub(crate) struct MigrationFile {
+    pub(crate) version: String,
+    pub(crate) name: String,
+    pub(crate) path: PathBuf,
+}
+
+impl MigrationFile {
+
+    pub(crate) fn dir_name(&self) -> String {
+        format!("{}_{}", self.version, self.name)
+    }
+
+    pub(crate) fn up(&self) -> String {
+        fs::read_to_string(self.path.join("up.sql")).unwrap_or_default()
+    }
+
+    pub(crate) fn down(&self) -> String {
+        fs::read_to_string(self.path.join("down.sql")).unwrap_or_default()
+    }
+}
+
+pub(crate) fn migrations_dir() -> PathBuf {
+    env::current_dir().unwrap().join("migrations")
+}
+
+pub(crate) fn migration_files(dir: &Path) -> Vec<MigrationFile> {
+    let mut files: Vec<MigrationFile> = match fs::read_dir(dir) {
+        Ok(entries) => entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if !path.is_dir() || !path.join("up.sql").exists() { return None }
+            let dir_name = path.file_name()?.to_str()?.to_owned();
+            let (version, name) = dir_name.split_once('_')?;
+            Some(MigrationFile { version: version.to_owned(), name: name.to_owned(), path: path.clone() })
+        }).collect(),
+        Err(_) => vec![],
+    };
+    files.sort_by(|a, b| a.version.cmp(&b.version));
+    files
+}
+
+pub(crate) fn write_migration_file(dir: &Path, name: &str, plan: &MigrationPlan) -> io::Result<MigrationFile> {
+    let version = Utc::now().format("%Y%m%d%H%M%S").to_string();
+    let path = dir.join(format!("{}_{}", version, name));
+    fs::create_dir_all(&path)?;
+    fs::write(path.join("up.sql"), statements_to_sql(&plan.up))?;
+    fs::write(path.join("down.sql"), statements_to_sql(&plan.down))?;
+    Ok(MigrationFile { version, name: name.to_owned(), path })
+}
+
+pub(crate) fn statements_to_sql(statements: &[String]) -> String {
+    statements.iter().map(|s| format!("{};\n", s.trim().trim_end_matches(';'))).collect()
+}
@@ -1,8 +1,181 @@
-use crate::prelude::{Graph};
+pub(crate) mod files;
 
-pub(crate) async fn migrate(graph: &mut Graph, _dry_run: bool) {
-    let result = graph.connector_mut().migrate(graph.models(), false).await;
-    if result.is_err() {
-        panic!("Migration error");
+use std::path::{Path, PathBuf};
+use crate::core::app::migrate::files::{migration_files, migrations_dir, MigrationFile, statements_to_sql, write_migration_file};
+use crate::core::error::Error;
+use crate::core::pipeline::ctx::Ctx;
+use crate::core::result::Result;
+use crate::prelude::{Graph, Value};
+
+/// Migration files of the default connector are in `migrations`, those of another connector are
+/// in `migrations/{name}`.
+fn connector_migrations_dir(graph: &Graph, connector: &str) -> PathBuf {
+    if connector == graph.default_connector_name() {
+        migrations_dir()
+    } else {
+        migrations_dir().join(connector)
+    }
+}
+
+fn print_connector_header(graph: &Graph, connector: &str) {
+    if graph.connector_names().len() > 1 {
+        println!("-- connector {}", connector);
+    }
+}
+
+fn migration_error(err: Error) -> Error {
+    Error::internal_server_error(format!("Migration error: {}", err.message()))
+}
+
+pub(crate) async fn migrate(graph: &mut Graph, dry_run: bool) -> Result<()> {
+    let connector_names: Vec<String> = graph.connector_names().iter().map(|n| n.to_string()).collect();
+    for connector in connector_names.iter() {
+        let dir = connector_migrations_dir(graph, connector);
+        let models = graph.connector_models(connector);
+        if dir.exists() {
+            apply_migration_files(graph, connector, &dir, dry_run).await?;
+        } else if dry_run {
+            let plan = graph.connector_named(connector).migration_plan(&models).await.map_err(migration_error)?;
+            print_connector_header(graph, connector);
+            print!("{}", statements_to_sql(&plan.up));
+        } else {
+            graph.connector_named(connector).migrate(&models, false).await.map_err(migration_error)?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn create_migration(graph: &Graph, name: &str, dry_run: bool) -> Result<()> {
+    let mut created = false;
+    for connector in graph.connector_names() {
+        let dir = connector_migrations_dir(graph, connector);
+        if !pending_migration_files(graph, connector, &dir).await?.is_empty() {
+            return Err(Error::internal_server_error("Apply pending migration files before creating a new one."));
+        }
+        let plan = graph.connector_named(connector).migration_plan(&graph.connector_models(connector)).await.map_err(migration_error)?;
+        if plan.up.is_empty() {
+            continue;
+        }
+        created = true;
+        if dry_run {
+            print_connector_header(graph, connector);
+            print!("{}", statements_to_sql(&plan.up));
+            continue;
+        }
+        match write_migration_file(&dir, name, &plan) {
+            Ok(file) => println!("Created migration file {}", file.path.display()),
+            Err(err) => return Err(Error::internal_server_error(format!("Cannot write migration file: {}", err))),
+        }
+    }
+    if !created {
+        println!("Database is up to date, no migration file is created.");
+    }
+    Ok(())
+}
+
+/// Roll back the latest applied migration file of all connectors.
+pub(crate) async fn rollback(graph: &Graph, dry_run: bool) -> Result<()> {
+    let mut latest: Option<(&str, String)> = None;
+    for connector in graph.connector_names() {
+        let applied = graph.connector_named(connector).applied_migrations().await.map_err(migration_error)?;
+        if let Some(version) = applied.last() {
+            if latest.as_ref().map(|(_, v)| version > v).unwrap_or(true) {
+                latest = Some((connector, version.clone()));
+            }
+        }
+    }
+    let Some((connector, version)) = latest else {
+        println!("No applied migration file to roll back.");
+        return Ok(());
+    };
+    let files = migration_files(&connector_migrations_dir(graph, connector));
+    let Some(file) = files.iter().find(|f| f.version == version) else {
+        return Err(Error::internal_server_error(format!("Cannot find migration file for version {}.", version)));
+    };
+    if dry_run {
+        print!("{}", file.down());
+        return Ok(());
+    }
+    graph.connector_named(connector).revert_migration(&file.version, &file.down()).await.map_err(migration_error)?;
+    println!("Reverted migration {}", file.dir_name());
+    Ok(())
+}
+
+async fn pending_migration_files(graph: &Graph, connector: &str, dir: &Path) -> Result<Vec<MigrationFile>> {
+    let files = migration_files(dir);
+    if files.is_empty() { return Ok(files) }
+    let applied = graph.connector_named(connector).applied_migrations().await.map_err(migration_error)?;
+    Ok(files.into_iter().filter(|f| !applied.contains(&f.version)).collect())
+}
+
+async fn apply_migration_files(graph: &Graph, connector: &str, dir: &Path, dry_run: bool) -> Result<()> {
+    for file in pending_migration_files(graph, connector, dir).await? {
+        if dry_run {
+            println!("-- {}", file.dir_name());
+            print!("{}", file.up());
+            continue;
+        }
+        graph.connector_named(connector).apply_migration(&file.version, &file.name, &file.up()).await.map_err(migration_error)?;
+        run_field_migration_actions(graph, connector, &file).await?;
+    }
+    Ok(())
+}
+
+/// Run the `action` of fields whose migration `version` matches the applied migration file.
+async fn run_field_migration_actions(graph: &Graph, connector: &str, file: &MigrationFile) -> Result<()> {
+    let dir_name = file.dir_name();
+    for model in graph.models() {
+        if model.connector_name() != connector { continue }
+        for field in model.fields() {
+            let Some(migration) = field.migration() else { continue };
+            let Some(version) = &migration.version else { continue };
+            if version != &file.version && version != &dir_name { continue }
+            if let Some(action) = &migration.action {
+                let ctx = Ctx::initial_state_with_value(Value::Null);
+                action.process(ctx).await.map_err(migration_error)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::serial;
+    use crate::core::app::testing::sqlite_app;
+    use super::*;
+
+    #[tokio::test]
+    #[serial]
+    async fn rollback_without_applied_migrations_is_ok() {
+        let app = sqlite_app("model User {\n  @id @autoIncrement\n  id: Int\n}\n").await;
+        assert!(rollback(&app.graph, false).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn failed_migration_file_is_rolled_back_and_not_recorded() {
+        let app = sqlite_app("model User {\n  @id @autoIncrement\n  id: Int\n}\n").await;
+        let connector = app.graph.connector();
+        let result = connector.apply_migration("20240101000000", "add_items", "CREATE TABLE items (id INTEGER);\nINSERT INTO missing VALUES (1);").await;
+        assert!(result.unwrap_err().message().contains("Migration 20240101000000 failed"));
+        assert!(connector.applied_migrations().await.unwrap().is_empty());
+        // the table created before the failing statement is gone
+        connector.apply_migration("20240101000000", "add_items", "CREATE TABLE items (id INTEGER);").await.unwrap();
+        assert_eq!(connector.applied_migrations().await.unwrap(), vec!["20240101000000".to_owned()]);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn failed_revert_is_rolled_back_and_keeps_the_version() {
+        let app = sqlite_app("model User {\n  @id @autoIncrement\n  id: Int\n}\n").await;
+        let connector = app.graph.connector();
+        connector.apply_migration("20240101000000", "add_items", "CREATE TABLE items (id INTEGER);").await.unwrap();
+        let result = connector.revert_migration("20240101000000", "DROP TABLE items;\nDROP TABLE missing;").await;
+        assert!(result.unwrap_err().message().contains("Reverting migration 20240101000000 failed"));
+        assert_eq!(connector.applied_migrations().await.unwrap(), vec!["20240101000000".to_owned()]);
+        // the dropped table is back
+        connector.revert_migration("20240101000000", "DROP TABLE items;").await.unwrap();
+        assert!(connector.applied_migrations().await.unwrap().is_empty());
     }
 }
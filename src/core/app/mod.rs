@@ -1,21 +1,28 @@
 pub mod builder;
 pub mod environment;
 pub mod entrance;
+pub mod revocation;
 pub(crate) mod conf;
 pub(crate) mod serve;
 pub(crate) mod command;
 pub(crate) mod migrate;
+pub(crate) mod seed;
+#[cfg(test)]
+pub(crate) mod testing;
 
+use std::path::Path;
 use std::sync::Arc;
 use to_mut::ToMut;
-use crate::core::app::command::{CLI, CLICommand, GenerateCommand};
+use crate::core::app::command::{CLI, CLICommand, GenerateCommand, MigrateSubcommand};
 use crate::core::app::conf::{ClientGeneratorConf, EntityGeneratorConf, ServerConf};
 use crate::core::app::entrance::Entrance;
 use crate::core::app::environment::EnvironmentVersion;
-use crate::core::app::migrate::migrate;
+use crate::core::app::migrate::{create_migration, migrate, rollback};
+use crate::core::app::seed::seed;
 use crate::core::app::serve::serve;
 use crate::core::graph::Graph;
 use crate::generator::client::generate_client;
+use crate::generator::openapi::generate_openapi;
 use crate::generator::server::generate_entity;
 
 pub struct App {
@@ -85,10 +92,30 @@ impl App {
                             }
                         }
                     }
+                    GenerateCommand::GenerateOpenAPICommand(openapi_command) => {
+                        generate_openapi(&self.graph, &self.server_conf, Path::new(&openapi_command.output)).await?;
+                    }
                 }
             }
             CLICommand::Migrate(migrate_command) => {
-                migrate(self.graph.to_mut(), migrate_command.dry).await;
+                let result = match &migrate_command.subcommand {
+                    Some(MigrateSubcommand::Create(create_command)) => {
+                        create_migration(&self.graph, &create_command.name, migrate_command.dry).await
+                    }
+                    Some(MigrateSubcommand::Rollback) => {
+                        rollback(&self.graph, migrate_command.dry).await
+                    }
+                    None => {
+                        migrate(self.graph.to_mut(), migrate_command.dry).await
+                    }
+                };
+                if let Err(err) = result {
+                    eprintln!("{}", err.message());
+                    std::process::exit(1);
+                }
+            }
+            CLICommand::Seed(seed_command) => {
+                seed(&self.graph, seed_command.name.as_deref(), seed_command.reset).await;
             }
         }
         Ok(())
@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::core::error::ErrorType;
+use crate::core::field::{Field, FieldIndex, IndexSettings};
+use crate::core::field::r#type::FieldType;
+use crate::core::graph::Graph;
+use crate::core::graph::builder::GraphBuilder;
+use crate::core::object::Object;
+use crate::core::result::Result;
+use crate::teon;
+
+/// Stores the ids of tokens which are signed out before they expire. A custom store can be
+/// registered with `AppBuilder::revocation_store`.
+#[async_trait]
+pub trait RevocationStore: Send + Sync {
+
+    /// Mark the token with `token_id` as revoked, and return `false` if it was revoked already.
+    /// This has to be atomic, since a refresh token is exchanged only by the request which
+    /// revokes it. The entry is not needed after `expires_at`.
+    async fn revoke(&self, graph: &Graph, token_id: &str, expires_at: DateTime<Utc>) -> Result<bool>;
+
+    async fn is_revoked(&self, graph: &Graph, token_id: &str) -> Result<bool>;
+}
+
+pub(crate) const REVOKED_TOKEN_MODEL: &str = "TeoRevokedToken";
+
+/// The default store, which keeps revoked token ids in an internal database table.
+pub(crate) struct DatabaseRevocationStore { }
+
+impl DatabaseRevocationStore {
+
+    pub(crate) fn new() -> Self {
+        Self { }
+    }
+
+    pub(crate) fn install_model(graph_builder: &mut GraphBuilder) {
+        graph_builder.model(REVOKED_TOKEN_MODEL, |model_builder| {
+            model_builder.table_name("_teo_revoked_tokens");
+            model_builder.internal();
+            // the column is the primary key, so that a token is revoked by one insert only
+            let mut token_id = Field::new("tokenId".to_owned());
+            token_id.set_required();
+            token_id.field_type = Some(FieldType::String);
+            token_id.primary = true;
+            token_id.index = Some(FieldIndex::Primary(IndexSettings::default()));
+            model_builder.field(token_id);
+            let mut expires_at = Field::new("expiresAt".to_owned());
+            expires_at.set_required();
+            expires_at.field_type = Some(FieldType::DateTime);
+            model_builder.field(expires_at);
+        });
+    }
+}
+
+#[async_trait]
+impl RevocationStore for DatabaseRevocationStore {
+
+    async fn revoke(&self, graph: &Graph, token_id: &str, expires_at: DateTime<Utc>) -> Result<bool> {
+        let expired: Vec<Object> = graph.find_many(REVOKED_TOKEN_MODEL, &teon!({
+            "where": { "expiresAt": { "lt": Utc::now() } }
+        })).await?;
+        for object in expired {
+            object.delete().await?;
+        }
+        let object = graph.create_object(REVOKED_TOKEN_MODEL, teon!({
+            "tokenId": token_id,
+            "expiresAt": expires_at,
+        })).await?;
+        // only one of concurrent revocations of a token inserts the row
+        match object.save().await {
+            Ok(()) => Ok(true),
+            Err(err) if err.r#type == ErrorType::ValidationError && err.errors.as_ref().is_some_and(|e| e.contains_key("tokenId")) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn is_revoked(&self, graph: &Graph, token_id: &str) -> Result<bool> {
+        Ok(graph.count(REVOKED_TOKEN_MODEL, &teon!({
+            "where": { "tokenId": token_id }
+        })).await? > 0)
+    }
+}
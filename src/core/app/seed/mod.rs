@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::{Map as JsonMap, Value as JsonValue};
+use crate::core::graph::Graph;
+use crate::core::model::Model;
+use crate::core::object::Object;
+use crate::core::teon::decoder::Decoder;
+use crate::prelude::Value;
+use crate::teon;
+
+/// A dataset is a JSON file `datasets/{name}.json`. It maps model names to named records. A
+/// relation of a record holds the name of the related record in the same dataset, or an array
+/// of names for vector relations:
+///
+/// ```json
+/// {
+///     "User": { "alice": { "email": "alice@example.com" } },
+///     "Post": { "hello": { "title": "Hello", "author": "alice" } }
+/// }
+/// ```
+#[derive(Debug)]
+pub(crate) struct Dataset {
+    pub(crate) name: String,
+    pub(crate) records: Vec<Record>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Record {
+    pub(crate) model: String,
+    pub(crate) name: String,
+    pub(crate) value: JsonMap<String, JsonValue>,
+}
+
+impl Record {
+
+    fn references<'a>(&'a self, model: &'a Model) -> impl Iterator<Item = (&'a str, &'a str)> + 'a {
+        self.value.iter().filter_map(|(key, value)| {
+            model.relation(key).map(|relation| (relation.model(), value))
+        }).flat_map(|(related_model, value)| {
+            let names: Vec<&str> = match value {
+                JsonValue::String(name) => vec![name.as_str()],
+                JsonValue::Array(names) => names.iter().filter_map(|n| n.as_str()).collect(),
+                _ => vec![],
+            };
+            names.into_iter().map(move |name| (related_model, name))
+        })
+    }
+}
+
+pub(crate) fn datasets_dir() -> PathBuf {
+    env::current_dir().unwrap().join("datasets")
+}
+
+fn dataset_names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = match fs::read_dir(dir) {
+        Ok(entries) => entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()?.to_str()? != "json" { return None }
+            Some(path.file_stem()?.to_str()?.to_owned())
+        }).collect(),
+        Err(_) => vec![],
+    };
+    names.sort();
+    names
+}
+
+fn load_dataset(graph: &Graph, dir: &Path, name: &str) -> Dataset {
+    let path = dir.join(format!("{name}.json"));
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(err) => panic!("Cannot read dataset file {}: {}", path.display(), err),
+    };
+    let json: JsonValue = match serde_json::from_str(&content) {
+        Ok(json) => json,
+        Err(err) => panic!("Dataset '{name}' is not valid JSON: {err}"),
+    };
+    let Some(models) = json.as_object() else {
+        panic!("Dataset '{name}' should be an object of models.");
+    };
+    let mut records = vec![];
+    for (model_name, model_records) in models {
+        match graph.model(model_name) {
+            Some(model) if !model.internal() => (),
+            _ => panic!("Model '{model_name}' in dataset '{name}' is not defined."),
+        }
+        let Some(model_records) = model_records.as_object() else {
+            panic!("Records of model '{model_name}' in dataset '{name}' should be an object.");
+        };
+        for (record_name, value) in model_records {
+            let Some(value) = value.as_object() else {
+                panic!("Record '{record_name}' of model '{model_name}' in dataset '{name}' should be an object.");
+            };
+            records.push(Record { model: model_name.clone(), name: record_name.clone(), value: value.clone() });
+        }
+    }
+    Dataset { name: name.to_owned(), records }
+}
+
+/// Sort records so that every record comes after the records it references.
+fn ordered_records<'a>(graph: &Graph, dataset: &'a Dataset) -> Vec<&'a Record> {
+    for record in dataset.records.iter() {
+        let model = graph.model(&record.model).unwrap();
+        for (related_model, name) in record.references(model) {
+            if !dataset.records.iter().any(|r| r.model == related_model && r.name == name) {
+                panic!("Record '{}' of model '{}' references '{}' of model '{}', which is not in dataset '{}'.", record.name, record.model, name, related_model, dataset.name);
+            }
+        }
+    }
+    let mut ordered: Vec<&Record> = vec![];
+    let mut pending: Vec<&Record> = dataset.records.iter().collect();
+    while !pending.is_empty() {
+        let (ready, rest): (Vec<&Record>, Vec<&Record>) = pending.into_iter().partition(|record| {
+            let model = graph.model(&record.model).unwrap();
+            record.references(model).all(|(related_model, name)| {
+                ordered.iter().any(|r| r.model == related_model && r.name == name)
+            })
+        });
+        if ready.is_empty() {
+            let names: Vec<String> = rest.iter().map(|r| format!("{}.{}", r.model, r.name)).collect();
+            panic!("Records {} of dataset '{}' reference each other.", names.join(", "), dataset.name);
+        }
+        ordered.extend(ready);
+        pending = rest;
+    }
+    ordered
+}
+
+/// Delete every record of the models in `records`, dependents first.
+async fn reset_records(graph: &Graph, records: &Vec<&Record>) {
+    let mut model_names: Vec<&str> = vec![];
+    for record in records.iter().rev() {
+        if !model_names.contains(&record.model.as_str()) {
+            model_names.push(record.model.as_str());
+        }
+    }
+    for model_name in model_names {
+        let objects: Vec<Object> = match graph.find_many(model_name, &teon!({"withDeleted": true})).await {
+            Ok(objects) => objects,
+            Err(err) => panic!("Cannot reset records of model '{model_name}': {}", err.message()),
+        };
+        for object in objects {
+            if let Err(err) = object.purge().await {
+                panic!("Cannot reset records of model '{model_name}': {}", err.message());
+            }
+        }
+    }
+}
+
+/// Create `record`, or update the existing record which has the same unique key.
+async fn seed_record(graph: &Graph, record: &Record, seeded: &HashMap<(String, String), Object>) -> Object {
+    let model = graph.model(&record.model).unwrap();
+    let scalars: JsonMap<String, JsonValue> = record.value.iter().filter(|(k, _)| model.relation(k).is_none()).map(|(k, v)| (k.clone(), v.clone())).collect();
+    let mut value = match Decoder::decode_object(model, graph, &JsonValue::Object(scalars)) {
+        Ok(value) => value,
+        Err(err) => panic!("Record '{}' of model '{}' is invalid: {}", record.name, record.model, err.message()),
+    };
+    let map = value.as_hashmap_mut().unwrap();
+    for (key, names) in record.value.iter().filter(|(k, _)| model.relation(k).is_some()) {
+        let relation = model.relation(key).unwrap();
+        let identifier = |name: &str| seeded.get(&(relation.model().to_owned(), name.to_owned())).unwrap().identifier();
+        let manipulation = match names {
+            JsonValue::String(name) if !relation.is_vec() => teon!({"connect": (identifier(name))}),
+            JsonValue::Array(names) if relation.is_vec() => {
+                teon!({"set": (Value::Vec(names.iter().map(|n| identifier(n.as_str().unwrap_or_default())).collect()))})
+            }
+            _ => panic!("Relation '{}' of record '{}' should be {}.", key, record.name, if relation.is_vec() { "an array of record names" } else { "a record name" }),
+        };
+        map.insert(key.clone(), manipulation);
+    }
+    let Some(unique_keys) = model.unique_query_keys().iter().find(|keys| keys.iter().all(|k| map.contains_key(k))) else {
+        panic!("Record '{}' of model '{}' doesn't have a unique key to upsert with.", record.name, record.model);
+    };
+    let r#where = Value::HashMap(unique_keys.iter().map(|k| (k.clone(), map.get(k).unwrap().clone())).collect());
+    let object = match graph.find_unique::<Object>(model.name(), &teon!({"where": r#where, "withDeleted": true})).await {
+        Ok(object) => {
+            for key in unique_keys {
+                map.remove(key);
+            }
+            if let Err(err) = object.set_teon(&value).await {
+                panic!("Cannot update record '{}' of model '{}': {}", record.name, record.model, err.message());
+            }
+            object
+        }
+        Err(_) => match graph.create_object(model.name(), &value).await {
+            Ok(object) => object,
+            Err(err) => panic!("Cannot create record '{}' of model '{}': {}", record.name, record.model, err.message()),
+        }
+    };
+    if let Err(err) = object.save().await {
+        panic!("Cannot save record '{}' of model '{}': {}", record.name, record.model, err.message());
+    }
+    object
+}
+
+/// Seed the dataset named `name`, or every dataset in the datasets directory.
+pub(crate) async fn seed(graph: &Graph, name: Option<&str>, reset: bool) {
+    let dir = datasets_dir();
+    let names = match name {
+        Some(name) => vec![name.to_owned()],
+        None => dataset_names(&dir),
+    };
+    if names.is_empty() {
+        println!("No dataset is found in {}.", dir.display());
+        return;
+    }
+    for name in names {
+        let dataset = load_dataset(graph, &dir, &name);
+        let seeded = seed_dataset(graph, &dataset, reset).await;
+        println!("Seeded dataset {} with {} records", dataset.name, seeded.len());
+    }
+}
+
+/// Seed the records of `dataset`, returning the seeded objects by model and record name.
+async fn seed_dataset(graph: &Graph, dataset: &Dataset, reset: bool) -> HashMap<(String, String), Object> {
+    let records = ordered_records(graph, dataset);
+    if reset {
+        reset_records(graph, &records).await;
+    }
+    let mut seeded: HashMap<(String, String), Object> = HashMap::new();
+    for record in records.iter() {
+        let object = seed_record(graph, record, &seeded).await;
+        seeded.insert((record.model.clone(), record.name.clone()), object);
+    }
+    seeded
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::serial;
+    use uuid::Uuid;
+    use crate::core::app::testing::sqlite_app;
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model User {
+  @id @autoIncrement
+  id: Int
+  @unique
+  email: String
+  name: String?
+  @relation(fields: .id, references: .authorId)
+  posts: Post[]
+}
+
+model Post {
+  @id @autoIncrement
+  id: Int
+  @unique
+  title: String
+  @foreignKey
+  authorId: Int
+  @relation(fields: .authorId, references: .id)
+  author: User
+}
+"#;
+
+    fn dataset(graph: &Graph, json: &str) -> Dataset {
+        let dir = env::temp_dir().join(format!("teo-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("default.json"), json).unwrap();
+        assert_eq!(dataset_names(&dir), vec!["default".to_owned()]);
+        load_dataset(graph, &dir, "default")
+    }
+
+    async fn count(graph: &Graph, model: &str) -> usize {
+        graph.find_many::<Object>(model, &teon!({})).await.unwrap().len()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn referenced_records_are_seeded_first() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        let dataset = dataset(graph, r#"{
+            "Post": { "hello": { "title": "Hello", "author": "alice" } },
+            "User": { "alice": { "email": "alice@example.com" } }
+        }"#);
+        let order: Vec<&str> = ordered_records(graph, &dataset).iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(order, vec!["alice", "hello"]);
+        let seeded = seed_dataset(graph, &dataset, false).await;
+        let alice = seeded.get(&("User".to_owned(), "alice".to_owned())).unwrap();
+        let hello = seeded.get(&("Post".to_owned(), "hello".to_owned())).unwrap();
+        assert_eq!(hello.get_value("authorId").unwrap(), alice.get_value("id").unwrap());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn seeding_again_updates_records_by_unique_key() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        let first = dataset(graph, r#"{ "User": { "alice": { "email": "alice@example.com", "name": "Alice" } } }"#);
+        seed_dataset(graph, &first, false).await;
+        let second = dataset(graph, r#"{ "User": { "alice": { "email": "alice@example.com", "name": "Alice B." } } }"#);
+        seed_dataset(graph, &second, false).await;
+        let users: Vec<Object> = graph.find_many("User", &teon!({})).await.unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].get_value("name").unwrap(), teon!("Alice B."));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn reset_deletes_records_which_are_not_in_the_dataset() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        let user = graph.create_object("User", teon!({"email": "bob@example.com"})).await.unwrap();
+        user.save().await.unwrap();
+        let dataset = dataset(graph, r#"{
+            "User": { "alice": { "email": "alice@example.com" } },
+            "Post": { "hello": { "title": "Hello", "author": "alice" } }
+        }"#);
+        seed_dataset(graph, &dataset, true).await;
+        let users: Vec<Object> = graph.find_many("User", &teon!({})).await.unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].get_value("email").unwrap(), teon!("alice@example.com"));
+        assert_eq!(count(graph, "Post").await, 1);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn references_outside_the_dataset_are_rejected() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        let dataset = dataset(graph, r#"{ "Post": { "hello": { "title": "Hello", "author": "bob" } } }"#);
+        let message = *std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| ordered_records(graph, &dataset))).unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(message, "Record 'hello' of model 'Post' references 'bob' of model 'User', which is not in dataset 'default'.");
+    }
+}
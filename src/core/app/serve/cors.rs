@@ -0,0 +1,39 @@
+use actix_http::header::{HeaderMap, HeaderName, HeaderValue, ORIGIN, VARY};
+use actix_http::header::{ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_MAX_AGE};
+use crate::core::app::conf::CorsConf;
+
+/// The methods which the server routes. Actions are POST, health checks and GraphQL queries
+/// can also be GET.
+const ALLOWED_METHODS: &str = "GET, POST, OPTIONS";
+
+/// The origin of a request as it should be echoed back, or `None` if it's not allowed. A
+/// wildcard is never combined with credentials, the config resolver rejects it.
+fn allowed_origin(conf: &CorsConf, request_headers: &HeaderMap) -> Option<String> {
+    if conf.origins.iter().any(|o| o == "*") {
+        return Some("*".to_owned());
+    }
+    let origin = request_headers.get(ORIGIN).and_then(|o| o.to_str().ok())?;
+    conf.origins.iter().any(|o| o == origin).then(|| origin.to_owned())
+}
+
+fn insert(headers: &mut HeaderMap, name: HeaderName, value: impl AsRef<str>) {
+    if let Ok(value) = HeaderValue::from_str(value.as_ref()) {
+        headers.insert(name, value);
+    }
+}
+
+pub(crate) fn apply_cors_headers(conf: &CorsConf, request_headers: &HeaderMap, response_headers: &mut HeaderMap) {
+    let Some(origin) = allowed_origin(conf, request_headers) else {
+        return
+    };
+    if origin != "*" {
+        insert(response_headers, VARY, "Origin");
+    }
+    insert(response_headers, ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+    insert(response_headers, ACCESS_CONTROL_ALLOW_METHODS, ALLOWED_METHODS);
+    insert(response_headers, ACCESS_CONTROL_ALLOW_HEADERS, conf.headers.join(", "));
+    insert(response_headers, ACCESS_CONTROL_MAX_AGE, conf.max_age.to_string());
+    if conf.credentials {
+        insert(response_headers, ACCESS_CONTROL_ALLOW_CREDENTIALS, "true");
+    }
+}
@@ -0,0 +1,126 @@
+use serde_json::{json, Map, Value as JsonValue};
+use super::Executor;
+use super::parser::{Field, Selection};
+use super::schema::{FieldDef, Schema, TypeDef, TypeKind};
+
+const DIRECTIVES: [(&str, &str, &[&str]); 3] = [
+    ("skip", "Boolean!", &["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"]),
+    ("include", "Boolean!", &["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"]),
+    ("deprecated", "String", &["FIELD_DEFINITION", "ARGUMENT_DEFINITION", "INPUT_FIELD_DEFINITION", "ENUM_VALUE"]),
+];
+
+/// Answers `__schema` and `__type` from the same schema which is served on `GET /graphql`. Types
+/// are resolved by the selection, so type references can be followed as deep as a query asks.
+impl<'a> Executor<'a> {
+
+    pub(super) fn resolve_schema(&self, schema: &Schema, field: &Field) -> Result<JsonValue, String> {
+        self.introspect(&field.selections, "__Schema", |f| Ok(match f.name.as_str() {
+            "description" | "subscriptionType" => JsonValue::Null,
+            "types" => JsonValue::Array(schema.types.iter().map(|t| self.resolve_type(schema, t, &f.selections)).collect::<Result<_, _>>()?),
+            "queryType" => self.resolve_type_name(schema, "Query", &f.selections)?,
+            "mutationType" => self.resolve_type_name(schema, "Mutation", &f.selections)?,
+            "directives" => JsonValue::Array(DIRECTIVES.iter().map(|d| self.resolve_directive(schema, d, &f.selections)).collect::<Result<_, _>>()?),
+            name => return Err(format!("Cannot query field '{name}' on type '__Schema'.")),
+        }))
+    }
+
+    pub(super) fn resolve_type_name(&self, schema: &Schema, name: &str, selections: &Vec<Selection>) -> Result<JsonValue, String> {
+        match schema.r#type(name) {
+            Some(t) => self.resolve_type(schema, t, selections),
+            None => Ok(JsonValue::Null),
+        }
+    }
+
+    fn resolve_type(&self, schema: &Schema, t: &TypeDef, selections: &Vec<Selection>) -> Result<JsonValue, String> {
+        self.introspect(selections, "__Type", |f| Ok(match f.name.as_str() {
+            "kind" => json!(t.kind.as_str()),
+            "name" => json!(t.name),
+            "description" | "specifiedByURL" | "ofType" | "possibleTypes" => JsonValue::Null,
+            "fields" => if t.kind == TypeKind::Object {
+                JsonValue::Array(t.fields.iter().map(|d| self.resolve_field(schema, d, &f.selections)).collect::<Result<_, _>>()?)
+            } else {
+                JsonValue::Null
+            },
+            "inputFields" => if t.kind == TypeKind::InputObject {
+                JsonValue::Array(t.fields.iter().map(|d| self.resolve_input_value(schema, d, &f.selections)).collect::<Result<_, _>>()?)
+            } else {
+                JsonValue::Null
+            },
+            "interfaces" => if t.kind == TypeKind::Object { json!([]) } else { JsonValue::Null },
+            "enumValues" => if t.kind == TypeKind::Enum {
+                JsonValue::Array(t.values.iter().map(|v| self.introspect(&f.selections, "__EnumValue", |f| Ok(match f.name.as_str() {
+                    "name" => json!(v),
+                    "description" | "deprecationReason" => JsonValue::Null,
+                    "isDeprecated" => json!(false),
+                    name => return Err(format!("Cannot query field '{name}' on type '__EnumValue'.")),
+                }))).collect::<Result<_, _>>()?)
+            } else {
+                JsonValue::Null
+            },
+            "isOneOf" => if t.kind == TypeKind::InputObject { json!(false) } else { JsonValue::Null },
+            name => return Err(format!("Cannot query field '{name}' on type '__Type'.")),
+        }))
+    }
+
+    /// A type reference like `[Post!]!` is a chain of `NON_NULL` and `LIST` wrappers around a
+    /// named type.
+    fn resolve_type_ref(&self, schema: &Schema, r#type: &str, selections: &Vec<Selection>) -> Result<JsonValue, String> {
+        let (kind, inner) = if let Some(inner) = r#type.strip_suffix('!') {
+            ("NON_NULL", inner)
+        } else if let Some(inner) = r#type.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+            ("LIST", inner)
+        } else {
+            return self.resolve_type_name(schema, r#type, selections);
+        };
+        self.introspect(selections, "__Type", |f| Ok(match f.name.as_str() {
+            "kind" => json!(kind),
+            "ofType" => self.resolve_type_ref(schema, inner, &f.selections)?,
+            "name" | "description" | "specifiedByURL" | "fields" | "inputFields" | "interfaces" |
+            "enumValues" | "possibleTypes" | "isOneOf" => JsonValue::Null,
+            name => return Err(format!("Cannot query field '{name}' on type '__Type'.")),
+        }))
+    }
+
+    fn resolve_field(&self, schema: &Schema, field: &FieldDef, selections: &Vec<Selection>) -> Result<JsonValue, String> {
+        self.introspect(selections, "__Field", |f| Ok(match f.name.as_str() {
+            "name" => json!(field.name),
+            "description" | "deprecationReason" => JsonValue::Null,
+            "args" => JsonValue::Array(field.args.iter().map(|a| self.resolve_input_value(schema, a, &f.selections)).collect::<Result<_, _>>()?),
+            "type" => self.resolve_type_ref(schema, &field.r#type, &f.selections)?,
+            "isDeprecated" => json!(false),
+            name => return Err(format!("Cannot query field '{name}' on type '__Field'.")),
+        }))
+    }
+
+    fn resolve_input_value(&self, schema: &Schema, value: &FieldDef, selections: &Vec<Selection>) -> Result<JsonValue, String> {
+        self.introspect(selections, "__InputValue", |f| Ok(match f.name.as_str() {
+            "name" => json!(value.name),
+            "description" | "defaultValue" | "deprecationReason" => JsonValue::Null,
+            "type" => self.resolve_type_ref(schema, &value.r#type, &f.selections)?,
+            "isDeprecated" => json!(false),
+            name => return Err(format!("Cannot query field '{name}' on type '__InputValue'.")),
+        }))
+    }
+
+    fn resolve_directive(&self, schema: &Schema, directive: &(&str, &str, &[&str]), selections: &Vec<Selection>) -> Result<JsonValue, String> {
+        let (name, argument_type, locations) = directive;
+        let argument = FieldDef { name: if *name == "deprecated" { "reason" } else { "if" }.to_owned(), args: vec![], r#type: argument_type.to_string() };
+        self.introspect(selections, "__Directive", |f| Ok(match f.name.as_str() {
+            "name" => json!(name),
+            "description" => JsonValue::Null,
+            "locations" => json!(locations),
+            "args" => json!([self.resolve_input_value(schema, &argument, &f.selections)?]),
+            "isRepeatable" => json!(false),
+            name => return Err(format!("Cannot query field '{name}' on type '__Directive'.")),
+        }))
+    }
+
+    fn introspect<F>(&self, selections: &Vec<Selection>, type_name: &str, resolve: F) -> Result<JsonValue, String> where F: Fn(&Field) -> Result<JsonValue, String> {
+        let mut result = Map::new();
+        for field in self.collect_fields(selections, 0)? {
+            let value = if field.name == "__typename" { json!(type_name) } else { resolve(field)? };
+            result.insert(field.response_key().to_owned(), value);
+        }
+        Ok(JsonValue::Object(result))
+    }
+}
@@ -0,0 +1,481 @@
+pub(crate) mod parser;
+pub(crate) mod schema;
+mod introspection;
+
+use std::time::SystemTime;
+use actix_http::Method;
+use actix_web::{HttpRequest, HttpResponse, web};
+use inflector::Inflector;
+use serde_json::{json, Map, Value as JsonValue};
+use crate::core::action::{
+    Action, ResMeta, AGGREGATE_HANDLER, COUNT_HANDLER, FIND_FIRST_HANDLER, FIND_MANY_HANDLER,
+    FIND_UNIQUE_HANDLER, GROUP_BY_HANDLER, IDENTITY_HANDLER,
+};
+use crate::core::app::conf::ServerConf;
+use crate::core::graph::Graph;
+use crate::core::model::Model;
+use crate::core::object::Object;
+use self::parser::{Document, Field, Operation, OperationKind, Selection, parse_document};
+use self::schema::{build_schema, generate_schema};
+use super::{get_identity, handle_action, log_request, log_unhandled, read_json_body};
+use super::jwt_token::Claims;
+
+pub(crate) fn is_query(action: Action) -> bool {
+    matches!(action.to_u32(), FIND_UNIQUE_HANDLER | FIND_FIRST_HANDLER | FIND_MANY_HANDLER | COUNT_HANDLER |
+        AGGREGATE_HANDLER | GROUP_BY_HANDLER | IDENTITY_HANDLER)
+}
+
+/// Sign in needs credentials, models without identity keys don't have the operation.
+pub(crate) fn has_operation(model: &Model, action: Action) -> bool {
+    model.actions().contains(&action) && !(action.handler_requires_credentials() && model.auth_identity_keys().is_empty())
+}
+
+/// Operations are named after the model and the handler, e.g. `userFindMany`.
+pub(crate) fn operation_field_name(model: &Model, action: Action) -> String {
+    format!("{}{}", model.name().to_camel_case(), action.as_handler_str().to_pascal_case())
+}
+
+fn error_response(message: impl AsRef<str>) -> JsonValue {
+    json!({ "errors": [{ "message": message.as_ref() }] })
+}
+
+/// Responses encode dates and decimals as `$date` and `$decimal` objects, GraphQL clients
+/// expect plain scalars.
+fn unwrap_wire_values(value: JsonValue) -> JsonValue {
+    match value {
+        JsonValue::Object(map) => {
+            if map.len() == 1 {
+                if let Some(inner) = map.get("$date").or(map.get("$decimal")) {
+                    return inner.clone();
+                }
+            }
+            JsonValue::Object(map.into_iter().map(|(k, v)| (k, unwrap_wire_values(v))).collect())
+        }
+        JsonValue::Array(vec) => JsonValue::Array(vec.into_iter().map(unwrap_wire_values).collect()),
+        _ => value,
+    }
+}
+
+pub(crate) async fn handle_graphql(graph: &'static Graph, conf: &'static ServerConf, r: &HttpRequest, payload: &mut web::Payload, start: SystemTime) -> HttpResponse {
+    let path = r.path();
+    if r.method() == Method::GET {
+        return HttpResponse::Ok().content_type("text/plain; charset=utf-8").body(generate_schema(graph));
+    }
+    if r.method() == Method::OPTIONS {
+        return HttpResponse::Ok().json(json!({}));
+    }
+    if r.method() != Method::POST {
+        log_unhandled(start, r.method().as_str(), path, 404);
+        return crate::core::error::Error::destination_not_found().into();
+    }
+    let body = match read_json_body(payload, conf.body_limit).await {
+        Ok(body) => body,
+        Err(response) => {
+            log_unhandled(start, r.method().as_str(), path, response.status().as_u16());
+            return response;
+        }
+    };
+    let Some(query) = body.get("query").and_then(|q| q.as_str()) else {
+        log_unhandled(start, r.method().as_str(), path, 400);
+        return HttpResponse::BadRequest().json(error_response("Missing 'query'."));
+    };
+    let document = match parse_document(query) {
+        Ok(document) => document,
+        Err(message) => {
+            log_unhandled(start, r.method().as_str(), path, 400);
+            return HttpResponse::BadRequest().json(error_response(message));
+        }
+    };
+    let operation_name = body.get("operationName").and_then(|n| n.as_str());
+    let operation = match operation_name {
+        Some(name) => document.operations.iter().find(|o| o.name.as_deref() == Some(name)),
+        None => if document.operations.len() == 1 { document.operations.first() } else { None },
+    };
+    let Some(operation) = operation else {
+        log_unhandled(start, r.method().as_str(), path, 400);
+        return HttpResponse::BadRequest().json(error_response(match operation_name {
+            Some(name) => format!("Unknown operation named '{name}'."),
+            None => "Must provide operation name if query contains multiple operations.".to_owned(),
+        }));
+    };
+    let identity = match get_identity(r, graph, conf).await {
+        Ok(identity) => identity,
+        Err(err) => return HttpResponse::Unauthorized().json(json!({
+            "errors": [{ "message": err.message(), "extensions": { "type": err.r#type } }]
+        })),
+    };
+    let mut variables = body.get("variables").and_then(|v| v.as_object()).cloned().unwrap_or_default();
+    for (name, default) in &operation.variable_defaults {
+        if !variables.contains_key(name) {
+            variables.insert(name.clone(), default.to_json(&Map::new()));
+        }
+    }
+    let executor = Executor { graph, conf, document: &document, variables, identity };
+    let result = executor.execute(operation).await;
+    let label = operation.name.clone().unwrap_or(match operation.kind {
+        OperationKind::Query => "query".to_owned(),
+        OperationKind::Mutation => "mutation".to_owned(),
+    });
+    log_request(start, "graphql", &label, 200);
+    HttpResponse::Ok().json(result)
+}
+
+struct Executor<'a> {
+    graph: &'a Graph,
+    conf: &'a ServerConf,
+    document: &'a Document,
+    variables: Map<String, JsonValue>,
+    identity: Option<(Object, Claims)>,
+}
+
+impl<'a> Executor<'a> {
+
+    /// Top level fields are performed one after another, so mutations run in document order.
+    async fn execute(&self, operation: &Operation) -> JsonValue {
+        let type_name = match operation.kind {
+            OperationKind::Query => "Query",
+            OperationKind::Mutation => "Mutation",
+        };
+        let mut data = Map::new();
+        let mut errors: Vec<JsonValue> = vec![];
+        let fields = match self.collect_fields(&operation.selections, 0) {
+            Ok(fields) => fields,
+            Err(message) => return error_response(message),
+        };
+        for field in fields {
+            let key = field.response_key().to_owned();
+            if field.name == "__typename" {
+                data.insert(key, json!(type_name));
+                continue;
+            }
+            if operation.kind == OperationKind::Query && (field.name == "__schema" || field.name == "__type") {
+                let schema = build_schema(self.graph);
+                let result = if field.name == "__schema" {
+                    self.resolve_schema(&schema, field)
+                } else {
+                    match field.arguments.iter().find(|(k, _)| k == "name").map(|(_, v)| v.to_json(&self.variables)) {
+                        Some(JsonValue::String(name)) => self.resolve_type_name(&schema, &name, &field.selections),
+                        _ => Err("Field '__type' argument 'name' of type 'String!' is required.".to_owned()),
+                    }
+                };
+                match result {
+                    Ok(value) => { data.insert(key, value); }
+                    Err(message) => {
+                        errors.push(json!({ "message": message, "path": [key] }));
+                        data.insert(key, JsonValue::Null);
+                    }
+                }
+                continue;
+            }
+            let found = self.graph.models().iter().find_map(|model| {
+                Action::handlers_iter().find(|action| {
+                    has_operation(model, **action)
+                        && is_query(**action) == (operation.kind == OperationKind::Query)
+                        && operation_field_name(model, **action) == field.name
+                }).map(|action| (model, *action))
+            });
+            let Some((model, action)) = found else {
+                errors.push(json!({ "message": format!("Cannot query field '{}' on type '{type_name}'.", field.name), "path": [key] }));
+                data.insert(key, JsonValue::Null);
+                continue;
+            };
+            match self.resolve(model, action, field).await {
+                Ok(value) => { data.insert(key, value); }
+                Err(mut error) => {
+                    error.as_object_mut().unwrap().insert("path".to_owned(), json!([key]));
+                    errors.push(error);
+                    data.insert(key, JsonValue::Null);
+                }
+            }
+        }
+        if errors.is_empty() {
+            json!({ "data": data })
+        } else {
+            json!({ "data": data, "errors": errors })
+        }
+    }
+
+    async fn resolve(&self, model: &Model, action: Action, field: &Field) -> Result<JsonValue, JsonValue> {
+        let mut args: Map<String, JsonValue> = field.arguments.iter().map(|(k, v)| (k.clone(), v.to_json(&self.variables))).collect();
+        let returns_model = !(action.to_u32() == COUNT_HANDLER || action.to_u32() == AGGREGATE_HANDLER || action.to_u32() == GROUP_BY_HANDLER);
+        let sign_in = action.handler_res_meta() == ResMeta::TokenInfo;
+        let model_selections: Vec<Selection> = if sign_in {
+            let mut selections = vec![];
+            for subfield in self.collect_fields(&field.selections, 0).map_err(|m| json!({ "message": m }))? {
+                match subfield.name.as_str() {
+                    "data" => selections.extend(subfield.selections.iter().cloned()),
+                    "token" | "refreshToken" | "__typename" => (),
+                    name => return Err(json!({ "message": format!("Cannot query field '{name}' on type '{}SignInResult'.", model.name()) })),
+                }
+            }
+            selections
+        } else {
+            field.selections.clone()
+        };
+        if returns_model {
+            if field.selections.is_empty() {
+                return Err(json!({ "message": format!("Field '{}' must have a selection of subfields.", field.name) }));
+            }
+            let include = self.include_for(model, &model_selections).map_err(|m| json!({ "message": m }))?;
+            if !include.is_empty() && !args.contains_key("include") {
+                args.insert("include".to_owned(), JsonValue::Object(include));
+            }
+        } else if !field.selections.is_empty() {
+            return Err(json!({ "message": format!("Field '{}' must not have a selection since its type has no subfields.", field.name) }));
+        }
+        let response = handle_action(self.graph, self.conf, model, action, &JsonValue::Object(args), self.identity.clone()).await;
+        let status = response.status();
+        let body: JsonValue = match actix_web::body::to_bytes(response.into_body()).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or(JsonValue::Null),
+            Err(_) => JsonValue::Null,
+        };
+        if !status.is_success() {
+            let single_find = action.to_u32() == FIND_UNIQUE_HANDLER || action.to_u32() == FIND_FIRST_HANDLER;
+            if single_find && status.as_u16() == 404 {
+                return Ok(JsonValue::Null);
+            }
+            let error = body.get("error").cloned().unwrap_or(JsonValue::Null);
+            return Err(json!({
+                "message": error.get("message").cloned().unwrap_or(json!("Unknown error.")),
+                "extensions": { "type": error.get("type"), "errors": error.get("errors") },
+            }));
+        }
+        let data = body.get("data").cloned().unwrap_or(JsonValue::Null);
+        if !returns_model {
+            return Ok(unwrap_wire_values(data));
+        }
+        if sign_in {
+            let mut result = Map::new();
+            for subfield in self.collect_fields(&field.selections, 0).unwrap_or_default() {
+                let value = match subfield.name.as_str() {
+                    "token" | "refreshToken" => body.get("meta").and_then(|m| m.get(subfield.name.as_str())).cloned().unwrap_or(JsonValue::Null),
+                    "data" => self.project(model, &data, &subfield.selections),
+                    _ => json!(format!("{}SignInResult", model.name())),
+                };
+                result.insert(subfield.response_key().to_owned(), value);
+            }
+            return Ok(JsonValue::Object(result));
+        }
+        Ok(self.project(model, &data, &field.selections))
+    }
+
+    /// Flatten fragments and apply `@skip` and `@include`.
+    fn collect_fields<'b>(&'b self, selections: &'b Vec<Selection>, depth: usize) -> Result<Vec<&'b Field>, String> where 'a: 'b {
+        if depth > 32 {
+            return Err("Fragments are nested too deeply.".to_owned());
+        }
+        let mut fields = vec![];
+        for selection in selections {
+            let (directives, nested) = match selection {
+                Selection::Field(field) => (&field.directives, None),
+                Selection::FragmentSpread(name, directives) => {
+                    let Some(fragment) = self.document.fragments.get(name) else {
+                        return Err(format!("Unknown fragment '{name}'."));
+                    };
+                    (directives, Some(fragment))
+                }
+                Selection::InlineFragment(selections, directives) => (directives, Some(selections)),
+            };
+            let mut included = true;
+            for directive in directives {
+                let condition = directive.arguments.iter().find(|(k, _)| k == "if").map(|(_, v)| v.to_json(&self.variables));
+                let condition = condition.and_then(|c| c.as_bool()).unwrap_or(false);
+                match directive.name.as_str() {
+                    "skip" => included = included && !condition,
+                    "include" => included = included && condition,
+                    _ => (),
+                }
+            }
+            if !included { continue }
+            match (selection, nested) {
+                (Selection::Field(field), _) => fields.push(field),
+                (_, Some(nested)) => fields.extend(self.collect_fields(nested, depth + 1)?),
+                _ => (),
+            }
+        }
+        Ok(fields)
+    }
+
+    /// Relations in the selection are fetched with `include`, which loads all related records
+    /// of a level in one query and applies the same read permissions as REST requests. Relation
+    /// fields aren't resolved one record at a time, so a list doesn't cost a query per record.
+    fn include_for(&self, model: &Model, selections: &Vec<Selection>) -> Result<Map<String, JsonValue>, String> {
+        let mut include = Map::new();
+        for field in self.collect_fields(selections, 0)? {
+            if field.name == "__typename" { continue }
+            if !model.output_keys().contains(&field.name) {
+                return Err(format!("Cannot query field '{}' on type '{}'.", field.name, model.name()));
+            }
+            if let Some(relation) = model.relation(&field.name) {
+                if field.selections.is_empty() {
+                    return Err(format!("Field '{}' of type '{}' must have a selection of subfields.", field.name, relation.model()));
+                }
+                let relation_model = self.graph.model(relation.model()).unwrap();
+                let mut args: Map<String, JsonValue> = field.arguments.iter().map(|(k, v)| (k.clone(), v.to_json(&self.variables))).collect();
+                let nested = self.include_for(relation_model, &field.selections)?;
+                if !nested.is_empty() {
+                    args.insert("include".to_owned(), JsonValue::Object(nested));
+                }
+                include.insert(field.name.clone(), if args.is_empty() { JsonValue::Bool(true) } else { JsonValue::Object(args) });
+            } else if !field.selections.is_empty() {
+                return Err(format!("Field '{}' must not have a selection since its type has no subfields.", field.name));
+            } else if !field.arguments.is_empty() {
+                return Err(format!("Field '{}' of type '{}' takes no arguments.", field.name, model.name()));
+            }
+        }
+        Ok(include)
+    }
+
+    fn project(&self, model: &Model, value: &JsonValue, selections: &Vec<Selection>) -> JsonValue {
+        match value {
+            JsonValue::Array(values) => JsonValue::Array(values.iter().map(|v| self.project(model, v, selections)).collect()),
+            JsonValue::Object(object) => {
+                let mut result = Map::new();
+                for field in self.collect_fields(selections, 0).unwrap_or_default() {
+                    let value = object.get(&field.name).cloned().unwrap_or(JsonValue::Null);
+                    let value = if field.name == "__typename" {
+                        json!(model.name())
+                    } else if let Some(relation) = model.relation(&field.name) {
+                        self.project(self.graph.model(relation.model()).unwrap(), &value, &field.selections)
+                    } else {
+                        unwrap_wire_values(value)
+                    };
+                    result.insert(field.response_key().to_owned(), value);
+                }
+                JsonValue::Object(result)
+            }
+            _ => JsonValue::Null,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::serial;
+    use crate::core::app::testing::sqlite_app;
+    use crate::teon;
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model Author {
+  @id @default($cuid)
+  id: String
+  @unique
+  name: String
+  age: Int?
+  @relation(fields: .id, references: .authorId)
+  posts: Post[]
+}
+
+model Post {
+  @id @default($cuid)
+  id: String
+  title: String
+  authorId: String?
+  @relation(fields: .authorId, references: .id)
+  author: Author?
+}
+"#;
+
+    async fn execute(graph: &Graph, conf: &ServerConf, query: &str) -> JsonValue {
+        let document = parse_document(query).unwrap();
+        let executor = Executor { graph, conf, document: &document, variables: Map::new(), identity: None };
+        executor.execute(&document.operations[0]).await
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn arguments_are_typed_with_input_types() {
+        let app = sqlite_app(SCHEMA).await;
+        let schema = generate_schema(app.graph());
+        assert!(schema.contains("authorFindMany(where: AuthorWhereInput, orderBy: [AuthorOrderByInput!], cursor: AuthorWhereUniqueInput, take: Int, skip: Int, pageSize: Int, pageNumber: Int): [Author!]!"));
+        assert!(schema.contains("authorCreate(create: AuthorCreateInput!): Author!"));
+        assert!(schema.contains("authorCreateMany(create: [AuthorCreateInput!]!): [Author!]!"));
+        assert!(schema.contains("postAggregate(where: PostWhereInput, _count: PostCountAggregateInput, _min: PostMinAggregateInput, _max: PostMaxAggregateInput): JSON!"));
+        assert!(schema.contains("input AuthorWhereInput {\n  AND: [AuthorWhereInput!]\n  OR: [AuthorWhereInput!]\n  NOT: AuthorWhereInput\n"));
+        assert!(schema.contains("  age: IntFilter\n"));
+        assert!(schema.contains("  posts: PostListRelationFilter\n"));
+        assert!(schema.contains("input PostCreateNestedManyWithoutAuthorInput {"));
+        assert!(schema.contains("  age: IntFieldUpdateInput\n"));
+        assert!(schema.contains("posts(where: PostWhereInput, orderBy: [PostOrderByInput!], cursor: PostWhereUniqueInput, take: Int, skip: Int): [Post!]!"));
+        for untyped in [": JSON,", ": JSON)", ": JSON!,", ": JSON!)"] {
+            assert!(!schema.contains(untyped));
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn introspection_describes_the_schema() {
+        let app = sqlite_app(SCHEMA).await;
+        let result = execute(app.graph(), &app.server_conf, r#"{
+  __schema { queryType { name } mutationType { name } directives { name } }
+  __type(name: "AuthorWhereInput") { kind inputFields { name type { kind name ofType { kind name } } } }
+}"#).await;
+        assert_eq!(result.get("errors"), None);
+        let data = &result["data"];
+        assert_eq!(data["__schema"]["queryType"]["name"], "Query");
+        assert_eq!(data["__schema"]["mutationType"]["name"], "Mutation");
+        assert_eq!(data["__schema"]["directives"][0]["name"], "skip");
+        assert_eq!(data["__type"]["kind"], "INPUT_OBJECT");
+        let fields = data["__type"]["inputFields"].as_array().unwrap();
+        let and = fields.iter().find(|f| f["name"] == "AND").unwrap();
+        assert_eq!(and["type"], json!({"kind": "LIST", "name": null, "ofType": {"kind": "NON_NULL", "name": null}}));
+        let name = fields.iter().find(|f| f["name"] == "name").unwrap();
+        assert_eq!(name["type"], json!({"kind": "INPUT_OBJECT", "name": "StringFilter", "ofType": null}));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn typed_arguments_are_decoded() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        for (name, age) in [("a", 1), ("b", 2), ("c", 3)] {
+            graph.create_object("Author", teon!({"name": name, "age": age})).await.unwrap().save().await.unwrap();
+        }
+        let result = execute(graph, &app.server_conf, r#"{
+  authorFindMany(where: {age: {gte: 2}}, orderBy: [{age: desc}]) { name }
+}"#).await;
+        assert_eq!(result, json!({"data": {"authorFindMany": [{"name": "c"}, {"name": "b"}]}}));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn nested_lists_are_loaded_with_one_include() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        for (name, titles) in [("a", vec!["a2", "a1"]), ("b", vec!["b1"]), ("c", vec![])] {
+            let author = graph.create_object("Author", teon!({"name": name})).await.unwrap();
+            author.save().await.unwrap();
+            let id = author.get_value("id").unwrap();
+            for title in titles {
+                graph.create_object("Post", teon!({"title": title, "authorId": id.clone()})).await.unwrap().save().await.unwrap();
+            }
+        }
+        let query = r#"{
+  authorFindMany(orderBy: [{name: asc}]) { name posts(orderBy: [{title: asc}]) { title author { name } } }
+}"#;
+        let document = parse_document(query).unwrap();
+        let executor = Executor { graph, conf: &app.server_conf, document: &document, variables: Map::new(), identity: None };
+        let Selection::Field(field) = &document.operations[0].selections[0] else { unreachable!() };
+        let include = executor.include_for(graph.model("Author").unwrap(), &field.selections).unwrap();
+        assert_eq!(JsonValue::Object(include), json!({"posts": {"orderBy": [{"title": "asc"}], "include": {"author": true}}}));
+        let result = execute(graph, &app.server_conf, query).await;
+        assert_eq!(result, json!({"data": {"authorFindMany": [
+            {"name": "a", "posts": [{"title": "a1", "author": {"name": "a"}}, {"title": "a2", "author": {"name": "a"}}]},
+            {"name": "b", "posts": [{"title": "b1", "author": {"name": "b"}}]},
+            {"name": "c", "posts": []},
+        ]}}));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn mutations_apply_write_permissions() {
+        let schema = format!("{SCHEMA}\nmodel Tag {{\n  @@canMutate($invalid)\n  @id @default($cuid)\n  id: String\n  name: String\n}}\n");
+        let app = sqlite_app(&schema).await;
+        let graph = app.graph();
+        let result = execute(graph, &app.server_conf, r#"mutation { tagCreate(create: {name: "a"}) { id name } }"#).await;
+        assert_eq!(result["data"], json!({"tagCreate": null}));
+        assert_eq!(result["errors"][0]["extensions"]["type"], "PermissionError");
+        assert_eq!(graph.count("Tag", &teon!({})).await.unwrap(), 0);
+    }
+}
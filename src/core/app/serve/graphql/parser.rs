@@ -0,0 +1,452 @@
+use std::collections::HashMap;
+use serde_json::{Map, Value as JsonValue};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum OperationKind {
+    Query,
+    Mutation,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Document {
+    pub(crate) operations: Vec<Operation>,
+    pub(crate) fragments: HashMap<String, Vec<Selection>>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Operation {
+    pub(crate) kind: OperationKind,
+    pub(crate) name: Option<String>,
+    pub(crate) variable_defaults: Vec<(String, GqlValue)>,
+    pub(crate) selections: Vec<Selection>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Selection {
+    Field(Field),
+    FragmentSpread(String, Vec<Directive>),
+    InlineFragment(Vec<Selection>, Vec<Directive>),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Field {
+    pub(crate) alias: Option<String>,
+    pub(crate) name: String,
+    pub(crate) arguments: Vec<(String, GqlValue)>,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) selections: Vec<Selection>,
+}
+
+impl Field {
+    pub(crate) fn response_key(&self) -> &str {
+        self.alias.as_ref().unwrap_or(&self.name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Directive {
+    pub(crate) name: String,
+    pub(crate) arguments: Vec<(String, GqlValue)>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum GqlValue {
+    Variable(String),
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Enum(String),
+    List(Vec<GqlValue>),
+    Object(Vec<(String, GqlValue)>),
+}
+
+impl GqlValue {
+
+    /// Resolve variables and convert into the JSON value the action decoder accepts. Enum
+    /// values are plain strings there.
+    pub(crate) fn to_json(&self, variables: &Map<String, JsonValue>) -> JsonValue {
+        match self {
+            GqlValue::Variable(name) => variables.get(name).cloned().unwrap_or(JsonValue::Null),
+            GqlValue::Null => JsonValue::Null,
+            GqlValue::Bool(b) => JsonValue::Bool(*b),
+            GqlValue::Int(i) => JsonValue::from(*i),
+            GqlValue::Float(f) => JsonValue::from(*f),
+            GqlValue::String(s) | GqlValue::Enum(s) => JsonValue::String(s.clone()),
+            GqlValue::List(list) => JsonValue::Array(list.iter().map(|v| v.to_json(variables)).collect()),
+            GqlValue::Object(pairs) => JsonValue::Object(pairs.iter().map(|(k, v)| (k.clone(), v.to_json(variables))).collect()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Punctuator(char),
+    Spread,
+    Name(String),
+    Int(i64),
+    Float(f64),
+    String(String),
+    End,
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Lexer {
+
+    fn new(source: &str) -> Self {
+        Self { chars: source.chars().collect(), position: 0 }
+    }
+
+    fn error(&self, message: impl AsRef<str>) -> String {
+        let consumed = &self.chars[..self.position.min(self.chars.len())];
+        let line = consumed.iter().filter(|c| **c == '\n').count() + 1;
+        let column = consumed.iter().rev().take_while(|c| **c != '\n').count() + 1;
+        format!("Syntax error: {} at {line}:{column}.", message.as_ref())
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.chars.get(self.position).cloned()
+    }
+
+    fn skip_ignored(&mut self) {
+        while let Some(c) = self.peek_char() {
+            if c.is_whitespace() || c == ',' || c == '\u{feff}' {
+                self.position += 1;
+            } else if c == '#' {
+                while let Some(c) = self.peek_char() {
+                    if c == '\n' { break }
+                    self.position += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Token, String> {
+        self.skip_ignored();
+        let Some(c) = self.peek_char() else { return Ok(Token::End) };
+        if "{}()[]:!$=@|&".contains(c) {
+            self.position += 1;
+            return Ok(Token::Punctuator(c));
+        }
+        if c == '.' {
+            if self.chars.get(self.position..self.position + 3) == Some(&['.', '.', '.']) {
+                self.position += 3;
+                return Ok(Token::Spread);
+            }
+            return Err(self.error("unexpected '.'"));
+        }
+        if c == '_' || c.is_ascii_alphabetic() {
+            let start = self.position;
+            while let Some(c) = self.peek_char() {
+                if c == '_' || c.is_ascii_alphanumeric() { self.position += 1 } else { break }
+            }
+            return Ok(Token::Name(self.chars[start..self.position].iter().collect()));
+        }
+        if c == '-' || c.is_ascii_digit() {
+            let start = self.position;
+            self.position += 1;
+            let mut float = false;
+            while let Some(c) = self.peek_char() {
+                if c.is_ascii_digit() {
+                    self.position += 1;
+                } else if c == '.' || c == 'e' || c == 'E' || ((c == '+' || c == '-') && float) {
+                    float = true;
+                    self.position += 1;
+                } else {
+                    break;
+                }
+            }
+            let literal: String = self.chars[start..self.position].iter().collect();
+            return if float {
+                literal.parse().map(Token::Float).map_err(|_| self.error(format!("invalid number '{literal}'")))
+            } else {
+                literal.parse().map(Token::Int).map_err(|_| self.error(format!("invalid number '{literal}'")))
+            };
+        }
+        if c == '"' {
+            return self.string();
+        }
+        Err(self.error(format!("unexpected character '{c}'")))
+    }
+
+    fn string(&mut self) -> Result<Token, String> {
+        if self.chars.get(self.position..self.position + 3) == Some(&['"', '"', '"']) {
+            self.position += 3;
+            let start = self.position;
+            while self.chars.get(self.position..self.position + 3) != Some(&['"', '"', '"']) {
+                if self.position >= self.chars.len() {
+                    return Err(self.error("unterminated string"));
+                }
+                self.position += 1;
+            }
+            let value: String = self.chars[start..self.position].iter().collect();
+            self.position += 3;
+            return Ok(Token::String(value.trim().to_owned()));
+        }
+        self.position += 1;
+        let mut value = String::new();
+        loop {
+            let Some(c) = self.peek_char() else { return Err(self.error("unterminated string")) };
+            self.position += 1;
+            match c {
+                '"' => return Ok(Token::String(value)),
+                '\n' => return Err(self.error("unterminated string")),
+                '\\' => {
+                    let Some(escaped) = self.peek_char() else { return Err(self.error("unterminated string")) };
+                    self.position += 1;
+                    match escaped {
+                        '"' | '\\' | '/' => value.push(escaped),
+                        'b' => value.push('\u{8}'),
+                        'f' => value.push('\u{c}'),
+                        'n' => value.push('\n'),
+                        'r' => value.push('\r'),
+                        't' => value.push('\t'),
+                        'u' => {
+                            let hex: String = self.chars.get(self.position..self.position + 4).map(|c| c.iter().collect()).unwrap_or_default();
+                            let Some(unicode) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) else {
+                                return Err(self.error("invalid unicode escape"));
+                            };
+                            self.position += 4;
+                            value.push(unicode);
+                        }
+                        _ => return Err(self.error(format!("invalid escape '\\{escaped}'"))),
+                    }
+                }
+                _ => value.push(c),
+            }
+        }
+    }
+}
+
+struct Parser {
+    lexer: Lexer,
+    current: Token,
+}
+
+/// Parse an executable GraphQL document. Type conditions are accepted and ignored, since every
+/// field has a concrete object type.
+pub(crate) fn parse_document(source: &str) -> Result<Document, String> {
+    let mut lexer = Lexer::new(source);
+    let current = lexer.next_token()?;
+    let mut parser = Parser { lexer, current };
+    let mut document = Document { operations: vec![], fragments: HashMap::new() };
+    while parser.current != Token::End {
+        match &parser.current {
+            Token::Punctuator('{') => {
+                let selections = parser.selection_set()?;
+                document.operations.push(Operation { kind: OperationKind::Query, name: None, variable_defaults: vec![], selections });
+            }
+            Token::Name(name) if name == "query" || name == "mutation" => {
+                let kind = if name == "query" { OperationKind::Query } else { OperationKind::Mutation };
+                parser.advance()?;
+                let name = if let Token::Name(_) = parser.current { Some(parser.name()?) } else { None };
+                let variable_defaults = parser.variable_definitions()?;
+                parser.directives()?;
+                let selections = parser.selection_set()?;
+                document.operations.push(Operation { kind, name, variable_defaults, selections });
+            }
+            Token::Name(name) if name == "fragment" => {
+                parser.advance()?;
+                let name = parser.name()?;
+                parser.expect_keyword("on")?;
+                parser.name()?;
+                parser.directives()?;
+                let selections = parser.selection_set()?;
+                document.fragments.insert(name, selections);
+            }
+            Token::Name(name) if name == "subscription" => return Err(parser.lexer.error("subscriptions are not supported")),
+            _ => return Err(parser.unexpected()),
+        }
+    }
+    if document.operations.is_empty() {
+        return Err("Syntax error: the document contains no operation.".to_owned());
+    }
+    Ok(document)
+}
+
+impl Parser {
+
+    fn advance(&mut self) -> Result<(), String> {
+        self.current = self.lexer.next_token()?;
+        Ok(())
+    }
+
+    fn unexpected(&self) -> String {
+        match &self.current {
+            Token::End => self.lexer.error("unexpected end of document"),
+            token => self.lexer.error(format!("unexpected {token:?}")),
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.current == Token::Punctuator(c) {
+            self.advance()
+        } else {
+            Err(self.lexer.error(format!("expected '{c}'")))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), String> {
+        if self.current == Token::Name(keyword.to_owned()) {
+            self.advance()
+        } else {
+            Err(self.lexer.error(format!("expected '{keyword}'")))
+        }
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        if let Token::Name(name) = &self.current {
+            let name = name.clone();
+            self.advance()?;
+            Ok(name)
+        } else {
+            Err(self.lexer.error("expected a name"))
+        }
+    }
+
+    fn variable_definitions(&mut self) -> Result<Vec<(String, GqlValue)>, String> {
+        let mut defaults = vec![];
+        if self.current != Token::Punctuator('(') {
+            return Ok(defaults);
+        }
+        self.advance()?;
+        while self.current != Token::Punctuator(')') {
+            self.expect('$')?;
+            let name = self.name()?;
+            self.expect(':')?;
+            self.skip_type()?;
+            if self.current == Token::Punctuator('=') {
+                self.advance()?;
+                defaults.push((name, self.value(true)?));
+            }
+            self.directives()?;
+        }
+        self.advance()?;
+        Ok(defaults)
+    }
+
+    fn skip_type(&mut self) -> Result<(), String> {
+        if self.current == Token::Punctuator('[') {
+            self.advance()?;
+            self.skip_type()?;
+            self.expect(']')?;
+        } else {
+            self.name()?;
+        }
+        if self.current == Token::Punctuator('!') {
+            self.advance()?;
+        }
+        Ok(())
+    }
+
+    fn directives(&mut self) -> Result<Vec<Directive>, String> {
+        let mut directives = vec![];
+        while self.current == Token::Punctuator('@') {
+            self.advance()?;
+            let name = self.name()?;
+            let arguments = self.arguments()?;
+            directives.push(Directive { name, arguments });
+        }
+        Ok(directives)
+    }
+
+    fn arguments(&mut self) -> Result<Vec<(String, GqlValue)>, String> {
+        let mut arguments = vec![];
+        if self.current != Token::Punctuator('(') {
+            return Ok(arguments);
+        }
+        self.advance()?;
+        while self.current != Token::Punctuator(')') {
+            let name = self.name()?;
+            self.expect(':')?;
+            arguments.push((name, self.value(false)?));
+        }
+        self.advance()?;
+        Ok(arguments)
+    }
+
+    fn selection_set(&mut self) -> Result<Vec<Selection>, String> {
+        self.expect('{')?;
+        let mut selections = vec![];
+        while self.current != Token::Punctuator('}') {
+            if self.current == Token::Spread {
+                self.advance()?;
+                match &self.current {
+                    Token::Name(name) if name != "on" => {
+                        let name = self.name()?;
+                        let directives = self.directives()?;
+                        selections.push(Selection::FragmentSpread(name, directives));
+                    }
+                    _ => {
+                        if self.current == Token::Name("on".to_owned()) {
+                            self.advance()?;
+                            self.name()?;
+                        }
+                        let directives = self.directives()?;
+                        selections.push(Selection::InlineFragment(self.selection_set()?, directives));
+                    }
+                }
+                continue;
+            }
+            let mut name = self.name()?;
+            let mut alias = None;
+            if self.current == Token::Punctuator(':') {
+                self.advance()?;
+                alias = Some(name);
+                name = self.name()?;
+            }
+            let arguments = self.arguments()?;
+            let directives = self.directives()?;
+            let selections_of_field = if self.current == Token::Punctuator('{') { self.selection_set()? } else { vec![] };
+            selections.push(Selection::Field(Field { alias, name, arguments, directives, selections: selections_of_field }));
+        }
+        self.advance()?;
+        Ok(selections)
+    }
+
+    fn value(&mut self, constant: bool) -> Result<GqlValue, String> {
+        let value = match &self.current {
+            Token::Punctuator('$') if !constant => {
+                self.advance()?;
+                return Ok(GqlValue::Variable(self.name()?));
+            }
+            Token::Punctuator('[') => {
+                self.advance()?;
+                let mut list = vec![];
+                while self.current != Token::Punctuator(']') {
+                    list.push(self.value(constant)?);
+                }
+                GqlValue::List(list)
+            }
+            Token::Punctuator('{') => {
+                self.advance()?;
+                let mut pairs = vec![];
+                while self.current != Token::Punctuator('}') {
+                    let name = self.name()?;
+                    self.expect(':')?;
+                    pairs.push((name, self.value(constant)?));
+                }
+                GqlValue::Object(pairs)
+            }
+            Token::Int(i) => GqlValue::Int(*i),
+            Token::Float(f) => GqlValue::Float(*f),
+            Token::String(s) => GqlValue::String(s.clone()),
+            Token::Name(name) => match name.as_str() {
+                "true" => GqlValue::Bool(true),
+                "false" => GqlValue::Bool(false),
+                "null" => GqlValue::Null,
+                _ => GqlValue::Enum(name.clone()),
+            },
+            _ => return Err(self.unexpected()),
+        };
+        self.advance()?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,654 @@
+use std::collections::{BTreeMap, HashSet};
+use inflector::Inflector;
+use crate::core::action::{
+    Action, ResData, ResMeta, AGGREGATE_HANDLER, CONNECT, CONNECT_OR_CREATE, COUNT_HANDLER, CREATE, CREATE_MANY_HANDLER,
+    DELETE, DISCONNECT, FIND_FIRST_HANDLER, FIND_UNIQUE_HANDLER, GROUP_BY_HANDLER, IDENTITY_HANDLER,
+    MANY, NESTED, SET, SINGLE, UPDATE, UPSERT,
+};
+use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+use crate::core::graph::Graph;
+use crate::core::model::Model;
+use crate::core::relation::Relation;
+use crate::generator::lib::code::Code;
+use super::{has_operation, is_query, operation_field_name};
+
+pub(crate) const BUILTIN_SCALARS: [&str; 5] = ["String", "Int", "Float", "Boolean", "ID"];
+const CUSTOM_SCALARS: [&str; 4] = ["DateTime", "Decimal", "Int64", "JSON"];
+
+/// Filter keys in the order they are listed in input types.
+const FILTER_KEYS: [&str; 28] = [
+    "equals", "not", "gt", "gte", "lt", "lte", "in", "notIn", "contains", "startsWith", "endsWith",
+    "matches", "mode", "search", "path", "arrayContains", "stringContains", "has", "hasEvery",
+    "hasSome", "hasKey", "isEmpty", "length", "_count", "_avg", "_sum", "_min", "_max",
+];
+const UPDATOR_KEYS: [&str; 7] = ["set", "increment", "decrement", "multiply", "divide", "push", "merge"];
+const RELATION_FILTER_KEYS: [&str; 5] = ["is", "isNot", "some", "every", "none"];
+const AGGREGATE_KEYS: [&str; 5] = ["_count", "_sum", "_avg", "_min", "_max"];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TypeKind {
+    Scalar,
+    Object,
+    InputObject,
+    Enum,
+}
+
+impl TypeKind {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            TypeKind::Scalar => "SCALAR",
+            TypeKind::Object => "OBJECT",
+            TypeKind::InputObject => "INPUT_OBJECT",
+            TypeKind::Enum => "ENUM",
+        }
+    }
+}
+
+/// A field of an object or input object type, or an argument. The type is written as in the
+/// schema definition language, e.g. `[Post!]!`.
+pub(crate) struct FieldDef {
+    pub(crate) name: String,
+    pub(crate) args: Vec<FieldDef>,
+    pub(crate) r#type: String,
+}
+
+impl FieldDef {
+    fn new(name: impl Into<String>, r#type: impl Into<String>) -> Self {
+        Self { name: name.into(), args: vec![], r#type: r#type.into() }
+    }
+
+    fn with_args(name: impl Into<String>, args: Vec<FieldDef>, r#type: impl Into<String>) -> Self {
+        Self { name: name.into(), args, r#type: r#type.into() }
+    }
+
+    fn to_sdl(&self) -> String {
+        if self.args.is_empty() {
+            format!("{}: {}", self.name, self.r#type)
+        } else {
+            let args: Vec<String> = self.args.iter().map(|a| a.to_sdl()).collect();
+            format!("{}({}): {}", self.name, args.join(", "), self.r#type)
+        }
+    }
+}
+
+pub(crate) struct TypeDef {
+    pub(crate) kind: TypeKind,
+    pub(crate) name: String,
+    pub(crate) fields: Vec<FieldDef>,
+    pub(crate) values: Vec<String>,
+}
+
+impl TypeDef {
+    fn scalar(name: &str) -> Self {
+        Self { kind: TypeKind::Scalar, name: name.to_owned(), fields: vec![], values: vec![] }
+    }
+
+    fn object(name: impl Into<String>, fields: Vec<FieldDef>) -> Self {
+        Self { kind: TypeKind::Object, name: name.into(), fields, values: vec![] }
+    }
+
+    fn r#enum(name: impl Into<String>, values: Vec<String>) -> Self {
+        Self { kind: TypeKind::Enum, name: name.into(), fields: vec![], values }
+    }
+}
+
+/// The GraphQL schema of a graph. It's rendered as SDL on `GET /graphql` and answers
+/// introspection queries.
+pub(crate) struct Schema {
+    pub(crate) types: Vec<TypeDef>,
+}
+
+impl Schema {
+
+    pub(crate) fn r#type(&self, name: &str) -> Option<&TypeDef> {
+        self.types.iter().find(|t| t.name == name)
+    }
+
+    pub(crate) fn to_sdl(&self) -> String {
+        Code::new(0, 2, |c| {
+            let mut first = true;
+            for t in self.types.iter().filter(|t| t.kind == TypeKind::Scalar && !BUILTIN_SCALARS.contains(&t.name.as_str())) {
+                c.line(format!("scalar {}", t.name));
+                first = false;
+            }
+            for t in self.types.iter().filter(|t| t.kind != TypeKind::Scalar) {
+                if !first { c.empty_line() }
+                first = false;
+                let keyword = match t.kind {
+                    TypeKind::Object => "type",
+                    TypeKind::InputObject => "input",
+                    _ => "enum",
+                };
+                c.block(format!("{keyword} {} {{", t.name), |b| {
+                    for value in &t.values {
+                        b.line(value);
+                    }
+                    for field in &t.fields {
+                        b.line(field.to_sdl());
+                    }
+                }, "}");
+            }
+        }).to_string()
+    }
+}
+
+/// The schema in the GraphQL schema definition language, served on `GET /graphql`.
+pub(crate) fn generate_schema(graph: &Graph) -> String {
+    build_schema(graph).to_sdl()
+}
+
+pub(crate) fn build_schema(graph: &Graph) -> Schema {
+    let mut builder = SchemaBuilder { graph, inputs: BTreeMap::new(), enums: BTreeMap::new() };
+    let mut types: Vec<TypeDef> = CUSTOM_SCALARS.iter().chain(BUILTIN_SCALARS.iter()).map(|s| TypeDef::scalar(s)).collect();
+    let mut enum_names: Vec<&String> = graph.enums().keys().collect();
+    enum_names.sort();
+    for name in enum_names {
+        types.push(TypeDef::r#enum(name, graph.enum_values(name).unwrap().clone()));
+    }
+    let mut outputs = vec![];
+    for model in graph.models() {
+        outputs.push(TypeDef::object(model.name(), builder.output_fields(model)));
+        if model.actions().iter().any(|a| a.handler_res_meta() == ResMeta::TokenInfo && has_operation(model, *a)) {
+            outputs.push(TypeDef::object(format!("{}SignInResult", model.name()), vec![
+                FieldDef::new("token", "String!"),
+                FieldDef::new("refreshToken", "String"),
+                FieldDef::new("data", format!("{}!", model.name())),
+            ]));
+        }
+    }
+    for (type_name, query) in [("Query", true), ("Mutation", false)] {
+        let fields: Vec<FieldDef> = graph.models().iter().flat_map(|model| {
+            Action::handlers_iter()
+                .filter(|a| has_operation(model, **a) && is_query(**a) == query)
+                .map(|a| FieldDef::with_args(operation_field_name(model, *a), builder.arguments(model, *a), return_type(model, *a)))
+                .collect::<Vec<FieldDef>>()
+        }).collect();
+        if !fields.is_empty() {
+            outputs.push(TypeDef::object(type_name, fields));
+        }
+    }
+    let (operations, outputs): (Vec<TypeDef>, Vec<TypeDef>) = outputs.into_iter().partition(|t| t.name == "Query" || t.name == "Mutation");
+    types.extend(builder.enums.into_values());
+    types.extend(outputs);
+    types.extend(builder.inputs.into_values());
+    types.extend(operations);
+    Schema { types }
+}
+
+fn graphql_type(field_type: &FieldType, optional: bool) -> String {
+    let base = match field_type {
+        #[cfg(feature = "data-source-mongodb")]
+        FieldType::ObjectId => "ID".to_owned(),
+        FieldType::Bool => "Boolean".to_owned(),
+        FieldType::I32 => "Int".to_owned(),
+        FieldType::I64 => "Int64".to_owned(),
+        FieldType::F32 | FieldType::F64 => "Float".to_owned(),
+        FieldType::Decimal => "Decimal".to_owned(),
+        FieldType::String | FieldType::Date => "String".to_owned(),
+        FieldType::DateTime => "DateTime".to_owned(),
+        FieldType::Enum(name) => name.clone(),
+        FieldType::Vec(inner) => format!("[{}]", graphql_type(inner.field_type(), inner.is_optional())),
+        FieldType::HashMap(_) | FieldType::BTreeMap(_) | FieldType::Json | FieldType::Object(_) => "JSON".to_owned(),
+    };
+    if optional { base } else { base + "!" }
+}
+
+/// The prefix of filter and updator input names. Types which share a GraphQL scalar but accept
+/// different filters, like `String` and `Date`, get different prefixes.
+fn type_prefix(field_type: &FieldType) -> String {
+    match field_type {
+        #[cfg(feature = "data-source-mongodb")]
+        FieldType::ObjectId => "ObjectId".to_owned(),
+        FieldType::Bool => "Bool".to_owned(),
+        FieldType::I32 => "Int".to_owned(),
+        FieldType::I64 => "Int64".to_owned(),
+        FieldType::F32 | FieldType::F64 => "Float".to_owned(),
+        FieldType::Decimal => "Decimal".to_owned(),
+        FieldType::String => "String".to_owned(),
+        FieldType::Date => "Date".to_owned(),
+        FieldType::DateTime => "DateTime".to_owned(),
+        FieldType::Enum(name) => name.clone(),
+        FieldType::Vec(inner) => format!("{}List", type_prefix(inner.field_type())),
+        FieldType::HashMap(inner) | FieldType::BTreeMap(inner) => format!("{}Map", type_prefix(inner.field_type())),
+        FieldType::Json => "Json".to_owned(),
+        FieldType::Object(name) => name.clone(),
+    }
+}
+
+fn return_type(model: &Model, action: Action) -> String {
+    let name = model.name();
+    if action.handler_res_meta() == ResMeta::TokenInfo {
+        return format!("{name}SignInResult!");
+    }
+    match action.to_u32() {
+        FIND_UNIQUE_HANDLER | FIND_FIRST_HANDLER | IDENTITY_HANDLER => return name.to_owned(),
+        AGGREGATE_HANDLER => return "JSON!".to_owned(),
+        GROUP_BY_HANDLER => return "[JSON!]!".to_owned(),
+        _ => (),
+    }
+    match action.handler_res_data() {
+        ResData::Single => format!("{name}!"),
+        ResData::Vec => format!("[{name}!]!"),
+        ResData::Number => "Int!".to_owned(),
+        ResData::Other => "JSON!".to_owned(),
+    }
+}
+
+/// Inputs of nested relation arguments. The names, actions and shapes follow the nested
+/// argument decoding of `Decoder`.
+#[derive(Clone, Copy)]
+enum Nested {
+    Create,
+    WhereUnique,
+    Where,
+    ConnectOrCreate,
+    Update,
+    UpdateWithWhereUnique,
+    UpdateManyWithWhere,
+    Upsert,
+    UpsertWithWhereUnique,
+    Bool,
+}
+
+const NESTED_CREATE_ONE: [(&str, u32, Nested); 3] = [
+    ("create", NESTED | CREATE | SINGLE, Nested::Create),
+    ("connect", NESTED | CONNECT | SINGLE, Nested::WhereUnique),
+    ("connectOrCreate", NESTED | CONNECT_OR_CREATE | SINGLE, Nested::ConnectOrCreate),
+];
+
+const NESTED_CREATE_MANY: [(&str, u32, Nested); 4] = [
+    ("create", NESTED | CREATE | MANY, Nested::Create),
+    ("createMany", NESTED | CREATE | MANY, Nested::Create),
+    ("connect", NESTED | CONNECT | SINGLE, Nested::WhereUnique),
+    ("connectOrCreate", NESTED | CONNECT_OR_CREATE | SINGLE, Nested::ConnectOrCreate),
+];
+
+const NESTED_UPDATE_ONE: [(&str, u32, Nested); 8] = [
+    ("create", NESTED | CREATE | SINGLE, Nested::Create),
+    ("connect", NESTED | CONNECT | SINGLE, Nested::WhereUnique),
+    ("set", NESTED | SET | SINGLE, Nested::WhereUnique),
+    ("connectOrCreate", NESTED | CONNECT_OR_CREATE | SINGLE, Nested::ConnectOrCreate),
+    ("disconnect", NESTED | DISCONNECT | SINGLE, Nested::Bool),
+    ("delete", NESTED | DELETE | SINGLE, Nested::Bool),
+    ("update", NESTED | UPDATE | SINGLE, Nested::Update),
+    ("upsert", NESTED | UPSERT | SINGLE, Nested::Upsert),
+];
+
+const NESTED_UPDATE_MANY: [(&str, u32, Nested); 11] = [
+    ("create", NESTED | CREATE | MANY, Nested::Create),
+    ("createMany", NESTED | CREATE | MANY, Nested::Create),
+    ("connect", NESTED | CONNECT | SINGLE, Nested::WhereUnique),
+    ("set", NESTED | SET | SINGLE, Nested::WhereUnique),
+    ("disconnect", NESTED | DISCONNECT | SINGLE, Nested::WhereUnique),
+    ("delete", NESTED | DELETE | SINGLE, Nested::WhereUnique),
+    ("connectOrCreate", NESTED | CONNECT_OR_CREATE | SINGLE, Nested::ConnectOrCreate),
+    ("update", NESTED | UPDATE | SINGLE, Nested::UpdateWithWhereUnique),
+    ("updateMany", NESTED | UPDATE | MANY, Nested::UpdateManyWithWhere),
+    ("deleteMany", NESTED | DELETE | MANY, Nested::Where),
+    ("upsert", NESTED | UPSERT | SINGLE, Nested::UpsertWithWhereUnique),
+];
+
+/// Generates input types on first use. Arguments are typed like the JSON which `Decoder`
+/// accepts, and the decoder still validates them, so input fields are nullable unless the
+/// decoder can't do without them.
+struct SchemaBuilder<'a> {
+    graph: &'a Graph,
+    inputs: BTreeMap<String, TypeDef>,
+    enums: BTreeMap<String, TypeDef>,
+}
+
+impl<'a> SchemaBuilder<'a> {
+
+    /// Returns the name of the input type, generating it with `fields` if it doesn't exist yet.
+    /// Input objects must have fields, an input without any is accepted as `JSON`.
+    fn input<F>(&mut self, name: String, fields: F) -> String where F: FnOnce(&mut Self) -> Vec<FieldDef> {
+        if self.inputs.contains_key(&name) {
+            return name;
+        }
+        // inserted before the fields are built so recursive inputs refer to it
+        self.inputs.insert(name.clone(), TypeDef { kind: TypeKind::InputObject, name: name.clone(), fields: vec![], values: vec![] });
+        let fields = fields(self);
+        if fields.is_empty() {
+            self.inputs.remove(&name);
+            return "JSON".to_owned();
+        }
+        self.inputs.get_mut(&name).unwrap().fields = fields;
+        name
+    }
+
+    fn r#enum(&mut self, name: impl Into<String>, values: Vec<String>) -> String {
+        let name = name.into();
+        self.enums.entry(name.clone()).or_insert_with(|| TypeDef::r#enum(name.clone(), values));
+        name
+    }
+
+    fn output_fields(&mut self, model: &'a Model) -> Vec<FieldDef> {
+        let mut fields = vec![];
+        for key in model.output_keys() {
+            if let Some(field) = model.field(key) {
+                fields.push(FieldDef::new(key, graphql_type(field.field_type(), field.is_optional())));
+            } else if let Some(relation) = model.relation(key) {
+                let related = self.graph.model(relation.model()).unwrap();
+                let mut args = vec![];
+                if relation.is_vec() {
+                    args.push(FieldDef::new("where", self.where_input(related)));
+                    args.push(FieldDef::new("orderBy", format!("[{}!]", self.order_by_input(related))));
+                    args.push(FieldDef::new("cursor", self.where_unique_input(related)));
+                    args.push(FieldDef::new("take", "Int"));
+                    args.push(FieldDef::new("skip", "Int"));
+                }
+                if related.soft_delete().is_some() {
+                    args.push(FieldDef::new("withDeleted", "Boolean"));
+                    args.push(FieldDef::new("onlyDeleted", "Boolean"));
+                }
+                let r#type = if relation.is_vec() {
+                    format!("[{}!]!", relation.model())
+                } else if relation.is_optional() || related.soft_delete().is_some() {
+                    // the related record is left out once it's deleted
+                    relation.model().to_owned()
+                } else {
+                    format!("{}!", relation.model())
+                };
+                fields.push(FieldDef::with_args(key, args, r#type));
+            } else if let Some(property) = model.property(key) {
+                fields.push(FieldDef::new(key, graphql_type(property.field_type(), true)));
+            }
+        }
+        fields
+    }
+
+    /// Arguments of an operation field.
+    fn arguments(&mut self, model: &'a Model, action: Action) -> Vec<FieldDef> {
+        let mut arguments = vec![];
+        if action.handler_requires_where_unique() {
+            arguments.push(FieldDef::new("where", format!("{}!", self.where_unique_input(model))));
+        } else if action.handler_requires_where() || action.handler_requires_aggregates() || action.to_u32() == COUNT_HANDLER {
+            arguments.push(FieldDef::new("where", self.where_input(model)));
+        }
+        if action.handler_requires_where() {
+            arguments.push(FieldDef::new("orderBy", format!("[{}!]", self.order_by_input(model))));
+            arguments.push(FieldDef::new("cursor", self.where_unique_input(model)));
+            for name in ["take", "skip", "pageSize", "pageNumber"] {
+                arguments.push(FieldDef::new(name, "Int"));
+            }
+        }
+        if action.handler_requires_create() {
+            let create_input = self.create_input(model, None);
+            let r#type = if action.to_u32() == CREATE_MANY_HANDLER { format!("[{create_input}!]!") } else { format!("{create_input}!") };
+            arguments.push(FieldDef::new("create", r#type));
+        }
+        if action.handler_requires_update() {
+            arguments.push(FieldDef::new("update", format!("{}!", self.update_input(model, None))));
+        }
+        if action.handler_requires_credentials() {
+            arguments.push(FieldDef::new("credentials", format!("{}!", self.credentials_input(model))));
+        }
+        if action.handler_requires_refresh_token() {
+            arguments.push(FieldDef::new("refreshToken", "String!"));
+        } else if action.handler_accepts_refresh_token() {
+            arguments.push(FieldDef::new("refreshToken", "String"));
+        }
+        if action.handler_requires_by_and_having() {
+            let scalar_fields = self.r#enum(format!("{}ScalarField", model.name()), model.scalar_keys().clone());
+            arguments.push(FieldDef::new("by", format!("[{scalar_fields}!]!")));
+            arguments.push(FieldDef::new("having", self.having_input(model)));
+        }
+        if action.handler_requires_aggregates() {
+            for key in AGGREGATE_KEYS {
+                if let Some(input) = self.aggregate_input(model, key) {
+                    arguments.push(FieldDef::new(key, input));
+                }
+            }
+        }
+        if model.soft_delete().is_some() && action.handler_allowed_input_json_keys().contains("withDeleted") {
+            arguments.push(FieldDef::new("withDeleted", "Boolean"));
+            arguments.push(FieldDef::new("onlyDeleted", "Boolean"));
+        }
+        arguments
+    }
+
+    fn where_input(&mut self, model: &'a Model) -> String {
+        let name = format!("{}WhereInput", model.name());
+        self.input(name.clone(), |b| {
+            let mut fields = vec![
+                FieldDef::new("AND", format!("[{name}!]")),
+                FieldDef::new("OR", format!("[{name}!]")),
+                FieldDef::new("NOT", name.clone()),
+            ];
+            for key in model.query_keys() {
+                if let Some(field) = model.field(key) {
+                    fields.push(FieldDef::new(key, b.filter(field.field_type(), false)));
+                } else if let Some(relation) = model.relation(key) {
+                    fields.push(FieldDef::new(key, b.relation_filter(relation)));
+                }
+            }
+            fields
+        })
+    }
+
+    fn where_unique_input(&mut self, model: &'a Model) -> String {
+        self.input(format!("{}WhereUniqueInput", model.name()), |_| {
+            let mut keys: Vec<&String> = vec![];
+            for index in model.indices() {
+                for key in index.keys() {
+                    if !keys.contains(&key) {
+                        keys.push(key);
+                    }
+                }
+            }
+            keys.into_iter().filter_map(|k| model.field(k).map(|f| FieldDef::new(k, graphql_type(f.field_type(), true)))).collect()
+        })
+    }
+
+    fn order_by_input(&mut self, model: &'a Model) -> String {
+        let sort_order = self.r#enum("SortOrder", vec!["asc".to_owned(), "desc".to_owned()]);
+        self.input(format!("{}OrderByInput", model.name()), |_| {
+            model.scalar_keys().iter().map(|k| FieldDef::new(k, sort_order.clone())).collect()
+        })
+    }
+
+    fn filter(&mut self, field_type: &FieldType, aggregates: bool) -> String {
+        let name = format!("{}{}Filter", type_prefix(field_type), if aggregates { "WithAggregates" } else { "" });
+        let keys: HashSet<&str> = if aggregates { field_type.filters_with_aggregates().clone() } else { field_type.filters().clone() };
+        let field_type = field_type.clone();
+        self.input(name.clone(), |b| {
+            FILTER_KEYS.iter().filter(|k| keys.contains(*k)).map(|key| {
+                let r#type = match *key {
+                    "not" => name.clone(),
+                    "in" | "notIn" => format!("[{}]", graphql_type(&field_type, false)),
+                    "stringContains" | "hasKey" => "String".to_owned(),
+                    "path" => "[JSON!]".to_owned(),
+                    "mode" => b.r#enum("QueryMode", vec!["caseInsensitive".to_owned()]),
+                    "has" => {
+                        let element = field_type.element_field().unwrap();
+                        graphql_type(element.field_type(), true)
+                    }
+                    "hasEvery" | "hasSome" => {
+                        let element = field_type.element_field().unwrap();
+                        format!("[{}]", graphql_type(element.field_type(), element.is_optional()))
+                    }
+                    "isEmpty" => "Boolean".to_owned(),
+                    "length" => "Int".to_owned(),
+                    "_count" | "_avg" | "_sum" => b.filter(&FieldType::I64, false),
+                    "_min" | "_max" => b.filter(&field_type, false),
+                    _ => graphql_type(&field_type, true),
+                };
+                FieldDef::new(*key, r#type)
+            }).collect()
+        })
+    }
+
+    fn relation_filter(&mut self, relation: &'a Relation) -> String {
+        let related = self.graph.model(relation.model()).unwrap();
+        let name = format!("{}{}RelationFilter", related.name(), if relation.is_vec() { "List" } else { "" });
+        self.input(name, |b| {
+            let where_input = b.where_input(related);
+            RELATION_FILTER_KEYS.iter().filter(|k| relation.filters().contains(*k)).map(|k| FieldDef::new(*k, where_input.clone())).collect()
+        })
+    }
+
+    fn having_input(&mut self, model: &'a Model) -> String {
+        self.input(format!("{}ScalarWhereWithAggregatesInput", model.name()), |b| {
+            model.scalar_keys().iter().filter_map(|k| model.field(k)).map(|f| {
+                FieldDef::new(f.name(), b.filter(f.field_type(), true))
+            }).collect()
+        })
+    }
+
+    /// Models without number fields can't be averaged or summed.
+    fn aggregate_input(&mut self, model: &'a Model, key: &str) -> Option<String> {
+        let allowed = model.allowed_keys_for_aggregate(key);
+        if allowed.is_empty() {
+            return None;
+        }
+        Some(self.input(format!("{}{}AggregateInput", model.name(), key.trim_start_matches('_').to_pascal_case()), |_| {
+            let mut fields = vec![];
+            if allowed.contains("_all") {
+                fields.push(FieldDef::new("_all", "Boolean"));
+            }
+            fields.extend(model.scalar_keys().iter().filter(|k| allowed.contains(k.as_str())).map(|k| FieldDef::new(k, "Boolean")));
+            fields
+        }))
+    }
+
+    fn credentials_input(&mut self, model: &'a Model) -> String {
+        self.input(format!("{}CredentialsInput", model.name()), |b| {
+            let mut keys: Vec<&String> = model.auth_identity_keys().iter().collect();
+            keys.extend(model.auth_by_keys().iter().filter(|k| !model.auth_identity_keys().contains(k)));
+            keys.into_iter().filter_map(|k| b.value_field(model, k)).collect()
+        })
+    }
+
+    /// A field or setter property in create and credentials inputs.
+    fn value_field(&self, model: &'a Model, key: &str) -> Option<FieldDef> {
+        if let Some(field) = model.field(key) {
+            Some(FieldDef::new(key, graphql_type(field.field_type(), true)))
+        } else {
+            model.property(key).map(|p| FieldDef::new(key, graphql_type(p.field_type(), true)))
+        }
+    }
+
+    fn without_suffix(without: Option<&Relation>) -> String {
+        without.map(|r| format!("Without{}", r.name().to_pascal_case())).unwrap_or_default()
+    }
+
+    /// Required fields aren't marked, a value for them may come from a default value, a
+    /// relation or a pipeline. The decoder reports missing ones.
+    fn create_input(&mut self, model: &'a Model, without: Option<&'a Relation>) -> String {
+        let name = format!("{}Create{}Input", model.name(), Self::without_suffix(without));
+        self.input(name, |b| {
+            let mut excluded: Vec<&str> = vec![];
+            if let Some(relation) = without {
+                excluded.push(relation.name());
+                if relation.has_foreign_key() {
+                    excluded.extend(relation.fields().iter().map(|k| k.as_str()));
+                }
+            }
+            model.input_keys().iter().filter(|k| !excluded.contains(&k.as_str())).filter_map(|key| {
+                match model.relation(key) {
+                    Some(relation) => Some(FieldDef::new(key, b.nested_relation_input(relation, false))),
+                    None => b.value_field(model, key),
+                }
+            }).collect()
+        })
+    }
+
+    fn update_input(&mut self, model: &'a Model, without: Option<&'a Relation>) -> String {
+        let name = format!("{}Update{}Input", model.name(), Self::without_suffix(without));
+        self.input(name, |b| {
+            let mut excluded: Vec<&str> = vec![];
+            if let Some(relation) = without {
+                excluded.push(relation.name());
+                excluded.extend(relation.fields().iter().map(|k| k.as_str()));
+            }
+            model.input_keys().iter().filter(|k| !excluded.contains(&k.as_str())).filter_map(|key| {
+                if let Some(field) = model.field(key) {
+                    Some(FieldDef::new(key, b.update_value(field.field_type(), false)))
+                } else if let Some(relation) = model.relation(key) {
+                    Some(FieldDef::new(key, b.nested_relation_input(relation, true)))
+                } else {
+                    model.property(key).map(|p| FieldDef::new(key, b.update_value(p.field_type(), true)))
+                }
+            }).collect()
+        })
+    }
+
+    /// Json values are taken as is unless they're a single updator. Objects of other types are
+    /// always updators, so values which are objects themselves are set with `set`.
+    fn update_value(&mut self, field_type: &FieldType, set_only: bool) -> String {
+        if field_type.is_json() {
+            return "JSON".to_owned();
+        }
+        let updators: HashSet<&str> = if set_only { field_type.default_updators().clone() } else { field_type.updators().clone() };
+        let is_map = matches!(field_type, FieldType::HashMap(_) | FieldType::BTreeMap(_));
+        if updators.len() == 1 && !is_map {
+            return graphql_type(field_type, true);
+        }
+        let field_type = field_type.clone();
+        self.input(format!("{}FieldUpdateInput", type_prefix(&field_type)), |_| {
+            UPDATOR_KEYS.iter().filter(|k| updators.contains(*k)).map(|key| {
+                let r#type = match *key {
+                    "push" => graphql_type(field_type.element_field().unwrap().field_type(), true),
+                    _ => graphql_type(&field_type, true),
+                };
+                FieldDef::new(*key, r#type)
+            }).collect()
+        })
+    }
+
+    fn nested_relation_input(&mut self, relation: &'a Relation, update: bool) -> String {
+        let (related, opposite) = self.graph.opposite_relation(relation);
+        let name = format!(
+            "{}{}Nested{}{}Input",
+            related.name(),
+            if update { "Update" } else { "Create" },
+            if relation.is_vec() { "Many" } else { "One" },
+            Self::without_suffix(opposite),
+        );
+        let entries: &[(&str, u32, Nested)] = match (update, relation.is_vec()) {
+            (false, false) => &NESTED_CREATE_ONE,
+            (false, true) => &NESTED_CREATE_MANY,
+            (true, false) => &NESTED_UPDATE_ONE,
+            (true, true) => &NESTED_UPDATE_MANY,
+        };
+        self.input(name, |b| {
+            entries.iter().filter(|(_, action, _)| related.has_action(Action::from_u32(*action))).map(|(key, _, nested)| {
+                let r#type = b.nested_input(related, opposite, *nested);
+                let r#type = if relation.is_vec() && !matches!(nested, Nested::Bool) { format!("[{type}!]") } else { r#type };
+                FieldDef::new(*key, r#type)
+            }).collect()
+        })
+    }
+
+    fn nested_input(&mut self, model: &'a Model, without: Option<&'a Relation>, nested: Nested) -> String {
+        let suffix = Self::without_suffix(without);
+        match nested {
+            Nested::Create => self.create_input(model, without),
+            Nested::WhereUnique => self.where_unique_input(model),
+            Nested::Where => self.where_input(model),
+            Nested::Update => self.update_input(model, without),
+            Nested::Bool => "Boolean".to_owned(),
+            Nested::ConnectOrCreate => self.input(format!("{}ConnectOrCreate{suffix}Input", model.name()), |b| vec![
+                FieldDef::new("where", format!("{}!", b.where_unique_input(model))),
+                FieldDef::new("create", format!("{}!", b.create_input(model, without))),
+            ]),
+            Nested::UpdateWithWhereUnique => self.input(format!("{}UpdateWithWhereUnique{suffix}Input", model.name()), |b| vec![
+                FieldDef::new("where", format!("{}!", b.where_unique_input(model))),
+                FieldDef::new("update", format!("{}!", b.update_input(model, without))),
+            ]),
+            Nested::UpdateManyWithWhere => self.input(format!("{}UpdateManyWithWhere{suffix}Input", model.name()), |b| vec![
+                FieldDef::new("where", format!("{}!", b.where_input(model))),
+                FieldDef::new("update", format!("{}!", b.update_input(model, without))),
+            ]),
+            Nested::Upsert => self.input(format!("{}Upsert{suffix}Input", model.name()), |b| vec![
+                FieldDef::new("create", format!("{}!", b.create_input(model, without))),
+                FieldDef::new("update", format!("{}!", b.update_input(model, without))),
+            ]),
+            Nested::UpsertWithWhereUnique => self.input(format!("{}UpsertWithWhereUnique{suffix}Input", model.name()), |b| vec![
+                FieldDef::new("where", format!("{}!", b.where_unique_input(model))),
+                FieldDef::new("create", format!("{}!", b.create_input(model, without))),
+                FieldDef::new("update", format!("{}!", b.update_input(model, without))),
+            ]),
+        }
+    }
+}
@@ -1,13 +1,21 @@
 use jsonwebtoken::{encode, decode, Header, Validation, EncodingKey, DecodingKey};
 use serde::{Serialize, Deserialize};
-use serde_json::{Value as JsonValue};
+use serde_json::{Map, Value as JsonValue};
 use crate::core::error::Error;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub id: JsonValue,
     pub model: String,
-    pub exp: usize
+    pub exp: usize,
+    #[serde(default)]
+    pub iat: usize,
+    #[serde(default)]
+    pub jti: Option<String>,
+    #[serde(default)]
+    pub refresh: bool,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub claims: Map<String, JsonValue>,
 }
 
 pub fn encode_token(claims: Claims, secret: &str) -> String {
@@ -15,9 +23,14 @@ pub fn encode_token(claims: Claims, secret: &str) -> String {
     token.unwrap()
 }
 
+/// Tokens are revoked by `jti`, so tokens without `jti` or `iat` are rejected instead of being
+/// valid until they expire.
 pub fn decode_token(token: &String, secret: &str) -> Result<Claims, Error> {
     let token = decode::<Claims>(&token, &DecodingKey::from_secret(secret.as_ref()), &Validation::default());
     return match token {
+        Ok(token) if token.claims.jti.is_none() || token.claims.iat == 0 => {
+            Err(Error::invalid_auth_token())
+        }
         Ok(token) => {
             Ok(token.claims)
         }
@@ -1,31 +1,39 @@
 use std::sync::Arc;
 use futures_util::future;
-use std::time::SystemTime;
+use std::time::{Instant, SystemTime};
 use actix_http::body::BoxBody;
 use actix_http::{Method};
+use actix_http::header::{HeaderName, HeaderValue};
 use actix_web::{App, HttpRequest, HttpResponse, HttpServer, web};
-use actix_web::dev::{ServiceFactory, ServiceRequest, ServiceResponse};
-use actix_web::middleware::DefaultHeaders;
-use chrono::{DateTime, Duration, Local, Utc};
-use colored::Colorize;
+use actix_web::dev::{Service, ServiceFactory, ServiceRequest, ServiceResponse};
+use actix_web::middleware::{Compress, Condition};
+use actix_web::rt::time::timeout;
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+use colored::{ColoredString, Colorize};
 use futures_util::StreamExt;
 use key_path::{KeyPath, path};
-use serde_json::{json, Value as JsonValue};
+use serde_json::{json, Map as JsonMap, Value as JsonValue};
 use to_mut::ToMut;
+use uuid::Uuid;
 use crate::core::action::{
-    Action, CREATE, DELETE, ENTRY, FIND, IDENTITY, MANY, SINGLE, UPDATE, UPSERT,
+    Action, CREATE, DELETE, ENTRY, FIND, IDENTITY, MANY, RESTORE, SINGLE, UPDATE, UPSERT,
     FIND_UNIQUE_HANDLER, FIND_FIRST_HANDLER, FIND_MANY_HANDLER, CREATE_HANDLER, UPDATE_HANDLER,
     UPSERT_HANDLER, DELETE_HANDLER, CREATE_MANY_HANDLER, UPDATE_MANY_HANDLER, DELETE_MANY_HANDLER,
     COUNT_HANDLER, AGGREGATE_HANDLER, GROUP_BY_HANDLER, SIGN_IN_HANDLER, IDENTITY_HANDLER,
+    RESTORE_HANDLER, REFRESH_HANDLER, SIGN_OUT_HANDLER,
 };
 use crate::core::action::source::ActionSource;
 use crate::core::app::conf::ServerConf;
 use crate::core::app::entrance::Entrance;
 use crate::core::app::environment::EnvironmentVersion;
 use crate::core::app::migrate::migrate;
-use crate::core::connector::SaveSession;
+use crate::core::connector::{ConnectorHealth, SaveSession};
+use crate::core::database::consistency;
+use crate::core::database::log as query_log;
 use self::jwt_token::{Claims, decode_token, encode_token};
 use crate::core::graph::Graph;
+use crate::core::handler::Handler;
+use crate::core::handler::ctx::HandlerCtx;
 use crate::core::model::Model;
 use crate::core::object::Object;
 use crate::core::pipeline::ctx::{Ctx};
@@ -36,6 +44,8 @@ use crate::teon;
 
 pub(crate) mod response;
 pub(crate) mod jwt_token;
+pub(crate) mod graphql;
+pub(crate) mod cors;
 
 fn j(v: Value) -> JsonValue {
     v.into()
@@ -55,6 +65,15 @@ fn path_components(path: &str) -> Vec<&str> {
     retval
 }
 
+/// The request id shown after the log line of a request, so that it can be matched with the logged
+/// database operations.
+fn request_id_label() -> ColoredString {
+    match query_log::request_id() {
+        Some(id) if query_log::enabled() => format!(" {id}").dimmed(),
+        _ => "".normal(),
+    }
+}
+
 fn log_unhandled(start: SystemTime, method: &str, path: &str, code: u16) {
     let now = SystemTime::now();
     let local: DateTime<Local> = Local::now();
@@ -69,7 +88,7 @@ fn log_unhandled(start: SystemTime, method: &str, path: &str, code: u16) {
     let ms_str = format!("{ms}ms").dimmed();
     let local_formatted = format!("{local}").dimmed();
     let unhandled = "Unhandled".red();
-    println!("{} {} {} on {} - {} {}", local_formatted, unhandled, method.bold(), path, code_string, ms_str);
+    println!("{} {} {} on {} - {} {}{}", local_formatted, unhandled, method.bold(), path, code_string, ms_str, request_id_label());
 }
 
 fn log_request(start: SystemTime, action: &str, model: &str, code: u16) {
@@ -85,38 +104,108 @@ fn log_request(start: SystemTime, action: &str, model: &str, code: u16) {
     let ms = elapsed.as_millis();
     let ms_str = format!("{ms}ms").normal().clear();
     let local_formatted = format!("{local}").dimmed();
-    println!("{} {} on {} - {} {}", local_formatted, action.bold(), model, code_string, ms_str.dimmed());
+    println!("{} {} on {} - {} {}{}", local_formatted, action.bold(), model, code_string, ms_str.dimmed(), request_id_label());
 }
 
-async fn get_identity(r: &HttpRequest, graph: &Graph, conf: &ServerConf) -> Result<Option<Object>, Error> {
+/// Read the identity from the authorization header. Refresh tokens, revoked tokens and tokens of
+/// models which are not identities are rejected.
+async fn get_identity(r: &HttpRequest, graph: &Graph, conf: &ServerConf) -> Result<Option<(Object, Claims)>, Error> {
     let header_value = r.headers().get("authorization");
     if let None = header_value {
         return Ok(None);
     }
-    let auth_str = header_value.unwrap().to_str().unwrap();
+    let auth_str = match header_value.unwrap().to_str() {
+        Ok(auth_str) => auth_str,
+        Err(_) => return Err(Error::invalid_auth_token()),
+    };
     if auth_str.len() < 7 {
         return Err(Error::invalid_auth_token());
     }
     let token_str = &auth_str[7..];
-    let claims_result = decode_token(&token_str.to_string(), &conf.jwt_secret.as_ref().unwrap());
-    if let Err(_) = claims_result {
+    let Some(jwt_secret) = conf.jwt_secret.as_ref() else {
+        return Err(Error::invalid_auth_token());
+    };
+    let claims = decode_token(&token_str.to_string(), jwt_secret)?;
+    if claims.refresh {
         return Err(Error::invalid_auth_token());
     }
-    let claims = claims_result.unwrap();
-    let json_identifier = claims.id;
-    let tson_identifier = Decoder::decode_object(graph.model(&claims.model).unwrap(), graph, &json_identifier)?;
-    // Decoder::
-    let _model = graph.model(claims.model.as_str()).unwrap();
+    if let Some(jti) = claims.jti.as_ref() {
+        if conf.revocation_store.is_revoked(graph, jti).await? {
+            return Err(Error::invalid_auth_token());
+        }
+    }
+    let identity = find_identity(graph, &claims).await?;
+    Ok(Some((identity, claims)))
+}
+
+/// Fetch the object which `claims` are issued for.
+async fn find_identity(graph: &Graph, claims: &Claims) -> Result<Object, Error> {
+    let model = match graph.model(claims.model.as_str()) {
+        Some(model) if model.identity() => model,
+        _ => return Err(Error::invalid_auth_token()),
+    };
+    let tson_identifier = Decoder::decode_object(model, graph, &claims.id)?;
     let identity = graph.find_unique_internal(
-        graph.model(claims.model.as_str()).unwrap().name(),
+        model.name(),
         &teon!({
             "where": tson_identifier
         }),
         true, Action::from_u32(IDENTITY | FIND | SINGLE | ENTRY), ActionSource::ProgramCode).await;
-    if let Err(_) = identity {
-        return Err(Error::invalid_auth_token())
+    match identity {
+        Ok(identity) => Ok(identity),
+        Err(_) => Err(Error::invalid_auth_token()),
+    }
+}
+
+/// Issue an access token and a refresh token for `obj`. Expiries set on the identity model take
+/// precedence over the server config.
+async fn issue_tokens(obj: &Object, conf: &ServerConf) -> Result<(String, String), Error> {
+    let Some(jwt_secret) = conf.jwt_secret.as_ref() else {
+        return Err(Error::internal_server_error("Missing JWT secret."));
+    };
+    let model = obj.model();
+    let custom_claims: JsonMap<String, JsonValue> = match model.identity_claims() {
+        Some(pipeline) => match pipeline.process(Ctx::initial_state_with_object(obj.clone())).await? {
+            Value::HashMap(map) => map.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            _ => return Err(Error::internal_server_error("Identity claims should be a dictionary.")),
+        },
+        None => JsonMap::new(),
+    };
+    let now = Utc::now();
+    let json_identifier: JsonValue = obj.identifier().into();
+    let token_expiry = model.identity_token_expiry().unwrap_or(conf.token_expiry);
+    let refresh_token_expiry = model.identity_refresh_token_expiry().unwrap_or(conf.refresh_token_expiry);
+    let token = encode_token(Claims {
+        id: json_identifier.clone(),
+        model: model.name().to_string(),
+        exp: (now + Duration::seconds(token_expiry)).timestamp() as usize,
+        iat: now.timestamp() as usize,
+        jti: Some(Uuid::new_v4().to_string()),
+        refresh: false,
+        claims: custom_claims,
+    }, jwt_secret);
+    let refresh_token = encode_token(Claims {
+        id: json_identifier,
+        model: model.name().to_string(),
+        exp: (now + Duration::seconds(refresh_token_expiry)).timestamp() as usize,
+        iat: now.timestamp() as usize,
+        jti: Some(Uuid::new_v4().to_string()),
+        refresh: true,
+        claims: JsonMap::new(),
+    }, jwt_secret);
+    Ok((token, refresh_token))
+}
+
+/// Revoke the token `claims` are decoded from until it expires.
+/// Revoke the token of `claims`, `false` if it was revoked already.
+async fn revoke_token(graph: &Graph, conf: &ServerConf, claims: &Claims) -> Result<bool, Error> {
+    match claims.jti.as_ref() {
+        Some(jti) => {
+            let expires_at = Utc.timestamp_opt(claims.exp as i64, 0).single().unwrap_or(Utc::now());
+            conf.revocation_store.revoke(graph, jti, expires_at).await
+        }
+        None => Ok(true),
     }
-    return Ok(Some(identity.unwrap()));
 }
 
 async fn handle_find_unique(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
@@ -218,8 +307,9 @@ async fn handle_create(graph: &Graph, input: &Value, model: &Model, source: Acti
     let create = input.get("create");
     let include = input.get("include");
     let select = input.get("select");
-    let session = graph.connector().new_save_session();
-    let result = handle_create_internal(graph, create, include, select, model, &path!["create"], action, source, session).await;
+    let result = graph.transaction(|graph| async move {
+        handle_create_internal(&graph, create, include, select, model, &path!["create"], action, source, graph.save_session(model)).await
+    }).await;
     match result {
         Ok(val) => {
             let json_val: JsonValue = val.into();
@@ -240,16 +330,14 @@ async fn handle_update_internal(_graph: &Graph, object: Object, update: Option<&
 
 async fn handle_update(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
     let action = Action::from_u32(UPDATE | ENTRY | SINGLE);
-    let result = graph.find_unique_internal(model.name(), input, true, action, source).await;
-    if result.is_err() {
-        return HttpResponse::NotFound().json(json!({"error": result.err()}));
-    }
-    let result = result.unwrap();
     let update = input.get("update");
     let include = input.get("include");
     let select = input.get("select");
     let r#where = input.get("where");
-    let update_result = handle_update_internal(graph, result.clone(), update, include, select, r#where, model).await;
+    let update_result = graph.transaction(|graph| async move {
+        let result = graph.find_unique_internal(model.name(), input, true, action, source).await?;
+        handle_update_internal(&graph, result, update, include, select, r#where, model).await
+    }).await;
     match update_result {
         Ok(value) => {
             let json_val: JsonValue = value.into();
@@ -261,91 +349,94 @@ async fn handle_update(graph: &Graph, input: &Value, model: &Model, source: Acti
     }
 }
 
-async fn handle_upsert(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
+async fn handle_upsert_internal(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> Result<Value, Error> {
     let action = Action::from_u32(UPSERT | UPDATE | ENTRY | SINGLE);
     let result = graph.find_unique_internal(model.name(), input, true, action, source.clone()).await;
     let include = input.get("include");
     let select = input.get("select");
-    return match result {
+    let empty = teon!({});
+    let obj = match result {
         Ok(obj) => {
             // find the object here
-            let update = input.get("update");
-            let set_json_result = match update {
-                Some(update) => {
-                    obj.set_teon_with_path(update, &path!["update"]).await
-                }
-                None => {
-                    let empty = teon!({});
-                    obj.set_teon_with_path(&empty, &path!["update"]).await
-                }
-            };
-            match set_json_result {
-                Ok(_) => {
-                    match obj.save().await {
-                        Ok(_) => {
-                            // refetch here
-                            let refetched = obj.refreshed(include, select).await.unwrap();
-                            let json_val: JsonValue = refetched.to_json_internal(&path!["data"]).await.unwrap().into();
-                            HttpResponse::Ok().json(json!({"data": json_val}))
-                        }
-                        Err(err) => {
-                            HttpResponse::BadRequest().json(json!({"error": err}))
-                        }
-                    }
-                }
-                Err(err) => {
-                    HttpResponse::BadRequest().json(json!({"error": err}))
-                }
-            }
+            let update = input.get("update").unwrap_or(&empty);
+            obj.set_teon_with_path(update, &path!["update"]).await?;
+            obj
         }
         Err(_) => {
-            let create = input.get("create");
+            let create = input.get("create").unwrap_or(&empty);
             let action = Action::from_u32(UPSERT | CREATE | ENTRY | SINGLE);
-            let obj = graph.new_object(model.name(), action, source).unwrap();
-            let set_json_result = match create {
-                Some(create) => {
-                    obj.set_teon_with_path(create, &path!["create"]).await
-                }
-                None => {
-                    let empty = teon!({});
-                    obj.set_teon_with_path(&empty, &path!["create"]).await
-                }
-            };
-            match set_json_result {
-                Ok(_) => {
-                    match obj.save().await {
-                        Ok(_) => {
-                            // refetch here
-                            let refetched = obj.refreshed(include, select).await.unwrap();
-                            let json_data: JsonValue = refetched.to_json_internal(&path!["data"]).await.unwrap().into();
-                            return HttpResponse::Ok().json(json!({"data": json_data}));
-                        }
-                        Err(err) => {
-                            HttpResponse::BadRequest().json(json!({"error": err}))
-                        }
-                    }
-                }
-                Err(err) => {
-                    HttpResponse::BadRequest().json(json!({"error": err}))
-                }
-            }
+            let obj = graph.new_object(model.name(), action, source)?;
+            obj.set_teon_with_path(create, &path!["create"]).await?;
+            obj
+        }
+    };
+    obj.save().await?;
+    // refetch here
+    let refetched = obj.refreshed(include, select).await?;
+    refetched.to_json_internal(&path!["data"]).await
+}
+
+async fn handle_upsert(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
+    let result = graph.transaction(|graph| async move {
+        handle_upsert_internal(&graph, input, model, source).await
+    }).await;
+    match result {
+        Ok(value) => {
+            let json_val: JsonValue = value.into();
+            HttpResponse::Ok().json(json!({"data": json_val}))
+        }
+        Err(err) => {
+            HttpResponse::BadRequest().json(json!({"error": err}))
         }
     }
 }
 
 async fn handle_delete(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
     let action = Action::from_u32(DELETE | SINGLE | ENTRY);
-    let result = graph.find_unique_internal(model.name(), input, true, action, source).await;
-    if result.is_err() {
-        return HttpResponse::NotFound().json(json!({"error": result.err()}));
-    }
-    let result = result.unwrap();
-    // find the object here
-    return match result.delete_internal(path!["delete"]).await {
-        Ok(_) => {
-            let json_data: JsonValue = result.to_json_internal(&path!["data"]).await.unwrap().into();
+    let result = graph.transaction(|graph| async move {
+        let object = match graph.find_unique_internal(model.name(), input, true, action, source).await {
+            Ok(object) => object,
+            Err(err) => return Ok(Err(err)),
+        };
+        // find the object here
+        object.delete_internal(path!["delete"]).await?;
+        Ok(Ok(object))
+    }).await;
+    match result {
+        Ok(Ok(object)) => {
+            let json_data: JsonValue = object.to_json_internal(&path!["data"]).await.unwrap().into();
+            HttpResponse::Ok().json(json!({"data": json_data}))
+        }
+        Ok(Err(err)) => {
+            HttpResponse::NotFound().json(json!({"error": err}))
+        }
+        Err(err) => {
+            err.into()
+        }
+    }
+}
+
+async fn handle_restore(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
+    let action = Action::from_u32(RESTORE | SINGLE | ENTRY);
+    let mut finder = input.as_hashmap().unwrap().clone();
+    finder.insert("onlyDeleted".to_owned(), Value::Bool(true));
+    let finder = Value::HashMap(finder);
+    let result = graph.transaction(|graph| async move {
+        let object = match graph.find_unique_internal(model.name(), &finder, true, action, source).await {
+            Ok(object) => object,
+            Err(err) => return Ok(Err(err)),
+        };
+        object.restore_internal(path!["restore"]).await?;
+        Ok(Ok(object))
+    }).await;
+    match result {
+        Ok(Ok(object)) => {
+            let json_data: JsonValue = object.to_json_internal(&path!["data"]).await.unwrap().into();
             HttpResponse::Ok().json(json!({"data": json_data}))
         }
+        Ok(Err(err)) => {
+            HttpResponse::NotFound().json(json!({"error": err}))
+        }
         Err(err) => {
             err.into()
         }
@@ -368,80 +459,73 @@ async fn handle_create_many(graph: &Graph, input: &Value, model: &Model, source:
         return HttpResponse::BadRequest().json(json!({"error": err}));
     }
     let create = create.as_vec().unwrap();
-    let mut count = 0;
-    let mut ret_data: Vec<Value> = vec![];
-    let session = graph.connector().new_save_session();
-    for (index, val) in create.iter().enumerate() {
-        let result = handle_create_internal(graph, Some(val), include, select, model, &path!["create", index], action, source.clone(), session.clone()).await;
-        match result {
-            Err(err) => {
-                println!("{:?}", err.errors);
-            },
-            Ok(val) => {
-                count += 1;
-                ret_data.push(val);
-            }
+    let result = graph.transaction(|graph| async move {
+        let session = graph.save_session(model);
+        let mut ret_data: Vec<Value> = vec![];
+        for (index, val) in create.iter().enumerate() {
+            let val = handle_create_internal(&graph, Some(val), include, select, model, &path!["create", index], action, source.clone(), session.clone()).await?;
+            ret_data.push(val);
         }
+        Ok(ret_data)
+    }).await;
+    match result {
+        Ok(ret_data) => {
+            let count = ret_data.len();
+            let json_ret_data: JsonValue = Value::Vec(ret_data).into();
+            HttpResponse::Ok().json(json!({
+                "meta": {"count": count},
+                "data": json_ret_data
+            }))
+        }
+        Err(err) => HttpResponse::BadRequest().json(json!({"error": err}))
     }
-    let json_ret_data: JsonValue = Value::Vec(ret_data).into();
-    HttpResponse::Ok().json(json!({
-        "meta": {"count": count},
-        "data": json_ret_data
-    }))
 }
 
 async fn handle_update_many(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
     let action = Action::from_u32(UPDATE | MANY | ENTRY);
-    let result = graph.find_many_internal(model.name(), input, true, action, source).await;
-    if result.is_err() {
-        return HttpResponse::BadRequest().json(json!({"error": result.err()}));
-    }
-    let result = result.unwrap();
     let update = input.get("update");
     let include = input.get("include");
     let select = input.get("select");
-
-    let mut count = 0;
-    let mut ret_data: Vec<Value> = vec![];
-    for object in result {
-        let update_result = handle_update_internal(graph, object.clone(), update, include, select, None, model).await;
-        match update_result {
-            Ok(json_value) => {
-                ret_data.push(json_value);
-                count += 1;
-            }
-            Err(_err) => {}
+    let result = graph.transaction(|graph| async move {
+        let objects = graph.find_many_internal(model.name(), input, true, action, source).await?;
+        let mut ret_data: Vec<Value> = vec![];
+        for object in objects {
+            ret_data.push(handle_update_internal(&graph, object, update, include, select, None, model).await?);
         }
+        Ok(ret_data)
+    }).await;
+    match result {
+        Ok(ret_data) => {
+            HttpResponse::Ok().json(json!({
+                "meta": {
+                    "count": ret_data.len()
+                },
+                "data": j(Value::Vec(ret_data))
+            }))
+        }
+        Err(err) => HttpResponse::BadRequest().json(json!({"error": err}))
     }
-    HttpResponse::Ok().json(json!({
-            "meta": {
-                "count": count
-            },
-            "data": j(Value::Vec(ret_data))
-        }))
 }
 
 async fn handle_delete_many(graph: &Graph, input: &Value, model: &Model, source: ActionSource) -> HttpResponse {
     let action = Action::from_u32(DELETE | MANY | ENTRY);
-    let result = graph.find_many_internal(model.name(), input, true, action, source).await;
-    if result.is_err() {
-        return HttpResponse::BadRequest().json(json!({"error": result.err()}));
-    }
-    let result = result.unwrap();
+    let result = graph.transaction(|graph| async move {
+        let objects = graph.find_many_internal(model.name(), input, true, action, source).await?;
+        for object in objects.iter() {
+            object.delete_internal(path!["delete"]).await?;
+        }
+        Ok(objects)
+    }).await;
+    let result = match result {
+        Ok(result) => result,
+        Err(err) => return HttpResponse::BadRequest().json(json!({"error": err})),
+    };
     let mut count = 0;
     let mut retval: Vec<Value> = vec![];
     for (index, object) in result.iter().enumerate() {
-        match object.delete_internal(path!["delete"]).await {
-            Ok(_) => {
-                match object.to_json_internal(&path!["data", index]).await {
-                    Ok(result) => {
-                        retval.push(result);
-                        count += 1;
-                    },
-                    Err(_) => ()
-                }
-            }
-            Err(_) => {}
+        if let Ok(result) = object.to_json_internal(&path!["data", index]).await {
+            retval.push(result);
+            count += 1;
         }
     }
     HttpResponse::Ok().json(json!({
@@ -549,26 +633,93 @@ async fn handle_sign_in(graph: &Graph, input: &Value, model: &Model, conf: &Serv
             let select = input.get("select");
             let obj = obj.refreshed(include, select).await.unwrap();
             let json_data = obj.to_json_internal(&path!["data"]).await;
-            let exp: usize = (Utc::now() + Duration::days(365)).timestamp() as usize;
-            let tson_identifier = obj.identifier();
-            let json_identifier: JsonValue = tson_identifier.into();
-            let claims = Claims {
-                id: json_identifier,
-                model: obj.model().name().to_string(),
-                exp
+            let (token, refresh_token) = match issue_tokens(&obj, conf).await {
+                Ok(tokens) => tokens,
+                Err(err) => return err.into(),
             };
-            if conf.jwt_secret.as_ref().is_none() {
-                return super::super::error::Error::internal_server_error("Missing JWT secret.").into();
-            }
-            let token = encode_token(claims, &conf.jwt_secret.as_ref().unwrap());
             HttpResponse::Ok().json(json!({
-            "meta": {
-                "token": token
-            },
-            "data": j(json_data.unwrap())
-        }))
+                "meta": {
+                    "token": token,
+                    "refreshToken": refresh_token
+                },
+                "data": j(json_data.unwrap())
+            }))
+        }
+    }
+}
+
+async fn handle_refresh(graph: &Graph, input: &Value, model: &Model, conf: &ServerConf) -> HttpResponse {
+    let input = input.as_hashmap().unwrap();
+    let Some(refresh_token) = input.get("refreshToken").and_then(|t| t.as_str()) else {
+        return Error::missing_required_input_with_type("string", path!["refreshToken"]).into();
+    };
+    let Some(jwt_secret) = conf.jwt_secret.as_ref() else {
+        return Error::internal_server_error("Missing JWT secret.").into();
+    };
+    // a refresh token without an id can't be revoked, so it couldn't be used only once
+    let claims = match decode_token(&refresh_token.to_string(), jwt_secret) {
+        Ok(claims) if claims.refresh && claims.model == model.name() && claims.jti.is_some() => claims,
+        _ => return Error::invalid_auth_token().into(),
+    };
+    let obj = match find_identity(graph, &claims).await {
+        Ok(obj) => obj,
+        Err(err) => return err.into(),
+    };
+    // a refresh token is used only once, only the request which revokes it gets new tokens
+    match revoke_token(graph, conf, &claims).await {
+        Ok(true) => (),
+        Ok(false) => return Error::invalid_auth_token().into(),
+        Err(err) => return err.into(),
+    }
+    let obj = match obj.refreshed(input.get("include"), input.get("select")).await {
+        Ok(obj) => obj,
+        Err(err) => return err.into(),
+    };
+    let json_data = match obj.to_json_internal(&path!["data"]).await {
+        Ok(json_data) => json_data,
+        Err(err) => return err.into(),
+    };
+    let (token, refresh_token) = match issue_tokens(&obj, conf).await {
+        Ok(tokens) => tokens,
+        Err(err) => return err.into(),
+    };
+    HttpResponse::Ok().json(json!({
+        "meta": {
+            "token": token,
+            "refreshToken": refresh_token
+        },
+        "data": j(json_data)
+    }))
+}
+
+async fn handle_sign_out(graph: &Graph, input: &Value, model: &Model, conf: &ServerConf, identity: Option<&(Object, Claims)>) -> HttpResponse {
+    let Some((obj, claims)) = identity else {
+        return HttpResponse::Unauthorized().json(json!({"error": Error::invalid_auth_token()}));
+    };
+    if obj.model() != model {
+        return HttpResponse::Unauthorized().json(json!({"error": Error::wrong_identity_model()}));
+    }
+    let input = input.as_hashmap().unwrap();
+    if let Some(refresh_token) = input.get("refreshToken").and_then(|t| t.as_str()) {
+        let refresh_claims = match decode_token(&refresh_token.to_string(), conf.jwt_secret.as_ref().unwrap()) {
+            Ok(refresh_claims) if refresh_claims.refresh && refresh_claims.model == claims.model && refresh_claims.id == claims.id => refresh_claims,
+            _ => return Error::unexpected_input_value("A refresh token of this identity.", path!["refreshToken"]).into(),
+        };
+        if let Err(err) = revoke_token(graph, conf, &refresh_claims).await {
+            return err.into();
         }
     }
+    if let Err(err) = revoke_token(graph, conf, claims).await {
+        return err.into();
+    }
+    let refreshed = match obj.refreshed(input.get("include"), input.get("select")).await {
+        Ok(refreshed) => refreshed,
+        Err(err) => return err.into(),
+    };
+    let json_data = refreshed.to_json_internal(&path!["data"]).await;
+    HttpResponse::Ok().json(json!({
+        "data": j(json_data.unwrap())
+    }))
 }
 
 async fn handle_identity(_graph: &Graph, input: &Value, model: &Model, _conf: &ServerConf, source: ActionSource) -> HttpResponse {
@@ -591,6 +742,215 @@ async fn handle_identity(_graph: &Graph, input: &Value, model: &Model, _conf: &S
     }
 }
 
+async fn read_json_body(payload: &mut web::Payload, limit: usize) -> Result<JsonValue, HttpResponse> {
+    let mut body = web::BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.unwrap();
+        // limit max size of in-memory payload
+        if (body.len() + chunk.len()) > limit {
+            return Err(Error::payload_too_large(limit).into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    let parsed_body: JsonValue = match serde_json::from_slice(&body) {
+        Ok(b) => b,
+        Err(_) => return Err(HttpResponse::BadRequest().json(json!({"error": Error::incorrect_json_format()}))),
+    };
+    if !parsed_body.is_object() {
+        return Err(HttpResponse::BadRequest().json(json!({"error": Error::unexpected_input_root_type("object")})));
+    }
+    Ok(parsed_body)
+}
+
+/// Decode, transform and perform an action with the identity's permissions.
+async fn handle_action(graph: &Graph, conf: &ServerConf, model_def: &Model, action: Action, parsed_body: &JsonValue, identity: Option<(Object, Claims)>) -> HttpResponse {
+    let parsed_body = match Decoder::decode_action_arg(model_def, graph, action, parsed_body) {
+        Ok(body) => body,
+        Err(err) => return err.into()
+    };
+    let (transformed_body, transformed_action) = if model_def.has_action_transformers() || parsed_body.as_hashmap().unwrap().get("include").is_some() {
+        if ((action.to_u32() == CREATE_MANY_HANDLER) || (action.to_u32() == CREATE_HANDLER)) && (parsed_body.get("create").unwrap().is_vec()) {
+            // create with many items
+            let entries = parsed_body.get("create").unwrap().as_vec().unwrap();
+            let mut transformed_entries: Vec<Value> = vec![];
+            let mut new_action = action;
+            for entry in entries.iter() {
+                let ctx = Ctx::initial_state_with_value(teon!({"create": entry})).with_action(action);
+                match model_def.transformed_action(ctx).await {
+                    Ok(result) => {
+                        transformed_entries.push(result.0.get("create").unwrap().clone());
+                        new_action = result.1;
+                    },
+                    Err(err) => return err.into(),
+                }
+            }
+            let mut new_val = parsed_body.clone();
+            new_val.as_hashmap_mut().unwrap().insert("create".to_owned(), Value::Vec(transformed_entries));
+            (new_val, new_action)
+        } else {
+            let ctx = Ctx::initial_state_with_value(parsed_body).with_action(action);
+            match model_def.transformed_action(ctx).await {
+                Ok(result) => result,
+                Err(err) => return err.into(),
+            }
+        }
+    } else {
+        (parsed_body, action)
+    };
+    let source = ActionSource::Identity(identity.as_ref().map(|(obj, _)| obj.clone()));
+    match transformed_action.to_u32() {
+        FIND_UNIQUE_HANDLER => {
+            handle_find_unique(graph, &transformed_body, model_def, source.clone()).await
+        }
+        FIND_FIRST_HANDLER => {
+            handle_find_first(graph, &transformed_body, model_def, source.clone()).await
+        }
+        FIND_MANY_HANDLER => {
+            handle_find_many(graph, &transformed_body, model_def, source.clone()).await
+        }
+        CREATE_HANDLER => {
+            handle_create(graph, &transformed_body, model_def, source.clone()).await
+        }
+        UPDATE_HANDLER => {
+            handle_update(graph, &transformed_body, model_def, source.clone()).await
+        }
+        UPSERT_HANDLER => {
+            handle_upsert(graph, &transformed_body, model_def, source.clone()).await
+        }
+        DELETE_HANDLER => {
+            handle_delete(graph, &transformed_body, model_def, source.clone()).await
+        }
+        CREATE_MANY_HANDLER => {
+            handle_create_many(graph, &transformed_body, model_def, source.clone()).await
+        }
+        UPDATE_MANY_HANDLER => {
+            handle_update_many(graph, &transformed_body, model_def, source.clone()).await
+        }
+        DELETE_MANY_HANDLER => {
+            handle_delete_many(graph, &transformed_body, model_def, source.clone()).await
+        }
+        COUNT_HANDLER => {
+            handle_count(graph, &transformed_body, model_def, source.clone()).await
+        }
+        AGGREGATE_HANDLER => {
+            handle_aggregate(graph, &transformed_body, model_def, source.clone()).await
+        }
+        GROUP_BY_HANDLER => {
+            handle_group_by(graph, &transformed_body, model_def, source.clone()).await
+        }
+        SIGN_IN_HANDLER => {
+            handle_sign_in(graph, &transformed_body, model_def, conf).await
+        }
+        IDENTITY_HANDLER => {
+            handle_identity(graph, &transformed_body, model_def, conf, source.clone()).await
+        }
+        RESTORE_HANDLER => {
+            handle_restore(graph, &transformed_body, model_def, source.clone()).await
+        }
+        REFRESH_HANDLER => {
+            handle_refresh(graph, &transformed_body, model_def, conf).await
+        }
+        SIGN_OUT_HANDLER => {
+            handle_sign_out(graph, &transformed_body, model_def, conf, identity.as_ref()).await
+        }
+        _ => unreachable!()
+    }
+}
+
+/// Encode the return value of a custom handler. Objects are encoded like in built-in actions.
+async fn handler_output_json(value: Value) -> Result<JsonValue, Error> {
+    match value {
+        Value::Object(object) => Ok(object.to_json_internal(&path!["data"]).await?.into()),
+        Value::Vec(values) if values.iter().all(|v| v.is_object()) => {
+            let mut retval: Vec<JsonValue> = vec![];
+            for (index, value) in values.iter().enumerate() {
+                retval.push(value.as_object().unwrap().to_json_internal(&path!["data", index]).await?.into());
+            }
+            Ok(JsonValue::Array(retval))
+        }
+        value => Ok(value.into()),
+    }
+}
+
+/// A custom handler may read and write objects of its model, so the identity needs both the
+/// `@@canRead` and the `@@canMutate` permission of the model to call it.
+async fn check_handler_permission(graph: &Graph, model_def: &Model, identity: Option<Object>) -> Result<(), Error> {
+    let object = graph.new_object(model_def.name(), Action::from_u32(UPDATE | SINGLE | ENTRY), ActionSource::Identity(identity))?;
+    object.check_model_read_permission(path![]).await?;
+    object.check_model_write_permission(path![]).await
+}
+
+/// Decode the input of a custom handler and call its implementation with the identity. The
+/// implementation runs in a transaction, it's rolled back if the handler returns an error.
+async fn handle_custom_handler(graph: &'static Graph, model_def: &Model, handler: &Handler, parsed_body: &JsonValue, identity: Option<(Object, Claims)>) -> HttpResponse {
+    let Some(implementation) = handler.implementation() else {
+        return Error::internal_server_error(format!("Handler '{}' of model '{}' is not implemented.", handler.name(), model_def.name())).into();
+    };
+    let input = match Decoder::decode_handler_input(graph, handler, parsed_body) {
+        Ok(input) => input,
+        Err(err) => return err.into()
+    };
+    let identity = identity.map(|(obj, _)| obj);
+    if let Err(err) = check_handler_permission(graph, model_def, identity.clone()).await {
+        return err.into();
+    }
+    let ctx = HandlerCtx { graph, model: model_def.clone(), input, identity };
+    let value = match graph.transaction(|_| implementation(ctx)).await {
+        Ok(value) => value,
+        Err(err) => return err.into()
+    };
+    match handler_output_json(value).await {
+        Ok(data) => HttpResponse::Ok().json(json!({"data": data})),
+        Err(err) => err.into()
+    }
+}
+
+/// How long a health check is reused for before the databases are checked again.
+const HEALTH_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Report whether the database of every connector is reachable, with pool usage. Responds with
+/// 503 if one isn't, so that load balancers take this instance out. The endpoint is public, so
+/// latencies and database errors are only reported with `healthDetails`.
+async fn handle_health(graph: &'static Graph, conf: &ServerConf) -> HttpResponse {
+    let mut last_checks = conf.health_checks.lock().await;
+    let checks = match last_checks.as_ref() {
+        Some((checked_at, checks)) if checked_at.elapsed() < HEALTH_CHECK_INTERVAL => checks.clone(),
+        _ => {
+            let checks: Vec<(String, ConnectorHealth)> = future::join_all(graph.connector_names().into_iter().map(|name| async move {
+                (name.to_owned(), graph.connector_named(name).health().await)
+            })).await;
+            *last_checks = Some((Instant::now(), checks.clone()));
+            checks
+        }
+    };
+    drop(last_checks);
+    let healthy = checks.iter().all(|(_, health)| health.reachable);
+    let mut connectors = JsonMap::new();
+    for (name, health) in checks {
+        let mut entry = JsonMap::new();
+        entry.insert("reachable".to_owned(), json!(health.reachable));
+        if conf.health_details {
+            if let Some(latency) = health.latency {
+                entry.insert("latencyMs".to_owned(), json!(latency.as_secs_f64() * 1000.0));
+            }
+            if let Some(error) = health.error {
+                entry.insert("error".to_owned(), json!(error));
+            }
+        }
+        // pool usage is current, it's read without reaching the database
+        if let Some(pool) = graph.connector_named(&name).pool_stats().await.or(health.pool) {
+            entry.insert("pool".to_owned(), json!({"size": pool.size, "inUse": pool.in_use, "timeouts": pool.timeouts}));
+        }
+        connectors.insert(name, JsonValue::Object(entry));
+    }
+    let body = json!({"status": if healthy { "ok" } else { "unavailable" }, "connectors": connectors});
+    if healthy {
+        HttpResponse::Ok().json(body)
+    } else {
+        HttpResponse::ServiceUnavailable().json(body)
+    }
+}
+
 pub fn make_app(graph: Graph, conf: ServerConf) ->  App<impl ServiceFactory<
     ServiceRequest,
     Response = ServiceResponse<BoxBody>,
@@ -612,11 +972,32 @@ fn make_app_inner(graph: &'static Graph, conf: &'static ServerConf) -> App<impl
     Error = actix_web::Error,
 > + 'static> {
     let app = App::new()
-        .wrap(DefaultHeaders::new()
-            .add(("Access-Control-Allow-Origin", "*"))
-            .add(("Access-Control-Allow-Methods", "OPTIONS, POST, GET"))
-            .add(("Access-Control-Allow-Headers", "*"))
-            .add(("Access-Control-Max-Age", "86400")))
+        .wrap_fn(move |req, srv| {
+            let start = SystemTime::now();
+            let request = req.request().clone();
+            let request_id = match request.headers().get("x-request-id").and_then(|v| v.to_str().ok()) {
+                Some(id) => id.to_owned(),
+                None => Uuid::new_v4().to_string(),
+            };
+            let response = srv.call(req);
+            query_log::with_request_id(request_id.clone(), consistency::track_writes(async move {
+                let mut response = match conf.request_timeout {
+                    Some(seconds) => match timeout(std::time::Duration::from_secs(seconds), response).await {
+                        Ok(response) => response?,
+                        Err(_) => {
+                            log_unhandled(start, request.method().as_str(), request.path(), 408);
+                            ServiceResponse::new(request.clone(), Error::request_timeout().into())
+                        }
+                    },
+                    None => response.await?,
+                };
+                cors::apply_cors_headers(&conf.cors, request.headers(), response.headers_mut());
+                if let Ok(value) = HeaderValue::from_str(&request_id) {
+                    response.headers_mut().insert(HeaderName::from_static("x-request-id"), value);
+                }
+                Ok(response)
+            }))
+        })
         .default_service(web::route().to(move |r: HttpRequest, mut payload: web::Payload| async move {
             let start = SystemTime::now();
             let mut path = r.path().to_string();
@@ -632,6 +1013,12 @@ fn make_app_inner(graph: &'static Graph, conf: &'static ServerConf) -> App<impl
             } else {
                 path
             };
+            if path == "/_health" && r.method() == Method::GET {
+                return handle_health(graph, conf).await;
+            }
+            if conf.graphql && path == "/graphql" {
+                return graphql::handle_graphql(graph, conf, &r, &mut payload, start).await;
+            }
             if (r.method() != Method::POST) && (r.method() != Method::OPTIONS) {
                 log_unhandled(start, r.method().as_str(), &path, 404);
                 return Error::destination_not_found().into();
@@ -644,14 +1031,6 @@ fn make_app_inner(graph: &'static Graph, conf: &'static ServerConf) -> App<impl
             }
             let model_url_segment_name = path_components[0];
             let action_segment_name = path_components[2];
-            let action = Action::handler_from_name(action_segment_name);
-            let action = match action {
-                Some(a) => a,
-                None => {
-                    log_unhandled(start, r.method().as_str(), &path, 404);
-                    return Error::destination_not_found().into();
-                }
-            };
             let model_def = match graph.model_with_url_segment_name(model_url_segment_name) {
                 Some(name) => name,
                 None => {
@@ -659,6 +1038,35 @@ fn make_app_inner(graph: &'static Graph, conf: &'static ServerConf) -> App<impl
                     return Error::destination_not_found().into();
                 }
             };
+            let action = Action::handler_from_name(action_segment_name);
+            let action = match action {
+                Some(a) => a,
+                None => match model_def.handler(action_segment_name) {
+                    Some(handler) => {
+                        if r.method() == Method::OPTIONS {
+                            return HttpResponse::Ok().json(json!({}));
+                        }
+                        let parsed_body = match read_json_body(&mut payload, conf.body_limit).await {
+                            Ok(body) => body,
+                            Err(response) => {
+                                log_unhandled(start, r.method().as_str(), &path, response.status().as_u16());
+                                return response;
+                            }
+                        };
+                        let identity = match get_identity(&r, graph, conf).await {
+                            Ok(identity) => { identity },
+                            Err(err) => return HttpResponse::Unauthorized().json(json!({"error": err }))
+                        };
+                        let result = handle_custom_handler(graph, model_def, handler, &parsed_body, identity).await;
+                        log_request(start, handler.name(), model_def.name(), result.status().as_u16());
+                        return result;
+                    }
+                    None => {
+                        log_unhandled(start, r.method().as_str(), &path, 404);
+                        return Error::destination_not_found().into();
+                    }
+                }
+            };
             if !model_def.has_action(action) {
                 log_unhandled(start, r.method().as_str(), &path, 400);
                 return Error::destination_not_found().into();
@@ -666,147 +1074,21 @@ fn make_app_inner(graph: &'static Graph, conf: &'static ServerConf) -> App<impl
             if r.method() == Method::OPTIONS {
                 return HttpResponse::Ok().json(json!({}));
             }
-            // read body
-            let mut body = web::BytesMut::new();
-            while let Some(chunk) = payload.next().await {
-                let chunk = chunk.unwrap();
-                // limit max size of in-memory payload
-                if (body.len() + chunk.len()) > 262_144usize {
-                    return HttpResponse::InternalServerError()
-                        .json(json!({"error": Error::internal_server_error("Memory overflow.".to_string())}));
-                }
-                body.extend_from_slice(&chunk);
-            }
-            let parsed_body: Result<JsonValue, serde_json::Error> = serde_json::from_slice(&body);
-            let parsed_body = match parsed_body {
-                Ok(b) => b,
-                Err(_) => {
-                    log_unhandled(start, r.method().as_str(), &path, 400);
-                    return HttpResponse::BadRequest().json(json!({"error": Error::incorrect_json_format()}));
+            let parsed_body = match read_json_body(&mut payload, conf.body_limit).await {
+                Ok(body) => body,
+                Err(response) => {
+                    log_unhandled(start, r.method().as_str(), &path, response.status().as_u16());
+                    return response;
                 }
             };
-
-            if !parsed_body.is_object() {
-                log_unhandled(start, r.method().as_str(), &path, 400);
-                return HttpResponse::BadRequest().json(json!({"error": Error::unexpected_input_root_type("object")}));
-            }
             let identity = match get_identity(&r, &graph, conf).await {
                 Ok(identity) => { identity },
                 Err(err) => return HttpResponse::Unauthorized().json(json!({"error": err }))
             };
 
-            let parsed_body = match Decoder::decode_action_arg(model_def, graph, action, &parsed_body) {
-                Ok(body) => body,
-                Err(err) => return err.into()
-            };
-            let (transformed_body, transformed_action) = if model_def.has_action_transformers() || parsed_body.as_hashmap().unwrap().get("include").is_some() {
-                if ((action.to_u32() == CREATE_MANY_HANDLER) || (action.to_u32() == CREATE_HANDLER)) && (parsed_body.get("create").unwrap().is_vec()) {
-                    // create with many items
-                    let entries = parsed_body.get("create").unwrap().as_vec().unwrap();
-                    let mut transformed_entries: Vec<Value> = vec![];
-                    let mut new_action = action;
-                    for (_index, entry) in entries.iter().enumerate() {
-                        let ctx = Ctx::initial_state_with_value(teon!({"create": entry})).with_action(action);
-                        match model_def.transformed_action(ctx).await {
-                            Ok(result) => {
-                                transformed_entries.push(result.0.get("create").unwrap().clone());
-                                new_action = result.1;
-                            },
-                            Err(err) => return err.into(),
-                        }
-                    }
-                    let mut new_val = parsed_body.clone();
-                    new_val.as_hashmap_mut().unwrap().insert("create".to_owned(), Value::Vec(transformed_entries));
-                    (new_val, new_action)
-                } else {
-                    let ctx = Ctx::initial_state_with_value(parsed_body).with_action(action);
-                    match model_def.transformed_action(ctx).await {
-                        Ok(result) => result,
-                        Err(err) => return err.into(),
-                    }
-                }
-            } else {
-                (parsed_body, action)
-            };
-            let source = ActionSource::Identity(identity);
-            match transformed_action.to_u32() {
-                FIND_UNIQUE_HANDLER => {
-                    let result = handle_find_unique(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    return result;
-                }
-                FIND_FIRST_HANDLER => {
-                    let result = handle_find_first(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                FIND_MANY_HANDLER => {
-                    let result = handle_find_many(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                CREATE_HANDLER => {
-                    let result = handle_create(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                UPDATE_HANDLER => {
-                    let result = handle_update(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                UPSERT_HANDLER => {
-                    let result = handle_upsert(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                DELETE_HANDLER => {
-                    let result = handle_delete(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                CREATE_MANY_HANDLER => {
-                    let result = handle_create_many(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                UPDATE_MANY_HANDLER => {
-                    let result = handle_update_many(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                DELETE_MANY_HANDLER => {
-                    let result = handle_delete_many(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                COUNT_HANDLER => {
-                    let result = handle_count(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                AGGREGATE_HANDLER => {
-                    let result = handle_aggregate(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                GROUP_BY_HANDLER => {
-                    let result = handle_group_by(&graph, &transformed_body, model_def, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                SIGN_IN_HANDLER => {
-                    let result = handle_sign_in(&graph, &transformed_body, model_def, conf).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                IDENTITY_HANDLER => {
-                    let result = handle_identity(&graph, &transformed_body, model_def, conf, source.clone()).await;
-                    log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
-                    result
-                }
-                _ => unreachable!()
-            }
+            let result = handle_action(graph, conf, model_def, action, &parsed_body, identity).await;
+            log_request(start, action.as_handler_str(), model_def.name(), result.status().as_u16());
+            result
         }));
     app
 }
@@ -835,12 +1117,16 @@ pub(crate) async fn serve(
     no_migration: bool,
 ) -> Result<(), std::io::Error> {
     if !no_migration {
-        migrate(graph.to_mut(), false).await;
+        if let Err(err) = migrate(graph.to_mut(), false).await {
+            eprintln!("{}", err.message());
+            std::process::exit(1);
+        }
     }
     let bind = conf.bind.clone();
     let port = bind.1;
+    let compression = conf.compression;
     let server = HttpServer::new(move || {
-        make_app(graph.clone(), conf.clone())
+        make_app(graph.clone(), conf.clone()).wrap(Condition::new(compression, Compress::default()))
     })
         .bind(bind)
         .unwrap()
@@ -848,3 +1134,310 @@ pub(crate) async fn serve(
     let result = future::join(server, server_start_message(port, environment_version, entrance)).await;
     result.0
 }
+
+#[cfg(test)]
+mod tests {
+    use serial_test::serial;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use actix_http::header::{HeaderMap, ACCEPT_ENCODING, ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, CONTENT_ENCODING, ORIGIN, VARY};
+    use actix_web::test::{TestRequest, call_service, init_service, read_body};
+    use crate::core::app::testing::{sqlite_app, sqlite_app_with};
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model Item {
+  @id @default($cuid)
+  id: String
+  @unique
+  name: String
+}
+"#;
+
+    async fn names(graph: &Graph) -> Vec<String> {
+        let items: Vec<Object> = graph.find_many("Item", &teon!({})).await.unwrap();
+        let mut names: Vec<String> = items.iter().map(|item| item.get_value("name").unwrap().as_str().unwrap().to_owned()).collect();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn create_many_creates_all() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        let model = graph.model("Item").unwrap();
+        let body = json!({"create": [{"name": "a"}, {"name": "b"}]});
+        let response = handle_action(graph, &app.server_conf, model, Action::from_u32(CREATE_MANY_HANDLER), &body, None).await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(names(graph).await, vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn create_many_creates_nothing_if_one_fails() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        let model = graph.model("Item").unwrap();
+        let body = json!({"create": [{"name": "a"}, {"name": "b"}, {"name": "a"}]});
+        let response = handle_action(graph, &app.server_conf, model, Action::from_u32(CREATE_MANY_HANDLER), &body, None).await;
+        assert_eq!(response.status(), 400);
+        assert!(names(graph).await.is_empty());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn update_many_updates_nothing_if_one_fails() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        let model = graph.model("Item").unwrap();
+        for name in ["a", "b"] {
+            graph.create_object("Item", teon!({"name": name})).await.unwrap().save().await.unwrap();
+        }
+        let body = json!({"where": {}, "update": {"name": "c"}});
+        let response = handle_action(graph, &app.server_conf, model, Action::from_u32(UPDATE_MANY_HANDLER), &body, None).await;
+        assert_eq!(response.status(), 400);
+        assert_eq!(names(graph).await, vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn custom_handler_is_rolled_back_if_it_fails() {
+        let schema = format!("{SCHEMA}\nmodel Tag {{\n  @@handler(.add, input: {{\"name\": \"String\"}})\n  @id @default($cuid)\n  id: String\n}}\n");
+        sqlite_app_with(&schema, |builder| {
+            builder.handler("Tag", "add", |ctx| async move {
+                ctx.create_object("Item", ctx.input()).await?.save().await?;
+                Err(Error::invalid_operation("failed after saving"))
+            });
+        }).await;
+        let graph = Graph::current();
+        let model = graph.model("Tag").unwrap();
+        let handler = model.handler("add").unwrap();
+        let response = handle_custom_handler(graph, model, handler, &json!({"name": "a"}), None).await;
+        assert_ne!(response.status(), 200);
+        assert!(names(graph).await.is_empty());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn custom_handler_requires_model_permissions() {
+        static CALLED: AtomicBool = AtomicBool::new(false);
+        let schema = format!("{SCHEMA}\nmodel Tag {{\n  @@handler(.touch)\n  @@canMutate($invalid)\n  @id @default($cuid)\n  id: String\n}}\n");
+        sqlite_app_with(&schema, |builder| {
+            builder.handler("Tag", "touch", |_| async move {
+                CALLED.store(true, Ordering::SeqCst);
+                Ok(Value::Null)
+            });
+        }).await;
+        let graph = Graph::current();
+        let model = graph.model("Tag").unwrap();
+        let response = handle_custom_handler(graph, model, model.handler("touch").unwrap(), &json!({}), None).await;
+        assert_eq!(response.status(), 401);
+        assert!(!CALLED.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Type 'Missing' of handler 'find' of model 'Tag' is not defined.")]
+    async fn undefined_handler_type_is_rejected() {
+        let schema = format!("{SCHEMA}\nmodel Tag {{\n  @@handler(.find, output: \"Missing\")\n  @id @default($cuid)\n  id: String\n}}\n");
+        sqlite_app(&schema).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn health_reports_status_and_pool_without_details() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = Graph::current();
+        for _ in 0..2 {
+            let response = handle_health(graph, &app.server_conf).await;
+            assert_eq!(response.status(), 200);
+            let body = actix_web::body::to_bytes(response.into_body()).await.unwrap();
+            let body: JsonValue = serde_json::from_slice(&body).unwrap();
+            let connector = body["connectors"].as_object().unwrap().values().next().unwrap();
+            assert_eq!(connector["reachable"], json!(true));
+            assert!(connector.get("latencyMs").is_none() && connector.get("error").is_none());
+            assert_eq!(connector["pool"]["inUse"], json!(0));
+        }
+        assert!(app.server_conf.health_checks.lock().await.is_some());
+    }
+
+    #[test]
+    fn token_without_jti_or_iat_is_rejected() {
+        let claims = Claims {
+            id: json!({"id": "a"}),
+            model: "Item".to_owned(),
+            exp: (Utc::now() + Duration::seconds(60)).timestamp() as usize,
+            iat: Utc::now().timestamp() as usize,
+            jti: Some(Uuid::new_v4().to_string()),
+            refresh: false,
+            claims: JsonMap::new(),
+        };
+        assert!(decode_token(&encode_token(claims.clone(), "secret"), "secret").is_ok());
+        let without_jti = Claims { jti: None, ..claims.clone() };
+        assert!(decode_token(&encode_token(without_jti, "secret"), "secret").is_err());
+        let without_iat = Claims { iat: 0, ..claims };
+        assert!(decode_token(&encode_token(without_iat, "secret"), "secret").is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn delete_many_deletes_matching() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        let model = graph.model("Item").unwrap();
+        for name in ["a", "b", "c"] {
+            graph.create_object("Item", teon!({"name": name})).await.unwrap().save().await.unwrap();
+        }
+        let body = json!({"where": {"name": {"in": ["a", "c"]}}});
+        let response = handle_action(graph, &app.server_conf, model, Action::from_u32(DELETE_MANY_HANDLER), &body, None).await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(names(graph).await, vec!["b"]);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn refresh_token_is_exchanged_only_once() {
+        let schema = format!("{SCHEMA}\nmodel User {{\n  @@identity\n  @id @default($cuid)\n  id: String\n}}\n");
+        let app = sqlite_app(&schema).await;
+        let graph = app.graph();
+        let mut conf = app.server_conf.clone();
+        conf.jwt_secret = Some("secret".to_owned());
+        let model = graph.model("User").unwrap();
+        let user = graph.create_object("User", teon!({})).await.unwrap();
+        user.save().await.unwrap();
+        let (_, refresh_token) = issue_tokens(&user, &conf).await.unwrap();
+        let input = teon!({"refreshToken": refresh_token});
+        let response = handle_refresh(graph, &input, model, &conf).await;
+        assert_eq!(response.status(), 200);
+        let response = handle_refresh(graph, &input, model, &conf).await;
+        assert_eq!(response.status(), 401);
+        // of concurrent requests with the same token, only one gets new tokens
+        let (_, refresh_token) = issue_tokens(&user, &conf).await.unwrap();
+        let input = teon!({"refreshToken": refresh_token});
+        let (a, b) = future::join(handle_refresh(graph, &input, model, &conf), handle_refresh(graph, &input, model, &conf)).await;
+        let mut statuses = vec![a.status().as_u16(), b.status().as_u16()];
+        statuses.sort();
+        assert_eq!(statuses, vec![200, 401]);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn json_updates_merge_or_replace_values() {
+        let schema = format!("{SCHEMA}\nmodel Tenant {{\n  @id @default($cuid)\n  id: String\n  @unique\n  name: String\n  settings: Json?\n}}\n");
+        let app = sqlite_app(&schema).await;
+        let graph = app.graph();
+        let conf = &app.server_conf;
+        let model = graph.model("Tenant").unwrap();
+        async fn settings(response: HttpResponse) -> JsonValue {
+            let status = response.status();
+            let body: JsonValue = serde_json::from_slice(&actix_web::body::to_bytes(response.into_body()).await.unwrap()).unwrap();
+            assert_eq!(status, 200, "{body}");
+            body["data"]["settings"].clone()
+        }
+        let create = json!({"create": {"name": "a", "settings": {"theme": "dark", "tags": ["x"]}}});
+        let response = handle_action(graph, conf, model, Action::from_u32(CREATE_HANDLER), &create, None).await;
+        assert_eq!(settings(response).await, json!({"theme": "dark", "tags": ["x"]}));
+        let update = json!({"where": {"name": "a"}, "update": {"settings": {"merge": {"theme": "light", "beta": true}}}});
+        let response = handle_action(graph, conf, model, Action::from_u32(UPDATE_HANDLER), &update, None).await;
+        assert_eq!(settings(response).await, json!({"theme": "light", "tags": ["x"], "beta": true}));
+        let update = json!({"where": {"name": "a"}, "update": {"settings": {"set": {"theme": "dark"}}}});
+        let response = handle_action(graph, conf, model, Action::from_u32(UPDATE_HANDLER), &update, None).await;
+        assert_eq!(settings(response).await, json!({"theme": "dark"}));
+        // upsert merges into existing records and creates missing ones with the whole value
+        let upsert = json!({"where": {"name": "a"}, "create": {"name": "a"}, "update": {"settings": {"merge": {"beta": false}}}});
+        let response = handle_action(graph, conf, model, Action::from_u32(UPSERT_HANDLER), &upsert, None).await;
+        assert_eq!(settings(response).await, json!({"theme": "dark", "beta": false}));
+        let upsert = json!({"where": {"name": "b"}, "create": {"name": "b", "settings": {"theme": "light"}}, "update": {"settings": {"merge": {"beta": false}}}});
+        let response = handle_action(graph, conf, model, Action::from_u32(UPSERT_HANDLER), &upsert, None).await;
+        assert_eq!(settings(response).await, json!({"theme": "light"}));
+        // merging into null starts from an empty object
+        let create = json!({"create": {"name": "c"}});
+        handle_action(graph, conf, model, Action::from_u32(CREATE_HANDLER), &create, None).await;
+        let update = json!({"where": {"name": "c"}, "update": {"settings": {"merge": {"beta": true}}}});
+        let response = handle_action(graph, conf, model, Action::from_u32(UPDATE_HANDLER), &update, None).await;
+        assert_eq!(settings(response).await, json!({"beta": true}));
+    }
+
+    /// Send `request` through the middlewares and routes of the server with `conf`.
+    async fn send(conf: ServerConf, request: TestRequest) -> (u16, HeaderMap, web::Bytes) {
+        let conf: &'static ServerConf = Box::leak(Box::new(conf));
+        let app = init_service(make_app_inner(Graph::current(), conf).wrap(Condition::new(conf.compression, Compress::default()))).await;
+        let response = call_service(&app, request.to_request()).await;
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        (status, headers, read_body(response).await)
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn cors_headers_are_only_sent_to_listed_origins() {
+        let app = sqlite_app(SCHEMA).await;
+        let mut conf = app.server_conf.clone();
+        conf.cors.origins = vec!["https://a.com".to_owned()];
+        conf.cors.credentials = true;
+        let preflight = |origin: &str| TestRequest::default().method(Method::OPTIONS).uri("/items/action/findMany").insert_header((ORIGIN, origin));
+        let (status, headers, _) = send(conf.clone(), preflight("https://a.com")).await;
+        assert_eq!(status, 200);
+        assert_eq!(headers.get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(), "https://a.com");
+        assert_eq!(headers.get(ACCESS_CONTROL_ALLOW_CREDENTIALS).unwrap(), "true");
+        assert_eq!(headers.get(ACCESS_CONTROL_ALLOW_METHODS).unwrap(), "GET, POST, OPTIONS");
+        assert_eq!(headers.get(VARY).unwrap(), "Origin");
+        let (status, headers, _) = send(conf, preflight("https://b.com")).await;
+        assert_eq!(status, 200);
+        assert!(headers.get(ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+        assert!(headers.get(ACCESS_CONTROL_ALLOW_CREDENTIALS).is_none());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn bodies_over_the_limit_are_rejected() {
+        let app = sqlite_app(SCHEMA).await;
+        let mut conf = app.server_conf.clone();
+        conf.body_limit = 32;
+        let request = TestRequest::post().uri("/items/action/create").set_json(json!({"create": {"name": "a".repeat(32)}}));
+        let (status, _, _) = send(conf.clone(), request).await;
+        assert_eq!(status, 413);
+        assert!(names(app.graph()).await.is_empty());
+        let request = TestRequest::post().uri("/items/action/create").set_json(json!({"create": {"name": "a"}}));
+        let (status, _, _) = send(conf, request).await;
+        assert_eq!(status, 200);
+        assert_eq!(names(app.graph()).await, vec!["a"]);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn slow_requests_time_out() {
+        let schema = format!("{SCHEMA}\nmodel Tag {{\n  @@handler(.wait)\n  @id @default($cuid)\n  id: String\n}}\n");
+        let app = sqlite_app_with(&schema, |builder| {
+            builder.handler("Tag", "wait", |_| async move {
+                tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+                Ok(Value::Null)
+            });
+        }).await;
+        let mut conf = app.server_conf.clone();
+        conf.request_timeout = Some(1);
+        let started = Instant::now();
+        let (status, _, body) = send(conf, TestRequest::post().uri("/tags/action/wait").set_json(json!({}))).await;
+        assert_eq!(status, 408);
+        assert!(started.elapsed() < std::time::Duration::from_secs(3));
+        let body: JsonValue = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["type"], "RequestTimeout");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn responses_are_compressed_if_enabled() {
+        let app = sqlite_app(SCHEMA).await;
+        let mut conf = app.server_conf.clone();
+        let request = || TestRequest::post().uri("/items/action/findMany").insert_header((ACCEPT_ENCODING, "gzip")).set_json(json!({}));
+        conf.compression = false;
+        let (status, headers, _) = send(conf.clone(), request()).await;
+        assert_eq!(status, 200);
+        assert!(headers.get(CONTENT_ENCODING).is_none());
+        conf.compression = true;
+        let (status, headers, body) = send(conf, request()).await;
+        assert_eq!(status, 200);
+        assert_eq!(headers.get(CONTENT_ENCODING).unwrap(), "gzip");
+        assert_eq!(&body[..2], &[0x1f, 0x8b]);
+    }
+}
+
@@ -0,0 +1,49 @@
+use std::fs;
+use uuid::Uuid;
+use crate::core::app::builder::AppBuilder;
+use crate::core::app::command::{CLI, CLICommand, ServeCommand};
+use crate::core::app::conf::ServerConf;
+use crate::core::app::entrance::Entrance;
+use crate::core::app::migrate::migrate;
+use crate::core::graph::Graph;
+use crate::prelude::App;
+
+/// Build an app from `models` on a new SQLite database, and migrate the database. The pool has
+/// one connection, so a request which checks out a second one times out instead of passing.
+pub(crate) async fn sqlite_app(models: &str) -> App {
+    sqlite_app_with(models, |_| {}).await
+}
+
+/// Like `sqlite_app`, `f` registers callbacks, handlers and connectors on the builder.
+pub(crate) async fn sqlite_app_with<F>(models: &str, f: F) -> App where F: FnOnce(&mut AppBuilder) {
+    let dir = std::env::temp_dir().join(format!("teo-test-{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    let schema = dir.join("schema.teo");
+    fs::write(&schema, format!(r#"
+connector {{
+  provider .sqlite
+  url "sqlite:{}"
+  poolSize 1
+  acquireTimeout 2000
+}}
+
+server {{
+  bind ("0.0.0.0", 5100)
+}}
+{}"#, dir.join("test.sqlite").display(), models)).unwrap();
+    let args = CLI {
+        command: CLICommand::Serve(ServeCommand { no_migration: true }),
+        schema: Some(schema.to_str().unwrap().to_owned()),
+    };
+    let mut builder = AppBuilder::new_with_args(AppBuilder::rust_environment_version(), Entrance::APP, args);
+    f(&mut builder);
+    let mut app = builder.build().await;
+    migrate(&mut app.graph, false).await.unwrap();
+    Graph::set_current(Box::leak(Box::new(app.graph.clone())));
+    app
+}
+
+/// The server configuration of `app`, for tests outside of `core::app`.
+pub(crate) fn server_conf(app: &App) -> &ServerConf {
+    &app.server_conf
+}
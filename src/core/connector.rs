@@ -1,51 +1,153 @@
+use std::any::Any;
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
 use async_trait::async_trait;
-use crate::core::action::Action;
-use crate::core::action::source::ActionSource;
-use crate::core::database::r#type::DatabaseType;
-use crate::core::field::r#type::FieldType;
+use futures_util::future::BoxFuture;
+pub use crate::core::action::Action;
+pub use crate::core::action::source::ActionSource;
+pub use crate::core::database::r#type::DatabaseType;
+pub use crate::core::field::r#type::FieldType;
 use crate::core::graph::Graph;
 use crate::core::model::Model;
 use crate::core::object::Object;
 use crate::core::result::Result;
 use crate::prelude::Value;
 
+/// A save session groups the database writes of one entry-level operation.
+///
+/// Sessions are created lazily by connectors and are either committed or aborted exactly once
+/// by their creator. Connectors that don't support transactions may implement both as no-ops.
 #[async_trait]
-pub(crate) trait SaveSession: Debug + Send + Sync { }
+pub trait SaveSession: Debug + Send + Sync {
 
+    /// Make every write performed inside this session permanent.
+    async fn commit(&self) -> Result<()>;
+
+    /// Discard every write performed inside this session.
+    async fn abort(&self) -> Result<()>;
+
+    /// Connectors downcast the sessions passed back to them with this.
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// The statements which bring the database in line with the models, and the statements which
+/// revert them in the order they should run.
+#[derive(Debug, Default)]
+pub struct MigrationPlan {
+    pub up: Vec<String>,
+    pub down: Vec<String>,
+}
+
+/// Usage of a connector's connection pool.
+#[derive(Debug, Clone, Default)]
+pub struct PoolStats {
+    /// The maximum number of open connections.
+    pub size: u32,
+    /// Connections which are checked out right now.
+    pub in_use: u32,
+    /// Checkouts which have timed out waiting for a free connection since start.
+    pub timeouts: u64,
+}
+
+/// Whether a connector's database is reachable, reported by the `/_health` endpoint.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectorHealth {
+    pub reachable: bool,
+    /// How long the check took.
+    pub latency: Option<Duration>,
+    /// Why the database is not reachable.
+    pub error: Option<String>,
+    pub pool: Option<PoolStats>,
+}
+
+/// A connector translates model operations into queries of one database.
+///
+/// The built-in SQL and MongoDB connectors implement this trait. Third-party databases implement
+/// it too, and are registered with `AppBuilder::register_connector` under the name used by a
+/// schema's `connector { provider: .custom("name") }` block.
+///
+/// Connectors receive finders which are already validated and normalized against the model, so
+/// only database specific work is left to them. Objects are returned by creating them with
+/// `Graph::new_object` and filling them with `Object::set_from_database_result_value`. The
+/// `connectors::suite` module checks that a connector implements these semantics as the built-in
+/// ones do.
 #[async_trait]
-pub(crate) trait Connector: Send + Sync {
+pub trait Connector: Send + Sync {
 
     // Query database types
 
+    /// The database type used for fields without an explicit `@db` decorator.
     fn default_database_type(&self, field_type: &FieldType) -> DatabaseType;
 
     // Migration
 
-    async fn migrate(&mut self, models: &Vec<Model>, reset_database: bool) -> Result<()>;
+    /// Bring the database in line with `models`. Drop everything first if `reset_database` is
+    /// true.
+    async fn migrate(&self, models: &[Model], reset_database: bool) -> Result<()>;
+
+    /// Diff the database against the models without changing it.
+    async fn migration_plan(&self, models: &[Model]) -> Result<MigrationPlan>;
+
+    /// Versions of the applied migration files in ascending order.
+    async fn applied_migrations(&self) -> Result<Vec<String>>;
+
+    /// Run the statements of a migration file and record its version as applied.
+    async fn apply_migration(&self, version: &str, name: &str, up: &str) -> Result<()>;
+
+    /// Run the reverting statements of a migration file and remove its version from the history.
+    async fn revert_migration(&self, version: &str, down: &str) -> Result<()>;
 
     // Raw query
 
+    /// Run a database specific query and return the database's response.
     async fn query_raw(&self, query: &Value) -> Result<Value>;
 
     // Object manipulation
 
+    /// Insert the object if it's new, otherwise update its modified keys. Values generated by
+    /// the database, such as auto increment ids, are written back to the object.
     async fn save_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()>;
 
+    /// Delete the record of the object.
     async fn delete_object(&self, object: &Object, session: Arc<dyn SaveSession>) -> Result<()>;
 
+    /// Find the record matching the unique `where` of `finder`. Returns a not found error if
+    /// there's none.
     async fn find_unique(&self, graph: &Graph, model: &Model, finder: &Value, mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Object>;
 
+    /// Find the records matching `finder`, honoring `where`, `orderBy`, `skip`, `take`, `cursor`,
+    /// `distinct`, `select` and `include`.
     async fn find_many(&self, graph: &Graph, model: &Model, finder: &Value, mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Vec<Object>>;
 
+    /// Count the records matching the `where` of `finder`.
     async fn count(&self, graph: &Graph, model: &Model, finder: &Value) -> Result<usize>;
 
+    /// Compute `_count`, `_sum`, `_avg`, `_min` and `_max` of the records matching `finder`.
     async fn aggregate(&self, graph: &Graph, model: &Model, finder: &Value) -> Result<Value>;
 
+    /// Compute the aggregates of `finder` for each group of the `by` keys.
     async fn group_by(&self, graph: &Graph, model: &Model, finder: &Value) -> Result<Value>;
 
     // Save session
 
+    /// Create a session which groups the writes of one entry-level operation.
     fn new_save_session(&self) -> Arc<dyn SaveSession>;
+
+    // Health
+
+    /// Check that the database is reachable. Connectors which can't check report themselves
+    /// reachable.
+    async fn health(&self) -> ConnectorHealth {
+        ConnectorHealth { reachable: true, ..Default::default() }
+    }
+
+    /// Usage of the connection pool, read without checking out a connection.
+    async fn pool_stats(&self) -> Option<PoolStats> {
+        None
+    }
 }
+
+/// Creates a connector from the url of a schema's connector block. Registered with
+/// `AppBuilder::register_connector`.
+pub type ConnectorFactory = Arc<dyn Fn(String) -> BoxFuture<'static, Arc<dyn Connector>> + Send + Sync>;
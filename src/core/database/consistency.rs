@@ -0,0 +1,22 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+tokio::task_local! {
+    static WRITTEN: AtomicBool;
+}
+
+/// Run `future` as one request. Connectors with `readYourWrites` read from the primary
+/// database once the request has written anything.
+pub(crate) async fn track_writes<F: Future>(future: F) -> F::Output {
+    WRITTEN.scope(AtomicBool::new(false), future).await
+}
+
+/// Record that the current request has written to a database.
+pub(crate) fn mark_written() {
+    let _ = WRITTEN.try_with(|written| written.store(true, Ordering::SeqCst));
+}
+
+/// Whether the current request has written to a database.
+pub(crate) fn has_written() -> bool {
+    WRITTEN.try_with(|written| written.load(Ordering::SeqCst)).unwrap_or(false)
+}
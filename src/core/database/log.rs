@@ -0,0 +1,169 @@
+use std::future::Future;
+use std::time::Duration;
+use chrono::Utc;
+use once_cell::sync::OnceCell;
+use serde_json::{json, Map as JsonMap, Value as JsonValue};
+
+/// Query logging options, read from the connector block.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct QueryLogConf {
+    /// Log every database operation. Set by `debug`.
+    pub(crate) debug: bool,
+    /// Warn about database operations which take longer than this. Set by `slowQueryThreshold`.
+    pub(crate) slow_query_threshold: Option<Duration>,
+}
+
+static QUERY_LOG_CONF: OnceCell<QueryLogConf> = OnceCell::new();
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+pub(crate) fn setup(conf: QueryLogConf) {
+    let _ = QUERY_LOG_CONF.set(conf);
+}
+
+pub(crate) fn conf() -> QueryLogConf {
+    QUERY_LOG_CONF.get().cloned().unwrap_or_default()
+}
+
+/// Whether database operations are timed at all.
+pub(crate) fn enabled() -> bool {
+    let conf = conf();
+    conf.debug || conf.slow_query_threshold.is_some()
+}
+
+/// Run `future` with `id` as the request id of the database operations it performs.
+pub(crate) async fn with_request_id<F: Future>(id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(id, future).await
+}
+
+/// The id of the HTTP request being handled, if any.
+pub(crate) fn request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// A database operation which has been performed.
+pub(crate) struct QueryLog<'a> {
+    pub(crate) database: &'a str,
+    pub(crate) statement: JsonValue,
+    pub(crate) params: Option<JsonValue>,
+    pub(crate) duration: Duration,
+    pub(crate) rows: Option<u64>,
+    pub(crate) error: Option<String>,
+}
+
+impl<'a> QueryLog<'a> {
+
+    /// Write this as a JSON line. Slow operations are written to stderr as warnings, other
+    /// operations are written to stdout if `debug` is on.
+    pub(crate) fn emit(self) {
+        match self.into_line(conf()) {
+            Some((true, line)) => eprintln!("{line}"),
+            Some((false, line)) => println!("{line}"),
+            None => (),
+        }
+    }
+
+    /// The JSON line of this operation and whether it's slow, or `None` if it isn't logged.
+    fn into_line(self, conf: QueryLogConf) -> Option<(bool, JsonValue)> {
+        let slow = match conf.slow_query_threshold {
+            Some(threshold) => self.duration >= threshold,
+            None => false,
+        };
+        if !slow && !conf.debug {
+            return None;
+        }
+        let mut line = JsonMap::new();
+        line.insert("time".to_owned(), json!(Utc::now().to_rfc3339()));
+        line.insert("level".to_owned(), json!(if slow { "warn" } else { "debug" }));
+        line.insert("database".to_owned(), json!(self.database));
+        if let Some(request_id) = request_id() {
+            line.insert("requestId".to_owned(), json!(request_id));
+        }
+        line.insert("statement".to_owned(), self.statement);
+        if let Some(params) = self.params {
+            line.insert("params".to_owned(), params);
+        }
+        line.insert("durationMs".to_owned(), json!(self.duration.as_secs_f64() * 1000.0));
+        if let Some(rows) = self.rows {
+            line.insert("rows".to_owned(), json!(rows));
+        }
+        if let Some(error) = self.error {
+            line.insert("error".to_owned(), json!(error));
+        }
+        if slow {
+            line.insert("slow".to_owned(), json!(true));
+        }
+        Some((slow, JsonValue::Object(line)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query_log(duration: Duration) -> QueryLog<'static> {
+        QueryLog {
+            database: "sqlite",
+            statement: json!("SELECT * FROM `User` WHERE `id` = ?"),
+            params: Some(json!([1])),
+            duration,
+            rows: Some(1),
+            error: None,
+        }
+    }
+
+    fn without_time(line: JsonValue) -> JsonValue {
+        let mut line = line.as_object().unwrap().clone();
+        assert!(line.remove("time").unwrap().is_string());
+        JsonValue::Object(line)
+    }
+
+    #[test]
+    fn nothing_is_logged_without_debug_or_a_slow_operation() {
+        let conf = QueryLogConf { debug: false, slow_query_threshold: Some(Duration::from_millis(100)) };
+        assert!(query_log(Duration::from_millis(5)).into_line(conf).is_none());
+        assert!(query_log(Duration::from_millis(5)).into_line(QueryLogConf::default()).is_none());
+    }
+
+    #[test]
+    fn debug_logs_every_operation() {
+        let conf = QueryLogConf { debug: true, slow_query_threshold: None };
+        let (slow, line) = query_log(Duration::from_millis(5)).into_line(conf).unwrap();
+        assert!(!slow);
+        assert_eq!(without_time(line), json!({
+            "level": "debug",
+            "database": "sqlite",
+            "statement": "SELECT * FROM `User` WHERE `id` = ?",
+            "params": [1],
+            "durationMs": 5.0,
+            "rows": 1,
+        }));
+    }
+
+    #[test]
+    fn slow_operations_are_warnings() {
+        let conf = QueryLogConf { debug: false, slow_query_threshold: Some(Duration::from_millis(100)) };
+        let mut log = query_log(Duration::from_millis(250));
+        log.rows = None;
+        log.error = Some("database is locked".to_owned());
+        let (slow, line) = log.into_line(conf).unwrap();
+        assert!(slow);
+        let line = without_time(line);
+        assert_eq!(line["level"], "warn");
+        assert_eq!(line["slow"], true);
+        assert_eq!(line["error"], "database is locked");
+        assert_eq!(line.get("rows"), None);
+    }
+
+    #[tokio::test]
+    async fn operations_of_a_request_carry_its_id() {
+        let conf = QueryLogConf { debug: true, slow_query_threshold: None };
+        assert_eq!(request_id(), None);
+        let line = with_request_id("3f2a".to_owned(), async {
+            query_log(Duration::from_millis(5)).into_line(conf).unwrap().1
+        }).await;
+        assert_eq!(line["requestId"], "3f2a");
+    }
+}
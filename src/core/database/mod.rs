@@ -1,2 +1,5 @@
 pub mod r#type;
 pub mod name;
+pub(crate) mod log;
+pub(crate) mod consistency;
+pub(crate) mod pool;
@@ -1,8 +1,13 @@
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum DatabaseName {
     MySQL,
     PostgreSQL,
     #[cfg(feature = "data-source-sqlite")]
     SQLite,
     MongoDB,
+    #[cfg(feature = "data-source-mssql")]
+    MSSQL,
+    /// A third-party database served by the connector registered under this name with
+    /// `AppBuilder::register_connector`.
+    Custom(String),
 }
@@ -0,0 +1,17 @@
+use std::time::Duration;
+
+/// Connection pool options, read from the connector block. Unset options keep the defaults of
+/// the database driver.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct PoolConf {
+    /// The maximum number of open connections. Set by `poolSize`.
+    pub(crate) size: Option<u32>,
+    /// How long opening a connection may take. Set by `connectTimeout`.
+    pub(crate) connect_timeout: Option<Duration>,
+    /// How long to wait for a free connection of the pool. Set by `acquireTimeout`.
+    pub(crate) acquire_timeout: Option<Duration>,
+    /// How long an idle connection is kept before it's closed. Set by `idleLifetime`.
+    pub(crate) idle_lifetime: Option<Duration>,
+    /// How long a single statement may run on the database server. Set by `statementTimeout`.
+    pub(crate) statement_timeout: Option<Duration>,
+}
@@ -171,9 +171,7 @@ pub enum DatabaseType {
 
     /// Text
     /// Represents a text.
-    ///
     /// Arguments:
-    ///
     ///     n: charset name
     ///     c: collate
     /// Note: In PostgreSQL, arguments are ingored.
@@ -181,6 +179,13 @@ pub enum DatabaseType {
     /// Availability: MySQL, PostgreSQL
     Text { m: Option<u16>, n: Option<String>, c: Option<String> },
 
+    /// NVarChar
+    /// Represents a variable-length unicode string.
+    /// Arguments:
+    ///     m: column length, from 1 to 4,000, if omitted, this is `NVARCHAR(MAX)`
+    /// Availability: MSSQL
+    NVarChar { m: Option<u16> },
+
     /// String
     /// Represents a string.
     // Availability: MongoDB
@@ -209,4 +214,11 @@ pub enum DatabaseType {
     ByteA,
 
     Vec(Box<DatabaseType>),
+
+    /// Json
+    /// Represents a JSON document.
+    /// Note: This is `JSON` in MySQL and `JSONB` in PostgreSQL. SQLite and MSSQL store the text.
+    /// In MongoDB, this is an embedded document.
+    /// Availability: MySQL, PostgreSQL, MongoDB
+    Json,
 }
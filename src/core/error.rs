@@ -25,6 +25,10 @@ pub(crate) enum ErrorType {
     // request destination
     DestinationNotFound,
 
+    // request limits
+    PayloadTooLarge,
+    RequestTimeout,
+
     // request input
     IncorrectJSONFormat,
     UnexpectedInputRootType,
@@ -40,9 +44,11 @@ pub(crate) enum ErrorType {
     // request permission
     PermissionError,
     DeletionDenied,
+    UpdateDenied,
 
     // response destination
     ObjectNotFound,
+    Conflict,
 
     // response output
     UnexpectedOutputException,
@@ -57,6 +63,9 @@ pub(crate) enum ErrorType {
 
     // database
     RecordDecodingError,
+    DatabaseUnavailable,
+    DatabasePoolExhausted,
+    DatabaseTimeout,
 }
 
 impl ErrorType {
@@ -70,8 +79,11 @@ impl ErrorType {
             ErrorType::UnknownDatabaseFindUniqueError => { 500 }
             ErrorType::UnknownDatabaseCountError => { 500 }
             ErrorType::DestinationNotFound => { 404 }
+            ErrorType::PayloadTooLarge => { 413 }
+            ErrorType::RequestTimeout => { 408 }
             ErrorType::InternalServerError => { 500 }
             ErrorType::ObjectNotFound => { 404 }
+            ErrorType::Conflict => { 409 }
             ErrorType::InvalidAuthToken => { 401 }
             ErrorType::CustomInternalServerError => { 500 }
             ErrorType::CustomValidationError => { 400 }
@@ -87,7 +99,11 @@ impl ErrorType {
             ErrorType::PermissionError => { 401 }
             ErrorType::UnexpectedOutputException => { 500 }
             ErrorType::DeletionDenied => { 400 }
+            ErrorType::UpdateDenied => { 400 }
             ErrorType::RecordDecodingError => { 500 }
+            ErrorType::DatabaseUnavailable => { 503 }
+            ErrorType::DatabasePoolExhausted => { 503 }
+            ErrorType::DatabaseTimeout => { 504 }
         }
     }
 }
@@ -167,6 +183,54 @@ impl Error {
         }
     }
 
+    pub(crate) fn payload_too_large(limit: usize) -> Self {
+        Error {
+            r#type: ErrorType::PayloadTooLarge,
+            message: format!("The request body exceeds the limit of {limit} bytes."),
+            errors: None
+        }
+    }
+
+    pub(crate) fn version_conflict(field: impl Into<String>) -> Self {
+        Error {
+            r#type: ErrorType::Conflict,
+            message: "The record has been updated by someone else.".to_string(),
+            errors: Some(hashmap!{field.into() => "Version is outdated.".to_string()})
+        }
+    }
+
+    pub(crate) fn database_unavailable(reason: impl Into<String>) -> Self {
+        Error {
+            r#type: ErrorType::DatabaseUnavailable,
+            message: "The database is unavailable.".to_string(),
+            errors: Some(hashmap!{"database".to_string() => reason.into()})
+        }
+    }
+
+    pub(crate) fn database_pool_exhausted() -> Self {
+        Error {
+            r#type: ErrorType::DatabasePoolExhausted,
+            message: "No database connection is available.".to_string(),
+            errors: None
+        }
+    }
+
+    pub(crate) fn database_timeout() -> Self {
+        Error {
+            r#type: ErrorType::DatabaseTimeout,
+            message: "The database operation timed out.".to_string(),
+            errors: None
+        }
+    }
+
+    pub(crate) fn request_timeout() -> Self {
+        Error {
+            r#type: ErrorType::RequestTimeout,
+            message: "The request timed out.".to_string(),
+            errors: None
+        }
+    }
+
     pub(crate) fn object_not_found() -> Self {
         Error {
             r#type: ErrorType::ObjectNotFound,
@@ -361,6 +425,14 @@ impl Error {
         }
     }
 
+    pub(crate) fn update_denied(relation_name: impl AsRef<str>) -> Self {
+        Error {
+            r#type: ErrorType::UpdateDenied,
+            message: format!("Update denied by `{}'.", relation_name.as_ref()),
+            errors: None
+        }
+    }
+
     pub(crate) fn validation_error<'a>(path: impl AsRef<KeyPath<'a>>, reason: impl Into<String>) -> Self {
         Error {
             r#type: ErrorType::ValidationError,
@@ -1,4 +1,4 @@
-pub(crate) mod r#type;
+pub mod r#type;
 pub(crate) mod optionality;
 pub(crate) mod read_rule;
 pub(crate) mod write_rule;
@@ -142,6 +142,7 @@ pub struct Field {
     pub(crate) foreign_key: bool,
     pub(crate) migration: Option<FieldMigration>,
     pub(crate) dropped: bool,
+    pub(crate) version: bool,
 }
 
 impl Debug for Field {
@@ -186,6 +187,7 @@ impl Field {
             foreign_key: false,
             migration: None,
             dropped: false,
+            version: false,
         }
     }
 
@@ -212,7 +214,7 @@ impl Field {
         self.optionality.is_required()
     }
 
-    pub(crate) fn column_name(&self) -> &str {
+    pub fn column_name(&self) -> &str {
         match &self.column_name {
             Some(column_name) => column_name.as_str(),
             None => &self.name
@@ -21,6 +21,7 @@ pub enum FieldType {
     Vec(Box<Field>),
     HashMap(Box<Field>),
     BTreeMap(Box<Field>),
+    Json,
     Object(String),
 }
 
@@ -136,6 +137,10 @@ impl FieldType {
         }
     }
 
+    pub fn is_json(&self) -> bool {
+        matches!(self, FieldType::Json)
+    }
+
     pub fn element_field(&self) -> Option<&Field> {
         match self {
             FieldType::Vec(inner) => Some(inner.as_ref()),
@@ -154,6 +159,8 @@ impl FieldType {
             &NUMBER_UPDATORS
         } else if self.is_vec() {
             &VEC_UPDATORS
+        } else if self.is_json() {
+            &JSON_UPDATORS
         } else {
             &DEFAULT_UPDATORS
         }
@@ -172,6 +179,7 @@ impl FieldType {
             FieldType::Vec(_) => &VEC_FILTERS,
             FieldType::HashMap(_) => &MAP_FILTERS,
             FieldType::BTreeMap(_) => &MAP_FILTERS,
+            FieldType::Json => &JSON_FILTERS,
             FieldType::Object(_) => panic!("Object filter is not implemented.")
         }
     }
@@ -189,6 +197,7 @@ impl FieldType {
             FieldType::Vec(_) => &VEC_FILTERS,
             FieldType::HashMap(_) => &MAP_FILTERS,
             FieldType::BTreeMap(_) => &MAP_FILTERS,
+            FieldType::Json => &JSON_FILTERS,
             FieldType::Object(_) => panic!("Object filter is not implemented.")
         }
     }
@@ -208,11 +217,14 @@ static NUMBER_UPDATORS: Lazy<HashSet<&str>> = Lazy::new(|| {
 static VEC_UPDATORS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset!{"set", "push"}
 });
+static JSON_UPDATORS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    hashset!{"set", "merge"}
+});
 static BOOL_FILTERS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset!{"equals", "not"}
 });
 static STRING_FILTERS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    hashset!{"equals", "not", "gt", "gte", "lt", "lte", "in", "notIn", "contains", "startsWith", "endsWith", "matches", "mode"}
+    hashset!{"equals", "not", "gt", "gte", "lt", "lte", "in", "notIn", "contains", "startsWith", "endsWith", "matches", "mode", "search"}
 });
 static DEFAULT_FILTERS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset!{"equals", "not", "gt", "gte", "lt", "lte", "in", "notIn"}
@@ -226,6 +238,9 @@ static VEC_FILTERS: Lazy<HashSet<&str>> = Lazy::new(|| {
 static MAP_FILTERS: Lazy<HashSet<&str>> = Lazy::new(|| {
     hashset! {"equals", "has", "hasEvery", "hasSome", "isEmpty", "length", "hasKey"}
 });
+static JSON_FILTERS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    hashset! {"path", "equals", "arrayContains", "stringContains"}
+});
 static STRING_FILTERS_WITH_AGGREGATE: Lazy<HashSet<&str>> = Lazy::new(|| {
     STRING_FILTERS.bitor(&hashset!{"_min", "_max", "_count"})
 });
@@ -6,6 +6,9 @@ use crate::core::graph::GraphInner;
 use crate::core::model::builder::ModelBuilder;
 use crate::core::model::Model;
 use crate::core::r#enum::Enum;
+use to_mut::ToMut;
+use crate::core::field::PreviousValueRule;
+use crate::core::relation::update_rule::UpdateRule;
 use crate::prelude::Graph;
 
 pub struct GraphBuilder {
@@ -52,24 +55,73 @@ impl GraphBuilder {
         retval
     }
 
-    pub(crate) async fn build(&self, connector: Arc<dyn Connector>) -> Graph {
+    /// Build the graph with `connectors` by their names. The first one is the default connector
+    /// of models without `@@connector`.
+    pub(crate) async fn build(&self, connectors: Vec<(String, Arc<dyn Connector>)>) -> Graph {
+        let (default_name, default_connector) = connectors.first().cloned().unwrap();
         let mut graph = GraphInner {
             enums: self.build_enums(),
             models_vec: Vec::new(),
+            all_models_vec: Vec::new(),
             models_map: HashMap::new(),
             url_segment_name_map: HashMap::new(),
             connector: None,
+            connectors: vec![],
         };
-        graph.models_vec = self.model_builders.iter().map(|mb| { mb.build(connector.clone()) }).collect();
+        graph.all_models_vec = self.model_builders.iter().map(|mb| {
+            let name = mb.connector.as_ref().unwrap_or(&default_name);
+            match connectors.iter().find(|(n, _)| n == name) {
+                Some((name, connector)) => mb.build(name, connector.clone()),
+                None => panic!("Connector '{}' of model '{}' is not defined.", name, mb.name),
+            }
+        }).collect();
+        graph.models_vec = graph.all_models_vec.iter().filter(|m| !m.internal()).cloned().collect();
         let mut models_map: HashMap<String, Model> = HashMap::new();
         let mut url_segment_name_map: HashMap<String, String> = HashMap::new();
-        for model in graph.models_vec.iter() {
+        for model in graph.all_models_vec.iter() {
             models_map.insert(model.name().to_owned(), model.clone());
+        }
+        for model in graph.models_vec.iter() {
             url_segment_name_map.insert(model.url_segment_name().to_owned(), model.name().to_owned());
         }
+        // join tables are queried together with both sides
+        for model in graph.all_models_vec.iter() {
+            for relation in model.relations() {
+                let Some(through) = relation.through() else { continue };
+                let through_model = models_map.get(through).unwrap();
+                let opposite_model = models_map.get(relation.model()).unwrap();
+                if through_model.connector_name() != model.connector_name() || opposite_model.connector_name() != model.connector_name() {
+                    panic!("Relation '{}' of model '{}' joins models of different connectors through '{}'.", relation.name(), model.name(), through);
+                }
+            }
+        }
+        // handler types are written as strings, so they aren't resolved with the schema
+        for model in graph.all_models_vec.iter() {
+            for handler in model.handlers() {
+                let types = handler.input().iter().map(|(_, t)| t).chain(handler.output());
+                for r#type in types {
+                    if !r#type.is_defined(&models_map, &graph.enums) {
+                        panic!("Type '{}' of handler '{}' of model '{}' is not defined.", r#type.name, handler.name(), model.name());
+                    }
+                }
+            }
+        }
+        // install recordPrevious for keys referenced by relations with update rules
+        for model in graph.all_models_vec.iter() {
+            for relation in model.relations() {
+                if !relation.has_foreign_key() || relation.update_rule() == UpdateRule::Default {
+                    continue
+                }
+                let referenced_model = models_map.get(relation.model()).unwrap();
+                for reference in relation.references() {
+                    referenced_model.field(reference).unwrap().to_mut().previous_value_rule = PreviousValueRule::Keep;
+                }
+            }
+        }
         graph.models_map = models_map;
         graph.url_segment_name_map = url_segment_name_map;
-        graph.connector = Some(connector.clone());
-        Graph { inner: Arc::new(graph) }
+        graph.connector = Some(default_connector);
+        graph.connectors = connectors;
+        Graph::new(graph)
     }
 }
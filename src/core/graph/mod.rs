@@ -1,12 +1,13 @@
 use std::collections::HashMap;
 use std::future::Future;
-use std::sync::Arc;
-use key_path::KeyPath;
+use std::sync::{Arc, Mutex};
+use async_recursion::async_recursion;
+use key_path::{KeyPath, path};
 use to_mut_proc_macro::ToMut;
 use to_mut::ToMut;
-use crate::core::action::{Action, CREATE, INTERNAL_AMOUNT, INTERNAL_POSITION, PROGRAM_CODE, SINGLE};
+use crate::core::action::{Action, CREATE, FIND, INTERNAL_AMOUNT, INTERNAL_POSITION, MANY, NESTED, PROGRAM_CODE, SINGLE};
 use crate::core::action::source::ActionSource;
-use crate::core::connector::Connector;
+use crate::core::connector::{Connector, SaveSession};
 use crate::core::model::Model;
 use crate::core::object::Object;
 use crate::core::r#enum::Enum;
@@ -14,30 +15,48 @@ use crate::core::error::Error;
 use crate::core::relation::Relation;
 use crate::core::result::Result;
 use crate::prelude::Value;
+use crate::teon;
 
 pub mod builder;
 
 #[derive(Clone, ToMut)]
 pub struct Graph {
-    inner: Arc<GraphInner>
+    inner: Arc<GraphInner>,
+    save_session: Option<Arc<TransactionSessions>>,
 }
 
 pub(crate) struct GraphInner {
     pub(crate) enums: HashMap<String, Enum>,
     pub(crate) models_vec: Vec<Model>,
+    pub(crate) all_models_vec: Vec<Model>,
     pub(crate) models_map: HashMap<String, Model>,
     pub(crate) url_segment_name_map: HashMap<String, String>,
     pub(crate) connector: Option<Arc<dyn Connector>>,
+    pub(crate) connectors: Vec<(String, Arc<dyn Connector>)>,
 }
 
 static mut CURRENT: Option<&'static Graph> = None;
 
+tokio::task_local! {
+    static TRANSACTION: Arc<TransactionSessions>;
+}
+
 impl Graph {
 
+    pub(crate) fn new(inner: GraphInner) -> Self {
+        Self { inner: Arc::new(inner), save_session: None }
+    }
+
     pub fn models(&self) -> &Vec<Model> {
         self.inner.models_vec.as_ref()
     }
 
+    /// Models including internal ones, which are stored by connectors but hidden from routes
+    /// and generators.
+    pub(crate) fn all_models(&self) -> &Vec<Model> {
+        self.inner.all_models_vec.as_ref()
+    }
+
     pub fn current() -> &'static Self {
         unsafe {
             if CURRENT.is_none() {
@@ -78,22 +97,28 @@ impl Graph {
 
     pub(crate) async fn find_unique_internal(&self, model: &str, finder: &Value, mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Object> {
         let model = self.model(model).unwrap();
-        self.connector().find_unique(self, model, finder, mutation_mode, action, action_source).await
+        let connector_finder = &self.strip_cross_connector_includes(model, &self.soft_delete_finder(model, finder))?;
+        let object = model.connector().find_unique(self, model, connector_finder, mutation_mode, action, action_source).await?;
+        self.fetch_cross_connector_includes(model, &vec![object.clone()], finder).await?;
+        Ok(object)
     }
 
     pub(crate) async fn find_first_internal(&self, model: &str, finder: &Value, mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Object> {
         let model = self.model(model).unwrap();
-        let mut finder = finder.as_hashmap().clone().unwrap().clone();
-        finder.insert("take".to_string(), 1.into());
-        let finder = Value::HashMap(finder);
-        let result = self.connector().find_many(self, model, &finder, mutation_mode, action, action_source).await;
+        let connector_finder = self.strip_cross_connector_includes(model, &self.soft_delete_finder(model, finder))?;
+        let mut connector_finder = connector_finder.as_hashmap().unwrap().clone();
+        connector_finder.insert("take".to_string(), 1.into());
+        let connector_finder = Value::HashMap(connector_finder);
+        let result = model.connector().find_many(self, model, &connector_finder, mutation_mode, action, action_source).await;
         match result {
             Err(err) => Err(err),
             Ok(retval) => {
                 if retval.is_empty() {
                     Err(Error::object_not_found())
                 } else {
-                    Ok(retval.get(0).unwrap().clone())
+                    let object = retval.first().unwrap().clone();
+                    self.fetch_cross_connector_includes(model, &vec![object.clone()], finder).await?;
+                    Ok(object)
                 }
             }
         }
@@ -101,47 +126,287 @@ impl Graph {
 
     pub(crate) async fn find_many_internal(&self, model: &str, finder: &Value, mutation_mode: bool, action: Action, action_source: ActionSource) -> Result<Vec<Object>> {
         let model = self.model(model).unwrap();
-        self.connector().find_many(self, model, finder, mutation_mode, action, action_source).await
-    }
-
-    pub(crate) async fn batch<F, Fut>(&self, model: &str, finder: &Value, action: Action, action_source: ActionSource, f: F) -> Result<()> where
-    F: Fn(Object) -> Fut,
-    Fut: Future<Output = Result<()>> {
-        let batch_size: usize = 200;
-        let mut index: usize = 0;
-        loop {
-            let mut batch_finder = finder.clone();
-            batch_finder.as_hashmap_mut().unwrap().insert("skip".to_owned(), (index * batch_size).into());
-            batch_finder.as_hashmap_mut().unwrap().insert("take".to_owned(), batch_size.into());
-            let results = self.find_many_internal(model, &batch_finder, true, action, action_source.clone()).await?;
-            for result in results.iter() {
-                f(result.clone()).await?;
-            }
-            if results.len() < batch_size {
-                return Ok(());
-            }
-            index += 1;
-        }
+        let connector_finder = &self.strip_cross_connector_includes(model, &self.soft_delete_finder(model, finder))?;
+        let objects = model.connector().find_many(self, model, connector_finder, mutation_mode, action, action_source).await?;
+        self.fetch_cross_connector_includes(model, &objects, finder).await?;
+        Ok(objects)
     }
 
     pub(crate) async fn count(&self, model: &str, finder: &Value) -> Result<usize> {
         let model = self.model(model).unwrap();
-        self.connector().count(self, model, finder).await
+        let finder = &self.soft_delete_finder(model, finder);
+        model.connector().count(self, model, finder).await
     }
 
     pub(crate) async fn aggregate(&self, model: &str, finder: &Value) -> Result<Value> {
         let model = self.model(model).unwrap();
-        self.connector().aggregate(self, model, finder).await
+        let finder = &self.soft_delete_finder(model, finder);
+        model.connector().aggregate(self, model, finder).await
     }
 
     pub(crate) async fn group_by(&self, model: &str, finder: &Value) -> Result<Value> {
         let model = self.model(model).unwrap();
-        self.connector().group_by(self, model, finder).await
+        let finder = &self.soft_delete_finder(model, finder);
+        model.connector().group_by(self, model, finder).await
+    }
+
+    /// Narrow `finder` to the records which are not deleted, or to the deleted ones with
+    /// `onlyDeleted`, for models with soft delete. Relation includes and relation filters are
+    /// narrowed the same way.
+    fn soft_delete_finder(&self, model: &Model, finder: &Value) -> Value {
+        let mut finder = finder.as_hashmap().cloned().unwrap_or_default();
+        let with_deleted = finder.remove("withDeleted").is_some_and(|v| v.as_bool() == Some(true));
+        let only_deleted = finder.remove("onlyDeleted").is_some_and(|v| v.as_bool() == Some(true));
+        if let Some(r#where) = finder.remove("where") {
+            finder.insert("where".to_owned(), self.soft_delete_where(model, &r#where));
+        }
+        if let Some(field) = model.soft_delete() {
+            if !with_deleted {
+                let condition = if only_deleted {
+                    teon!({(field): {"not": null}})
+                } else {
+                    teon!({(field): {"equals": null}})
+                };
+                let r#where = match finder.remove("where") {
+                    Some(r#where) => teon!({"AND": [r#where, condition]}),
+                    None => condition,
+                };
+                finder.insert("where".to_owned(), r#where);
+            }
+        }
+        if let Some(include) = finder.get("include").and_then(|i| i.as_hashmap()) {
+            let include = include.iter().map(|(key, value)| {
+                let relation_model = match model.relation(key) {
+                    Some(relation) => self.model(relation.model()).unwrap(),
+                    None => return (key.clone(), value.clone()),
+                };
+                let value = match value {
+                    Value::Bool(true) if relation_model.soft_delete().is_some() => self.soft_delete_finder(relation_model, &teon!({})),
+                    Value::HashMap(_) => self.soft_delete_finder(relation_model, value),
+                    _ => value.clone(),
+                };
+                (key.clone(), value)
+            }).collect();
+            finder.insert("include".to_owned(), Value::HashMap(include));
+        }
+        Value::HashMap(finder)
+    }
+
+    /// Narrow the relation filters in `r#where` to related records which are not deleted. With
+    /// `every`, deleted records don't have to match.
+    fn soft_delete_where(&self, model: &Model, r#where: &Value) -> Value {
+        let Some(r#where) = r#where.as_hashmap() else { return r#where.clone() };
+        let r#where = r#where.iter().map(|(key, value)| {
+            let value = match key.as_str() {
+                "AND" | "OR" | "NOT" => match value {
+                    Value::Vec(values) => Value::Vec(values.iter().map(|v| self.soft_delete_where(model, v)).collect()),
+                    _ => self.soft_delete_where(model, value),
+                },
+                _ => match (model.relation(key), value.as_hashmap()) {
+                    (Some(relation), Some(filters)) => {
+                        let relation_model = self.model(relation.model()).unwrap();
+                        Value::HashMap(filters.iter().map(|(filter, inner)| {
+                            let inner = self.soft_delete_where(relation_model, inner);
+                            let inner = match (relation_model.soft_delete(), inner.is_null()) {
+                                (Some(field), false) if filter == "every" => teon!({"OR": [inner, {(field): {"not": null}}]}),
+                                (Some(field), false) => teon!({"AND": [inner, {(field): {"equals": null}}]}),
+                                _ => inner,
+                            };
+                            (filter.clone(), inner)
+                        }).collect())
+                    }
+                    _ => value.clone(),
+                },
+            };
+            (key.clone(), value)
+        }).collect();
+        Value::HashMap(r#where)
+    }
+
+    // MARK: - Relations across connectors
+
+    /// Whether `relation` of `model` points to a model saved by another connector. Connectors
+    /// can't join such relations, they are fetched separately after the records are found.
+    fn is_cross_connector(&self, model: &Model, relation: &Relation) -> bool {
+        self.model(relation.model()).is_some_and(|m| m.connector_name() != model.connector_name())
+    }
+
+    /// Remove includes of relations across connectors from `finder` before it's passed to the
+    /// connector of `model`. Local fields of removed relations are kept in `select` to fetch
+    /// them later. Filtering by these relations is not supported.
+    fn strip_cross_connector_includes(&self, model: &Model, finder: &Value) -> Result<Value> {
+        let mut finder = match finder.as_hashmap() {
+            Some(finder) => finder.clone(),
+            None => return Ok(finder.clone()),
+        };
+        if let Some(r#where) = finder.get("where") {
+            self.check_cross_connector_where(model, r#where, &path!["where"])?;
+        }
+        let include = match finder.remove("include") {
+            Some(Value::HashMap(include)) => include,
+            Some(include) => {
+                finder.insert("include".to_owned(), include);
+                return Ok(Value::HashMap(finder));
+            }
+            None => return Ok(Value::HashMap(finder)),
+        };
+        let mut retained = HashMap::new();
+        let mut local_fields = vec![];
+        for (key, value) in include {
+            let relation = match model.relation(&key) {
+                Some(relation) => relation,
+                None => {
+                    retained.insert(key, value);
+                    continue
+                }
+            };
+            if self.is_cross_connector(model, relation) {
+                local_fields.extend(relation.fields().iter().cloned());
+            } else {
+                let value = match value {
+                    Value::HashMap(_) => self.strip_cross_connector_includes(self.model(relation.model()).unwrap(), &value)?,
+                    _ => value,
+                };
+                retained.insert(key, value);
+            }
+        }
+        if !retained.is_empty() {
+            finder.insert("include".to_owned(), Value::HashMap(retained));
+        }
+        if let Some(Value::HashMap(select)) = finder.get_mut("select") {
+            let inclusive = select.values().any(|v| v.as_bool() == Some(true));
+            for field in local_fields {
+                if inclusive {
+                    select.insert(field, Value::Bool(true));
+                } else {
+                    select.remove(&field);
+                }
+            }
+        }
+        Ok(Value::HashMap(finder))
+    }
+
+    fn check_cross_connector_where(&self, model: &Model, r#where: &Value, path: &KeyPath) -> Result<()> {
+        let r#where = match r#where.as_hashmap() {
+            Some(r#where) => r#where,
+            None => return Ok(()),
+        };
+        for (key, value) in r#where {
+            match key.as_str() {
+                "AND" | "OR" => if let Some(list) = value.as_vec() {
+                    for (index, item) in list.iter().enumerate() {
+                        self.check_cross_connector_where(model, item, &(path + key + index))?;
+                    }
+                },
+                "NOT" => self.check_cross_connector_where(model, value, &(path + key))?,
+                _ => if let Some(relation) = model.relation(key) {
+                    if self.is_cross_connector(model, relation) {
+                        return Err(Error::unexpected_input_value_with_reason("Relations across connectors can't be filtered.", path + key));
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetch includes of relations across connectors for `objects` found with `finder`.
+    /// Included relations of the same connector are walked for nested includes.
+    #[async_recursion]
+    async fn fetch_cross_connector_includes(&self, model: &Model, objects: &Vec<Object>, finder: &Value) -> Result<()> {
+        if objects.is_empty() {
+            return Ok(());
+        }
+        let include = match finder.get("include").and_then(|i| i.as_hashmap()) {
+            Some(include) => include,
+            None => return Ok(()),
+        };
+        for (key, value) in include {
+            let relation = match model.relation(key) {
+                Some(relation) => relation,
+                None => continue,
+            };
+            let arg = match value {
+                Value::Bool(true) => teon!({}),
+                Value::HashMap(_) => value.clone(),
+                _ => continue,
+            };
+            if self.is_cross_connector(model, relation) {
+                self.fetch_cross_connector_relation(objects, relation, &arg).await?;
+            } else {
+                let mut children = vec![];
+                for object in objects {
+                    children.extend(object.get_relation_vec(key)?);
+                }
+                self.fetch_cross_connector_includes(self.model(relation.model()).unwrap(), &children, &arg).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetch `relation` of all `objects` with one query and distribute the records by their
+    /// references. Paginated includes are fetched object by object.
+    #[async_recursion]
+    async fn fetch_cross_connector_relation(&self, objects: &Vec<Object>, relation: &Relation, arg: &Value) -> Result<()> {
+        let key = relation.name();
+        let paginated = ["take", "skip", "cursor", "pageSize", "pageNumber"].iter().any(|k| arg.get(*k).is_some());
+        if paginated {
+            for object in objects {
+                let records = object.fetch_relation_objects(key, Some(arg)).await?;
+                object.inner.relation_query_map.lock().unwrap().insert(key.to_owned(), records);
+            }
+            return Ok(());
+        }
+        let mut local_values = vec![];
+        for object in objects {
+            let values = relation.fields().iter().map(|f| object.get_value(f)).collect::<Result<Vec<Value>>>()?;
+            local_values.push(values);
+        }
+        let conditions: Vec<Value> = local_values.iter().filter(|values| !values.iter().any(|v| v.is_null())).map(|values| {
+            Value::HashMap(relation.references().iter().cloned().zip(values.iter().cloned()).collect())
+        }).collect();
+        let records = if conditions.is_empty() {
+            vec![]
+        } else {
+            let mut finder = arg.as_hashmap().cloned().unwrap_or_default();
+            let condition = teon!({"OR": Value::Vec(conditions)});
+            let r#where = match finder.remove("where") {
+                Some(r#where) => teon!({"AND": [r#where, condition]}),
+                None => condition,
+            };
+            finder.insert("where".to_owned(), r#where);
+            if let Some(Value::HashMap(select)) = finder.get_mut("select") {
+                if select.values().any(|v| v.as_bool() == Some(true)) {
+                    for reference in relation.references() {
+                        select.insert(reference.clone(), Value::Bool(true));
+                    }
+                } else {
+                    for reference in relation.references() {
+                        select.remove(reference);
+                    }
+                }
+            }
+            let action = Action::from_u32(FIND | MANY | NESTED);
+            let action_source = objects.first().unwrap().action_source().clone();
+            self.find_many_internal(relation.model(), &Value::HashMap(finder), false, action, action_source).await?
+        };
+        for (object, values) in objects.iter().zip(local_values.iter()) {
+            let matched: Vec<Object> = if values.iter().any(|v| v.is_null()) {
+                vec![]
+            } else {
+                records.iter().filter(|record| {
+                    relation.references().iter().zip(values.iter()).all(|(reference, value)| record.get_value(reference).ok().as_ref() == Some(value))
+                }).cloned().collect()
+            };
+            object.inner.relation_query_map.lock().unwrap().insert(key.to_owned(), matched);
+        }
+        Ok(())
     }
 
     // MARK: - Create an object
 
-    pub(crate) fn new_object(&self, model: &str, action: Action, action_source: ActionSource) -> Result<Object> {
+    /// Create an empty object of `model`. Connectors use this to build objects from fetched
+    /// records before filling them with `Object::set_from_database_result_value`.
+    pub fn new_object(&self, model: &str, action: Action, action_source: ActionSource) -> Result<Object> {
         match self.model(model) {
             Some(model) => Ok(Object::new(self, model, action, action_source)),
             None => Err(Error::invalid_operation(format!("Model with name '{model}' is not defined.")))
@@ -160,6 +425,62 @@ impl Graph {
         Ok(obj)
     }
 
+    // MARK: - Transactions
+
+    /// Run `f` inside a transaction. Every object created, found, saved or deleted while `f`
+    /// runs shares one save session for each connector, whether it's reached through the graph
+    /// passed to `f` or through an object's own graph. The sessions are committed if `f` returns
+    /// `Ok`, and aborted if it returns `Err`.
+    ///
+    /// When called inside a transaction, `f` joins the outer transaction and the outer caller
+    /// decides whether it's committed.
+    ///
+    /// A transaction is atomic on each connector but not across connectors. Sessions of
+    /// different connectors are committed one after another without a two-phase commit: when a
+    /// commit fails, the sessions after it are aborted, but the connectors committed before it
+    /// keep their writes. Writes which must succeed or fail together should go to models of
+    /// one connector.
+    pub async fn transaction<F, Fut, T>(&self, f: F) -> Result<T> where
+        F: FnOnce(Graph) -> Fut,
+        Fut: Future<Output = Result<T>> {
+        if let Some(sessions) = self.transaction_sessions() {
+            return f(Graph { inner: self.inner.clone(), save_session: Some(sessions) }).await;
+        }
+        let sessions = Arc::new(TransactionSessions::default());
+        let graph = Graph { inner: self.inner.clone(), save_session: Some(sessions.clone()) };
+        match TRANSACTION.scope(sessions.clone(), f(graph)).await {
+            Ok(result) => {
+                sessions.commit().await?;
+                Ok(result)
+            }
+            Err(err) => {
+                if let Err(abort_err) = sessions.abort().await {
+                    eprintln!("Failed to abort transaction: {}", abort_err.message());
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// The sessions of the transaction this graph is bound to, or of the transaction the
+    /// current task runs in.
+    fn transaction_sessions(&self) -> Option<Arc<TransactionSessions>> {
+        self.save_session.clone().or_else(|| TRANSACTION.try_with(|sessions| sessions.clone()).ok())
+    }
+
+    /// The save session of the connector of `model` in the current transaction.
+    /// A new session is created if there's no transaction.
+    pub(crate) fn save_session(&self, model: &Model) -> Arc<dyn SaveSession> {
+        match self.transaction_sessions() {
+            Some(sessions) => sessions.session(model),
+            None => model.connector().new_save_session(),
+        }
+    }
+
+    pub(crate) fn bound_save_session(&self, model: &Model) -> Option<Arc<dyn SaveSession>> {
+        self.transaction_sessions().map(|sessions| sessions.session(model))
+    }
+
     // MARK: - Getting the connector
 
     pub(crate) fn connector(&self) -> &dyn Connector {
@@ -169,22 +490,28 @@ impl Graph {
         }
     }
 
-    pub(crate) fn connector_mut(&self) -> &mut dyn Connector {
-        match &self.inner.connector {
-            Some(c) => {
-                let r = c.as_ref();
-                let result = unsafe {
-                    let d: * const dyn Connector = r;
-                    let e: * mut dyn Connector = d as *mut dyn Connector;
-                    &mut *e
-                };
-                result
-            }
-            None => { panic!() }
+    /// Names of the connectors, the default one first.
+    pub(crate) fn connector_names(&self) -> Vec<&str> {
+        self.inner.connectors.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    pub(crate) fn default_connector_name(&self) -> &str {
+        self.inner.connectors.first().unwrap().0.as_str()
+    }
+
+    pub(crate) fn connector_named(&self, name: &str) -> &dyn Connector {
+        match self.inner.connectors.iter().find(|(n, _)| n == name) {
+            Some((_, c)) => c.as_ref(),
+            None => panic!("Connector '{}' is not defined.", name),
         }
     }
 
-    pub(crate) fn model(&self, name: &str) -> Option<&Model> {
+    /// Models including internal ones, which are stored by the connector named `name`.
+    pub(crate) fn connector_models(&self, name: &str) -> Vec<Model> {
+        self.all_models().iter().filter(|m| m.connector_name() == name).cloned().collect()
+    }
+
+    pub fn model(&self, name: &str) -> Option<&Model> {
         self.inner.models_map.get(name)
     }
 
@@ -218,7 +545,7 @@ impl Graph {
     ///
     /// A tuple of opposite relation's model and opposite relation.
     ///
-    pub(crate) fn opposite_relation(&self, relation: &Relation) -> (&Model, Option<&Relation>) {
+    pub fn opposite_relation(&self, relation: &Relation) -> (&Model, Option<&Relation>) {
         let opposite_model = self.model(relation.model()).unwrap();
         let opposite_relation = opposite_model.relations().iter().find(|r| r.fields() == relation.references() && r.references() == relation.fields());
         match opposite_relation {
@@ -238,7 +565,7 @@ impl Graph {
     ///
     /// A tuple of through relation's model and through model's local relation.
     ///
-    pub(crate) fn through_relation(&self, relation: &Relation) -> (&Model, &Relation) {
+    pub fn through_relation(&self, relation: &Relation) -> (&Model, &Relation) {
         let through_model = self.model(relation.through().unwrap()).unwrap();
         let through_local_relation = through_model.relation(relation.local()).unwrap();
         (through_model, through_local_relation)
@@ -264,3 +591,212 @@ impl Graph {
 
 unsafe impl Send for Graph { }
 unsafe impl Sync for Graph { }
+
+/// The save sessions of a transaction, one for each connector which is read from or written to.
+#[derive(Default)]
+pub(crate) struct TransactionSessions {
+    sessions: Mutex<Vec<(String, Arc<dyn SaveSession>)>>,
+}
+
+impl TransactionSessions {
+
+    fn session(&self, model: &Model) -> Arc<dyn SaveSession> {
+        let mut sessions = self.sessions.lock().unwrap();
+        if let Some((_, session)) = sessions.iter().find(|(name, _)| name == model.connector_name()) {
+            return session.clone();
+        }
+        let session = model.connector().new_save_session();
+        sessions.push((model.connector_name().to_owned(), session.clone()));
+        session
+    }
+
+    fn sessions(&self) -> Vec<Arc<dyn SaveSession>> {
+        self.sessions.lock().unwrap().iter().map(|(_, session)| session.clone()).collect()
+    }
+
+    /// Commit every session. If one fails, the sessions after it are aborted. Sessions which are
+    /// committed already can't be aborted, aborting them is a no-op.
+    async fn commit(&self) -> Result<()> {
+        for session in self.sessions() {
+            if let Err(err) = session.commit().await {
+                if let Err(abort_err) = self.abort().await {
+                    eprintln!("Failed to abort transaction: {}", abort_err.message());
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Abort every session, and return the first error.
+    async fn abort(&self) -> Result<()> {
+        let mut result = Ok(());
+        for session in self.sessions() {
+            if let Err(err) = session.abort().await {
+                if result.is_ok() { result = Err(err) }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use async_trait::async_trait;
+    use serial_test::serial;
+    use crate::core::app::testing::sqlite_app;
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model Item {
+  @id @default($cuid)
+  id: String
+  @unique
+  name: String
+}
+"#;
+
+    #[tokio::test]
+    #[serial]
+    async fn transaction_commits_when_ok() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        graph.transaction(|graph| async move {
+            graph.create_object("Item", teon!({"name": "a"})).await?.save().await?;
+            graph.create_object("Item", teon!({"name": "b"})).await?.save().await
+        }).await.unwrap();
+        assert_eq!(graph.count("Item", &teon!({})).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn transaction_aborts_and_keeps_error() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        let result: Result<()> = graph.transaction(|graph| async move {
+            graph.create_object("Item", teon!({"name": "a"})).await?.save().await?;
+            Err(Error::internal_server_error("handler failed"))
+        }).await;
+        assert_eq!(result.unwrap_err().message(), "handler failed");
+        assert_eq!(graph.count("Item", &teon!({})).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn transaction_rolls_back_nested_save() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        graph.create_object("Item", teon!({"name": "a"})).await.unwrap().save().await.unwrap();
+        let result: Result<()> = graph.transaction(|graph| async move {
+            graph.create_object("Item", teon!({"name": "b"})).await?.save().await?;
+            graph.create_object("Item", teon!({"name": "a"})).await?.save().await
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(graph.count("Item", &teon!({})).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn reads_through_unbound_graph_use_transaction() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        let found = graph.transaction(|graph| async move {
+            graph.create_object("Item", teon!({"name": "a"})).await?.save().await?;
+            // the pool has one connection, which the transaction holds
+            let found: Vec<Object> = Graph::current().find_many("Item", &teon!({})).await?;
+            Ok(found.len())
+        }).await.unwrap();
+        assert_eq!(found, 1);
+    }
+
+    #[derive(Debug, Default)]
+    struct StubSession {
+        fails: bool,
+        aborted: AtomicBool,
+    }
+
+    #[async_trait]
+    impl SaveSession for StubSession {
+        async fn commit(&self) -> Result<()> {
+            if self.fails { Err(Error::internal_server_error("commit failed")) } else { Ok(()) }
+        }
+        async fn abort(&self) -> Result<()> {
+            self.aborted.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_commit_aborts_remaining_sessions() {
+        let failing = Arc::new(StubSession { fails: true, ..Default::default() });
+        let remaining = Arc::new(StubSession::default());
+        let sessions = TransactionSessions { sessions: Mutex::new(vec![
+            ("a".to_owned(), failing.clone() as Arc<dyn SaveSession>),
+            ("b".to_owned(), remaining.clone() as Arc<dyn SaveSession>),
+        ]) };
+        assert_eq!(sessions.commit().await.unwrap_err().message(), "commit failed");
+        assert!(remaining.aborted.load(Ordering::SeqCst));
+    }
+
+    const SOFT_DELETE_SCHEMA: &str = r#"
+model Author {
+  @@softDelete(.deletedAt)
+  @id @default($cuid)
+  id: String
+  @unique
+  name: String
+  deletedAt: DateTime?
+  @relation(fields: .id, references: .authorId)
+  posts: Post[]
+}
+
+model Post {
+  @@softDelete(.deletedAt)
+  @id @default($cuid)
+  id: String
+  title: String
+  deletedAt: DateTime?
+  @foreignKey
+  authorId: String
+  @relation(fields: .authorId, references: .id)
+  author: Author
+}
+"#;
+
+    #[tokio::test]
+    #[serial]
+    async fn relation_filters_skip_soft_deleted_records() {
+        let app = sqlite_app(SOFT_DELETE_SCHEMA).await;
+        let graph = app.graph();
+        for (name, posts) in [("a", vec![("x", true)]), ("b", vec![("x", false)]), ("c", vec![("y", false), ("x", true)]), ("d", vec![("z", false)])] {
+            let author = graph.create_object("Author", teon!({"name": name})).await.unwrap();
+            author.save().await.unwrap();
+            for (title, deleted) in posts {
+                let post = graph.create_object("Post", teon!({"title": title, "authorId": author.get_value("id").unwrap()})).await.unwrap();
+                post.save().await.unwrap();
+                if deleted {
+                    post.delete().await.unwrap();
+                }
+            }
+            if name == "d" {
+                author.delete().await.unwrap();
+            }
+        }
+        async fn names(graph: &Graph, model: &str, key: &str, r#where: Value) -> Vec<String> {
+            let objects: Vec<Object> = graph.find_many(model, &teon!({"where": r#where, "orderBy": [{(key): "asc"}]})).await.unwrap();
+            objects.iter().map(|o| o.get(key).unwrap()).collect()
+        }
+        assert_eq!(names(graph, "Author", "name", teon!({"posts": {"some": {"title": "x"}}})).await, vec!["b"]);
+        assert_eq!(names(graph, "Author", "name", teon!({"posts": {"none": {"title": "x"}}})).await, vec!["a", "c"]);
+        assert_eq!(names(graph, "Author", "name", teon!({"posts": {"every": {"title": "y"}}})).await, vec!["a", "c"]);
+        assert_eq!(names(graph, "Author", "name", teon!({"OR": [{"posts": {"some": {"title": "y"}}}, {"NOT": {"posts": {"none": {"title": "x"}}}}]})).await, vec!["b", "c"]);
+        assert_eq!(names(graph, "Post", "title", teon!({"author": {"is": {"name": {"in": ["b", "d"]}}}})).await, vec!["x"]);
+        assert_eq!(names(graph, "Post", "title", teon!({"author": {"isNot": {"name": "b"}}})).await, vec!["y", "z"]);
+    }
+}
+
@@ -0,0 +1,62 @@
+use crate::core::action::{Action, CREATE, ENTRY, FIND, FIND_FIRST, MANY, SINGLE};
+use crate::core::action::source::ActionSource;
+use crate::core::graph::Graph;
+use crate::core::model::Model;
+use crate::core::object::Object;
+use crate::core::result::Result;
+use crate::core::teon::Value;
+
+/// The context a custom handler is called with.
+///
+/// Objects found or created through this belong to the requesting identity, so the model's
+/// `@@canRead` and `@@canMutate` pipelines apply to them like they do in built-in actions.
+#[derive(Clone)]
+pub struct HandlerCtx {
+    pub(crate) graph: &'static Graph,
+    pub(crate) model: Model,
+    pub(crate) input: Value,
+    pub(crate) identity: Option<Object>,
+}
+
+impl HandlerCtx {
+
+    pub fn graph(&self) -> &'static Graph {
+        self.graph
+    }
+
+    /// The model which the handler is declared on.
+    pub fn model(&self) -> &Model {
+        &self.model
+    }
+
+    /// The decoded request body, with a value for each declared input key.
+    pub fn input(&self) -> &Value {
+        &self.input
+    }
+
+    pub fn identity(&self) -> Option<&Object> {
+        self.identity.as_ref()
+    }
+
+    fn source(&self) -> ActionSource {
+        ActionSource::Identity(self.identity.clone())
+    }
+
+    pub async fn find_unique(&self, model: &str, finder: &Value) -> Result<Object> {
+        self.graph.find_unique_internal(model, finder, false, Action::from_u32(FIND | SINGLE | ENTRY), self.source()).await
+    }
+
+    pub async fn find_first(&self, model: &str, finder: &Value) -> Result<Object> {
+        self.graph.find_first_internal(model, finder, false, Action::from_u32(FIND_FIRST | SINGLE | ENTRY), self.source()).await
+    }
+
+    pub async fn find_many(&self, model: &str, finder: &Value) -> Result<Vec<Object>> {
+        self.graph.find_many_internal(model, finder, false, Action::from_u32(FIND | MANY | ENTRY), self.source()).await
+    }
+
+    pub async fn create_object(&self, model: &str, initial: impl AsRef<Value>) -> Result<Object> {
+        let object = self.graph.new_object(model, Action::from_u32(CREATE | SINGLE | ENTRY), self.source())?;
+        object.set_teon(initial.as_ref()).await?;
+        Ok(object)
+    }
+}
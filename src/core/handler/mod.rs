@@ -0,0 +1,130 @@
+pub mod ctx;
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use futures_util::future::BoxFuture;
+use crate::core::field::Field;
+use crate::core::field::r#type::FieldType;
+use crate::core::graph::Graph;
+use crate::core::model::Model;
+use crate::core::r#enum::Enum;
+use crate::core::handler::ctx::HandlerCtx;
+use crate::core::result::Result;
+use crate::core::teon::Value;
+
+pub type HandlerFn = Arc<dyn Fn(HandlerCtx) -> BoxFuture<'static, Result<Value>> + Send + Sync>;
+
+/// A type in the input or output of a custom handler. It's written like a field type, e.g.
+/// `Int`, `Post[]` or `String?`. Model names declare objects of that model.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct HandlerType {
+    pub(crate) name: String,
+    pub(crate) array: bool,
+    pub(crate) optional: bool,
+}
+
+impl HandlerType {
+
+    pub(crate) fn parse(string: &str) -> Option<Self> {
+        let mut name = string.trim();
+        let optional = name.ends_with("?");
+        if optional {
+            name = &name[..name.len() - 1];
+        }
+        let array = name.ends_with("[]");
+        if array {
+            name = &name[..name.len() - 2];
+        }
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return None;
+        }
+        Some(Self { name: name.to_owned(), array, optional })
+    }
+
+    /// Whether the name is a scalar type, or a model or an enum of `graph`.
+    pub(crate) fn is_defined(&self, models: &HashMap<String, Model>, enums: &HashMap<String, Enum>) -> bool {
+        Self::scalar_type(&self.name).is_some() || models.contains_key(&self.name) || enums.contains_key(&self.name)
+    }
+
+    /// The field type of this, with model and enum names resolved against `graph`.
+    pub(crate) fn field_type(&self, graph: &Graph) -> FieldType {
+        let item_type = match Self::scalar_type(&self.name) {
+            Some(field_type) => field_type,
+            None if graph.model(&self.name).is_some() => FieldType::Object(self.name.clone()),
+            None if graph.r#enum(&self.name).is_some() => FieldType::Enum(self.name.clone()),
+            // undefined types are rejected when the graph is built
+            None => unreachable!("Handler type '{}' is not defined.", self.name),
+        };
+        if self.array {
+            let mut inner = Field::new("".to_owned());
+            inner.set_required();
+            inner.field_type = Some(item_type);
+            FieldType::Vec(Box::new(inner))
+        } else {
+            item_type
+        }
+    }
+
+    fn scalar_type(name: &str) -> Option<FieldType> {
+        Some(match name {
+            "String" => FieldType::String,
+            "Bool" => FieldType::Bool,
+            "Int" | "Int32" => FieldType::I32,
+            "Int64" => FieldType::I64,
+            "Float32" => FieldType::F32,
+            "Float" | "Float64" => FieldType::F64,
+            "Date" => FieldType::Date,
+            "DateTime" => FieldType::DateTime,
+            "Decimal" => FieldType::Decimal,
+            "Json" => FieldType::Json,
+            #[cfg(feature = "data-source-mongodb")]
+            "ObjectId" => FieldType::ObjectId,
+            _ => return None,
+        })
+    }
+}
+
+/// A handler declared with `@@handler` besides the built-in actions of a model. It's served at
+/// `/{model}/action/{name}` and implemented with `AppBuilder::handler`.
+#[derive(Clone)]
+pub struct Handler {
+    pub(crate) name: String,
+    pub(crate) input: Vec<(String, HandlerType)>,
+    pub(crate) output: Option<HandlerType>,
+    pub(crate) implementation: Option<HandlerFn>,
+}
+
+impl Handler {
+
+    pub(crate) fn new(name: impl Into<String>, input: Vec<(String, HandlerType)>, output: Option<HandlerType>) -> Self {
+        Self { name: name.into(), input, output, implementation: None }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn input(&self) -> &Vec<(String, HandlerType)> {
+        &self.input
+    }
+
+    pub(crate) fn output(&self) -> Option<&HandlerType> {
+        self.output.as_ref()
+    }
+
+    pub(crate) fn implementation(&self) -> Option<&HandlerFn> {
+        self.implementation.as_ref()
+    }
+}
+
+impl Debug for Handler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handler")
+            .field("name", &self.name)
+            .field("input", &self.input)
+            .field("output", &self.output)
+            .field("implemented", &self.implementation.is_some())
+            .finish()
+    }
+}
@@ -23,6 +23,15 @@ impl Input {
         }
     }
 
+    /// Objects are values of json fields, unless they only have a `set` or a `merge` key.
+    pub(crate) fn decode_json_field(updator: &Value) -> Input {
+        match updator.as_hashmap() {
+            Some(map) if map.len() == 1 && map.contains_key("set") => SetValue(map.get("set").unwrap().clone()),
+            Some(map) if map.len() == 1 && map.contains_key("merge") => AtomicUpdator(updator.clone()),
+            _ => SetValue(updator.clone()),
+        }
+    }
+
     pub(crate) fn key_value(value: &HashMap<String, Value>) -> (&str, &Value) {
         (value.keys().next().unwrap().as_str(), value.values().next().unwrap())
     }
@@ -42,6 +51,13 @@ impl Input {
         }
     }
 
+    /// The field and term of a full-text search at the top level of a where input.
+    pub(crate) fn full_text_search(r#where: Option<&Value>) -> Option<(&str, &str)> {
+        r#where?.as_hashmap()?.iter().find_map(|(key, value)| {
+            value.as_hashmap()?.get("search")?.as_str().map(|term| (key.as_str(), term))
+        })
+    }
+
     pub(crate) fn has_negative_take(json_value: &Value) -> bool {
         if json_value.is_hashmap() {
             let take = json_value.as_hashmap().unwrap().get("take");
@@ -8,9 +8,10 @@ pub mod pipeline;
 pub mod object;
 pub mod teon;
 pub mod app;
-pub(crate) mod connector;
-pub(crate) mod field;
-pub(crate) mod relation;
+pub mod connector;
+pub mod field;
+pub mod relation;
 pub(crate) mod property;
 pub(crate) mod input;
-pub(crate) mod action;
+pub mod action;
+pub mod handler;
@@ -2,11 +2,12 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use inflector::Inflector;
 use to_mut::ToMut;
-use crate::core::action::{Action, CREATE_HANDLER, CREATE_MANY_HANDLER, IDENTITY_HANDLER, SIGN_IN_HANDLER};
+use crate::core::action::{Action, CREATE_HANDLER, CREATE_MANY_HANDLER, IDENTITY_HANDLER, REFRESH_HANDLER, RESTORE_HANDLER, SIGN_IN_HANDLER, SIGN_OUT_HANDLER};
 use crate::core::connector::Connector;
 use crate::core::field::*;
 use crate::core::field::Field;
 use crate::core::field::r#type::FieldTypeOwner;
+use crate::core::handler::Handler;
 use crate::core::relation::Relation;
 use crate::core::property::Property;
 use crate::core::relation::delete_rule::DeleteRule;
@@ -23,6 +24,9 @@ pub struct ModelBuilder {
     pub(crate) localized_name: String,
     pub(crate) description: String,
     pub(crate) identity: bool,
+    pub(crate) identity_token_expiry: Option<i64>,
+    pub(crate) identity_refresh_token_expiry: Option<i64>,
+    pub(crate) identity_claims: Option<Pipeline>,
     pub(crate) internal: bool,
     pub(crate) r#virtual: bool,
     pub(crate) fields: Vec<Field>,
@@ -31,6 +35,7 @@ pub struct ModelBuilder {
     pub(crate) properties: Vec<Property>,
     pub(crate) primary: Option<ModelIndex>,
     pub(crate) indices: Vec<ModelIndex>,
+    pub(crate) full_text_indices: Vec<Vec<String>>,
     pub(crate) before_save_pipeline: Pipeline,
     pub(crate) after_save_pipeline: Pipeline,
     pub(crate) before_delete_pipeline: Pipeline,
@@ -39,7 +44,10 @@ pub struct ModelBuilder {
     pub(crate) can_mutate_pipeline: Pipeline,
     pub(crate) disabled_actions: Option<Vec<Action>>,
     pub(crate) action_transformers: Vec<Pipeline>,
+    pub(crate) handlers: Vec<Handler>,
     pub(crate) migration: Option<ModelMigration>,
+    pub(crate) soft_delete: Option<String>,
+    pub(crate) connector: Option<String>,
 }
 
 impl ModelBuilder {
@@ -52,6 +60,9 @@ impl ModelBuilder {
             localized_name: "".to_string(),
             description: "".to_string(),
             identity: false,
+            identity_token_expiry: None,
+            identity_refresh_token_expiry: None,
+            identity_claims: None,
             internal: false,
             r#virtual: false,
             fields: vec![],
@@ -60,6 +71,7 @@ impl ModelBuilder {
             properties: vec![],
             primary: None,
             indices: Vec::new(),
+            full_text_indices: vec![],
             before_save_pipeline: Pipeline::new(),
             after_save_pipeline: Pipeline::new(),
             before_delete_pipeline: Pipeline::new(),
@@ -68,7 +80,10 @@ impl ModelBuilder {
             can_mutate_pipeline: Pipeline::new(),
             disabled_actions: None,
             action_transformers: vec![],
+            handlers: vec![],
             migration: None,
+            soft_delete: None,
+            connector: None,
         }
     }
 
@@ -77,6 +92,12 @@ impl ModelBuilder {
         self
     }
 
+    /// Store this model with the connector named `name` instead of the default one.
+    pub fn connector(&mut self, name: impl Into<String>) -> &mut Self {
+        self.connector = Some(name.into());
+        self
+    }
+
     pub fn url_segment_name(&mut self, url_segment_name: impl Into<String>) -> &mut Self {
         self.url_segment_name = url_segment_name.into();
         self
@@ -97,6 +118,11 @@ impl ModelBuilder {
         self
     }
 
+    pub fn soft_delete(&mut self, field: impl Into<String>) -> &mut Self {
+        self.soft_delete = Some(field.into());
+        self
+    }
+
     pub(crate) fn field(&mut self, field: Field) -> &mut Self {
         self.fields.push(field);
         self
@@ -182,7 +208,17 @@ impl ModelBuilder {
         self
     }
 
-    pub(crate) fn build(&self, connector: Arc<dyn Connector>) -> Model {
+    pub fn full_text_index<I, T>(&mut self, keys: I) -> &mut Self where I: IntoIterator<Item = T>, T: Into<String> {
+        self.full_text_indices.push(keys.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub(crate) fn handler(&mut self, handler: Handler) -> &mut Self {
+        self.handlers.push(handler);
+        self
+    }
+
+    pub(crate) fn build(&self, connector_name: &str, connector: Arc<dyn Connector>) -> Model {
         let fields_vec: Vec<Arc<Field>> = self.fields.clone().iter_mut().map(|fb| { Arc::new({ fb.finalize(connector.clone()); fb.clone()}) }).collect();
         let dropped_fields_vec: Vec<Arc<Field>> = self.dropped_fields.clone().iter_mut().map(|fb| { Arc::new({ fb.finalize(connector.clone()); fb.clone()}) }).collect();
         let properties_vec: Vec<Arc<Property>> = self.properties.clone().iter_mut().map(|pb| { Arc::new({ pb.finalize(connector.clone()); pb.clone() }) }).collect();
@@ -238,6 +274,49 @@ impl ModelBuilder {
             let field = fields_map.get(key).unwrap();
             field.as_ref().to_mut().previous_value_rule = PreviousValueRule::Keep;
         }
+        if let Some(soft_delete) = &self.soft_delete {
+            match fields_map.get(soft_delete) {
+                Some(field) if field.field_type().is_datetime() && field.is_optional() => (),
+                // reported by the resolver
+                _ => unreachable!("Soft delete field '{}' of model '{}' should be optional DateTime.", soft_delete, self.name),
+            }
+        }
+        let version_fields: Vec<&Arc<Field>> = fields_vec.iter().filter(|f| f.version).collect();
+        // reported by the resolver
+        if version_fields.len() > 1 {
+            unreachable!("Model '{}' has more than one version field.", self.name);
+        }
+        if let Some(field) = version_fields.first() {
+            if !field.field_type().is_int() || field.is_optional() {
+                unreachable!("Version field '{}' of model '{}' should be required Int or Int64.", field.name(), self.name);
+            }
+        }
+        let version = version_fields.first().map(|f| f.name().to_owned());
+        let mut full_text_fields: HashSet<&String> = HashSet::new();
+        for index in &self.full_text_indices {
+            if index.is_empty() {
+                panic!("Full-text index of model '{}' should have at least one field.", self.name);
+            }
+            for key in index {
+                match fields_map.get(key) {
+                    Some(field) if field.field_type().is_string() => (),
+                    Some(_) => panic!("Full-text index field '{}' of model '{}' should be String.", key, self.name),
+                    None => panic!("Full-text index field '{}' is not found on model '{}'.", key, self.name),
+                }
+                if !full_text_fields.insert(key) {
+                    panic!("Field '{}' of model '{}' is in more than one full-text index.", key, self.name);
+                }
+            }
+        }
+        let mut handler_names: HashSet<&str> = HashSet::new();
+        for handler in &self.handlers {
+            if Action::handler_from_name(handler.name()).is_some() {
+                panic!("Handler '{}' of model '{}' has the name of a built-in action.", handler.name(), self.name);
+            }
+            if !handler_names.insert(handler.name()) {
+                panic!("Handler '{}' of model '{}' is declared more than once.", handler.name(), self.name);
+            }
+        }
         let unique_query_keys = Self::unique_query_keys(self, &indices, primary.as_ref());
         let inner = ModelInner {
             name: self.name.clone(),
@@ -246,6 +325,10 @@ impl ModelBuilder {
             localized_name: self.localized_name.clone(),
             description: self.description.clone(),
             identity: self.identity,
+            identity_token_expiry: self.identity_token_expiry,
+            identity_refresh_token_expiry: self.identity_refresh_token_expiry,
+            identity_claims: self.identity_claims.clone(),
+            internal: self.internal,
             r#virtual: self.r#virtual,
             fields_vec,
             fields_map,
@@ -257,6 +340,7 @@ impl ModelBuilder {
             properties_map,
             primary,
             indices: indices.clone(),
+            full_text_indices: self.full_text_indices.clone(),
             before_save_pipeline: self.before_save_pipeline.clone(),
             after_save_pipeline: self.after_save_pipeline.clone(),
             before_delete_pipeline: self.before_delete_pipeline.clone(),
@@ -281,7 +365,12 @@ impl ModelBuilder {
             handler_actions: self.figure_out_actions(),
             disabled_actions: self.disabled_actions.clone(),
             action_transformers: self.action_transformers.clone(),
+            handlers: self.handlers.clone(),
             migration: self.migration.clone(),
+            soft_delete: self.soft_delete.clone(),
+            version,
+            connector_name: connector_name.to_owned(),
+            connector,
         };
         Model::new_with_inner(Arc::new(inner))
     }
@@ -458,6 +547,11 @@ impl ModelBuilder {
         if self.identity {
             default.insert(Action::from_u32(SIGN_IN_HANDLER));
             default.insert(Action::from_u32(IDENTITY_HANDLER));
+            default.insert(Action::from_u32(REFRESH_HANDLER));
+            default.insert(Action::from_u32(SIGN_OUT_HANDLER));
+        }
+        if self.soft_delete.is_some() && !self.internal && !self.r#virtual {
+            default.insert(Action::from_u32(RESTORE_HANDLER));
         }
         if let Some(disabled) = &self.disabled_actions {
             default.iter().filter(|a| {
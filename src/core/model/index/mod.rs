@@ -156,7 +156,7 @@ impl ModelIndex {
                 SQLDialect::MySQL => "PRIMARY".to_owned(),
                 SQLDialect::SQLite => format!("sqlite_autoindex_{}_1", table_name),
                 SQLDialect::PostgreSQL => self.normalize_name_psql(table_name),
-                _ => unreachable!()
+                SQLDialect::MSSQL => self.normalize_name_normal(table_name),
             },
             _ => match dialect {
                 SQLDialect::PostgreSQL => self.normalize_name_psql(table_name),
@@ -4,8 +4,10 @@ use std::sync::Arc;
 use async_recursion::async_recursion;
 use inflector::Inflector;
 use maplit::hashset;
-use crate::core::action::{Action, FIND, IDENTITY, MANY, NESTED, SIGN_IN, SINGLE};
+use crate::core::action::{Action, FIND, IDENTITY, MANY, NESTED, REFRESH, RESTORE, SIGN_IN, SIGN_OUT, SINGLE};
+use crate::core::connector::Connector;
 use crate::core::field::Field;
+use crate::core::handler::Handler;
 use crate::core::model::migration::ModelMigration;
 use crate::core::pipeline::ctx::Ctx;
 use crate::core::relation::Relation;
@@ -27,6 +29,10 @@ pub struct ModelInner {
     pub(crate) localized_name: String,
     pub(crate) description: String,
     pub(crate) identity: bool,
+    pub(crate) identity_token_expiry: Option<i64>,
+    pub(crate) identity_refresh_token_expiry: Option<i64>,
+    pub(crate) identity_claims: Option<Pipeline>,
+    pub(crate) internal: bool,
     pub(crate) r#virtual: bool,
     pub(crate) fields_vec: Vec<Arc<Field>>,
     pub(crate) fields_map: HashMap<String, Arc<Field>>,
@@ -37,6 +43,7 @@ pub struct ModelInner {
     pub(crate) properties_vec: Vec<Arc<Property>>,
     pub(crate) properties_map: HashMap<String, Arc<Property>>,
     pub(crate) indices: Vec<ModelIndex>,
+    pub(crate) full_text_indices: Vec<Vec<String>>,
     pub(crate) primary: Option<ModelIndex>,
     pub(crate) before_save_pipeline: Pipeline,
     pub(crate) after_save_pipeline: Pipeline,
@@ -62,7 +69,12 @@ pub struct ModelInner {
     pub(crate) handler_actions: HashSet<Action>,
     pub(crate) disabled_actions: Option<Vec<Action>>,
     pub(crate) action_transformers: Vec<Pipeline>,
+    pub(crate) handlers: Vec<Handler>,
     pub(crate) migration: Option<ModelMigration>,
+    pub(crate) soft_delete: Option<String>,
+    pub(crate) version: Option<String>,
+    pub(crate) connector_name: String,
+    pub(crate) connector: Arc<dyn Connector>,
 }
 
 #[derive(Clone)]
@@ -92,7 +104,7 @@ impl Model {
         &self.inner.name
     }
 
-    pub(crate) fn table_name(&self) -> &str {
+    pub fn table_name(&self) -> &str {
         &self.inner.table_name
     }
 
@@ -116,6 +128,25 @@ impl Model {
         self.inner.identity
     }
 
+    /// Seconds until access tokens of this identity model expire, overriding the server's.
+    pub(crate) fn identity_token_expiry(&self) -> Option<i64> {
+        self.inner.identity_token_expiry
+    }
+
+    /// Seconds until refresh tokens of this identity model expire, overriding the server's.
+    pub(crate) fn identity_refresh_token_expiry(&self) -> Option<i64> {
+        self.inner.identity_refresh_token_expiry
+    }
+
+    /// The pipeline which outputs custom claims of this identity model's tokens.
+    pub(crate) fn identity_claims(&self) -> Option<&Pipeline> {
+        self.inner.identity_claims.as_ref()
+    }
+
+    pub(crate) fn internal(&self) -> bool {
+        self.inner.internal
+    }
+
     pub(crate) fn r#virtual(&self) -> bool {
         self.inner.r#virtual
     }
@@ -135,7 +166,7 @@ impl Model {
         }
     }
 
-    pub(crate) fn field(&self, name: &str) -> Option<&Field> {
+    pub fn field(&self, name: &str) -> Option<&Field> {
         match self.inner.fields_map.get(name) {
             Some(f) => Some(f.as_ref()),
             None => None
@@ -149,7 +180,7 @@ impl Model {
         }
     }
 
-    pub(crate) fn relation(&self, name: &str) -> Option<&Relation> {
+    pub fn relation(&self, name: &str) -> Option<&Relation> {
         match self.inner.relations_map.get(name) {
             Some(r) => Some(r.as_ref()),
             None => None
@@ -163,11 +194,11 @@ impl Model {
         }
     }
 
-    pub(crate) fn primary_field_names(&self) -> Vec<&str> {
+    pub fn primary_field_names(&self) -> Vec<&str> {
         self.primary_index().items().iter().map(|i| i.field_name()).collect::<Vec<&str>>()
     }
 
-    pub(crate) fn column_name_for_field_name(&self, column_name: &str) -> Option<&str> {
+    pub fn column_name_for_field_name(&self, column_name: &str) -> Option<&str> {
         for field in self.fields().iter() {
             if field.column_name() == column_name {
                 return Some(&field.name);
@@ -182,7 +213,7 @@ impl Model {
         &self.inner.input_keys
     }
 
-    pub(crate) fn save_keys(&self) -> &Vec<String> {
+    pub fn save_keys(&self) -> &Vec<String> {
         &self.inner.save_keys
     }
 
@@ -234,9 +265,15 @@ impl Model {
                 return false;
             }
         }
-        if ((action.to_u32() & IDENTITY) != 0) || ((action.to_u32() & SIGN_IN) != 0) {
+        if self.inner.internal {
+            return false;
+        }
+        if ((action.to_u32() & IDENTITY) != 0) || ((action.to_u32() & SIGN_IN) != 0) || ((action.to_u32() & REFRESH) != 0) || ((action.to_u32() & SIGN_OUT) != 0) {
             return self.inner.identity;
         }
+        if (action.to_u32() & RESTORE) != 0 {
+            return self.inner.soft_delete.is_some();
+        }
         true
     }
 
@@ -252,6 +289,15 @@ impl Model {
         &self.inner.indices
     }
 
+    pub(crate) fn full_text_indices(&self) -> &Vec<Vec<String>> {
+        &self.inner.full_text_indices
+    }
+
+    /// The full-text index which a field belongs to.
+    pub(crate) fn full_text_index(&self, field: &str) -> Option<&Vec<String>> {
+        self.inner.full_text_indices.iter().find(|i| i.iter().any(|k| k == field))
+    }
+
     pub(crate) fn primary_index(&self) -> &ModelIndex {
         self.inner.primary.as_ref().unwrap()
     }
@@ -280,6 +326,15 @@ impl Model {
         self.inner.migration.as_ref()
     }
 
+    pub(crate) fn soft_delete(&self) -> Option<&str> {
+        self.inner.soft_delete.as_deref()
+    }
+
+    /// The field decorated with `@version`. Updates only apply to the version they were based on.
+    pub(crate) fn version_field(&self) -> Option<&Field> {
+        self.inner.version.as_ref().map(|name| self.field(name).unwrap())
+    }
+
     pub(crate) fn disabled_actions(&self) -> Option<&Vec<Action>> {
         self.inner.disabled_actions.as_ref()
     }
@@ -288,6 +343,23 @@ impl Model {
         self.inner.action_transformers.len() > 0
     }
 
+    pub(crate) fn handlers(&self) -> &Vec<Handler> {
+        &self.inner.handlers
+    }
+
+    pub(crate) fn handler(&self, name: &str) -> Option<&Handler> {
+        self.inner.handlers.iter().find(|h| h.name() == name)
+    }
+
+    /// The name of the connector which stores this model.
+    pub fn connector_name(&self) -> &str {
+        &self.inner.connector_name
+    }
+
+    pub(crate) fn connector(&self) -> &dyn Connector {
+        self.inner.connector.as_ref()
+    }
+
     #[async_recursion]
     pub(crate) async fn transformed_action<'a: 'async_recursion>(&self, ctx: Ctx<'a>) -> Result<(Value, Action)> {
         let mut ctx = ctx;
@@ -8,14 +8,16 @@ use tokio::sync::Mutex as TokioMutex;
 use std::sync::atomic::{AtomicBool, Ordering};
 use key_path::{KeyPath, path};
 use async_recursion::async_recursion;
+use chrono::{DateTime, Utc};
 use maplit::hashmap;
 use indexmap::IndexMap;
 use to_mut::ToMut;
 use to_mut_proc_macro::ToMut;
-use crate::core::action::{Action, CONNECT, CONNECT_OR_CREATE, CREATE, PROGRAM_CODE, DELETE, DISCONNECT, FIND, JOIN_CREATE, JOIN_DELETE, MANY, NESTED, SINGLE, UPDATE, UPSERT, NESTED_CREATE_ACTION, NESTED_DISCONNECT_ACTION, NESTED_SET_ACTION, NESTED_CONNECT_ACTION, NESTED_DELETE_MANY_ACTION, NESTED_UPDATE_MANY_ACTION, NESTED_UPDATE_ACTION, NESTED_DELETE_ACTION, NESTED_CONNECT_OR_CREATE_ACTION, NESTED_UPSERT_ACTION, INTERNAL_POSITION, SET};
+use crate::core::action::{Action, CONNECT, CONNECT_OR_CREATE, CREATE, PROGRAM_CODE, DELETE, DISCONNECT, FIND, JOIN_CREATE, JOIN_DELETE, MANY, NESTED, SINGLE, UPDATE, UPSERT, NESTED_CREATE_ACTION, NESTED_DISCONNECT_ACTION, NESTED_SET_ACTION, NESTED_CONNECT_ACTION, NESTED_DELETE_MANY_ACTION, NESTED_UPDATE_MANY_ACTION, NESTED_UPDATE_ACTION, NESTED_DELETE_ACTION, NESTED_CONNECT_OR_CREATE_ACTION, NESTED_UPSERT_ACTION, INTERNAL_POSITION, RESTORE, SET};
 use crate::core::action::source::ActionSource;
 use crate::core::field::{Field, PreviousValueRule};
 use crate::core::field::optionality::Optionality;
+use crate::core::field::r#type::FieldTypeOwner;
 use crate::core::input::Input;
 use crate::core::input::Input::{AtomicUpdator, SetValue};
 use crate::core::graph::Graph;
@@ -28,6 +30,8 @@ use crate::core::error::{Error, ErrorType};
 use crate::core::field::write_rule::WriteRule;
 use crate::core::relation::delete_rule::DeleteRule;
 use crate::core::relation::delete_rule::DeleteRule::Deny;
+use crate::core::relation::disconnect_rule::DisconnectRule;
+use crate::core::relation::update_rule::UpdateRule;
 use crate::core::result::Result;
 use crate::teon;
 
@@ -62,6 +66,7 @@ pub(crate) struct ObjectInner {
     pub(crate) object_connect_map: Arc<TokioMutex<HashMap<String, Vec<Object>>>>,
     pub(crate) object_disconnect_map: Arc<TokioMutex<HashMap<String, Vec<Object>>>>,
     pub(crate) ignore_relation: Option<String>,
+    pub(crate) expected_version: Arc<Mutex<Option<Value>>>,
 }
 
 fn check_user_json_keys<'a>(map: &HashMap<String, Value>, allowed: &HashSet<&str>, model: &Model) -> Result<()> {
@@ -100,6 +105,7 @@ impl Object {
                 object_connect_map: Arc::new(TokioMutex::new(HashMap::new())),
                 object_disconnect_map: Arc::new(TokioMutex::new(HashMap::new())),
                 ignore_relation: None,
+                expected_version: Arc::new(Mutex::new(None)),
             })
         }
     }
@@ -172,7 +178,8 @@ impl Object {
                     }
                     // set_value_to_value_map
                     let value = value_map.get(key).unwrap();
-                    match Input::decode_field(value) {
+                    let input = if field.field_type().is_json() { Input::decode_json_field(value) } else { Input::decode_field(value) };
+                    match input {
                         AtomicUpdator(updator) => self.set_value_to_atomic_updator_map(key, updator),
                         SetValue(value) => {
                             // record previous value if needed
@@ -214,12 +221,12 @@ impl Object {
         Ok(())
     }
 
-    async fn check_model_write_permission<'a>(&self, path: impl AsRef<KeyPath<'a>>) -> Result<()> {
+    pub(crate) async fn check_model_write_permission<'a>(&self, path: impl AsRef<KeyPath<'a>>) -> Result<()> {
         let ctx = Ctx::initial_state_with_object(self.clone()).with_path(path.as_ref());
         self.model().can_mutate_pipeline().process_into_permission_result(ctx).await
     }
 
-    async fn check_model_read_permission<'a>(&self, _path: impl AsRef<KeyPath<'a>>) -> Result<()> {
+    pub(crate) async fn check_model_read_permission<'a>(&self, _path: impl AsRef<KeyPath<'a>>) -> Result<()> {
         let ctx = Ctx::initial_state_with_object(self.clone());
         let result = self.model().can_read_pipeline().process_into_permission_result(ctx).await;
         return result
@@ -304,7 +311,9 @@ impl Object {
         Ok(())
     }
 
-    pub(crate) fn set_from_database_result_value(&self, value: &Value, select: Option<&Value>, include: Option<&Value>) {
+    /// Fill this object with a record fetched by a connector. Keys of `value` are field,
+    /// property and relation names. The object is marked as saved afterwards.
+    pub fn set_from_database_result_value(&self, value: &Value, select: Option<&Value>, include: Option<&Value>) {
         let model = self.model();
         for (k, v) in value.as_hashmap().unwrap() {
             if let Some(_) = model.field(k) {
@@ -325,6 +334,8 @@ impl Object {
             }
         }
         self.set_select(select).unwrap();
+        // defaults are for new objects, input which updates this object must not reset fields
+        self.inner.is_initialized.store(true, Ordering::SeqCst);
         self.inner.is_new.store(false, Ordering::SeqCst);
         self.inner.is_modified.store(false, Ordering::SeqCst);
     }
@@ -663,11 +674,18 @@ impl Object {
         *self.inner.modified_fields.lock().unwrap() = HashSet::new();
     }
 
-    #[async_recursion]
+    /// Delete this object, or stamp it deleted if its model has soft delete.
     pub(crate) async fn delete_from_database(&self, session: Arc<dyn SaveSession>) -> Result<()> {
+        if self.model().soft_delete().is_some() {
+            self.soft_delete_from_database(session, Utc::now()).await
+        } else {
+            self.hard_delete_from_database(session).await
+        }
+    }
+
+    async fn check_delete_deny_rules(&self, with_deleted: bool) -> Result<()> {
         let model = self.model();
         let graph = self.graph();
-        // check deny first
         for relation in model.relations() {
             if relation.through().is_some() {
                 continue
@@ -675,7 +693,7 @@ impl Object {
             let (opposite_model, opposite_relation) = graph.opposite_relation(relation);
             if let Some(opposite_relation) = opposite_relation {
                 if opposite_relation.delete_rule() == Deny {
-                    let finder = self.intrinsic_where_unique_for_relation(relation);
+                    let finder = teon!({ "where": self.intrinsic_where_unique_for_relation(relation), "withDeleted": with_deleted });
                     let count = graph.count(opposite_model.name(), &finder).await.unwrap();
                     if count > 0 {
                         return Err(Error::deletion_denied(relation.name()));
@@ -683,8 +701,18 @@ impl Object {
                 }
             }
         }
+        Ok(())
+    }
+
+    #[async_recursion]
+    async fn hard_delete_from_database(&self, session: Arc<dyn SaveSession>) -> Result<()> {
+        let model = self.model();
+        let graph = self.graph();
+        // check deny first, soft deleted records still reference this object
+        self.check_delete_deny_rules(true).await?;
         // real delete
-        let connector = self.graph().connector();
+        let session = self.session_of_connector(session);
+        let connector = self.model().connector();
         connector.delete_object(self, session.clone()).await?;
         // nullify and cascade
         for relation in model.relations() {
@@ -700,21 +728,161 @@ impl Object {
                         if !opposite_relation.has_foreign_key() {
                             continue
                         }
-                        let finder = self.intrinsic_where_unique_for_relation(relation);
-                        graph.batch(opposite_model.name(), &finder, Action::from_u32(PROGRAM_CODE | DISCONNECT | (if relation.is_vec() { MANY } else { SINGLE })), ActionSource::ProgramCode, |object| async move {
+                        let finder = teon!({ "where": self.intrinsic_where_unique_for_relation(relation), "withDeleted": true });
+                        let action = Action::from_u32(PROGRAM_CODE | DISCONNECT | (if relation.is_vec() { MANY } else { SINGLE }));
+                        // records leave the result set once they're updated, fetch them all at once
+                        for object in graph.find_many_internal(opposite_model.name(), &finder, true, action, ActionSource::ProgramCode).await? {
                             for key in opposite_relation.fields() {
                                 object.set_value(key, Value::Null)?;
                             }
-                            object.save_with_session_and_path(self.graph().connector().new_save_session(), &path![]).await?;
-                            Ok(())
-                        }).await?;
+                            object.save_with_session_and_path(session.clone(), &path![]).await?;
+                        }
                     },
                     DeleteRule::Cascade => {
-                        let finder = self.intrinsic_where_unique_for_relation(relation);
-                        graph.batch(opposite_model.name(), &finder, Action::from_u32(PROGRAM_CODE | DELETE | (if relation.is_vec() { MANY } else { SINGLE })), ActionSource::ProgramCode, |object| async move {
-                            object.delete_from_database(self.graph().connector().new_save_session()).await?;
-                            Ok(())
-                        }).await?;
+                        let finder = teon!({ "where": self.intrinsic_where_unique_for_relation(relation), "withDeleted": true });
+                        let action = Action::from_u32(PROGRAM_CODE | DELETE | (if relation.is_vec() { MANY } else { SINGLE }));
+                        for object in graph.find_many_internal(opposite_model.name(), &finder, true, action, ActionSource::ProgramCode).await? {
+                            object.hard_delete_from_database(session.clone()).await?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Stamp this object deleted. Cascade relations stamp their soft delete records with the
+    /// same time so that they are restored together, records without soft delete are kept.
+    /// Nothing is nullified since this object stays in the database.
+    #[async_recursion]
+    async fn soft_delete_from_database(&self, session: Arc<dyn SaveSession>, deleted_at: DateTime<Utc>) -> Result<()> {
+        let model = self.model();
+        let graph = self.graph();
+        self.check_delete_deny_rules(false).await?;
+        self.set_value(model.soft_delete().unwrap(), Value::DateTime(deleted_at))?;
+        self.save_to_database(session.clone()).await?;
+        self.clear_state();
+        for relation in model.relations() {
+            if relation.through().is_some() {
+                continue
+            }
+            let (opposite_model, opposite_relation) = graph.opposite_relation(relation);
+            if let Some(opposite_relation) = opposite_relation {
+                if opposite_relation.delete_rule() != DeleteRule::Cascade || opposite_model.soft_delete().is_none() {
+                    continue
+                }
+                let finder = teon!({ "where": self.intrinsic_where_unique_for_relation(relation) });
+                let action = Action::from_u32(PROGRAM_CODE | DELETE | (if relation.is_vec() { MANY } else { SINGLE }));
+                for object in graph.find_many_internal(opposite_model.name(), &finder, true, action, ActionSource::ProgramCode).await? {
+                    object.soft_delete_from_database(session.clone(), deleted_at).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Clear the soft delete stamp of this object and of the records which were deleted
+    /// together with it through cascade relations.
+    #[async_recursion]
+    async fn restore_from_database(&self, session: Arc<dyn SaveSession>) -> Result<()> {
+        let model = self.model();
+        let graph = self.graph();
+        let deleted_at = self.get_value(model.soft_delete().unwrap())?;
+        if deleted_at.is_null() {
+            return Ok(());
+        }
+        self.set_value(model.soft_delete().unwrap(), Value::Null)?;
+        self.save_to_database(session.clone()).await?;
+        self.clear_state();
+        for relation in model.relations() {
+            if relation.through().is_some() {
+                continue
+            }
+            let (opposite_model, opposite_relation) = graph.opposite_relation(relation);
+            if let Some(opposite_relation) = opposite_relation {
+                let field = match opposite_model.soft_delete() {
+                    Some(field) if opposite_relation.delete_rule() == DeleteRule::Cascade => field,
+                    _ => continue,
+                };
+                let r#where = teon!({ "AND": [self.intrinsic_where_unique_for_relation(relation), { (field): deleted_at.clone() }] });
+                let finder = teon!({ "where": r#where, "onlyDeleted": true });
+                let action = Action::from_u32(PROGRAM_CODE | RESTORE | (if relation.is_vec() { MANY } else { SINGLE }));
+                for object in graph.find_many_internal(opposite_model.name(), &finder, true, action, ActionSource::ProgramCode).await? {
+                    object.restore_from_database(session.clone()).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the opposite model and relation if this object changes keys which the opposite
+    /// relation's foreign key references.
+    fn opposite_relation_with_modified_references(&self, relation: &Relation) -> Option<(&Model, &Relation)> {
+        if relation.has_foreign_key() || relation.has_join_table() {
+            return None;
+        }
+        let modified = {
+            let modified_fields = self.inner.modified_fields.lock().unwrap();
+            relation.fields().iter().any(|f| modified_fields.contains(f))
+        };
+        if !modified {
+            return None;
+        }
+        match self.graph().opposite_relation(relation) {
+            (opposite_model, Some(opposite_relation)) if opposite_relation.has_foreign_key() => Some((opposite_model, opposite_relation)),
+            _ => None,
+        }
+    }
+
+    fn previous_where_unique_for_relation(&self, relation: &Relation) -> Value {
+        let modified_fields = self.inner.modified_fields.lock().unwrap();
+        Value::HashMap(relation.iter().map(|(f, r)| {
+            let value = if modified_fields.contains(f) {
+                self.get_previous_value(f).unwrap()
+            } else {
+                self.get_value(f).unwrap()
+            };
+            (r.to_owned(), value)
+        }).collect())
+    }
+
+    async fn check_update_deny_rules(&self) -> Result<()> {
+        for relation in self.model().relations() {
+            if let Some((opposite_model, opposite_relation)) = self.opposite_relation_with_modified_references(relation) {
+                if opposite_relation.update_rule() == UpdateRule::Deny {
+                    let finder = teon!({ "where": self.previous_where_unique_for_relation(relation) });
+                    let count = self.graph().count(opposite_model.name(), &finder).await?;
+                    if count > 0 {
+                        return Err(Error::update_denied(relation.name()));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn perform_update_rules(&self, session: Arc<dyn SaveSession>) -> Result<()> {
+        let graph = self.graph();
+        for relation in self.model().relations() {
+            if let Some((opposite_model, opposite_relation)) = self.opposite_relation_with_modified_references(relation) {
+                let finder = teon!({ "where": self.previous_where_unique_for_relation(relation) });
+                let action = Action::from_u32(PROGRAM_CODE | UPDATE | (if relation.is_vec() { MANY } else { SINGLE }));
+                match opposite_relation.update_rule() {
+                    UpdateRule::Default => {}, // do nothing
+                    UpdateRule::Deny => {}, // done before
+                    UpdateRule::Nullify => {
+                        for object in graph.find_many_internal(opposite_model.name(), &finder, true, action, ActionSource::ProgramCode).await? {
+                            for key in opposite_relation.fields() {
+                                object.set_value(key, Value::Null)?;
+                            }
+                            object.save_with_session_and_path(session.clone(), &path![]).await?;
+                        }
+                    }
+                    UpdateRule::Cascade => {
+                        for object in graph.find_many_internal(opposite_model.name(), &finder, true, action, ActionSource::ProgramCode).await? {
+                            self.assign_linked_values_to_related_object(&object, opposite_relation);
+                            object.save_with_session_and_path(session.clone(), &path![]).await?;
+                        }
                     }
                 }
             }
@@ -724,12 +892,67 @@ impl Object {
 
     #[async_recursion]
     async fn save_to_database(&self, session: Arc<dyn SaveSession>) -> Result<()> {
-        let connector = self.graph().connector();
-        connector.save_object(self, session).await?;
+        let session = self.session_of_connector(session);
+        let connector = self.model().connector();
+        if !self.is_new() {
+            self.bump_version();
+        }
+        if let Err(err) = connector.save_object(self, session).await {
+            self.restore_version();
+            return Err(err);
+        }
+        *self.inner.expected_version.lock().unwrap() = None;
         self.clear_new_state();
         Ok(())
     }
 
+    /// New records of a model with `@version` start at version 1.
+    fn set_initial_version(&self) {
+        if let Some(field) = self.model().version_field() {
+            if self.get_value(field.name()).unwrap().is_null() {
+                let initial = if field.field_type().is_int32() { Value::I32(1) } else { Value::I64(1) };
+                self.set_value_to_value_map(field.name(), initial);
+            }
+        }
+    }
+
+    /// Move the version forward before an update. The version the object has, either fetched
+    /// or passed in by the client, is the one the update expects the record to be at.
+    fn bump_version(&self) {
+        if let Some(field) = self.model().version_field() {
+            let expected = self.get_value(field.name()).unwrap();
+            let next = match &expected {
+                Value::I32(version) => Value::I32(version + 1),
+                Value::I64(version) => Value::I64(version + 1),
+                _ => return,
+            };
+            self.inner.atomic_updator_map.lock().unwrap().remove(field.name());
+            self.set_value_to_value_map(field.name(), next);
+            *self.inner.expected_version.lock().unwrap() = Some(expected);
+        }
+    }
+
+    /// Move the version back after a failed update, so that saving again expects the version
+    /// the record is still at.
+    fn restore_version(&self) {
+        let expected = self.inner.expected_version.lock().unwrap().take();
+        if let (Some(field), Some(expected)) = (self.model().version_field(), expected) {
+            self.set_value_to_value_map(field.name(), expected);
+        }
+    }
+
+    /// The version an update expects the record to be at. Connectors only update the record if
+    /// it's still at this version, and return a conflict error otherwise.
+    pub(crate) fn expected_version(&self) -> Option<Value> {
+        self.inner.expected_version.lock().unwrap().clone()
+    }
+
+    /// Related objects are saved with the session of the object they're nested in. Inside a
+    /// transaction, switch to the session of this object's connector.
+    fn session_of_connector(&self, session: Arc<dyn SaveSession>) -> Arc<dyn SaveSession> {
+        self.graph().bound_save_session(self.model()).unwrap_or(session)
+    }
+
     fn before_save_callback_check(&self) -> Result<()> {
         let inside_before_callback = self.inner.inside_before_save_callback.load(Ordering::SeqCst);
         if inside_before_callback {
@@ -746,13 +969,22 @@ impl Object {
         // validate and save
         let is_modified = self.is_modified();
         if is_modified || is_new {
+            if is_new {
+                self.set_initial_version();
+            }
             // apply pipeline
             self.apply_on_save_pipeline_and_validate_required_fields(path).await?;
             self.trigger_before_save_callbacks(path).await?;
             // perform relation manipulations (has foreign key)
             self.perform_relation_manipulations(|r| r.has_foreign_key(), session.clone(), path).await?;
             if !self.model().r#virtual() {
+                if !is_new {
+                    self.check_update_deny_rules().await?;
+                }
                 self.save_to_database(session.clone()).await?;
+                if !is_new {
+                    self.perform_update_rules(session.clone()).await?;
+                }
             }
         } else {
             // perform relation manipulations (has foreign key)
@@ -769,8 +1001,9 @@ impl Object {
     }
 
     pub async fn save(&self) -> Result<()> {
-        let session = self.graph().connector().new_save_session();
-        self.save_with_session_and_path(session, &path![]).await
+        self.graph().transaction(|graph| async move {
+            self.save_with_session_and_path(graph.save_session(self.model()), &path![]).await
+        }).await
     }
 
     async fn trigger_before_delete_callbacks<'a>(&self, path: impl AsRef<KeyPath<'a>>) -> Result<()> {
@@ -810,14 +1043,41 @@ impl Object {
 
     pub async fn delete(&self) -> Result<()> {
         self.trigger_before_delete_callbacks(path![]).await?;
-        self.delete_from_database(self.graph().connector().new_save_session()).await
+        self.graph().transaction(|graph| async move {
+            self.delete_from_database(graph.save_session(self.model())).await
+        }).await
     }
 
     pub(crate) async fn delete_internal<'a>(&self, path: impl AsRef<KeyPath<'a>>) -> Result<()> {
         self.check_model_write_permission(path.as_ref()).await?;
         self.trigger_before_delete_callbacks(path.as_ref()).await?;
-        self.delete_from_database(self.graph().connector().new_save_session()).await?;
-        self.trigger_after_delete_callbacks(path.as_ref()).await
+        self.graph().transaction(|graph| async move {
+            self.delete_from_database(graph.save_session(self.model())).await?;
+            self.trigger_after_delete_callbacks(path.as_ref()).await
+        }).await
+    }
+
+    /// Delete this object in an ongoing save like `delete_internal` does, with its permission
+    /// checked and its callbacks triggered.
+    async fn delete_with_session_and_path(&self, session: Arc<dyn SaveSession>, path: &KeyPath<'_>) -> Result<()> {
+        self.check_model_write_permission(path).await?;
+        self.trigger_before_delete_callbacks(path).await?;
+        self.delete_from_database(session).await?;
+        self.trigger_after_delete_callbacks(path).await
+    }
+
+    /// Delete this object for real, even if its model has soft delete.
+    pub(crate) async fn purge(&self) -> Result<()> {
+        self.graph().transaction(|graph| async move {
+            self.hard_delete_from_database(graph.save_session(self.model())).await
+        }).await
+    }
+
+    pub(crate) async fn restore_internal<'a>(&self, path: impl AsRef<KeyPath<'a>>) -> Result<()> {
+        self.check_model_write_permission(path.as_ref()).await?;
+        self.graph().transaction(|graph| async move {
+            self.restore_from_database(graph.save_session(self.model())).await
+        }).await
     }
 
     #[async_recursion]
@@ -899,7 +1159,7 @@ impl Object {
         &self.inner.graph
     }
 
-    pub(crate) fn identifier(&self) -> Value {
+    pub fn identifier(&self) -> Value {
         let model = self.model();
         let mut identifier: HashMap<String, Value> = HashMap::new();
         for item in model.primary_index().items() {
@@ -962,8 +1222,7 @@ impl Object {
                         } else if relation.has_foreign_key() {
                             self.remove_linked_values_from_related_relation(relation);
                         } else {
-                            object.remove_linked_values_from_related_relation_on_related_object(relation, &object);
-                            object.save_with_session_and_path(session.clone(), path).await?;
+                            self.disconnect_related_object(relation, object, session.clone(), path).await?;
                         }
                     }
                 }
@@ -1137,6 +1396,21 @@ impl Object {
         Value::HashMap(relation.iter().map(|(f, r)| (r.to_owned(), self.get_value(f).unwrap())).collect())
     }
 
+    /// Disconnect an object which holds the foreign key of this relation. It's deleted or its
+    /// foreign key is nullified according to its `onDisconnect` rule.
+    async fn disconnect_related_object(&self, relation: &Relation, object: &Object, session: Arc<dyn SaveSession>, path: &KeyPath<'_>) -> Result<()> {
+        let opposite_relation = self.graph().opposite_relation(relation).1;
+        let disconnect_rule = opposite_relation.map(|r| r.disconnect_rule()).unwrap_or(DisconnectRule::Default);
+        if disconnect_rule == DisconnectRule::Delete {
+            return object.delete_with_session_and_path(session.clone(), path).await;
+        }
+        if opposite_relation.map(|r| r.is_required()).unwrap_or(false) {
+            return Err(Error::unexpected_input_value_with_reason("Cannot disconnect required relation.", path));
+        }
+        object.remove_linked_values_from_related_relation_on_related_object(relation, object);
+        object.save_with_session_and_path(session.clone(), path).await
+    }
+
     async fn nested_disconnect_relation_object_object(&self, relation: &Relation, object: &Object, session: Arc<dyn SaveSession>, path: &KeyPath<'_>) -> Result<()> {
         if !relation.is_vec() && relation.is_required() {
             return Err(Error::unexpected_input_value_with_reason("Cannot disconnect required relation.", path));
@@ -1144,8 +1418,7 @@ impl Object {
         if relation.has_foreign_key() {
             self.remove_linked_values_from_related_relation(relation);
         } else {
-            object.remove_linked_values_from_related_relation_on_related_object(relation, &object);
-            object.save_with_session_and_path(session.clone(), path).await?;
+            self.disconnect_related_object(relation, object, session.clone(), path).await?;
         }
         Ok(())
     }
@@ -1163,8 +1436,7 @@ impl Object {
                 Ok(object) => object,
                 Err(_) => return Err(Error::unexpected_input_value_with_reason("object is not found", path)),
             };
-            object.remove_linked_values_from_related_relation_on_related_object(relation, &object);
-            object.save_with_session_and_path(session.clone(), path).await?;
+            self.disconnect_related_object(relation, &object, session.clone(), path).await?;
         }
         Ok(())
     }
@@ -1206,8 +1478,7 @@ impl Object {
                 Ok(object) => object,
                 Err(_) => return Err(Error::unexpected_input_value_with_reason("Object is not found.", path)),
             };
-            object.remove_linked_values_from_related_relation_on_related_object(relation, &object);
-            object.save_with_session_and_path(session.clone(), path).await?;
+            self.disconnect_related_object(relation, &object, session.clone(), path).await?;
         }
         Ok(())
     }
@@ -1412,7 +1683,8 @@ impl Object {
         if let Some(select) = select {
             finder.as_hashmap_mut().unwrap().insert("select".to_string(), select.clone());
         }
-        graph.find_unique_internal(self.model().name(), &finder, false, self.action(), self.action_source().clone()).await
+        // read from the primary, a replica may not have this object's latest write yet
+        graph.find_unique_internal(self.model().name(), &finder, true, self.action(), self.action_source().clone()).await
     }
 
     pub async fn force_set_relation_objects(&self, key: &str, objects: Vec<Object>) -> () {
@@ -1531,7 +1803,7 @@ impl Object {
         }
     }
 
-    pub(crate) fn keys_for_save(&self) -> Vec<&str> {
+    pub fn keys_for_save(&self) -> Vec<&str> {
         if self.is_new() {
             self.model().save_keys().iter().map(|k| k.as_str()).collect()
         } else {
@@ -1546,7 +1818,7 @@ impl Object {
         self.inner.action
     }
 
-    pub(crate) fn action_source(&self) -> &ActionSource {
+    pub fn action_source(&self) -> &ActionSource {
         &self.inner.action_source
     }
 
@@ -1587,3 +1859,67 @@ impl PartialEq for Object {
 
 unsafe impl Send for Object { }
 unsafe impl Sync for Object { }
+
+#[cfg(test)]
+mod tests {
+    use serial_test::serial;
+    use crate::core::app::testing::sqlite_app;
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model Author {
+  @id @default($cuid)
+  id: String
+  @relation(fields: .id, references: .authorId)
+  posts: Post[]
+}
+
+@beforeDelete($invalid)
+model Post {
+  @id @default($cuid)
+  id: String
+  authorId: String?
+  @relation(fields: .authorId, references: .id, onDisconnect: .delete)
+  author: Author?
+}
+"#;
+
+    #[tokio::test]
+    #[serial]
+    async fn disconnect_delete_runs_delete_callbacks() {
+        let app = sqlite_app(SCHEMA).await;
+        let graph = app.graph();
+        let author = graph.create_object("Author", teon!({})).await.unwrap();
+        author.save().await.unwrap();
+        let post = graph.create_object("Post", teon!({"authorId": author.get_value("id").unwrap()})).await.unwrap();
+        post.save().await.unwrap();
+        author.set_teon(&teon!({"posts": {"disconnect": {"id": post.get_value("id").unwrap()}}})).await.unwrap();
+        assert!(author.save().await.is_err());
+        assert_eq!(graph.count("Post", &teon!({})).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn failed_save_restores_version() {
+        let app = sqlite_app(r#"
+model Item {
+  @id @default($cuid)
+  id: String
+  @unique
+  name: String
+  @version
+  version: Int
+}
+"#).await;
+        let graph = app.graph();
+        graph.create_object("Item", teon!({"name": "a"})).await.unwrap().save().await.unwrap();
+        let item = graph.create_object("Item", teon!({"name": "b"})).await.unwrap();
+        item.save().await.unwrap();
+        item.set_teon(&teon!({"name": "a"})).await.unwrap();
+        assert!(item.save().await.is_err());
+        assert_eq!(item.get_value("version").unwrap(), Value::I32(1));
+        item.set_teon(&teon!({"name": "c"})).await.unwrap();
+        item.save().await.unwrap();
+        assert_eq!(item.get_value("version").unwrap(), Value::I32(2));
+    }
+}
@@ -0,0 +1,6 @@
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DisconnectRule {
+    Default,
+    Nullify,
+    Delete,
+}
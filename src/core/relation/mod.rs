@@ -10,6 +10,8 @@ use once_cell::sync::Lazy;
 use crate::core::field::Field;
 use crate::core::field::optionality::Optionality;
 use crate::core::relation::delete_rule::DeleteRule;
+use crate::core::relation::disconnect_rule::DisconnectRule;
+use crate::core::relation::update_rule::UpdateRule;
 
 #[derive(Debug, Clone)]
 pub struct Relation {
@@ -23,6 +25,8 @@ pub struct Relation {
     pub(self) fields: Vec<String>,
     pub(self) references: Vec<String>,
     pub(self) delete_rule: DeleteRule,
+    pub(self) update_rule: UpdateRule,
+    pub(self) disconnect_rule: DisconnectRule,
     pub(self) has_foreign_key: bool,
 }
 
@@ -40,6 +44,8 @@ impl Relation {
             fields: Vec::new(),
             references: Vec::new(),
             delete_rule: DeleteRule::Default,
+            update_rule: UpdateRule::Default,
+            disconnect_rule: DisconnectRule::Default,
             has_foreign_key: false,
         }
     }
@@ -118,10 +124,36 @@ impl Relation {
         self.references.get(0).unwrap()
     }
 
+    pub(crate) fn set_delete_rule(&mut self, rule: DeleteRule) {
+        self.delete_rule = rule;
+    }
+
     pub(crate) fn delete_rule(&self) -> DeleteRule {
         self.delete_rule
     }
 
+    pub(crate) fn set_update_rule(&mut self, rule: UpdateRule) {
+        self.update_rule = rule;
+    }
+
+    pub(crate) fn update_rule(&self) -> UpdateRule {
+        self.update_rule
+    }
+
+    pub(crate) fn set_disconnect_rule(&mut self, rule: DisconnectRule) {
+        self.disconnect_rule = rule;
+    }
+
+    pub(crate) fn disconnect_rule(&self) -> DisconnectRule {
+        self.disconnect_rule
+    }
+
+    /// Whether a foreign key constraint is created for this relation in SQL databases. Only
+    /// relations which declare both `onDelete` and `onUpdate` have one.
+    pub(crate) fn has_foreign_key_constraint(&self) -> bool {
+        self.has_foreign_key && self.delete_rule != DeleteRule::Default && self.update_rule != UpdateRule::Default
+    }
+
     pub(crate) fn has_foreign_key(&self) -> bool {
         self.has_foreign_key
     }
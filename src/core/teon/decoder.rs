@@ -12,6 +12,7 @@ use serde_json::{Value as JsonValue, Map as JsonMap};
 use crate::core::action::{Action, CONNECT, CONNECT_OR_CREATE, CREATE, CREATE_MANY_HANDLER, DELETE, DISCONNECT, FIND_MANY_HANDLER, FIND_UNIQUE_HANDLER, MANY, NESTED, SET, SINGLE, UPDATE, UPSERT};
 use crate::core::error::Error;
 use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+use crate::core::handler::Handler;
 use crate::core::model::Model;
 use crate::core::result::Result;
 use crate::core::graph::Graph;
@@ -30,6 +31,47 @@ impl Decoder {
         Self::decode_action_arg_at_path(model, graph, action, json_value, path![])
     }
 
+    /// Decode the body of a custom handler request. Each key is decoded with its declared type.
+    pub(crate) fn decode_handler_input(graph: &Graph, handler: &Handler, json_value: &JsonValue) -> Result<Value> {
+        let path = path![];
+        let json_map = if let Some(json_map) = json_value.as_object() {
+            json_map
+        } else {
+            return Err(Error::unexpected_input_type("object", path));
+        };
+        Self::check_json_keys(json_map, &handler.input().iter().map(|(k, _)| k.as_str()).collect(), &path)?;
+        let mut retval: HashMap<String, Value> = HashMap::new();
+        for (key, r#type) in handler.input() {
+            let path = &path + key;
+            match json_map.get(key) {
+                Some(json_value) => {
+                    retval.insert(key.clone(), Self::decode_handler_value(graph, &r#type.field_type(graph), r#type.optional, json_value, path)?);
+                }
+                None => if !r#type.optional {
+                    return Err(Error::missing_required_input(path));
+                }
+            }
+        }
+        Ok(Value::HashMap(retval))
+    }
+
+    fn decode_handler_value<'a>(graph: &Graph, r#type: &FieldType, optional: bool, json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
+        let path = path.as_ref();
+        if optional && json_value.is_null() {
+            return Ok(Value::Null);
+        }
+        match r#type {
+            FieldType::Object(model) => Self::decode_object_at_path(graph.model(model).unwrap(), graph, json_value, path),
+            FieldType::Vec(inner) if matches!(inner.field_type(), FieldType::Object(_)) => match json_value.as_array() {
+                Some(a) => Ok(Value::Vec(a.iter().enumerate().map(|(i, v)| {
+                    Self::decode_handler_value(graph, inner.field_type(), inner.is_optional(), v, path + i)
+                }).collect::<Result<Vec<Value>>>()?)),
+                None => Err(Error::unexpected_input_type("array", path))
+            },
+            _ => Self::decode_value_for_field_type(graph, r#type, optional, json_value, path),
+        }
+    }
+
     fn decode_object_at_path<'a>(model: &Model, graph: &Graph, json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
         let path = path.as_ref();
         let json_map = if let Some(json_map) = json_value.as_object() {
@@ -87,9 +129,18 @@ impl Decoder {
                 "create" => { retval.insert(key.to_owned(), if action.to_u32() == CREATE_MANY_HANDLER { Self::decode_enumerate(value, path, |v, p: &KeyPath| Self::decode_create(model, graph, v, p))? } else { Self::decode_create(model, graph, value, path)? } ); }
                 "update" => { retval.insert(key.to_owned(), Self::decode_update(model, graph, value, path)?); }
                 "credentials" => { retval.insert(key.to_owned(), Self::decode_credentials(model, graph, value, path)?); }
+                "refreshToken" => { retval.insert(key.to_owned(), Self::decode_string(value, path)?); }
+                "withDeleted" | "onlyDeleted" => if model.soft_delete().is_some() {
+                    retval.insert(key.to_owned(), Self::decode_bool(value, path)?);
+                } else {
+                    return Err(Error::unexpected_input_key(key, path));
+                },
                 _ => unreachable!()
             }
         }
+        if retval.get("withDeleted").is_some_and(|v| v.as_bool() == Some(true)) && retval.get("onlyDeleted").is_some_and(|v| v.as_bool() == Some(true)) {
+            return Err(Error::unexpected_input_key("onlyDeleted", path + "onlyDeleted"));
+        }
         if retval.contains_key("skip") || retval.contains_key("take") {
             for k in ["pageSize", "pageNumber"] {
                 if retval.contains_key(k) {
@@ -657,6 +708,15 @@ impl Decoder {
         }
     }
 
+    fn decode_string<'a>(json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
+        let path = path.as_ref();
+        if let Some(s) = json_value.as_str() {
+            Ok(Value::String(s.to_owned()))
+        } else {
+            Err(Error::unexpected_input_type("string", path))
+        }
+    }
+
     fn decode_distinct<'a>(model: &Model, json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
         let path = path.as_ref();
         if let Some(_) = json_value.as_str() {
@@ -719,9 +779,13 @@ impl Decoder {
             return Err(Error::unexpected_input_type("object", path));
         };
         let mut retval: HashMap<String, Value> = hashmap!{};
+        let mut has_search = false;
         for (key, value) in json_map {
             let key = key.as_str();
             match key {
+                "AND" | "OR" | "NOT" if Self::has_full_text_search(value) => {
+                    return Err(Error::unexpected_input_value_with_reason("Full-text search can't be nested.", path + key));
+                }
                 "AND" | "OR" => {
                     let path = &(path + key);
                     match value {
@@ -755,6 +819,15 @@ impl Decoder {
                         return Err(Error::unexpected_input_key(key, path));
                     }
                     if let Some(field) = model.field(key) {
+                        if value.get("search").is_some() {
+                            if model.full_text_index(key).is_none() {
+                                return Err(Error::unexpected_input_key("search", path + "search"));
+                            }
+                            if has_search {
+                                return Err(Error::unexpected_input_value_with_reason("Only one full-text search is allowed.", path + "search"));
+                            }
+                            has_search = true;
+                        }
                         let optional = field.optionality.is_optional();
                         retval.insert(key.to_owned(), Self::decode_where_for_field(graph, field.field_type(), optional, value, path)?);
                     } else if let Some(relation) = model.relation(key) {
@@ -766,6 +839,14 @@ impl Decoder {
         Ok(Value::HashMap(retval))
     }
 
+    fn has_full_text_search(json_value: &JsonValue) -> bool {
+        match json_value {
+            JsonValue::Object(map) => map.values().any(|v| v.get("search").is_some()),
+            JsonValue::Array(vec) => vec.iter().any(Self::has_full_text_search),
+            _ => false,
+        }
+    }
+
     fn decode_where_unique<'a>(model: &Model, graph: &Graph, json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
         let path = path.as_ref();
         let json_map = if let Some(json_map) = json_value.as_object() {
@@ -806,12 +887,21 @@ impl Decoder {
                     "not" => {
                         retval.insert(key.to_owned(), Self::decode_where_for_field(graph, r#type, optional, value, path)?);
                     }
-                    "gt" | "gte" | "lt" | "lte" | "contains" | "startsWith" | "endsWith" | "matches" => {
+                    "gt" | "gte" | "lt" | "lte" | "contains" | "startsWith" | "endsWith" | "matches" | "search" => {
                         retval.insert(key.to_owned(), Self::decode_value_for_field_type(graph, r#type, false, value, path)?);
                     }
                     "in" | "notIn" => {
                         retval.insert(key.to_owned(), Self::decode_value_array_for_field_type(graph, r#type, false, value, path)?);
                     }
+                    "path" => {
+                        retval.insert(key.to_owned(), Self::decode_json_path(value, path)?);
+                    }
+                    "arrayContains" => {
+                        retval.insert(key.to_owned(), Self::decode_value_for_field_type(graph, r#type, false, value, path)?);
+                    }
+                    "stringContains" => {
+                        retval.insert(key.to_owned(), Self::decode_value_for_field_type(graph, &FieldType::String, false, value, path)?);
+                    }
                     "mode" => match value.as_str() {
                         Some(s) => if s == "caseInsensitive" {
                             retval.insert(key.to_owned(), Value::String("caseInsensitive".to_owned()));
@@ -890,9 +980,14 @@ impl Decoder {
 
     fn decode_value_or_updator_for_field_type<'a>(graph: &Graph, r#type: &FieldType, optional: bool, json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>, set_only: bool) -> Result<Value> {
         let path = path.as_ref();
-        if let Some(json_map) = json_value.as_object() {
+        let updators = if set_only { r#type.default_updators() } else { r#type.updators() };
+        // other objects are values of json fields
+        let is_updator = |json_map: &&JsonMap<String, JsonValue>| {
+            !r#type.is_json() || (json_map.len() == 1 && json_map.keys().all(|k| updators.contains(k.as_str())))
+        };
+        if let Some(json_map) = json_value.as_object().filter(is_updator) {
             Self::check_length_1(json_value, path)?;
-            Self::check_json_keys(json_map, if set_only { r#type.default_updators() } else { r#type.updators() }, path)?;
+            Self::check_json_keys(json_map, updators, path)?;
             Ok(Value::HashMap(json_map.iter().map(|(k, v)| {
                 let k = k.as_str();
                 let path = path + k;
@@ -903,6 +998,11 @@ impl Decoder {
                         let element_field = r#type.element_field().unwrap();
                         Self::decode_value_for_field_type(graph, element_field.field_type(), element_field.is_optional(), v, path)?
                     }
+                    "merge" => if v.is_object() {
+                        Self::decode_value_for_field_type(graph, r#type, false, v, path)?
+                    } else {
+                        return Err(Error::unexpected_input_type("object", path));
+                    }
                     _ => panic!("Unknown updator name.")
                 }))
             }).collect::<Result<HashMap<String, Value>>>()?))
@@ -1005,9 +1105,30 @@ impl Decoder {
                 },
                 None => Err(Error::unexpected_input_type("object", path))
             }
+            FieldType::Json => if json_value.is_null() {
+                Err(Error::unexpected_input_type("json value", path))
+            } else {
+                Ok(json_value.into())
+            }
             FieldType::Object(_) => panic!("Object input is not implemented yet.")
         }
     }
+
+    fn decode_json_path<'a>(json_value: &JsonValue, path: impl AsRef<KeyPath<'a>>) -> Result<Value> {
+        let path = path.as_ref();
+        match json_value.as_array() {
+            Some(array) => Ok(Value::Vec(array.iter().enumerate().map(|(i, v)| {
+                if let Some(s) = v.as_str() {
+                    Ok(Value::String(s.to_owned()))
+                } else if let Some(n) = v.as_u64() {
+                    Ok(Value::I64(n as i64))
+                } else {
+                    Err(Error::unexpected_input_type("string or index", path + i))
+                }
+            }).collect::<Result<Vec<Value>>>()?)),
+            None => Err(Error::unexpected_input_type("array", path)),
+        }
+    }
 }
 
 static NESTED_UPDATE_INPUT_KEYS: Lazy<HashSet<&str>> = Lazy::new(|| {
@@ -78,3 +78,21 @@ impl Into<JsonValue> for &Value {
         self.clone().into()
     }
 }
+
+/// Plain JSON, as stored in `Json` fields. Objects keep their key order.
+impl From<&JsonValue> for Value {
+    fn from(json_value: &JsonValue) -> Self {
+        match json_value {
+            JsonValue::Null => Value::Null,
+            JsonValue::Bool(b) => Value::Bool(*b),
+            JsonValue::Number(n) => if let Some(i) = n.as_i64() {
+                Value::I64(i)
+            } else {
+                Value::F64(n.as_f64().unwrap())
+            },
+            JsonValue::String(s) => Value::String(s.clone()),
+            JsonValue::Array(a) => Value::Vec(a.iter().map(|v| v.into()).collect()),
+            JsonValue::Object(o) => Value::IndexMap(o.iter().map(|(k, v)| (k.clone(), v.into())).collect()),
+        }
+    }
+}
@@ -4,7 +4,7 @@
 /// ```
 /// # use teo::prelude::teon;
 /// #
-/// let value = teon!{
+/// let value = teon!({
 ///     "code": 200,
 ///     "success": true,
 ///     "payload": {
@@ -13,7 +13,7 @@
 ///             "teon"
 ///         ]
 ///     }
-/// };
+/// });
 /// ```
 ///
 #[macro_export]
@@ -27,12 +27,12 @@ macro_rules! teon {
 
     // Done with trailing comma.
     (@array [$($elems:expr,)*]) => {
-        crate::teon_vec![$($elems,)*]
+        $crate::teon_vec![$($elems,)*]
     };
 
     // Done without trailing comma.
     (@array [$($elems:expr),*]) => {
-        crate::teon_vec![$($elems),*]
+        $crate::teon_vec![$($elems),*]
     };
 
     // Next element is `null`.
@@ -2,6 +2,7 @@ use crate::core::graph::Graph;
 
 pub(crate) async fn generate_filters_cs(_graph: &Graph) -> String {
     format!(r#"using System;
+using System.Text.Json;
 
 namespace Teo {{
     public class ObjectIdFilter {{
@@ -166,6 +167,7 @@ namespace Teo {{
         public string? StartsWith {{ get; set; }}
         public string? EndsWith {{ get; set; }}
         public string? Matches {{ get; set; }}
+        public string? Search {{ get; set; }}
         public OneOf<string, StringFilter>? Not {{ get; set; }}
 
         public StringFilter(
@@ -180,6 +182,7 @@ namespace Teo {{
             string? startsWith = null,
             string? endsWith = null,
             string? matches = null,
+            string? search = null,
             OneOf<string, StringFilter>? not = null
         ) {{
             Equals = equals;
@@ -193,6 +196,7 @@ namespace Teo {{
             StartsWith = startsWith;
             EndsWith = endsWith;
             Matches = matches;
+            Search = search;
             Not = not;
         }}
     }}
@@ -209,6 +213,7 @@ namespace Teo {{
         public string? StartsWith {{ get; set; }}
         public string? EndsWith {{ get; set; }}
         public string? Matches {{ get; set; }}
+        public string? Search {{ get; set; }}
         public OneOf<Optional<string>, StringNullableFilter>? Not {{ get; set; }}
 
         public StringNullableFilter(
@@ -223,6 +228,7 @@ namespace Teo {{
             string? startsWith = null,
             string? endsWith = null,
             string? matches = null,
+            string? search = null,
             OneOf<Optional<string>, StringNullableFilter>? not = null
         ) {{
             Equals = equals;
@@ -236,6 +242,7 @@ namespace Teo {{
             StartsWith = startsWith;
             EndsWith = endsWith;
             Matches = matches;
+            Search = search;
             Not = not;
         }}
     }}
@@ -501,6 +508,44 @@ namespace Teo {{
             Length = length;
         }}
     }}
+
+    public class JsonFilter {{
+        public object[]? Path {{ get; set; }}
+        public new JsonElement? Equals {{ get; set; }}
+        public JsonElement? ArrayContains {{ get; set; }}
+        public string? StringContains {{ get; set; }}
+
+        public JsonFilter(
+            object[]? path = null,
+            JsonElement? equals = null,
+            JsonElement? arrayContains = null,
+            string? stringContains = null
+        ) {{
+            Path = path;
+            Equals = equals;
+            ArrayContains = arrayContains;
+            StringContains = stringContains;
+        }}
+    }}
+
+    public class JsonNullableFilter {{
+        public object[]? Path {{ get; set; }}
+        public new Optional<JsonElement>? Equals {{ get; set; }}
+        public JsonElement? ArrayContains {{ get; set; }}
+        public string? StringContains {{ get; set; }}
+
+        public JsonNullableFilter(
+            object[]? path = null,
+            Optional<JsonElement>? equals = null,
+            JsonElement? arrayContains = null,
+            string? stringContains = null
+        ) {{
+            Path = path;
+            Equals = equals;
+            ArrayContains = arrayContains;
+            StringContains = stringContains;
+        }}
+    }}
 }}
 "#)
 }
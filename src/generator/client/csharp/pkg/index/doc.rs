@@ -3,6 +3,7 @@ use crate::core::action::{Action, FIND_MANY_HANDLER, FIND_FIRST_HANDLER, FIND_UN
 use crate::generator::lib::cases::{field_description, field_localized_name, model_api_object_description, model_localized_name, model_localized_name_word_case, relation_description, relation_localized_name};
 use crate::core::field::Field;
 use crate::core::graph::Graph;
+use crate::core::handler::Handler;
 use crate::core::model::Model;
 use crate::core::relation::Relation;
 
@@ -94,6 +95,23 @@ pub(crate) fn action_doc(name: &str, r#type: Action, model: &Model) -> String {
 "#)
 }
 
+pub(crate) fn handler_doc(name: &str, handler: &Handler, model: &Model) -> String {
+    let model_name = model.name();
+    let model_name_camel_case = model_name.to_camel_case();
+    let handler_name = handler.name();
+    let handler_name_camel_case = handler_name.to_camel_case();
+    let main_doc = format!("{} {}", handler_name.to_word_case(), model_localized_name_word_case(model)).to_sentence_case();
+    format!(r#"/**
+ * {main_doc}.
+ * @param {{{model_name}{handler_name}Args}} args - Input of the {handler_name} handler.
+ * @example
+ * const result = await {name}.{model_name_camel_case}.{handler_name_camel_case}({{
+ *     // input of the {handler_name} handler
+ * }})
+ */
+"#)
+}
+
 pub(crate) fn select_doc(model: &Model) -> String {
     let model_word = model.name().to_word_case();
     format!(r#"/**
@@ -122,6 +140,17 @@ pub(crate) fn credentials_doc(model: &Model, action: Action) -> String {
  */"#)
 }
 
+pub(crate) fn refresh_token_doc(action: Action) -> String {
+    let text = if action.handler_requires_refresh_token() {
+        "The refresh token to exchange for new tokens."
+    } else {
+        "The refresh token to revoke as well."
+    };
+    format!(r#"/**
+ * {text}
+ */"#)
+}
+
 pub(crate) fn unique_connect_doc(model: &Model) -> String {
     let object = model_localized_name_word_case(model);
     format!(r#"/**
@@ -1,8 +1,9 @@
 use inflector::Inflector;
 use crate::core::action::{Action, CREATE_HANDLER, FIND_FIRST_HANDLER, ResData, ResMeta, UPDATE_HANDLER, UPDATE_MANY_HANDLER, UPSERT_HANDLER};
 use crate::core::app::conf::ClientGeneratorConf;
-use crate::core::field::r#type::FieldTypeOwner;
-use crate::generator::client::csharp::pkg::index::doc::{action_doc, action_group_doc, create_or_update_doc, credentials_doc, cursor_doc, field_doc, include_doc, nested_connect_doc, nested_create_doc, nested_create_or_connect_doc, nested_delete_doc, nested_disconnect_doc, nested_set_doc, nested_update_doc, nested_upsert_doc, order_by_doc, page_number_doc, page_size_doc, relation_doc, select_doc, skip_doc, take_doc, unique_connect_create_doc, unique_connect_doc, unique_where_doc, where_doc, where_doc_first};
+use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+use crate::core::handler::HandlerType;
+use crate::generator::client::csharp::pkg::index::doc::{action_doc, action_group_doc, create_or_update_doc, credentials_doc, cursor_doc, refresh_token_doc, field_doc, handler_doc, include_doc, nested_connect_doc, nested_create_doc, nested_create_or_connect_doc, nested_delete_doc, nested_disconnect_doc, nested_set_doc, nested_update_doc, nested_upsert_doc, order_by_doc, page_number_doc, page_size_doc, relation_doc, select_doc, skip_doc, take_doc, unique_connect_create_doc, unique_connect_doc, unique_where_doc, where_doc, where_doc_first};
 use crate::generator::client::csharp::r#type::ToCSharpType;
 
 use crate::core::graph::Graph;
@@ -99,6 +100,18 @@ impl CSharpClassBuilder {
     }
 }
 
+/// Model objects in handler inputs are accepted in the shape of their create inputs.
+fn handler_input_type(r#type: &HandlerType, graph: &Graph) -> String {
+    match r#type.field_type(graph) {
+        FieldType::Object(name) => format!("{name}CreateInput"),
+        FieldType::Vec(inner) => match inner.field_type() {
+            FieldType::Object(name) => format!("{name}CreateInput[]"),
+            _ => FieldType::Vec(inner).to_csharp_type(false),
+        },
+        field_type => field_type.to_csharp_type(false),
+    }
+}
+
 fn generate_model_create_nested_input(_graph: &Graph, model: &Model, without: Option<&str>, many: bool) -> String {
     let _get_set = get_set();
     let model_name = model.name();
@@ -554,6 +567,7 @@ fn generate_model_credentials_input(model: &Model) -> String {
 pub(crate) async fn generate_index_cs(graph: &Graph, _client: &ClientGeneratorConf) -> String {
     Code::new(0, 4, |c| {
         c.line("using System;");
+        c.line("using System.Text.Json;");
         c.line("using System.Text.Json.Serialization;");
         c.line("using System.Threading.Tasks;");
         c.empty_line();
@@ -974,6 +988,15 @@ pub(crate) async fn generate_index_cs(graph: &Graph, _client: &ClientGeneratorCo
                             j: None,
                         });
                     }
+                    if a.handler_accepts_refresh_token() {
+                        fields.push(CSharpClassField {
+                            n: "RefreshToken".to_owned(),
+                            t: "string".to_owned(),
+                            o: true,
+                            d: Some(refresh_token_doc(*a)),
+                            j: None,
+                        });
+                    }
                     let builder = CSharpClassBuilder {
                         name: format!("{model_name}{action_name}Args"),
                         fields,
@@ -982,12 +1005,29 @@ pub(crate) async fn generate_index_cs(graph: &Graph, _client: &ClientGeneratorCo
                     };
                     c.indented(builder.build());
                 });
+                m.handlers().iter().for_each(|h| {
+                    let handler_name = h.name();
+                    let fields = h.input().iter().map(|(key, r#type)| CSharpClassField {
+                        n: key.to_pascal_case(),
+                        t: handler_input_type(r#type, graph),
+                        o: r#type.optional,
+                        d: None,
+                        j: None,
+                    }).collect();
+                    let builder = CSharpClassBuilder {
+                        name: format!("{model_name}{handler_name}Args"),
+                        fields,
+                        indent_spaces: 4,
+                        indent_level: 0
+                    };
+                    c.indented(builder.build());
+                });
             });
             // delegates
             let object_name = "teo";
             c.empty_line();
             graph.models().iter().for_each(|m| {
-                if m.actions().len() > 0 {
+                if !m.actions().is_empty() || !m.handlers().is_empty() {
                     let model_name = m.name();
                     let model_var_name = model_name.to_camel_case();
                     let model_class_name = model_var_name.to_pascal_case();
@@ -1023,6 +1063,25 @@ pub(crate) async fn generate_index_cs(graph: &Graph, _client: &ClientGeneratorCo
                                 }, "}");
                             }
                         });
+                        m.handlers().iter().for_each(|h| {
+                            let handler_name = h.name();
+                            let handler_var_name = handler_name.to_pascal_case();
+                            let res_data = match h.output() {
+                                Some(r#type) => r#type.field_type(graph).to_csharp_type(r#type.optional),
+                                None => "JsonElement".to_owned(),
+                            };
+                            let args_type = format!("{model_name}{handler_name}Args");
+                            let (params, args) = if h.input().iter().all(|(_, t)| t.optional) {
+                                (format!("{args_type}? args = null"), "args ?? new()")
+                            } else {
+                                (format!("{args_type} args"), "args")
+                            };
+                            b.empty_line();
+                            b.doc(handler_doc(object_name, h, m));
+                            b.block(format!("public async Task<Response<{res_data}>> {handler_var_name}({params}, string? token = null) {{"), |b| {
+                                b.line(format!(r#"return await Request<Response<{res_data}>>("{model_url_segment_name}", "{handler_name}", {args}, token ?? _Token);"#));
+                            }, "}");
+                        });
                     }, "}");
                     c.empty_line();
                 }
@@ -1031,7 +1090,7 @@ pub(crate) async fn generate_index_cs(graph: &Graph, _client: &ClientGeneratorCo
             c.block(format!("public class Teo {{"), |b| {
                 b.empty_line();
                 graph.models().iter().for_each(|m| {
-                    if m.actions().len() > 0 {
+                    if !m.actions().is_empty() || !m.handlers().is_empty() {
                         let model_name = m.name();
                         let model_class_name = model_name.to_pascal_case();
                         b.doc(action_group_doc(object_name, m));
@@ -1041,7 +1100,7 @@ pub(crate) async fn generate_index_cs(graph: &Graph, _client: &ClientGeneratorCo
                 b.empty_line();
                 b.block("public Teo(string? token = null) {", |b| {
                     graph.models().iter().for_each(|m| {
-                        if m.actions().len() > 0 {
+                        if !m.actions().is_empty() || !m.handlers().is_empty() {
                             let model_name = m.name();
                             let model_class_name = model_name.to_pascal_case();
                             b.line(format!("{model_class_name} = new(token);"));
@@ -2,6 +2,8 @@ use crate::core::graph::Graph;
 
 pub(crate) async fn generate_operations_cs(_graph: &Graph) -> String {
     format!(r#"using System;
+using System.Collections.Generic;
+using System.Text.Json;
 
 namespace Teo {{
     public class ObjectIdFieldUpdateOperationsInput {{
@@ -141,5 +143,23 @@ namespace Teo {{
             Push = push;
         }}
     }}
+
+    public class JsonFieldUpdateOperationsInput {{
+        public JsonElement? Set {{ get; set; }}
+        public Dictionary<string, JsonElement>? Merge {{ get; set; }}
+        public JsonFieldUpdateOperationsInput(JsonElement? set = null, Dictionary<string, JsonElement>? merge = null) {{
+            Set = set;
+            Merge = merge;
+        }}
+    }}
+
+    public class NullableJsonFieldUpdateOperationsInput {{
+        public Optional<JsonElement>? Set {{ get; set; }}
+        public Dictionary<string, JsonElement>? Merge {{ get; set; }}
+        public NullableJsonFieldUpdateOperationsInput(Optional<JsonElement>? set = null, Dictionary<string, JsonElement>? merge = null) {{
+            Set = set;
+            Merge = merge;
+        }}
+    }}
 }}"#)
 }
\ No newline at end of file
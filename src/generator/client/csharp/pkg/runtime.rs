@@ -32,6 +32,7 @@ namespace Teo {{
 
     public struct TokenInfo {{
         public string Token {{ get; set; }}
+        public string? RefreshToken {{ get; set; }}
     }}
 
     public class TeoException : Exception {{
@@ -59,6 +59,7 @@ impl ToCSharpType for FieldType {
             FieldType::Vec(internal) => internal.field_type().to_csharp_type(internal.optionality.is_optional()) + "[]",
             FieldType::HashMap(_) => panic!(),
             FieldType::BTreeMap(_) => panic!(),
+            FieldType::Json => "JsonElement".to_string(),
             FieldType::Object(name) => name.to_string(),
         };
         if optional {
@@ -93,6 +94,8 @@ impl ToCSharpType for FieldType {
             },
             FieldType::HashMap(_) => panic!(),
             FieldType::BTreeMap(_) => panic!(),
+            // a bare json object would be read as a filter
+            FieldType::Json => format!("Json{nullable}Filter"),
             FieldType::Object(_name) => "Unimplemented".to_string(),
         }
     }
@@ -138,6 +141,7 @@ impl ToCSharpType for FieldType {
             },
             FieldType::HashMap(_) => panic!(),
             FieldType::BTreeMap(_) => panic!(),
+            FieldType::Json => format!("{prefix}JsonFieldUpdateOperationsInput"),
             FieldType::Object(_name) => "Unimplemented".to_string(),
         }
     }
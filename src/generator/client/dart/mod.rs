@@ -1,10 +1,19 @@
+pub mod pkg;
+pub mod r#type;
+
 use async_trait::async_trait;
 use crate::core::app::conf::ClientGeneratorConf;
+use crate::core::graph::Graph;
 use crate::generator::client::ClientGenerator;
+use crate::generator::client::dart::pkg::gitignore::generate_gitignore_dart;
+use crate::generator::client::dart::pkg::lib::filter_dart::generate_filter_dart;
+use crate::generator::client::dart::pkg::lib::index_dart::generate_index_dart;
+use crate::generator::client::dart::pkg::lib::operation_dart::generate_operation_dart;
+use crate::generator::client::dart::pkg::lib::runtime_dart::generate_runtime_dart;
+use crate::generator::client::dart::pkg::pubspec_yaml::{generate_pubspec_yaml, update_pubspec_yaml};
+use crate::generator::client::dart::pkg::readme::generate_readme_dart;
 use crate::generator::lib::generator::Generator;
 
-use crate::prelude::Graph;
-
 pub(crate) struct DartClientGenerator { }
 
 impl DartClientGenerator {
@@ -14,18 +23,31 @@ impl DartClientGenerator {
 #[async_trait]
 impl ClientGenerator for DartClientGenerator {
     fn module_directory_in_package(&self, _client: &ClientGeneratorConf) -> String {
-        todo!()
+        "lib".to_owned()
     }
 
-    async fn generate_module_files(&self, _graph: &Graph, _client: &ClientGeneratorConf, _generator: &Generator) -> std::io::Result<()> {
-        todo!()
+    async fn generate_module_files(&self, graph: &Graph, client: &ClientGeneratorConf, generator: &Generator) -> std::io::Result<()> {
+        generator.ensure_root_directory().await?;
+        generator.clear_root_directory().await?;
+        generator.generate_file("runtime.dart", generate_runtime_dart(graph, client).await).await?;
+        generator.generate_file("filter.dart", generate_filter_dart(graph).await).await?;
+        generator.generate_file("operation.dart", generate_operation_dart(graph).await).await
     }
 
-    async fn generate_package_files(&self, _graph: &Graph, _client: &ClientGeneratorConf, _generator: &Generator) -> std::io::Result<()> {
-        todo!()
+    async fn generate_package_files(&self, _graph: &Graph, _client: &ClientGeneratorConf, generator: &Generator) -> std::io::Result<()> {
+        generator.ensure_root_directory().await?;
+        generator.generate_file_if_not_exist(".gitignore", generate_gitignore_dart()).await?;
+        generator.generate_file_if_not_exist("README.md", generate_readme_dart(generator.get_base_dir())).await?;
+        if generator.generate_file_if_not_exist("pubspec.yaml", generate_pubspec_yaml(generator.get_base_dir())).await? {
+            // if exist, update pubspec.yaml with a patch version
+            let yaml_data = std::fs::read_to_string(generator.get_file_path("pubspec.yaml"))
+                .expect("Unable to read pubspec.yaml");
+            generator.generate_file("pubspec.yaml", update_pubspec_yaml(yaml_data)).await?;
+        }
+        Ok(())
     }
 
-    async fn generate_main(&self, _graph: &Graph, _client: &ClientGeneratorConf, _generator: &Generator) -> std::io::Result<()> {
-        todo!()
+    async fn generate_main(&self, graph: &Graph, client: &ClientGeneratorConf, generator: &Generator) -> std::io::Result<()> {
+        generator.generate_file("index.dart", generate_index_dart(graph, client).await).await
     }
 }
@@ -0,0 +1,3 @@
+pub(crate) fn generate_gitignore_dart() -> String {
+    ".dart_tool/\n.packages\nbuild/\npubspec.lock\n".to_owned()
+}
@@ -0,0 +1,228 @@
+use crate::core::graph::Graph;
+
+pub(crate) async fn generate_filter_dart(_graph: &Graph) -> String {
+    r#"import 'runtime.dart';
+
+class Filter<T> implements TeoInput {
+  final T? equals;
+  final List<T>? $in;
+  final List<T>? notIn;
+  final T? lt;
+  final T? lte;
+  final T? gt;
+  final T? gte;
+  final Filter<T>? not;
+
+  const Filter({this.equals, this.$in, this.notIn, this.lt, this.lte, this.gt, this.gte, this.not});
+
+  @override
+  Map<String, dynamic> toJson() => {
+    if (equals != null) 'equals': toJsonValue(equals),
+    if ($in != null) 'in': toJsonValue($in),
+    if (notIn != null) 'notIn': toJsonValue(notIn),
+    if (lt != null) 'lt': toJsonValue(lt),
+    if (lte != null) 'lte': toJsonValue(lte),
+    if (gt != null) 'gt': toJsonValue(gt),
+    if (gte != null) 'gte': toJsonValue(gte),
+    if (not != null) 'not': toJsonValue(not),
+  };
+}
+
+class NullableFilter<T> implements TeoInput {
+  final Optional<T>? equals;
+  final List<T?>? $in;
+  final List<T?>? notIn;
+  final T? lt;
+  final T? lte;
+  final T? gt;
+  final T? gte;
+  final NullableFilter<T>? not;
+
+  const NullableFilter({this.equals, this.$in, this.notIn, this.lt, this.lte, this.gt, this.gte, this.not});
+
+  @override
+  Map<String, dynamic> toJson() => {
+    if (equals != null) 'equals': toJsonValue(equals),
+    if ($in != null) 'in': toJsonValue($in),
+    if (notIn != null) 'notIn': toJsonValue(notIn),
+    if (lt != null) 'lt': toJsonValue(lt),
+    if (lte != null) 'lte': toJsonValue(lte),
+    if (gt != null) 'gt': toJsonValue(gt),
+    if (gte != null) 'gte': toJsonValue(gte),
+    if (not != null) 'not': toJsonValue(not),
+  };
+}
+
+class BoolFilter implements TeoInput {
+  final bool? equals;
+  final BoolFilter? not;
+
+  const BoolFilter({this.equals, this.not});
+
+  @override
+  Map<String, dynamic> toJson() => {
+    if (equals != null) 'equals': equals,
+    if (not != null) 'not': toJsonValue(not),
+  };
+}
+
+class BoolNullableFilter implements TeoInput {
+  final Optional<bool>? equals;
+  final BoolNullableFilter? not;
+
+  const BoolNullableFilter({this.equals, this.not});
+
+  @override
+  Map<String, dynamic> toJson() => {
+    if (equals != null) 'equals': toJsonValue(equals),
+    if (not != null) 'not': toJsonValue(not),
+  };
+}
+
+class StringFilter implements TeoInput {
+  final String? equals;
+  final List<String>? $in;
+  final List<String>? notIn;
+  final String? lt;
+  final String? lte;
+  final String? gt;
+  final String? gte;
+  final String? contains;
+  final String? startsWith;
+  final String? endsWith;
+  final String? matches;
+  final String? search;
+  final StringFilter? not;
+
+  const StringFilter({this.equals, this.$in, this.notIn, this.lt, this.lte, this.gt, this.gte, this.contains, this.startsWith, this.endsWith, this.matches, this.search, this.not});
+
+  @override
+  Map<String, dynamic> toJson() => {
+    if (equals != null) 'equals': equals,
+    if ($in != null) 'in': $in,
+    if (notIn != null) 'notIn': notIn,
+    if (lt != null) 'lt': lt,
+    if (lte != null) 'lte': lte,
+    if (gt != null) 'gt': gt,
+    if (gte != null) 'gte': gte,
+    if (contains != null) 'contains': contains,
+    if (startsWith != null) 'startsWith': startsWith,
+    if (endsWith != null) 'endsWith': endsWith,
+    if (matches != null) 'matches': matches,
+    if (search != null) 'search': search,
+    if (not != null) 'not': toJsonValue(not),
+  };
+}
+
+class StringNullableFilter implements TeoInput {
+  final Optional<String>? equals;
+  final List<String?>? $in;
+  final List<String?>? notIn;
+  final String? lt;
+  final String? lte;
+  final String? gt;
+  final String? gte;
+  final String? contains;
+  final String? startsWith;
+  final String? endsWith;
+  final String? matches;
+  final String? search;
+  final StringNullableFilter? not;
+
+  const StringNullableFilter({this.equals, this.$in, this.notIn, this.lt, this.lte, this.gt, this.gte, this.contains, this.startsWith, this.endsWith, this.matches, this.search, this.not});
+
+  @override
+  Map<String, dynamic> toJson() => {
+    if (equals != null) 'equals': toJsonValue(equals),
+    if ($in != null) 'in': $in,
+    if (notIn != null) 'notIn': notIn,
+    if (lt != null) 'lt': lt,
+    if (lte != null) 'lte': lte,
+    if (gt != null) 'gt': gt,
+    if (gte != null) 'gte': gte,
+    if (contains != null) 'contains': contains,
+    if (startsWith != null) 'startsWith': startsWith,
+    if (endsWith != null) 'endsWith': endsWith,
+    if (matches != null) 'matches': matches,
+    if (search != null) 'search': search,
+    if (not != null) 'not': toJsonValue(not),
+  };
+}
+
+class EnumFilter<T extends Enum> implements TeoInput {
+  final T? equals;
+  final List<T>? $in;
+  final List<T>? notIn;
+  final EnumFilter<T>? not;
+
+  const EnumFilter({this.equals, this.$in, this.notIn, this.not});
+
+  @override
+  Map<String, dynamic> toJson() => {
+    if (equals != null) 'equals': toJsonValue(equals),
+    if ($in != null) 'in': toJsonValue($in),
+    if (notIn != null) 'notIn': toJsonValue(notIn),
+    if (not != null) 'not': toJsonValue(not),
+  };
+}
+
+class EnumNullableFilter<T extends Enum> implements TeoInput {
+  final Optional<T>? equals;
+  final List<T?>? $in;
+  final List<T?>? notIn;
+  final EnumNullableFilter<T>? not;
+
+  const EnumNullableFilter({this.equals, this.$in, this.notIn, this.not});
+
+  @override
+  Map<String, dynamic> toJson() => {
+    if (equals != null) 'equals': toJsonValue(equals),
+    if ($in != null) 'in': toJsonValue($in),
+    if (notIn != null) 'notIn': toJsonValue(notIn),
+    if (not != null) 'not': toJsonValue(not),
+  };
+}
+
+class ArrayFilter<T> implements TeoInput {
+  final List<T>? equals;
+  final T? has;
+  final List<T>? hasSome;
+  final List<T>? hasEvery;
+  final bool? isEmpty;
+  final int? length;
+
+  const ArrayFilter({this.equals, this.has, this.hasSome, this.hasEvery, this.isEmpty, this.length});
+
+  @override
+  Map<String, dynamic> toJson() => {
+    if (equals != null) 'equals': toJsonValue(equals),
+    if (has != null) 'has': toJsonValue(has),
+    if (hasSome != null) 'hasSome': toJsonValue(hasSome),
+    if (hasEvery != null) 'hasEvery': toJsonValue(hasEvery),
+    if (isEmpty != null) 'isEmpty': isEmpty,
+    if (length != null) 'length': length,
+  };
+}
+
+class ArrayNullableFilter<T> implements TeoInput {
+  final Optional<List<T>>? equals;
+  final T? has;
+  final List<T>? hasSome;
+  final List<T>? hasEvery;
+  final bool? isEmpty;
+  final int? length;
+
+  const ArrayNullableFilter({this.equals, this.has, this.hasSome, this.hasEvery, this.isEmpty, this.length});
+
+  @override
+  Map<String, dynamic> toJson() => {
+    if (equals != null) 'equals': toJsonValue(equals),
+    if (has != null) 'has': toJsonValue(has),
+    if (hasSome != null) 'hasSome': toJsonValue(hasSome),
+    if (hasEvery != null) 'hasEvery': toJsonValue(hasEvery),
+    if (isEmpty != null) 'isEmpty': isEmpty,
+    if (length != null) 'length': length,
+  };
+}
+"#.to_owned()
+}
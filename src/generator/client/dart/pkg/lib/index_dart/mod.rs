@@ -0,0 +1,691 @@
+use inflector::Inflector;
+use crate::core::action::{Action, CREATE_HANDLER, CREATE_MANY_HANDLER, FIND_FIRST_HANDLER, FIND_MANY_HANDLER, ResData, ResMeta, UPDATE_HANDLER, UPDATE_MANY_HANDLER, UPSERT_HANDLER};
+use crate::core::app::conf::ClientGeneratorConf;
+use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+use crate::core::handler::HandlerType;
+use crate::generator::client::dart::pkg::lib::index_dart::doc::{action_doc, action_group_doc, create_or_update_doc, credentials_doc, cursor_doc, refresh_token_doc, field_doc, handler_doc, include_doc, main_object_doc, nested_connect_doc, nested_create_doc, nested_create_or_connect_doc, nested_delete_doc, nested_disconnect_doc, nested_set_doc, nested_update_doc, nested_upsert_doc, order_by_doc, page_number_doc, page_size_doc, relation_doc, select_doc, skip_doc, take_doc, unique_connect_create_doc, unique_connect_doc, unique_where_doc, where_doc, where_doc_first, with_token_doc};
+use crate::generator::client::dart::r#type::ToDartType;
+use crate::core::graph::Graph;
+use crate::core::model::Model;
+use crate::generator::lib::code::Code;
+
+mod doc;
+
+static RESERVED_WORDS: [&str; 33] = [
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+    "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+    "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void",
+    "while", "with",
+];
+
+fn escape(before: impl AsRef<str>) -> String {
+    let before = before.as_ref();
+    if RESERVED_WORDS.contains(&before) {
+        format!("${before}")
+    } else {
+        before.to_string()
+    }
+}
+
+struct DartClassField {
+    n: String,
+    t: String,
+    o: bool,
+    d: Option<String>,
+    e: &'static str,
+}
+
+impl DartClassField {
+    fn new(n: impl Into<String>, t: impl Into<String>, o: bool, d: Option<String>) -> Self {
+        Self { n: n.into(), t: t.into(), o, d, e: "toJsonValue" }
+    }
+}
+
+struct DartClassBuilder {
+    name: String,
+    fields: Vec<DartClassField>,
+}
+
+impl DartClassBuilder {
+    fn build(&self) -> String {
+        Code::new(0, 2, |c| {
+            let class_name = &self.name;
+            c.block(format!("class {class_name} implements TeoInput {{"), |b| {
+                for f in &self.fields {
+                    if let Some(doc) = &f.d {
+                        b.doc(doc);
+                    }
+                    let field_type = &f.t;
+                    let question_mark = if f.o { "?" } else { "" };
+                    let field_name = escape(&f.n);
+                    b.line(format!("final {field_type}{question_mark} {field_name};"));
+                }
+                if self.fields.is_empty() {
+                    b.line(format!("const {class_name}();"));
+                } else {
+                    b.empty_line();
+                    b.block(format!("const {class_name}({{"), |b| {
+                        for f in &self.fields {
+                            let required = if f.o { "" } else { "required " };
+                            let field_name = escape(&f.n);
+                            b.line(format!("{required}this.{field_name},"));
+                        }
+                    }, "});");
+                }
+                b.empty_line();
+                b.line("@override");
+                b.block("Map<String, dynamic> toJson() => {", |b| {
+                    for f in &self.fields {
+                        let key = &f.n;
+                        let field_name = escape(&f.n);
+                        let encoder = f.e;
+                        if f.o {
+                            b.line(format!("if ({field_name} != null) '{key}': {encoder}({field_name}!),"));
+                        } else {
+                            b.line(format!("'{key}': {encoder}({field_name}),"));
+                        }
+                    }
+                }, "};");
+            }, "}");
+        }).to_string()
+    }
+}
+
+fn without_title(without: Option<&str>) -> String {
+    if let Some(title) = without {
+        let title = title.to_pascal_case();
+        format!("Without{title}")
+    } else {
+        "".to_owned()
+    }
+}
+
+/// Model objects in handler inputs are accepted in the shape of their create inputs.
+fn handler_input_type(r#type: &HandlerType, graph: &Graph) -> String {
+    match r#type.field_type(graph) {
+        FieldType::Object(name) => format!("{name}CreateInput"),
+        FieldType::Vec(inner) => match inner.field_type() {
+            FieldType::Object(name) => format!("List<{name}CreateInput>"),
+            _ => FieldType::Vec(inner).to_dart_type(false),
+        },
+        field_type => field_type.to_dart_type(false),
+    }
+}
+
+fn list_if(t: String, many: bool) -> String {
+    if many { format!("List<{t}>") } else { t }
+}
+
+fn generate_model_create_nested_input(model: &Model, without: Option<&str>, many: bool) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    let many_title = if many { "Many" } else { "One" };
+    DartClassBuilder {
+        name: format!("{model_name}CreateNested{many_title}{without_title}Input"),
+        fields: vec![
+            DartClassField::new("create", list_if(format!("{model_name}Create{without_title}Input"), many), true, Some(nested_create_doc(model, many))),
+            DartClassField::new("connectOrCreate", list_if(format!("{model_name}ConnectOrCreate{without_title}Input"), many), true, Some(nested_create_or_connect_doc(model, many))),
+            DartClassField::new("connect", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_connect_doc(model, many))),
+        ],
+    }.build()
+}
+
+fn generate_model_create_or_connect_input(model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    DartClassBuilder {
+        name: format!("{model_name}ConnectOrCreate{without_title}Input"),
+        fields: vec![
+            DartClassField::new("where", format!("{model_name}WhereUniqueInput"), false, Some(unique_connect_doc(model))),
+            DartClassField::new("create", format!("{model_name}Create{without_title}Input"), false, Some(unique_connect_create_doc(model))),
+        ],
+    }.build()
+}
+
+fn generate_model_create_input(graph: &Graph, model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    let without_relation = without.map(|title| model.relation(title).unwrap());
+    let mut fields = Vec::<DartClassField>::new();
+    model.input_keys().iter().for_each(|k| {
+        if let Some(field) = model.field(k) {
+            if let Some(without_relation) = without_relation {
+                if without_relation.fields().contains(k) { return }
+            }
+            let field_type = field.field_type().to_dart_create_input_type(false);
+            fields.push(DartClassField::new(field.name(), field_type, true, Some(field_doc(field))));
+        } else if let Some(relation) = model.relation(k) {
+            if let Some(without_relation) = without_relation {
+                if without_relation.name() == k { return }
+            }
+            let relation_model_name = relation.model();
+            let relation_model = graph.model(relation_model_name).unwrap();
+            let num = if relation.is_vec() { "Many" } else { "One" };
+            let without = if let Some(opposite_relation) = relation_model.relations().iter().find(|r| {
+                r.fields() == relation.references() && r.references() == relation.fields()
+            }) {
+                let opposite_relation_name = opposite_relation.name().to_pascal_case();
+                format!("Without{opposite_relation_name}")
+            } else {
+                "".to_owned()
+            };
+            fields.push(DartClassField::new(relation.name(), format!("{relation_model_name}CreateNested{num}{without}Input"), true, Some(relation_doc(relation))));
+        }
+    });
+    DartClassBuilder { name: format!("{model_name}Create{without_title}Input"), fields }.build()
+}
+
+fn generate_model_upsert_with_where_unique_input(model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    DartClassBuilder {
+        name: format!("{model_name}UpsertWithWhereUnique{without_title}Input"),
+        fields: vec![
+            DartClassField::new("where", format!("{model_name}WhereUniqueInput"), false, Some(unique_where_doc(model))),
+            DartClassField::new("update", format!("{model_name}Update{without_title}Input"), false, Some(create_or_update_doc(model, Action::from_u32(UPDATE_HANDLER)))),
+            DartClassField::new("create", format!("{model_name}Create{without_title}Input"), false, Some(create_or_update_doc(model, Action::from_u32(CREATE_HANDLER)))),
+        ],
+    }.build()
+}
+
+fn generate_model_update_with_where_unique_input(model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    DartClassBuilder {
+        name: format!("{model_name}UpdateWithWhereUnique{without_title}Input"),
+        fields: vec![
+            DartClassField::new("where", format!("{model_name}WhereUniqueInput"), false, Some(unique_where_doc(model))),
+            DartClassField::new("update", format!("{model_name}Update{without_title}Input"), false, Some(create_or_update_doc(model, Action::from_u32(UPDATE_HANDLER)))),
+        ],
+    }.build()
+}
+
+fn generate_model_update_many_with_where_input(model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    DartClassBuilder {
+        name: format!("{model_name}UpdateManyWithWhere{without_title}Input"),
+        fields: vec![
+            DartClassField::new("where", format!("{model_name}WhereInput"), false, Some(where_doc(model))),
+            DartClassField::new("update", format!("{model_name}Update{without_title}Input"), false, Some(create_or_update_doc(model, Action::from_u32(UPDATE_MANY_HANDLER)))),
+        ],
+    }.build()
+}
+
+fn generate_model_update_nested_input(model: &Model, without: Option<&str>, many: bool) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    let many_title = if many { "Many" } else { "One" };
+    let mut fields = vec![
+        DartClassField::new("create", list_if(format!("{model_name}Create{without_title}Input"), many), true, Some(nested_create_doc(model, many))),
+        DartClassField::new("connectOrCreate", list_if(format!("{model_name}ConnectOrCreate{without_title}Input"), many), true, Some(nested_create_or_connect_doc(model, many))),
+        DartClassField::new("connect", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_connect_doc(model, many))),
+        DartClassField::new("set", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_set_doc(model, many))),
+        DartClassField::new("update", list_if(format!("{model_name}UpdateWithWhereUnique{without_title}Input"), many), true, Some(nested_update_doc(model, many))),
+        DartClassField::new("upsert", list_if(format!("{model_name}UpsertWithWhereUnique{without_title}Input"), many), true, Some(nested_upsert_doc(model, many))),
+        DartClassField::new("disconnect", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_disconnect_doc(model, many))),
+        DartClassField::new("delete", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_delete_doc(model, many))),
+    ];
+    if many {
+        fields.push(DartClassField::new("updateMany", format!("List<{model_name}UpdateManyWithWhere{without_title}Input>"), true, Some(nested_update_doc(model, many))));
+        fields.push(DartClassField::new("deleteMany", format!("List<{model_name}WhereInput>"), true, Some(nested_delete_doc(model, many))));
+    }
+    DartClassBuilder { name: format!("{model_name}UpdateNested{many_title}{without_title}Input"), fields }.build()
+}
+
+fn generate_model_update_input(graph: &Graph, model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    let without_relation = without.map(|title| model.relation(title).unwrap());
+    let mut fields = Vec::<DartClassField>::new();
+    model.input_keys().iter().for_each(|k| {
+        if let Some(field) = model.field(k) {
+            if let Some(without_relation) = without_relation {
+                if without_relation.fields().contains(k) { return }
+            }
+            let field_type = field.field_type().to_dart_update_input_type(field.optionality.is_optional());
+            fields.push(DartClassField::new(field.name(), field_type, true, Some(field_doc(field))));
+        } else if let Some(relation) = model.relation(k) {
+            if let Some(without_relation) = without_relation {
+                if without_relation.name() == k { return }
+            }
+            let relation_model_name = relation.model();
+            let relation_model = graph.model(relation_model_name).unwrap();
+            let num = if relation.is_vec() { "Many" } else { "One" };
+            let without = if let Some(opposite_relation) = relation_model.relations().iter().find(|r| {
+                r.fields() == relation.references() && r.references() == relation.fields()
+            }) {
+                let opposite_relation_name = opposite_relation.name().to_pascal_case();
+                format!("Without{opposite_relation_name}")
+            } else {
+                "".to_owned()
+            };
+            fields.push(DartClassField::new(relation.name(), format!("{relation_model_name}UpdateNested{num}{without}Input"), true, Some(relation_doc(relation))));
+        }
+    });
+    DartClassBuilder { name: format!("{model_name}Update{without_title}Input"), fields }.build()
+}
+
+fn generate_model_credentials_input(model: &Model) -> String {
+    let model_name = model.name();
+    let mut fields = Vec::<DartClassField>::new();
+    let auth_identity_keys = model.auth_identity_keys();
+    let auth_by_keys = model.auth_by_keys();
+    let auth_identity_optional = auth_identity_keys.len() != 1;
+    let auth_by_keys_optional = auth_by_keys.len() != 1;
+    for key in auth_identity_keys {
+        let field = model.field(key).unwrap();
+        fields.push(DartClassField::new(field.name(), field.field_type().to_dart_type(false), auth_identity_optional, Some(field_doc(field))));
+    }
+    for key in auth_by_keys {
+        let field = model.field(key).unwrap();
+        fields.push(DartClassField::new(field.name(), field.field_type().to_dart_type(false), auth_by_keys_optional, Some(field_doc(field))));
+    }
+    DartClassBuilder { name: format!("{model_name}CredentialsInput"), fields }.build()
+}
+
+fn generate_model_class(model: &Model) -> String {
+    let model_name = model.name();
+    let mut fields: Vec<(String, String, String, String)> = vec![];
+    model.output_keys().iter().for_each(|k| {
+        if let Some(field) = model.field(k) {
+            let value = format!("json['{}']", field.name());
+            let decoder = field.field_type().to_dart_decoder(&value);
+            fields.push((field.name().to_owned(), field.field_type().to_dart_type(false), field_doc(field), format!("{value} == null ? null : {decoder}")));
+        } else if let Some(relation) = model.relation(k) {
+            let relation_model = relation.model();
+            let value = format!("json['{}']", relation.name());
+            let (relation_type, decoder) = if relation.is_vec() {
+                (format!("List<{relation_model}>"), format!("({value} as List).map((e) => {relation_model}.fromJson(e as Map<String, dynamic>)).toList()"))
+            } else {
+                (relation_model.to_owned(), format!("{relation_model}.fromJson({value} as Map<String, dynamic>)"))
+            };
+            fields.push((relation.name().to_owned(), relation_type, relation_doc(relation), format!("{value} == null ? null : {decoder}")));
+        }
+    });
+    Code::new(0, 2, |c| {
+        c.block(format!("class {model_name} {{"), |b| {
+            for (name, t, doc, _) in &fields {
+                let field_name = escape(name);
+                b.doc(doc);
+                b.line(format!("final {t}? {field_name};"));
+            }
+            b.empty_line();
+            if fields.is_empty() {
+                b.line(format!("const {model_name}();"));
+            } else {
+                b.block(format!("const {model_name}({{"), |b| {
+                    for (name, _, _, _) in &fields {
+                        let field_name = escape(name);
+                        b.line(format!("this.{field_name},"));
+                    }
+                }, "});");
+            }
+            b.empty_line();
+            b.block(format!("factory {model_name}.fromJson(Map<String, dynamic> json) => {model_name}("), |b| {
+                for (name, _, _, decoder) in &fields {
+                    let field_name = escape(name);
+                    b.line(format!("{field_name}: {decoder},"));
+                }
+            }, ");");
+        }, "}");
+    }).to_string()
+}
+
+pub(crate) async fn generate_index_dart(graph: &Graph, client: &ClientGeneratorConf) -> String {
+    let object_name = client.object_name.clone().unwrap_or("teo".to_owned());
+    let mut object_class_name = object_name.to_pascal_case();
+    if object_name == object_class_name { // in case of object name is capitalized
+        object_class_name += "Class";
+    }
+    Code::new(0, 2, |c| {
+        c.line("// ignore_for_file: non_constant_identifier_names, unused_import");
+        c.empty_line();
+        c.line("import 'package:decimal/decimal.dart';");
+        c.empty_line();
+        c.line("import 'filter.dart';");
+        c.line("import 'operation.dart';");
+        c.line("import 'runtime.dart';");
+        c.empty_line();
+        c.line("export 'package:decimal/decimal.dart' show Decimal;");
+        c.line("export 'filter.dart';");
+        c.line("export 'operation.dart';");
+        c.line("export 'runtime.dart' show Optional, SortOrder, PagingInfo, TokenInfo, Response, TeoError, setBearerToken, getBearerToken;");
+        c.empty_line();
+        // enum definitions
+        let mut enum_names: Vec<&String> = graph.enums().keys().collect();
+        enum_names.sort();
+        for name in enum_names {
+            let choices = graph.enums().get(name).unwrap().values();
+            c.block(format!("enum {name} {{"), |b| {
+                for choice in choices {
+                    b.line(format!("{},", escape(choice)));
+                }
+            }, "}");
+            c.empty_line();
+        }
+        // model definitions
+        graph.models().iter().for_each(|m| {
+            c.line(generate_model_class(m));
+        });
+        // model input arguments
+        graph.models().iter().for_each(|m| {
+            let model_name = m.name();
+            // select
+            let mut select_fields = Vec::<DartClassField>::new();
+            m.output_keys().iter().for_each(|k| {
+                if let Some(field) = m.field(k) {
+                    select_fields.push(DartClassField::new(field.name(), "bool", true, Some(field_doc(field))));
+                }
+            });
+            c.line(DartClassBuilder { name: format!("{model_name}Select"), fields: select_fields }.build());
+            // include
+            let mut include_fields = Vec::<DartClassField>::new();
+            for relation in m.relations() {
+                let r_model = relation.model();
+                let has_find_many = graph.model(r_model).map(|r| r.has_action(Action::from_u32(FIND_MANY_HANDLER))).unwrap_or(false);
+                let find_many = if relation.is_vec() && has_find_many { "FindMany" } else { "" };
+                let mut field = DartClassField::new(relation.name(), format!("{r_model}{find_many}Args"), true, Some(relation_doc(relation)));
+                field.e = "toIncludeValue";
+                include_fields.push(field);
+            }
+            c.line(DartClassBuilder { name: format!("{model_name}Include"), fields: include_fields }.build());
+            // where
+            let mut where_fields = vec![
+                DartClassField::new("AND", format!("List<{model_name}WhereInput>"), true, None),
+                DartClassField::new("OR", format!("List<{model_name}WhereInput>"), true, None),
+                DartClassField::new("NOT", format!("List<{model_name}WhereInput>"), true, None),
+            ];
+            m.query_keys().iter().for_each(|k| {
+                if let Some(field) = m.field(k) {
+                    let field_filter = field.field_type().to_dart_filter_type(field.optionality.is_optional());
+                    where_fields.push(DartClassField::new(field.name(), field_filter, true, Some(field_doc(field))));
+                } else if let Some(relation) = m.relation(k) {
+                    let list = if relation.is_vec() { "List" } else { "" };
+                    let relation_model = relation.model();
+                    where_fields.push(DartClassField::new(relation.name(), format!("{relation_model}{list}RelationFilter"), true, Some(relation_doc(relation))));
+                }
+            });
+            c.line(DartClassBuilder { name: format!("{model_name}WhereInput"), fields: where_fields }.build());
+            // where unique
+            let mut where_unique_fields = Vec::<DartClassField>::new();
+            let mut used_where_unique_field_names: Vec<&str> = Vec::new();
+            m.indices().iter().for_each(|index| {
+                if index.r#type().is_unique() {
+                    index.items().iter().for_each(|item| {
+                        if !used_where_unique_field_names.contains(&item.field_name()) {
+                            if let Some(field) = m.field(item.field_name()) {
+                                where_unique_fields.push(DartClassField::new(field.name(), field.field_type().to_dart_type(false), true, Some(field_doc(field))));
+                            }
+                            used_where_unique_field_names.push(item.field_name());
+                        }
+                    });
+                }
+            });
+            c.line(DartClassBuilder { name: format!("{model_name}WhereUniqueInput"), fields: where_unique_fields }.build());
+            // relation filter
+            c.line(DartClassBuilder {
+                name: format!("{model_name}RelationFilter"),
+                fields: vec![
+                    DartClassField::new("is", format!("{model_name}WhereInput"), true, None),
+                    DartClassField::new("isNot", format!("{model_name}WhereInput"), true, None),
+                ],
+            }.build());
+            // list relation filter
+            c.line(DartClassBuilder {
+                name: format!("{model_name}ListRelationFilter"),
+                fields: vec![
+                    DartClassField::new("every", format!("{model_name}WhereInput"), true, None),
+                    DartClassField::new("some", format!("{model_name}WhereInput"), true, None),
+                    DartClassField::new("none", format!("{model_name}WhereInput"), true, None),
+                ],
+            }.build());
+            // order by
+            let mut order_by_fields = Vec::<DartClassField>::new();
+            m.query_keys().iter().for_each(|k| {
+                if let Some(field) = m.field(k) {
+                    order_by_fields.push(DartClassField::new(field.name(), "SortOrder", true, Some(field_doc(field))));
+                }
+            });
+            c.line(DartClassBuilder { name: format!("{model_name}OrderByInput"), fields: order_by_fields }.build());
+            // create and update inputs without anything
+            c.line(generate_model_create_input(graph, m, None));
+            c.line(generate_model_create_nested_input(m, None, true));
+            c.line(generate_model_create_nested_input(m, None, false));
+            c.line(generate_model_create_or_connect_input(m, None));
+            m.relations().iter().for_each(|r| {
+                c.line(generate_model_create_input(graph, m, Some(r.name())));
+                c.line(generate_model_create_nested_input(m, Some(r.name()), true));
+                c.line(generate_model_create_nested_input(m, Some(r.name()), false));
+                c.line(generate_model_create_or_connect_input(m, Some(r.name())));
+            });
+            c.line(generate_model_update_input(graph, m, None));
+            c.line(generate_model_update_nested_input(m, None, true));
+            c.line(generate_model_update_nested_input(m, None, false));
+            c.line(generate_model_upsert_with_where_unique_input(m, None));
+            c.line(generate_model_update_with_where_unique_input(m, None));
+            c.line(generate_model_update_many_with_where_input(m, None));
+            m.relations().iter().for_each(|r| {
+                c.line(generate_model_update_input(graph, m, Some(r.name())));
+                c.line(generate_model_update_nested_input(m, Some(r.name()), true));
+                c.line(generate_model_update_nested_input(m, Some(r.name()), false));
+                c.line(generate_model_upsert_with_where_unique_input(m, Some(r.name())));
+                c.line(generate_model_update_with_where_unique_input(m, Some(r.name())));
+                c.line(generate_model_update_many_with_where_input(m, Some(r.name())));
+            });
+            if m.identity() {
+                c.line(generate_model_credentials_input(m));
+            }
+            // action args
+            c.line(DartClassBuilder {
+                name: format!("{model_name}Args"),
+                fields: vec![
+                    DartClassField::new("select", format!("{model_name}Select"), true, Some(select_doc(m))),
+                    DartClassField::new("include", format!("{model_name}Include"), true, Some(include_doc(m))),
+                ],
+            }.build());
+            Action::handlers_iter().for_each(|a| {
+                if !m.has_action(*a) { return }
+                let action_name = a.as_handler_str().to_pascal_case();
+                let mut fields = Vec::<DartClassField>::new();
+                if a.handler_requires_where() {
+                    let doc = if a == &Action::from_u32(FIND_FIRST_HANDLER) { where_doc_first(m) } else { where_doc(m) };
+                    fields.push(DartClassField::new("where", format!("{model_name}WhereInput"), true, Some(doc)));
+                }
+                if a.handler_requires_where_unique() {
+                    fields.push(DartClassField::new("where", format!("{model_name}WhereUniqueInput"), true, Some(unique_where_doc(m))));
+                }
+                fields.push(DartClassField::new("select", format!("{model_name}Select"), true, Some(select_doc(m))));
+                fields.push(DartClassField::new("include", format!("{model_name}Include"), true, Some(include_doc(m))));
+                if a.handler_requires_where() {
+                    fields.push(DartClassField::new("orderBy", format!("List<{model_name}OrderByInput>"), true, Some(order_by_doc(m))));
+                    fields.push(DartClassField::new("cursor", format!("{model_name}WhereUniqueInput"), true, Some(cursor_doc(m))));
+                    fields.push(DartClassField::new("take", "int", true, Some(take_doc(m))));
+                    fields.push(DartClassField::new("skip", "int", true, Some(skip_doc(m))));
+                    fields.push(DartClassField::new("pageSize", "int", true, Some(page_size_doc(m))));
+                    fields.push(DartClassField::new("pageNumber", "int", true, Some(page_number_doc(m))));
+                }
+                if a.handler_requires_create() {
+                    let doc = create_or_update_doc(m, if a == &Action::from_u32(UPSERT_HANDLER) { Action::from_u32(CREATE_HANDLER) } else { *a });
+                    let create_type = list_if(format!("{model_name}CreateInput"), a == &Action::from_u32(CREATE_MANY_HANDLER));
+                    fields.push(DartClassField::new("create", create_type, false, Some(doc)));
+                }
+                if a.handler_requires_update() {
+                    let doc = create_or_update_doc(m, if a == &Action::from_u32(UPSERT_HANDLER) { Action::from_u32(UPDATE_HANDLER) } else { *a });
+                    fields.push(DartClassField::new("update", format!("{model_name}UpdateInput"), false, Some(doc)));
+                }
+                if a.handler_requires_credentials() {
+                    fields.push(DartClassField::new("credentials", format!("{model_name}CredentialsInput"), false, Some(credentials_doc(m, *a))));
+                }
+                if a.handler_accepts_refresh_token() {
+                    fields.push(DartClassField::new("refreshToken", "String", !a.handler_requires_refresh_token(), Some(refresh_token_doc(*a))));
+                }
+                c.line(DartClassBuilder { name: format!("{model_name}{action_name}Args"), fields }.build());
+            });
+            m.handlers().iter().for_each(|h| {
+                let handler_name = h.name().to_pascal_case();
+                let fields = h.input().iter().map(|(key, r#type)| {
+                    DartClassField::new(key, handler_input_type(r#type, graph), r#type.optional, None)
+                }).collect();
+                c.line(DartClassBuilder { name: format!("{model_name}{handler_name}Args"), fields }.build());
+            });
+        });
+        // delegates
+        graph.models().iter().for_each(|m| {
+            if !m.actions().is_empty() || !m.handlers().is_empty() {
+                let model_name = m.name();
+                let model_class_name = model_name.to_camel_case().to_pascal_case();
+                let model_url_segment_name = m.url_segment_name();
+                c.block(format!("class {model_class_name}Delegate {{"), |b| {
+                    b.line("final String? _token;");
+                    b.empty_line();
+                    b.line(format!("const {model_class_name}Delegate._(this._token);"));
+                    Action::handlers_iter().for_each(|a| {
+                        if !m.has_action(*a) { return }
+                        let action_url_name = a.as_handler_str();
+                        let action_var_name = action_url_name.to_camel_case();
+                        let action_name = action_url_name.to_pascal_case();
+                        let (res_meta, meta_decoder) = match a.handler_res_meta() {
+                            ResMeta::PagingInfo => ("PagingInfo", "PagingInfo.fromJson(json['meta'] as Map<String, dynamic>)"),
+                            ResMeta::TokenInfo => ("TokenInfo", "TokenInfo.fromJson(json['meta'] as Map<String, dynamic>)"),
+                            ResMeta::NoMeta | ResMeta::Other => ("Null", "null"),
+                        };
+                        let (res_data, data_decoder) = match a.handler_res_data() {
+                            ResData::Single => (model_name.to_string(), format!("{model_name}.fromJson(json['data'] as Map<String, dynamic>)")),
+                            ResData::Vec => (format!("List<{model_name}>"), format!("(json['data'] as List).map((e) => {model_name}.fromJson(e as Map<String, dynamic>)).toList()")),
+                            ResData::Number => ("int".to_owned(), "(json['data'] as num).toInt()".to_owned()),
+                            ResData::Other => ("dynamic".to_owned(), "json['data']".to_owned()),
+                        };
+                        let args_type = format!("{model_name}{action_name}Args");
+                        let args_required = a.handler_requires_create() || a.handler_requires_update() || a.handler_requires_credentials() || a.handler_requires_refresh_token();
+                        let params = if args_required {
+                            format!("{args_type} args, [String? token]")
+                        } else {
+                            format!("[{args_type} args = const {args_type}(), String? token]")
+                        };
+                        b.empty_line();
+                        b.doc(action_doc(&object_name, *a, m));
+                        b.block(format!("Future<Response<{res_meta}, {res_data}>> {action_var_name}({params}) async {{"), |b| {
+                            b.line(format!("final json = await request('{model_url_segment_name}', '{action_url_name}', args.toJson(), token ?? _token);"));
+                            b.line(format!("return Response({meta_decoder}, {data_decoder});"));
+                        }, "}");
+                    });
+                    m.handlers().iter().for_each(|h| {
+                        let handler_url_name = h.name();
+                        let handler_var_name = handler_url_name.to_camel_case();
+                        let handler_name = handler_url_name.to_pascal_case();
+                        let (res_data, data_decoder) = match h.output() {
+                            Some(r#type) => {
+                                let field_type = r#type.field_type(graph);
+                                let decoder = field_type.to_dart_decoder("json['data']");
+                                if r#type.optional {
+                                    (field_type.to_dart_type(true), format!("json['data'] == null ? null : {decoder}"))
+                                } else {
+                                    (field_type.to_dart_type(false), decoder)
+                                }
+                            }
+                            None => ("dynamic".to_owned(), "json['data']".to_owned()),
+                        };
+                        let args_type = format!("{model_name}{handler_name}Args");
+                        let params = if h.input().iter().all(|(_, t)| t.optional) {
+                            format!("[{args_type} args = const {args_type}(), String? token]")
+                        } else {
+                            format!("{args_type} args, [String? token]")
+                        };
+                        b.empty_line();
+                        b.doc(handler_doc(&object_name, h, m));
+                        b.block(format!("Future<Response<Null, {res_data}>> {handler_var_name}({params}) async {{"), |b| {
+                            b.line(format!("final json = await request('{model_url_segment_name}', '{handler_url_name}', args.toJson(), token ?? _token);"));
+                            b.line(format!("return Response(null, {data_decoder});"));
+                        }, "}");
+                    });
+                }, "}");
+                c.empty_line();
+            }
+        });
+        // main object
+        c.block(format!("class {object_class_name} {{"), |b| {
+            b.line("final String? _token;");
+            b.empty_line();
+            b.line(format!("const {object_class_name}([this._token]);"));
+            graph.models().iter().for_each(|m| {
+                if !m.actions().is_empty() || !m.handlers().is_empty() {
+                    let model_name = m.name();
+                    let model_var_name = model_name.to_camel_case();
+                    let model_class_name = model_var_name.to_pascal_case();
+                    b.empty_line();
+                    b.doc(action_group_doc(&object_name, m));
+                    b.line(format!("{model_class_name}Delegate get {model_var_name} => {model_class_name}Delegate._(_token);"));
+                }
+            });
+            b.empty_line();
+            b.doc(with_token_doc());
+            b.line(format!("{object_class_name} withToken(String? token) => {object_class_name}(token);"));
+        }, "}");
+        c.empty_line();
+        c.doc(main_object_doc(&object_name, graph));
+        c.line(format!("const {object_name} = {object_class_name}();"));
+    }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use serial_test::serial;
+    use crate::core::app::testing::sqlite_app;
+    use crate::parser::ast::client::ClientLanguage;
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model User {
+  @id @autoIncrement
+  id: Int
+  name: String
+  age: Int?
+  @relation(fields: .id, references: .authorId)
+  posts: Post[]
+}
+
+model Post {
+  @id @autoIncrement
+  id: Int
+  authorId: Int
+  @relation(fields: .authorId, references: .id)
+  author: User
+}
+"#;
+
+    fn conf() -> ClientGeneratorConf {
+        ClientGeneratorConf {
+            name: None,
+            provider: ClientLanguage::Dart,
+            dest: PathBuf::new(),
+            package: false,
+            host: "http://localhost:5100".to_owned(),
+            object_name: None,
+            git_commit: false,
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn models_decode_fields_and_relations() {
+        let app = sqlite_app(SCHEMA).await;
+        let index = generate_index_dart(app.graph(), &conf()).await;
+        assert!(index.contains("final int? age;"));
+        assert!(index.contains("age: json['age'] == null ? null : (json['age'] as num).toInt(),"));
+        assert!(index.contains("posts: json['posts'] == null ? null : (json['posts'] as List).map((e) => Post.fromJson(e as Map<String, dynamic>)).toList(),"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn delegates_request_model_actions() {
+        let app = sqlite_app(SCHEMA).await;
+        let index = generate_index_dart(app.graph(), &conf()).await;
+        assert!(index.contains("Future<Response<PagingInfo, List<User>>> findMany([UserFindManyArgs args = const UserFindManyArgs(), String? token]) async {"));
+        assert!(index.contains("Future<Response<Null, User>> create(UserCreateArgs args, [String? token]) async {"));
+        assert!(index.contains("final json = await request('users', 'create', args.toJson(), token ?? _token);"));
+        assert!(index.contains("UserDelegate get user => UserDelegate._(_token);"));
+        assert!(index.contains("const teo = Teo();"));
+    }
+}
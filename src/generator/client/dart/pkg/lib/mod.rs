@@ -0,0 +1,4 @@
+pub(crate) mod index_dart;
+pub(crate) mod runtime_dart;
+pub(crate) mod filter_dart;
+pub(crate) mod operation_dart;
@@ -0,0 +1,42 @@
+use crate::core::graph::Graph;
+
+pub(crate) async fn generate_operation_dart(_graph: &Graph) -> String {
+    r#"import 'runtime.dart';
+
+class FieldUpdateOperationsInput<T> implements TeoInput {
+  final String _operation;
+  final Object? _value;
+
+  const FieldUpdateOperationsInput._(this._operation, this._value);
+
+  const FieldUpdateOperationsInput.set(T value) : this._('set', value);
+
+  @override
+  Map<String, dynamic> toJson() => {_operation: toJsonValue(_value)};
+}
+
+class NumberFieldUpdateOperationsInput<T> extends FieldUpdateOperationsInput<T> {
+  const NumberFieldUpdateOperationsInput.set(T value) : super._('set', value);
+
+  const NumberFieldUpdateOperationsInput.increment(T value) : super._('increment', value);
+
+  const NumberFieldUpdateOperationsInput.decrement(T value) : super._('decrement', value);
+
+  const NumberFieldUpdateOperationsInput.multiply(T value) : super._('multiply', value);
+
+  const NumberFieldUpdateOperationsInput.divide(T value) : super._('divide', value);
+}
+
+class ArrayFieldUpdateOperationsInput<T> extends FieldUpdateOperationsInput<List<T>> {
+  const ArrayFieldUpdateOperationsInput.set(List<T> value) : super._('set', value);
+
+  const ArrayFieldUpdateOperationsInput.push(T value) : super._('push', value);
+}
+
+class NullableArrayFieldUpdateOperationsInput<T> extends FieldUpdateOperationsInput<List<T>?> {
+  const NullableArrayFieldUpdateOperationsInput.set(List<T>? value) : super._('set', value);
+
+  const NullableArrayFieldUpdateOperationsInput.push(T value) : super._('push', value);
+}
+"#.to_owned()
+}
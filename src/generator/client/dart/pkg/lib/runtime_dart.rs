@@ -0,0 +1,127 @@
+use crate::core::app::conf::ClientGeneratorConf;
+use crate::core::graph::Graph;
+
+pub(crate) async fn generate_runtime_dart(_graph: &Graph, conf: &ClientGeneratorConf) -> String {
+    let host = conf.host.trim_end_matches('/');
+    format!(r#"import 'dart:convert';
+
+import 'package:decimal/decimal.dart';
+import 'package:http/http.dart' as http;
+
+abstract class TeoInput {{
+  Map<String, dynamic> toJson();
+}}
+
+/// Wraps a nullable value, so that an explicit `null` is sent instead of omitting the argument.
+class Optional<T> {{
+  final T? value;
+
+  const Optional(this.value);
+}}
+
+enum SortOrder {{
+  asc,
+  desc,
+}}
+
+class PagingInfo {{
+  final int count;
+  final int? numberOfPages;
+
+  const PagingInfo({{required this.count, this.numberOfPages}});
+
+  factory PagingInfo.fromJson(Map<String, dynamic> json) => PagingInfo(
+    count: (json['count'] as num).toInt(),
+    numberOfPages: json['numberOfPages'] == null ? null : (json['numberOfPages'] as num).toInt(),
+  );
+}}
+
+class TokenInfo {{
+  final String token;
+  final String? refreshToken;
+
+  const TokenInfo({{required this.token, this.refreshToken}});
+
+  factory TokenInfo.fromJson(Map<String, dynamic> json) => TokenInfo(
+    token: json['token'] as String,
+    refreshToken: json['refreshToken'] as String?,
+  );
+}}
+
+class Response<Meta, Data> {{
+  final Meta meta;
+  final Data data;
+
+  const Response(this.meta, this.data);
+}}
+
+class TeoError implements Exception {{
+  final String type;
+  final String message;
+  final Map<String, String>? errors;
+
+  const TeoError(this.type, this.message, this.errors);
+
+  factory TeoError.fromJson(Map<String, dynamic> json) => TeoError(
+    json['type'] as String,
+    json['message'] as String,
+    (json['errors'] as Map<String, dynamic>?)?.map((k, v) => MapEntry(k, v as String)),
+  );
+
+  @override
+  String toString() => 'TeoError($type): $message';
+}}
+
+String? _bearerToken;
+
+void setBearerToken(String? token) {{
+  _bearerToken = token;
+}}
+
+String? getBearerToken() => _bearerToken;
+
+Object? toJsonValue(Object? value) {{
+  if (value is Optional) return toJsonValue(value.value);
+  if (value is DateTime) return value.toUtc().toIso8601String();
+  if (value is Decimal) return value.toString();
+  if (value is Enum) return value.name;
+  if (value is TeoInput) return value.toJson();
+  if (value is List) return value.map(toJsonValue).toList();
+  return value;
+}}
+
+/// Relations are included with `true` when no argument is given.
+Object? toIncludeValue(TeoInput value) {{
+  final json = value.toJson();
+  return json.isEmpty ? true : json;
+}}
+
+/// Convert `$date` and `$decimal` objects in a response into `DateTime` and `Decimal`.
+Object? decodeTeon(Object? value) {{
+  if (value is Map<String, dynamic>) {{
+    if (value.containsKey('\$date')) return DateTime.parse(value['\$date'] as String);
+    if (value.containsKey('\$decimal')) return Decimal.parse(value['\$decimal'] as String);
+    return value.map<String, dynamic>((k, v) => MapEntry(k, decodeTeon(v)));
+  }}
+  if (value is List) return value.map(decodeTeon).toList();
+  return value;
+}}
+
+Future<Map<String, dynamic>> request(String urlSegmentName, String action, Map<String, dynamic> args, [String? token]) async {{
+  token ??= getBearerToken();
+  final response = await http.post(
+    Uri.parse('{host}/$urlSegmentName/action/$action'),
+    headers: {{
+      'Content-Type': 'application/json',
+      if (token != null) 'Authorization': 'Bearer $token',
+    }},
+    body: jsonEncode(args),
+  );
+  final json = decodeTeon(jsonDecode(utf8.decode(response.bodyBytes))) as Map<String, dynamic>;
+  if (response.statusCode >= 400) {{
+    throw TeoError.fromJson(json['error'] as Map<String, dynamic>);
+  }}
+  return json;
+}}
+"#)
+}
@@ -0,0 +1,4 @@
+pub(crate) mod lib;
+pub(crate) mod gitignore;
+pub(crate) mod readme;
+pub(crate) mod pubspec_yaml;
@@ -0,0 +1,42 @@
+use std::path::Path;
+use inflector::Inflector;
+
+pub(crate) fn generate_pubspec_yaml(path: &Path) -> String {
+    let name = path.file_name().unwrap().to_str().unwrap().to_snake_case();
+    format!(r#"name: {name}
+description: This package is generated by TEO.
+version: 0.1.0
+publish_to: none
+
+environment:
+  sdk: '>=3.0.0 <4.0.0'
+
+dependencies:
+  decimal: ^2.3.0
+  http: ^1.1.0
+"#)
+}
+
+pub(crate) fn update_pubspec_yaml(content: String) -> String {
+    let mut found = false;
+    let mut lines: Vec<String> = content.lines().map(|line| {
+        if let Some(previous) = line.strip_prefix("version:") {
+            found = true;
+            let previous = previous.trim();
+            let parts: Vec<&str> = previous.split(".").collect();
+            match parts.last().unwrap().parse::<u32>() {
+                Ok(num) => {
+                    let new_version = parts.split_last().unwrap().1.join(".") + "." + &format!("{}", num + 1);
+                    format!("version: {new_version}")
+                },
+                Err(_) => line.to_owned(),
+            }
+        } else {
+            line.to_owned()
+        }
+    }).collect();
+    if !found {
+        lines.insert(lines.len().min(1), "version: 0.1.1".to_owned());
+    }
+    lines.join("\n") + "\n"
+}
@@ -0,0 +1,111 @@
+use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+
+pub(crate) trait ToDartType {
+    fn to_dart_type(&self, optional: bool) -> String;
+    fn to_dart_filter_type(&self, optional: bool) -> String;
+    fn to_dart_create_input_type(&self, optional: bool) -> String;
+    fn to_dart_update_input_type(&self, optional: bool) -> String;
+    fn to_dart_decoder(&self, value: &str) -> String;
+}
+
+impl ToDartType for FieldType {
+    fn to_dart_type(&self, optional: bool) -> String {
+        let base: String = match self {
+            #[cfg(feature = "data-source-mongodb")]
+            FieldType::ObjectId => "String".to_string(),
+            FieldType::String | FieldType::Date => "String".to_string(),
+            FieldType::DateTime => "DateTime".to_string(),
+            FieldType::Bool => "bool".to_string(),
+            FieldType::I32 | FieldType::I64 => "int".to_string(),
+            FieldType::F32 | FieldType::F64 => "double".to_string(),
+            FieldType::Decimal => "Decimal".to_string(),
+            FieldType::Enum(name) => name.to_string(),
+            FieldType::Vec(internal) => format!("List<{}>", internal.field_type().to_dart_type(internal.optionality.is_optional())),
+            FieldType::HashMap(_) => panic!(),
+            FieldType::BTreeMap(_) => panic!(),
+            FieldType::Json => "Object".to_string(),
+            FieldType::Object(name) => name.to_string(),
+        };
+        if optional {
+            base + "?"
+        } else {
+            base
+        }
+    }
+
+    fn to_dart_filter_type(&self, optional: bool) -> String {
+        let nullable = if optional { "Nullable" } else { "" };
+        match self {
+            #[cfg(feature = "data-source-mongodb")]
+            FieldType::ObjectId => format!("{nullable}Filter<String>"),
+            FieldType::String => format!("String{nullable}Filter"),
+            FieldType::Bool => format!("Bool{nullable}Filter"),
+            FieldType::Date | FieldType::DateTime | FieldType::I32 | FieldType::I64 | FieldType::F32 | FieldType::F64 | FieldType::Decimal => {
+                let dart_type = self.to_dart_type(false);
+                format!("{nullable}Filter<{dart_type}>")
+            },
+            FieldType::Enum(name) => format!("Enum{nullable}Filter<{name}>"),
+            FieldType::Vec(internal) => {
+                let internal_type = internal.field_type().to_dart_type(false);
+                format!("Array{nullable}Filter<{internal_type}>")
+            },
+            FieldType::HashMap(_) => panic!(),
+            FieldType::BTreeMap(_) => panic!(),
+            FieldType::Json => format!("{nullable}Filter<Object>"),
+            FieldType::Object(_name) => "Unimplemented".to_string(),
+        }
+    }
+
+    fn to_dart_create_input_type(&self, optional: bool) -> String {
+        self.to_dart_type(optional)
+    }
+
+    fn to_dart_update_input_type(&self, optional: bool) -> String {
+        let question_mark = if optional { "?" } else { "" };
+        match self {
+            FieldType::I32 | FieldType::I64 | FieldType::F32 | FieldType::F64 | FieldType::Decimal => {
+                let number_type = self.to_dart_type(false);
+                format!("NumberFieldUpdateOperationsInput<{number_type}{question_mark}>")
+            },
+            FieldType::Vec(internal) => {
+                let internal_type = internal.field_type().to_dart_type(internal.optionality.is_optional());
+                let nullable = if optional { "Nullable" } else { "" };
+                format!("{nullable}ArrayFieldUpdateOperationsInput<{internal_type}>")
+            },
+            FieldType::HashMap(_) => panic!(),
+            FieldType::BTreeMap(_) => panic!(),
+            _ => {
+                let dart_type = self.to_dart_type(false);
+                format!("FieldUpdateOperationsInput<{dart_type}{question_mark}>")
+            }
+        }
+    }
+
+    /// Dart expression which converts a non-null decoded JSON `value` into this type. Dates and
+    /// decimals are already converted by the runtime when the response is decoded.
+    fn to_dart_decoder(&self, value: &str) -> String {
+        match self {
+            #[cfg(feature = "data-source-mongodb")]
+            FieldType::ObjectId => format!("{value} as String"),
+            FieldType::String | FieldType::Date => format!("{value} as String"),
+            FieldType::DateTime => format!("{value} as DateTime"),
+            FieldType::Bool => format!("{value} as bool"),
+            FieldType::I32 | FieldType::I64 => format!("({value} as num).toInt()"),
+            FieldType::F32 | FieldType::F64 => format!("({value} as num).toDouble()"),
+            FieldType::Decimal => format!("{value} as Decimal"),
+            FieldType::Enum(name) => format!("{name}.values.byName({value} as String)"),
+            FieldType::Vec(internal) => {
+                let item_decoder = internal.field_type().to_dart_decoder("e");
+                if internal.optionality.is_optional() {
+                    format!("({value} as List).map((e) => e == null ? null : {item_decoder}).toList()")
+                } else {
+                    format!("({value} as List).map((e) => {item_decoder}).toList()")
+                }
+            },
+            FieldType::HashMap(_) => panic!(),
+            FieldType::BTreeMap(_) => panic!(),
+            FieldType::Json => format!("{value} as Object"),
+            FieldType::Object(name) => format!("{name}.fromJson({value} as Map<String, dynamic>)"),
+        }
+    }
+}
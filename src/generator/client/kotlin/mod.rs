@@ -1,9 +1,25 @@
+pub mod pkg;
+pub mod r#type;
+
 use async_trait::async_trait;
+use inflector::Inflector;
 use crate::core::app::conf::ClientGeneratorConf;
+use crate::core::graph::Graph;
 use crate::generator::client::ClientGenerator;
+use crate::generator::client::kotlin::pkg::build_gradle_kts::{generate_build_gradle_kts, update_build_gradle_kts};
+use crate::generator::client::kotlin::pkg::gitignore::generate_gitignore_kt;
+use crate::generator::client::kotlin::pkg::readme::generate_readme_kt;
+use crate::generator::client::kotlin::pkg::settings_gradle_kts::generate_settings_gradle_kts;
+use crate::generator::client::kotlin::pkg::src::filter_kt::generate_filter_kt;
+use crate::generator::client::kotlin::pkg::src::index_kt::generate_index_kt;
+use crate::generator::client::kotlin::pkg::src::operation_kt::generate_operation_kt;
+use crate::generator::client::kotlin::pkg::src::runtime_kt::generate_runtime_kt;
 use crate::generator::lib::generator::Generator;
 
-use crate::prelude::Graph;
+/// The Kotlin package of the generated sources is named after the destination directory.
+pub(crate) fn kotlin_package_name(client: &ClientGeneratorConf) -> String {
+    client.dest.file_name().unwrap().to_str().unwrap().to_snake_case()
+}
 
 pub(crate) struct KotlinClientGenerator { }
 
@@ -14,18 +30,32 @@ impl KotlinClientGenerator {
 #[async_trait]
 impl ClientGenerator for KotlinClientGenerator {
     fn module_directory_in_package(&self, _client: &ClientGeneratorConf) -> String {
-        todo!()
+        "src/main/kotlin".to_owned()
     }
 
-    async fn generate_module_files(&self, _graph: &Graph, _client: &ClientGeneratorConf, _generator: &Generator) -> std::io::Result<()> {
-        todo!()
+    async fn generate_module_files(&self, graph: &Graph, client: &ClientGeneratorConf, generator: &Generator) -> std::io::Result<()> {
+        generator.ensure_root_directory().await?;
+        generator.clear_root_directory().await?;
+        generator.generate_file("Runtime.kt", generate_runtime_kt(graph, client).await).await?;
+        generator.generate_file("Filters.kt", generate_filter_kt(graph, client).await).await?;
+        generator.generate_file("Operations.kt", generate_operation_kt(graph, client).await).await
     }
 
-    async fn generate_package_files(&self, _graph: &Graph, _client: &ClientGeneratorConf, _generator: &Generator) -> std::io::Result<()> {
-        todo!()
+    async fn generate_package_files(&self, _graph: &Graph, client: &ClientGeneratorConf, generator: &Generator) -> std::io::Result<()> {
+        generator.ensure_root_directory().await?;
+        generator.generate_file_if_not_exist(".gitignore", generate_gitignore_kt()).await?;
+        generator.generate_file_if_not_exist("README.md", generate_readme_kt(generator.get_base_dir())).await?;
+        generator.generate_file_if_not_exist("settings.gradle.kts", generate_settings_gradle_kts(generator.get_base_dir())).await?;
+        if generator.generate_file_if_not_exist("build.gradle.kts", generate_build_gradle_kts(&kotlin_package_name(client))).await? {
+            // if exist, update build.gradle.kts with a patch version
+            let gradle_data = std::fs::read_to_string(generator.get_file_path("build.gradle.kts"))
+                .expect("Unable to read build.gradle.kts");
+            generator.generate_file("build.gradle.kts", update_build_gradle_kts(gradle_data)).await?;
+        }
+        Ok(())
     }
 
-    async fn generate_main(&self, _graph: &Graph, _client: &ClientGeneratorConf, _generator: &Generator) -> std::io::Result<()> {
-        todo!()
+    async fn generate_main(&self, graph: &Graph, client: &ClientGeneratorConf, generator: &Generator) -> std::io::Result<()> {
+        generator.generate_file("Index.kt", generate_index_kt(graph, client).await).await
     }
 }
@@ -0,0 +1,43 @@
+pub(crate) fn generate_build_gradle_kts(package_name: &str) -> String {
+    format!(r#"plugins {{
+    `java-library`
+    kotlin("jvm") version "1.9.20"
+    kotlin("plugin.serialization") version "1.9.20"
+}}
+
+group = "{package_name}"
+version = "0.1.0"
+
+repositories {{
+    mavenCentral()
+}}
+
+dependencies {{
+    api("org.jetbrains.kotlinx:kotlinx-serialization-json:1.6.0")
+    api("org.jetbrains.kotlinx:kotlinx-coroutines-core:1.7.3")
+}}
+
+kotlin {{
+    jvmToolchain(17)
+}}
+"#)
+}
+
+pub(crate) fn update_build_gradle_kts(content: String) -> String {
+    let lines: Vec<String> = content.lines().map(|line| {
+        if let Some(previous) = line.strip_prefix("version = ") {
+            let previous = previous.trim_matches('"');
+            let parts: Vec<&str> = previous.split(".").collect();
+            match parts.last().unwrap().parse::<u32>() {
+                Ok(num) => {
+                    let new_version = parts.split_last().unwrap().1.join(".") + "." + &format!("{}", num + 1);
+                    format!("version = \"{new_version}\"")
+                },
+                Err(_) => line.to_owned(),
+            }
+        } else {
+            line.to_owned()
+        }
+    }).collect();
+    lines.join("\n") + "\n"
+}
@@ -0,0 +1,3 @@
+pub(crate) fn generate_gitignore_kt() -> String {
+    ".gradle/\nbuild/\n.idea/\nlocal.properties\n".to_owned()
+}
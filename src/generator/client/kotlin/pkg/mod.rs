@@ -0,0 +1,5 @@
+pub(crate) mod src;
+pub(crate) mod gitignore;
+pub(crate) mod readme;
+pub(crate) mod build_gradle_kts;
+pub(crate) mod settings_gradle_kts;
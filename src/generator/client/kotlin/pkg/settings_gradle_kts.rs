@@ -0,0 +1,7 @@
+use std::path::Path;
+use inflector::Inflector;
+
+pub(crate) fn generate_settings_gradle_kts(path: &Path) -> String {
+    let name = path.file_name().unwrap().to_str().unwrap().to_kebab_case();
+    format!("rootProject.name = \"{name}\"\n")
+}
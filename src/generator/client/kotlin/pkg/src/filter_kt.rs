@@ -0,0 +1,263 @@
+use crate::core::app::conf::ClientGeneratorConf;
+use crate::core::graph::Graph;
+use crate::generator::client::kotlin::kotlin_package_name;
+
+pub(crate) async fn generate_filter_kt(_graph: &Graph, conf: &ClientGeneratorConf) -> String {
+    let package_name = kotlin_package_name(conf);
+    format!(r#"package {package_name}
+
+import kotlinx.serialization.json.JsonElement
+
+sealed class Filter<out T> : TeoInput {{
+    data class Equals<T>(val value: T) : Filter<T>() {{
+        override fun toJson(): JsonElement = toJsonValue(value)
+    }}
+
+    data class Where<T>(
+        val equals: T? = null,
+        val `in`: List<T>? = null,
+        val notIn: List<T>? = null,
+        val lt: T? = null,
+        val lte: T? = null,
+        val gt: T? = null,
+        val gte: T? = null,
+        val not: Filter<T>? = null,
+    ) : Filter<T>() {{
+        override fun toJson(): JsonElement = jsonObjectOf(
+            "equals" to equals,
+            "in" to `in`,
+            "notIn" to notIn,
+            "lt" to lt,
+            "lte" to lte,
+            "gt" to gt,
+            "gte" to gte,
+            "not" to not,
+        )
+    }}
+}}
+
+sealed class NullableFilter<out T> : TeoInput {{
+    data class Equals<T>(val value: T?) : NullableFilter<T>() {{
+        override fun toJson(): JsonElement = toJsonValue(value)
+    }}
+
+    data class Where<T>(
+        val equals: T? = null,
+        val `in`: List<T?>? = null,
+        val notIn: List<T?>? = null,
+        val lt: T? = null,
+        val lte: T? = null,
+        val gt: T? = null,
+        val gte: T? = null,
+        val not: NullableFilter<T>? = null,
+    ) : NullableFilter<T>() {{
+        override fun toJson(): JsonElement = jsonObjectOf(
+            "equals" to equals,
+            "in" to `in`,
+            "notIn" to notIn,
+            "lt" to lt,
+            "lte" to lte,
+            "gt" to gt,
+            "gte" to gte,
+            "not" to not,
+        )
+    }}
+}}
+
+sealed class BoolFilter : TeoInput {{
+    data class Equals(val value: Boolean) : BoolFilter() {{
+        override fun toJson(): JsonElement = toJsonValue(value)
+    }}
+
+    data class Where(
+        val equals: Boolean? = null,
+        val not: BoolFilter? = null,
+    ) : BoolFilter() {{
+        override fun toJson(): JsonElement = jsonObjectOf(
+            "equals" to equals,
+            "not" to not,
+        )
+    }}
+}}
+
+sealed class BoolNullableFilter : TeoInput {{
+    data class Equals(val value: Boolean?) : BoolNullableFilter() {{
+        override fun toJson(): JsonElement = toJsonValue(value)
+    }}
+
+    data class Where(
+        val equals: Boolean? = null,
+        val not: BoolNullableFilter? = null,
+    ) : BoolNullableFilter() {{
+        override fun toJson(): JsonElement = jsonObjectOf(
+            "equals" to equals,
+            "not" to not,
+        )
+    }}
+}}
+
+sealed class StringFilter : TeoInput {{
+    data class Equals(val value: String) : StringFilter() {{
+        override fun toJson(): JsonElement = toJsonValue(value)
+    }}
+
+    data class Where(
+        val equals: String? = null,
+        val `in`: List<String>? = null,
+        val notIn: List<String>? = null,
+        val lt: String? = null,
+        val lte: String? = null,
+        val gt: String? = null,
+        val gte: String? = null,
+        val contains: String? = null,
+        val startsWith: String? = null,
+        val endsWith: String? = null,
+        val matches: String? = null,
+        val search: String? = null,
+        val not: StringFilter? = null,
+    ) : StringFilter() {{
+        override fun toJson(): JsonElement = jsonObjectOf(
+            "equals" to equals,
+            "in" to `in`,
+            "notIn" to notIn,
+            "lt" to lt,
+            "lte" to lte,
+            "gt" to gt,
+            "gte" to gte,
+            "contains" to contains,
+            "startsWith" to startsWith,
+            "endsWith" to endsWith,
+            "matches" to matches,
+            "search" to search,
+            "not" to not,
+        )
+    }}
+}}
+
+sealed class StringNullableFilter : TeoInput {{
+    data class Equals(val value: String?) : StringNullableFilter() {{
+        override fun toJson(): JsonElement = toJsonValue(value)
+    }}
+
+    data class Where(
+        val equals: String? = null,
+        val `in`: List<String?>? = null,
+        val notIn: List<String?>? = null,
+        val lt: String? = null,
+        val lte: String? = null,
+        val gt: String? = null,
+        val gte: String? = null,
+        val contains: String? = null,
+        val startsWith: String? = null,
+        val endsWith: String? = null,
+        val matches: String? = null,
+        val search: String? = null,
+        val not: StringNullableFilter? = null,
+    ) : StringNullableFilter() {{
+        override fun toJson(): JsonElement = jsonObjectOf(
+            "equals" to equals,
+            "in" to `in`,
+            "notIn" to notIn,
+            "lt" to lt,
+            "lte" to lte,
+            "gt" to gt,
+            "gte" to gte,
+            "contains" to contains,
+            "startsWith" to startsWith,
+            "endsWith" to endsWith,
+            "matches" to matches,
+            "search" to search,
+            "not" to not,
+        )
+    }}
+}}
+
+sealed class EnumFilter<out T : Enum<*>> : TeoInput {{
+    data class Equals<T : Enum<*>>(val value: T) : EnumFilter<T>() {{
+        override fun toJson(): JsonElement = toJsonValue(value)
+    }}
+
+    data class Where<T : Enum<*>>(
+        val equals: T? = null,
+        val `in`: List<T>? = null,
+        val notIn: List<T>? = null,
+        val not: EnumFilter<T>? = null,
+    ) : EnumFilter<T>() {{
+        override fun toJson(): JsonElement = jsonObjectOf(
+            "equals" to equals,
+            "in" to `in`,
+            "notIn" to notIn,
+            "not" to not,
+        )
+    }}
+}}
+
+sealed class EnumNullableFilter<out T : Enum<*>> : TeoInput {{
+    data class Equals<T : Enum<*>>(val value: T?) : EnumNullableFilter<T>() {{
+        override fun toJson(): JsonElement = toJsonValue(value)
+    }}
+
+    data class Where<T : Enum<*>>(
+        val equals: T? = null,
+        val `in`: List<T?>? = null,
+        val notIn: List<T?>? = null,
+        val not: EnumNullableFilter<T>? = null,
+    ) : EnumNullableFilter<T>() {{
+        override fun toJson(): JsonElement = jsonObjectOf(
+            "equals" to equals,
+            "in" to `in`,
+            "notIn" to notIn,
+            "not" to not,
+        )
+    }}
+}}
+
+sealed class ArrayFilter<out T> : TeoInput {{
+    data class Equals<T>(val value: List<T>) : ArrayFilter<T>() {{
+        override fun toJson(): JsonElement = toJsonValue(value)
+    }}
+
+    data class Where<T>(
+        val equals: List<T>? = null,
+        val has: T? = null,
+        val hasSome: List<T>? = null,
+        val hasEvery: List<T>? = null,
+        val isEmpty: Boolean? = null,
+        val length: Int? = null,
+    ) : ArrayFilter<T>() {{
+        override fun toJson(): JsonElement = jsonObjectOf(
+            "equals" to equals,
+            "has" to has,
+            "hasSome" to hasSome,
+            "hasEvery" to hasEvery,
+            "isEmpty" to isEmpty,
+            "length" to length,
+        )
+    }}
+}}
+
+sealed class ArrayNullableFilter<out T> : TeoInput {{
+    data class Equals<T>(val value: List<T>?) : ArrayNullableFilter<T>() {{
+        override fun toJson(): JsonElement = toJsonValue(value)
+    }}
+
+    data class Where<T>(
+        val equals: List<T>? = null,
+        val has: T? = null,
+        val hasSome: List<T>? = null,
+        val hasEvery: List<T>? = null,
+        val isEmpty: Boolean? = null,
+        val length: Int? = null,
+    ) : ArrayNullableFilter<T>() {{
+        override fun toJson(): JsonElement = jsonObjectOf(
+            "equals" to equals,
+            "has" to has,
+            "hasSome" to hasSome,
+            "hasEvery" to hasEvery,
+            "isEmpty" to isEmpty,
+            "length" to length,
+        )
+    }}
+}}
+"#)
+}
@@ -0,0 +1,671 @@
+use inflector::Inflector;
+use crate::core::action::{Action, CREATE_HANDLER, CREATE_MANY_HANDLER, FIND_FIRST_HANDLER, FIND_MANY_HANDLER, ResData, ResMeta, UPDATE_HANDLER, UPDATE_MANY_HANDLER, UPSERT_HANDLER};
+use crate::core::app::conf::ClientGeneratorConf;
+use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+use crate::core::handler::HandlerType;
+use crate::generator::client::kotlin::kotlin_package_name;
+use crate::generator::client::kotlin::pkg::src::index_kt::doc::{action_doc, action_group_doc, create_or_update_doc, credentials_doc, cursor_doc, refresh_token_doc, field_doc, handler_doc, include_doc, main_object_doc, nested_connect_doc, nested_create_doc, nested_create_or_connect_doc, nested_delete_doc, nested_disconnect_doc, nested_set_doc, nested_update_doc, nested_upsert_doc, order_by_doc, page_number_doc, page_size_doc, relation_doc, select_doc, skip_doc, take_doc, unique_connect_create_doc, unique_connect_doc, unique_where_doc, where_doc, where_doc_first, with_token_doc};
+use crate::generator::client::kotlin::r#type::ToKotlinType;
+use crate::core::graph::Graph;
+use crate::core::model::Model;
+use crate::generator::lib::code::Code;
+
+mod doc;
+
+static RESERVED_WORDS: [&str; 28] = [
+    "as", "break", "class", "continue", "do", "else", "false", "for", "fun", "if", "in",
+    "interface", "is", "null", "object", "package", "return", "super", "this", "throw", "true",
+    "try", "typealias", "typeof", "val", "var", "when", "while",
+];
+
+fn escape(before: impl AsRef<str>) -> String {
+    let before = before.as_ref();
+    if RESERVED_WORDS.contains(&before) {
+        format!("`{before}`")
+    } else {
+        before.to_string()
+    }
+}
+
+struct KotlinClassField {
+    n: String,
+    t: String,
+    o: bool,
+    d: Option<String>,
+    i: bool,
+}
+
+impl KotlinClassField {
+    fn new(n: impl Into<String>, t: impl Into<String>, o: bool, d: Option<String>) -> Self {
+        Self { n: n.into(), t: t.into(), o, d, i: false }
+    }
+}
+
+struct KotlinClassBuilder {
+    name: String,
+    fields: Vec<KotlinClassField>,
+}
+
+impl KotlinClassBuilder {
+    fn build(&self) -> String {
+        Code::new(0, 4, |c| {
+            let class_name = &self.name;
+            if self.fields.is_empty() {
+                c.block(format!("class {class_name} : TeoInput {{"), |b| {
+                    b.line("override fun toJson(): JsonElement = JsonObject(emptyMap())");
+                }, "}");
+                return
+            }
+            c.block(format!("data class {class_name}("), |b| {
+                for f in &self.fields {
+                    if let Some(doc) = &f.d {
+                        b.doc(doc);
+                    }
+                    let field_type = &f.t;
+                    let field_name = escape(&f.n);
+                    if f.o {
+                        b.line(format!("val {field_name}: {field_type}? = null,"));
+                    } else {
+                        b.line(format!("val {field_name}: {field_type},"));
+                    }
+                }
+            }, ") : TeoInput {");
+            c.block("", |b| {
+                b.block("override fun toJson(): JsonElement = jsonObjectOf(", |b| {
+                    for f in &self.fields {
+                        let key = &f.n;
+                        let field_name = escape(&f.n);
+                        if f.i {
+                            b.line(format!("\"{key}\" to {field_name}?.let {{ toIncludeValue(it) }},"));
+                        } else {
+                            b.line(format!("\"{key}\" to {field_name},"));
+                        }
+                    }
+                }, ")");
+            }, "}");
+        }).to_string()
+    }
+}
+
+fn without_title(without: Option<&str>) -> String {
+    if let Some(title) = without {
+        let title = title.to_pascal_case();
+        format!("Without{title}")
+    } else {
+        "".to_owned()
+    }
+}
+
+/// Model objects in handler inputs are accepted in the shape of their create inputs.
+fn handler_input_type(r#type: &HandlerType, graph: &Graph) -> String {
+    match r#type.field_type(graph) {
+        FieldType::Object(name) => format!("{name}CreateInput"),
+        FieldType::Vec(inner) => match inner.field_type() {
+            FieldType::Object(name) => format!("List<{name}CreateInput>"),
+            _ => FieldType::Vec(inner).to_kotlin_type(false),
+        },
+        field_type => field_type.to_kotlin_type(false),
+    }
+}
+
+fn list_if(t: String, many: bool) -> String {
+    if many { format!("List<{t}>") } else { t }
+}
+
+fn generate_model_create_nested_input(model: &Model, without: Option<&str>, many: bool) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    let many_title = if many { "Many" } else { "One" };
+    KotlinClassBuilder {
+        name: format!("{model_name}CreateNested{many_title}{without_title}Input"),
+        fields: vec![
+            KotlinClassField::new("create", list_if(format!("{model_name}Create{without_title}Input"), many), true, Some(nested_create_doc(model, many))),
+            KotlinClassField::new("connectOrCreate", list_if(format!("{model_name}ConnectOrCreate{without_title}Input"), many), true, Some(nested_create_or_connect_doc(model, many))),
+            KotlinClassField::new("connect", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_connect_doc(model, many))),
+        ],
+    }.build()
+}
+
+fn generate_model_create_or_connect_input(model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    KotlinClassBuilder {
+        name: format!("{model_name}ConnectOrCreate{without_title}Input"),
+        fields: vec![
+            KotlinClassField::new("where", format!("{model_name}WhereUniqueInput"), false, Some(unique_connect_doc(model))),
+            KotlinClassField::new("create", format!("{model_name}Create{without_title}Input"), false, Some(unique_connect_create_doc(model))),
+        ],
+    }.build()
+}
+
+fn generate_model_create_input(graph: &Graph, model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    let without_relation = without.map(|title| model.relation(title).unwrap());
+    let mut fields = Vec::<KotlinClassField>::new();
+    model.input_keys().iter().for_each(|k| {
+        if let Some(field) = model.field(k) {
+            if let Some(without_relation) = without_relation {
+                if without_relation.fields().contains(k) { return }
+            }
+            let field_type = field.field_type().to_kotlin_create_input_type(false);
+            fields.push(KotlinClassField::new(field.name(), field_type, true, Some(field_doc(field))));
+        } else if let Some(relation) = model.relation(k) {
+            if let Some(without_relation) = without_relation {
+                if without_relation.name() == k { return }
+            }
+            let relation_model_name = relation.model();
+            let relation_model = graph.model(relation_model_name).unwrap();
+            let num = if relation.is_vec() { "Many" } else { "One" };
+            let without = if let Some(opposite_relation) = relation_model.relations().iter().find(|r| {
+                r.fields() == relation.references() && r.references() == relation.fields()
+            }) {
+                let opposite_relation_name = opposite_relation.name().to_pascal_case();
+                format!("Without{opposite_relation_name}")
+            } else {
+                "".to_owned()
+            };
+            fields.push(KotlinClassField::new(relation.name(), format!("{relation_model_name}CreateNested{num}{without}Input"), true, Some(relation_doc(relation))));
+        }
+    });
+    KotlinClassBuilder { name: format!("{model_name}Create{without_title}Input"), fields }.build()
+}
+
+fn generate_model_upsert_with_where_unique_input(model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    KotlinClassBuilder {
+        name: format!("{model_name}UpsertWithWhereUnique{without_title}Input"),
+        fields: vec![
+            KotlinClassField::new("where", format!("{model_name}WhereUniqueInput"), false, Some(unique_where_doc(model))),
+            KotlinClassField::new("update", format!("{model_name}Update{without_title}Input"), false, Some(create_or_update_doc(model, Action::from_u32(UPDATE_HANDLER)))),
+            KotlinClassField::new("create", format!("{model_name}Create{without_title}Input"), false, Some(create_or_update_doc(model, Action::from_u32(CREATE_HANDLER)))),
+        ],
+    }.build()
+}
+
+fn generate_model_update_with_where_unique_input(model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    KotlinClassBuilder {
+        name: format!("{model_name}UpdateWithWhereUnique{without_title}Input"),
+        fields: vec![
+            KotlinClassField::new("where", format!("{model_name}WhereUniqueInput"), false, Some(unique_where_doc(model))),
+            KotlinClassField::new("update", format!("{model_name}Update{without_title}Input"), false, Some(create_or_update_doc(model, Action::from_u32(UPDATE_HANDLER)))),
+        ],
+    }.build()
+}
+
+fn generate_model_update_many_with_where_input(model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    KotlinClassBuilder {
+        name: format!("{model_name}UpdateManyWithWhere{without_title}Input"),
+        fields: vec![
+            KotlinClassField::new("where", format!("{model_name}WhereInput"), false, Some(where_doc(model))),
+            KotlinClassField::new("update", format!("{model_name}Update{without_title}Input"), false, Some(create_or_update_doc(model, Action::from_u32(UPDATE_MANY_HANDLER)))),
+        ],
+    }.build()
+}
+
+fn generate_model_update_nested_input(model: &Model, without: Option<&str>, many: bool) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    let many_title = if many { "Many" } else { "One" };
+    let mut fields = vec![
+        KotlinClassField::new("create", list_if(format!("{model_name}Create{without_title}Input"), many), true, Some(nested_create_doc(model, many))),
+        KotlinClassField::new("connectOrCreate", list_if(format!("{model_name}ConnectOrCreate{without_title}Input"), many), true, Some(nested_create_or_connect_doc(model, many))),
+        KotlinClassField::new("connect", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_connect_doc(model, many))),
+        KotlinClassField::new("set", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_set_doc(model, many))),
+        KotlinClassField::new("update", list_if(format!("{model_name}UpdateWithWhereUnique{without_title}Input"), many), true, Some(nested_update_doc(model, many))),
+        KotlinClassField::new("upsert", list_if(format!("{model_name}UpsertWithWhereUnique{without_title}Input"), many), true, Some(nested_upsert_doc(model, many))),
+        KotlinClassField::new("disconnect", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_disconnect_doc(model, many))),
+        KotlinClassField::new("delete", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_delete_doc(model, many))),
+    ];
+    if many {
+        fields.push(KotlinClassField::new("updateMany", format!("List<{model_name}UpdateManyWithWhere{without_title}Input>"), true, Some(nested_update_doc(model, many))));
+        fields.push(KotlinClassField::new("deleteMany", format!("List<{model_name}WhereInput>"), true, Some(nested_delete_doc(model, many))));
+    }
+    KotlinClassBuilder { name: format!("{model_name}UpdateNested{many_title}{without_title}Input"), fields }.build()
+}
+
+fn generate_model_update_input(graph: &Graph, model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    let without_relation = without.map(|title| model.relation(title).unwrap());
+    let mut fields = Vec::<KotlinClassField>::new();
+    model.input_keys().iter().for_each(|k| {
+        if let Some(field) = model.field(k) {
+            if let Some(without_relation) = without_relation {
+                if without_relation.fields().contains(k) { return }
+            }
+            let field_type = field.field_type().to_kotlin_update_input_type(field.optionality.is_optional());
+            fields.push(KotlinClassField::new(field.name(), field_type, true, Some(field_doc(field))));
+        } else if let Some(relation) = model.relation(k) {
+            if let Some(without_relation) = without_relation {
+                if without_relation.name() == k { return }
+            }
+            let relation_model_name = relation.model();
+            let relation_model = graph.model(relation_model_name).unwrap();
+            let num = if relation.is_vec() { "Many" } else { "One" };
+            let without = if let Some(opposite_relation) = relation_model.relations().iter().find(|r| {
+                r.fields() == relation.references() && r.references() == relation.fields()
+            }) {
+                let opposite_relation_name = opposite_relation.name().to_pascal_case();
+                format!("Without{opposite_relation_name}")
+            } else {
+                "".to_owned()
+            };
+            fields.push(KotlinClassField::new(relation.name(), format!("{relation_model_name}UpdateNested{num}{without}Input"), true, Some(relation_doc(relation))));
+        }
+    });
+    KotlinClassBuilder { name: format!("{model_name}Update{without_title}Input"), fields }.build()
+}
+
+fn generate_model_credentials_input(model: &Model) -> String {
+    let model_name = model.name();
+    let mut fields = Vec::<KotlinClassField>::new();
+    let auth_identity_keys = model.auth_identity_keys();
+    let auth_by_keys = model.auth_by_keys();
+    let auth_identity_optional = auth_identity_keys.len() != 1;
+    let auth_by_keys_optional = auth_by_keys.len() != 1;
+    for key in auth_identity_keys {
+        let field = model.field(key).unwrap();
+        fields.push(KotlinClassField::new(field.name(), field.field_type().to_kotlin_type(false), auth_identity_optional, Some(field_doc(field))));
+    }
+    for key in auth_by_keys {
+        let field = model.field(key).unwrap();
+        fields.push(KotlinClassField::new(field.name(), field.field_type().to_kotlin_type(false), auth_by_keys_optional, Some(field_doc(field))));
+    }
+    KotlinClassBuilder { name: format!("{model_name}CredentialsInput"), fields }.build()
+}
+
+
+fn generate_model_class(model: &Model) -> String {
+    let model_name = model.name();
+    let mut fields: Vec<(String, String, String)> = vec![];
+    model.output_keys().iter().for_each(|k| {
+        if let Some(field) = model.field(k) {
+            fields.push((field.name().to_owned(), field.field_type().to_kotlin_type(false), field_doc(field)));
+        } else if let Some(relation) = model.relation(k) {
+            let relation_model = relation.model();
+            let relation_type = if relation.is_vec() { format!("List<{relation_model}>") } else { relation_model.to_owned() };
+            fields.push((relation.name().to_owned(), relation_type, relation_doc(relation)));
+        }
+    });
+    Code::new(0, 4, |c| {
+        c.line("@Serializable");
+        if fields.is_empty() {
+            c.line(format!("class {model_name}"));
+            return
+        }
+        c.block(format!("data class {model_name}("), |b| {
+            for (name, t, doc) in &fields {
+                let field_name = escape(name);
+                b.doc(doc);
+                b.line(format!("val {field_name}: {t}? = null,"));
+            }
+        }, ")");
+    }).to_string()
+}
+
+pub(crate) async fn generate_index_kt(graph: &Graph, client: &ClientGeneratorConf) -> String {
+    let package_name = kotlin_package_name(client);
+    let object_name = client.object_name.clone().unwrap_or("teo".to_owned());
+    let mut object_class_name = object_name.to_pascal_case();
+    if object_name == object_class_name { // in case of object name is capitalized
+        object_class_name += "Class";
+    }
+    Code::new(0, 4, |c| {
+        c.line("@file:UseContextualSerialization(OffsetDateTime::class, BigDecimal::class)");
+        c.line("@file:Suppress(\"unused\", \"PropertyName\", \"EnumEntryName\")");
+        c.empty_line();
+        c.line(format!("package {package_name}"));
+        c.empty_line();
+        c.line("import java.math.BigDecimal");
+        c.line("import java.time.OffsetDateTime");
+        c.line("import kotlinx.serialization.Serializable");
+        c.line("import kotlinx.serialization.UseContextualSerialization");
+        c.line("import kotlinx.serialization.json.JsonElement");
+        c.line("import kotlinx.serialization.json.JsonObject");
+        c.line("import kotlinx.serialization.json.decodeFromJsonElement");
+        c.empty_line();
+        // enum definitions
+        let mut enum_names: Vec<&String> = graph.enums().keys().collect();
+        enum_names.sort();
+        for name in enum_names {
+            let choices = graph.enums().get(name).unwrap().values();
+            c.line("@Serializable");
+            c.block(format!("enum class {name} {{"), |b| {
+                for choice in choices {
+                    b.line(format!("{},", escape(choice)));
+                }
+            }, "}");
+            c.empty_line();
+        }
+        // model definitions
+        graph.models().iter().for_each(|m| {
+            c.line(generate_model_class(m));
+        });
+        // model input arguments
+        graph.models().iter().for_each(|m| {
+            let model_name = m.name();
+            // select
+            let mut select_fields = Vec::<KotlinClassField>::new();
+            m.output_keys().iter().for_each(|k| {
+                if let Some(field) = m.field(k) {
+                    select_fields.push(KotlinClassField::new(field.name(), "Boolean", true, Some(field_doc(field))));
+                }
+            });
+            c.line(KotlinClassBuilder { name: format!("{model_name}Select"), fields: select_fields }.build());
+            // include
+            let mut include_fields = Vec::<KotlinClassField>::new();
+            for relation in m.relations() {
+                let r_model = relation.model();
+                let has_find_many = graph.model(r_model).map(|r| r.has_action(Action::from_u32(FIND_MANY_HANDLER))).unwrap_or(false);
+                let find_many = if relation.is_vec() && has_find_many { "FindMany" } else { "" };
+                let mut field = KotlinClassField::new(relation.name(), format!("{r_model}{find_many}Args"), true, Some(relation_doc(relation)));
+                field.i = true;
+                include_fields.push(field);
+            }
+            c.line(KotlinClassBuilder { name: format!("{model_name}Include"), fields: include_fields }.build());
+            // where
+            let mut where_fields = vec![
+                KotlinClassField::new("AND", format!("List<{model_name}WhereInput>"), true, None),
+                KotlinClassField::new("OR", format!("List<{model_name}WhereInput>"), true, None),
+                KotlinClassField::new("NOT", format!("List<{model_name}WhereInput>"), true, None),
+            ];
+            m.query_keys().iter().for_each(|k| {
+                if let Some(field) = m.field(k) {
+                    let field_filter = field.field_type().to_kotlin_filter_type(field.optionality.is_optional());
+                    where_fields.push(KotlinClassField::new(field.name(), field_filter, true, Some(field_doc(field))));
+                } else if let Some(relation) = m.relation(k) {
+                    let list = if relation.is_vec() { "List" } else { "" };
+                    let relation_model = relation.model();
+                    where_fields.push(KotlinClassField::new(relation.name(), format!("{relation_model}{list}RelationFilter"), true, Some(relation_doc(relation))));
+                }
+            });
+            c.line(KotlinClassBuilder { name: format!("{model_name}WhereInput"), fields: where_fields }.build());
+            // where unique
+            let mut where_unique_fields = Vec::<KotlinClassField>::new();
+            let mut used_where_unique_field_names: Vec<&str> = Vec::new();
+            m.indices().iter().for_each(|index| {
+                if index.r#type().is_unique() {
+                    index.items().iter().for_each(|item| {
+                        if !used_where_unique_field_names.contains(&item.field_name()) {
+                            if let Some(field) = m.field(item.field_name()) {
+                                where_unique_fields.push(KotlinClassField::new(field.name(), field.field_type().to_kotlin_type(false), true, Some(field_doc(field))));
+                            }
+                            used_where_unique_field_names.push(item.field_name());
+                        }
+                    });
+                }
+            });
+            c.line(KotlinClassBuilder { name: format!("{model_name}WhereUniqueInput"), fields: where_unique_fields }.build());
+            // relation filter
+            c.line(KotlinClassBuilder {
+                name: format!("{model_name}RelationFilter"),
+                fields: vec![
+                    KotlinClassField::new("is", format!("{model_name}WhereInput"), true, None),
+                    KotlinClassField::new("isNot", format!("{model_name}WhereInput"), true, None),
+                ],
+            }.build());
+            // list relation filter
+            c.line(KotlinClassBuilder {
+                name: format!("{model_name}ListRelationFilter"),
+                fields: vec![
+                    KotlinClassField::new("every", format!("{model_name}WhereInput"), true, None),
+                    KotlinClassField::new("some", format!("{model_name}WhereInput"), true, None),
+                    KotlinClassField::new("none", format!("{model_name}WhereInput"), true, None),
+                ],
+            }.build());
+            // order by
+            let mut order_by_fields = Vec::<KotlinClassField>::new();
+            m.query_keys().iter().for_each(|k| {
+                if let Some(field) = m.field(k) {
+                    order_by_fields.push(KotlinClassField::new(field.name(), "SortOrder", true, Some(field_doc(field))));
+                }
+            });
+            c.line(KotlinClassBuilder { name: format!("{model_name}OrderByInput"), fields: order_by_fields }.build());
+            // create and update inputs without anything
+            c.line(generate_model_create_input(graph, m, None));
+            c.line(generate_model_create_nested_input(m, None, true));
+            c.line(generate_model_create_nested_input(m, None, false));
+            c.line(generate_model_create_or_connect_input(m, None));
+            m.relations().iter().for_each(|r| {
+                c.line(generate_model_create_input(graph, m, Some(r.name())));
+                c.line(generate_model_create_nested_input(m, Some(r.name()), true));
+                c.line(generate_model_create_nested_input(m, Some(r.name()), false));
+                c.line(generate_model_create_or_connect_input(m, Some(r.name())));
+            });
+            c.line(generate_model_update_input(graph, m, None));
+            c.line(generate_model_update_nested_input(m, None, true));
+            c.line(generate_model_update_nested_input(m, None, false));
+            c.line(generate_model_upsert_with_where_unique_input(m, None));
+            c.line(generate_model_update_with_where_unique_input(m, None));
+            c.line(generate_model_update_many_with_where_input(m, None));
+            m.relations().iter().for_each(|r| {
+                c.line(generate_model_update_input(graph, m, Some(r.name())));
+                c.line(generate_model_update_nested_input(m, Some(r.name()), true));
+                c.line(generate_model_update_nested_input(m, Some(r.name()), false));
+                c.line(generate_model_upsert_with_where_unique_input(m, Some(r.name())));
+                c.line(generate_model_update_with_where_unique_input(m, Some(r.name())));
+                c.line(generate_model_update_many_with_where_input(m, Some(r.name())));
+            });
+            if m.identity() {
+                c.line(generate_model_credentials_input(m));
+            }
+            // action args
+            c.line(KotlinClassBuilder {
+                name: format!("{model_name}Args"),
+                fields: vec![
+                    KotlinClassField::new("select", format!("{model_name}Select"), true, Some(select_doc(m))),
+                    KotlinClassField::new("include", format!("{model_name}Include"), true, Some(include_doc(m))),
+                ],
+            }.build());
+            Action::handlers_iter().for_each(|a| {
+                if !m.has_action(*a) { return }
+                let action_name = a.as_handler_str().to_pascal_case();
+                let mut fields = Vec::<KotlinClassField>::new();
+                if a.handler_requires_where() {
+                    let doc = if a == &Action::from_u32(FIND_FIRST_HANDLER) { where_doc_first(m) } else { where_doc(m) };
+                    fields.push(KotlinClassField::new("where", format!("{model_name}WhereInput"), true, Some(doc)));
+                }
+                if a.handler_requires_where_unique() {
+                    fields.push(KotlinClassField::new("where", format!("{model_name}WhereUniqueInput"), true, Some(unique_where_doc(m))));
+                }
+                fields.push(KotlinClassField::new("select", format!("{model_name}Select"), true, Some(select_doc(m))));
+                fields.push(KotlinClassField::new("include", format!("{model_name}Include"), true, Some(include_doc(m))));
+                if a.handler_requires_where() {
+                    fields.push(KotlinClassField::new("orderBy", format!("List<{model_name}OrderByInput>"), true, Some(order_by_doc(m))));
+                    fields.push(KotlinClassField::new("cursor", format!("{model_name}WhereUniqueInput"), true, Some(cursor_doc(m))));
+                    fields.push(KotlinClassField::new("take", "Int", true, Some(take_doc(m))));
+                    fields.push(KotlinClassField::new("skip", "Int", true, Some(skip_doc(m))));
+                    fields.push(KotlinClassField::new("pageSize", "Int", true, Some(page_size_doc(m))));
+                    fields.push(KotlinClassField::new("pageNumber", "Int", true, Some(page_number_doc(m))));
+                }
+                if a.handler_requires_create() {
+                    let doc = create_or_update_doc(m, if a == &Action::from_u32(UPSERT_HANDLER) { Action::from_u32(CREATE_HANDLER) } else { *a });
+                    let create_type = list_if(format!("{model_name}CreateInput"), a == &Action::from_u32(CREATE_MANY_HANDLER));
+                    fields.push(KotlinClassField::new("create", create_type, false, Some(doc)));
+                }
+                if a.handler_requires_update() {
+                    let doc = create_or_update_doc(m, if a == &Action::from_u32(UPSERT_HANDLER) { Action::from_u32(UPDATE_HANDLER) } else { *a });
+                    fields.push(KotlinClassField::new("update", format!("{model_name}UpdateInput"), false, Some(doc)));
+                }
+                if a.handler_requires_credentials() {
+                    fields.push(KotlinClassField::new("credentials", format!("{model_name}CredentialsInput"), false, Some(credentials_doc(m, *a))));
+                }
+                if a.handler_accepts_refresh_token() {
+                    fields.push(KotlinClassField::new("refreshToken", "String", !a.handler_requires_refresh_token(), Some(refresh_token_doc(*a))));
+                }
+                c.line(KotlinClassBuilder { name: format!("{model_name}{action_name}Args"), fields }.build());
+            });
+            m.handlers().iter().for_each(|h| {
+                let handler_name = h.name().to_pascal_case();
+                let fields = h.input().iter().map(|(key, r#type)| {
+                    KotlinClassField::new(key, handler_input_type(r#type, graph), r#type.optional, None)
+                }).collect();
+                c.line(KotlinClassBuilder { name: format!("{model_name}{handler_name}Args"), fields }.build());
+            });
+        });
+        // delegates
+        graph.models().iter().for_each(|m| {
+            if !m.actions().is_empty() || !m.handlers().is_empty() {
+                let model_name = m.name();
+                let model_class_name = model_name.to_camel_case().to_pascal_case();
+                let model_url_segment_name = m.url_segment_name();
+                c.block(format!("class {model_class_name}Delegate internal constructor(private val token: String?) {{"), |b| {
+                    Action::handlers_iter().filter(|a| m.has_action(**a)).enumerate().for_each(|(i, a)| {
+                        let action_url_name = a.as_handler_str();
+                        let action_var_name = action_url_name.to_camel_case();
+                        let action_name = action_url_name.to_pascal_case();
+                        let (res_meta, meta_decoder) = match a.handler_res_meta() {
+                            ResMeta::PagingInfo => ("PagingInfo", "teoJson.decodeFromJsonElement<PagingInfo>(json.getValue(\"meta\"))"),
+                            ResMeta::TokenInfo => ("TokenInfo", "teoJson.decodeFromJsonElement<TokenInfo>(json.getValue(\"meta\"))"),
+                            ResMeta::NoMeta | ResMeta::Other => ("Nothing?", "null"),
+                        };
+                        let res_data = match a.handler_res_data() {
+                            ResData::Single => model_name.to_string(),
+                            ResData::Vec => format!("List<{model_name}>"),
+                            ResData::Number => "Int".to_owned(),
+                            ResData::Other => "JsonElement".to_owned(),
+                        };
+                        let data_decoder = match a.handler_res_data() {
+                            ResData::Other => "json.getValue(\"data\")".to_owned(),
+                            _ => format!("teoJson.decodeFromJsonElement<{res_data}>(json.getValue(\"data\"))"),
+                        };
+                        let args_type = format!("{model_name}{action_name}Args");
+                        let args_required = a.handler_requires_create() || a.handler_requires_update() || a.handler_requires_credentials() || a.handler_requires_refresh_token();
+                        let params = if args_required {
+                            format!("args: {args_type}")
+                        } else {
+                            format!("args: {args_type} = {args_type}()")
+                        };
+                        if i > 0 { b.empty_line(); }
+                        b.doc(action_doc(&object_name, *a, m));
+                        b.block(format!("suspend fun {action_var_name}({params}): Response<{res_meta}, {res_data}> {{"), |b| {
+                            b.line(format!("val json = request(\"{model_url_segment_name}\", \"{action_url_name}\", args.toJson(), token)"));
+                            b.line(format!("return Response({meta_decoder}, {data_decoder})"));
+                        }, "}");
+                    });
+                    let has_actions = !m.actions().is_empty();
+                    m.handlers().iter().enumerate().for_each(|(i, h)| {
+                        let handler_url_name = h.name();
+                        let handler_var_name = handler_url_name.to_camel_case();
+                        let handler_name = handler_url_name.to_pascal_case();
+                        let (res_data, data_decoder) = match h.output() {
+                            Some(r#type) => {
+                                let res_data = r#type.field_type(graph).to_kotlin_type(r#type.optional);
+                                let data_decoder = format!("teoJson.decodeFromJsonElement<{res_data}>(json.getValue(\"data\"))");
+                                (res_data, data_decoder)
+                            }
+                            None => ("JsonElement".to_owned(), "json.getValue(\"data\")".to_owned()),
+                        };
+                        let args_type = format!("{model_name}{handler_name}Args");
+                        let params = if h.input().iter().all(|(_, t)| t.optional) {
+                            format!("args: {args_type} = {args_type}()")
+                        } else {
+                            format!("args: {args_type}")
+                        };
+                        if has_actions || i > 0 { b.empty_line(); }
+                        b.doc(handler_doc(&object_name, h, m));
+                        b.block(format!("suspend fun {}({params}): Response<Nothing?, {res_data}> {{", escape(&handler_var_name)), |b| {
+                            b.line(format!("val json = request(\"{model_url_segment_name}\", \"{handler_url_name}\", args.toJson(), token)"));
+                            b.line(format!("return Response(null, {data_decoder})"));
+                        }, "}");
+                    });
+                }, "}");
+                c.empty_line();
+            }
+        });
+        // main object
+        c.block(format!("class {object_class_name}(private val token: String? = null) {{"), |b| {
+            graph.models().iter().for_each(|m| {
+                if !m.actions().is_empty() || !m.handlers().is_empty() {
+                    let model_name = m.name();
+                    let model_var_name = model_name.to_camel_case();
+                    let model_class_name = model_var_name.to_pascal_case();
+                    b.doc(action_group_doc(&object_name, m));
+                    b.line(format!("val {}: {model_class_name}Delegate get() = {model_class_name}Delegate(token)", escape(&model_var_name)));
+                    b.empty_line();
+                }
+            });
+            b.doc(with_token_doc());
+            b.line(format!("fun withToken(token: String?): {object_class_name} = {object_class_name}(token)"));
+        }, "}");
+        c.empty_line();
+        c.doc(main_object_doc(&object_name, graph));
+        c.line(format!("val {object_name} = {object_class_name}()"));
+    }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use serial_test::serial;
+    use crate::core::app::testing::sqlite_app;
+    use crate::parser::ast::client::ClientLanguage;
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model User {
+  @id @autoIncrement
+  id: Int
+  name: String
+  age: Int?
+  @relation(fields: .id, references: .authorId)
+  posts: Post[]
+}
+
+model Post {
+  @id @autoIncrement
+  id: Int
+  authorId: Int
+  @relation(fields: .authorId, references: .id)
+  author: User
+}
+"#;
+
+    fn conf() -> ClientGeneratorConf {
+        ClientGeneratorConf {
+            name: None,
+            provider: ClientLanguage::Kotlin,
+            dest: PathBuf::from("my-client"),
+            package: false,
+            host: "http://localhost:5100".to_owned(),
+            object_name: None,
+            git_commit: false,
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn package_is_named_after_destination() {
+        let app = sqlite_app(SCHEMA).await;
+        let index = generate_index_kt(app.graph(), &conf()).await;
+        assert!(index.contains("\npackage my_client\n"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn models_are_serializable_data_classes() {
+        let app = sqlite_app(SCHEMA).await;
+        let index = generate_index_kt(app.graph(), &conf()).await;
+        assert!(index.contains("@Serializable\ndata class User("));
+        assert!(index.contains("val age: Int? = null,"));
+        assert!(index.contains("val posts: List<Post>? = null,"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn delegates_request_model_actions() {
+        let app = sqlite_app(SCHEMA).await;
+        let index = generate_index_kt(app.graph(), &conf()).await;
+        assert!(index.contains("suspend fun findMany(args: UserFindManyArgs = UserFindManyArgs()): Response<PagingInfo, List<User>> {"));
+        assert!(index.contains("suspend fun create(args: UserCreateArgs): Response<Nothing?, User> {"));
+        assert!(index.contains("val json = request(\"users\", \"create\", args.toJson(), token)"));
+        assert!(index.contains("val user: UserDelegate get() = UserDelegate(token)"));
+        assert!(index.contains("val teo = Teo()"));
+    }
+}
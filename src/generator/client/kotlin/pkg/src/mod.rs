@@ -0,0 +1,4 @@
+pub(crate) mod index_kt;
+pub(crate) mod runtime_kt;
+pub(crate) mod filter_kt;
+pub(crate) mod operation_kt;
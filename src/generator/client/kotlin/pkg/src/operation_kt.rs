@@ -0,0 +1,42 @@
+use crate::core::app::conf::ClientGeneratorConf;
+use crate::core::graph::Graph;
+use crate::generator::client::kotlin::kotlin_package_name;
+
+pub(crate) async fn generate_operation_kt(_graph: &Graph, conf: &ClientGeneratorConf) -> String {
+    let package_name = kotlin_package_name(conf);
+    format!(r#"package {package_name}
+
+import kotlinx.serialization.json.JsonElement
+import kotlinx.serialization.json.buildJsonObject
+
+sealed class FieldUpdateOperationsInput<out T>(private val operation: String, private val value: Any?) : TeoInput {{
+    class Set<T>(value: T) : FieldUpdateOperationsInput<T>("set", value)
+
+    override fun toJson(): JsonElement = buildJsonObject {{ put(operation, toJsonValue(value)) }}
+}}
+
+sealed class NumberFieldUpdateOperationsInput<out T>(private val operation: String, private val value: Any?) : TeoInput {{
+    class Set<T>(value: T) : NumberFieldUpdateOperationsInput<T>("set", value)
+    class Increment<T>(value: T) : NumberFieldUpdateOperationsInput<T>("increment", value)
+    class Decrement<T>(value: T) : NumberFieldUpdateOperationsInput<T>("decrement", value)
+    class Multiply<T>(value: T) : NumberFieldUpdateOperationsInput<T>("multiply", value)
+    class Divide<T>(value: T) : NumberFieldUpdateOperationsInput<T>("divide", value)
+
+    override fun toJson(): JsonElement = buildJsonObject {{ put(operation, toJsonValue(value)) }}
+}}
+
+sealed class ArrayFieldUpdateOperationsInput<out T>(private val operation: String, private val value: Any?) : TeoInput {{
+    class Set<T>(value: List<T>) : ArrayFieldUpdateOperationsInput<T>("set", value)
+    class Push<T>(value: T) : ArrayFieldUpdateOperationsInput<T>("push", value)
+
+    override fun toJson(): JsonElement = buildJsonObject {{ put(operation, toJsonValue(value)) }}
+}}
+
+sealed class NullableArrayFieldUpdateOperationsInput<out T>(private val operation: String, private val value: Any?) : TeoInput {{
+    class Set<T>(value: List<T>?) : NullableArrayFieldUpdateOperationsInput<T>("set", value)
+    class Push<T>(value: T) : NullableArrayFieldUpdateOperationsInput<T>("push", value)
+
+    override fun toJson(): JsonElement = buildJsonObject {{ put(operation, toJsonValue(value)) }}
+}}
+"#)
+}
@@ -0,0 +1,153 @@
+use crate::core::app::conf::ClientGeneratorConf;
+use crate::core::graph::Graph;
+use crate::generator::client::kotlin::kotlin_package_name;
+
+pub(crate) async fn generate_runtime_kt(_graph: &Graph, conf: &ClientGeneratorConf) -> String {
+    let package_name = kotlin_package_name(conf);
+    let host = conf.host.trim_end_matches('/');
+    format!(r#"package {package_name}
+
+import java.math.BigDecimal
+import java.net.HttpURLConnection
+import java.net.URL
+import java.time.OffsetDateTime
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.withContext
+import kotlinx.serialization.KSerializer
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.descriptors.PrimitiveKind
+import kotlinx.serialization.descriptors.PrimitiveSerialDescriptor
+import kotlinx.serialization.descriptors.SerialDescriptor
+import kotlinx.serialization.encoding.Decoder
+import kotlinx.serialization.encoding.Encoder
+import kotlinx.serialization.json.Json
+import kotlinx.serialization.json.JsonArray
+import kotlinx.serialization.json.JsonDecoder
+import kotlinx.serialization.json.JsonElement
+import kotlinx.serialization.json.JsonNull
+import kotlinx.serialization.json.JsonObject
+import kotlinx.serialization.json.JsonPrimitive
+import kotlinx.serialization.json.buildJsonObject
+import kotlinx.serialization.json.jsonObject
+import kotlinx.serialization.json.jsonPrimitive
+import kotlinx.serialization.modules.SerializersModule
+import kotlinx.serialization.modules.contextual
+
+interface TeoInput {{
+    fun toJson(): JsonElement
+}}
+
+@Serializable
+enum class SortOrder {{
+    asc,
+    desc,
+}}
+
+@Serializable
+data class PagingInfo(val count: Int, val numberOfPages: Int? = null)
+
+@Serializable
+data class TokenInfo(val token: String, val refreshToken: String? = null)
+
+data class Response<out Meta, out Data>(val meta: Meta, val data: Data)
+
+class TeoException(val type: String, message: String, val errors: Map<String, String>?) : Exception(message)
+
+@Serializable
+internal data class TeoErrorBody(val type: String, val message: String, val errors: Map<String, String>? = null)
+
+/** Decodes `$date` objects in responses. */
+object DateTimeSerializer : KSerializer<OffsetDateTime> {{
+    override val descriptor: SerialDescriptor = PrimitiveSerialDescriptor("DateTime", PrimitiveKind.STRING)
+
+    override fun serialize(encoder: Encoder, value: OffsetDateTime) {{
+        encoder.encodeString(value.toInstant().toString())
+    }}
+
+    override fun deserialize(decoder: Decoder): OffsetDateTime {{
+        val element = (decoder as JsonDecoder).decodeJsonElement()
+        val string = if (element is JsonObject) element.getValue("\$date").jsonPrimitive.content else element.jsonPrimitive.content
+        return OffsetDateTime.parse(string)
+    }}
+}}
+
+/** Decodes `$decimal` objects in responses. */
+object DecimalSerializer : KSerializer<BigDecimal> {{
+    override val descriptor: SerialDescriptor = PrimitiveSerialDescriptor("Decimal", PrimitiveKind.STRING)
+
+    override fun serialize(encoder: Encoder, value: BigDecimal) {{
+        encoder.encodeString(value.toPlainString())
+    }}
+
+    override fun deserialize(decoder: Decoder): BigDecimal {{
+        val element = (decoder as JsonDecoder).decodeJsonElement()
+        val string = if (element is JsonObject) element.getValue("\$decimal").jsonPrimitive.content else element.jsonPrimitive.content
+        return BigDecimal(string)
+    }}
+}}
+
+internal val teoJson = Json {{
+    ignoreUnknownKeys = true
+    serializersModule = SerializersModule {{
+        contextual(DateTimeSerializer)
+        contextual(DecimalSerializer)
+    }}
+}}
+
+@Volatile
+private var bearerToken: String? = null
+
+fun setBearerToken(token: String?) {{
+    bearerToken = token
+}}
+
+fun getBearerToken(): String? = bearerToken
+
+fun toJsonValue(value: Any?): JsonElement = when (value) {{
+    null -> JsonNull
+    is JsonElement -> value
+    is TeoInput -> value.toJson()
+    is String -> JsonPrimitive(value)
+    is Boolean -> JsonPrimitive(value)
+    is BigDecimal -> JsonPrimitive(value.toPlainString())
+    is Number -> JsonPrimitive(value)
+    is OffsetDateTime -> JsonPrimitive(value.toInstant().toString())
+    is Enum<*> -> JsonPrimitive(value.name)
+    is List<*> -> JsonArray(value.map {{ toJsonValue(it) }})
+    else -> throw IllegalArgumentException("Cannot convert $value into JSON.")
+}}
+
+/** Relations are included with `true` when no argument is given. */
+fun toIncludeValue(value: TeoInput): JsonElement {{
+    val json = value.toJson()
+    return if (json is JsonObject && json.isEmpty()) JsonPrimitive(true) else json
+}}
+
+internal fun jsonObjectOf(vararg pairs: Pair<String, Any?>): JsonObject = buildJsonObject {{
+    for ((key, value) in pairs) {{
+        if (value != null) put(key, toJsonValue(value))
+    }}
+}}
+
+internal suspend fun request(urlSegmentName: String, action: String, args: JsonElement, token: String? = null): JsonObject = withContext(Dispatchers.IO) {{
+    val connection = URL("{host}/$urlSegmentName/action/$action").openConnection() as HttpURLConnection
+    try {{
+        connection.requestMethod = "POST"
+        connection.doOutput = true
+        connection.setRequestProperty("Content-Type", "application/json")
+        (token ?: getBearerToken())?.let {{ connection.setRequestProperty("Authorization", "Bearer $it") }}
+        connection.outputStream.use {{ it.write(args.toString().toByteArray(Charsets.UTF_8)) }}
+        val status = connection.responseCode
+        val stream = if (status >= 400) connection.errorStream else connection.inputStream
+        val json = teoJson.parseToJsonElement(stream.bufferedReader(Charsets.UTF_8).use {{ it.readText() }}).jsonObject
+        if (status >= 400) {{
+            val error = teoJson.decodeFromJsonElement(TeoErrorBody.serializer(), json.getValue("error"))
+            throw TeoException(error.type, error.message, error.errors)
+        }}
+        json
+    }} finally {{
+        connection.disconnect()
+    }}
+}}
+"#)
+}
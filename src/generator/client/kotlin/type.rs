@@ -0,0 +1,84 @@
+use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+
+pub(crate) trait ToKotlinType {
+    fn to_kotlin_type(&self, optional: bool) -> String;
+    fn to_kotlin_filter_type(&self, optional: bool) -> String;
+    fn to_kotlin_create_input_type(&self, optional: bool) -> String;
+    fn to_kotlin_update_input_type(&self, optional: bool) -> String;
+}
+
+impl ToKotlinType for FieldType {
+    fn to_kotlin_type(&self, optional: bool) -> String {
+        let base: String = match self {
+            #[cfg(feature = "data-source-mongodb")]
+            FieldType::ObjectId => "String".to_string(),
+            FieldType::String | FieldType::Date => "String".to_string(),
+            FieldType::DateTime => "OffsetDateTime".to_string(),
+            FieldType::Bool => "Boolean".to_string(),
+            FieldType::I32 => "Int".to_string(),
+            FieldType::I64 => "Long".to_string(),
+            FieldType::F32 => "Float".to_string(),
+            FieldType::F64 => "Double".to_string(),
+            FieldType::Decimal => "BigDecimal".to_string(),
+            FieldType::Enum(name) => name.to_string(),
+            FieldType::Vec(internal) => format!("List<{}>", internal.field_type().to_kotlin_type(internal.optionality.is_optional())),
+            FieldType::HashMap(_) => panic!(),
+            FieldType::BTreeMap(_) => panic!(),
+            FieldType::Json => "JsonElement".to_string(),
+            FieldType::Object(name) => name.to_string(),
+        };
+        if optional {
+            base + "?"
+        } else {
+            base
+        }
+    }
+
+    fn to_kotlin_filter_type(&self, optional: bool) -> String {
+        let nullable = if optional { "Nullable" } else { "" };
+        match self {
+            #[cfg(feature = "data-source-mongodb")]
+            FieldType::ObjectId => format!("{nullable}Filter<String>"),
+            FieldType::String => format!("String{nullable}Filter"),
+            FieldType::Bool => format!("Bool{nullable}Filter"),
+            FieldType::Date | FieldType::DateTime | FieldType::I32 | FieldType::I64 | FieldType::F32 | FieldType::F64 | FieldType::Decimal => {
+                let kotlin_type = self.to_kotlin_type(false);
+                format!("{nullable}Filter<{kotlin_type}>")
+            },
+            FieldType::Enum(name) => format!("Enum{nullable}Filter<{name}>"),
+            FieldType::Vec(internal) => {
+                let internal_type = internal.field_type().to_kotlin_type(false);
+                format!("Array{nullable}Filter<{internal_type}>")
+            },
+            FieldType::HashMap(_) => panic!(),
+            FieldType::BTreeMap(_) => panic!(),
+            FieldType::Json => format!("{nullable}Filter<JsonElement>"),
+            FieldType::Object(_name) => "Unimplemented".to_string(),
+        }
+    }
+
+    fn to_kotlin_create_input_type(&self, optional: bool) -> String {
+        self.to_kotlin_type(optional)
+    }
+
+    fn to_kotlin_update_input_type(&self, optional: bool) -> String {
+        let question_mark = if optional { "?" } else { "" };
+        match self {
+            FieldType::I32 | FieldType::I64 | FieldType::F32 | FieldType::F64 | FieldType::Decimal => {
+                let number_type = self.to_kotlin_type(false);
+                format!("NumberFieldUpdateOperationsInput<{number_type}{question_mark}>")
+            },
+            FieldType::Vec(internal) => {
+                let internal_type = internal.field_type().to_kotlin_type(internal.optionality.is_optional());
+                let nullable = if optional { "Nullable" } else { "" };
+                format!("{nullable}ArrayFieldUpdateOperationsInput<{internal_type}>")
+            },
+            FieldType::HashMap(_) => panic!(),
+            FieldType::BTreeMap(_) => panic!(),
+            _ => {
+                let kotlin_type = self.to_kotlin_type(false);
+                format!("FieldUpdateOperationsInput<{kotlin_type}{question_mark}>")
+            }
+        }
+    }
+}
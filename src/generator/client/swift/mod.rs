@@ -1,14 +1,24 @@
+pub(crate) mod pkg;
+pub(crate) mod r#type;
+
 use async_trait::async_trait;
+use inflector::Inflector;
 use crate::core::app::conf::ClientGeneratorConf;
-use crate::generator::client::swift::pkg::gitignore::generate_gitignore;
+use crate::generator::client::swift::pkg::gitignore::generate_gitignore_swift;
 use crate::generator::client::swift::pkg::package_swift::generate_package_swift;
 use crate::generator::client::swift::pkg::readme_md::generate_readme_md;
+use crate::generator::client::swift::pkg::sources::filter_swift::generate_filter_swift;
+use crate::generator::client::swift::pkg::sources::index_swift::generate_index_swift;
+use crate::generator::client::swift::pkg::sources::operation_swift::generate_operation_swift;
+use crate::generator::client::swift::pkg::sources::runtime_swift::generate_runtime_swift;
 use crate::core::graph::Graph;
 use crate::generator::client::ClientGenerator;
 use crate::generator::lib::generator::Generator;
 
-
-pub(crate) mod pkg;
+/// The Swift package and its only target are named after the destination directory.
+pub(crate) fn swift_package_name(client: &ClientGeneratorConf) -> String {
+    client.dest.file_name().unwrap().to_str().unwrap().to_pascal_case()
+}
 
 pub(crate) struct SwiftClientGenerator { }
 
@@ -18,26 +28,27 @@ impl SwiftClientGenerator {
 
 #[async_trait]
 impl ClientGenerator for SwiftClientGenerator {
-    fn module_directory_in_package(&self, _client: &ClientGeneratorConf) -> String {
-        return "src".to_owned()
+    fn module_directory_in_package(&self, client: &ClientGeneratorConf) -> String {
+        format!("Sources/{}", swift_package_name(client))
     }
 
-    async fn generate_module_files(&self, _graph: &Graph, _client: &ClientGeneratorConf, generator: &Generator) -> std::io::Result<()> {
+    async fn generate_module_files(&self, graph: &Graph, client: &ClientGeneratorConf, generator: &Generator) -> std::io::Result<()> {
         generator.ensure_root_directory().await?;
         generator.clear_root_directory().await?;
-        Ok(())
+        generator.generate_file("Runtime.swift", generate_runtime_swift(graph, client).await).await?;
+        generator.generate_file("Filters.swift", generate_filter_swift(graph).await).await?;
+        generator.generate_file("Operations.swift", generate_operation_swift(graph).await).await
     }
 
-    async fn generate_package_files(&self, graph: &Graph, _client: &ClientGeneratorConf, generator: &Generator) -> std::io::Result<()> {
+    async fn generate_package_files(&self, _graph: &Graph, client: &ClientGeneratorConf, generator: &Generator) -> std::io::Result<()> {
         generator.ensure_root_directory().await?;
-        generator.clear_root_directory().await?;
-        generator.generate_file("client/swift/README.md", generate_readme_md(graph).await).await?;
-        generator.generate_file("client/swift/.gitignore", generate_gitignore(graph).await).await?;
-        generator.generate_file("client/swift/Package.swift", generate_package_swift(graph).await).await?;
+        generator.generate_file_if_not_exist(".gitignore", generate_gitignore_swift()).await?;
+        generator.generate_file_if_not_exist("README.md", generate_readme_md(generator.get_base_dir())).await?;
+        generator.generate_file_if_not_exist("Package.swift", generate_package_swift(&swift_package_name(client))).await?;
         Ok(())
     }
 
-    async fn generate_main(&self, _graph: &Graph, _client: &ClientGeneratorConf, _generator: &Generator) -> std::io::Result<()> {
-        Ok(())
+    async fn generate_main(&self, graph: &Graph, client: &ClientGeneratorConf, generator: &Generator) -> std::io::Result<()> {
+        generator.generate_file("Index.swift", generate_index_swift(graph, client).await).await
     }
 }
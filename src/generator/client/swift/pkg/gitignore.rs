@@ -1,13 +1,10 @@
-use crate::core::graph::Graph;
-
-
-pub(crate) async fn generate_gitignore(_graph: &Graph) -> String {
-    format!(r#".DS_Store
+pub(crate) fn generate_gitignore_swift() -> String {
+    r#".DS_Store
 /.build
 /Packages
 /*.xcodeproj
 xcuserdata/
 DerivedData/
 .swiftpm/xcode/package.xcworkspace/contents.xcworkspacedata
-"#)
+"#.to_owned()
 }
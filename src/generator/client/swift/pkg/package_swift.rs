@@ -1,25 +1,22 @@
-use crate::core::graph::Graph;
-
-
-pub(crate) async fn generate_package_swift(_graph: &Graph) -> String {
+pub(crate) fn generate_package_swift(package_name: &str) -> String {
     format!(r#"// swift-tools-version:5.5
 
 import PackageDescription
 
 let package = Package(
-    name: "Teo",
+    name: "{package_name}",
     platforms: [
         .macOS(.v12),
         .iOS(.v15)
     ],
     products: [
         .library(
-            name: "Teo",
-            targets: ["Teo"]),
+            name: "{package_name}",
+            targets: ["{package_name}"]),
     ],
     targets: [
         .target(
-            name: "API",
+            name: "{package_name}",
             dependencies: [])
     ]
 )
@@ -1,8 +1,6 @@
-use crate::core::graph::Graph;
+use std::path::Path;
 
-
-pub(crate) async fn generate_readme_md(_graph: &Graph) -> String {
-    format!(r#"# TEO Swift Client
-This project is generated by TEO -- backend framework, Redefined.
-"#)
+pub(crate) fn generate_readme_md(path: &Path) -> String {
+    let last_component = path.file_name().unwrap().to_str().unwrap();
+    format!("{last_component}\n================\n\nThis package is generated by TEO.\n")
 }
@@ -0,0 +1,95 @@
+use crate::core::graph::Graph;
+use crate::generator::lib::code::Code;
+
+/// A filter is either a plain value to compare with, or a set of conditions.
+fn generate_filter(declaration: &str, value_type: &str, conditions: Vec<(&str, String)>) -> String {
+    Code::new(0, 4, |c| {
+        c.block(format!("public indirect enum {declaration}: TeoInput {{"), |b| {
+            b.line(format!("case equals({value_type})"));
+            let parameters: Vec<String> = conditions.iter().map(|(n, t)| format!("{}: {t}? = nil", escape(n))).collect();
+            b.line(format!("case `where`({})", parameters.join(", ")));
+            b.empty_line();
+            b.block("public func toJSON() -> Any {", |b| {
+                b.line("switch self {");
+                b.line("case .equals(let value):");
+                b.block("", |b| b.line("return toJSONValue(value)"), "");
+                let bindings: Vec<String> = conditions.iter().map(|(n, _)| format!("{n}Value")).collect();
+                b.line(format!("case let .`where`({}):", bindings.join(", ")));
+                b.block("", |b| {
+                    b.block("return jsonObject([", |b| {
+                        for (n, _) in &conditions {
+                            b.line(format!("\"{n}\": {n}Value,"));
+                        }
+                    }, "])");
+                }, "");
+                b.line("}");
+            }, "}");
+        }, "}");
+    }).to_string()
+}
+
+fn escape(name: &str) -> String {
+    if name == "in" { format!("`{name}`") } else { name.to_owned() }
+}
+
+fn comparable(t: &str, list_element: &str, not: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("equals", t.to_owned()),
+        ("in", format!("[{list_element}]")),
+        ("notIn", format!("[{list_element}]")),
+        ("lt", t.to_owned()),
+        ("lte", t.to_owned()),
+        ("gt", t.to_owned()),
+        ("gte", t.to_owned()),
+        ("not", not.to_owned()),
+    ]
+}
+
+fn string_conditions(list_element: &str, not: &str) -> Vec<(&'static str, String)> {
+    let mut conditions = comparable("String", list_element, not);
+    let not = conditions.pop().unwrap();
+    conditions.push(("contains", "String".to_owned()));
+    conditions.push(("startsWith", "String".to_owned()));
+    conditions.push(("endsWith", "String".to_owned()));
+    conditions.push(("matches", "String".to_owned()));
+    conditions.push(("search", "String".to_owned()));
+    conditions.push(not);
+    conditions
+}
+
+fn array_conditions() -> Vec<(&'static str, String)> {
+    vec![
+        ("equals", "[T]".to_owned()),
+        ("has", "T".to_owned()),
+        ("hasSome", "[T]".to_owned()),
+        ("hasEvery", "[T]".to_owned()),
+        ("isEmpty", "Bool".to_owned()),
+        ("length", "Int".to_owned()),
+    ]
+}
+
+pub(crate) async fn generate_filter_swift(_graph: &Graph) -> String {
+    let filters = vec![
+        generate_filter("Filter<T>", "T", comparable("T", "T", "Filter<T>")),
+        generate_filter("NullableFilter<T>", "T?", comparable("T", "T?", "NullableFilter<T>")),
+        generate_filter("BoolFilter", "Bool", vec![("equals", "Bool".to_owned()), ("not", "BoolFilter".to_owned())]),
+        generate_filter("BoolNullableFilter", "Bool?", vec![("equals", "Bool".to_owned()), ("not", "BoolNullableFilter".to_owned())]),
+        generate_filter("StringFilter", "String", string_conditions("String", "StringFilter")),
+        generate_filter("StringNullableFilter", "String?", string_conditions("String?", "StringNullableFilter")),
+        generate_filter("EnumFilter<T: TeoEnum>", "T", vec![
+            ("equals", "T".to_owned()),
+            ("in", "[T]".to_owned()),
+            ("notIn", "[T]".to_owned()),
+            ("not", "EnumFilter<T>".to_owned()),
+        ]),
+        generate_filter("EnumNullableFilter<T: TeoEnum>", "T?", vec![
+            ("equals", "T".to_owned()),
+            ("in", "[T?]".to_owned()),
+            ("notIn", "[T?]".to_owned()),
+            ("not", "EnumNullableFilter<T>".to_owned()),
+        ]),
+        generate_filter("ArrayFilter<T>", "[T]", array_conditions()),
+        generate_filter("ArrayNullableFilter<T>", "[T]?", array_conditions()),
+    ];
+    format!("import Foundation\n\n{}", filters.join("\n"))
+}
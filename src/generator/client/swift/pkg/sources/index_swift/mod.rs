@@ -0,0 +1,695 @@
+use inflector::Inflector;
+use crate::core::action::{Action, CREATE_HANDLER, CREATE_MANY_HANDLER, FIND_FIRST_HANDLER, FIND_MANY_HANDLER, ResData, ResMeta, UPDATE_HANDLER, UPDATE_MANY_HANDLER, UPSERT_HANDLER};
+use crate::core::app::conf::ClientGeneratorConf;
+use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+use crate::core::handler::HandlerType;
+use crate::generator::client::swift::pkg::sources::index_swift::doc::{action_doc, action_group_doc, create_or_update_doc, credentials_doc, cursor_doc, refresh_token_doc, field_doc, handler_doc, include_doc, main_object_doc, nested_connect_doc, nested_create_doc, nested_create_or_connect_doc, nested_delete_doc, nested_disconnect_doc, nested_set_doc, nested_update_doc, nested_upsert_doc, order_by_doc, page_number_doc, page_size_doc, relation_doc, select_doc, skip_doc, take_doc, unique_connect_create_doc, unique_connect_doc, unique_where_doc, where_doc, where_doc_first, with_token_doc};
+use crate::generator::client::swift::r#type::ToSwiftType;
+use crate::core::graph::Graph;
+use crate::core::model::Model;
+use crate::generator::lib::code::Code;
+
+mod doc;
+
+static RESERVED_WORDS: [&str; 47] = [
+    "associatedtype", "class", "deinit", "enum", "extension", "fileprivate", "func", "import",
+    "init", "inout", "internal", "let", "open", "operator", "private", "protocol", "public",
+    "rethrows", "static", "struct", "subscript", "typealias", "var", "break", "case", "continue",
+    "default", "defer", "do", "else", "fallthrough", "for", "guard", "if", "in", "repeat",
+    "return", "switch", "where", "while", "as", "catch", "false", "is", "nil", "throw", "true",
+];
+
+fn escape(before: impl AsRef<str>) -> String {
+    let before = before.as_ref();
+    if RESERVED_WORDS.contains(&before) {
+        format!("`{before}`")
+    } else {
+        before.to_string()
+    }
+}
+
+struct SwiftClassField {
+    n: String,
+    t: String,
+    o: bool,
+    d: Option<String>,
+    i: bool,
+}
+
+impl SwiftClassField {
+    fn new(n: impl Into<String>, t: impl Into<String>, o: bool, d: Option<String>) -> Self {
+        Self { n: n.into(), t: t.into(), o, d, i: false }
+    }
+}
+
+struct SwiftClassBuilder {
+    name: String,
+    fields: Vec<SwiftClassField>,
+}
+
+impl SwiftClassBuilder {
+    fn build(&self) -> String {
+        Code::new(0, 4, |c| {
+            let class_name = &self.name;
+            c.block(format!("public final class {class_name}: TeoInput {{"), |b| {
+                if self.fields.is_empty() {
+                    b.line("public init() { }");
+                    b.empty_line();
+                    b.block("public func toJSON() -> Any {", |b| {
+                        b.line("return [String: Any]()");
+                    }, "}");
+                    return
+                }
+                for f in &self.fields {
+                    if let Some(doc) = &f.d {
+                        b.doc(doc);
+                    }
+                    let question_mark = if f.o { "?" } else { "" };
+                    b.line(format!("public var {}: {}{question_mark}", escape(&f.n), &f.t));
+                }
+                b.empty_line();
+                b.block("public init(", |b| {
+                    let count = self.fields.len();
+                    for (index, f) in self.fields.iter().enumerate() {
+                        let default = if f.o { "? = nil" } else { "" };
+                        let comma = if index + 1 < count { "," } else { "" };
+                        b.line(format!("{}: {}{default}{comma}", escape(&f.n), &f.t));
+                    }
+                }, ") {");
+                b.block("", |b| {
+                    for f in &self.fields {
+                        b.line(format!("self.{} = {}", &f.n, escape(&f.n)));
+                    }
+                }, "}");
+                b.empty_line();
+                b.block("public func toJSON() -> Any {", |b| {
+                    b.block("return jsonObject([", |b| {
+                        for f in &self.fields {
+                            let key = &f.n;
+                            let field_name = escape(&f.n);
+                            if f.i {
+                                b.line(format!("\"{key}\": {field_name}.map {{ toIncludeValue($0) }},"));
+                            } else {
+                                b.line(format!("\"{key}\": {field_name},"));
+                            }
+                        }
+                    }, "])");
+                }, "}");
+            }, "}");
+        }).to_string()
+    }
+}
+
+fn without_title(without: Option<&str>) -> String {
+    if let Some(title) = without {
+        let title = title.to_pascal_case();
+        format!("Without{title}")
+    } else {
+        "".to_owned()
+    }
+}
+
+/// Model objects in handler inputs are accepted in the shape of their create inputs.
+fn handler_input_type(r#type: &HandlerType, graph: &Graph) -> String {
+    match r#type.field_type(graph) {
+        FieldType::Object(name) => format!("{name}CreateInput"),
+        FieldType::Vec(inner) => match inner.field_type() {
+            FieldType::Object(name) => format!("[{name}CreateInput]"),
+            _ => FieldType::Vec(inner).to_swift_type(false),
+        },
+        field_type => field_type.to_swift_type(false),
+    }
+}
+
+fn list_if(t: String, many: bool) -> String {
+    if many { format!("[{t}]") } else { t }
+}
+
+fn generate_model_create_nested_input(model: &Model, without: Option<&str>, many: bool) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    let many_title = if many { "Many" } else { "One" };
+    SwiftClassBuilder {
+        name: format!("{model_name}CreateNested{many_title}{without_title}Input"),
+        fields: vec![
+            SwiftClassField::new("create", list_if(format!("{model_name}Create{without_title}Input"), many), true, Some(nested_create_doc(model, many))),
+            SwiftClassField::new("connectOrCreate", list_if(format!("{model_name}ConnectOrCreate{without_title}Input"), many), true, Some(nested_create_or_connect_doc(model, many))),
+            SwiftClassField::new("connect", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_connect_doc(model, many))),
+        ],
+    }.build()
+}
+
+fn generate_model_create_or_connect_input(model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    SwiftClassBuilder {
+        name: format!("{model_name}ConnectOrCreate{without_title}Input"),
+        fields: vec![
+            SwiftClassField::new("where", format!("{model_name}WhereUniqueInput"), false, Some(unique_connect_doc(model))),
+            SwiftClassField::new("create", format!("{model_name}Create{without_title}Input"), false, Some(unique_connect_create_doc(model))),
+        ],
+    }.build()
+}
+
+fn generate_model_create_input(graph: &Graph, model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    let without_relation = without.map(|title| model.relation(title).unwrap());
+    let mut fields = Vec::<SwiftClassField>::new();
+    model.input_keys().iter().for_each(|k| {
+        if let Some(field) = model.field(k) {
+            if let Some(without_relation) = without_relation {
+                if without_relation.fields().contains(k) { return }
+            }
+            let field_type = field.field_type().to_swift_create_input_type(false);
+            fields.push(SwiftClassField::new(field.name(), field_type, true, Some(field_doc(field))));
+        } else if let Some(relation) = model.relation(k) {
+            if let Some(without_relation) = without_relation {
+                if without_relation.name() == k { return }
+            }
+            let relation_model_name = relation.model();
+            let relation_model = graph.model(relation_model_name).unwrap();
+            let num = if relation.is_vec() { "Many" } else { "One" };
+            let without = if let Some(opposite_relation) = relation_model.relations().iter().find(|r| {
+                r.fields() == relation.references() && r.references() == relation.fields()
+            }) {
+                let opposite_relation_name = opposite_relation.name().to_pascal_case();
+                format!("Without{opposite_relation_name}")
+            } else {
+                "".to_owned()
+            };
+            fields.push(SwiftClassField::new(relation.name(), format!("{relation_model_name}CreateNested{num}{without}Input"), true, Some(relation_doc(relation))));
+        }
+    });
+    SwiftClassBuilder { name: format!("{model_name}Create{without_title}Input"), fields }.build()
+}
+
+fn generate_model_upsert_with_where_unique_input(model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    SwiftClassBuilder {
+        name: format!("{model_name}UpsertWithWhereUnique{without_title}Input"),
+        fields: vec![
+            SwiftClassField::new("where", format!("{model_name}WhereUniqueInput"), false, Some(unique_where_doc(model))),
+            SwiftClassField::new("update", format!("{model_name}Update{without_title}Input"), false, Some(create_or_update_doc(model, Action::from_u32(UPDATE_HANDLER)))),
+            SwiftClassField::new("create", format!("{model_name}Create{without_title}Input"), false, Some(create_or_update_doc(model, Action::from_u32(CREATE_HANDLER)))),
+        ],
+    }.build()
+}
+
+fn generate_model_update_with_where_unique_input(model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    SwiftClassBuilder {
+        name: format!("{model_name}UpdateWithWhereUnique{without_title}Input"),
+        fields: vec![
+            SwiftClassField::new("where", format!("{model_name}WhereUniqueInput"), false, Some(unique_where_doc(model))),
+            SwiftClassField::new("update", format!("{model_name}Update{without_title}Input"), false, Some(create_or_update_doc(model, Action::from_u32(UPDATE_HANDLER)))),
+        ],
+    }.build()
+}
+
+fn generate_model_update_many_with_where_input(model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    SwiftClassBuilder {
+        name: format!("{model_name}UpdateManyWithWhere{without_title}Input"),
+        fields: vec![
+            SwiftClassField::new("where", format!("{model_name}WhereInput"), false, Some(where_doc(model))),
+            SwiftClassField::new("update", format!("{model_name}Update{without_title}Input"), false, Some(create_or_update_doc(model, Action::from_u32(UPDATE_MANY_HANDLER)))),
+        ],
+    }.build()
+}
+
+fn generate_model_update_nested_input(model: &Model, without: Option<&str>, many: bool) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    let many_title = if many { "Many" } else { "One" };
+    let mut fields = vec![
+        SwiftClassField::new("create", list_if(format!("{model_name}Create{without_title}Input"), many), true, Some(nested_create_doc(model, many))),
+        SwiftClassField::new("connectOrCreate", list_if(format!("{model_name}ConnectOrCreate{without_title}Input"), many), true, Some(nested_create_or_connect_doc(model, many))),
+        SwiftClassField::new("connect", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_connect_doc(model, many))),
+        SwiftClassField::new("set", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_set_doc(model, many))),
+        SwiftClassField::new("update", list_if(format!("{model_name}UpdateWithWhereUnique{without_title}Input"), many), true, Some(nested_update_doc(model, many))),
+        SwiftClassField::new("upsert", list_if(format!("{model_name}UpsertWithWhereUnique{without_title}Input"), many), true, Some(nested_upsert_doc(model, many))),
+        SwiftClassField::new("disconnect", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_disconnect_doc(model, many))),
+        SwiftClassField::new("delete", list_if(format!("{model_name}WhereUniqueInput"), many), true, Some(nested_delete_doc(model, many))),
+    ];
+    if many {
+        fields.push(SwiftClassField::new("updateMany", format!("[{model_name}UpdateManyWithWhere{without_title}Input]"), true, Some(nested_update_doc(model, many))));
+        fields.push(SwiftClassField::new("deleteMany", format!("[{model_name}WhereInput]"), true, Some(nested_delete_doc(model, many))));
+    }
+    SwiftClassBuilder { name: format!("{model_name}UpdateNested{many_title}{without_title}Input"), fields }.build()
+}
+
+fn generate_model_update_input(graph: &Graph, model: &Model, without: Option<&str>) -> String {
+    let model_name = model.name();
+    let without_title = without_title(without);
+    let without_relation = without.map(|title| model.relation(title).unwrap());
+    let mut fields = Vec::<SwiftClassField>::new();
+    model.input_keys().iter().for_each(|k| {
+        if let Some(field) = model.field(k) {
+            if let Some(without_relation) = without_relation {
+                if without_relation.fields().contains(k) { return }
+            }
+            let field_type = field.field_type().to_swift_update_input_type(field.optionality.is_optional());
+            fields.push(SwiftClassField::new(field.name(), field_type, true, Some(field_doc(field))));
+        } else if let Some(relation) = model.relation(k) {
+            if let Some(without_relation) = without_relation {
+                if without_relation.name() == k { return }
+            }
+            let relation_model_name = relation.model();
+            let relation_model = graph.model(relation_model_name).unwrap();
+            let num = if relation.is_vec() { "Many" } else { "One" };
+            let without = if let Some(opposite_relation) = relation_model.relations().iter().find(|r| {
+                r.fields() == relation.references() && r.references() == relation.fields()
+            }) {
+                let opposite_relation_name = opposite_relation.name().to_pascal_case();
+                format!("Without{opposite_relation_name}")
+            } else {
+                "".to_owned()
+            };
+            fields.push(SwiftClassField::new(relation.name(), format!("{relation_model_name}UpdateNested{num}{without}Input"), true, Some(relation_doc(relation))));
+        }
+    });
+    SwiftClassBuilder { name: format!("{model_name}Update{without_title}Input"), fields }.build()
+}
+
+fn generate_model_credentials_input(model: &Model) -> String {
+    let model_name = model.name();
+    let mut fields = Vec::<SwiftClassField>::new();
+    let auth_identity_keys = model.auth_identity_keys();
+    let auth_by_keys = model.auth_by_keys();
+    let auth_identity_optional = auth_identity_keys.len() != 1;
+    let auth_by_keys_optional = auth_by_keys.len() != 1;
+    for key in auth_identity_keys {
+        let field = model.field(key).unwrap();
+        fields.push(SwiftClassField::new(field.name(), field.field_type().to_swift_type(false), auth_identity_optional, Some(field_doc(field))));
+    }
+    for key in auth_by_keys {
+        let field = model.field(key).unwrap();
+        fields.push(SwiftClassField::new(field.name(), field.field_type().to_swift_type(false), auth_by_keys_optional, Some(field_doc(field))));
+    }
+    SwiftClassBuilder { name: format!("{model_name}CredentialsInput"), fields }.build()
+}
+
+fn generate_model_struct(model: &Model) -> String {
+    let model_name = model.name();
+    Code::new(0, 4, |c| {
+        c.block(format!("public struct {model_name}: Codable {{"), |b| {
+            let mut keys: Vec<&str> = vec![];
+            model.output_keys().iter().for_each(|k| {
+                if let Some(field) = model.field(k) {
+                    b.doc(field_doc(field));
+                    b.line(format!("public var {}: {}", escape(field.name()), field.field_type().to_swift_type(true)));
+                    keys.push(field.name());
+                } else if let Some(relation) = model.relation(k) {
+                    let relation_model = relation.model();
+                    b.doc(relation_doc(relation));
+                    if relation.is_vec() {
+                        b.line(format!("public var {}: [{relation_model}]?", escape(relation.name())));
+                    } else {
+                        b.line(format!("@Indirect public var {}: {relation_model}?", escape(relation.name())));
+                    }
+                    keys.push(relation.name());
+                }
+            });
+            if keys.is_empty() { return }
+            b.empty_line();
+            b.block("enum CodingKeys: String, CodingKey {", |b| {
+                for key in &keys {
+                    b.line(format!("case {}", escape(key)));
+                }
+            }, "}");
+            b.empty_line();
+            b.block("public init(from decoder: Decoder) throws {", |b| {
+                b.line("let container = try decoder.container(keyedBy: CodingKeys.self)");
+                for key in &keys {
+                    let case_name = escape(key);
+                    if let Some(field) = model.field(key) {
+                        let field_type = field.field_type();
+                        let wire_type = field_type.to_swift_wire_type(false);
+                        let decode = field_type.swift_from_wire(&format!("try container.decodeIfPresent({wire_type}.self, forKey: .{case_name})"), true);
+                        b.line(format!("self.{key} = {decode}"));
+                    } else if let Some(relation) = model.relation(key) {
+                        let relation_model = relation.model();
+                        let relation_type = if relation.is_vec() { format!("[{relation_model}]") } else { relation_model.to_owned() };
+                        b.line(format!("self.{key} = try container.decodeIfPresent({relation_type}.self, forKey: .{case_name})"));
+                    }
+                }
+            }, "}");
+            b.empty_line();
+            b.block("public func encode(to encoder: Encoder) throws {", |b| {
+                b.line("var container = encoder.container(keyedBy: CodingKeys.self)");
+                for key in &keys {
+                    let case_name = escape(key);
+                    let value = match model.field(key) {
+                        Some(field) => field.field_type().swift_to_wire(&format!("self.{key}"), true),
+                        None => format!("self.{key}"),
+                    };
+                    b.line(format!("try container.encodeIfPresent({value}, forKey: .{case_name})"));
+                }
+            }, "}");
+        }, "}");
+    }).to_string()
+}
+
+pub(crate) async fn generate_index_swift(graph: &Graph, client: &ClientGeneratorConf) -> String {
+    let object_name = client.object_name.clone().unwrap_or("teo".to_owned());
+    let mut object_class_name = object_name.to_pascal_case();
+    if object_name == object_class_name { // in case of object name is capitalized
+        object_class_name += "Class";
+    }
+    Code::new(0, 4, |c| {
+        c.line("import Foundation");
+        c.empty_line();
+        // enum definitions
+        let mut enum_names: Vec<&String> = graph.enums().keys().collect();
+        enum_names.sort();
+        for name in enum_names {
+            let choices = graph.enums().get(name).unwrap().values();
+            c.block(format!("public enum {name}: String, Codable, TeoEnum {{"), |b| {
+                for choice in choices {
+                    b.line(format!("case {}", escape(choice)));
+                }
+            }, "}");
+            c.empty_line();
+        }
+        // model definitions
+        graph.models().iter().for_each(|m| {
+            c.line(generate_model_struct(m));
+        });
+        // model input arguments
+        graph.models().iter().for_each(|m| {
+            let model_name = m.name();
+            // select
+            let mut select_fields = Vec::<SwiftClassField>::new();
+            m.output_keys().iter().for_each(|k| {
+                if let Some(field) = m.field(k) {
+                    select_fields.push(SwiftClassField::new(field.name(), "Bool", true, Some(field_doc(field))));
+                }
+            });
+            c.line(SwiftClassBuilder { name: format!("{model_name}Select"), fields: select_fields }.build());
+            // include
+            let mut include_fields = Vec::<SwiftClassField>::new();
+            for relation in m.relations() {
+                let r_model = relation.model();
+                let has_find_many = graph.model(r_model).map(|r| r.has_action(Action::from_u32(FIND_MANY_HANDLER))).unwrap_or(false);
+                let find_many = if relation.is_vec() && has_find_many { "FindMany" } else { "" };
+                let mut field = SwiftClassField::new(relation.name(), format!("{r_model}{find_many}Args"), true, Some(relation_doc(relation)));
+                field.i = true;
+                include_fields.push(field);
+            }
+            c.line(SwiftClassBuilder { name: format!("{model_name}Include"), fields: include_fields }.build());
+            // where
+            let mut where_fields = vec![
+                SwiftClassField::new("AND", format!("[{model_name}WhereInput]"), true, None),
+                SwiftClassField::new("OR", format!("[{model_name}WhereInput]"), true, None),
+                SwiftClassField::new("NOT", format!("[{model_name}WhereInput]"), true, None),
+            ];
+            m.query_keys().iter().for_each(|k| {
+                if let Some(field) = m.field(k) {
+                    let field_filter = field.field_type().to_swift_filter_type(field.optionality.is_optional());
+                    where_fields.push(SwiftClassField::new(field.name(), field_filter, true, Some(field_doc(field))));
+                } else if let Some(relation) = m.relation(k) {
+                    let list = if relation.is_vec() { "List" } else { "" };
+                    let relation_model = relation.model();
+                    where_fields.push(SwiftClassField::new(relation.name(), format!("{relation_model}{list}RelationFilter"), true, Some(relation_doc(relation))));
+                }
+            });
+            c.line(SwiftClassBuilder { name: format!("{model_name}WhereInput"), fields: where_fields }.build());
+            // where unique
+            let mut where_unique_fields = Vec::<SwiftClassField>::new();
+            let mut used_where_unique_field_names: Vec<&str> = Vec::new();
+            m.indices().iter().for_each(|index| {
+                if index.r#type().is_unique() {
+                    index.items().iter().for_each(|item| {
+                        if !used_where_unique_field_names.contains(&item.field_name()) {
+                            if let Some(field) = m.field(item.field_name()) {
+                                where_unique_fields.push(SwiftClassField::new(field.name(), field.field_type().to_swift_type(false), true, Some(field_doc(field))));
+                            }
+                            used_where_unique_field_names.push(item.field_name());
+                        }
+                    });
+                }
+            });
+            c.line(SwiftClassBuilder { name: format!("{model_name}WhereUniqueInput"), fields: where_unique_fields }.build());
+            // relation filter
+            c.line(SwiftClassBuilder {
+                name: format!("{model_name}RelationFilter"),
+                fields: vec![
+                    SwiftClassField::new("is", format!("{model_name}WhereInput"), true, None),
+                    SwiftClassField::new("isNot", format!("{model_name}WhereInput"), true, None),
+                ],
+            }.build());
+            // list relation filter
+            c.line(SwiftClassBuilder {
+                name: format!("{model_name}ListRelationFilter"),
+                fields: vec![
+                    SwiftClassField::new("every", format!("{model_name}WhereInput"), true, None),
+                    SwiftClassField::new("some", format!("{model_name}WhereInput"), true, None),
+                    SwiftClassField::new("none", format!("{model_name}WhereInput"), true, None),
+                ],
+            }.build());
+            // order by
+            let mut order_by_fields = Vec::<SwiftClassField>::new();
+            m.query_keys().iter().for_each(|k| {
+                if let Some(field) = m.field(k) {
+                    order_by_fields.push(SwiftClassField::new(field.name(), "SortOrder", true, Some(field_doc(field))));
+                }
+            });
+            c.line(SwiftClassBuilder { name: format!("{model_name}OrderByInput"), fields: order_by_fields }.build());
+            // create and update inputs without anything
+            c.line(generate_model_create_input(graph, m, None));
+            c.line(generate_model_create_nested_input(m, None, true));
+            c.line(generate_model_create_nested_input(m, None, false));
+            c.line(generate_model_create_or_connect_input(m, None));
+            m.relations().iter().for_each(|r| {
+                c.line(generate_model_create_input(graph, m, Some(r.name())));
+                c.line(generate_model_create_nested_input(m, Some(r.name()), true));
+                c.line(generate_model_create_nested_input(m, Some(r.name()), false));
+                c.line(generate_model_create_or_connect_input(m, Some(r.name())));
+            });
+            c.line(generate_model_update_input(graph, m, None));
+            c.line(generate_model_update_nested_input(m, None, true));
+            c.line(generate_model_update_nested_input(m, None, false));
+            c.line(generate_model_upsert_with_where_unique_input(m, None));
+            c.line(generate_model_update_with_where_unique_input(m, None));
+            c.line(generate_model_update_many_with_where_input(m, None));
+            m.relations().iter().for_each(|r| {
+                c.line(generate_model_update_input(graph, m, Some(r.name())));
+                c.line(generate_model_update_nested_input(m, Some(r.name()), true));
+                c.line(generate_model_update_nested_input(m, Some(r.name()), false));
+                c.line(generate_model_upsert_with_where_unique_input(m, Some(r.name())));
+                c.line(generate_model_update_with_where_unique_input(m, Some(r.name())));
+                c.line(generate_model_update_many_with_where_input(m, Some(r.name())));
+            });
+            if m.identity() {
+                c.line(generate_model_credentials_input(m));
+            }
+            // action args
+            c.line(SwiftClassBuilder {
+                name: format!("{model_name}Args"),
+                fields: vec![
+                    SwiftClassField::new("select", format!("{model_name}Select"), true, Some(select_doc(m))),
+                    SwiftClassField::new("include", format!("{model_name}Include"), true, Some(include_doc(m))),
+                ],
+            }.build());
+            Action::handlers_iter().for_each(|a| {
+                if !m.has_action(*a) { return }
+                let action_name = a.as_handler_str().to_pascal_case();
+                let mut fields = Vec::<SwiftClassField>::new();
+                if a.handler_requires_where() {
+                    let doc = if a == &Action::from_u32(FIND_FIRST_HANDLER) { where_doc_first(m) } else { where_doc(m) };
+                    fields.push(SwiftClassField::new("where", format!("{model_name}WhereInput"), true, Some(doc)));
+                }
+                if a.handler_requires_where_unique() {
+                    fields.push(SwiftClassField::new("where", format!("{model_name}WhereUniqueInput"), true, Some(unique_where_doc(m))));
+                }
+                fields.push(SwiftClassField::new("select", format!("{model_name}Select"), true, Some(select_doc(m))));
+                fields.push(SwiftClassField::new("include", format!("{model_name}Include"), true, Some(include_doc(m))));
+                if a.handler_requires_where() {
+                    fields.push(SwiftClassField::new("orderBy", format!("[{model_name}OrderByInput]"), true, Some(order_by_doc(m))));
+                    fields.push(SwiftClassField::new("cursor", format!("{model_name}WhereUniqueInput"), true, Some(cursor_doc(m))));
+                    fields.push(SwiftClassField::new("take", "Int", true, Some(take_doc(m))));
+                    fields.push(SwiftClassField::new("skip", "Int", true, Some(skip_doc(m))));
+                    fields.push(SwiftClassField::new("pageSize", "Int", true, Some(page_size_doc(m))));
+                    fields.push(SwiftClassField::new("pageNumber", "Int", true, Some(page_number_doc(m))));
+                }
+                if a.handler_requires_create() {
+                    let doc = create_or_update_doc(m, if a == &Action::from_u32(UPSERT_HANDLER) { Action::from_u32(CREATE_HANDLER) } else { *a });
+                    let create_type = list_if(format!("{model_name}CreateInput"), a == &Action::from_u32(CREATE_MANY_HANDLER));
+                    fields.push(SwiftClassField::new("create", create_type, false, Some(doc)));
+                }
+                if a.handler_requires_update() {
+                    let doc = create_or_update_doc(m, if a == &Action::from_u32(UPSERT_HANDLER) { Action::from_u32(UPDATE_HANDLER) } else { *a });
+                    fields.push(SwiftClassField::new("update", format!("{model_name}UpdateInput"), false, Some(doc)));
+                }
+                if a.handler_requires_credentials() {
+                    fields.push(SwiftClassField::new("credentials", format!("{model_name}CredentialsInput"), false, Some(credentials_doc(m, *a))));
+                }
+                if a.handler_accepts_refresh_token() {
+                    fields.push(SwiftClassField::new("refreshToken", "String", !a.handler_requires_refresh_token(), Some(refresh_token_doc(*a))));
+                }
+                c.line(SwiftClassBuilder { name: format!("{model_name}{action_name}Args"), fields }.build());
+            });
+            m.handlers().iter().for_each(|h| {
+                let handler_name = h.name().to_pascal_case();
+                let fields = h.input().iter().map(|(key, r#type)| {
+                    SwiftClassField::new(key, handler_input_type(r#type, graph), r#type.optional, None)
+                }).collect();
+                c.line(SwiftClassBuilder { name: format!("{model_name}{handler_name}Args"), fields }.build());
+            });
+        });
+        // delegates
+        graph.models().iter().for_each(|m| {
+            if !m.actions().is_empty() || !m.handlers().is_empty() {
+                let model_name = m.name();
+                let model_class_name = model_name.to_camel_case().to_pascal_case();
+                let model_url_segment_name = m.url_segment_name();
+                c.block(format!("public struct {model_class_name}Delegate {{"), |b| {
+                    b.line("let token: String?");
+                    Action::handlers_iter().filter(|a| m.has_action(**a)).for_each(|a| {
+                        let action_url_name = a.as_handler_str();
+                        let action_var_name = action_url_name.to_camel_case();
+                        let action_name = action_url_name.to_pascal_case();
+                        let res_meta = match a.handler_res_meta() {
+                            ResMeta::PagingInfo => "PagingInfo",
+                            ResMeta::TokenInfo => "TokenInfo",
+                            ResMeta::NoMeta | ResMeta::Other => "NoMeta",
+                        };
+                        let res_data = match a.handler_res_data() {
+                            ResData::Single => model_name.to_string(),
+                            ResData::Vec => format!("[{model_name}]"),
+                            ResData::Number => "Int".to_owned(),
+                            ResData::Other => "JSONValue".to_owned(),
+                        };
+                        let args_type = format!("{model_name}{action_name}Args");
+                        let args_required = a.handler_requires_create() || a.handler_requires_update() || a.handler_requires_credentials() || a.handler_requires_refresh_token();
+                        let params = if args_required {
+                            format!("_ args: {args_type}")
+                        } else {
+                            format!("_ args: {args_type} = {args_type}()")
+                        };
+                        b.empty_line();
+                        b.doc(action_doc(&object_name, *a, m));
+                        b.block(format!("public func {action_var_name}({params}) async throws -> Response<{res_meta}, {res_data}> {{"), |b| {
+                            b.line(format!("return try await request(\"{model_url_segment_name}\", \"{action_url_name}\", args, token: token)"));
+                        }, "}");
+                    });
+                    m.handlers().iter().for_each(|h| {
+                        let handler_url_name = h.name();
+                        let handler_var_name = handler_url_name.to_camel_case();
+                        let handler_name = handler_url_name.to_pascal_case();
+                        // dates and decimals are returned in their wire types
+                        let res_data = match h.output() {
+                            Some(r#type) => r#type.field_type(graph).to_swift_wire_type(r#type.optional),
+                            None => "JSONValue".to_owned(),
+                        };
+                        let args_type = format!("{model_name}{handler_name}Args");
+                        let params = if h.input().iter().all(|(_, t)| t.optional) {
+                            format!("_ args: {args_type} = {args_type}()")
+                        } else {
+                            format!("_ args: {args_type}")
+                        };
+                        b.empty_line();
+                        b.doc(handler_doc(&object_name, h, m));
+                        b.block(format!("public func {}({params}) async throws -> Response<NoMeta, {res_data}> {{", escape(&handler_var_name)), |b| {
+                            b.line(format!("return try await request(\"{model_url_segment_name}\", \"{handler_url_name}\", args, token: token)"));
+                        }, "}");
+                    });
+                }, "}");
+                c.empty_line();
+            }
+        });
+        // main object
+        c.block(format!("public struct {object_class_name} {{"), |b| {
+            b.line("let token: String?");
+            b.empty_line();
+            b.block("public init(token: String? = nil) {", |b| {
+                b.line("self.token = token");
+            }, "}");
+            b.empty_line();
+            graph.models().iter().for_each(|m| {
+                if !m.actions().is_empty() || !m.handlers().is_empty() {
+                    let model_name = m.name();
+                    let model_var_name = model_name.to_camel_case();
+                    let model_class_name = model_var_name.to_pascal_case();
+                    b.doc(action_group_doc(&object_name, m));
+                    b.line(format!("public var {}: {model_class_name}Delegate {{ {model_class_name}Delegate(token: token) }}", escape(&model_var_name)));
+                    b.empty_line();
+                }
+            });
+            b.doc(with_token_doc());
+            b.block(format!("public func withToken(_ token: String?) -> {object_class_name} {{"), |b| {
+                b.line(format!("return {object_class_name}(token: token)"));
+            }, "}");
+        }, "}");
+        c.empty_line();
+        c.doc(main_object_doc(&object_name, graph));
+        c.line(format!("public let {object_name} = {object_class_name}()"));
+    }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use serial_test::serial;
+    use crate::core::app::testing::sqlite_app;
+    use crate::parser::ast::client::ClientLanguage;
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model User {
+  @id @autoIncrement
+  id: Int
+  name: String
+  age: Int?
+  @relation(fields: .id, references: .authorId)
+  posts: Post[]
+}
+
+model Post {
+  @id @autoIncrement
+  id: Int
+  authorId: Int
+  @relation(fields: .authorId, references: .id)
+  author: User
+}
+"#;
+
+    fn conf() -> ClientGeneratorConf {
+        ClientGeneratorConf {
+            name: None,
+            provider: ClientLanguage::Swift,
+            dest: PathBuf::new(),
+            package: false,
+            host: "http://localhost:5100".to_owned(),
+            object_name: None,
+            git_commit: false,
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn models_decode_fields_and_relations() {
+        let app = sqlite_app(SCHEMA).await;
+        let index = generate_index_swift(app.graph(), &conf()).await;
+        assert!(index.contains("public struct User: Codable {"));
+        assert!(index.contains("public var posts: [Post]?"));
+        assert!(index.contains("self.age = try container.decodeIfPresent(Int.self, forKey: .age)"));
+        assert!(index.contains("self.posts = try container.decodeIfPresent([Post].self, forKey: .posts)"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn delegates_request_model_actions() {
+        let app = sqlite_app(SCHEMA).await;
+        let index = generate_index_swift(app.graph(), &conf()).await;
+        assert!(index.contains("public func findMany(_ args: UserFindManyArgs = UserFindManyArgs()) async throws -> Response<PagingInfo, [User]> {"));
+        assert!(index.contains("public func create(_ args: UserCreateArgs) async throws -> Response<NoMeta, User> {"));
+        assert!(index.contains("return try await request(\"users\", \"create\", args, token: token)"));
+        assert!(index.contains("public var user: UserDelegate { UserDelegate(token: token) }"));
+        assert!(index.contains("public let teo = Teo()"));
+    }
+}
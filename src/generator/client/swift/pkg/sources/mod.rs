@@ -0,0 +1,4 @@
+pub(crate) mod index_swift;
+pub(crate) mod runtime_swift;
+pub(crate) mod filter_swift;
+pub(crate) mod operation_swift;
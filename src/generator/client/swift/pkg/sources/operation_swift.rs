@@ -0,0 +1,58 @@
+use crate::core::graph::Graph;
+
+pub(crate) async fn generate_operation_swift(_graph: &Graph) -> String {
+    r#"import Foundation
+
+public enum FieldUpdateOperationsInput<T>: TeoInput {
+    case set(T)
+
+    public func toJSON() -> Any {
+        switch self {
+        case .set(let value): return ["set": toJSONValue(value)]
+        }
+    }
+}
+
+public enum NumberFieldUpdateOperationsInput<T>: TeoInput {
+    case set(T)
+    case increment(T)
+    case decrement(T)
+    case multiply(T)
+    case divide(T)
+
+    public func toJSON() -> Any {
+        switch self {
+        case .set(let value): return ["set": toJSONValue(value)]
+        case .increment(let value): return ["increment": toJSONValue(value)]
+        case .decrement(let value): return ["decrement": toJSONValue(value)]
+        case .multiply(let value): return ["multiply": toJSONValue(value)]
+        case .divide(let value): return ["divide": toJSONValue(value)]
+        }
+    }
+}
+
+public enum ArrayFieldUpdateOperationsInput<T>: TeoInput {
+    case set([T])
+    case push(T)
+
+    public func toJSON() -> Any {
+        switch self {
+        case .set(let value): return ["set": toJSONValue(value)]
+        case .push(let value): return ["push": toJSONValue(value)]
+        }
+    }
+}
+
+public enum NullableArrayFieldUpdateOperationsInput<T>: TeoInput {
+    case set([T]?)
+    case push(T)
+
+    public func toJSON() -> Any {
+        switch self {
+        case .set(let value): return ["set": toJSONValue(value)]
+        case .push(let value): return ["push": toJSONValue(value)]
+        }
+    }
+}
+"#.to_owned()
+}
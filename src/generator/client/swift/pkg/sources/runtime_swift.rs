@@ -0,0 +1,251 @@
+use crate::core::app::conf::ClientGeneratorConf;
+use crate::core::graph::Graph;
+
+pub(crate) async fn generate_runtime_swift(_graph: &Graph, conf: &ClientGeneratorConf) -> String {
+    let host = conf.host.trim_end_matches('/');
+    format!(r#"import Foundation
+
+public protocol TeoInput {{
+    func toJSON() -> Any
+}}
+
+public protocol TeoEnum {{
+    var rawValue: String {{ get }}
+}}
+
+public enum SortOrder: String, Codable, TeoEnum {{
+    case asc
+    case desc
+}}
+
+public struct PagingInfo: Codable {{
+    public let count: Int
+    public let numberOfPages: Int?
+}}
+
+public struct TokenInfo: Codable {{
+    public let token: String
+    public let refreshToken: String?
+}}
+
+public struct NoMeta: Codable {{ }}
+
+public struct Response<M: Decodable, D: Decodable>: Decodable {{
+    public let meta: M?
+    public let data: D
+}}
+
+public struct TeoError: Error {{
+    public let type: String
+    public let message: String
+    public let errors: [String: String]?
+}}
+
+struct TeoErrorBody: Decodable {{
+    let error: TeoErrorContent
+}}
+
+struct TeoErrorContent: Decodable {{
+    let type: String
+    let message: String
+    let errors: [String: String]?
+}}
+
+/// Any JSON value, used for responses without a generated type.
+public enum JSONValue: Codable {{
+    case null
+    case bool(Bool)
+    case number(Double)
+    case string(String)
+    case array([JSONValue])
+    case object([String: JSONValue])
+
+    public init(from decoder: Decoder) throws {{
+        let container = try decoder.singleValueContainer()
+        if container.decodeNil() {{
+            self = .null
+        }} else if let value = try? container.decode(Bool.self) {{
+            self = .bool(value)
+        }} else if let value = try? container.decode(Double.self) {{
+            self = .number(value)
+        }} else if let value = try? container.decode(String.self) {{
+            self = .string(value)
+        }} else if let value = try? container.decode([JSONValue].self) {{
+            self = .array(value)
+        }} else {{
+            self = .object(try container.decode([String: JSONValue].self))
+        }}
+    }}
+
+    public func encode(to encoder: Encoder) throws {{
+        var container = encoder.singleValueContainer()
+        switch self {{
+        case .null: try container.encodeNil()
+        case .bool(let value): try container.encode(value)
+        case .number(let value): try container.encode(value)
+        case .string(let value): try container.encode(value)
+        case .array(let value): try container.encode(value)
+        case .object(let value): try container.encode(value)
+        }}
+    }}
+}}
+
+private enum WireKey: String, CodingKey {{
+    case date = "$date"
+    case decimal = "$decimal"
+}}
+
+private func formatDate(_ date: Date) -> String {{
+    let formatter = ISO8601DateFormatter()
+    formatter.formatOptions = [.withInternetDateTime, .withFractionalSeconds]
+    return formatter.string(from: date)
+}}
+
+private func parseDate(_ string: String) -> Date? {{
+    let formatter = ISO8601DateFormatter()
+    formatter.formatOptions = [.withInternetDateTime, .withFractionalSeconds]
+    if let date = formatter.date(from: string) {{
+        return date
+    }}
+    formatter.formatOptions = [.withInternetDateTime]
+    return formatter.date(from: string)
+}}
+
+/// Decodes `$date` objects in responses.
+public struct TeoDate: Codable {{
+    public let value: Date
+
+    public init(value: Date) {{
+        self.value = value
+    }}
+
+    public init(from decoder: Decoder) throws {{
+        let container = try decoder.container(keyedBy: WireKey.self)
+        let string = try container.decode(String.self, forKey: .date)
+        guard let date = parseDate(string) else {{
+            throw DecodingError.dataCorruptedError(forKey: .date, in: container, debugDescription: "Invalid date '\(string)'.")
+        }}
+        value = date
+    }}
+
+    public func encode(to encoder: Encoder) throws {{
+        var container = encoder.container(keyedBy: WireKey.self)
+        try container.encode(formatDate(value), forKey: .date)
+    }}
+}}
+
+/// Decodes `$decimal` objects in responses.
+public struct TeoDecimal: Codable {{
+    public let value: Decimal
+
+    public init(value: Decimal) {{
+        self.value = value
+    }}
+
+    public init(from decoder: Decoder) throws {{
+        let container = try decoder.container(keyedBy: WireKey.self)
+        let string = try container.decode(String.self, forKey: .decimal)
+        guard let decimal = Decimal(string: string) else {{
+            throw DecodingError.dataCorruptedError(forKey: .decimal, in: container, debugDescription: "Invalid decimal '\(string)'.")
+        }}
+        value = decimal
+    }}
+
+    public func encode(to encoder: Encoder) throws {{
+        var container = encoder.container(keyedBy: WireKey.self)
+        try container.encode(NSDecimalNumber(decimal: value).stringValue, forKey: .decimal)
+    }}
+}}
+
+/// Stores a value out of line, so that models can hold related objects of their own type.
+@propertyWrapper
+public enum Indirect<T> {{
+    indirect case wrapped(T)
+
+    public init(wrappedValue: T) {{
+        self = .wrapped(wrappedValue)
+    }}
+
+    public var wrappedValue: T {{
+        get {{
+            switch self {{
+            case .wrapped(let value): return value
+            }}
+        }}
+        set {{
+            self = .wrapped(newValue)
+        }}
+    }}
+}}
+
+private let bearerTokenLock = NSLock()
+private var bearerToken: String? = nil
+
+public func setBearerToken(_ token: String?) {{
+    bearerTokenLock.lock()
+    bearerToken = token
+    bearerTokenLock.unlock()
+}}
+
+public func getBearerToken() -> String? {{
+    bearerTokenLock.lock()
+    defer {{ bearerTokenLock.unlock() }}
+    return bearerToken
+}}
+
+public func toJSONValue(_ value: Any?) -> Any {{
+    guard let value = value else {{
+        return NSNull()
+    }}
+    // generic values may hold optionals themselves
+    let mirror = Mirror(reflecting: value)
+    if mirror.displayStyle == .optional {{
+        return toJSONValue(mirror.children.first?.value)
+    }}
+    switch value {{
+    case let value as TeoInput: return value.toJSON()
+    case let value as TeoEnum: return value.rawValue
+    case let value as Date: return formatDate(value)
+    case let value as Decimal: return NSDecimalNumber(decimal: value).stringValue
+    case let value as [Any?]: return value.map {{ toJSONValue($0) }}
+    default: return value
+    }}
+}}
+
+/// Relations are included with `true` when no argument is given.
+public func toIncludeValue(_ value: TeoInput) -> Any {{
+    let json = value.toJSON()
+    if let object = json as? [String: Any], object.isEmpty {{
+        return true
+    }}
+    return json
+}}
+
+func jsonObject(_ pairs: [String: Any?]) -> [String: Any] {{
+    var result: [String: Any] = [:]
+    for (key, value) in pairs {{
+        if let value = value {{
+            result[key] = toJSONValue(value)
+        }}
+    }}
+    return result
+}}
+
+func request<M: Decodable, D: Decodable>(_ urlSegmentName: String, _ action: String, _ args: TeoInput, token: String?) async throws -> Response<M, D> {{
+    var request = URLRequest(url: URL(string: "{host}/\(urlSegmentName)/action/\(action)")!)
+    request.httpMethod = "POST"
+    request.setValue("application/json", forHTTPHeaderField: "Content-Type")
+    if let token = token ?? getBearerToken() {{
+        request.setValue("Bearer \(token)", forHTTPHeaderField: "Authorization")
+    }}
+    request.httpBody = try JSONSerialization.data(withJSONObject: args.toJSON())
+    let (data, response) = try await URLSession.shared.data(for: request)
+    let status = (response as? HTTPURLResponse)?.statusCode ?? 0
+    if status >= 400 {{
+        let error = try JSONDecoder().decode(TeoErrorBody.self, from: data).error
+        throw TeoError(type: error.type, message: error.message, errors: error.errors)
+    }}
+    return try JSONDecoder().decode(Response<M, D>.self, from: data)
+}}
+"#)
+}
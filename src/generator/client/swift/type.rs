@@ -0,0 +1,139 @@
+use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+
+pub(crate) trait ToSwiftType {
+    fn to_swift_type(&self, optional: bool) -> String;
+    fn to_swift_filter_type(&self, optional: bool) -> String;
+    fn to_swift_create_input_type(&self, optional: bool) -> String;
+    fn to_swift_update_input_type(&self, optional: bool) -> String;
+    /// The type decoded from and encoded into JSON, dates and decimals are wrapped objects.
+    fn to_swift_wire_type(&self, optional: bool) -> String;
+    fn has_swift_wire_type(&self) -> bool;
+    fn swift_from_wire(&self, expr: &str, optional: bool) -> String;
+    fn swift_to_wire(&self, expr: &str, optional: bool) -> String;
+}
+
+impl ToSwiftType for FieldType {
+    fn to_swift_type(&self, optional: bool) -> String {
+        let base: String = match self {
+            #[cfg(feature = "data-source-mongodb")]
+            FieldType::ObjectId => "String".to_string(),
+            FieldType::String | FieldType::Date => "String".to_string(),
+            FieldType::DateTime => "Date".to_string(),
+            FieldType::Bool => "Bool".to_string(),
+            FieldType::I32 => "Int".to_string(),
+            FieldType::I64 => "Int64".to_string(),
+            FieldType::F32 => "Float".to_string(),
+            FieldType::F64 => "Double".to_string(),
+            FieldType::Decimal => "Decimal".to_string(),
+            FieldType::Enum(name) => name.to_string(),
+            FieldType::Vec(internal) => format!("[{}]", internal.field_type().to_swift_type(internal.optionality.is_optional())),
+            FieldType::HashMap(_) => panic!(),
+            FieldType::BTreeMap(_) => panic!(),
+            FieldType::Json => "JSONValue".to_string(),
+            FieldType::Object(name) => name.to_string(),
+        };
+        if optional {
+            base + "?"
+        } else {
+            base
+        }
+    }
+
+    fn to_swift_filter_type(&self, optional: bool) -> String {
+        let nullable = if optional { "Nullable" } else { "" };
+        match self {
+            #[cfg(feature = "data-source-mongodb")]
+            FieldType::ObjectId => format!("{nullable}Filter<String>"),
+            FieldType::String => format!("String{nullable}Filter"),
+            FieldType::Bool => format!("Bool{nullable}Filter"),
+            FieldType::Date | FieldType::DateTime | FieldType::I32 | FieldType::I64 | FieldType::F32 | FieldType::F64 | FieldType::Decimal => {
+                let swift_type = self.to_swift_type(false);
+                format!("{nullable}Filter<{swift_type}>")
+            },
+            FieldType::Enum(name) => format!("Enum{nullable}Filter<{name}>"),
+            FieldType::Vec(internal) => {
+                let internal_type = internal.field_type().to_swift_type(false);
+                format!("Array{nullable}Filter<{internal_type}>")
+            },
+            FieldType::HashMap(_) => panic!(),
+            FieldType::BTreeMap(_) => panic!(),
+            FieldType::Json => format!("{nullable}Filter<JSONValue>"),
+            FieldType::Object(_name) => "Unimplemented".to_string(),
+        }
+    }
+
+    fn to_swift_create_input_type(&self, optional: bool) -> String {
+        self.to_swift_type(optional)
+    }
+
+    fn to_swift_update_input_type(&self, optional: bool) -> String {
+        let question_mark = if optional { "?" } else { "" };
+        match self {
+            FieldType::I32 | FieldType::I64 | FieldType::F32 | FieldType::F64 | FieldType::Decimal => {
+                let number_type = self.to_swift_type(false);
+                format!("NumberFieldUpdateOperationsInput<{number_type}{question_mark}>")
+            },
+            FieldType::Vec(internal) => {
+                let internal_type = internal.field_type().to_swift_type(internal.optionality.is_optional());
+                let nullable = if optional { "Nullable" } else { "" };
+                format!("{nullable}ArrayFieldUpdateOperationsInput<{internal_type}>")
+            },
+            FieldType::HashMap(_) => panic!(),
+            FieldType::BTreeMap(_) => panic!(),
+            _ => {
+                let swift_type = self.to_swift_type(false);
+                format!("FieldUpdateOperationsInput<{swift_type}{question_mark}>")
+            }
+        }
+    }
+
+    fn to_swift_wire_type(&self, optional: bool) -> String {
+        let base = match self {
+            FieldType::DateTime => "TeoDate".to_string(),
+            FieldType::Decimal => "TeoDecimal".to_string(),
+            FieldType::Vec(internal) => format!("[{}]", internal.field_type().to_swift_wire_type(internal.optionality.is_optional())),
+            _ => return self.to_swift_type(optional),
+        };
+        if optional {
+            base + "?"
+        } else {
+            base
+        }
+    }
+
+    fn has_swift_wire_type(&self) -> bool {
+        match self {
+            FieldType::DateTime | FieldType::Decimal => true,
+            FieldType::Vec(internal) => internal.field_type().has_swift_wire_type(),
+            _ => false,
+        }
+    }
+
+    fn swift_from_wire(&self, expr: &str, optional: bool) -> String {
+        if !self.has_swift_wire_type() {
+            return expr.to_owned();
+        }
+        if optional {
+            return format!("{expr}.map {{ {} }}", self.swift_from_wire("$0", false));
+        }
+        match self {
+            FieldType::Vec(internal) => format!("{expr}.map {{ {} }}", internal.field_type().swift_from_wire("$0", internal.optionality.is_optional())),
+            _ => format!("{expr}.value"),
+        }
+    }
+
+    fn swift_to_wire(&self, expr: &str, optional: bool) -> String {
+        if !self.has_swift_wire_type() {
+            return expr.to_owned();
+        }
+        if optional {
+            return format!("{expr}.map {{ {} }}", self.swift_to_wire("$0", false));
+        }
+        match self {
+            FieldType::DateTime => format!("TeoDate(value: {expr})"),
+            FieldType::Decimal => format!("TeoDecimal(value: {expr})"),
+            FieldType::Vec(internal) => format!("{expr}.map {{ {} }}", internal.field_type().swift_to_wire("$0", internal.optionality.is_optional())),
+            _ => unreachable!(),
+        }
+    }
+}
@@ -2,6 +2,7 @@ use crate::core::graph::Graph;
 
 pub(crate) async fn generate_filter_d_ts(_graph: &Graph) -> String {
     format!(r#"import Decimal from "./decimal"
+import {{ JsonValue }} from "./runtime"
 
 export type ObjectIdFilter = {{
     equals?: string
@@ -91,6 +92,7 @@ export type StringFilter = {{
     startsWith?: string
     endsWith?: string
     matches?: string
+    search?: string
     not?: StringFilter | string
 }}
 
@@ -106,6 +108,7 @@ export type StringNullableFilter = {{
     startsWith?: string
     endsWith?: string
     matches?: string
+    search?: string
     not?: StringNullableFilter | string | null
 }}
 
@@ -184,5 +187,19 @@ export type ArrayNullableFilter<T> = {{
     isEmpty?: boolean
     length?: number
 }}
+
+export type JsonFilter = {{
+    path?: (string | number)[]
+    equals?: JsonValue
+    arrayContains?: JsonValue
+    stringContains?: string
+}}
+
+export type JsonNullableFilter = {{
+    path?: (string | number)[]
+    equals?: JsonValue | null
+    arrayContains?: JsonValue
+    stringContains?: string
+}}
 "#)
 }
@@ -1,8 +1,9 @@
 use inflector::Inflector;
 use crate::core::action::{ResMeta, ResData, Action, UPDATE_HANDLER, CREATE_HANDLER, FIND_FIRST_HANDLER, UPSERT_HANDLER, UPDATE_MANY_HANDLER};
 use crate::core::app::conf::ClientGeneratorConf;
-use crate::core::field::r#type::FieldTypeOwner;
-use crate::generator::client::typescript::pkg::src::index_d_ts::docs::{action_doc, action_group_doc, create_or_update_doc, credentials_doc, cursor_doc, field_doc, include_doc, main_object_doc, nested_connect_doc, nested_create_doc, nested_create_or_connect_doc, nested_delete_doc, nested_disconnect_doc, nested_set_doc, nested_update_doc, nested_upsert_doc, order_by_doc, page_number_doc, page_size_doc, relation_doc, select_doc, skip_doc, take_doc, unique_connect_create_doc, unique_connect_doc, unique_where_doc, where_doc, where_doc_first, with_token_doc};
+use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+use crate::core::handler::HandlerType;
+use crate::generator::client::typescript::pkg::src::index_d_ts::docs::{action_doc, action_group_doc, handler_doc, create_or_update_doc, credentials_doc, cursor_doc, refresh_token_doc, field_doc, include_doc, main_object_doc, nested_connect_doc, nested_create_doc, nested_create_or_connect_doc, nested_delete_doc, nested_disconnect_doc, nested_set_doc, nested_update_doc, nested_upsert_doc, only_deleted_doc, order_by_doc, page_number_doc, page_size_doc, relation_doc, select_doc, skip_doc, take_doc, unique_connect_create_doc, unique_connect_doc, unique_where_doc, where_doc, where_doc_first, with_deleted_doc, with_token_doc};
 use crate::generator::client::typescript::r#type::ToTypeScriptType;
 
 use crate::core::graph::Graph;
@@ -318,14 +319,26 @@ fn generate_model_credentials_input(model: &Model) -> String {
     }).to_string()
 }
 
+/// Model objects in handler inputs are accepted in the shape of their create inputs.
+fn handler_input_type(r#type: &HandlerType, graph: &Graph) -> String {
+    match r#type.field_type(graph) {
+        FieldType::Object(name) => format!("{name}CreateInput"),
+        FieldType::Vec(inner) => match inner.field_type() {
+            FieldType::Object(name) => format!("{name}CreateInput[]"),
+            _ => FieldType::Vec(inner).to_typescript_type(false),
+        },
+        field_type => field_type.to_typescript_type(false),
+    }
+}
+
 pub(crate) async fn generate_index_d_ts(graph: &Graph, client: &ClientGeneratorConf) -> String {
     Code::new(0, 4, |c| {
-        c.line(r#"import { Response, PagingInfo, TokenInfo, SortOrder, Enumerable, CheckSelectInclude, SelectSubset, ExistKeys, ResponseError } from "./runtime""#);
+        c.line(r#"import { Response, PagingInfo, TokenInfo, SortOrder, Enumerable, CheckSelectInclude, SelectSubset, ExistKeys, ResponseError, JsonValue } from "./runtime""#);
         c.block("import {", |b| {
             b.line("ObjectIdFilter, ObjectIdNullableFilter, StringFilter, StringNullableFilter, NumberFilter,");
             b.line("NumberNullableFilter, DecimalFilter, DecimalNullableFilter, BoolFilter, BoolNullableFilter, DateFilter, DateNullableFilter,");
             b.line("DateTimeFilter, DateTimeNullableFilter, EnumFilter, EnumNullableFilter,");
-            b.line("ArrayFilter, ArrayNullableFilter,");
+            b.line("ArrayFilter, ArrayNullableFilter, JsonFilter, JsonNullableFilter,");
         }, "} from \"./filter\"");
         c.block("import {", |b| {
             b.line("ObjectIdFieldUpdateOperationsInput, NullableObjectIdFieldUpdateOperationsInput, StringFieldUpdateOperationsInput,");
@@ -335,6 +348,7 @@ pub(crate) async fn generate_index_d_ts(graph: &Graph, client: &ClientGeneratorC
             b.line("NullableDateFieldUpdateOperationsInput, DateTimeFieldUpdateOperationsInput, NullableDateTimeFieldUpdateOperationsInput,");
             b.line("EnumFieldUpdateOperationsInput, NullableEnumFieldUpdateOperationsInput,");
             b.line("ArrayFieldUpdateOperationsInput, NullableArrayFieldUpdateOperationsInput,");
+            b.line("JsonFieldUpdateOperationsInput, NullableJsonFieldUpdateOperationsInput,");
         }, "} from \"./operation\"");
         c.line(r#"import Decimal from "./decimal""#);
         c.line(r#"
@@ -534,6 +548,12 @@ export declare class TeoError extends Error {
                         b.line(format!(r#"pageNumber?: number"#));
                         //b.line(format!{r#"distinct? {model_name}ScalarFieldEnum"#})
                     }
+                    if m.soft_delete().is_some() && a.handler_allowed_input_json_keys().contains("withDeleted") {
+                        b.doc(with_deleted_doc(m));
+                        b.line(r#"withDeleted?: boolean"#);
+                        b.doc(only_deleted_doc(m));
+                        b.line(r#"onlyDeleted?: boolean"#);
+                    }
                     if a.handler_requires_create() {
                         b.doc(create_or_update_doc(m, if a == &Action::from_u32(UPSERT_HANDLER) { Action::from_u32(CREATE_HANDLER) } else { a.clone() }));
                         b.line(format!(r#"create: {model_name}CreateInput"#));
@@ -546,6 +566,19 @@ export declare class TeoError extends Error {
                         b.doc(credentials_doc(m, *a));
                         b.line(format!(r#"credentials: {model_name}CredentialsInput"#))
                     }
+                    if a.handler_accepts_refresh_token() {
+                        b.doc(refresh_token_doc(*a));
+                        b.line(if a.handler_requires_refresh_token() { "refreshToken: string" } else { "refreshToken?: string" });
+                    }
+                }, "}");
+            });
+            m.handlers().iter().for_each(|h| {
+                let handler_name = h.name().to_pascal_case();
+                c.block(format!(r#"export type {model_name}{handler_name}Args = {{"#), |b| {
+                    for (key, r#type) in h.input() {
+                        let optional = if r#type.optional { "?" } else { "" };
+                        b.line(format!("{key}{optional}: {}", handler_input_type(r#type, graph)));
+                    }
                 }, "}");
             });
             // get payload is for typescript only
@@ -579,7 +612,7 @@ export declare class TeoError extends Error {
         let object_name = client.object_name.as_ref().unwrap();
         let object_class_name = object_name.to_pascal_case();
         graph.models().iter().for_each(|m| {
-            if m.actions().len() > 0 {
+            if !m.actions().is_empty() || !m.handlers().is_empty() {
                 let model_name = m.name();
                 let model_var_name = model_name.to_camel_case();
                 let model_class_name = model_var_name.to_pascal_case();
@@ -609,6 +642,18 @@ export declare class TeoError extends Error {
                             b.line(format!("{action_var_name}<T extends {model_name}{action_capitalized_name}Args>(args?: T): Promise<Response<{res_meta}, CheckSelectInclude<T, {res_data}, {model_name}GetPayload<T>{payload_array}>>>"));
                         }
                     });
+                    m.handlers().iter().for_each(|h| {
+                        let handler_var_name = h.name().to_camel_case();
+                        let handler_capitalized_name = h.name().to_pascal_case();
+                        let args_optional = if h.input().iter().all(|(_, t)| t.optional) { "?" } else { "" };
+                        let res_data = match h.output() {
+                            Some(r#type) => r#type.field_type(graph).to_typescript_type(r#type.optional),
+                            None => "JsonValue".to_owned(),
+                        };
+                        b.empty_line();
+                        b.doc(handler_doc(object_name, h, m));
+                        b.line(format!("{handler_var_name}(args{args_optional}: {model_name}{handler_capitalized_name}Args): Promise<Response<undefined, {res_data}>>"));
+                    });
                 }, "}");
                 c.empty_line();
             }
@@ -616,7 +661,7 @@ export declare class TeoError extends Error {
         // main interface
         c.block(format!("declare class {object_class_name} {{"), |b| {
             graph.models().iter().for_each(|m| {
-                if m.actions().len() > 0 {
+                if !m.actions().is_empty() || !m.handlers().is_empty() {
                     let model_name = m.name();
                     let model_var_name = model_name.to_camel_case();
                     let model_class_name = model_var_name.to_pascal_case();
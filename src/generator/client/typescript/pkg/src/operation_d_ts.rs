@@ -3,6 +3,7 @@ use crate::core::graph::Graph;
 
 pub(crate) async fn generate_operation_d_ts(_graph: &Graph) -> String {
     format!(r#"import Decimal from "./decimal"
+import {{ JsonValue }} from "./runtime"
 
 export type ObjectIdFieldUpdateOperationsInput = {{
     set?: string
@@ -93,5 +94,15 @@ export type NullableArrayFieldUpdateOperationsInput<T> = {{
     set?: T[] | null,
     push?: T
 }}
+
+export type JsonFieldUpdateOperationsInput = {{
+    set?: JsonValue,
+    merge?: {{ [key: string]: JsonValue }}
+}}
+
+export type NullableJsonFieldUpdateOperationsInput = {{
+    set?: JsonValue | null,
+    merge?: {{ [key: string]: JsonValue }}
+}}
 "#)
 }
@@ -36,6 +36,8 @@ export type Enumerable<T> = T | Array<T>
 
 export type SortOrder = "asc" | "desc"
 
+export type JsonValue = string | number | boolean | null | JsonValue[] | {{ [key: string]: JsonValue }}
+
 export interface Response<Meta, Data> {{
     meta: Meta
     data: Data
@@ -54,6 +56,7 @@ export type PagingInfo = {{
 
 export type TokenInfo = {{
     token: string
+    refreshToken?: string
 }}
 "#)
 }
@@ -22,6 +22,7 @@ impl ToTypeScriptType for FieldType {
             FieldType::Vec(internal) => internal.field_type().to_typescript_type(internal.optionality.is_optional()) + "[]",
             FieldType::HashMap(_) => panic!(),
             FieldType::BTreeMap(_) => panic!(),
+            FieldType::Json => "JsonValue".to_string(),
             FieldType::Object(name) => name.to_string(),
         };
         if optional {
@@ -61,6 +62,7 @@ impl ToTypeScriptType for FieldType {
             },
             FieldType::HashMap(_) => panic!(),
             FieldType::BTreeMap(_) => panic!(),
+            FieldType::Json => "Json".to_string(),
             FieldType::Object(_name) => "undefined | Unimplemented".to_string(),
         };
         if !with_generic {
@@ -87,6 +89,7 @@ impl ToTypeScriptType for FieldType {
             FieldType::Vec(internal) => internal.field_type().to_typescript_type(internal.optionality.is_optional()) + "[]",
             FieldType::HashMap(_) => panic!(),
             FieldType::BTreeMap(_) => panic!(),
+            FieldType::Json => "JsonValue".to_string(),
             FieldType::Object(name) => name.to_string(),
         };
         if optional {
@@ -122,6 +125,7 @@ impl ToTypeScriptType for FieldType {
                 generic = format!("<{create_type}>");
                 "Array"
             },
+            FieldType::Json => "Json",
             _ => panic!(),
         };
         let suffix = "FieldUpdateOperationsInput";
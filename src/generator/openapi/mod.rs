@@ -0,0 +1,631 @@
+mod yaml;
+
+use std::path::Path;
+use inflector::Inflector;
+use serde_json::{json, Map, Value as JsonValue};
+use crate::core::action::{Action, COUNT_HANDLER, FIND_MANY_HANDLER, GROUP_BY_HANDLER, ResData, ResMeta};
+use crate::core::app::conf::ServerConf;
+use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+use crate::core::graph::Graph;
+use crate::core::handler::{Handler, HandlerType};
+use crate::core::model::Model;
+use crate::generator::lib::generator::Generator;
+use crate::generator::openapi::yaml::to_yaml;
+
+/// Write an OpenAPI 3.1 document describing every enabled handler of the graph. The document is
+/// written as YAML if `output` ends with `.yaml` or `.yml`, otherwise as JSON.
+pub(crate) async fn generate_openapi(graph: &Graph, conf: &ServerConf, output: &Path) -> std::io::Result<()> {
+    let document = OpenAPIBuilder::new(graph).build(conf);
+    let content = match output.extension().and_then(|e| e.to_str()) {
+        Some("yaml") | Some("yml") => to_yaml(&document),
+        _ => serde_json::to_string_pretty(&document).unwrap() + "\n",
+    };
+    let generator = Generator::new(output.parent().unwrap_or(Path::new(".")));
+    generator.ensure_root_directory().await?;
+    generator.generate_file(output.file_name().unwrap().to_str().unwrap(), content).await
+}
+
+fn schema_ref(name: impl AsRef<str>) -> JsonValue {
+    json!({ "$ref": format!("#/components/schemas/{}", name.as_ref()) })
+}
+
+fn array_of(items: JsonValue) -> JsonValue {
+    json!({ "type": "array", "items": items })
+}
+
+fn nullable(schema: JsonValue) -> JsonValue {
+    match schema.get("type").and_then(|t| t.as_str()) {
+        Some(t) => {
+            let mut schema = schema.clone();
+            schema["type"] = json!([t, "null"]);
+            schema
+        }
+        None => json!({ "anyOf": [schema, { "type": "null" }] }),
+    }
+}
+
+fn object(properties: Map<String, JsonValue>, required: Vec<&str>) -> JsonValue {
+    let mut schema = json!({ "type": "object", "properties": properties, "additionalProperties": false });
+    if !required.is_empty() {
+        schema["required"] = json!(required);
+    }
+    schema
+}
+
+/// The schema of a value. Responses wrap dates and decimals into `$date` and `$decimal` objects,
+/// while requests take them as strings.
+fn value_schema(field_type: &FieldType, optional: bool, output: bool) -> JsonValue {
+    let schema = match field_type {
+        #[cfg(feature = "data-source-mongodb")]
+        FieldType::ObjectId => json!({ "type": "string" }),
+        FieldType::Bool => json!({ "type": "boolean" }),
+        FieldType::I32 => json!({ "type": "integer", "format": "int32" }),
+        FieldType::I64 => json!({ "type": "integer", "format": "int64" }),
+        FieldType::F32 => json!({ "type": "number", "format": "float" }),
+        FieldType::F64 => json!({ "type": "number", "format": "double" }),
+        FieldType::Decimal => if output { schema_ref("Decimal") } else { json!({ "type": ["string", "number"] }) },
+        FieldType::String => json!({ "type": "string" }),
+        FieldType::Date => json!({ "type": "string", "format": "date" }),
+        FieldType::DateTime => if output { schema_ref("DateTime") } else { json!({ "type": "string", "format": "date-time" }) },
+        FieldType::Enum(name) => schema_ref(name),
+        FieldType::Vec(inner) => array_of(value_schema(inner.field_type(), inner.is_optional(), output)),
+        FieldType::HashMap(inner) | FieldType::BTreeMap(inner) => json!({ "type": "object", "additionalProperties": value_schema(inner.field_type(), inner.is_optional(), output) }),
+        // any json value, null included
+        FieldType::Json => return json!({}),
+        FieldType::Object(name) => schema_ref(name),
+    };
+    if optional { nullable(schema) } else { schema }
+}
+
+fn type_title(field_type: &FieldType) -> String {
+    match field_type {
+        #[cfg(feature = "data-source-mongodb")]
+        FieldType::ObjectId => "ObjectId".to_owned(),
+        FieldType::Bool => "Bool".to_owned(),
+        FieldType::I32 => "Int".to_owned(),
+        FieldType::I64 => "Int64".to_owned(),
+        FieldType::F32 => "Float".to_owned(),
+        FieldType::F64 => "Double".to_owned(),
+        FieldType::Decimal => "Decimal".to_owned(),
+        FieldType::String => "String".to_owned(),
+        FieldType::Date => "Date".to_owned(),
+        FieldType::DateTime => "DateTime".to_owned(),
+        FieldType::Enum(name) => format!("{name}Enum"),
+        FieldType::Vec(inner) => format!("{}Array", type_title(inner.field_type())),
+        FieldType::HashMap(inner) | FieldType::BTreeMap(inner) => format!("{}Map", type_title(inner.field_type())),
+        FieldType::Json => "Json".to_owned(),
+        FieldType::Object(name) => name.clone(),
+    }
+}
+
+struct OpenAPIBuilder<'a> {
+    graph: &'a Graph,
+    schemas: Map<String, JsonValue>,
+}
+
+impl<'a> OpenAPIBuilder<'a> {
+
+    fn new(graph: &'a Graph) -> Self {
+        Self { graph, schemas: Map::new() }
+    }
+
+    fn build(mut self, conf: &ServerConf) -> JsonValue {
+        self.install_shared_schemas();
+        let mut enum_names: Vec<&String> = self.graph.enums().keys().collect();
+        enum_names.sort();
+        for name in enum_names {
+            let values = self.graph.enum_values(name).unwrap();
+            self.schemas.insert(name.clone(), json!({ "type": "string", "enum": values }));
+        }
+        let prefix = conf.path_prefix.as_deref().unwrap_or("").trim_end_matches('/');
+        let mut paths = Map::new();
+        for model in self.graph.models() {
+            self.install_model_schemas(model);
+            for action in Action::handlers_iter() {
+                if !model.actions().contains(action) { continue }
+                self.install_action_args(model, *action);
+                let path = format!("{prefix}/{}/action/{}", model.url_segment_name(), action.as_handler_str());
+                paths.insert(path, self.operation(model, *action));
+            }
+            for handler in model.handlers() {
+                self.install_handler_args(model, handler);
+                let path = format!("{prefix}/{}/action/{}", model.url_segment_name(), handler.name());
+                paths.insert(path, self.handler_operation(model, handler));
+            }
+        }
+        let host = if conf.bind.0 == "0.0.0.0" { "localhost" } else { conf.bind.0.as_str() };
+        json!({
+            "openapi": "3.1.0",
+            "info": { "title": "Teo API", "version": "1.0.0" },
+            "servers": [{ "url": format!("http://{host}:{}", conf.bind.1) }],
+            "paths": paths,
+            "components": {
+                "schemas": self.schemas,
+                "responses": {
+                    "Error": {
+                        "description": "The request failed.",
+                        "content": { "application/json": { "schema": schema_ref("Error") } },
+                    },
+                },
+                "securitySchemes": {
+                    "bearerAuth": { "type": "http", "scheme": "bearer", "bearerFormat": "JWT" },
+                },
+            },
+            "security": [{ "bearerAuth": [] }, {}],
+        })
+    }
+
+    fn install_shared_schemas(&mut self) {
+        self.schemas.insert("DateTime".to_owned(), json!({
+            "type": "object",
+            "properties": { "$date": { "type": "string", "format": "date-time" } },
+            "required": ["$date"],
+        }));
+        self.schemas.insert("Decimal".to_owned(), json!({
+            "type": "object",
+            "properties": { "$decimal": { "type": "string" } },
+            "required": ["$decimal"],
+        }));
+        self.schemas.insert("SortOrder".to_owned(), json!({ "type": "string", "enum": ["asc", "desc"] }));
+        self.schemas.insert("PagingInfo".to_owned(), json!({
+            "type": "object",
+            "properties": { "count": { "type": "integer" }, "numberOfPages": { "type": "integer" } },
+            "required": ["count"],
+        }));
+        self.schemas.insert("TokenInfo".to_owned(), json!({
+            "type": "object",
+            "properties": { "token": { "type": "string" }, "refreshToken": { "type": "string" } },
+            "required": ["token"],
+        }));
+        self.schemas.insert("Error".to_owned(), json!({
+            "type": "object",
+            "properties": {
+                "error": {
+                    "type": "object",
+                    "properties": {
+                        "type": { "type": "string" },
+                        "message": { "type": "string" },
+                        "errors": { "type": "object", "additionalProperties": { "type": "string" } },
+                    },
+                    "required": ["type", "message"],
+                },
+            },
+            "required": ["error"],
+        }));
+    }
+
+    /// Filters are shared by fields of the same type, and referenced by name.
+    fn filter(&mut self, field_type: &FieldType, optional: bool) -> JsonValue {
+        let nullable_title = if optional { "Nullable" } else { "" };
+        let name = format!("{}{nullable_title}Filter", type_title(field_type));
+        if !self.schemas.contains_key(&name) {
+            let value = value_schema(field_type, optional, false);
+            let element = value_schema(field_type, false, false);
+            let mut conditions = Map::new();
+            conditions.insert("equals".to_owned(), value.clone());
+            match field_type {
+                FieldType::Bool => (),
+                FieldType::Json => {
+                    conditions.insert("path".to_owned(), array_of(json!({ "type": ["string", "integer"] })));
+                    conditions.insert("arrayContains".to_owned(), element.clone());
+                    conditions.insert("stringContains".to_owned(), json!({ "type": "string" }));
+                    // a json object is always read as conditions
+                    self.schemas.insert(name.clone(), object(conditions, vec![]));
+                    return schema_ref(name);
+                }
+                FieldType::Vec(inner) => {
+                    let inner_value = value_schema(inner.field_type(), inner.is_optional(), false);
+                    conditions.insert("has".to_owned(), inner_value.clone());
+                    conditions.insert("hasSome".to_owned(), array_of(inner_value.clone()));
+                    conditions.insert("hasEvery".to_owned(), array_of(inner_value));
+                    conditions.insert("isEmpty".to_owned(), json!({ "type": "boolean" }));
+                    conditions.insert("length".to_owned(), json!({ "type": "integer" }));
+                }
+                FieldType::Enum(_) => {
+                    conditions.insert("in".to_owned(), array_of(value.clone()));
+                    conditions.insert("notIn".to_owned(), array_of(value.clone()));
+                }
+                _ => {
+                    conditions.insert("in".to_owned(), array_of(value.clone()));
+                    conditions.insert("notIn".to_owned(), array_of(value.clone()));
+                    for key in ["lt", "lte", "gt", "gte"] {
+                        conditions.insert(key.to_owned(), element.clone());
+                    }
+                    if field_type.is_string() {
+                        for key in ["contains", "startsWith", "endsWith", "matches", "search"] {
+                            conditions.insert(key.to_owned(), element.clone());
+                        }
+                    }
+                }
+            }
+            if !field_type.is_vec() {
+                conditions.insert("not".to_owned(), schema_ref(&name));
+            }
+            self.schemas.insert(name.clone(), json!({ "oneOf": [value, object(conditions, vec![])] }));
+        }
+        schema_ref(name)
+    }
+
+    fn update_operations(&self, field_type: &FieldType, optional: bool) -> JsonValue {
+        let value = value_schema(field_type, optional, false);
+        let mut operations = Map::new();
+        operations.insert("set".to_owned(), value.clone());
+        match field_type {
+            FieldType::I32 | FieldType::I64 | FieldType::F32 | FieldType::F64 | FieldType::Decimal => {
+                let number = value_schema(field_type, false, false);
+                for key in ["increment", "decrement", "multiply", "divide"] {
+                    operations.insert(key.to_owned(), number.clone());
+                }
+            }
+            FieldType::Vec(inner) => {
+                operations.insert("push".to_owned(), value_schema(inner.field_type(), inner.is_optional(), false));
+            }
+            FieldType::Json => {
+                operations.insert("merge".to_owned(), json!({ "type": "object" }));
+            }
+            _ => (),
+        }
+        let mut schema = object(operations, vec![]);
+        schema["minProperties"] = json!(1);
+        schema["maxProperties"] = json!(1);
+        json!({ "oneOf": [value, schema] })
+    }
+
+    fn install_model_schemas(&mut self, model: &Model) {
+        let model_name = model.name();
+        // output
+        let mut output = Map::new();
+        let mut select = Map::new();
+        for key in model.output_keys() {
+            if let Some(field) = model.field(key) {
+                output.insert(key.clone(), value_schema(field.field_type(), field.is_optional(), true));
+                select.insert(key.clone(), json!({ "type": "boolean" }));
+            } else if let Some(relation) = model.relation(key) {
+                let schema = if relation.is_vec() { array_of(schema_ref(relation.model())) } else { schema_ref(relation.model()) };
+                output.insert(key.clone(), if relation.is_optional() { nullable(schema) } else { schema });
+            } else if let Some(property) = model.property(key) {
+                output.insert(key.clone(), value_schema(property.field_type(), true, true));
+                select.insert(key.clone(), json!({ "type": "boolean" }));
+            }
+        }
+        let mut output_schema = json!({ "type": "object", "properties": output });
+        if !model.description().is_empty() {
+            output_schema["description"] = json!(model.description());
+        }
+        self.schemas.insert(model_name.to_owned(), output_schema);
+        self.schemas.insert(format!("{model_name}Select"), object(select, vec![]));
+        // include
+        let mut include = Map::new();
+        for relation in model.relations() {
+            let relation_model = relation.model();
+            let has_find_many = self.graph.model(relation_model).map(|m| m.actions().contains(&Action::from_u32(FIND_MANY_HANDLER))).unwrap_or(false);
+            let args = if relation.is_vec() && has_find_many { format!("{relation_model}FindManyArgs") } else { format!("{relation_model}Args") };
+            include.insert(relation.name().to_owned(), json!({ "oneOf": [{ "type": "boolean" }, schema_ref(args)] }));
+        }
+        self.schemas.insert(format!("{model_name}Include"), object(include, vec![]));
+        let mut args = Map::from_iter([
+            ("select".to_owned(), schema_ref(format!("{model_name}Select"))),
+            ("include".to_owned(), schema_ref(format!("{model_name}Include"))),
+        ]);
+        if model.soft_delete().is_some() {
+            args.insert("withDeleted".to_owned(), json!({ "type": "boolean" }));
+            args.insert("onlyDeleted".to_owned(), json!({ "type": "boolean" }));
+        }
+        self.schemas.insert(format!("{model_name}Args"), object(args, vec![]));
+        // where
+        let mut where_input = Map::new();
+        for key in ["AND", "OR", "NOT"] {
+            where_input.insert(key.to_owned(), array_of(schema_ref(format!("{model_name}WhereInput"))));
+        }
+        let mut order_by = Map::new();
+        for key in model.query_keys() {
+            if let Some(field) = model.field(key) {
+                where_input.insert(key.clone(), self.filter(field.field_type(), field.is_optional()));
+                order_by.insert(key.clone(), schema_ref("SortOrder"));
+            } else if let Some(relation) = model.relation(key) {
+                let list = if relation.is_vec() { "List" } else { "" };
+                where_input.insert(key.clone(), schema_ref(format!("{}{list}RelationFilter", relation.model())));
+            }
+        }
+        self.schemas.insert(format!("{model_name}WhereInput"), object(where_input, vec![]));
+        self.schemas.insert(format!("{model_name}OrderByInput"), object(order_by, vec![]));
+        let mut where_unique = Map::new();
+        for index in model.indices() {
+            if !index.r#type().is_unique() { continue }
+            for item in index.items() {
+                if let Some(field) = model.field(item.field_name()) {
+                    where_unique.insert(field.name().to_owned(), value_schema(field.field_type(), false, false));
+                }
+            }
+        }
+        let mut where_unique_schema = object(where_unique, vec![]);
+        where_unique_schema["minProperties"] = json!(1);
+        self.schemas.insert(format!("{model_name}WhereUniqueInput"), where_unique_schema);
+        self.schemas.insert(format!("{model_name}RelationFilter"), object(Map::from_iter([
+            ("is".to_owned(), schema_ref(format!("{model_name}WhereInput"))),
+            ("isNot".to_owned(), schema_ref(format!("{model_name}WhereInput"))),
+        ]), vec![]));
+        self.schemas.insert(format!("{model_name}ListRelationFilter"), object(Map::from_iter([
+            ("every".to_owned(), schema_ref(format!("{model_name}WhereInput"))),
+            ("some".to_owned(), schema_ref(format!("{model_name}WhereInput"))),
+            ("none".to_owned(), schema_ref(format!("{model_name}WhereInput"))),
+        ]), vec![]));
+        // create and update
+        let mut create = Map::new();
+        let mut update = Map::new();
+        for key in model.input_keys() {
+            if let Some(field) = model.field(key) {
+                create.insert(key.clone(), value_schema(field.field_type(), field.is_optional(), false));
+                update.insert(key.clone(), self.update_operations(field.field_type(), field.is_optional()));
+            } else if let Some(relation) = model.relation(key) {
+                let num = if relation.is_vec() { "Many" } else { "One" };
+                create.insert(key.clone(), schema_ref(format!("{}CreateNested{num}Input", relation.model())));
+                update.insert(key.clone(), schema_ref(format!("{}UpdateNested{num}Input", relation.model())));
+            } else if let Some(property) = model.property(key) {
+                create.insert(key.clone(), value_schema(property.field_type(), property.is_optional(), false));
+                update.insert(key.clone(), value_schema(property.field_type(), property.is_optional(), false));
+            }
+        }
+        self.schemas.insert(format!("{model_name}CreateInput"), object(create, vec![]));
+        self.schemas.insert(format!("{model_name}UpdateInput"), object(update, vec![]));
+        self.schemas.insert(format!("{model_name}ConnectOrCreateInput"), object(Map::from_iter([
+            ("where".to_owned(), schema_ref(format!("{model_name}WhereUniqueInput"))),
+            ("create".to_owned(), schema_ref(format!("{model_name}CreateInput"))),
+        ]), vec!["where", "create"]));
+        self.schemas.insert(format!("{model_name}UpdateWithWhereUniqueInput"), object(Map::from_iter([
+            ("where".to_owned(), schema_ref(format!("{model_name}WhereUniqueInput"))),
+            ("update".to_owned(), schema_ref(format!("{model_name}UpdateInput"))),
+        ]), vec!["where", "update"]));
+        self.schemas.insert(format!("{model_name}UpsertWithWhereUniqueInput"), object(Map::from_iter([
+            ("where".to_owned(), schema_ref(format!("{model_name}WhereUniqueInput"))),
+            ("update".to_owned(), schema_ref(format!("{model_name}UpdateInput"))),
+            ("create".to_owned(), schema_ref(format!("{model_name}CreateInput"))),
+        ]), vec!["where", "update", "create"]));
+        self.schemas.insert(format!("{model_name}UpdateManyWithWhereInput"), object(Map::from_iter([
+            ("where".to_owned(), schema_ref(format!("{model_name}WhereInput"))),
+            ("update".to_owned(), schema_ref(format!("{model_name}UpdateInput"))),
+        ]), vec!["where", "update"]));
+        for many in [false, true] {
+            let num = if many { "Many" } else { "One" };
+            let list_if = |name: String| if many { array_of(schema_ref(name)) } else { schema_ref(name) };
+            self.schemas.insert(format!("{model_name}CreateNested{num}Input"), object(Map::from_iter([
+                ("create".to_owned(), list_if(format!("{model_name}CreateInput"))),
+                ("connectOrCreate".to_owned(), list_if(format!("{model_name}ConnectOrCreateInput"))),
+                ("connect".to_owned(), list_if(format!("{model_name}WhereUniqueInput"))),
+            ]), vec![]));
+            let mut nested_update = Map::from_iter([
+                ("create".to_owned(), list_if(format!("{model_name}CreateInput"))),
+                ("connectOrCreate".to_owned(), list_if(format!("{model_name}ConnectOrCreateInput"))),
+                ("connect".to_owned(), list_if(format!("{model_name}WhereUniqueInput"))),
+                ("set".to_owned(), list_if(format!("{model_name}WhereUniqueInput"))),
+                ("update".to_owned(), list_if(format!("{model_name}UpdateWithWhereUniqueInput"))),
+                ("upsert".to_owned(), list_if(format!("{model_name}UpsertWithWhereUniqueInput"))),
+                ("disconnect".to_owned(), list_if(format!("{model_name}WhereUniqueInput"))),
+                ("delete".to_owned(), list_if(format!("{model_name}WhereUniqueInput"))),
+            ]);
+            if many {
+                nested_update.insert("updateMany".to_owned(), array_of(schema_ref(format!("{model_name}UpdateManyWithWhereInput"))));
+                nested_update.insert("deleteMany".to_owned(), array_of(schema_ref(format!("{model_name}WhereInput"))));
+            }
+            self.schemas.insert(format!("{model_name}UpdateNested{num}Input"), object(nested_update, vec![]));
+        }
+        // credentials
+        if model.identity() {
+            let mut credentials = Map::new();
+            for key in model.auth_identity_keys().iter().chain(model.auth_by_keys().iter()) {
+                if let Some(field) = model.field(key) {
+                    credentials.insert(key.clone(), value_schema(field.field_type(), false, false));
+                }
+            }
+            self.schemas.insert(format!("{model_name}CredentialsInput"), object(credentials, vec![]));
+        }
+    }
+
+    fn install_action_args(&mut self, model: &Model, action: Action) {
+        let model_name = model.name();
+        let action_name = action.as_handler_str().to_pascal_case();
+        let mut properties = Map::new();
+        let mut required = vec![];
+        if action.handler_requires_where() {
+            properties.insert("where".to_owned(), schema_ref(format!("{model_name}WhereInput")));
+        }
+        if action.handler_requires_where_unique() {
+            properties.insert("where".to_owned(), schema_ref(format!("{model_name}WhereUniqueInput")));
+            required.push("where");
+        }
+        if action.handler_requires_aggregates() {
+            let mut count = Map::from_iter([("_all".to_owned(), json!({ "type": "boolean" }))]);
+            for key in model.scalar_keys() {
+                count.insert(key.clone(), json!({ "type": "boolean" }));
+            }
+            let numbers = Map::from_iter(model.scalar_number_keys().iter().map(|k| (k.clone(), json!({ "type": "boolean" }))));
+            let scalars = Map::from_iter(model.scalar_keys().iter().map(|k| (k.clone(), json!({ "type": "boolean" }))));
+            properties.insert("where".to_owned(), schema_ref(format!("{model_name}WhereInput")));
+            properties.insert("_count".to_owned(), object(count, vec![]));
+            properties.insert("_sum".to_owned(), object(numbers.clone(), vec![]));
+            properties.insert("_avg".to_owned(), object(numbers, vec![]));
+            properties.insert("_min".to_owned(), object(scalars.clone(), vec![]));
+            properties.insert("_max".to_owned(), object(scalars, vec![]));
+        } else if action.to_u32() != COUNT_HANDLER {
+            properties.insert("select".to_owned(), schema_ref(format!("{model_name}Select")));
+            properties.insert("include".to_owned(), schema_ref(format!("{model_name}Include")));
+        } else {
+            properties.insert("where".to_owned(), schema_ref(format!("{model_name}WhereInput")));
+        }
+        if action.handler_requires_by_and_having() {
+            properties.insert("by".to_owned(), array_of(json!({ "type": "string", "enum": model.scalar_keys() })));
+            properties.insert("having".to_owned(), json!({ "type": "object" }));
+            required.push("by");
+        }
+        if action.handler_requires_where() {
+            properties.insert("orderBy".to_owned(), array_of(schema_ref(format!("{model_name}OrderByInput"))));
+            properties.insert("cursor".to_owned(), schema_ref(format!("{model_name}WhereUniqueInput")));
+            properties.insert("take".to_owned(), json!({ "type": "integer" }));
+            properties.insert("skip".to_owned(), json!({ "type": "integer" }));
+            properties.insert("pageSize".to_owned(), json!({ "type": "integer" }));
+            properties.insert("pageNumber".to_owned(), json!({ "type": "integer" }));
+        }
+        if model.soft_delete().is_some() && action.handler_allowed_input_json_keys().contains("withDeleted") {
+            properties.insert("withDeleted".to_owned(), json!({ "type": "boolean" }));
+            properties.insert("onlyDeleted".to_owned(), json!({ "type": "boolean" }));
+        }
+        if action.handler_requires_create() {
+            let create = schema_ref(format!("{model_name}CreateInput"));
+            properties.insert("create".to_owned(), if action.handler_res_data() == ResData::Vec { array_of(create) } else { create });
+            required.push("create");
+        }
+        if action.handler_requires_update() {
+            properties.insert("update".to_owned(), schema_ref(format!("{model_name}UpdateInput")));
+            required.push("update");
+        }
+        if action.handler_requires_credentials() {
+            properties.insert("credentials".to_owned(), schema_ref(format!("{model_name}CredentialsInput")));
+            required.push("credentials");
+        }
+        if action.handler_accepts_refresh_token() {
+            properties.insert("refreshToken".to_owned(), json!({ "type": "string" }));
+            if action.handler_requires_refresh_token() {
+                required.push("refreshToken");
+            }
+        }
+        self.schemas.insert(format!("{model_name}{action_name}Args"), object(properties, required));
+    }
+
+    fn operation(&self, model: &Model, action: Action) -> JsonValue {
+        let model_name = model.name();
+        let action_str = action.as_handler_str();
+        let action_name = action_str.to_pascal_case();
+        let mut response = Map::new();
+        let meta = match action.handler_res_meta() {
+            ResMeta::PagingInfo => Some(schema_ref("PagingInfo")),
+            ResMeta::TokenInfo => Some(schema_ref("TokenInfo")),
+            ResMeta::NoMeta | ResMeta::Other => None,
+        };
+        if let Some(meta) = meta {
+            response.insert("meta".to_owned(), meta);
+        }
+        let data = match action.handler_res_data() {
+            ResData::Single => schema_ref(model_name),
+            ResData::Vec => array_of(schema_ref(model_name)),
+            ResData::Number => json!({ "type": "integer" }),
+            ResData::Other => if action.to_u32() == GROUP_BY_HANDLER { array_of(json!({ "type": "object" })) } else { json!({ "type": "object" }) },
+        };
+        response.insert("data".to_owned(), data);
+        post_operation(model_name, &action_name, format!("{action_str} on {model_name}"), object(response, vec!["data"]))
+    }
+
+    /// The schema of a handler input or output. Objects are taken as create inputs and returned
+    /// like the model's other actions return them.
+    fn handler_type_schema(&self, r#type: &HandlerType, output: bool) -> JsonValue {
+        let field_type = r#type.field_type(self.graph);
+        let item = match &field_type {
+            FieldType::Object(name) if !output => schema_ref(format!("{name}CreateInput")),
+            FieldType::Vec(inner) => match inner.field_type() {
+                FieldType::Object(name) if !output => array_of(schema_ref(format!("{name}CreateInput"))),
+                _ => value_schema(&field_type, false, output),
+            },
+            _ => value_schema(&field_type, false, output),
+        };
+        if r#type.optional { nullable(item) } else { item }
+    }
+
+    fn install_handler_args(&mut self, model: &Model, handler: &Handler) {
+        let mut properties = Map::new();
+        let mut required = vec![];
+        for (key, r#type) in handler.input() {
+            properties.insert(key.clone(), self.handler_type_schema(r#type, false));
+            if !r#type.optional {
+                required.push(key.as_str());
+            }
+        }
+        let schema = object(properties, required);
+        self.schemas.insert(format!("{}{}Args", model.name(), handler.name().to_pascal_case()), schema);
+    }
+
+    fn handler_operation(&self, model: &Model, handler: &Handler) -> JsonValue {
+        let data = match handler.output() {
+            Some(r#type) => self.handler_type_schema(r#type, true),
+            None => json!({}),
+        };
+        let response = object(Map::from_iter([("data".to_owned(), data)]), vec!["data"]);
+        let summary = format!("custom handler {} on {}", handler.name(), model.name());
+        post_operation(model.name(), &handler.name().to_pascal_case(), summary, response)
+    }
+}
+
+fn post_operation(model_name: &str, action_name: &str, summary: String, response: JsonValue) -> JsonValue {
+    let error = json!({ "$ref": "#/components/responses/Error" });
+    json!({
+        "post": {
+            "operationId": format!("{}{action_name}", model_name.to_camel_case()),
+            "summary": summary,
+            "tags": [model_name],
+            "requestBody": {
+                "required": true,
+                "content": { "application/json": { "schema": schema_ref(format!("{model_name}{action_name}Args")) } },
+            },
+            "responses": {
+                "200": {
+                    "description": "The request succeeded.",
+                    "content": { "application/json": { "schema": response } },
+                },
+                "400": error.clone(),
+                "401": error.clone(),
+                "404": error.clone(),
+                "500": error,
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::serial;
+    use crate::core::app::testing::{server_conf, sqlite_app};
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model User {
+  @id @autoIncrement
+  id: Int
+  name: String
+  age: Int?
+  @relation(fields: .id, references: .authorId)
+  posts: Post[]
+}
+
+model Post {
+  @id @autoIncrement
+  id: Int
+  authorId: Int
+  @relation(fields: .authorId, references: .id)
+  author: User
+}
+"#;
+
+    #[tokio::test]
+    #[serial]
+    async fn paths_describe_enabled_actions() {
+        let app = sqlite_app(SCHEMA).await;
+        let document = OpenAPIBuilder::new(app.graph()).build(server_conf(&app));
+        assert_eq!(document["servers"], json!([{ "url": "http://localhost:5100" }]));
+        let find_many = &document["paths"]["/users/action/findMany"]["post"];
+        assert_eq!(find_many["operationId"], json!("userFindMany"));
+        assert_eq!(find_many["requestBody"]["content"]["application/json"]["schema"], schema_ref("UserFindManyArgs"));
+        let data = &find_many["responses"]["200"]["content"]["application/json"]["schema"]["properties"];
+        assert_eq!(data["meta"], schema_ref("PagingInfo"));
+        assert_eq!(data["data"], array_of(schema_ref("User")));
+        assert!(document["components"]["schemas"]["UserFindManyArgs"].is_object());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn optional_fields_are_nullable() {
+        let app = sqlite_app(SCHEMA).await;
+        let document = OpenAPIBuilder::new(app.graph()).build(server_conf(&app));
+        let user = &document["components"]["schemas"]["User"]["properties"];
+        assert_eq!(user["name"], json!({ "type": "string" }));
+        assert_eq!(user["age"], json!({ "type": ["integer", "null"], "format": "int32" }));
+        assert_eq!(user["posts"], array_of(schema_ref("Post")));
+    }
+}
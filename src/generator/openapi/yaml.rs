@@ -0,0 +1,122 @@
+use serde_json::Value as JsonValue;
+
+/// Render a JSON value as block style YAML. Strings are written plainly when this is
+/// unambiguous, otherwise they are quoted as JSON strings, which are valid YAML as well.
+pub(crate) fn to_yaml(value: &JsonValue) -> String {
+    let mut result = String::new();
+    match value {
+        JsonValue::Object(map) if !map.is_empty() => write_map(&mut result, map, 0),
+        JsonValue::Array(vec) if !vec.is_empty() => write_vec(&mut result, vec, 0),
+        _ => {
+            result.push_str(&scalar(value));
+            result.push('\n');
+        }
+    }
+    result
+}
+
+fn is_collection(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Object(map) => !map.is_empty(),
+        JsonValue::Array(vec) => !vec.is_empty(),
+        _ => false,
+    }
+}
+
+fn write_map(result: &mut String, map: &serde_json::Map<String, JsonValue>, indent: usize) {
+    for (index, (key, value)) in map.iter().enumerate() {
+        // the first key of a map inside a sequence follows the dash
+        if !(index == 0 && result.ends_with("- ")) {
+            result.push_str(&" ".repeat(indent));
+        }
+        result.push_str(&string(key));
+        result.push(':');
+        write_value(result, value, indent);
+    }
+}
+
+fn write_vec(result: &mut String, vec: &[JsonValue], indent: usize) {
+    for (index, value) in vec.iter().enumerate() {
+        if !(index == 0 && result.ends_with("- ")) {
+            result.push_str(&" ".repeat(indent));
+        }
+        result.push_str("- ");
+        match value {
+            JsonValue::Object(map) if !map.is_empty() => write_map(result, map, indent + 2),
+            JsonValue::Array(vec) if !vec.is_empty() => write_vec(result, vec, indent + 2),
+            _ => {
+                result.push_str(&scalar(value));
+                result.push('\n');
+            }
+        }
+    }
+}
+
+fn write_value(result: &mut String, value: &JsonValue, indent: usize) {
+    if is_collection(value) {
+        result.push('\n');
+        match value {
+            JsonValue::Object(map) => write_map(result, map, indent + 2),
+            JsonValue::Array(vec) => write_vec(result, vec, indent + 2),
+            _ => unreachable!(),
+        }
+    } else {
+        result.push(' ');
+        result.push_str(&scalar(value));
+        result.push('\n');
+    }
+}
+
+fn scalar(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => string(s),
+        JsonValue::Object(_) => "{}".to_owned(),
+        JsonValue::Array(_) => "[]".to_owned(),
+        _ => value.to_string(),
+    }
+}
+
+fn string(s: &str) -> String {
+    let plain = !s.is_empty()
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || "_-./ ".contains(c))
+        && !s.starts_with(|c: char| c == '-' || c == ' ' || c == '.' || c.is_ascii_digit())
+        && !s.ends_with(' ')
+        && !["true", "false", "null", "yes", "no", "on", "off", "~"].contains(&s.to_lowercase().as_str());
+    if plain { s.to_owned() } else { JsonValue::String(s.to_owned()).to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+
+    #[test]
+    fn nested_collections_are_indented() {
+        let value = json!({
+            "paths": { "/users": { "post": { "tags": ["User"] } } },
+            "security": [{ "bearerAuth": [] }, {}],
+        });
+        assert_eq!(to_yaml(&value), "\
+paths:
+  /users:
+    post:
+      tags:
+        - User
+security:
+  - bearerAuth: []
+  - {}
+");
+    }
+
+    #[test]
+    fn ambiguous_strings_are_quoted() {
+        let value = json!({ "a": "true", "b": "1.0", "c": "#/components/schemas/User", "d": "plain text", "e": 1 });
+        assert_eq!(to_yaml(&value), "\
+a: \"true\"
+b: \"1.0\"
+c: \"#/components/schemas/User\"
+d: plain text
+e: 1
+");
+    }
+}
@@ -1,20 +1,417 @@
+use std::collections::BTreeSet;
+use async_trait::async_trait;
+use inflector::Inflector;
 use crate::core::app::conf::EntityGeneratorConf;
+use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+use crate::core::model::Model;
+use crate::core::r#enum::Enum;
+use crate::generator::lib::code::Code;
 use crate::generator::lib::generator::Generator;
 use crate::generator::server::EntityGenerator;
 use crate::prelude::Graph;
-use async_trait::async_trait;
 
 pub(crate) struct GoEntityGenerator {}
 
 impl GoEntityGenerator {
+
     pub fn new() -> Self {
         Self {}
     }
+
+    /// Optional scalars are represented with pointers, optional slices, maps and json values are
+    /// just `nil`.
+    fn is_pointer<T: FieldTypeOwner>(&self, field: &T) -> bool {
+        field.is_optional() && !matches!(field.field_type(), FieldType::Vec(_) | FieldType::HashMap(_) | FieldType::BTreeMap(_) | FieldType::Json)
+    }
+
+    fn type_for_field<T: FieldTypeOwner>(&self, field: &T) -> String {
+        if self.is_pointer(field) {
+            format!("*{}", self.type_for_field_type(field.field_type()))
+        } else {
+            self.type_for_field_type(field.field_type())
+        }
+    }
+
+    fn type_for_field_type(&self, field_type: &FieldType) -> String {
+        match field_type {
+            #[cfg(feature = "data-source-mongodb")]
+            FieldType::ObjectId => "string".to_owned(),
+            FieldType::Bool => "bool".to_owned(),
+            FieldType::I32 => "int32".to_owned(),
+            FieldType::I64 => "int64".to_owned(),
+            FieldType::F32 => "float32".to_owned(),
+            FieldType::F64 => "float64".to_owned(),
+            FieldType::Decimal => "decimal.Decimal".to_owned(),
+            FieldType::String => "string".to_owned(),
+            FieldType::Date | FieldType::DateTime => "time.Time".to_owned(),
+            FieldType::Enum(name) => name.clone(),
+            FieldType::Vec(inner) => format!("[]{}", self.type_for_field(inner.as_ref())),
+            FieldType::HashMap(inner) | FieldType::BTreeMap(inner) => format!("map[string]{}", self.type_for_field(inner.as_ref())),
+            FieldType::Json => "any".to_owned(),
+            FieldType::Object(name) => name.clone(),
+        }
+    }
+
+    fn collect_imports(field_type: &FieldType, imports: &mut BTreeSet<&'static str>) {
+        match field_type {
+            FieldType::Decimal => { imports.insert("\"github.com/shopspring/decimal\""); },
+            FieldType::Date | FieldType::DateTime => { imports.insert("\"time\""); },
+            FieldType::Vec(inner) | FieldType::HashMap(inner) | FieldType::BTreeMap(inner) => Self::collect_imports(inner.field_type(), imports),
+            _ => (),
+        }
+    }
+
+    /// Lines which convert `value` into the typed named result `result`.
+    fn decode<T: FieldTypeOwner>(&self, b: &mut Code, field: &T) {
+        if field.field_type().is_enum() {
+            let enum_name = field.field_type().enum_name();
+            b.block("if v, ok := value.(string); ok {", |b| {
+                if field.is_optional() {
+                    b.line(format!("e := {enum_name}(v)"));
+                    b.line("result = &e");
+                } else {
+                    b.line(format!("result = {enum_name}(v)"));
+                }
+            }, "}");
+        } else if self.is_pointer(field) {
+            b.block(format!("if v, ok := value.({}); ok {{", self.type_for_field_type(field.field_type())), |b| {
+                b.line("result = &v");
+            }, "}");
+        } else {
+            b.line(format!("result, _ = value.({})", self.type_for_field(field)));
+        }
+    }
+
+    /// Lines which pass `newValue` to `method`.
+    fn encode<T: FieldTypeOwner>(&self, b: &mut Code, field: &T, method: &str) {
+        let convert = |v: &str| if field.field_type().is_enum() { format!("string({v})") } else { v.to_owned() };
+        if self.is_pointer(field) {
+            b.block("if newValue == nil {", |b| {
+                b.line(format!("return {method}, nil)"));
+            }, "}");
+            b.line(format!("return {method}, {})", convert("*newValue")));
+        } else {
+            b.line(format!("return {method}, {})", convert("newValue")));
+        }
+    }
+
+    fn doc(&self, b: &mut Code, name: &str, title: String, description: Option<&str>) {
+        b.line(format!("// {name} {title}"));
+        if let Some(description) = description {
+            b.line("//");
+            b.line(format!("// {description}"));
+        }
+    }
+
+    async fn generate_file_for_model(&self, package: &str, name: String, model: &Model, generator: &Generator) -> std::io::Result<()> {
+        let model_name = model.name();
+        let model_name_plural = model_name.to_plural();
+        let localized_name_title_case = model.localized_name();
+        let localized_name_word_case = localized_name_title_case.to_word_case();
+        let localized_name_word_case_plural = localized_name_word_case.to_plural();
+        let description = model.description();
+        let mut imports = BTreeSet::new();
+        for field in model.fields() {
+            Self::collect_imports(field.field_type(), &mut imports);
+        }
+        for property in model.properties() {
+            Self::collect_imports(property.field_type(), &mut imports);
+        }
+        generator.generate_file(format!("{}.go", name), Code::new(0, 4, |b| {
+            b.line(format!("package {package}"));
+            b.empty_line();
+            b.block("import (", |b| {
+                for import in imports.iter() {
+                    b.line(*import);
+                }
+                b.line("teo \"github.com/teocloud/teo-go\"");
+            }, ")");
+            b.empty_line();
+            // struct
+            self.doc(b, model_name, localized_name_title_case.clone(), if description.is_empty() { None } else { Some(description) });
+            b.block(format!("type {model_name} struct {{"), |b| {
+                b.line("inner *teo.Object");
+            }, "}");
+            b.empty_line();
+            b.line(format!(r#"// {model_name}FromObject wraps an object of the {localized_name_word_case} model.
+func {model_name}FromObject(object *teo.Object) *{model_name} {{
+	return &{model_name}{{inner: object}}
+}}
+
+func wrap{model_name_plural}(objects []*teo.Object) []*{model_name} {{
+	result := make([]*{model_name}, len(objects))
+	for i, o := range objects {{
+		result[i] = &{model_name}{{inner: o}}
+	}}
+	return result
+}}
+
+func unwrap{model_name_plural}(models []*{model_name}) []*teo.Object {{
+	result := make([]*teo.Object, len(models))
+	for i, m := range models {{
+		result[i] = m.inner
+	}}
+	return result
+}}
+
+// FindMany{model_name_plural} finds many {localized_name_word_case_plural}.
+func FindMany{model_name_plural}(query map[string]any) ([]*{model_name}, error) {{
+	objects, err := teo.CurrentGraph().FindMany("{model_name}", query)
+	if err != nil {{
+		return nil, err
+	}}
+	return wrap{model_name_plural}(objects), nil
+}}
+
+// FindUnique{model_name} finds a unique {localized_name_word_case}.
+func FindUnique{model_name}(query map[string]any) (*{model_name}, error) {{
+	object, err := teo.CurrentGraph().FindUnique("{model_name}", query)
+	if err != nil || object == nil {{
+		return nil, err
+	}}
+	return &{model_name}{{inner: object}}, nil
+}}
+
+// FindFirst{model_name} finds a non unique {localized_name_word_case}.
+func FindFirst{model_name}(query map[string]any) (*{model_name}, error) {{
+	object, err := teo.CurrentGraph().FindFirst("{model_name}", query)
+	if err != nil || object == nil {{
+		return nil, err
+	}}
+	return &{model_name}{{inner: object}}, nil
+}}
+
+// New{model_name} creates a new {localized_name_word_case}.
+func New{model_name}(values map[string]any) (*{model_name}, error) {{
+	object, err := teo.CurrentGraph().CreateObject("{model_name}", values)
+	if err != nil {{
+		return nil, err
+	}}
+	return &{model_name}{{inner: object}}, nil
+}}
+
+// Object returns the underlying object.
+func (m *{model_name}) Object() *teo.Object {{
+	return m.inner
+}}
+
+// IsNew returns whether this {localized_name_word_case} is new.
+func (m *{model_name}) IsNew() bool {{
+	return m.inner.IsNew()
+}}
+
+// IsModified returns whether this {localized_name_word_case} is modified.
+func (m *{model_name}) IsModified() bool {{
+	return m.inner.IsModified()
+}}
+
+// Set sets new values to a {localized_name_word_case}. Validations and transformations are
+// triggered.
+func (m *{model_name}) Set(values map[string]any) error {{
+	return m.inner.SetTeon(values)
+}}
+
+// Update updates new values to a {localized_name_word_case}. Validations and transformations
+// are not triggered.
+func (m *{model_name}) Update(values map[string]any) error {{
+	return m.inner.UpdateTeon(values)
+}}
+
+// Save saves this {localized_name_word_case}.
+func (m *{model_name}) Save() error {{
+	return m.inner.Save()
+}}
+
+// Delete deletes this {localized_name_word_case}.
+func (m *{model_name}) Delete() error {{
+	return m.inner.Delete()
+}}
+
+func (m *{model_name}) String() string {{
+	return m.inner.String()
+}}
+"#).replace('\t', "    "));
+            // field getters and setters
+            for field in model.fields() {
+                let method_name = field.name.to_pascal_case();
+                let field_type = self.type_for_field(field.as_ref());
+                self.doc(b, &method_name, field.localized_name(), field.description());
+                b.block(format!("func (m *{model_name}) {method_name}() (result {field_type}) {{"), |b| {
+                    b.line(format!("value := m.inner.Get(\"{}\")", field.name()));
+                    self.decode(b, field.as_ref());
+                    b.line("return");
+                }, "}");
+                b.empty_line();
+                b.block(format!("func (m *{model_name}) Set{method_name}(newValue {field_type}) error {{"), |b| {
+                    self.encode(b, field.as_ref(), &format!("m.inner.Set(\"{}\"", field.name()));
+                }, "}");
+                b.empty_line();
+            }
+            // relations
+            for relation in model.relations() {
+                let relation_name = relation.name();
+                let method_name = relation_name.to_pascal_case();
+                let param_name = relation_name.to_camel_case();
+                let relation_model = relation.model();
+                let relation_model_plural = relation_model.to_plural();
+                self.doc(b, &method_name, relation.localized_name(), relation.description().map(|d| d.as_str()));
+                if relation.is_vec() {
+                    b.block(format!("func (m *{model_name}) {method_name}(findManyInput map[string]any) ([]*{relation_model}, error) {{"), |b| {
+                        b.line(format!("objects, err := m.inner.ForceGetRelationObjects(\"{relation_name}\", findManyInput)"));
+                        b.block("if err != nil {", |b| {
+                            b.line("return nil, err");
+                        }, "}");
+                        b.line(format!("return wrap{relation_model_plural}(objects), nil"));
+                    }, "}");
+                    b.empty_line();
+                    for (prefix, method) in [("Set", "ForceSetRelationObjects"), ("AddTo", "ForceAddRelationObjects"), ("RemoveFrom", "ForceRemoveRelationObjects")] {
+                        b.block(format!("func (m *{model_name}) {prefix}{method_name}({param_name} []*{relation_model}) error {{"), |b| {
+                            b.line(format!("return m.inner.{method}(\"{relation_name}\", unwrap{relation_model_plural}({param_name}))"));
+                        }, "}");
+                        b.empty_line();
+                    }
+                } else {
+                    b.block(format!("func (m *{model_name}) {method_name}() (*{relation_model}, error) {{"), |b| {
+                        b.line(format!("object, err := m.inner.ForceGetRelationObject(\"{relation_name}\")"));
+                        b.block("if err != nil || object == nil {", |b| {
+                            b.line("return nil, err");
+                        }, "}");
+                        b.line(format!("return &{relation_model}{{inner: object}}, nil"));
+                    }, "}");
+                    b.empty_line();
+                    b.block(format!("func (m *{model_name}) Set{method_name}({param_name} *{relation_model}) error {{"), |b| {
+                        b.block(format!("if {param_name} == nil {{"), |b| {
+                            b.line(format!("return m.inner.ForceSetRelationObject(\"{relation_name}\", nil)"));
+                        }, "}");
+                        b.line(format!("return m.inner.ForceSetRelationObject(\"{relation_name}\", {param_name}.inner)"));
+                    }, "}");
+                    b.empty_line();
+                }
+            }
+            // properties
+            for property in model.properties() {
+                let property_name = property.name();
+                let method_name = property.name.to_pascal_case();
+                let property_type = self.type_for_field(property.as_ref());
+                self.doc(b, &method_name, property.localized_name(), property.description.as_deref());
+                if property.getter.is_some() {
+                    b.block(format!("func (m *{model_name}) {method_name}() (result {property_type}, err error) {{"), |b| {
+                        b.line(format!("value, err := m.inner.GetProperty(\"{property_name}\")"));
+                        b.block("if err != nil {", |b| {
+                            b.line("return");
+                        }, "}");
+                        self.decode(b, property.as_ref());
+                        b.line("return");
+                    }, "}");
+                    b.empty_line();
+                }
+                if property.setter.is_some() {
+                    b.block(format!("func (m *{model_name}) Set{method_name}(newValue {property_type}) error {{"), |b| {
+                        self.encode(b, property.as_ref(), &format!("m.inner.SetProperty(\"{property_name}\""));
+                    }, "}");
+                    b.empty_line();
+                }
+            }
+        }).to_string()).await
+    }
+
+    async fn generate_file_for_enum(&self, package: &str, name: String, e: &Enum, generator: &Generator) -> std::io::Result<()> {
+        let enum_name = e.name();
+        generator.generate_file(format!("{name}.go"), Code::new(0, 4, |b| {
+            b.line(format!("package {package}"));
+            b.empty_line();
+            b.line(format!("type {enum_name} string"));
+            b.empty_line();
+            b.block("const (", |b| {
+                for choice in e.choices() {
+                    b.line(format!("{enum_name}{} {enum_name} = \"{}\"", choice.name().to_pascal_case(), choice.name()));
+                }
+            }, ")");
+        }).to_string()).await
+    }
 }
 
 #[async_trait]
 impl EntityGenerator for GoEntityGenerator {
-    async fn generate_entity_files(&self, _graph: &Graph, _conf: &EntityGeneratorConf, _generator: &Generator) -> std::io::Result<()> {
+    async fn generate_entity_files(&self, graph: &Graph, conf: &EntityGeneratorConf, generator: &Generator) -> std::io::Result<()> {
+        let package = conf.dest.file_name().unwrap().to_str().unwrap().to_snake_case().replace("_", "");
+        for (name, e) in graph.enums() {
+            self.generate_file_for_enum(&package, name.to_snake_case(), e, generator).await?;
+        }
+        for model in graph.models() {
+            self.generate_file_for_model(&package, model.name().to_snake_case(), model, generator).await?;
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use serial_test::serial;
+    use uuid::Uuid;
+    use crate::core::app::environment::Environment;
+    use crate::core::app::testing::sqlite_app;
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model User {
+  @id @autoIncrement
+  id: Int
+  name: String
+  age: Int?
+  @relation(fields: .id, references: .authorId)
+  posts: Post[]
+}
+
+model Post {
+  @id @autoIncrement
+  id: Int
+  authorId: Int
+  @relation(fields: .authorId, references: .id)
+  author: User
+}
+"#;
+
+    async fn generate() -> PathBuf {
+        let app = sqlite_app(SCHEMA).await;
+        let dest = std::env::temp_dir().join(format!("teo-test-{}", Uuid::new_v4())).join("my_models");
+        let conf = EntityGeneratorConf { name: None, provider: Environment::Go, dest: dest.clone() };
+        let generator = Generator::new(&dest);
+        generator.ensure_root_directory().await.unwrap();
+        GoEntityGenerator::new().generate_entity_files(app.graph(), &conf, &generator).await.unwrap();
+        dest
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn models_are_wrapped_in_the_destination_package() {
+        let dest = generate().await;
+        let user = fs::read_to_string(dest.join("user.go")).unwrap();
+        assert!(user.starts_with("package mymodels\n"));
+        assert!(user.contains("type User struct {\n    inner *teo.Object\n}"));
+        assert!(user.contains("func FindManyUsers(query map[string]any) ([]*User, error) {"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn optional_fields_are_pointers() {
+        let dest = generate().await;
+        let user = fs::read_to_string(dest.join("user.go")).unwrap();
+        assert!(user.contains("func (m *User) Name() (result string) {"));
+        assert!(user.contains("func (m *User) Age() (result *int32) {"));
+        assert!(user.contains("func (m *User) SetAge(newValue *int32) error {"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn relations_have_typed_accessors() {
+        let dest = generate().await;
+        let user = fs::read_to_string(dest.join("user.go")).unwrap();
+        let post = fs::read_to_string(dest.join("post.go")).unwrap();
+        assert!(user.contains("func (m *User) Posts(findManyInput map[string]any) ([]*Post, error) {"));
+        assert!(user.contains("func (m *User) AddToPosts(posts []*Post) error {"));
+        assert!(post.contains("func (m *Post) Author() (*User, error) {"));
+    }
+}
@@ -1,20 +1,360 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+use async_trait::async_trait;
+use inflector::Inflector;
 use crate::core::app::conf::EntityGeneratorConf;
+use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+use crate::core::model::Model;
+use crate::core::r#enum::Enum;
+use crate::generator::lib::code::Code;
 use crate::generator::lib::generator::Generator;
 use crate::generator::server::EntityGenerator;
 use crate::prelude::Graph;
-use async_trait::async_trait;
 
 pub(crate) struct JavaEntityGenerator {}
 
 impl JavaEntityGenerator {
+
     pub fn new() -> Self {
         Self {}
     }
+
+    /// The package is inferred from the path components after the `java` source directory,
+    /// otherwise the destination directory name is used.
+    fn package_name(&self, dest: &Path) -> String {
+        let components: Vec<&str> = dest.iter().map(|c| c.to_str().unwrap()).collect();
+        match components.iter().rposition(|c| *c == "java") {
+            Some(index) if index + 1 < components.len() => components[index + 1..].join("."),
+            _ => dest.file_name().unwrap().to_str().unwrap().to_snake_case().replace("_", ""),
+        }
+    }
+
+    fn type_for_field<T: FieldTypeOwner>(&self, field: &T) -> String {
+        self.type_for_field_type(field.field_type())
+    }
+
+    fn type_for_field_type(&self, field_type: &FieldType) -> String {
+        match field_type {
+            #[cfg(feature = "data-source-mongodb")]
+            FieldType::ObjectId => "String".to_owned(),
+            FieldType::Bool => "Boolean".to_owned(),
+            FieldType::I32 => "Integer".to_owned(),
+            FieldType::I64 => "Long".to_owned(),
+            FieldType::F32 => "Float".to_owned(),
+            FieldType::F64 => "Double".to_owned(),
+            FieldType::Decimal => "BigDecimal".to_owned(),
+            FieldType::String => "String".to_owned(),
+            FieldType::Date => "LocalDate".to_owned(),
+            FieldType::DateTime => "OffsetDateTime".to_owned(),
+            FieldType::Enum(name) => name.clone(),
+            FieldType::Vec(inner) => format!("List<{}>", self.type_for_field(inner.as_ref())),
+            FieldType::HashMap(inner) | FieldType::BTreeMap(inner) => format!("Map<String, {}>", self.type_for_field(inner.as_ref())),
+            FieldType::Json => "Object".to_owned(),
+            FieldType::Object(name) => name.clone(),
+        }
+    }
+
+    fn collect_imports(field_type: &FieldType, imports: &mut BTreeSet<&'static str>) {
+        match field_type {
+            FieldType::Decimal => { imports.insert("java.math.BigDecimal"); },
+            FieldType::Date => { imports.insert("java.time.LocalDate"); },
+            FieldType::DateTime => { imports.insert("java.time.OffsetDateTime"); },
+            FieldType::Vec(inner) | FieldType::HashMap(inner) | FieldType::BTreeMap(inner) => Self::collect_imports(inner.field_type(), imports),
+            _ => (),
+        }
+    }
+
+    fn is_generic(&self, field_type: &FieldType) -> bool {
+        matches!(field_type, FieldType::Vec(_) | FieldType::HashMap(_) | FieldType::BTreeMap(_))
+    }
+
+    fn decode<T: FieldTypeOwner>(&self, field: &T, value: &str) -> String {
+        if field.field_type().is_enum() {
+            let enum_name = field.field_type().enum_name();
+            format!("{value} == null ? null : {enum_name}.valueOf((String) {value})")
+        } else {
+            format!("({}) {value}", self.type_for_field(field))
+        }
+    }
+
+    fn encode<T: FieldTypeOwner>(&self, field: &T) -> String {
+        if field.field_type().is_enum() {
+            "newValue == null ? null : newValue.name()".to_owned()
+        } else {
+            "newValue".to_owned()
+        }
+    }
+
+    fn doc(&self, b: &mut Code, title: String, description: Option<&str>) {
+        if let Some(description) = description {
+            b.line("/**");
+            b.line(format!(" * {title}"));
+            b.line(" *");
+            b.line(format!(" * {description}"));
+            b.line(" */");
+        } else {
+            b.line(format!("/** {title} */"));
+        }
+    }
+
+    async fn generate_file_for_model(&self, package: &str, model: &Model, generator: &Generator) -> std::io::Result<()> {
+        let model_name = model.name();
+        let localized_name_title_case = model.localized_name();
+        let localized_name_word_case = localized_name_title_case.to_word_case();
+        let localized_name_word_case_plural = localized_name_word_case.to_plural();
+        let description = model.description();
+        let mut imports = BTreeSet::new();
+        for field in model.fields() {
+            Self::collect_imports(field.field_type(), &mut imports);
+        }
+        for property in model.properties() {
+            Self::collect_imports(property.field_type(), &mut imports);
+        }
+        generator.generate_file(format!("{}.java", model_name), Code::new(0, 4, |b| {
+            b.line(format!("package {package};"));
+            b.empty_line();
+            b.line("import io.teocloud.teo.Graph;");
+            b.line("import io.teocloud.teo.TeoObject;");
+            for import in imports.iter() {
+                b.line(format!("import {import};"));
+            }
+            b.line("import java.util.List;");
+            b.line("import java.util.Map;");
+            b.line("import java.util.stream.Collectors;");
+            b.empty_line();
+            self.doc(b, localized_name_title_case.clone(), if description.is_empty() { None } else { Some(description) });
+            b.block(format!("public class {model_name} {{"), |b| {
+                b.indented(format!(r#"private final TeoObject inner;
+
+public {model_name}(TeoObject inner) {{
+    this.inner = inner;
+}}
+
+/** Find many {localized_name_word_case_plural}. */
+public static List<{model_name}> findMany(Map<String, Object> query) {{
+    return Graph.current().findMany("{model_name}", query).stream().map({model_name}::new).collect(Collectors.toList());
+}}
+
+/** Find a unique {localized_name_word_case}. */
+public static {model_name} findUnique(Map<String, Object> query) {{
+    TeoObject object = Graph.current().findUnique("{model_name}", query);
+    return object == null ? null : new {model_name}(object);
+}}
+
+/** Find a non unique {localized_name_word_case}. */
+public static {model_name} findFirst(Map<String, Object> query) {{
+    TeoObject object = Graph.current().findFirst("{model_name}", query);
+    return object == null ? null : new {model_name}(object);
+}}
+
+/** Create a new {localized_name_word_case}. */
+public static {model_name} create(Map<String, Object> values) {{
+    return new {model_name}(Graph.current().createObject("{model_name}", values));
+}}
+
+/** The underlying object. */
+public TeoObject getInner() {{
+    return inner;
+}}
+
+/** Whether this {localized_name_word_case} is new. */
+public boolean isNew() {{
+    return inner.isNew();
+}}
+
+/** Whether this {localized_name_word_case} is modified. */
+public boolean isModified() {{
+    return inner.isModified();
+}}
+
+/**
+ * Set new values to a {localized_name_word_case}. Validations and transformations are
+ * triggered.
+ */
+public void set(Map<String, Object> values) {{
+    inner.setTeon(values);
+}}
+
+/**
+ * Update new values to a {localized_name_word_case}. Validations and transformations are
+ * not triggered.
+ */
+public void update(Map<String, Object> values) {{
+    inner.updateTeon(values);
+}}
+
+/** Save this {localized_name_word_case}. */
+public void save() {{
+    inner.save();
+}}
+
+/** Delete this {localized_name_word_case}. */
+public void delete() {{
+    inner.delete();
+}}
+
+@Override
+public String toString() {{
+    return inner.toString();
+}}
+"#));
+                // field getters and setters
+                for field in model.fields() {
+                    let method_name = field.name.to_pascal_case();
+                    let field_type = self.type_for_field(field.as_ref());
+                    b.empty_line();
+                    self.doc(b, field.localized_name(), field.description());
+                    if self.is_generic(field.field_type()) {
+                        b.line("@SuppressWarnings(\"unchecked\")");
+                    }
+                    b.block(format!("public {field_type} get{method_name}() {{"), |b| {
+                        b.line(format!("return {};", self.decode(field.as_ref(), &format!("inner.get(\"{}\")", field.name()))));
+                    }, "}");
+                    b.empty_line();
+                    b.block(format!("public void set{method_name}({field_type} newValue) {{"), |b| {
+                        b.line(format!("inner.set(\"{}\", {});", field.name(), self.encode(field.as_ref())));
+                    }, "}");
+                }
+                // relations
+                for relation in model.relations() {
+                    let relation_name = relation.name();
+                    let method_name = relation_name.to_pascal_case();
+                    let param_name = relation_name.to_camel_case();
+                    let relation_model = relation.model();
+                    b.empty_line();
+                    self.doc(b, relation.localized_name(), relation.description().map(|d| d.as_str()));
+                    if relation.is_vec() {
+                        b.block(format!("public List<{relation_model}> get{method_name}(Map<String, Object> findManyInput) {{"), |b| {
+                            b.line(format!("return inner.forceGetRelationObjects(\"{relation_name}\", findManyInput).stream().map({relation_model}::new).collect(Collectors.toList());"));
+                        }, "}");
+                        for (prefix, method) in [("set", "forceSetRelationObjects"), ("addTo", "forceAddRelationObjects"), ("removeFrom", "forceRemoveRelationObjects")] {
+                            b.empty_line();
+                            b.block(format!("public void {prefix}{method_name}(List<{relation_model}> {param_name}) {{"), |b| {
+                                b.line(format!("inner.{method}(\"{relation_name}\", {param_name}.stream().map({relation_model}::getInner).collect(Collectors.toList()));"));
+                            }, "}");
+                        }
+                    } else {
+                        b.block(format!("public {relation_model} get{method_name}() {{"), |b| {
+                            b.line(format!("TeoObject object = inner.forceGetRelationObject(\"{relation_name}\");"));
+                            b.line(format!("return object == null ? null : new {relation_model}(object);"));
+                        }, "}");
+                        b.empty_line();
+                        b.block(format!("public void set{method_name}({relation_model} {param_name}) {{"), |b| {
+                            b.line(format!("inner.forceSetRelationObject(\"{relation_name}\", {param_name} == null ? null : {param_name}.getInner());"));
+                        }, "}");
+                    }
+                }
+                // properties
+                for property in model.properties() {
+                    let property_name = property.name();
+                    let method_name = property.name.to_pascal_case();
+                    let property_type = self.type_for_field(property.as_ref());
+                    b.empty_line();
+                    self.doc(b, property.localized_name(), property.description.as_deref());
+                    if property.getter.is_some() {
+                        if self.is_generic(property.field_type()) {
+                            b.line("@SuppressWarnings(\"unchecked\")");
+                        }
+                        b.block(format!("public {property_type} get{method_name}() {{"), |b| {
+                            b.line(format!("Object value = inner.getProperty(\"{property_name}\");"));
+                            b.line(format!("return {};", self.decode(property.as_ref(), "value")));
+                        }, "}");
+                    }
+                    if property.setter.is_some() {
+                        if property.getter.is_some() { b.empty_line(); }
+                        b.block(format!("public void set{method_name}({property_type} newValue) {{"), |b| {
+                            b.line(format!("inner.setProperty(\"{property_name}\", {});", self.encode(property.as_ref())));
+                        }, "}");
+                    }
+                }
+            }, "}");
+        }).to_string()).await
+    }
+
+    async fn generate_file_for_enum(&self, package: &str, e: &Enum, generator: &Generator) -> std::io::Result<()> {
+        let enum_name = e.name();
+        generator.generate_file(format!("{enum_name}.java"), Code::new(0, 4, |b| {
+            b.line(format!("package {package};"));
+            b.empty_line();
+            b.block(format!("public enum {enum_name} {{"), |b| {
+                for choice in e.choices() {
+                    b.line(format!("{},", choice.name()));
+                }
+            }, "}");
+        }).to_string()).await
+    }
 }
 
 #[async_trait]
 impl EntityGenerator for JavaEntityGenerator {
-    async fn generate_entity_files(&self, _graph: &Graph, _conf: &EntityGeneratorConf, _generator: &Generator) -> std::io::Result<()> {
+    async fn generate_entity_files(&self, graph: &Graph, conf: &EntityGeneratorConf, generator: &Generator) -> std::io::Result<()> {
+        let package = self.package_name(&conf.dest);
+        for e in graph.enums().values() {
+            self.generate_file_for_enum(&package, e, generator).await?;
+        }
+        for model in graph.models() {
+            self.generate_file_for_model(&package, model, generator).await?;
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use serial_test::serial;
+    use uuid::Uuid;
+    use crate::core::app::environment::Environment;
+    use crate::core::app::testing::sqlite_app;
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model User {
+  @id @autoIncrement
+  id: Int
+  name: String
+  age: Int?
+  @relation(fields: .id, references: .authorId)
+  posts: Post[]
+}
+
+model Post {
+  @id @autoIncrement
+  id: Int
+  authorId: Int
+  @relation(fields: .authorId, references: .id)
+  author: User
+}
+"#;
+
+    async fn generate() -> PathBuf {
+        let app = sqlite_app(SCHEMA).await;
+        let dest = std::env::temp_dir().join(format!("teo-test-{}", Uuid::new_v4())).join("my_models");
+        let conf = EntityGeneratorConf { name: None, provider: Environment::Java, dest: dest.clone() };
+        let generator = Generator::new(&dest);
+        generator.ensure_root_directory().await.unwrap();
+        JavaEntityGenerator::new().generate_entity_files(app.graph(), &conf, &generator).await.unwrap();
+        dest
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn models_are_wrapped_in_the_destination_package() {
+        let dest = generate().await;
+        let user = fs::read_to_string(dest.join("User.java")).unwrap();
+        assert!(user.starts_with("package mymodels;\n"));
+        assert!(user.contains("public static List<User> findMany(Map<String, Object> query) {"));
+        assert!(user.contains("public static User create(Map<String, Object> values) {"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn fields_and_relations_have_typed_accessors() {
+        let dest = generate().await;
+        let user = fs::read_to_string(dest.join("User.java")).unwrap();
+        assert!(user.contains("public Integer getAge() {\n        return (Integer) inner.get(\"age\");"));
+        assert!(user.contains("public List<Post> getPosts(Map<String, Object> findManyInput) {"));
+        assert!(user.contains("public void setPosts(List<Post> posts) {"));
+    }
+}
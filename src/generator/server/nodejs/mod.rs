@@ -1,5 +1,9 @@
 use array_tool::vec::Join;
+use inflector::Inflector;
 use crate::core::app::conf::EntityGeneratorConf;
+use crate::core::field::r#type::FieldTypeOwner;
+use crate::generator::client::typescript::r#type::ToTypeScriptType;
+use crate::generator::lib::code::Code;
 use crate::generator::lib::generator::Generator;
 use crate::generator::server::EntityGenerator;
 use crate::prelude::Graph;
@@ -21,8 +25,104 @@ impl NodeJSEntityGenerator {
         generator.generate_file("index.js", format!("{import}\n\n{body}\n\n{export}\n")).await
     }
 
+    fn type_for_field<T: FieldTypeOwner>(&self, field: &T) -> String {
+        let base = field.field_type().to_typescript_type(false);
+        if field.is_optional() { base + " | null" } else { base }
+    }
+
+    fn doc(&self, b: &mut Code, title: String, description: Option<&str>) {
+        if let Some(description) = description {
+            b.line("/**");
+            b.line(format!(" * {title}"));
+            b.line(" *");
+            b.line(format!(" * {description}"));
+            b.line(" */");
+        } else {
+            b.line(format!("/** {title} */"));
+        }
+    }
+
     async fn generate_index_d_ts(&self, graph: &Graph, generator: &Generator) -> std::io::Result<()> {
-        Ok(())
+        let has_decimal = graph.models().iter().any(|m| {
+            m.fields().iter().any(|f| f.field_type().is_decimal()) || m.properties().iter().any(|p| p.field_type().is_decimal())
+        });
+        generator.generate_file("index.d.ts", Code::new(0, 4, |b| {
+            if has_decimal {
+                b.line("import Decimal from \"decimal.js\"");
+                b.empty_line();
+            }
+            graph.enums().iter().for_each(|e| {
+                let name = e.0;
+                let choices = e.1.values().iter().map(|i| {String::from("\"") + i + "\""}).collect::<Vec<String>>().join(" | ");
+                b.line(format!("export type {name} = {choices}"));
+                b.empty_line();
+            });
+            for model in graph.models() {
+                let model_name = model.name();
+                let localized_name_word_case = model.localized_name().to_word_case();
+                let localized_name_word_case_plural = localized_name_word_case.to_plural();
+                self.doc(b, model.localized_name(), if model.description().is_empty() { None } else { Some(model.description()) });
+                b.block(format!("export declare class {model_name} {{"), |b| {
+                    b.line(format!("/** Find many {localized_name_word_case_plural}. */"));
+                    b.line(format!("static findMany(query?: any): Promise<{model_name}[]>"));
+                    b.line(format!("/** Find a unique {localized_name_word_case}. */"));
+                    b.line(format!("static findUnique(query: any): Promise<{model_name} | null>"));
+                    b.line(format!("/** Find a non unique {localized_name_word_case}. */"));
+                    b.line(format!("static findFirst(query?: any): Promise<{model_name} | null>"));
+                    b.line(format!("/** Create a new {localized_name_word_case}. */"));
+                    b.line(format!("static new(values?: any): Promise<{model_name}>"));
+                    b.line(format!("/** Whether this {localized_name_word_case} is new. */"));
+                    b.line("isNew(): boolean");
+                    b.line(format!("/** Whether this {localized_name_word_case} is modified. */"));
+                    b.line("isModified(): boolean");
+                    b.line(format!("/** Set new values to a {localized_name_word_case}. Validations and transformations are triggered. */"));
+                    b.line("set(values: any): Promise<void>");
+                    b.line(format!("/** Update new values to a {localized_name_word_case}. Validations and transformations are not triggered. */"));
+                    b.line("update(values: any): Promise<void>");
+                    b.line(format!("/** Save this {localized_name_word_case}. */"));
+                    b.line("save(): Promise<void>");
+                    b.line(format!("/** Delete this {localized_name_word_case}. */"));
+                    b.line("delete(): Promise<void>");
+                    // fields
+                    for field in model.fields() {
+                        let field_type = self.type_for_field(field.as_ref());
+                        self.doc(b, field.localized_name(), field.description());
+                        b.line(format!("get {}(): {field_type}", field.name()));
+                        b.line(format!("set {}(newValue: {field_type})", field.name()));
+                    }
+                    // relations
+                    for relation in model.relations() {
+                        let relation_name = relation.name();
+                        let relation_title = relation_name.to_pascal_case();
+                        let relation_model = relation.model();
+                        self.doc(b, relation.localized_name(), relation.description().map(|d| d.as_str()));
+                        if relation.is_vec() {
+                            b.line(format!("{relation_name}(findManyInput?: any): Promise<{relation_model}[]>"));
+                            b.line(format!("set{relation_title}({relation_name}: {relation_model}[]): Promise<void>"));
+                            b.line(format!("addTo{relation_title}({relation_name}: {relation_model}[]): Promise<void>"));
+                            b.line(format!("removeFrom{relation_title}({relation_name}: {relation_model}[]): Promise<void>"));
+                        } else {
+                            b.line(format!("{relation_name}(): Promise<{relation_model} | null>"));
+                            b.line(format!("set{relation_title}({relation_name}: {relation_model} | null): Promise<void>"));
+                        }
+                    }
+                    // properties
+                    for property in model.properties() {
+                        let property_name = property.name();
+                        let property_title = property_name.to_pascal_case();
+                        let property_type = self.type_for_field(property.as_ref());
+                        self.doc(b, property.localized_name(), property.description.as_deref());
+                        if property.getter.is_some() {
+                            b.line(format!("{property_name}(): Promise<{property_type}>"));
+                        }
+                        if property.setter.is_some() {
+                            b.line(format!("set{property_title}(newValue: {property_type}): Promise<void>"));
+                        }
+                    }
+                }, "}");
+                b.empty_line();
+            }
+        }).to_string()).await
     }
 }
 
@@ -34,3 +134,55 @@ impl EntityGenerator for NodeJSEntityGenerator {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use serial_test::serial;
+    use uuid::Uuid;
+    use crate::core::app::environment::Environment;
+    use crate::core::app::testing::sqlite_app;
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model User {
+  @id @autoIncrement
+  id: Int
+  name: String
+  age: Int?
+  @relation(fields: .id, references: .authorId)
+  posts: Post[]
+}
+
+model Post {
+  @id @autoIncrement
+  id: Int
+  authorId: Int
+  @relation(fields: .authorId, references: .id)
+  author: User
+}
+"#;
+
+    async fn generate() -> PathBuf {
+        let app = sqlite_app(SCHEMA).await;
+        let dest = std::env::temp_dir().join(format!("teo-test-{}", Uuid::new_v4())).join("my_models");
+        let conf = EntityGeneratorConf { name: None, provider: Environment::NodeJS, dest: dest.clone() };
+        let generator = Generator::new(&dest);
+        generator.ensure_root_directory().await.unwrap();
+        NodeJSEntityGenerator::new().generate_entity_files(app.graph(), &conf, &generator).await.unwrap();
+        dest
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn declarations_type_fields_and_relations() {
+        let dest = generate().await;
+        let index = fs::read_to_string(dest.join("index.d.ts")).unwrap();
+        assert!(index.contains("export declare class User {"));
+        assert!(index.contains("static findMany(query?: any): Promise<User[]>"));
+        assert!(index.contains("get age(): number | null\n    set age(newValue: number | null)"));
+        assert!(index.contains("posts(findManyInput?: any): Promise<Post[]>"));
+        assert!(index.contains("author(): Promise<User | null>"));
+    }
+}
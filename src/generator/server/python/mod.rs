@@ -1,20 +1,368 @@
+use std::collections::BTreeSet;
+use async_trait::async_trait;
+use inflector::Inflector;
 use crate::core::app::conf::EntityGeneratorConf;
+use crate::core::field::r#type::{FieldType, FieldTypeOwner};
+use crate::core::model::Model;
+use crate::core::r#enum::Enum;
+use crate::generator::lib::code::Code;
 use crate::generator::lib::generator::Generator;
 use crate::generator::server::EntityGenerator;
 use crate::prelude::Graph;
-use async_trait::async_trait;
 
 pub(crate) struct PythonEntityGenerator {}
 
 impl PythonEntityGenerator {
+
     pub fn new() -> Self {
         Self {}
     }
+
+    fn type_for_field<T: FieldTypeOwner>(&self, field: &T) -> String {
+        if field.is_optional() {
+            format!("Optional[{}]", self.type_for_field_type(field.field_type()))
+        } else {
+            self.type_for_field_type(field.field_type())
+        }
+    }
+
+    fn type_for_field_type(&self, field_type: &FieldType) -> String {
+        match field_type {
+            #[cfg(feature = "data-source-mongodb")]
+            FieldType::ObjectId => "str".to_owned(),
+            FieldType::Bool => "bool".to_owned(),
+            FieldType::I32 | FieldType::I64 => "int".to_owned(),
+            FieldType::F32 | FieldType::F64 => "float".to_owned(),
+            FieldType::Decimal => "Decimal".to_owned(),
+            FieldType::String => "str".to_owned(),
+            FieldType::Date => "date".to_owned(),
+            FieldType::DateTime => "datetime".to_owned(),
+            FieldType::Enum(name) => name.clone(),
+            FieldType::Vec(inner) => format!("List[{}]", self.type_for_field(inner.as_ref())),
+            FieldType::HashMap(inner) | FieldType::BTreeMap(inner) => format!("Dict[str, {}]", self.type_for_field(inner.as_ref())),
+            FieldType::Json => "Any".to_owned(),
+            FieldType::Object(name) => name.clone(),
+        }
+    }
+
+    fn collect_imports(field_type: &FieldType, imports: &mut BTreeSet<String>) {
+        match field_type {
+            FieldType::Decimal => { imports.insert("from decimal import Decimal".to_owned()); },
+            FieldType::Date => { imports.insert("from datetime import date".to_owned()); },
+            FieldType::DateTime => { imports.insert("from datetime import datetime".to_owned()); },
+            FieldType::Enum(name) => { imports.insert(format!("from .{} import {}", name.to_snake_case(), name)); },
+            FieldType::Vec(inner) | FieldType::HashMap(inner) | FieldType::BTreeMap(inner) => Self::collect_imports(inner.field_type(), imports),
+            _ => (),
+        }
+    }
+
+    fn decode<T: FieldTypeOwner>(&self, field: &T, value: &str) -> String {
+        if field.field_type().is_enum() {
+            let enum_name = field.field_type().enum_name();
+            if field.is_optional() {
+                format!("None if {value} is None else {enum_name}({value})")
+            } else {
+                format!("{enum_name}({value})")
+            }
+        } else {
+            value.to_owned()
+        }
+    }
+
+    fn encode<T: FieldTypeOwner>(&self, field: &T) -> String {
+        if field.field_type().is_enum() {
+            if field.is_optional() {
+                "None if new_value is None else new_value.value".to_owned()
+            } else {
+                "new_value.value".to_owned()
+            }
+        } else {
+            "new_value".to_owned()
+        }
+    }
+
+    fn doc(&self, b: &mut Code, title: String, description: Option<&str>) {
+        if let Some(description) = description {
+            b.line(format!("\"\"\"{title}"));
+            b.empty_line();
+            b.line(description);
+            b.line("\"\"\"");
+        } else {
+            b.line(format!("\"\"\"{title}\"\"\""));
+        }
+    }
+
+    async fn generate_file_for_model(&self, name: String, model: &Model, generator: &Generator) -> std::io::Result<()> {
+        let model_name = model.name();
+        let localized_name_title_case = model.localized_name();
+        let localized_name_word_case = localized_name_title_case.to_word_case();
+        let localized_name_word_case_plural = localized_name_word_case.to_plural();
+        let description = model.description();
+        let mut imports = BTreeSet::new();
+        for field in model.fields() {
+            Self::collect_imports(field.field_type(), &mut imports);
+        }
+        for property in model.properties() {
+            Self::collect_imports(property.field_type(), &mut imports);
+        }
+        let mut relation_models: Vec<&str> = model.relations().iter().map(|r| r.model()).filter(|m| *m != model_name).collect();
+        relation_models.sort();
+        relation_models.dedup();
+        generator.generate_file(format!("{}.py", name), Code::new(0, 4, |b| {
+            // import lines
+            b.line("from __future__ import annotations");
+            b.line("from typing import TYPE_CHECKING, Any, Dict, List, Optional");
+            for import in imports.iter() {
+                b.line(import);
+            }
+            b.line("from teo import Graph, Object");
+            if !relation_models.is_empty() {
+                b.block("if TYPE_CHECKING:", |b| {
+                    for relation_model in relation_models.iter() {
+                        b.line(format!("from .{} import {}", relation_model.to_snake_case(), relation_model));
+                    }
+                }, "");
+            }
+            b.empty_line();
+            b.empty_line();
+            // class and methods
+            b.block(format!("class {model_name}:"), |b| {
+                self.doc(b, localized_name_title_case.clone(), if description.is_empty() { None } else { Some(description) });
+                b.empty_line();
+                b.block("def __init__(self, inner: Object) -> None:", |b| {
+                    b.line("self.inner = inner");
+                }, "");
+                b.empty_line();
+                b.indented(format!(r#"@staticmethod
+async def find_many(query: Optional[Dict[str, Any]] = None) -> List[{model_name}]:
+    """Find many {localized_name_word_case_plural}."""
+    objects = await Graph.current().find_many("{model_name}", query or {{}})
+    return [{model_name}(o) for o in objects]
+
+@staticmethod
+async def find_unique(query: Dict[str, Any]) -> Optional[{model_name}]:
+    """Find a unique {localized_name_word_case}."""
+    object = await Graph.current().find_unique("{model_name}", query)
+    return None if object is None else {model_name}(object)
+
+@staticmethod
+async def find_first(query: Optional[Dict[str, Any]] = None) -> Optional[{model_name}]:
+    """Find a non unique {localized_name_word_case}."""
+    object = await Graph.current().find_first("{model_name}", query or {{}})
+    return None if object is None else {model_name}(object)
+
+@staticmethod
+async def new(values: Optional[Dict[str, Any]] = None) -> {model_name}:
+    """Create a new {localized_name_word_case}."""
+    return {model_name}(await Graph.current().create_object("{model_name}", values or {{}}))
+
+def is_new(self) -> bool:
+    """Whether this {localized_name_word_case} is new."""
+    return self.inner.is_new()
+
+def is_modified(self) -> bool:
+    """Whether this {localized_name_word_case} is modified."""
+    return self.inner.is_modified()
+
+async def set(self, values: Dict[str, Any]) -> None:
+    """Set new values to a {localized_name_word_case}. Validations and transformations are
+    triggered."""
+    await self.inner.set_teon(values)
+
+async def update(self, values: Dict[str, Any]) -> None:
+    """Update new values to a {localized_name_word_case}. Validations and transformations are
+    not triggered."""
+    await self.inner.update_teon(values)
+
+async def save(self) -> None:
+    """Save this {localized_name_word_case}."""
+    await self.inner.save()
+
+async def delete(self) -> None:
+    """Delete this {localized_name_word_case}."""
+    await self.inner.delete()
+
+def __repr__(self) -> str:
+    return repr(self.inner)
+"#));
+                // field getters and setters
+                for field in model.fields() {
+                    let field_method_name = field.name.to_snake_case();
+                    let field_type = self.type_for_field(field.as_ref());
+                    b.line("@property");
+                    b.block(format!("def {field_method_name}(self) -> {field_type}:"), |b| {
+                        self.doc(b, field.localized_name(), field.description());
+                        b.line(format!("return {}", self.decode(field.as_ref(), &format!("self.inner.get(\"{}\")", field.name()))));
+                    }, "");
+                    b.empty_line();
+                    b.line(format!("@{field_method_name}.setter"));
+                    b.block(format!("def {field_method_name}(self, new_value: {field_type}) -> None:"), |b| {
+                        b.line(format!("self.inner.set(\"{}\", {})", field.name(), self.encode(field.as_ref())));
+                    }, "");
+                    b.empty_line();
+                }
+                // relations
+                for relation in model.relations() {
+                    let relation_name = relation.name();
+                    let relation_method_name = relation_name.to_snake_case();
+                    let relation_model = relation.model();
+                    let description = relation.description().map(|d| d.as_str());
+                    let local_import = format!("from .{} import {}", relation_model.to_snake_case(), relation_model);
+                    if relation.is_vec() {
+                        b.block(format!("async def {relation_method_name}(self, find_many_input: Optional[Dict[str, Any]] = None) -> List[{relation_model}]:"), |b| {
+                            self.doc(b, relation.localized_name(), description);
+                            if relation_model != model_name { b.line(&local_import); }
+                            b.line(format!("objects = await self.inner.force_get_relation_objects(\"{relation_name}\", find_many_input or {{}})"));
+                            b.line(format!("return [{relation_model}(o) for o in objects]"));
+                        }, "");
+                        b.empty_line();
+                        for (prefix, method) in [("set_", "force_set_relation_objects"), ("add_to_", "force_add_relation_objects"), ("remove_from_", "force_remove_relation_objects")] {
+                            b.block(format!("async def {prefix}{relation_method_name}(self, {relation_method_name}: List[{relation_model}]) -> None:"), |b| {
+                                b.line(format!("await self.inner.{method}(\"{relation_name}\", [o.inner for o in {relation_method_name}])"));
+                            }, "");
+                            b.empty_line();
+                        }
+                    } else {
+                        b.block(format!("async def {relation_method_name}(self) -> Optional[{relation_model}]:"), |b| {
+                            self.doc(b, relation.localized_name(), description);
+                            if relation_model != model_name { b.line(&local_import); }
+                            b.line(format!("object = await self.inner.force_get_relation_object(\"{relation_name}\")"));
+                            b.line(format!("return None if object is None else {relation_model}(object)"));
+                        }, "");
+                        b.empty_line();
+                        b.block(format!("async def set_{relation_method_name}(self, {relation_method_name}: Optional[{relation_model}]) -> None:"), |b| {
+                            b.line(format!("await self.inner.force_set_relation_object(\"{relation_name}\", None if {relation_method_name} is None else {relation_method_name}.inner)"));
+                        }, "");
+                        b.empty_line();
+                    }
+                }
+                // properties
+                for property in model.properties() {
+                    let property_name = property.name();
+                    let property_method_name = property.name.to_snake_case();
+                    let property_type = self.type_for_field(property.as_ref());
+                    if property.getter.is_some() {
+                        b.block(format!("async def {property_method_name}(self) -> {property_type}:"), |b| {
+                            self.doc(b, property.localized_name(), property.description.as_deref());
+                            b.line(format!("return {}", self.decode(property.as_ref(), &format!("await self.inner.get_property(\"{property_name}\")"))));
+                        }, "");
+                        b.empty_line();
+                    }
+                    if property.setter.is_some() {
+                        b.block(format!("async def set_{property_method_name}(self, new_value: {property_type}) -> None:"), |b| {
+                            b.line(format!("await self.inner.set_property(\"{property_name}\", {})", self.encode(property.as_ref())));
+                        }, "");
+                        b.empty_line();
+                    }
+                }
+            }, "");
+        }).to_string()).await
+    }
+
+    async fn generate_file_for_enum(&self, name: String, e: &Enum, generator: &Generator) -> std::io::Result<()> {
+        let enum_name = e.name();
+        generator.generate_file(format!("{name}.py"), Code::new(0, 4, |b| {
+            b.line("from enum import Enum");
+            b.empty_line();
+            b.empty_line();
+            b.block(format!("class {enum_name}(str, Enum):"), |b| {
+                for choice in e.choices() {
+                    b.line(format!("{} = \"{}\"", choice.name(), choice.name()));
+                }
+            }, "");
+        }).to_string()).await
+    }
+
+    async fn generate_init_py(&self, names: Vec<(String, String)>, generator: &Generator) -> std::io::Result<()> {
+        generator.generate_file("__init__.py", Code::new(0, 4, |b| {
+            for (file_name, class_name) in names.iter() {
+                b.line(format!("from .{file_name} import {class_name}"));
+            }
+            b.empty_line();
+            b.block("__all__ = [", |b| {
+                for (_, class_name) in names.iter() {
+                    b.line(format!("\"{class_name}\","));
+                }
+            }, "]");
+        }).to_string()).await
+    }
 }
 
 #[async_trait]
 impl EntityGenerator for PythonEntityGenerator {
-    async fn generate_entity_files(&self, _graph: &Graph, _conf: &EntityGeneratorConf, _generator: &Generator) -> std::io::Result<()> {
-        Ok(())
+    async fn generate_entity_files(&self, graph: &Graph, _conf: &EntityGeneratorConf, generator: &Generator) -> std::io::Result<()> {
+        let mut names: Vec<(String, String)> = vec![];
+        for (name, e) in graph.enums() {
+            let file_name = name.to_snake_case();
+            names.push((file_name.clone(), name.clone()));
+            self.generate_file_for_enum(file_name, e, generator).await?;
+        }
+        for model in graph.models() {
+            let file_name = model.name().to_snake_case();
+            names.push((file_name.clone(), model.name().to_owned()));
+            self.generate_file_for_model(file_name, model, generator).await?;
+        }
+        self.generate_init_py(names, generator).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use serial_test::serial;
+    use uuid::Uuid;
+    use crate::core::app::environment::Environment;
+    use crate::core::app::testing::sqlite_app;
+    use super::*;
+
+    const SCHEMA: &str = r#"
+model User {
+  @id @autoIncrement
+  id: Int
+  name: String
+  age: Int?
+  @relation(fields: .id, references: .authorId)
+  posts: Post[]
+}
+
+model Post {
+  @id @autoIncrement
+  id: Int
+  authorId: Int
+  @relation(fields: .authorId, references: .id)
+  author: User
+}
+"#;
+
+    async fn generate() -> PathBuf {
+        let app = sqlite_app(SCHEMA).await;
+        let dest = std::env::temp_dir().join(format!("teo-test-{}", Uuid::new_v4())).join("my_models");
+        let conf = EntityGeneratorConf { name: None, provider: Environment::Python, dest: dest.clone() };
+        let generator = Generator::new(&dest);
+        generator.ensure_root_directory().await.unwrap();
+        PythonEntityGenerator::new().generate_entity_files(app.graph(), &conf, &generator).await.unwrap();
+        dest
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn package_exports_every_model() {
+        let dest = generate().await;
+        let init = fs::read_to_string(dest.join("__init__.py")).unwrap();
+        assert!(init.contains("from .user import User\n"));
+        assert!(init.contains("from .post import Post\n"));
+        assert!(init.contains("__all__ = [\n    \"User\",\n    \"Post\",\n]"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn fields_and_relations_are_typed() {
+        let dest = generate().await;
+        let user = fs::read_to_string(dest.join("user.py")).unwrap();
+        assert!(user.contains("async def find_many(query: Optional[Dict[str, Any]] = None) -> List[User]:"));
+        assert!(user.contains("def age(self) -> Optional[int]:"));
+        assert!(user.contains("async def posts(self, find_many_input: Optional[Dict[str, Any]] = None) -> List[Post]:"));
+        assert!(user.contains("if TYPE_CHECKING:\n    from .post import Post"));
     }
 }
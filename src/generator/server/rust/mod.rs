@@ -66,6 +66,7 @@ impl RustEntityGenerator {
             FieldType::Vec(inner) => format!("Vec<{}>", self.getter_type_for_field(inner.as_ref())),
             FieldType::HashMap(inner) => format!("HashMap<String, {}>", self.getter_type_for_field(inner.as_ref())),
             FieldType::BTreeMap(inner) => format!("BTreemap<String, {}>", self.getter_type_for_field(inner.as_ref())),
+            FieldType::Json => "Value".to_owned(),
             FieldType::Object(name) => name.clone(),
         }
     }
@@ -12,6 +12,7 @@ pub mod prelude {
     pub use crate::core::teon::Value;
     pub use crate::teon;
     pub use crate::core::object::Object;
+    pub use crate::core::handler::ctx::HandlerCtx;
     pub extern crate tokio;
     pub use tokio::main;
     pub extern crate key_path;
@@ -82,10 +82,6 @@ impl Container {
             }
         }
     }
-
-    pub(crate) fn access_property(&self, name: &str) -> &Entity {
-        self.objects.get(name).unwrap()
-    }
 }
 
 #[derive(Clone)]
@@ -114,10 +110,6 @@ impl Accessible {
         }
     }
 
-    pub(crate) fn is_container(&self) -> bool {
-        self.as_container().is_some()
-    }
-
     pub(crate) fn as_env(&self) -> Option<&EnvObject> {
         match self {
             Accessible::Env(e) => Some(e),
@@ -156,11 +148,4 @@ impl Accessible {
             _ => None,
         }
     }
-
-    pub(crate) fn access_property(&self, name: &str) -> &Entity {
-        match self.as_container() {
-            Some(c) => c.access_property(name),
-            None => panic!("Cannot access property '{}'", name),
-        }
-    }
 }
@@ -1,5 +1,6 @@
 use std::fmt::{Display, Formatter};
 use crate::parser::ast::expression::ExpressionKind;
+use crate::parser::ast::span::Span;
 
 #[derive(Debug, Clone, Copy)]
 pub enum Op {
@@ -35,22 +36,19 @@ impl Display for Op {
 #[derive(Debug, Clone)]
 pub(crate) enum ArithExpr {
     Expression(Box<ExpressionKind>),
-    UnaryNeg(Box<ExpressionKind>),
-    UnaryBitNeg(Box<ExpressionKind>),
     BinaryOp {
         lhs: Box<ArithExpr>,
         op: Op,
         rhs: Box<ArithExpr>,
+        span: Span,
     },
 }
 
 impl Display for ArithExpr {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            ArithExpr::UnaryNeg(e) => Display::fmt(&e, f),
-            ArithExpr::UnaryBitNeg(e) => Display::fmt(&e, f),
             ArithExpr::Expression(e) => Display::fmt(&e, f),
-            ArithExpr::BinaryOp { lhs, op, rhs } => {
+            ArithExpr::BinaryOp { lhs, op, rhs, .. } => {
                 Display::fmt(&lhs, f)?;
                 f.write_str(" ")?;
                 Display::fmt(op, f)?;
@@ -10,6 +10,17 @@ pub struct ServerConfig {
     pub(crate) bind: Option<(String, u16)>,
     pub(crate) jwt_secret: Option<String>,
     pub(crate) path_prefix: Option<String>,
+    pub(crate) graphql: bool,
+    pub(crate) cors_origins: Option<Vec<String>>,
+    pub(crate) cors_headers: Option<Vec<String>>,
+    pub(crate) cors_credentials: bool,
+    pub(crate) cors_max_age: Option<u32>,
+    pub(crate) body_limit: Option<usize>,
+    pub(crate) compression: bool,
+    pub(crate) request_timeout: Option<u64>,
+    pub(crate) token_expiry: Option<i64>,
+    pub(crate) refresh_token_expiry: Option<i64>,
+    pub(crate) health_details: bool,
 }
 
 impl ServerConfig {
@@ -22,6 +33,17 @@ impl ServerConfig {
             bind: None,
             jwt_secret: None,
             path_prefix: None,
+            graphql: false,
+            cors_origins: None,
+            cors_headers: None,
+            cors_credentials: false,
+            cors_max_age: None,
+            body_limit: None,
+            compression: false,
+            request_timeout: None,
+            token_expiry: None,
+            refresh_token_expiry: None,
+            health_details: false,
         }
     }
 }
@@ -1,4 +1,6 @@
 use crate::core::database::name::DatabaseName;
+use crate::core::database::pool::PoolConf;
+use crate::parser::ast::identifier::Identifier;
 use crate::parser::ast::span::Span;
 use crate::parser::ast::item::Item;
 
@@ -6,17 +8,32 @@ use crate::parser::ast::item::Item;
 pub struct Connector {
     pub(crate) id: usize,
     pub(crate) source_id: usize,
+    pub(crate) identifier: Option<Identifier>,
     pub(crate) items: Vec<Item>,
     pub(crate) span: Span,
     pub(crate) provider: Option<DatabaseName>,
     pub(crate) url: Option<String>,
     pub(crate) debug: bool,
+    pub(crate) slow_query_threshold: Option<u64>,
+    pub(crate) replicas: Vec<String>,
+    pub(crate) read_your_writes: bool,
+    pub(crate) pool: PoolConf,
 }
 
 impl Connector {
-    pub(crate) fn new(items: Vec<Item>, span: Span, source_id: usize, item_id: usize) -> Self {
+    pub(crate) fn new(identifier: Option<Identifier>, items: Vec<Item>, span: Span, source_id: usize, item_id: usize) -> Self {
         Self {
-            id: item_id, items, span, source_id, provider: None, url: None, debug: false
+            id: item_id, identifier, items, span, source_id, provider: None, url: None, debug: false, slow_query_threshold: None,
+            replicas: vec![], read_your_writes: false, pool: PoolConf::default(),
+        }
+    }
+
+    /// The name models refer to this connector with in `@@connector`. An unnamed connector is
+    /// named `default`.
+    pub(crate) fn name(&self) -> &str {
+        match &self.identifier {
+            Some(identifier) => identifier.name.as_str(),
+            None => "default",
         }
     }
 }
@@ -0,0 +1,151 @@
+pub(crate) mod printer;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+pub use crate::parser::ast::pos::Pos;
+pub use crate::parser::ast::span::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticsLevel {
+    Error,
+    Warning,
+}
+
+/// An error or a warning found in a schema file.
+#[derive(Debug, Clone)]
+pub struct DiagnosticsItem {
+    pub(crate) level: DiagnosticsLevel,
+    pub(crate) message: String,
+    pub(crate) source_id: Option<usize>,
+    pub(crate) source_path: Option<PathBuf>,
+    pub(crate) span: Option<Span>,
+    pub(crate) start: Option<Pos>,
+    pub(crate) end: Option<Pos>,
+}
+
+impl DiagnosticsItem {
+
+    pub fn level(&self) -> DiagnosticsLevel {
+        self.level
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.level == DiagnosticsLevel::Error
+    }
+
+    pub fn is_warning(&self) -> bool {
+        self.level == DiagnosticsLevel::Warning
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn source_id(&self) -> Option<usize> {
+        self.source_id
+    }
+
+    pub fn source_path(&self) -> Option<&Path> {
+        self.source_path.as_deref()
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// One-based line and column where the span starts.
+    pub fn start(&self) -> Option<Pos> {
+        self.start
+    }
+
+    /// One-based line and column where the span ends.
+    pub fn end(&self) -> Option<Pos> {
+        self.end
+    }
+}
+
+/// Errors and warnings collected while parsing and resolving schema files. Parsing doesn't stop
+/// at the first error, every error found in a run is collected here.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    items: Vec<DiagnosticsItem>,
+    sources: BTreeMap<usize, (PathBuf, String)>,
+}
+
+impl Diagnostics {
+
+    pub(crate) fn new() -> Self {
+        Self { items: vec![], sources: BTreeMap::new() }
+    }
+
+    pub(crate) fn insert_source(&mut self, source_id: usize, path: PathBuf, content: String) {
+        self.sources.insert(source_id, (path, content));
+    }
+
+    pub(crate) fn source_content(&self, source_id: usize) -> Option<&str> {
+        self.sources.get(&source_id).map(|(_, content)| content.as_str())
+    }
+
+    pub(crate) fn add_error(&mut self, source_id: usize, span: Span, message: impl Into<String>) {
+        self.add(DiagnosticsLevel::Error, Some(source_id), Some(span), message.into());
+    }
+
+    pub(crate) fn add_warning(&mut self, source_id: usize, span: Span, message: impl Into<String>) {
+        self.add(DiagnosticsLevel::Warning, Some(source_id), Some(span), message.into());
+    }
+
+    /// Errors which don't belong to any schema file, like a missing main schema file.
+    pub(crate) fn add_error_without_source(&mut self, message: impl Into<String>) {
+        self.add(DiagnosticsLevel::Error, None, None, message.into());
+    }
+
+    fn add(&mut self, level: DiagnosticsLevel, source_id: Option<usize>, span: Option<Span>, message: String) {
+        let source = source_id.and_then(|id| self.sources.get(&id));
+        let source_path = source.map(|(path, _)| path.clone());
+        let (start, end) = match (source, span) {
+            (Some((_, content)), Some(span)) => (Some(pos_at(content, span.start)), Some(pos_at(content, span.end))),
+            _ => (None, None),
+        };
+        self.items.push(DiagnosticsItem { level, message, source_id, source_path, span, start, end });
+    }
+
+    pub fn items(&self) -> &Vec<DiagnosticsItem> {
+        &self.items
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item=&DiagnosticsItem> {
+        self.items.iter().filter(|i| i.is_error())
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item=&DiagnosticsItem> {
+        self.items.iter().filter(|i| i.is_warning())
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn has_warnings(&self) -> bool {
+        self.warnings().next().is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Render every item with its source snippet, the way rustc does.
+    pub fn render(&self, colored: bool) -> String {
+        printer::render(self, colored)
+    }
+}
+
+fn pos_at(content: &str, offset: usize) -> Pos {
+    let offset = offset.min(content.len());
+    let before = &content[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = match before.rfind('\n') {
+        Some(index) => before[index + 1..].chars().count() + 1,
+        None => before.chars().count() + 1,
+    };
+    Pos { line, column }
+}
@@ -0,0 +1,111 @@
+use colored::Colorize;
+use crate::parser::diagnostics::{Diagnostics, DiagnosticsItem, DiagnosticsLevel};
+
+pub(crate) fn render(diagnostics: &Diagnostics, colored: bool) -> String {
+    let mut result = String::new();
+    for item in diagnostics.items() {
+        result += &render_item(diagnostics, item, colored);
+        result += "\n";
+    }
+    let error_count = diagnostics.errors().count();
+    let warning_count = diagnostics.warnings().count();
+    if error_count > 0 {
+        let summary = format!("could not load schema due to {} previous error{}{}", error_count, plural(error_count), if warning_count > 0 {
+            format!("; {} warning{} emitted", warning_count, plural(warning_count))
+        } else { "".to_owned() });
+        result += &format!("{}: {}\n", paint("error", DiagnosticsLevel::Error, colored), bold(&summary, colored));
+    } else if warning_count > 0 {
+        let summary = format!("{} warning{} emitted", warning_count, plural(warning_count));
+        result += &format!("{}: {}\n", paint("warning", DiagnosticsLevel::Warning, colored), bold(&summary, colored));
+    }
+    result
+}
+
+fn render_item(diagnostics: &Diagnostics, item: &DiagnosticsItem, colored: bool) -> String {
+    let label = match item.level {
+        DiagnosticsLevel::Error => "error",
+        DiagnosticsLevel::Warning => "warning",
+    };
+    let mut result = format!("{}: {}\n", paint(label, item.level, colored), bold(item.message(), colored));
+    let (Some(path), Some(start), Some(end)) = (item.source_path(), item.start(), item.end()) else {
+        return result;
+    };
+    let content = item.source_id().and_then(|id| diagnostics.source_content(id)).unwrap_or("");
+    let line_content = content.lines().nth(start.line - 1).unwrap_or("");
+    let gutter = " ".repeat(start.line.to_string().len());
+    let pipe = paint_blue("|", colored);
+    result += &format!("{}{} {}:{}\n", gutter, paint_blue("-->", colored), path.display(), start);
+    result += &format!("{} {}\n", gutter, pipe);
+    result += &format!("{} {} {}\n", paint_blue(&start.line.to_string(), colored), pipe, line_content);
+    // spans over multiple lines are underlined until the end of the first line
+    let line_length = line_content.chars().count();
+    let underline_length = if end.line == start.line {
+        end.column.saturating_sub(start.column).max(1)
+    } else {
+        (line_length + 1).saturating_sub(start.column).max(1)
+    };
+    let underline = format!("{}{}", " ".repeat(start.column - 1), "^".repeat(underline_length));
+    result += &format!("{} {} {}\n", gutter, pipe, paint(&underline, item.level, colored));
+    result
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+fn paint(text: &str, level: DiagnosticsLevel, colored: bool) -> String {
+    if !colored { return text.to_owned() }
+    match level {
+        DiagnosticsLevel::Error => text.red().bold().to_string(),
+        DiagnosticsLevel::Warning => text.yellow().bold().to_string(),
+    }
+}
+
+fn paint_blue(text: &str, colored: bool) -> String {
+    if colored { text.blue().bold().to_string() } else { text.to_owned() }
+}
+
+fn bold(text: &str, colored: bool) -> String {
+    if colored { text.bold().to_string() } else { text.to_owned() }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use crate::parser::ast::span::Span;
+    use super::*;
+
+    #[test]
+    fn renders_snippet_and_summary() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.insert_source(0, PathBuf::from("schema.teo"), "model User {\n  id: Strin\n}\n".to_owned());
+        diagnostics.add_error(0, Span::new(19, 24), "Unknown type 'Strin'.");
+        diagnostics.add_warning(0, Span::new(0, 5), "Model has no id.");
+        assert_eq!(render(&diagnostics, false), "\
+error: Unknown type 'Strin'.
+ --> schema.teo:2:7
+  |
+2 |   id: Strin
+  |       ^^^^^
+
+warning: Model has no id.
+ --> schema.teo:1:1
+  |
+1 | model User {
+  | ^^^^^
+
+error: could not load schema due to 1 previous error; 1 warning emitted
+");
+    }
+
+    #[test]
+    fn renders_items_without_source() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.add_error_without_source("Connector is not defined.");
+        assert_eq!(render(&diagnostics, false), "\
+error: Connector is not defined.
+
+error: could not load schema due to 1 previous error
+");
+    }
+}
@@ -1,3 +1,28 @@
 pub(crate) mod parser;
 pub(crate) mod ast;
 pub(crate) mod std;
+pub mod diagnostics;
+
+use ::std::sync::{Arc, Mutex};
+use crate::core::app::builder::CallbackLookupTable;
+use crate::parser::diagnostics::Diagnostics;
+use crate::parser::parser::Parser;
+
+/// Parse and resolve a schema without building an app. Every error and warning found is
+/// returned instead of being printed. When `main` is `None`, `schema.teo` or `src/schema.teo`
+/// in the current directory is used.
+pub fn check_schema(main: Option<&str>) -> Diagnostics {
+    let mut parser = Parser::new(Arc::new(Mutex::new(CallbackLookupTable::new())));
+    parser.parse(main);
+    ::std::mem::take(&mut parser.diagnostics)
+}
+
+/// Check `schema` written to a temporary schema file.
+#[cfg(test)]
+pub(crate) fn check_schema_source(schema: &str) -> Diagnostics {
+    let path = ::std::env::temp_dir().join(format!("teo-test-{}.teo", uuid::Uuid::new_v4()));
+    ::std::fs::write(&path, schema).unwrap();
+    let diagnostics = check_schema(Some(path.to_str().unwrap()));
+    let _ = ::std::fs::remove_file(path);
+    diagnostics
+}
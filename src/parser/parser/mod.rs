@@ -2,12 +2,13 @@ pub(crate) mod resolver;
 
 use std::borrow::Borrow;
 use snailquote::unescape;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::PathBuf;
 use std::fs;
 use std::sync::{Arc, Mutex};
 use maplit::{btreemap, btreeset};
 use pest::Parser as PestParser;
+use pest::error::{ErrorVariant, InputLocation};
 use pest::pratt_parser::PrattParser;
 use to_mut::ToMut;
 use to_mut_proc_macro::ToMut;
@@ -37,6 +38,7 @@ use crate::parser::ast::span::Span;
 use crate::parser::ast::subscript::Subscript;
 use crate::parser::ast::top::Top;
 use crate::parser::ast::unit::Unit;
+use crate::parser::diagnostics::Diagnostics;
 use crate::parser::parser::resolver::Resolver;
 use crate::parser::std::decorators::field::GlobalFieldDecorators;
 use crate::parser::std::decorators::model::GlobalModelDecorators;
@@ -70,19 +72,20 @@ pub(crate) struct Parser {
     pub(crate) sources: BTreeMap<usize, Source>,
     pub(crate) enums: Vec<(usize, usize)>,
     pub(crate) models: Vec<(usize, usize)>,
-    pub(crate) connector: Option<(usize, usize)>,
+    pub(crate) connectors: Vec<(usize, usize)>,
     pub(crate) config: Option<(usize, usize)>,
     pub(crate) generators: Vec<(usize, usize)>,
     pub(crate) clients: Vec<(usize, usize)>,
     pub(crate) next_id: usize,
     pub(crate) resolved: bool,
     pub(crate) global_model_decorators: Option<GlobalModelDecorators>,
-    pub(crate) global_field_decorators: Option<GlobalFieldDecorators>,
+    pub(crate) global_field_decorators: HashMap<String, GlobalFieldDecorators>,
     pub(crate) global_relation_decorators: Option<GlobalRelationDecorators>,
     pub(crate) global_property_decorators: Option<GlobalPropertyDecorators>,
     pub(crate) global_pipeline_installers: Option<GlobalPipelineInstallers>,
     pub(crate) global_function_installers: Option<GlobalFunctionInstallers>,
     pub(crate) callback_lookup_table: Arc<Mutex<CallbackLookupTable>>,
+    pub(crate) diagnostics: Diagnostics,
 }
 
 impl Parser {
@@ -92,19 +95,20 @@ impl Parser {
             sources: btreemap!{},
             enums: vec![],
             models: vec![],
-            connector: None,
+            connectors: vec![],
             config: None,
             generators: vec![],
             clients: vec![],
             next_id: 0,
             resolved: false,
             global_model_decorators: None,
-            global_field_decorators: None,
+            global_field_decorators: HashMap::new(),
             global_relation_decorators: None,
             global_property_decorators: None,
             global_pipeline_installers: None,
             global_function_installers: None,
             callback_lookup_table,
+            diagnostics: Diagnostics::new(),
         }
     }
 
@@ -130,28 +134,73 @@ impl Parser {
             if result.is_some() {
                 result.unwrap()
             } else {
-                panic!("Cannot find a schema file.")
+                self.diagnostics.add_error_without_source("Cannot find a schema file.");
+                return
             }
         };
         let relative = PathBuf::from(main);
         let absolute = match fs::canonicalize(&relative) {
             Ok(path) => path,
-            Err(_) => panic!("Schema file '{}' is not found.", relative.to_str().unwrap()),
+            Err(_) => {
+                self.diagnostics.add_error_without_source(format!("Schema file '{}' is not found.", relative.to_str().unwrap()));
+                return
+            }
         };
         self.parse_source(&absolute);
+        // resolving an incomplete syntax tree only produces noise
+        if self.diagnostics.has_errors() { return }
         Resolver::resolve_parser(self);
     }
 
+    pub(crate) fn report_error(&self, source_id: usize, span: Span, message: impl Into<String>) {
+        self.to_mut().diagnostics.add_error(source_id, span, message);
+    }
+
+    pub(crate) fn report_warning(&self, source_id: usize, span: Span, message: impl Into<String>) {
+        self.to_mut().diagnostics.add_warning(source_id, span, message);
+    }
+
+    pub(crate) fn report_error_without_source(&self, message: impl Into<String>) {
+        self.to_mut().diagnostics.add_error_without_source(message);
+    }
+
     fn parse_source(&mut self, path: &PathBuf) {
         let source_id = self.next_id();
         let content = match fs::read_to_string(&path) {
             Ok(content) => content,
-            Err(err) => panic!("{}", err)
+            Err(err) => {
+                self.diagnostics.add_error_without_source(format!("Cannot read schema file '{}': {}", path.display(), err));
+                return
+            }
         };
+        self.diagnostics.insert_source(source_id, path.clone(), content.clone());
         let mut pairs = match SchemaParser::parse(Rule::schema, &content) {
             Ok(pairs) => pairs,
-            Err(err) => panic!("{}", err)
+            Err(err) => {
+                let span = match err.location {
+                    InputLocation::Pos(pos) => Span::new(pos, pos),
+                    InputLocation::Span((start, end)) => Span::new(start, end),
+                };
+                self.diagnostics.add_error(source_id, span, Self::syntax_error_message(&err));
+                return
+            }
         };
+        // the grammar accepts these to report them here with their positions
+        let mut has_syntax_errors = false;
+        for pair in pairs.clone().flatten() {
+            match pair.as_rule() {
+                Rule::CATCH_ALL => {
+                    self.diagnostics.add_error(source_id, Self::parse_span(&pair), format!("Unexpected token '{}'.", pair.as_str().trim()));
+                    has_syntax_errors = true;
+                }
+                Rule::empty_argument => {
+                    self.diagnostics.add_error(source_id, Self::parse_span(&pair), "Empty argument found.");
+                    has_syntax_errors = true;
+                }
+                _ => (),
+            }
+        }
+        if has_syntax_errors { return }
         let pairs = pairs.next().unwrap();
         let mut tops: BTreeMap<usize, Top> = btreemap![];
         let mut imports: BTreeSet<usize> = btreeset!{};
@@ -164,9 +213,10 @@ impl Parser {
             let item_id = self.next_id();
             match current.as_rule() {
                 Rule::import_statement => {
-                    let import = self.parse_import(current, source_id, item_id, path.clone());
-                    tops.insert(item_id, import);
-                    imports.insert(item_id);
+                    if let Some(import) = self.parse_import(current, source_id, item_id, path.clone()) {
+                        tops.insert(item_id, import);
+                        imports.insert(item_id);
+                    }
                 },
                 Rule::let_declaration => {
                     let constant = self.parse_let_declaration(current, source_id, item_id);
@@ -186,13 +236,13 @@ impl Parser {
                     self.enums.push((source_id, item_id));
                 },
                 Rule::config_declaration => {
-                    let config_block = self.parse_config_block(current, source_id, item_id);
-                    tops.insert(item_id, config_block);
+                    if let Some(config_block) = self.parse_config_block(current, source_id, item_id) {
+                        tops.insert(item_id, config_block);
+                    }
                 },
                 Rule::EOI | Rule::EMPTY_LINES => {},
-                Rule::CATCH_ALL => panic!("Catch all: {}", current.as_str()),
                 Rule::comment_block => (),
-                _ => panic!("Parsing panic! {}", current),
+                _ => unreachable!("unexpected top level rule {:?}", current.as_rule()),
             }
         }
         let result = Source::new(source_id, path.clone(), tops, imports, constants, enums, models);
@@ -207,7 +257,23 @@ impl Parser {
         self.sources.insert(source_id, result);
     }
 
-    fn parse_import(&mut self, pair: Pair<'_>, source_id: usize, item_id: usize, path: PathBuf) -> Top {
+    fn syntax_error_message(err: &pest::error::Error<Rule>) -> String {
+        match &err.variant {
+            ErrorVariant::ParsingError { positives, negatives } => {
+                let names = |rules: &Vec<Rule>| rules.iter().map(|r| format!("{:?}", r)).collect::<Vec<String>>().join(", ");
+                if !positives.is_empty() {
+                    format!("Syntax error, expected {}.", names(positives))
+                } else if !negatives.is_empty() {
+                    format!("Syntax error, unexpected {}.", names(negatives))
+                } else {
+                    "Syntax error.".to_owned()
+                }
+            }
+            ErrorVariant::CustomError { message } => format!("Syntax error, {}.", message),
+        }
+    }
+
+    fn parse_import(&mut self, pair: Pair<'_>, source_id: usize, item_id: usize, path: PathBuf) -> Option<Top> {
         let mut identifiers = vec![];
         let span = Self::parse_span(&pair);
         let mut source: Option<StringLiteral> = None;
@@ -225,9 +291,12 @@ impl Parser {
         let new = dir.join(&relative);
         let absolute = match Self::canonicalize(&new) {
             Some(path) => path,
-            None => panic!("Schema file '{}' is not found.", relative.to_str().unwrap()),
+            None => {
+                self.diagnostics.add_error(source_id, span, format!("Schema file '{}' is not found.", relative.to_str().unwrap()));
+                return None
+            }
         };
-        Top::Import(Import::new(item_id, source_id, identifiers, source.unwrap(), absolute, span))
+        Some(Top::Import(Import::new(item_id, source_id, identifiers, source.unwrap(), absolute, span)))
     }
 
     fn canonicalize(path_buf: &PathBuf) -> Option<PathBuf> {
@@ -279,7 +348,7 @@ impl Parser {
                     }
 
                 },
-                _ => panic!("error."),
+                _ => unreachable!(),
             }
         }
         CommentBlock {
@@ -323,7 +392,7 @@ impl Parser {
                 Rule::block_decorator => decorators.push(Self::parse_decorator(current)),
                 Rule::item_decorator => decorators.push(Self::parse_decorator(current)),
                 Rule::triple_comment_block => comment_block = Some(Self::parse_comment_block(current)),
-                _ => panic!("error. {:?}", current),
+                _ => unreachable!(),
             }
         }
         Top::Model(Model::new(
@@ -375,7 +444,7 @@ impl Parser {
                 Rule::identifier => identifier = Some(Self::parse_identifier(&current)),
                 Rule::enum_value_declaration => choices.push(self.parse_enum_value(current)),
                 Rule::block_decorator => decorators.push(Self::parse_decorator(current)),
-                _ => panic!("error. {}", current),
+                _ => unreachable!(),
             }
         }
         Top::Enum(Enum::new(
@@ -398,7 +467,7 @@ impl Parser {
                 Rule::COLON | Rule::EMPTY_LINES | Rule::comment_block => {},
                 Rule::identifier => identifier = Some(Self::parse_identifier(&current)),
                 Rule::item_decorator => decorators.push(Self::parse_decorator(current)),
-                _ => panic!("error."),
+                _ => unreachable!(),
             }
         }
         EnumChoice::new(identifier.unwrap(), decorators, span)
@@ -412,13 +481,13 @@ impl Parser {
             match current.as_rule() {
                 Rule::identifier => identifier = Some(Self::parse_identifier(&current)),
                 Rule::expression => expression = Some(Self::parse_expression(current)),
-                _ => panic!("error."),
+                _ => unreachable!(),
             }
         }
         Top::Constant(Constant::new(item_id, source_id, identifier.unwrap(), expression.unwrap(), span))
     }
 
-    fn parse_config_block(&mut self, pair: Pair<'_>, source_id: usize, item_id: usize) -> Top {
+    fn parse_config_block(&mut self, pair: Pair<'_>, source_id: usize, item_id: usize) -> Option<Top> {
         let mut identifier: Option<Identifier> = None;
         let mut items: Vec<Item> = vec![];
         let mut keyword = "";
@@ -438,27 +507,25 @@ impl Parser {
         match keyword {
             "server" => {
                 if self.config.is_some() {
-                    panic!("Duplicated config found.");
+                    self.diagnostics.add_error(source_id, span, "Duplicated server config found.");
+                    return None
                 }
                 self.config = Some((source_id, item_id));
-                Top::ServerConfig(ServerConfig::new(item_id, source_id, items, span))
+                Some(Top::ServerConfig(ServerConfig::new(item_id, source_id, items, span)))
             },
             "connector" => {
-                if self.connector.is_some() {
-                    panic!("Duplicated connector found.");
-                }
-                self.connector = Some((source_id, item_id));
-                Top::Connector(Connector::new(items, span, source_id, item_id))
+                self.connectors.push((source_id, item_id));
+                Some(Top::Connector(Connector::new(identifier, items, span, source_id, item_id)))
             },
             "entity" => {
                 self.generators.push((source_id, item_id));
-                Top::Generator(Generator::new(item_id, source_id, identifier, items, span))
+                Some(Top::Generator(Generator::new(item_id, source_id, identifier, items, span)))
             },
             "client" => {
                 self.clients.push((source_id, item_id));
-                Top::Client(Client::new(item_id, source_id, identifier, items, span))
+                Some(Top::Client(Client::new(item_id, source_id, identifier, items, span)))
             },
-            _ => panic!(),
+            _ => unreachable!(),
         }
     }
 
@@ -470,7 +537,7 @@ impl Parser {
             match current.as_rule() {
                 Rule::identifier => identifier = Some(Self::parse_identifier(&current)),
                 Rule::expression => expression = Some(Self::parse_expression(current)),
-                _ => panic!("error."),
+                _ => unreachable!(),
             }
         }
         Item { identifier: identifier.unwrap(), expression: expression.unwrap(), span }
@@ -482,7 +549,7 @@ impl Parser {
         for current in pair.into_inner() {
             match current.as_rule() {
                 Rule::identifier_unit => unit = Some(Self::parse_unit(current)),
-                _ => panic!(),
+                _ => unreachable!(),
             }
         }
         Decorator::new(unit.unwrap(), span)
@@ -494,7 +561,7 @@ impl Parser {
         for current in pair.into_inner() {
             match current.as_rule() {
                 Rule::identifier_unit => unit = Some(Self::parse_unit(current)),
-                _ => panic!(),
+                _ => unreachable!(),
             }
         }
         Pipeline {
@@ -513,8 +580,8 @@ impl Parser {
                     return Self::parse_named_argument(current);
                 },
                 Rule::expression => value = Some(Self::parse_expression(current).kind),
-                Rule::empty_argument => panic!("Empty argument found."),
-                _ => panic!(),
+                Rule::empty_argument => unreachable!("empty arguments are reported before building the syntax tree"),
+                _ => unreachable!(),
             }
         }
         Argument { name, value: value.unwrap(), span, resolved: None }
@@ -528,8 +595,8 @@ impl Parser {
             match current.as_rule() {
                 Rule::identifier => name = Some(Self::parse_identifier(&current)),
                 Rule::expression => value = Some(Self::parse_expression(current).kind),
-                Rule::empty_argument => panic!("Empty argument found."),
-                _ => panic!(),
+                Rule::empty_argument => unreachable!("empty arguments are reported before building the syntax tree"),
+                _ => unreachable!(),
             }
         }
         Argument { name, value: value.unwrap(), span, resolved: None }
@@ -544,10 +611,10 @@ impl Parser {
                 Rule::arith_expr => return Expression::new(ExpressionKind::ArithExpr(Self::parse_arith_expr(current))),
                 Rule::unit => return Expression::new(Self::parse_unit(current)),
                 Rule::pipeline => return Expression::new(ExpressionKind::Pipeline(Self::parse_pipeline(current))),
-                _ => panic!(),
+                _ => unreachable!(),
             }
         }
-        panic!();
+        unreachable!();
     }
 
     fn parse_unit(pair: Pair<'_>) -> ExpressionKind {
@@ -587,7 +654,7 @@ impl Parser {
             match current.as_rule() {
                 Rule::identifier => value = Some(current.as_str().to_owned()),
                 Rule::argument_list => arg_list = Some(Self::parse_argument_list(current)),
-                _ => panic!()
+                _ => unreachable!()
             }
         }
         EnumChoiceLiteral { value: value.unwrap(), span, argument_list: arg_list }
@@ -600,7 +667,7 @@ impl Parser {
         for current in pair.into_inner() {
             match current.as_rule() {
                 Rule::unit => expressions.push(Self::parse_unit(current)),
-                _ => panic!()
+                _ => unreachable!()
             }
         }
         NullishCoalescing { expressions, span }
@@ -636,6 +703,7 @@ impl Parser {
             Rule::operand => ArithExpr::Expression(Box::new(Self::parse_expression(primary).kind)),
             _ => unreachable!(),
         }).map_infix(|lhs, op, rhs| {
+            let span = Self::parse_span(&op);
             let op = match op.as_rule() {
                 Rule::ADD => Op::Add,
                 Rule::SUB => Op::Sub,
@@ -651,6 +719,7 @@ impl Parser {
                 lhs: Box::new(lhs),
                 op,
                 rhs: Box::new(rhs),
+                span,
             }
         }).parse(pair.into_inner())
     }
@@ -660,10 +729,10 @@ impl Parser {
         for current in pair.into_inner() {
             match current.as_rule() {
                 Rule::expression => return Subscript { expression: Box::new(Self::parse_expression(current).kind), span },
-                _ => panic!(),
+                _ => unreachable!(),
             }
         }
-        panic!()
+        unreachable!()
     }
 
     fn parse_argument_list(pair: Pair<'_>) -> ArgumentList {
@@ -672,7 +741,7 @@ impl Parser {
         for current in pair.into_inner() {
             match current.as_rule() {
                 Rule::argument => arguments.push(Self::parse_argument(current)),
-                _ => panic!("{}", current),
+                _ => unreachable!(),
             }
         }
         ArgumentList { arguments, span, resolved: false }
@@ -683,10 +752,10 @@ impl Parser {
         for current in pair.into_inner() {
             match current.as_rule() {
                 Rule::expression => return Group { expression: Box::new(Self::parse_expression(current).kind), span },
-                _ => panic!(),
+                _ => unreachable!(),
             }
         }
-        panic!()
+        unreachable!()
     }
 
     fn parse_range_literal(pair: Pair<'_>) -> RangeLiteral {
@@ -698,7 +767,7 @@ impl Parser {
                 Rule::range_end => expressions.push(Self::parse_range_end(current)),
                 Rule::RANGE_OPEN => closed = false,
                 Rule::RANGE_CLOSE => closed = true,
-                _ => panic!("{:?} {:?}", current.as_rule(), current.as_span()),
+                _ => unreachable!(),
             }
         }
         RangeLiteral { closed, expressions, span }
@@ -710,10 +779,10 @@ impl Parser {
             match current.as_rule() {
                 Rule::numeric_literal => return ExpressionKind::NumericLiteral(NumericLiteral { value: current.as_str().to_string(), span }),
                 Rule::unit_without_range_literal => return Self::parse_unit(current),
-                _ => panic!(),
+                _ => unreachable!(),
             }
         }
-        panic!()
+        unreachable!()
     }
 
     fn parse_tuple_literal(pair: Pair<'_>) -> TupleLiteral {
@@ -722,7 +791,7 @@ impl Parser {
         for current in pair.into_inner() {
             match current.as_rule() {
                 Rule::expression => expressions.push(Self::parse_expression(current).kind),
-                _ => panic!(),
+                _ => unreachable!(),
             }
         }
         TupleLiteral { expressions, span }
@@ -734,7 +803,7 @@ impl Parser {
         for current in pair.into_inner() {
             match current.as_rule() {
                 Rule::expression => expressions.push(Self::parse_expression(current).kind),
-                _ => panic!(),
+                _ => unreachable!(),
             }
         }
         ArrayLiteral { expressions, span }
@@ -785,7 +854,7 @@ impl Parser {
                         collection_required = false;
                     }
                 },
-                _ => panic!(),
+                _ => unreachable!(),
             }
         }
         Type::new(
@@ -801,7 +870,7 @@ impl Parser {
         for current in pair.into_inner() {
             match current.as_rule() {
                 Rule::identifier => identifiers.push(Self::parse_identifier(&current)),
-                _ => panic!(),
+                _ => unreachable!(),
             }
         }
         identifiers
@@ -826,12 +895,23 @@ impl Parser {
         self.sources.get(&id).unwrap()
     }
 
+    pub(crate) fn get_connector(&self, connector_ref: (usize, usize)) -> &Connector {
+        self.get_source(connector_ref.0).get_connector(connector_ref.1)
+    }
+
+    /// The connector of models without `@@connector`. It's the unnamed connector, or the first
+    /// declared one if every connector is named.
+    pub(crate) fn default_connector(&self) -> Option<&Connector> {
+        let connectors: Vec<&Connector> = self.connectors.iter().map(|r| self.get_connector(*r)).collect();
+        connectors.iter().find(|c| c.identifier.is_none()).or(connectors.first()).cloned()
+    }
+
     pub(crate) fn set_global_model_decorators(&self, deco: GlobalModelDecorators) {
         self.to_mut().global_model_decorators = Some(deco);
     }
 
-    pub(crate) fn set_global_field_decorators(&self, deco: GlobalFieldDecorators) {
-        self.to_mut().global_field_decorators = Some(deco);
+    pub(crate) fn set_global_field_decorators(&self, connector: &str, deco: GlobalFieldDecorators) {
+        self.to_mut().global_field_decorators.insert(connector.to_owned(), deco);
     }
 
     pub(crate) fn set_global_relation_decorators(&self, deco: GlobalRelationDecorators) {
@@ -854,8 +934,9 @@ impl Parser {
         self.global_model_decorators.as_ref().unwrap()
     }
 
-    pub(crate) fn global_field_decorators(&self) -> &GlobalFieldDecorators {
-        self.global_field_decorators.as_ref().unwrap()
+    /// Field decorators of models stored by `connector`. `@db` types differ between databases.
+    pub(crate) fn global_field_decorators(&self, connector: &str) -> &GlobalFieldDecorators {
+        self.global_field_decorators.get(connector).unwrap()
     }
 
     pub(crate) fn global_relation_decorators(&self) -> &GlobalRelationDecorators {
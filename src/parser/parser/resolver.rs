@@ -1,7 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::i64;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 use path_absolutize::Absolutize;
 use regex::Regex;
 use snailquote::unescape;
@@ -15,9 +16,11 @@ use crate::parser::ast::decorator::Decorator;
 use crate::parser::ast::entity::Entity;
 use crate::parser::ast::expression::{ArrayLiteral, BitwiseNegation, BoolLiteral, DictionaryLiteral, EnumChoiceLiteral, Expression, ExpressionKind, Negation, NullishCoalescing, NullLiteral, NumericLiteral, RangeLiteral, RegExpLiteral, StringLiteral, TupleLiteral};
 use crate::parser::ast::field::{Field, FieldClass};
+use crate::parser::ast::r#type::Arity;
 use crate::parser::ast::group::Group;
 use crate::parser::ast::identifier::Identifier;
 use crate::parser::ast::import::Import;
+use crate::parser::ast::item::Item;
 use crate::parser::ast::model::Model;
 use crate::parser::ast::pipeline::Pipeline;
 use crate::parser::ast::r#enum::{Enum, EnumChoice};
@@ -45,9 +48,14 @@ pub(crate) struct Resolver { }
 impl Resolver {
 
     pub(crate) fn resolve_parser(parser: &Parser) {
-        let database_name = Self::resolve_connector(parser);
+        if parser.config.is_none() {
+            parser.report_error_without_source("Server config is not defined.");
+        }
+        let Some(database_names) = Self::resolve_connectors(parser) else { return };
         parser.set_global_model_decorators(GlobalModelDecorators::new());
-        parser.set_global_field_decorators(GlobalFieldDecorators::new(database_name));
+        for (connector_name, database_name) in database_names {
+            parser.set_global_field_decorators(&connector_name, GlobalFieldDecorators::new(database_name));
+        }
         parser.set_global_relation_decorators(GlobalRelationDecorators::new());
         parser.set_global_property_decorators(GlobalPropertyDecorators::new());
         parser.set_global_pipeline_installers(GlobalPipelineInstallers::new());
@@ -140,9 +148,15 @@ impl Resolver {
         for decorator in model.decorators.iter_mut() {
             Self::resolve_model_decorator(parser, source, decorator);
         }
+        let connector = Self::model_connector_name(parser, source, model);
         // fields
         for field in model.fields.iter_mut() {
-            Self::resolve_field(parser, source, field);
+            Self::resolve_field(parser, source, field, &connector);
+        }
+        Self::check_soft_delete_decorator(parser, source, model);
+        Self::check_version_fields(parser, source, model);
+        if let Some(database) = Self::connector_database(parser, &connector) {
+            Self::check_model_features(parser, source, model, &database);
         }
         // cached enums
         //
@@ -150,141 +164,135 @@ impl Resolver {
     }
 
     fn resolve_model_decorator(parser: &Parser, source: &Source, decorator: &mut Decorator) {
-        match &decorator.expression {
-            ExpressionKind::Identifier(identifier) => {
-                let d = parser.global_model_decorators();
-                let accessible = d.get(&identifier.name);
-                decorator.accessible = Some(accessible.clone());
-                decorator.arguments = None;
-            }
-            ExpressionKind::Unit(unit) => {
-                let identifier = unit.expressions.get(0).unwrap().as_identifier().unwrap();
-                let d = parser.global_model_decorators();
-                let mut accessible = d.get(&identifier.name);
-                let mut arg_list: Option<ArgumentList> = None;
-                for (index, expression) in unit.expressions.iter().enumerate() {
-                    if index == 0 { continue }
-                    match expression {
-                        ExpressionKind::ArgumentList(argument_list) => {
-                            arg_list = Some(argument_list.clone());
-                        }
-                        ExpressionKind::Subscript(_subscript) => {
-                            panic!("Cannot access decorator object with subscript.")
-                        }
-                        ExpressionKind::Identifier(identifier) => {
-                            accessible = accessible.access_property(&identifier.name).as_accessible().unwrap()
-                        }
-                        _ => panic!()
-                    }
-                }
-                decorator.accessible = Some(accessible.clone());
-                for argument in arg_list.as_mut().unwrap().arguments.iter_mut() {
-                    let when_option = identifier.name.as_str() == "disable";
-                    let result = Self::resolve_expression_kind(parser, source, &argument.value, when_option);
-                    let value = Self::unwrap_into_value_if_needed(parser, source, &result);
-                    argument.resolved = Some(Entity::Value(value));
+        let decorators = parser.global_model_decorators();
+        Self::resolve_decorator(parser, source, decorator, "model", |name| decorators.get(name));
+    }
+
+    /// The name of the connector which stores `model`, taken from its resolved `@@connector`.
+    fn model_connector_name(parser: &Parser, source: &Source, model: &Model) -> String {
+        let default = parser.default_connector().unwrap().name().to_owned();
+        for decorator in model.decorators.iter() {
+            let identifier = match &decorator.expression {
+                ExpressionKind::Unit(unit) => unit.expressions.first().unwrap().as_identifier().unwrap(),
+                _ => continue,
+            };
+            if identifier.name != "connector" { continue }
+            let value = decorator.arguments.as_ref()
+                .and_then(|list| list.arguments.first())
+                .and_then(|arg| arg.resolved.as_ref())
+                .and_then(|entity| entity.as_value());
+            let name = match value {
+                Some(Value::RawEnumChoice(name, _)) => name.clone(),
+                Some(Value::String(name)) => name.clone(),
+                _ => {
+                    parser.report_error(source.id, decorator.span, "@@connector requires a connector name, e.g. '.analytics'.");
+                    return default;
                 }
-                decorator.arguments = arg_list;
+            };
+            if !parser.global_field_decorators.contains_key(&name) {
+                parser.report_error(source.id, decorator.span, format!("Undefined connector '{}'.", name));
+                return default;
             }
-            _ => panic!()
+            return name;
         }
-        decorator.resolved = true;
+        default
     }
 
-    fn resolve_field_decorator(parser: &Parser, source: &Source, decorator: &mut Decorator) {
-        match &decorator.expression {
-            ExpressionKind::Identifier(identifier) => {
-                let d = parser.global_field_decorators();
-                let accessible = d.get(&identifier.name);
-                decorator.accessible = Some(accessible.clone());
-                decorator.arguments = None;
-            }
-            ExpressionKind::Unit(unit) => {
-                let identifier = unit.expressions.get(0).unwrap().as_identifier().unwrap();
-                let d = parser.global_field_decorators();
-                let mut accessible = d.get(&identifier.name);
-                let mut arg_list: Option<ArgumentList> = None;
-                for (index, expression) in unit.expressions.iter().enumerate() {
-                    if index == 0 { continue }
-                    match expression {
-                        ExpressionKind::ArgumentList(argument_list) => {
-                            arg_list = Some(argument_list.clone());
-                        }
-                        ExpressionKind::Subscript(_subscript) => {
-                            panic!("Cannot access decorator object with subscript.")
-                        }
-                        ExpressionKind::Identifier(identifier) => {
-                            accessible = accessible.access_property(&identifier.name).as_accessible().unwrap()
-                        }
-                        _ => panic!()
-                    }
-                }
-                decorator.accessible = Some(accessible.clone());
-                for argument in arg_list.as_mut().unwrap().arguments.iter_mut() {
-                    let result = Self::resolve_expression_kind(parser, source, &argument.value, false);
-                    let value = Self::unwrap_into_value_if_needed(parser, source, &result);
-                    argument.resolved = Some(Entity::Value(value));
+    /// The decorator of `model` named `name`.
+    fn model_decorator<'a>(model: &'a Model, name: &str) -> Option<&'a Decorator> {
+        Self::named_decorator(&model.decorators, name)
+    }
+
+    fn named_decorator<'a>(decorators: &'a [Decorator], name: &str) -> Option<&'a Decorator> {
+        decorators.iter().find(|decorator| match &decorator.expression {
+            ExpressionKind::Identifier(identifier) => identifier.name == name,
+            ExpressionKind::Unit(unit) => unit.expressions.first().and_then(|e| e.as_identifier()).is_some_and(|i| i.name == name),
+            _ => false,
+        })
+    }
+
+    /// The field name which is the first argument of `decorator`, either unnamed or named
+    /// `argument`.
+    fn decorator_field_name<'a>(decorator: &'a Decorator, argument: &str) -> Option<&'a str> {
+        let arg0 = decorator.arguments.as_ref().and_then(|list| list.arguments.first())?;
+        if arg0.name.as_ref().is_some_and(|n| n.name != argument) {
+            return None;
+        }
+        match arg0.resolved.as_ref().and_then(|entity| entity.as_value()) {
+            Some(Value::RawEnumChoice(name, _)) => Some(name.as_str()),
+            Some(Value::String(name)) => Some(name.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Report a `@@softDelete` field which isn't an optional DateTime field of the model.
+    fn check_soft_delete_decorator(parser: &Parser, source: &Source, model: &Model) {
+        let Some(decorator) = Self::model_decorator(model, "softDelete") else { return };
+        if !decorator.resolved { return }
+        let Some(name) = Self::decorator_field_name(decorator, "field") else {
+            parser.report_error(source.id, decorator.span, "@@softDelete requires a field name, e.g. '.deletedAt'.");
+            return
+        };
+        match model.fields.iter().find(|f| f.identifier.name == name) {
+            Some(field) => {
+                let r#type = &field.r#type;
+                let is_field = matches!(field.field_class, FieldClass::Field);
+                if !is_field || r#type.identifier.name != "DateTime" || r#type.arity != Arity::Scalar || r#type.item_required {
+                    parser.report_error(source.id, decorator.span, format!("Soft delete field '{}' should be optional DateTime.", name));
                 }
-                decorator.arguments = arg_list;
             }
-            _ => panic!()
+            None => parser.report_error(source.id, decorator.span, format!("Soft delete field '{}' is not found on model '{}'.", name, model.identifier.name)),
         }
-        decorator.resolved = true;
     }
 
-    fn resolve_property_decorator(parser: &Parser, source: &Source, decorator: &mut Decorator) {
-        match &decorator.expression {
-            ExpressionKind::Identifier(identifier) => {
-                let d = parser.global_property_decorators();
-                let accessible = d.get(&identifier.name);
-                decorator.accessible = Some(accessible.clone());
-                decorator.arguments = None;
+    fn check_version_fields(parser: &Parser, source: &Source, model: &Model) {
+        let mut found = false;
+        for field in model.fields.iter() {
+            let Some(decorator) = Self::named_decorator(&field.decorators, "version") else { continue };
+            if found {
+                parser.report_error(source.id, decorator.span, format!("Model '{}' has more than one version field.", model.identifier.name));
+                continue
             }
-            ExpressionKind::Unit(unit) => {
-                let identifier = unit.expressions.get(0).unwrap().as_identifier().unwrap();
-                let d = parser.global_property_decorators();
-                let mut accessible = d.get(&identifier.name);
-                let mut arg_list: Option<ArgumentList> = None;
-                for (index, expression) in unit.expressions.iter().enumerate() {
-                    if index == 0 { continue }
-                    match expression {
-                        ExpressionKind::ArgumentList(argument_list) => {
-                            arg_list = Some(argument_list.clone());
-                        }
-                        ExpressionKind::Subscript(_subscript) => {
-                            panic!("Cannot access decorator object with subscript.")
-                        }
-                        ExpressionKind::Identifier(identifier) => {
-                            accessible = accessible.access_property(&identifier.name).as_accessible().unwrap()
-                        }
-                        _ => panic!()
-                    }
-                }
-                decorator.accessible = Some(accessible.clone());
-                for argument in arg_list.as_mut().unwrap().arguments.iter_mut() {
-                    let result = Self::resolve_expression_kind(parser, source, &argument.value, false);
-                    let value = Self::unwrap_into_value_if_needed(parser, source, &result);
-                    argument.resolved = Some(Entity::Value(value));
-                }
-                decorator.arguments = arg_list;
+            found = true;
+            let r#type = &field.r#type;
+            let is_field = matches!(field.field_class, FieldClass::Field);
+            let is_int = ["Int", "Int32", "Int64"].contains(&r#type.identifier.name.as_str());
+            if !is_field || !is_int || r#type.arity != Arity::Scalar || !r#type.item_required {
+                parser.report_error(source.id, decorator.span, format!("Version field '{}' of model '{}' should be required Int or Int64.", field.identifier.name, model.identifier.name));
             }
-            _ => panic!()
         }
-        decorator.resolved = true;
+    }
+
+    fn resolve_field_decorator(parser: &Parser, source: &Source, decorator: &mut Decorator, connector: &str) {
+        let decorators = parser.global_field_decorators(connector);
+        Self::resolve_decorator(parser, source, decorator, "field", |name| decorators.get(name));
+    }
+
+    fn resolve_property_decorator(parser: &Parser, source: &Source, decorator: &mut Decorator) {
+        let decorators = parser.global_property_decorators();
+        Self::resolve_decorator(parser, source, decorator, "property", |name| decorators.get(name));
     }
 
     fn resolve_relation_decorator(parser: &Parser, source: &Source, decorator: &mut Decorator) {
+        let decorators = parser.global_relation_decorators();
+        Self::resolve_decorator(parser, source, decorator, "relation", |name| decorators.get(name));
+    }
+
+    fn resolve_decorator<'a>(parser: &Parser, source: &Source, decorator: &mut Decorator, kind: &str, lookup: impl Fn(&str) -> Option<&'a Accessible>) {
         match &decorator.expression {
             ExpressionKind::Identifier(identifier) => {
-                let d = parser.global_relation_decorators();
-                let accessible = d.get(&identifier.name);
-                decorator.accessible = Some(accessible.clone());
+                match lookup(&identifier.name) {
+                    Some(accessible) => decorator.accessible = Some(accessible.clone()),
+                    None => parser.report_error(source.id, identifier.span, format!("Unknown {} decorator '@{}'.", kind, identifier.name)),
+                }
                 decorator.arguments = None;
             }
             ExpressionKind::Unit(unit) => {
                 let identifier = unit.expressions.get(0).unwrap().as_identifier().unwrap();
-                let d = parser.global_relation_decorators();
-                let mut accessible = d.get(&identifier.name);
+                let Some(mut accessible) = lookup(&identifier.name) else {
+                    parser.report_error(source.id, identifier.span, format!("Unknown {} decorator '@{}'.", kind, identifier.name));
+                    return
+                };
                 let mut arg_list: Option<ArgumentList> = None;
                 for (index, expression) in unit.expressions.iter().enumerate() {
                     if index == 0 { continue }
@@ -292,24 +300,34 @@ impl Resolver {
                         ExpressionKind::ArgumentList(argument_list) => {
                             arg_list = Some(argument_list.clone());
                         }
-                        ExpressionKind::Subscript(_subscript) => {
-                            panic!("Cannot access decorator object with subscript.")
+                        ExpressionKind::Subscript(subscript) => {
+                            parser.report_error(source.id, subscript.span, "Cannot access decorator object with subscript.");
+                            return
                         }
-                        ExpressionKind::Identifier(identifier) => {
-                            accessible = accessible.access_property(&identifier.name).as_accessible().unwrap()
+                        ExpressionKind::Identifier(member) => {
+                            match accessible.as_container().and_then(|c| c.objects.get(&member.name)).and_then(|e| e.as_accessible()) {
+                                Some(member_accessible) => accessible = member_accessible,
+                                None => {
+                                    parser.report_error(source.id, member.span, format!("Unknown {} decorator '@{}.{}'.", kind, identifier.name, member.name));
+                                    return
+                                }
+                            }
                         }
-                        _ => panic!()
+                        _ => unreachable!()
                     }
                 }
                 decorator.accessible = Some(accessible.clone());
-                for argument in arg_list.as_mut().unwrap().arguments.iter_mut() {
-                    let result = Self::resolve_expression_kind(parser, source, &argument.value, false);
-                    let value = Self::unwrap_into_value_if_needed(parser, source, &result);
-                    argument.resolved = Some(Entity::Value(value));
+                if let Some(arg_list) = arg_list.as_mut() {
+                    let when_option = kind == "model" && identifier.name.as_str() == "disable";
+                    for argument in arg_list.arguments.iter_mut() {
+                        let result = Self::resolve_expression_kind(parser, source, &argument.value, when_option);
+                        let value = Self::unwrap_into_value_if_needed(parser, source, &result);
+                        argument.resolved = Some(Entity::Value(value));
+                    }
                 }
                 decorator.arguments = arg_list;
             }
-            _ => panic!()
+            _ => unreachable!()
         }
         decorator.resolved = true;
     }
@@ -336,7 +354,7 @@ impl Resolver {
                             args: vec![]
                         })
                     } else {
-                        panic!("Cannot find pipeline item named '{}'.", identifier.name);
+                        parser.report_error(source.id, identifier.span, format!("Cannot find pipeline item named '{}'.", identifier.name));
                     }
                 }
             }
@@ -350,7 +368,7 @@ impl Resolver {
                                 if let Some(installer) = installer {
                                     items.push(ASTPipelineItem { installer: Some(installer.clone()), function_installer: None, lookup_table: None, args: vec![]});
                                 } else {
-                                    panic!("Cannot find pipeline item named '{}'.", identifier.name);
+                                    parser.report_error(source.id, previous_identifier.span, format!("Cannot find pipeline item named '{}'.", previous_identifier.name));
                                 }
                             }
                             previous_identifier = Some(&identifier);
@@ -373,12 +391,13 @@ impl Resolver {
                                 if let Some(installer) = installer {
                                     items.push(ASTPipelineItem { installer: None, function_installer: Some(installer.clone()), lookup_table: Some(parser.callback_lookup_table.clone()), args: args.arguments});
                                 } else {
-                                    panic!("Cannot find pipeline item named '{}'.", previous_identifier.unwrap().name);
+                                    let previous_identifier = previous_identifier.unwrap();
+                                    parser.report_error(source.id, previous_identifier.span, format!("Cannot find pipeline item named '{}'.", previous_identifier.name));
                                 }
                             }
                             previous_identifier = None;
                         }
-                        _ => panic!()
+                        _ => unreachable!()
                     }
                 }
                 if let Some(previous_identifier) = previous_identifier {
@@ -386,28 +405,32 @@ impl Resolver {
                     if let Some(installer) = installer {
                         items.push(ASTPipelineItem { installer: Some(installer.clone()), function_installer: None, lookup_table: None, args: vec![]});
                     } else {
-                        panic!("Cannot find pipeline item named '{}'.", previous_identifier.name);
+                        parser.report_error(source.id, previous_identifier.span, format!("Cannot find pipeline item named '{}'.", previous_identifier.name));
                     }
                 }
             }
-            _ => panic!()
+            _ => unreachable!()
         }
         let ast_pipeline = ASTPipeline { items };
         let value_pipeline = ast_pipeline.to_value_pipeline();
         Entity::Value(Value::Pipeline(value_pipeline))
     }
 
-    fn resolve_field(parser: &Parser, source: &Source, field: &mut Field) {
+    fn resolve_field(parser: &Parser, source: &Source, field: &mut Field, connector: &str) {
         field.figure_out_class();
         match &field.field_class {
             FieldClass::Field => {
                 for decorator in field.decorators.iter_mut() {
-                    Self::resolve_field_decorator(parser, source, decorator);
+                    Self::resolve_field_decorator(parser, source, decorator, connector);
+                }
+                if let Some(database) = Self::connector_database(parser, connector) {
+                    Self::check_field_type(parser, source, field, &database);
                 }
             }
             FieldClass::Relation => {
                 for decorator in field.decorators.iter_mut() {
                     Self::resolve_relation_decorator(parser, source, decorator);
+                    Self::check_relation_decorator(parser, source, decorator);
                 }
             }
             FieldClass::Property => {
@@ -420,67 +443,235 @@ impl Resolver {
         field.resolved = true;
     }
 
-    pub(crate) fn resolve_connector(parser: &Parser) -> DatabaseName {
-        if parser.connector.is_none() {
-            panic!("Connector is not defined.");
+    /// Report `@relation` arguments which the relation decorator can't apply.
+    fn check_relation_decorator(parser: &Parser, source: &Source, decorator: &Decorator) {
+        let identifier = match &decorator.expression {
+            ExpressionKind::Identifier(identifier) => identifier,
+            ExpressionKind::Unit(unit) => unit.expressions.first().unwrap().as_identifier().unwrap(),
+            _ => return,
+        };
+        if identifier.name != "relation" || !decorator.resolved { return }
+        let arguments = decorator.arguments.as_ref().map(|list| list.arguments.as_slice()).unwrap_or(&[]);
+        let argument = |name: &str| arguments.iter().find(|a| a.name.as_ref().map(|n| n.name.as_str()) == Some(name));
+        match (argument("fields"), argument("through")) {
+            (Some(_), Some(_)) => parser.report_error(source.id, decorator.span, "A relation cannot have both 'fields' and 'through'."),
+            (Some(_), None) => if argument("references").is_none() {
+                parser.report_error(source.id, decorator.span, "A relation with 'fields' must have 'references'.");
+            }
+            (None, Some(_)) => if argument("local").is_none() || argument("foreign").is_none() {
+                parser.report_error(source.id, decorator.span, "A relation with 'through' must have 'local' and 'foreign'.");
+            }
+            (None, None) => parser.report_error(source.id, decorator.span, "One of 'fields' or 'through' must be provided."),
+        }
+        let rules: [(&str, &str, &[&str]); 3] = [
+            ("onDelete", "delete", &["default", "nullify", "cascade", "deny"]),
+            ("onUpdate", "update", &["default", "nullify", "cascade", "deny"]),
+            ("onDisconnect", "disconnect", &["default", "nullify", "delete"]),
+        ];
+        for (name, kind, choices) in rules {
+            let Some(argument) = argument(name) else { continue };
+            match argument.resolved.as_ref().and_then(|e| e.as_value()).and_then(|v| v.as_raw_enum_choice()) {
+                Some(rule) if choices.contains(&rule) => (),
+                Some(rule) => parser.report_error(source.id, argument.span, format!("Unknown {} rule '{}'.", kind, rule)),
+                None => parser.report_error(source.id, argument.span, format!("Value of '{}' should be an enum choice.", name)),
+            }
+        }
+    }
+
+    /// The database of the connector named `name`.
+    fn connector_database(parser: &Parser, name: &str) -> Option<DatabaseName> {
+        parser.connectors.iter()
+            .map(|connector_ref| parser.get_connector(*connector_ref))
+            .find(|connector| connector.name() == name)
+            .and_then(|connector| connector.provider.clone())
+    }
+
+    /// Report field types which `database` can't store.
+    fn check_field_type(parser: &Parser, source: &Source, field: &Field, database: &DatabaseName) {
+        let r#type = &field.r#type;
+        match database {
+            #[cfg(feature = "data-source-mssql")]
+            DatabaseName::MSSQL => {
+                if r#type.arity != Arity::Scalar {
+                    parser.report_error(source.id, r#type.identifier.span, "SQL Server doesn't support array or dictionary fields.");
+                } else if r#type.identifier.name == "ObjectId" {
+                    parser.report_error(source.id, r#type.identifier.span, "SQL Server doesn't support ObjectId fields.");
+                } else if r#type.identifier.name == "Json" {
+                    parser.report_error(source.id, r#type.identifier.span, "SQL Server doesn't support Json fields.");
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Report model decorators which `database` can't support.
+    fn check_model_features(parser: &Parser, source: &Source, model: &Model, database: &DatabaseName) {
+        match database {
+            #[cfg(feature = "data-source-mssql")]
+            DatabaseName::MSSQL => {
+                if let Some(decorator) = Self::model_decorator(model, "fullTextIndex") {
+                    parser.report_error(source.id, decorator.span, "SQL Server doesn't support full-text indices.");
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Resolve connector blocks into the database of each connector name.
+    pub(crate) fn resolve_connectors(parser: &Parser) -> Option<HashMap<String, DatabaseName>> {
+        if parser.connectors.is_empty() {
+            parser.report_error_without_source("Connector is not defined.");
+            return None;
+        }
+        let mut database_names = HashMap::new();
+        let mut complete = true;
+        for connector_ref in parser.connectors.iter() {
+            let database_name = Self::resolve_connector(parser, *connector_ref);
+            let connector = parser.get_connector(*connector_ref);
+            if database_names.contains_key(connector.name()) {
+                parser.report_error(connector.source_id, connector.span, "Duplicated connector found.");
+                continue
+            }
+            match database_name {
+                Some(database_name) => { database_names.insert(connector.name().to_owned(), database_name); }
+                None => complete = false,
+            }
         }
-        let connector_ref = parser.connector.unwrap();
+        if complete { Some(database_names) } else { None }
+    }
+
+    fn resolve_connector(parser: &Parser, connector_ref: (usize, usize)) -> Option<DatabaseName> {
         let source = parser.get_source(connector_ref.0);
         let top = source.to_mut().tops.get_mut(&connector_ref.1).unwrap();
-        let mut connector = top.as_connector_mut().unwrap();
+        let connector = top.as_connector_mut().unwrap();
+        Self::warn_duplicated_items(parser, source, &connector.items, "connector");
         for item in connector.items.iter_mut() {
             match item.identifier.name.as_str() {
                 "provider" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let provider_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let provider_str = provider_value.as_raw_enum_choice().unwrap();
-                    match provider_str {
+                    let provider_value = Self::resolve_item_value(parser, source, item);
+                    match provider_value.as_raw_enum_choice() {
                         #[cfg(feature = "data-source-sqlite")]
-                        "sqlite" => connector.provider = Some(DatabaseName::SQLite),
-                        "mongo" => connector.provider = Some(DatabaseName::MongoDB),
-                        "mysql" => connector.provider = Some(DatabaseName::MySQL),
-                        "postgres" => connector.provider = Some(DatabaseName::PostgreSQL),
-                        _ => panic!("Unrecognized provider. {}", provider_str)
+                        Some("sqlite") => connector.provider = Some(DatabaseName::SQLite),
+                        Some("mongo") => connector.provider = Some(DatabaseName::MongoDB),
+                        Some("mysql") => connector.provider = Some(DatabaseName::MySQL),
+                        Some("postgres") => connector.provider = Some(DatabaseName::PostgreSQL),
+                        #[cfg(feature = "data-source-mssql")]
+                        Some("mssql") => connector.provider = Some(DatabaseName::MSSQL),
+                        Some("custom") => match Self::custom_connector_name(&provider_value) {
+                            Some(name) => connector.provider = Some(DatabaseName::Custom(name)),
+                            None => parser.report_error(source.id, item.span, "Custom connector provider requires a name, e.g. '.custom(\"name\")'."),
+                        },
+                        Some(provider_str) => parser.report_error(source.id, item.span, format!("Unrecognized connector provider '{}'.", provider_str)),
+                        None => parser.report_error(source.id, item.span, "Value of 'provider' should be an enum choice."),
                     }
                 },
                 "url" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let url_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let url_str = url_value.as_str().unwrap();
-                    connector.url = Some(url_str.to_owned());
+                    let url_value = Self::resolve_item_value(parser, source, item);
+                    match url_value.as_str() {
+                        Some(url_str) => connector.url = Some(url_str.to_owned()),
+                        None => parser.report_error(source.id, item.span, "Value of 'url' should be string."),
+                    }
                 },
                 "debug" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let bool_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let bool = bool_value.as_bool().unwrap();
-                    connector.debug = bool;
+                    let bool_value = Self::resolve_item_value(parser, source, item);
+                    match bool_value.as_bool() {
+                        Some(bool) => connector.debug = bool,
+                        None => parser.report_error(source.id, item.span, "Value of 'debug' should be bool."),
+                    }
                 }
-                _ => { panic!("Undefined name '{}' in connector block.", item.identifier.name.as_str())}
+                "slowQueryThreshold" => {
+                    let value = Self::resolve_item_value(parser, source, item);
+                    match value.as_i64() {
+                        Some(n) if n > 0 => connector.slow_query_threshold = Some(n as u64),
+                        _ => parser.report_error(source.id, item.span, "Value of 'slowQueryThreshold' should be a positive int."),
+                    }
+                }
+                "poolSize" => {
+                    let value = Self::resolve_item_value(parser, source, item);
+                    match value.as_i64() {
+                        Some(n) if n > 0 => connector.pool.size = Some(n as u32),
+                        _ => parser.report_error(source.id, item.span, "Value of 'poolSize' should be a positive int."),
+                    }
+                }
+                "connectTimeout" => connector.pool.connect_timeout = Self::resolve_connector_duration(parser, source, item),
+                "acquireTimeout" => connector.pool.acquire_timeout = Self::resolve_connector_duration(parser, source, item),
+                "idleLifetime" => connector.pool.idle_lifetime = Self::resolve_connector_duration(parser, source, item),
+                "statementTimeout" => connector.pool.statement_timeout = Self::resolve_connector_duration(parser, source, item),
+                "replicas" => {
+                    let value = Self::resolve_item_value(parser, source, item);
+                    match value.as_vec().and_then(|urls| urls.iter().map(|url| url.as_str().map(|s| s.to_owned())).collect::<Option<Vec<String>>>()) {
+                        Some(urls) => connector.replicas = urls,
+                        None => parser.report_error(source.id, item.span, "Value of 'replicas' should be an array of strings."),
+                    }
+                }
+                "readYourWrites" => {
+                    let bool_value = Self::resolve_item_value(parser, source, item);
+                    match bool_value.as_bool() {
+                        Some(bool) => connector.read_your_writes = bool,
+                        None => parser.report_error(source.id, item.span, "Value of 'readYourWrites' should be bool."),
+                    }
+                }
+                _ => parser.report_error(source.id, item.identifier.span, format!("Undefined name '{}' in connector block.", item.identifier.name.as_str())),
             }
         }
-        connector.provider.unwrap()
+        if connector.provider.is_none() {
+            parser.report_error(source.id, connector.span, "Connector provider is not defined.");
+        }
+        if connector.url.is_none() {
+            parser.report_error(source.id, connector.span, "Connector url is not defined.");
+        }
+        match connector.provider {
+            Some(DatabaseName::MySQL) | Some(DatabaseName::PostgreSQL) | None => (),
+            #[cfg(feature = "data-source-mssql")]
+            Some(DatabaseName::MSSQL) => (),
+            _ => if !connector.replicas.is_empty() {
+                parser.report_error(source.id, connector.span, "Read replicas are only supported by MySQL, PostgreSQL and SQL Server connectors.");
+            },
+        }
+        connector.provider.clone()
+    }
+
+    /// Durations of the connector block are positive ints of milliseconds.
+    fn resolve_connector_duration(parser: &Parser, source: &Source, item: &mut Item) -> Option<Duration> {
+        let value = Self::resolve_item_value(parser, source, item);
+        match value.as_i64() {
+            Some(n) if n > 0 => Some(Duration::from_millis(n as u64)),
+            _ => {
+                parser.report_error(source.id, item.span, format!("Value of '{}' should be a positive int.", item.identifier.name));
+                None
+            }
+        }
+    }
+
+    fn custom_connector_name(provider_value: &Value) -> Option<String> {
+        match provider_value {
+            Value::RawEnumChoice(_, Some(args)) => args.first().and_then(|(_, v)| v.as_str()).map(|s| s.to_owned()),
+            _ => None,
+        }
     }
 
     pub(crate) fn resolve_client_generator(parser: &Parser, source: &Source, client: &mut Client) {
+        Self::warn_duplicated_items(parser, source, &client.items, "client generator");
         for item in client.items.iter_mut() {
             match item.identifier.name.as_str() {
                 "provider" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let provider_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let provider_str = provider_value.as_raw_enum_choice().unwrap();
-                    match provider_str {
-                        "javaScript" | "typeScript" => client.provider = Some(ClientLanguage::TypeScript),
-                        "swift" => client.provider = Some(ClientLanguage::Swift),
-                        "kotlin" => client.provider = Some(ClientLanguage::Kotlin),
-                        "cSharp" => client.provider = Some(ClientLanguage::CSharp),
-                        "dart" => client.provider = Some(ClientLanguage::Dart),
-                        _ => panic!("Unrecognized client generator provider. {}", provider_str)
+                    let provider_value = Self::resolve_item_value(parser, source, item);
+                    match provider_value.as_raw_enum_choice() {
+                        Some("javaScript" | "typeScript") => client.provider = Some(ClientLanguage::TypeScript),
+                        Some("swift") => client.provider = Some(ClientLanguage::Swift),
+                        Some("kotlin") => client.provider = Some(ClientLanguage::Kotlin),
+                        Some("cSharp") => client.provider = Some(ClientLanguage::CSharp),
+                        Some("dart") => client.provider = Some(ClientLanguage::Dart),
+                        Some(provider_str) => parser.report_error(source.id, item.span, format!("Unrecognized client generator provider '{}'.", provider_str)),
+                        None => parser.report_error(source.id, item.span, "Value of 'provider' should be an enum choice."),
                     }
                 },
                 "dest" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let dest_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let dest_str = dest_value.as_str().unwrap();
+                    let dest_value = Self::resolve_item_value(parser, source, item);
+                    let Some(dest_str) = dest_value.as_str() else {
+                        parser.report_error(source.id, item.span, "Value of 'dest' should be string.");
+                        continue
+                    };
                     let mut dest_path = source.path.clone();
                     dest_path.pop();
                     let dest = dest_path.join(PathBuf::from(dest_str));
@@ -488,100 +679,204 @@ impl Resolver {
                     client.dest = Some(absolute.as_ref().to_owned());
                 },
                 "package" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let package_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let package_bool = package_value.as_bool().unwrap();
-                    client.package = Some(package_bool);
+                    let package_value = Self::resolve_item_value(parser, source, item);
+                    match package_value.as_bool() {
+                        Some(package_bool) => client.package = Some(package_bool),
+                        None => parser.report_error(source.id, item.span, "Value of 'package' should be bool."),
+                    }
                 },
                 "host" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let host_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let host_str = host_value.as_str().unwrap();
-                    client.host = Some(host_str.to_owned());
+                    let host_value = Self::resolve_item_value(parser, source, item);
+                    match host_value.as_str() {
+                        Some(host_str) => client.host = Some(host_str.to_owned()),
+                        None => parser.report_error(source.id, item.span, "Value of 'host' should be string."),
+                    }
                 },
                 "objectName" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let object_name_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let object_name_str = object_name_value.as_str().unwrap();
-                    client.object_name = Some(object_name_str.to_owned());
+                    let object_name_value = Self::resolve_item_value(parser, source, item);
+                    match object_name_value.as_str() {
+                        Some(object_name_str) => client.object_name = Some(object_name_str.to_owned()),
+                        None => parser.report_error(source.id, item.span, "Value of 'objectName' should be string."),
+                    }
                 },
                 "gitCommit" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let git_commit_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let git_commit_bool = git_commit_value.as_bool().unwrap();
-                    client.git_commit = git_commit_bool;
+                    let git_commit_value = Self::resolve_item_value(parser, source, item);
+                    match git_commit_value.as_bool() {
+                        Some(git_commit_bool) => client.git_commit = git_commit_bool,
+                        None => parser.report_error(source.id, item.span, "Value of 'gitCommit' should be bool."),
+                    }
                 }
-                _ => { panic!("Undefined name '{}' in client generator block.", item.identifier.name.as_str())}
+                _ => parser.report_error(source.id, item.identifier.span, format!("Undefined name '{}' in client generator block.", item.identifier.name.as_str())),
             }
         }
     }
 
     pub(crate) fn resolve_model_entity_generator(parser: &Parser, source: &Source, generator: &mut Generator) {
+        Self::warn_duplicated_items(parser, source, &generator.items, "entity generator");
         for item in generator.items.iter_mut() {
             match item.identifier.name.as_str() {
                 "provider" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let provider_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    let provider_str = provider_value.as_raw_enum_choice().unwrap();
-                    match provider_str {
-                        "rust" => generator.provider = Some(Environment::Rust),
-                        "node" => generator.provider = Some(Environment::NodeJS),
-                        "python" => generator.provider = Some(Environment::Python),
-                        "go" => generator.provider = Some(Environment::Go),
-                        "java" => generator.provider = Some(Environment::Java),
-                        _ => panic!("Unrecognized entity generator provider. {}", provider_str)
+                    let provider_value = Self::resolve_item_value(parser, source, item);
+                    match provider_value.as_raw_enum_choice() {
+                        Some("rust") => generator.provider = Some(Environment::Rust),
+                        Some("node") => generator.provider = Some(Environment::NodeJS),
+                        Some("python") => generator.provider = Some(Environment::Python),
+                        Some("go") => generator.provider = Some(Environment::Go),
+                        Some("java") => generator.provider = Some(Environment::Java),
+                        Some(provider_str) => parser.report_error(source.id, item.span, format!("Unrecognized entity generator provider '{}'.", provider_str)),
+                        None => parser.report_error(source.id, item.span, "Value of 'provider' should be an enum choice."),
                     }
                 },
                 "dest" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let dest_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
+                    let dest_value = Self::resolve_item_value(parser, source, item);
+                    let Some(dest_str) = dest_value.as_str() else {
+                        parser.report_error(source.id, item.span, "Value of 'dest' should be string.");
+                        continue
+                    };
                     let mut dest = source.path.clone();
                     dest.pop();
-                    dest.push(PathBuf::from(dest_value.as_str().unwrap()));
+                    dest.push(PathBuf::from(dest_str));
                     let absolute = dest.absolutize().unwrap();
                     generator.dest = Some(absolute.as_ref().to_owned());
                 },
-                _ => { panic!("Undefined name '{}' in entity generator block.", item.identifier.name.as_str())}
+                _ => parser.report_error(source.id, item.identifier.span, format!("Undefined name '{}' in entity generator block.", item.identifier.name.as_str())),
             }
         }
     }
 
     pub(crate) fn resolve_config(parser: &Parser, source: &Source, config: &mut ServerConfig) {
+        Self::warn_duplicated_items(parser, source, &config.items, "config");
         for item in config.items.iter_mut() {
             match item.identifier.name.as_str() {
                 "bind" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let bind_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
-                    match bind_value.as_tuple() {
-                        Some(tuple_vec) => {
-                            let arg1 = tuple_vec.get(0).unwrap();
-                            let arg2 = tuple_vec.get(1).unwrap();
-                            let str = arg1.as_str().unwrap().to_owned();
-                            let int = arg2.as_i32().unwrap().to_owned();
-                            config.bind = Some((str, int as u16));
-                        }
-                        None => panic!("Argument to 'bind' should be a tuple.")
+                    let bind_value = Self::resolve_item_value(parser, source, item);
+                    let bind = bind_value.as_tuple().and_then(|tuple_vec| {
+                        let str = tuple_vec.first()?.as_str()?.to_owned();
+                        let int = tuple_vec.get(1)?.as_i32()?;
+                        Some((str, int as u16))
+                    });
+                    match bind {
+                        Some(bind) => config.bind = Some(bind),
+                        None => parser.report_error(source.id, item.span, "Argument to 'bind' should be a tuple of host and port."),
                     }
                 }
                 "jwtSecret" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let jwt_secret_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
+                    let jwt_secret_value = Self::resolve_item_value(parser, source, item);
                     match jwt_secret_value {
                         Value::Null => (),
                         Value::String(s) => config.jwt_secret = Some(s.clone()),
-                        _ => panic!("Value of 'jwtSecret' should be string.")
+                        _ => parser.report_error(source.id, item.span, "Value of 'jwtSecret' should be string."),
                     }
                 }
                 "pathPrefix" => {
-                    Self::resolve_expression(parser, source, &mut item.expression);
-                    let path_prefix_value = Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap());
+                    let path_prefix_value = Self::resolve_item_value(parser, source, item);
                     match path_prefix_value {
                         Value::Null => (),
                         Value::String(s) => config.path_prefix = Some(s.clone()),
-                        _ => panic!("Value of 'pathPrefix' should be string.")
+                        _ => parser.report_error(source.id, item.span, "Value of 'pathPrefix' should be string."),
+                    }
+                }
+                "graphql" => {
+                    let graphql_value = Self::resolve_item_value(parser, source, item);
+                    match graphql_value {
+                        Value::Null => (),
+                        Value::Bool(b) => config.graphql = b,
+                        _ => parser.report_error(source.id, item.span, "Value of 'graphql' should be bool."),
+                    }
+                }
+                "corsOrigins" | "corsHeaders" => {
+                    let value = Self::resolve_item_value(parser, source, item);
+                    let strings = value.as_vec().and_then(|vec| {
+                        vec.iter().map(|v| v.as_str().map(|s| s.to_owned())).collect::<Option<Vec<String>>>()
+                    });
+                    match strings {
+                        Some(strings) => match item.identifier.name.as_str() {
+                            "corsOrigins" => config.cors_origins = Some(strings),
+                            _ => config.cors_headers = Some(strings),
+                        },
+                        None => parser.report_error(source.id, item.span, format!("Value of '{}' should be array of strings.", item.identifier.name.as_str())),
+                    }
+                }
+                "corsCredentials" => {
+                    let value = Self::resolve_item_value(parser, source, item);
+                    match value {
+                        Value::Null => (),
+                        Value::Bool(b) => config.cors_credentials = b,
+                        _ => parser.report_error(source.id, item.span, "Value of 'corsCredentials' should be bool."),
+                    }
+                }
+                "corsMaxAge" => {
+                    let value = Self::resolve_item_value(parser, source, item);
+                    match value.as_i64() {
+                        Some(n) if n >= 0 => config.cors_max_age = Some(n as u32),
+                        _ => parser.report_error(source.id, item.span, "Value of 'corsMaxAge' should be a non-negative int."),
+                    }
+                }
+                "bodyLimit" => {
+                    let value = Self::resolve_item_value(parser, source, item);
+                    match value.as_i64() {
+                        Some(n) if n > 0 => config.body_limit = Some(n as usize),
+                        _ => parser.report_error(source.id, item.span, "Value of 'bodyLimit' should be a positive int."),
+                    }
+                }
+                "compression" => {
+                    let value = Self::resolve_item_value(parser, source, item);
+                    match value {
+                        Value::Null => (),
+                        Value::Bool(b) => config.compression = b,
+                        _ => parser.report_error(source.id, item.span, "Value of 'compression' should be bool."),
+                    }
+                }
+                "healthDetails" => {
+                    let value = Self::resolve_item_value(parser, source, item);
+                    match value {
+                        Value::Null => (),
+                        Value::Bool(b) => config.health_details = b,
+                        _ => parser.report_error(source.id, item.span, "Value of 'healthDetails' should be bool."),
+                    }
+                }
+                "requestTimeout" => {
+                    let value = Self::resolve_item_value(parser, source, item);
+                    match value.as_i64() {
+                        Some(n) if n > 0 => config.request_timeout = Some(n as u64),
+                        _ => parser.report_error(source.id, item.span, "Value of 'requestTimeout' should be a positive int."),
+                    }
+                }
+                "tokenExpiry" => {
+                    let value = Self::resolve_item_value(parser, source, item);
+                    match value.as_i64() {
+                        Some(n) if n > 0 => config.token_expiry = Some(n),
+                        _ => parser.report_error(source.id, item.span, "Value of 'tokenExpiry' should be a positive int."),
+                    }
+                }
+                "refreshTokenExpiry" => {
+                    let value = Self::resolve_item_value(parser, source, item);
+                    match value.as_i64() {
+                        Some(n) if n > 0 => config.refresh_token_expiry = Some(n),
+                        _ => parser.report_error(source.id, item.span, "Value of 'refreshTokenExpiry' should be a positive int."),
                     }
                 }
-                _ => { panic!("Undefined name '{}' in config block.", item.identifier.name.as_str())}
+                _ => parser.report_error(source.id, item.identifier.span, format!("Undefined name '{}' in config block.", item.identifier.name.as_str())),
+            }
+        }
+        // browsers don't send credentials to a wildcard origin, the origins have to be listed
+        if config.cors_credentials && config.cors_origins.as_ref().is_none_or(|origins| origins.iter().any(|o| o == "*")) {
+            if let Some(item) = config.items.iter().find(|item| item.identifier.name.as_str() == "corsCredentials") {
+                parser.report_error(source.id, item.span, "'corsCredentials' requires 'corsOrigins' to list the allowed origins without '*'.");
+            }
+        }
+    }
+
+    fn resolve_item_value(parser: &Parser, source: &Source, item: &mut Item) -> Value {
+        Self::resolve_expression(parser, source, &mut item.expression);
+        Self::unwrap_into_value_if_needed(parser, source, item.expression.resolved.as_ref().unwrap())
+    }
+
+    fn warn_duplicated_items(parser: &Parser, source: &Source, items: &Vec<Item>, block: &str) {
+        let mut names: HashSet<&str> = HashSet::new();
+        for item in items {
+            if !names.insert(item.identifier.name.as_str()) {
+                parser.report_warning(source.id, item.identifier.span, format!("'{}' is defined more than once in {} block, the last one is used.", item.identifier.name, block));
             }
         }
     }
@@ -610,13 +905,13 @@ impl Resolver {
                 Self::resolve_arith_expr(parser, source, arith, when_option)
             }
             ExpressionKind::NumericLiteral(n) => {
-                Self::resolve_numeric_literal(n)
+                Self::resolve_numeric_literal(parser, source, n)
             }
             ExpressionKind::StringLiteral(s) => {
-                Self::resolve_string_literal(s)
+                Self::resolve_string_literal(parser, source, s)
             }
             ExpressionKind::RegExpLiteral(r) => {
-                Self::resolve_regexp_literal(r)
+                Self::resolve_regexp_literal(parser, source, r)
             }
             ExpressionKind::BoolLiteral(b) => {
                 Self::resolve_bool_literal(b)
@@ -643,10 +938,10 @@ impl Resolver {
                 Self::resolve_identifier(parser, source, identifier, None)
             }
             ExpressionKind::ArgumentList(_a) => {
-                panic!("Argument list cannot appear alone.")
+                unreachable!("Argument list cannot appear alone.")
             }
             ExpressionKind::Subscript(_s) => {
-                panic!("Subscript cannot appear alone.")
+                unreachable!("Subscript cannot appear alone.")
             }
             ExpressionKind::Unit(unit) => {
                 Self::resolve_unit(parser, source, unit)
@@ -671,26 +966,25 @@ impl Resolver {
     fn resolve_identifier(parser: &Parser, source: &Source, identifier: &Identifier, parent: Option<&Entity>) -> Entity {
         match parent {
             Some(parent) => {
-                if parent.is_accessible() {
-                    let parent = parent.as_accessible().unwrap();
-                    if parent.is_container() {
-                        let container = parent.as_container().unwrap();
-                        let result = container.objects.get(&identifier.name);
-                        match result {
-                            Some(entity) => entity.clone(),
-                            None => panic!("Cannot access {}", identifier.name),
-                        }
-                    } else {
-                        panic!("Cannot access {}", identifier.name);
+                let result = parent.as_accessible().and_then(|a| a.as_container()).and_then(|c| c.objects.get(&identifier.name));
+                match result {
+                    Some(entity) => entity.clone(),
+                    None => {
+                        parser.report_error(source.id, identifier.span, format!("Cannot access '{}'.", identifier.name));
+                        Entity::Value(Value::Null)
                     }
-                } else {
-                    panic!("Cannot access {}", identifier.name);
                 }
             }
             None => {
                 match Self::find_identifier_origin_in_source(parser, source, identifier) {
                     Some(reference) => Entity::Reference(reference),
-                    None => Container::std_global_constants().access_property(&identifier.name).clone()
+                    None => match Container::std_global_constants().objects.get(&identifier.name) {
+                        Some(entity) => entity.clone(),
+                        None => {
+                            parser.report_error(source.id, identifier.span, format!("Cannot find '{}' in this scope.", identifier.name));
+                            Entity::Value(Value::Null)
+                        }
+                    }
                 }
             }
         }
@@ -725,67 +1019,72 @@ impl Resolver {
             ExpressionKind::Identifier(identifier) => {
                 Self::resolve_identifier(parser, source, identifier, Some(entity))
             }
-            _ => panic!()
+            _ => unreachable!()
         }
     }
 
     fn resolve_subscript(parser: &Parser, source: &Source, subscript: &Subscript, entity: &Entity) -> Entity {
         let index_entity = Self::resolve_expression_kind(parser, source, &subscript.expression, false);
         let index_value = Self::unwrap_into_value_if_needed(parser, source, &index_entity);
-        if entity.is_accessible() {
+        let result = if entity.is_accessible() {
             let accessible = entity.as_accessible().unwrap();
             match accessible {
                 Accessible::Env(env) => {
                     match index_value.as_str() {
-                        Some(s) => Entity::Value(env.get_value(s)),
-                        None => panic!("ENV can only be subscripted with string.")
+                        Some(s) => Ok(env.get_value(s)),
+                        None => Err("ENV can only be subscripted with string."),
                     }
                 }
-                _ => panic!("Cannot access subscript"),
+                _ => Err("Cannot access subscript."),
             }
         } else {
             let entity_value = Self::unwrap_into_value_if_needed(parser, source, entity);
             match entity_value {
                 Value::String(s) => {
                     match index_value.as_i64() {
-                        Some(i) => Entity::Value(Value::String(s.chars().nth(i as usize).unwrap().to_string())),
-                        None => panic!("String can only be subscripted with integer.")
+                        Some(i) => s.chars().nth(i as usize).map(|c| Value::String(c.to_string())).ok_or("Index out of range."),
+                        None => Err("String can only be subscripted with integer."),
                     }
                 }
                 Value::Vec(v) => {
                     match index_value.as_i64() {
-                        Some(i) => Entity::Value(v.get(i as usize).unwrap().clone()),
-                        None => panic!("Array can only be subscripted with integer.")
+                        Some(i) => v.get(i as usize).cloned().ok_or("Index out of range."),
+                        None => Err("Array can only be subscripted with integer."),
                     }
                 }
                 Value::HashMap(m) => {
                     match index_value.as_str() {
-                        Some(s) => Entity::Value(m.get(s).unwrap().clone()),
-                        None => panic!("Map can only be subscripted with string.")
+                        Some(s) => m.get(s).cloned().ok_or("Key not found."),
+                        None => Err("Map can only be subscripted with string."),
                     }
                 }
                 Value::BTreeMap(m) => {
                     match index_value.as_str() {
-                        Some(s) => Entity::Value(m.get(s).unwrap().clone()),
-                        None => panic!("Map can only be subscripted with string.")
+                        Some(s) => m.get(s).cloned().ok_or("Key not found."),
+                        None => Err("Map can only be subscripted with string."),
                     }
                 }
                 Value::IndexMap(m) => {
                     match index_value.as_str() {
-                        Some(s) => Entity::Value(m.get(s).unwrap().clone()),
-                        None => panic!("Map can only be subscripted with string.")
+                        Some(s) => m.get(s).cloned().ok_or("Key not found."),
+                        None => Err("Map can only be subscripted with string."),
                     }
                 }
-                _ => {
-                    panic!("")
-                }
+                _ => Err("Cannot access subscript."),
+            }
+        };
+        match result {
+            Ok(value) => Entity::Value(value),
+            Err(message) => {
+                parser.report_error(source.id, subscript.span, message);
+                Entity::Value(Value::Null)
             }
         }
     }
 
     // literals and operators
 
-    fn resolve_numeric_literal(n: &NumericLiteral) -> Entity {
+    fn resolve_numeric_literal(parser: &Parser, source: &Source, n: &NumericLiteral) -> Entity {
         let i = i32::from_str(&n.value);
         if i.is_ok() {
             return Entity::Value(Value::I32(i.unwrap()));
@@ -798,15 +1097,28 @@ impl Resolver {
         if i.is_ok() {
             return Entity::Value(Value::F64(i.unwrap()));
         }
-        panic!("Cannot resolve numeric value: {}.", n.value.as_str())
+        parser.report_error(source.id, n.span, format!("Cannot resolve numeric value: {}.", n.value.as_str()));
+        Entity::Value(Value::Null)
     }
 
-    fn resolve_string_literal(s: &StringLiteral) -> Entity {
-        return Entity::Value(Value::String(unescape(s.value.as_str()).unwrap()));
+    fn resolve_string_literal(parser: &Parser, source: &Source, s: &StringLiteral) -> Entity {
+        match unescape(s.value.as_str()) {
+            Ok(string) => Entity::Value(Value::String(string)),
+            Err(err) => {
+                parser.report_error(source.id, s.span, format!("Invalid string literal: {}.", err));
+                Entity::Value(Value::Null)
+            }
+        }
     }
 
-    fn resolve_regexp_literal(r: &RegExpLiteral) -> Entity {
-        return Entity::Value(Value::RegExp(Regex::new(r.value.as_str()).unwrap()));
+    fn resolve_regexp_literal(parser: &Parser, source: &Source, r: &RegExpLiteral) -> Entity {
+        match Regex::new(r.value.as_str()) {
+            Ok(regex) => Entity::Value(Value::RegExp(regex)),
+            Err(err) => {
+                parser.report_error(source.id, r.span, format!("Invalid regular expression: {}.", err));
+                Entity::Value(Value::Null)
+            }
+        }
     }
 
     fn resolve_bool_literal(b: &BoolLiteral) -> Entity {
@@ -877,7 +1189,10 @@ impl Resolver {
             let k = Self::unwrap_into_value_if_needed(parser, source, &k);
             let v = Self::resolve_expression_kind(parser, source, value, false);
             let v = Self::unwrap_into_value_if_needed(parser, source, &v);
-            resolved.insert(k.as_str().unwrap().to_string(), v);
+            match k.as_str() {
+                Some(k) => { resolved.insert(k.to_string(), v); }
+                None => parser.report_error(source.id, dic.span, format!("Dictionary key should be string, found {:?}.", k)),
+            }
         }
         Entity::Value(Value::HashMap(resolved))
     }
@@ -900,7 +1215,10 @@ impl Resolver {
             Value::I64(v) => Value::I64(-v),
             Value::F32(v) => Value::F32(-v),
             Value::F64(v) => Value::F64(-v),
-            _ => panic!("Cannot negate value {:?}", value)
+            _ => {
+                parser.report_error(source.id, negation.span, format!("Cannot negate value {:?}.", value));
+                Value::Null
+            }
         })
     }
 
@@ -909,102 +1227,72 @@ impl Resolver {
         Entity::Value(match value {
             Value::I32(v) => Value::I32(!v),
             Value::I64(v) => Value::I64(!v),
-            Value::RawEnumChoice(e, _) => if when_option {
-                Value::RawOptionChoice(Action::from_name(&e).neg().to_u32())
-            } else {
-                panic!("Unhandled option bitwise operation")
-            }
-            Value::RawOptionChoice(o) => if when_option {
-                Value::RawOptionChoice(Action::from_u32(o).neg().to_u32())
-            } else {
-                panic!("Unhandled option bitwise operation")
+            Value::RawEnumChoice(e, _) if when_option => match Action::try_from_name(&e) {
+                Some(action) => Value::RawOptionChoice(action.neg().to_u32()),
+                None => {
+                    parser.report_error(source.id, negation.span, format!("Unrecognized action option name '{}'.", e));
+                    Value::Null
+                }
             },
-            _ => panic!("Cannot negate value {:?}", value)
+            Value::RawOptionChoice(o) if when_option => Value::RawOptionChoice(Action::from_u32(o).neg().to_u32()),
+            Value::RawEnumChoice(_, _) | Value::RawOptionChoice(_) => {
+                parser.report_error(source.id, negation.span, "Bitwise operation on options is only allowed in option arguments.");
+                Value::Null
+            }
+            _ => {
+                parser.report_error(source.id, negation.span, format!("Cannot negate value {:?}.", value));
+                Value::Null
+            }
         })
     }
 
     fn resolve_arith_expr(parser: &Parser, source: &Source, arith_expr: &ArithExpr, when_option: bool) -> Entity {
         match arith_expr {
-            ArithExpr::Expression(expression) => return Self::resolve_expression_kind(parser, source, &expression, when_option),
-            ArithExpr::UnaryNeg(expression) => {
-                let origin = Self::resolve_expression_kind_force_value(parser, source, &expression, when_option);
-                return Entity::Value((-origin).unwrap());
-            }
-            ArithExpr::UnaryBitNeg(expression) => {
-                let origin = Self::resolve_expression_kind_force_value(parser, source, &expression, when_option);
-                return Entity::Value(match origin {
-                    Value::I32(v) => Value::I32(!v),
-                    Value::I64(v) => Value::I64(!v),
-                    Value::RawEnumChoice(e, _) => if when_option {
-                        Value::RawOptionChoice(Action::from_name(&e).neg().to_u32())
-                    } else {
-                        panic!("Unhandled option bitwise operation")
-                    }
-                    Value::RawOptionChoice(o) => if when_option {
-                        Value::RawOptionChoice(Action::from_u32(o).neg().to_u32())
-                    } else {
-                        panic!("Unhandled option bitwise operation")
-                    },
-                    _ => panic!("Cannot negate value {:?}", origin)
-                });
-            }
-            ArithExpr::BinaryOp { lhs, op, rhs } => {
-                let lhs_value = Self::resolve_arith_expr(parser, source, &lhs, when_option).as_value().unwrap().clone();
-                let rhs_value = Self::resolve_arith_expr(parser, source, &rhs, when_option).as_value().unwrap().clone();
-                match op {
-                    Op::Add => {
-                        Entity::Value((lhs_value.clone() + rhs_value.clone()).unwrap())
-                    }
-                    Op::Sub => {
-                        Entity::Value((lhs_value.clone() - rhs_value.clone()).unwrap())
-                    }
-                    Op::Mul => {
-                        Entity::Value((lhs_value.clone() * rhs_value.clone()).unwrap())
-                    }
-                    Op::Div => {
-                        Entity::Value((lhs_value.clone() / rhs_value.clone()).unwrap())
-                    }
-                    Op::Mod => {
-                        Entity::Value((lhs_value.clone() % rhs_value.clone()).unwrap())
-                    }
-                    Op::BitAnd => {
-                        if when_option {
-                            let lhs_action = Self::value_to_action_option(&lhs_value);
-                            let rhs_action = Self::value_to_action_option(&rhs_value);
-                            Entity::Value(Value::RawOptionChoice(lhs_action.and(rhs_action).to_u32()))
-                        } else {
-                            Entity::Value((lhs_value.clone() & rhs_value.clone()).unwrap())
-                        }
-                    }
-                    Op::BitXor => {
-                        if when_option {
-                            let lhs_action = Self::value_to_action_option(&lhs_value);
-                            let rhs_action = Self::value_to_action_option(&rhs_value);
-                            Entity::Value(Value::RawOptionChoice(lhs_action.xor(rhs_action).to_u32()))
-                        } else {
-                            Entity::Value((lhs_value.clone() ^ rhs_value.clone()).unwrap())
-                        }
-                    }
-                    Op::BitOr => {
-                        if when_option {
-                            let lhs_action = Self::value_to_action_option(&lhs_value);
-                            let rhs_action = Self::value_to_action_option(&rhs_value);
-                            Entity::Value(Value::RawOptionChoice(lhs_action.or(rhs_action).to_u32()))
-                        } else {
-                            Entity::Value((lhs_value.clone() | rhs_value.clone()).unwrap())
-                        }
-                    }
+            ArithExpr::Expression(expression) => Self::resolve_expression_kind(parser, source, expression, when_option),
+            ArithExpr::BinaryOp { lhs, op, rhs, span } => {
+                let lhs_entity = Self::resolve_arith_expr(parser, source, lhs, when_option);
+                let lhs_value = Self::unwrap_into_value_if_needed(parser, source, &lhs_entity);
+                let rhs_entity = Self::resolve_arith_expr(parser, source, rhs, when_option);
+                let rhs_value = Self::unwrap_into_value_if_needed(parser, source, &rhs_entity);
+                if when_option && matches!(op, Op::BitAnd | Op::BitXor | Op::BitOr) {
+                    let (Some(lhs_action), Some(rhs_action)) = (Self::value_to_action_option(&lhs_value), Self::value_to_action_option(&rhs_value)) else {
+                        parser.report_error(source.id, *span, format!("Cannot apply '{}' to {:?} and {:?}.", op, lhs_value, rhs_value));
+                        return Entity::Value(Value::Null);
+                    };
+                    let action = match op {
+                        Op::BitAnd => lhs_action.and(rhs_action),
+                        Op::BitXor => lhs_action.xor(rhs_action),
+                        _ => lhs_action.or(rhs_action),
+                    };
+                    return Entity::Value(Value::RawOptionChoice(action.to_u32()));
+                }
+                let result = match op {
+                    Op::Add => lhs_value.clone() + rhs_value.clone(),
+                    Op::Sub => lhs_value.clone() - rhs_value.clone(),
+                    Op::Mul => lhs_value.clone() * rhs_value.clone(),
+                    Op::Div => lhs_value.clone() / rhs_value.clone(),
+                    Op::Mod => lhs_value.clone() % rhs_value.clone(),
+                    Op::BitAnd => lhs_value.clone() & rhs_value.clone(),
+                    Op::BitXor => lhs_value.clone() ^ rhs_value.clone(),
+                    Op::BitOr => lhs_value.clone() | rhs_value.clone(),
                     _ => unreachable!()
+                };
+                match result {
+                    Ok(value) => Entity::Value(value),
+                    Err(_) => {
+                        parser.report_error(source.id, *span, format!("Cannot apply '{}' to {:?} and {:?}.", op, lhs_value, rhs_value));
+                        Entity::Value(Value::Null)
+                    }
                 }
             }
         }
     }
 
-    fn value_to_action_option(v: &Value) -> Action {
+    fn value_to_action_option(v: &Value) -> Option<Action> {
         match v {
-            Value::RawEnumChoice(e, _) => Action::from_name(&e),
-            Value::RawOptionChoice(u) => Action::from_u32(*u),
-            _ => unreachable!()
+            Value::RawEnumChoice(e, _) => Action::try_from_name(e),
+            Value::RawOptionChoice(u) => Some(Action::from_u32(*u)),
+            _ => None
         }
     }
 
@@ -1056,7 +1344,246 @@ impl Resolver {
                 Value::RawEnumChoice(r.as_model_ref().unwrap().2.clone(), None)
             }
         } else {
-            panic!("Cannot unwrap accessible into value.")
+            parser.report_error_without_source("Cannot use a pipeline item or function as a value.");
+            Value::Null
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::check_schema_source;
+
+    fn mssql_schema(models: &str) -> String {
+        format!(r#"
+connector {{
+  provider .mssql
+  url "sqlserver://localhost:1433;database=test"
+}}
+
+server {{
+  bind ("0.0.0.0", 5100)
+}}
+{models}"#)
+    }
+
+    fn sqlite_schema(models: &str) -> String {
+        format!(r#"
+connector {{
+  provider .sqlite
+  url "sqlite::memory:"
+}}
+
+server {{
+  bind ("0.0.0.0", 5100)
+}}
+{models}"#)
+    }
+
+    #[test]
+    fn mssql_rejects_array_fields() {
+        let diagnostics = check_schema_source(&mssql_schema(r#"
+model User {
+  @id
+  id: Int
+  tags: String[]
+}
+"#));
+        let errors: Vec<&str> = diagnostics.errors().map(|e| e.message()).collect();
+        assert_eq!(errors, vec!["SQL Server doesn't support array or dictionary fields."]);
+    }
+
+    #[test]
+    fn unknown_relation_rule_is_reported() {
+        let diagnostics = check_schema_source(&sqlite_schema(r#"
+model User {
+  @id
+  id: Int
+  @relation(fields: .id, references: .userId)
+  posts: Post[]
+}
+
+model Post {
+  @id
+  id: Int
+  userId: Int
+  @relation(fields: .userId, references: .id, onDelete: .remove)
+  user: User
+}
+"#));
+        let errors: Vec<&str> = diagnostics.errors().map(|e| e.message()).collect();
+        assert_eq!(errors, vec!["Unknown delete rule 'remove'."]);
+    }
+
+    #[test]
+    fn relation_fields_without_references_is_reported() {
+        let diagnostics = check_schema_source(&sqlite_schema(r#"
+model User {
+  @id
+  id: Int
+  @relation(fields: .id)
+  posts: Post[]
+}
+
+model Post {
+  @id
+  id: Int
+  userId: Int
+}
+"#));
+        let errors: Vec<&str> = diagnostics.errors().map(|e| e.message()).collect();
+        assert_eq!(errors, vec!["A relation with 'fields' must have 'references'."]);
+    }
+
+    #[test]
+    fn invalid_arithmetic_is_reported() {
+        let diagnostics = check_schema_source(&sqlite_schema(r#"
+model User {
+  @id
+  id: Int
+  @default(1 + "a")
+  age: Int
+  @default(-"a")
+  score: Int
+}
+"#));
+        let errors: Vec<&str> = diagnostics.errors().map(|e| e.message()).collect();
+        assert_eq!(errors, vec![
+            "Cannot apply '+' to I32(1) and String(\"a\").",
+            "Cannot negate value String(\"a\").",
+        ]);
+    }
+
+    #[test]
+    fn invalid_soft_delete_field_is_reported() {
+        let diagnostics = check_schema_source(&sqlite_schema(r#"
+model User {
+  @id
+  id: Int
+  deletedAt: DateTime
+  @@softDelete(.deletedAt)
+}
+
+model Post {
+  @id
+  id: Int
+  @@softDelete(.removedAt)
+}
+
+model Tag {
+  @id
+  id: Int
+  @@softDelete
+}
+
+model Comment {
+  @id
+  id: Int
+  deletedAt: DateTime?
+  @@softDelete(.deletedAt)
+}
+"#));
+        let errors: Vec<&str> = diagnostics.errors().map(|e| e.message()).collect();
+        assert_eq!(errors, vec![
+            "Soft delete field 'deletedAt' should be optional DateTime.",
+            "Soft delete field 'removedAt' is not found on model 'Post'.",
+            "@@softDelete requires a field name, e.g. '.deletedAt'.",
+        ]);
+    }
+
+    #[test]
+    fn invalid_version_fields_are_reported() {
+        let diagnostics = check_schema_source(&sqlite_schema(r#"
+model User {
+  @id
+  id: Int
+  @version
+  version: Int?
+}
+
+model Post {
+  @id
+  id: Int
+  @version
+  version: Int
+  @version
+  revision: Int64
+}
+
+model Tag {
+  @id
+  id: Int
+  @version
+  version: Int64
+}
+"#));
+        let errors: Vec<&str> = diagnostics.errors().map(|e| e.message()).collect();
+        assert_eq!(errors, vec![
+            "Version field 'version' of model 'User' should be required Int or Int64.",
+            "Model 'Post' has more than one version field.",
+        ]);
+    }
+
+    #[test]
+    fn mssql_rejects_json_fields() {
+        let diagnostics = check_schema_source(&mssql_schema(r#"
+model Post {
+  @id
+  id: Int
+  meta: Json?
+}
+"#));
+        let errors: Vec<&str> = diagnostics.errors().map(|e| e.message()).collect();
+        assert_eq!(errors, vec!["SQL Server doesn't support Json fields."]);
+    }
+
+    #[test]
+    fn mssql_rejects_full_text_indices() {
+        let diagnostics = check_schema_source(&mssql_schema(r#"
+model Post {
+  @id
+  id: Int
+  title: String
+  @@fullTextIndex([.title])
+}
+"#));
+        let errors: Vec<&str> = diagnostics.errors().map(|e| e.message()).collect();
+        assert_eq!(errors, vec!["SQL Server doesn't support full-text indices."]);
+    }
+
+    #[test]
+    fn mssql_accepts_scalar_fields() {
+        let diagnostics = check_schema_source(&mssql_schema(r#"
+model User {
+  @id
+  id: Int
+  name: String
+}
+"#));
+        assert!(!diagnostics.has_errors(), "{}", diagnostics.render(false));
+    }
+
+    #[test]
+    fn cors_credentials_require_listed_origins() {
+        let schema = |origins: &str| format!(r#"
+connector {{
+  provider .sqlite
+  url "sqlite::memory:"
+}}
+
+server {{
+  bind ("0.0.0.0", 5100)
+  corsCredentials true
+  {origins}
+}}
+"#);
+        for origins in ["", r#"corsOrigins ["*"]"#, r#"corsOrigins ["https://a.com", "*"]"#] {
+            let diagnostics = check_schema_source(&schema(origins));
+            let errors: Vec<&str> = diagnostics.errors().map(|e| e.message()).collect();
+            assert_eq!(errors, vec!["'corsCredentials' requires 'corsOrigins' to list the allowed origins without '*'."]);
         }
+        let diagnostics = check_schema_source(&schema(r#"corsOrigins ["https://a.com"]"#));
+        assert!(!diagnostics.has_errors(), "{}", diagnostics.render(false));
     }
 }
+
@@ -17,5 +17,12 @@ pub(crate) fn db_container(database_name: DatabaseName) -> Container {
         DatabaseName::MongoDB => {
             Container { objects: hashmap!{} }
         }
+        #[cfg(feature = "data-source-mssql")]
+        DatabaseName::MSSQL => {
+            Container { objects: hashmap!{} }
+        }
+        DatabaseName::Custom(_) => {
+            Container { objects: hashmap!{} }
+        }
     }
 }
@@ -34,6 +34,7 @@ pub(crate) mod can_read;
 pub(crate) mod can_mutate;
 pub(crate) mod migration_decorator;
 pub(crate) mod dropped;
+pub(crate) mod version;
 
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
@@ -75,6 +76,7 @@ use crate::parser::std::decorators::field::writeonly::{writeonly_decorator};
 use crate::parser::std::decorators::field::r#virtual::virtual_decorator;
 use crate::parser::std::decorators::field::record_previous::record_previous_decorator;
 use crate::parser::std::decorators::field::unqueryable::unqueryable_decorator;
+use crate::parser::std::decorators::field::version::version_decorator;
 
 
 pub(crate) struct GlobalFieldDecorators {
@@ -129,13 +131,11 @@ impl GlobalFieldDecorators {
         objects.insert("canRead".to_owned(), Accessible::FieldDecorator(can_read_decorator));
         objects.insert("migration".to_owned(), Accessible::FieldDecorator(migration_decorator));
         objects.insert("dropped".to_owned(), Accessible::FieldDecorator(dropped_decorator));
+        objects.insert("version".to_owned(), Accessible::FieldDecorator(version_decorator));
         Self { objects }
     }
 
-    pub(crate) fn get(&self, key: &str) -> &Accessible {
-        match self.objects.get(key) {
-            Some(o) => o,
-            None => panic!("Object with key '{}' is not found.", key),
-        }
+    pub(crate) fn get(&self, key: &str) -> Option<&Accessible> {
+        self.objects.get(key)
     }
 }
@@ -0,0 +1,12 @@
+use crate::core::model::builder::ModelBuilder;
+
+use crate::parser::ast::argument::Argument;
+use crate::prelude::Value;
+
+pub(crate) fn connector_decorator(args: Vec<Argument>, model: &mut ModelBuilder) {
+    match args.first().unwrap().resolved.as_ref().unwrap().as_value().unwrap() {
+        Value::RawEnumChoice(name, _) => model.connector(name),
+        Value::String(name) => model.connector(name),
+        _ => panic!("@@connector of model '{}' requires a connector name.", model.name),
+    };
+}
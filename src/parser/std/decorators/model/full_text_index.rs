@@ -0,0 +1,22 @@
+use crate::core::model::builder::ModelBuilder;
+use crate::parser::ast::argument::Argument;
+use crate::prelude::Value;
+
+pub(crate) fn full_text_index_decorator(args: Vec<Argument>, model: &mut ModelBuilder) {
+    let arg0 = match args.first() {
+        Some(arg) => arg,
+        None => panic!("Model full-text index decorator takes a fields argument."),
+    };
+    if arg0.name.is_some() && (arg0.name.as_ref().unwrap().name.as_str() != "fields") {
+        panic!("Model full-text index decorator's argument should be fields or no name.")
+    }
+    let fields: Vec<String> = match arg0.resolved.as_ref().unwrap().as_value().unwrap() {
+        Value::Vec(vec) => vec.iter().map(|value| match value {
+            Value::RawEnumChoice(name, _) => name.clone(),
+            _ => panic!("Model full-text index decorator's fields should be field names."),
+        }).collect(),
+        Value::RawEnumChoice(name, _) => vec![name.clone()],
+        _ => panic!("Model full-text index decorator's fields should be field names."),
+    };
+    model.full_text_index(fields);
+}
@@ -0,0 +1,54 @@
+use crate::core::handler::{Handler, HandlerType};
+use crate::core::model::builder::ModelBuilder;
+use crate::parser::ast::argument::Argument;
+use crate::prelude::Value;
+
+static VALID_NAMES: [&str; 3] = ["name", "input", "output"];
+
+fn handler_type(value: &Value, model: &ModelBuilder) -> HandlerType {
+    let string = match value.as_str() {
+        Some(string) => string,
+        None => panic!("Types of handlers of model '{}' should be strings, e.g. \"Int\" or \"Post[]\".", model.name),
+    };
+    match HandlerType::parse(string) {
+        Some(r#type) => r#type,
+        None => panic!("Invalid handler type '{}' of model '{}'.", string, model.name),
+    }
+}
+
+pub(crate) fn handler_decorator(args: Vec<Argument>, model: &mut ModelBuilder) {
+    let mut name: Option<String> = None;
+    let mut input: Vec<(String, HandlerType)> = vec![];
+    let mut output: Option<HandlerType> = None;
+    for (index, arg) in args.iter().enumerate() {
+        let arg_name = match arg.name.as_ref() {
+            Some(identifier) => identifier.name.as_str(),
+            None if index == 0 => "name",
+            None => panic!("@handler requires argument names except for the first one."),
+        };
+        if !VALID_NAMES.contains(&arg_name) {
+            panic!("Unknown argument name: {}", arg_name);
+        }
+        let value = arg.resolved.as_ref().unwrap().as_value().unwrap();
+        match arg_name {
+            "name" => name = match value {
+                Value::RawEnumChoice(name, _) => Some(name.clone()),
+                Value::String(name) => Some(name.clone()),
+                _ => panic!("Handler name of model '{}' should be an enum choice or a string.", model.name),
+            },
+            "input" => match value.as_hashmap() {
+                Some(map) => {
+                    input = map.iter().map(|(k, v)| (k.clone(), handler_type(v, model))).collect();
+                    input.sort_by(|a, b| a.0.cmp(&b.0));
+                }
+                None => panic!("Handler input of model '{}' should be a dictionary of key types.", model.name),
+            },
+            "output" => output = Some(handler_type(value, model)),
+            _ => unreachable!()
+        }
+    }
+    match name {
+        Some(name) => model.handler(Handler::new(name, input, output)),
+        None => panic!("@handler requires a name."),
+    };
+}
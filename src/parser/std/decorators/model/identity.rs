@@ -2,6 +2,19 @@ use crate::core::model::builder::ModelBuilder;
 
 use crate::parser::ast::argument::Argument;
 
-pub(crate) fn identity_decorator(_args: Vec<Argument>, model: &mut ModelBuilder) {
+pub(crate) fn identity_decorator(args: Vec<Argument>, model: &mut ModelBuilder) {
     model.identity = true;
+    for arg in args {
+        let name = match &arg.name {
+            Some(name) => name.name.as_str(),
+            None => panic!("Model identity decorator's arguments should be named."),
+        };
+        let value = arg.resolved.as_ref().unwrap().as_value().unwrap();
+        match name {
+            "tokenExpiry" => model.identity_token_expiry = Some(value.as_i64().expect("Model identity decorator's tokenExpiry should be int.")),
+            "refreshTokenExpiry" => model.identity_refresh_token_expiry = Some(value.as_i64().expect("Model identity decorator's refreshTokenExpiry should be int.")),
+            "claims" => model.identity_claims = Some(value.as_pipeline().expect("Model identity decorator's claims should be pipeline.").clone()),
+            _ => panic!("Unknown argument '{}' of model identity decorator.", name),
+        }
+    }
 }
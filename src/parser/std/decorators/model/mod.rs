@@ -12,6 +12,10 @@ pub(crate) mod can_mutate;
 pub(crate) mod disable;
 pub(crate) mod action;
 pub(crate) mod migration;
+pub(crate) mod soft_delete;
+pub(crate) mod full_text_index;
+pub(crate) mod handler;
+pub(crate) mod connector;
 
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
@@ -23,12 +27,16 @@ use crate::parser::std::decorators::model::before_delete::before_delete_decorato
 use crate::parser::std::decorators::model::before_save::before_save_decorator;
 use crate::parser::std::decorators::model::can_mutate::can_mutate_decorator;
 use crate::parser::std::decorators::model::can_read::can_read_decorator;
+use crate::parser::std::decorators::model::connector::connector_decorator;
 use crate::parser::std::decorators::model::disable::disable_decorator;
+use crate::parser::std::decorators::model::full_text_index::full_text_index_decorator;
+use crate::parser::std::decorators::model::handler::handler_decorator;
 use crate::parser::std::decorators::model::identity::identity_decorator;
 use crate::parser::std::decorators::model::index::{index_decorator, id_decorator, unique_decorator};
 use crate::parser::std::decorators::model::map::map_decorator;
 use crate::parser::std::decorators::model::migration::migration_decorator;
 use crate::parser::std::decorators::model::r#virtual::virtual_decorator;
+use crate::parser::std::decorators::model::soft_delete::soft_delete_decorator;
 use crate::parser::std::decorators::model::url::url_decorator;
 
 pub(crate) struct GlobalModelDecorators {
@@ -61,13 +69,14 @@ impl GlobalModelDecorators {
         objects.insert("canRead".to_owned(), Accessible::ModelDecorator(can_read_decorator));
         objects.insert("canMutate".to_owned(), Accessible::ModelDecorator(can_mutate_decorator));
         objects.insert("migration".to_owned(), Accessible::ModelDecorator(migration_decorator));
+        objects.insert("softDelete".to_owned(), Accessible::ModelDecorator(soft_delete_decorator));
+        objects.insert("fullTextIndex".to_owned(), Accessible::ModelDecorator(full_text_index_decorator));
+        objects.insert("handler".to_owned(), Accessible::ModelDecorator(handler_decorator));
+        objects.insert("connector".to_owned(), Accessible::ModelDecorator(connector_decorator));
         Self { objects }
     }
 
-    pub(crate) fn get(&self, key: &str) -> &Accessible {
-        match self.objects.get(key) {
-            Some(o) => o,
-            None => panic!("Object with key '{}' is not found.", key),
-        }
+    pub(crate) fn get(&self, key: &str) -> Option<&Accessible> {
+        self.objects.get(key)
     }
 }
@@ -0,0 +1,18 @@
+use crate::core::model::builder::ModelBuilder;
+use crate::parser::ast::argument::Argument;
+use crate::prelude::Value;
+
+pub(crate) fn soft_delete_decorator(args: Vec<Argument>, model: &mut ModelBuilder) {
+    let arg0 = match args.first() {
+        Some(arg) => arg,
+        None => panic!("Model soft delete decorator takes a field argument."),
+    };
+    if arg0.name.is_some() && (arg0.name.as_ref().unwrap().name.as_str() != "field") {
+        panic!("Model soft delete decorator's argument should be field or no name.")
+    }
+    match arg0.resolved.as_ref().unwrap().as_value().unwrap() {
+        Value::RawEnumChoice(name, _) => { model.soft_delete(name); }
+        Value::String(name) => { model.soft_delete(name); }
+        _ => panic!("Model soft delete decorator's field argument should be a field name."),
+    }
+}
@@ -1,3 +1,6 @@
+use crate::core::relation::delete_rule::DeleteRule;
+use crate::core::relation::disconnect_rule::DisconnectRule;
+use crate::core::relation::update_rule::UpdateRule;
 use crate::core::relation::Relation;
 use crate::parser::ast::argument::Argument;
 
@@ -53,14 +56,35 @@ pub(crate) fn relation_decorator(args: Vec<Argument>, relation: &mut Relation) {
         panic!("One of 'fields' or 'through' must be provided.")
     }
     // delete rule
-    // let on_delete_arg = args.iter().find(|a| {
-    //     &a.name.unwrap().name == "onDelete"
-    // });
-    // if on_delete_arg.is_some() {
-    //     let rule = on_delete_arg.unwrap().resolved.unwrap().as_value().unwrap().as_raw_enum_choice().unwrap();
-    //     match rule {
-    //
-    //     }
-    // }
+    if let Some(on_delete_arg) = args.iter().find(|a| a.name.as_ref().unwrap().name == "onDelete") {
+        let rule = on_delete_arg.resolved.as_ref().unwrap().as_value().unwrap().as_raw_enum_choice().unwrap();
+        relation.set_delete_rule(match rule {
+            "default" => DeleteRule::Default,
+            "nullify" => DeleteRule::Nullify,
+            "cascade" => DeleteRule::Cascade,
+            "deny" => DeleteRule::Deny,
+            _ => unreachable!("Unknown delete rule '{}'.", rule), // reported by the resolver
+        });
+    }
     // update rule
+    if let Some(on_update_arg) = args.iter().find(|a| a.name.as_ref().unwrap().name == "onUpdate") {
+        let rule = on_update_arg.resolved.as_ref().unwrap().as_value().unwrap().as_raw_enum_choice().unwrap();
+        relation.set_update_rule(match rule {
+            "default" => UpdateRule::Default,
+            "nullify" => UpdateRule::Nullify,
+            "cascade" => UpdateRule::Cascade,
+            "deny" => UpdateRule::Deny,
+            _ => unreachable!("Unknown update rule '{}'.", rule), // reported by the resolver
+        });
+    }
+    // disconnect rule
+    if let Some(on_disconnect_arg) = args.iter().find(|a| a.name.as_ref().unwrap().name == "onDisconnect") {
+        let rule = on_disconnect_arg.resolved.as_ref().unwrap().as_value().unwrap().as_raw_enum_choice().unwrap();
+        relation.set_disconnect_rule(match rule {
+            "default" => DisconnectRule::Default,
+            "nullify" => DisconnectRule::Nullify,
+            "delete" => DisconnectRule::Delete,
+            _ => unreachable!("Unknown disconnect rule '{}'.", rule), // reported by the resolver
+        });
+    }
 }